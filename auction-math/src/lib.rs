@@ -0,0 +1,293 @@
+#![no_std]
+#![allow(clippy::zero_prefixed_literal)]
+
+//! Dutch auction scaling math used by the pool contract and off-chain tooling.
+//!
+//! All functions are pure and take no `Env`, so bots simulating auction fills get the exact
+//! amounts, including rounding, that the pool will compute on-chain. Scaling returns None if an
+//! amount is too large for i128 math, in which case the pool scales the amount with 256 bit math.
+
+use soroban_fixed_point_math::FixedPoint;
+
+/// Fixed-point scalar for 7 decimal numbers
+pub const SCALAR_7: i128 = 1_0000000;
+
+/// The amount the auction modifiers move every block (0.5%)
+pub const PER_BLOCK_SCALAR: i128 = 0_0050000;
+
+/// The number of blocks it takes for the lot modifier to reach 100%, and afterwards, for the bid
+/// modifier to reach 0%
+pub const AUCTION_SCALING_BLOCKS: u32 = 200;
+
+/// Calculate the block based auction modifiers
+///
+/// For the first 200 blocks of an auction the lot scales from 0% to 100% while the bid stays at 100%.
+/// Over the next 200 blocks the lot stays at 100% while the bid scales from 100% to 0%.
+///
+/// ### Arguments
+/// * `block_dif` - The number of blocks that have passed since the auction started
+///
+/// Returns (bid_modifier, lot_modifier) as 7 decimal fixed point numbers
+pub fn get_modifiers(block_dif: u32) -> (i128, i128) {
+    let block_dif = block_dif as i128;
+    let scaling_blocks = AUCTION_SCALING_BLOCKS as i128;
+    if block_dif > scaling_blocks {
+        // lot 100%, bid scaling down from 100% to 0%
+        if block_dif < 2 * scaling_blocks {
+            (
+                SCALAR_7 - (block_dif - scaling_blocks) * PER_BLOCK_SCALAR,
+                SCALAR_7,
+            )
+        } else {
+            (0, SCALAR_7)
+        }
+    } else {
+        // lot scaling from 0% to 100%, bid 100%
+        (SCALAR_7, block_dif * PER_BLOCK_SCALAR)
+    }
+}
+
+/// The number of blocks the remaining distance of an exponential curve takes to halve
+pub const EXPONENTIAL_HALF_LIFE_BLOCKS: u32 = 25;
+
+/// The number of blocks between each step of a stepwise curve
+pub const STEP_BLOCKS: u32 = 20;
+
+/// The shape of the curve the auction modifiers follow. Every curve scales the lot from 0% to 100%
+/// over the first 200 blocks of an auction, then scales the bid from 100% to 0% over the next 200 blocks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum AuctionCurve {
+    /// The modifiers move 0.5% every block
+    Linear = 0,
+    /// The remaining distance of the modifiers halves every 25 blocks, moving quickly at the start of each
+    /// phase for fast-moving assets
+    Exponential = 1,
+    /// The modifiers move 10% every 20 blocks, holding each price for longer for illiquid assets
+    Stepwise = 2,
+}
+
+impl AuctionCurve {
+    /// Convert a u32 to an auction curve, or None if the value is not a valid curve
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(AuctionCurve::Linear),
+            1 => Some(AuctionCurve::Exponential),
+            2 => Some(AuctionCurve::Stepwise),
+            _ => None,
+        }
+    }
+}
+
+/// Calculate the block based auction modifiers for an auction curve
+///
+/// ### Arguments
+/// * `curve` - The curve the modifiers follow
+/// * `block_dif` - The number of blocks that have passed since the auction started
+///
+/// Returns (bid_modifier, lot_modifier) as 7 decimal fixed point numbers
+pub fn get_curve_modifiers(curve: AuctionCurve, block_dif: u32) -> (i128, i128) {
+    if curve == AuctionCurve::Linear {
+        return get_modifiers(block_dif);
+    }
+    if block_dif >= AUCTION_SCALING_BLOCKS {
+        // lot 100%, bid scaling down from 100% to 0%
+        let phase_blocks = block_dif - AUCTION_SCALING_BLOCKS;
+        if phase_blocks < AUCTION_SCALING_BLOCKS {
+            (SCALAR_7 - get_curve_progress(curve, phase_blocks), SCALAR_7)
+        } else {
+            (0, SCALAR_7)
+        }
+    } else {
+        // lot scaling from 0% to 100%, bid 100%
+        (SCALAR_7, get_curve_progress(curve, block_dif))
+    }
+}
+
+/// Calculate how far a non-linear curve has progressed through a 200 block phase
+///
+/// Returns the progress as a 7 decimal fixed point number
+fn get_curve_progress(curve: AuctionCurve, phase_blocks: u32) -> i128 {
+    match curve {
+        AuctionCurve::Linear => phase_blocks as i128 * PER_BLOCK_SCALAR,
+        AuctionCurve::Exponential => {
+            // interpolate linearly between each halving to avoid fractional powers
+            let halvings = phase_blocks / EXPONENTIAL_HALF_LIFE_BLOCKS;
+            let blocks_into_halving = (phase_blocks % EXPONENTIAL_HALF_LIFE_BLOCKS) as i128;
+            let start = SCALAR_7 >> halvings;
+            let end = SCALAR_7 >> (halvings + 1);
+            let remaining =
+                start - (start - end) * blocks_into_halving / EXPONENTIAL_HALF_LIFE_BLOCKS as i128;
+            SCALAR_7 - remaining
+        }
+        AuctionCurve::Stepwise => (phase_blocks / STEP_BLOCKS) as i128 * 0_1000000,
+    }
+}
+
+/// Calculate the block based bid premium for auctions that start their bid above 100%
+///
+/// The premium decays linearly from `start_premium` to 0% over the first 200 blocks of the auction,
+/// while the lot scales up, so the bid side is at `100% + premium` when the auction starts and at
+/// 100% once the lot is fully available.
+///
+/// ### Arguments
+/// * `block_dif` - The number of blocks that have passed since the auction started
+/// * `start_premium` - The bid premium at the start of the auction, as a 7 decimal fixed point number
+///
+/// Returns the bid premium as a 7 decimal fixed point number
+pub fn get_bid_premium(block_dif: u32, start_premium: i128) -> i128 {
+    if block_dif >= AUCTION_SCALING_BLOCKS {
+        0
+    } else {
+        start_premium * (AUCTION_SCALING_BLOCKS - block_dif) as i128
+            / AUCTION_SCALING_BLOCKS as i128
+    }
+}
+
+/// Scale a bid amount for a fill. Rounds up to avoid rounding exploits.
+///
+/// ### Arguments
+/// * `amount` - The bid amount of an asset in the auction
+/// * `percent_filled` - The percentage being filled as a number (i.e. 15 => 15%)
+/// * `bid_modifier` - The bid modifier from `get_modifiers`
+///
+/// Returns (to_fill, remaining) where `to_fill` is the amount the filler pays and `remaining` is the
+/// amount left in the auction, or None if the math overflows
+pub fn scale_bid_amount(
+    amount: i128,
+    percent_filled: u64,
+    bid_modifier: i128,
+) -> Option<(i128, i128)> {
+    let to_fill_base = amount.fixed_mul_ceil(percent_to_fixed(percent_filled), SCALAR_7)?;
+    let remaining = amount.checked_sub(to_fill_base)?;
+    let to_fill = to_fill_base.fixed_mul_ceil(bid_modifier, SCALAR_7)?;
+    Some((to_fill, remaining))
+}
+
+/// Scale a lot amount for a fill. Rounds down to avoid rounding exploits.
+///
+/// ### Arguments
+/// * `amount` - The lot amount of an asset in the auction
+/// * `percent_filled` - The percentage being filled as a number (i.e. 15 => 15%)
+/// * `lot_modifier` - The lot modifier from `get_modifiers`
+///
+/// Returns (to_fill, remaining) where `to_fill` is the amount the filler receives and `remaining` is
+/// the amount left in the auction, or None if the math overflows
+pub fn scale_lot_amount(
+    amount: i128,
+    percent_filled: u64,
+    lot_modifier: i128,
+) -> Option<(i128, i128)> {
+    let to_fill_base = amount.fixed_mul_floor(percent_to_fixed(percent_filled), SCALAR_7)?;
+    let remaining = amount.checked_sub(to_fill_base)?;
+    let to_fill = to_fill_base.fixed_mul_floor(lot_modifier, SCALAR_7)?;
+    Some((to_fill, remaining))
+}
+
+/// Convert a percentage as a number (i.e. 15 => 15%) to 7 decimal fixed point
+fn percent_to_fixed(percent: u64) -> i128 {
+    percent as i128 * 1_00000
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_modifiers() {
+        assert_eq!(get_modifiers(0), (1_0000000, 0));
+        assert_eq!(get_modifiers(100), (1_0000000, 0_5000000));
+        assert_eq!(get_modifiers(200), (1_0000000, 1_0000000));
+        assert_eq!(get_modifiers(201), (0_9950000, 1_0000000));
+        assert_eq!(get_modifiers(300), (0_5000000, 1_0000000));
+        assert_eq!(get_modifiers(399), (0_0050000, 1_0000000));
+        assert_eq!(get_modifiers(400), (0, 1_0000000));
+        assert_eq!(get_modifiers(u32::MAX), (0, 1_0000000));
+    }
+
+    #[test]
+    fn test_get_curve_modifiers_linear() {
+        for block_dif in [0, 1, 100, 200, 201, 300, 399, 400, u32::MAX] {
+            assert_eq!(
+                get_curve_modifiers(AuctionCurve::Linear, block_dif),
+                get_modifiers(block_dif)
+            );
+        }
+    }
+
+    #[test]
+    fn test_get_curve_modifiers_exponential() {
+        let curve = AuctionCurve::Exponential;
+        assert_eq!(get_curve_modifiers(curve, 0), (1_0000000, 0));
+        assert_eq!(get_curve_modifiers(curve, 10), (1_0000000, 0_2000000));
+        assert_eq!(get_curve_modifiers(curve, 25), (1_0000000, 0_5000000));
+        assert_eq!(get_curve_modifiers(curve, 50), (1_0000000, 0_7500000));
+        assert_eq!(get_curve_modifiers(curve, 199), (1_0000000, 0_9959375));
+        assert_eq!(get_curve_modifiers(curve, 200), (1_0000000, 1_0000000));
+        assert_eq!(get_curve_modifiers(curve, 225), (0_5000000, 1_0000000));
+        assert_eq!(get_curve_modifiers(curve, 399), (0_0040625, 1_0000000));
+        assert_eq!(get_curve_modifiers(curve, 400), (0, 1_0000000));
+        assert_eq!(get_curve_modifiers(curve, u32::MAX), (0, 1_0000000));
+    }
+
+    #[test]
+    fn test_get_curve_modifiers_stepwise() {
+        let curve = AuctionCurve::Stepwise;
+        assert_eq!(get_curve_modifiers(curve, 0), (1_0000000, 0));
+        assert_eq!(get_curve_modifiers(curve, 19), (1_0000000, 0));
+        assert_eq!(get_curve_modifiers(curve, 20), (1_0000000, 0_1000000));
+        assert_eq!(get_curve_modifiers(curve, 199), (1_0000000, 0_9000000));
+        assert_eq!(get_curve_modifiers(curve, 200), (1_0000000, 1_0000000));
+        assert_eq!(get_curve_modifiers(curve, 219), (1_0000000, 1_0000000));
+        assert_eq!(get_curve_modifiers(curve, 220), (0_9000000, 1_0000000));
+        assert_eq!(get_curve_modifiers(curve, 399), (0_1000000, 1_0000000));
+        assert_eq!(get_curve_modifiers(curve, 400), (0, 1_0000000));
+    }
+
+    #[test]
+    fn test_auction_curve_from_u32() {
+        assert_eq!(AuctionCurve::from_u32(0), Some(AuctionCurve::Linear));
+        assert_eq!(AuctionCurve::from_u32(1), Some(AuctionCurve::Exponential));
+        assert_eq!(AuctionCurve::from_u32(2), Some(AuctionCurve::Stepwise));
+        assert_eq!(AuctionCurve::from_u32(3), None);
+    }
+
+    #[test]
+    fn test_get_bid_premium() {
+        assert_eq!(get_bid_premium(0, 0_0500000), 0_0500000);
+        assert_eq!(get_bid_premium(100, 0_0500000), 0_0250000);
+        assert_eq!(get_bid_premium(199, 0_0500000), 0_0002500);
+        assert_eq!(get_bid_premium(200, 0_0500000), 0);
+        assert_eq!(get_bid_premium(u32::MAX, 0_0500000), 0);
+        assert_eq!(get_bid_premium(0, 0), 0);
+    }
+
+    #[test]
+    fn test_scale_bid_amount_rounds_up() {
+        assert_eq!(
+            scale_bid_amount(25_0000005, 50, 0_5000000),
+            Some((6_2500002, 12_5000002))
+        );
+        assert_eq!(scale_bid_amount(1, 1, 0_0050000), Some((1, 0)));
+        assert_eq!(scale_bid_amount(100_0000000, 100, 0), Some((0, 0)));
+    }
+
+    #[test]
+    fn test_scale_lot_amount_rounds_down() {
+        assert_eq!(
+            scale_lot_amount(25_0000005, 50, 0_5000000),
+            Some((6_2500001, 12_5000003))
+        );
+        assert_eq!(scale_lot_amount(1, 1, 0_0050000), Some((0, 1)));
+        assert_eq!(
+            scale_lot_amount(100_0000000, 100, 1_0000000),
+            Some((100_0000000, 0))
+        );
+    }
+
+    #[test]
+    fn test_scale_amount_overflow() {
+        assert_eq!(scale_bid_amount(i128::MAX, 100, 1_0000000), None);
+        assert_eq!(scale_lot_amount(i128::MAX, 100, 1_0000000), None);
+    }
+}
@@ -10,6 +10,17 @@ pub fn execute_deposit(e: &Env, from: &Address, pool_address: &Address, amount:
     if from == pool_address || from == &e.current_contract_address() {
         panic_with_error!(e, &BackstopError::BadRequest)
     }
+    deposit(e, from, pool_address, amount)
+}
+
+/// Perform a deposit by a pool into its own backstop. The pool owns the minted shares, which can't
+/// be withdrawn, so like a donation the tokens permanently back the pool.
+pub fn execute_deposit_from_pool(e: &Env, pool_address: &Address, amount: i128) -> i128 {
+    require_nonnegative(e, amount);
+    deposit(e, pool_address, pool_address, amount)
+}
+
+fn deposit(e: &Env, from: &Address, pool_address: &Address, amount: i128) -> i128 {
     let mut pool_balance = storage::get_pool_balance(e, pool_address);
     require_is_from_pool_factory(e, pool_address, pool_balance.shares);
     let mut user_balance = storage::get_user_balance(e, pool_address, from);
@@ -175,6 +186,37 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_execute_deposit_from_pool() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        let backstop_address = create_backstop(&e);
+        let pool_0_id = Address::generate(&e);
+        let bombadil = Address::generate(&e);
+
+        let (_, backstop_token_client) = create_backstop_token(&e, &backstop_address, &bombadil);
+        backstop_token_client.mint(&pool_0_id, &100_0000000);
+
+        let (_, mock_pool_factory_client) = create_mock_pool_factory(&e, &backstop_address);
+        mock_pool_factory_client.set_pool(&pool_0_id);
+
+        // a pool can hold shares in its own backstop
+        e.as_contract(&backstop_address, || {
+            let to_mint = execute_deposit_from_pool(&e, &pool_0_id, 100_0000000);
+            assert_eq!(to_mint, 100_0000000);
+            assert_eq!(
+                storage::get_user_balance(&e, &pool_0_id, &pool_0_id).shares,
+                100_0000000
+            );
+            assert_eq!(
+                storage::get_pool_balance(&e, &pool_0_id).tokens,
+                100_0000000
+            );
+        });
+        assert_eq!(backstop_token_client.balance(&pool_0_id), 0);
+    }
+
     #[test]
     #[should_panic(expected = "Error(Contract, #1000)")]
     fn test_execute_deposit_from_self() {
@@ -1,5 +1,5 @@
 mod deposit;
-pub use deposit::execute_deposit;
+pub use deposit::{execute_deposit, execute_deposit_from_pool};
 
 mod fund_management;
 pub use fund_management::{execute_donate, execute_draw};
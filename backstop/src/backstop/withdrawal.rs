@@ -4,7 +4,7 @@ use crate::{
 use sep_41_token::TokenClient;
 use soroban_sdk::{panic_with_error, unwrap::UnwrapOptimized, Address, Env};
 
-use super::Q4W;
+use super::{PoolBalance, Q4W};
 
 /// Perform a queue for withdraw from the backstop module
 pub fn execute_queue_withdrawal(
@@ -23,6 +23,7 @@ pub fn execute_queue_withdrawal(
 
     user_balance.queue_shares_for_withdrawal(e, amount);
     pool_balance.queue_for_withdraw(amount);
+    require_bad_debt_auction_covered(e, pool_address, &pool_balance);
 
     storage::set_user_balance(e, pool_address, from, &user_balance);
     storage::set_pool_balance(e, pool_address, &pool_balance);
@@ -48,13 +49,28 @@ pub fn execute_dequeue_withdrawal(e: &Env, from: &Address, pool_address: &Addres
     storage::set_pool_balance(e, pool_address, &pool_balance);
 }
 
+/// Require that the pool's backstop tokens not queued for withdrawal cover the lot of any bad debt
+/// auction in progress for the pool, so depositors cannot queue withdrawals ahead of a bad debt
+/// auction draining the backstop
+///
+/// ### Panics
+/// If the tokens not queued for withdrawal are less than the bad debt auction's lot
+fn require_bad_debt_auction_covered(e: &Env, pool_address: &Address, pool_balance: &PoolBalance) {
+    let pool_client = PoolClient::new(e, pool_address);
+    if let Ok(Ok(auction)) = pool_client.try_get_auction(&1, &e.current_contract_address()) {
+        let lot = auction.lot.get(storage::get_backstop_token(e)).unwrap_or(0);
+        if pool_balance.non_queued_tokens() < lot {
+            panic_with_error!(e, &BackstopError::BadDebtAuctionInProgress);
+        }
+    }
+}
+
 /// Perform a withdraw from the backstop module
 pub fn execute_withdraw(e: &Env, from: &Address, pool_address: &Address, amount: i128) -> i128 {
     require_nonnegative(e, amount);
 
     let pool_client = PoolClient::new(e, pool_address);
-    let backstop_positions = pool_client.get_positions(&e.current_contract_address());
-    if backstop_positions.liabilities.len() > 0 {
+    if !pool_client.get_bad_debt().is_empty() {
         panic_with_error!(e, &BackstopError::BadDebtExists);
     }
 
@@ -80,7 +96,7 @@ pub fn execute_withdraw(e: &Env, from: &Address, pool_address: &Address, amount:
 
 #[cfg(test)]
 mod tests {
-    use mock_pool::Positions;
+    use mock_pool::AuctionData;
     use soroban_sdk::{
         map,
         testutils::{Address as _, Ledger, LedgerInfo},
@@ -156,6 +172,80 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_execute_queue_withdrawal_bad_debt_auction_covered() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        let backstop_address = create_backstop(&e);
+        let (pool_address, mock_pool_client) = create_mock_pool(&e);
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let (backstop_token, backstop_token_client) =
+            create_backstop_token(&e, &backstop_address, &bombadil);
+        backstop_token_client.mint(&samwise, &100_0000000);
+
+        let (_, mock_pool_factory_client) = create_mock_pool_factory(&e, &backstop_address);
+        mock_pool_factory_client.set_pool(&pool_address);
+
+        e.as_contract(&backstop_address, || {
+            execute_deposit(&e, &samwise, &pool_address, 100_0000000);
+        });
+        mock_pool_client.set_auction(
+            &1,
+            &backstop_address,
+            &AuctionData {
+                bid: map![&e, (Address::generate(&e), 10_0000000)],
+                lot: map![&e, (backstop_token, 58_0000000)],
+                block: 100,
+            },
+        );
+
+        e.as_contract(&backstop_address, || {
+            execute_queue_withdrawal(&e, &samwise, &pool_address, 42_0000000);
+
+            let new_pool_balance = storage::get_pool_balance(&e, &pool_address);
+            assert_eq!(new_pool_balance.q4w, 42_0000000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1012)")]
+    fn test_execute_queue_withdrawal_bad_debt_auction_not_covered() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        let backstop_address = create_backstop(&e);
+        let (pool_address, mock_pool_client) = create_mock_pool(&e);
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let (backstop_token, backstop_token_client) =
+            create_backstop_token(&e, &backstop_address, &bombadil);
+        backstop_token_client.mint(&samwise, &100_0000000);
+
+        let (_, mock_pool_factory_client) = create_mock_pool_factory(&e, &backstop_address);
+        mock_pool_factory_client.set_pool(&pool_address);
+
+        e.as_contract(&backstop_address, || {
+            execute_deposit(&e, &samwise, &pool_address, 100_0000000);
+        });
+        mock_pool_client.set_auction(
+            &1,
+            &backstop_address,
+            &AuctionData {
+                bid: map![&e, (Address::generate(&e), 10_0000000)],
+                lot: map![&e, (backstop_token, 58_0000001)],
+                block: 100,
+            },
+        );
+
+        e.as_contract(&backstop_address, || {
+            execute_queue_withdrawal(&e, &samwise, &pool_address, 42_0000000);
+        });
+    }
+
     #[test]
     #[should_panic(expected = "Error(Contract, #8)")]
     fn test_execute_queue_withdrawal_negative_amount() {
@@ -544,12 +634,7 @@ mod tests {
         });
 
         // give the backstop bad debt
-        let backstop_positions = Positions {
-            liabilities: map![&e, (0, 1_0000000)],
-            collateral: map![&e],
-            supply: map![&e],
-        };
-        mock_pool_client.set_positions(&backstop_address, &backstop_positions);
+        mock_pool_client.set_bad_debt(&map![&e, (Address::generate(&e), 1_0000000)]);
 
         backstop_token_client.approve(
             &samwise,
@@ -159,6 +159,21 @@ pub trait Backstop {
     /// If the `pool_address` is not valid, backstop does not have sufficient allowance from `from`, or if the pool does not
     /// authorize the call
     fn donate(e: Env, from: Address, pool_address: Address, amount: i128);
+
+    /// (Only Pool) Deposit backstop tokens held by a pool into its own backstop
+    ///
+    /// NOTE: The pool owns the minted shares, and they can't be withdrawn. Like a donation, the tokens
+    /// permanently back the pool, but the pool's shares grow with the backstop.
+    ///
+    /// Returns the number of backstop pool shares minted to the pool
+    ///
+    /// ### Arguments
+    /// * `pool_address` - The address of the pool
+    /// * `amount` - The amount of backstop tokens to deposit
+    ///
+    /// ### Errors
+    /// If the `pool_address` is not valid, or if the pool does not authorize the call
+    fn deposit_from_pool(e: Env, pool_address: Address, amount: i128) -> i128;
 }
 
 #[contractimpl]
@@ -330,6 +345,16 @@ impl Backstop for BackstopContract {
 
         BackstopEvents::donate(&e, pool_address, from, amount);
     }
+
+    fn deposit_from_pool(e: Env, pool_address: Address, amount: i128) -> i128 {
+        storage::extend_instance(&e);
+        pool_address.require_auth();
+
+        let to_mint = backstop::execute_deposit_from_pool(&e, &pool_address, amount);
+
+        BackstopEvents::deposit(&e, pool_address.clone(), pool_address, amount, to_mint);
+        to_mint
+    }
 }
 
 /// Require that an incoming amount is not negative
@@ -11,6 +11,24 @@ pub struct Positions {
     pub supply: Map<u32, i128>,      // Map of Reserve Index to non-collateral supply share balance
 }
 
+#[derive(Clone)]
+#[contracttype]
+pub struct AuctionData {
+    pub bid: Map<Address, i128>,
+    pub lot: Map<Address, i128>,
+    pub block: u32,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct ReserveSummary {
+    pub index: u32,            // the index of the reserve in the pool
+    pub supply: i128,          // the total supplied tokens, including accrued interest
+    pub liabilities: i128,     // the total borrowed tokens, including accrued interest
+    pub backstop_credit: i128, // the interest owed to the backstop
+    pub bad_debt: i128,        // the bad debt held by the backstop, including accrued interest
+}
+
 #[allow(dead_code)]
 #[contractclient(name = "PoolClient")]
 pub trait Pool {
@@ -19,4 +37,26 @@ pub trait Pool {
     /// ### Arguments
     /// * `address` - The address to fetch positions for
     fn get_positions(e: Env, address: Address) -> Positions;
+
+    /// Fetch an auction from the ledger
+    ///
+    /// ### Arguments
+    /// * `auction_type` - The type of auction, 0 for liquidation auction, 1 for bad debt auction, and 2 for interest auction
+    /// * `user` - The Address involved in the auction
+    ///
+    /// ### Panics
+    /// If the auction does not exist
+    fn get_auction(e: Env, auction_type: u32, user: Address) -> AuctionData;
+
+    /// Fetch a summary of a reserve's balances in underlying tokens
+    ///
+    /// ### Arguments
+    /// * `asset` - The address of the reserve asset
+    ///
+    /// ### Panics
+    /// If the reserve does not exist
+    fn get_reserve_summary(e: Env, asset: Address) -> ReserveSummary;
+
+    /// Fetch the bad debt held by the backstop in underlying tokens, by reserve asset
+    fn get_bad_debt(e: Env) -> Map<Address, i128>;
 }
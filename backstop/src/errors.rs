@@ -29,4 +29,5 @@ pub enum BackstopError {
     RewardZoneFull = 1009,
     MaxBackfillEmissions = 1010,
     BadDebtExists = 1011,
+    BadDebtAuctionInProgress = 1012,
 }
@@ -0,0 +1,211 @@
+#![no_std]
+
+use sep_40_oracle::{Asset, PriceData};
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Vec};
+
+const ONE_DAY_LEDGERS: u32 = 17280; // assumes 5s a ledger
+const LEDGER_THRESHOLD: u32 = ONE_DAY_LEDGERS * 90;
+const LEDGER_BUMP: u32 = ONE_DAY_LEDGERS * 120;
+
+#[derive(Clone)]
+#[contracttype]
+pub struct OracleData {
+    pub base: Asset,
+    pub assets: Vec<Asset>,
+    pub decimals: u32,
+    pub resolution: u32,
+}
+
+/// A scheduled price for an asset, reported from `ledger` until the next scheduled price. A `None`
+/// price reports the asset as having no price.
+#[derive(Clone)]
+#[contracttype]
+pub struct ScheduledPrice {
+    pub ledger: u32,
+    pub price: Option<i128>,
+}
+
+/// An outage overriding how an asset's price is reported
+#[derive(Clone)]
+#[contracttype]
+pub enum Outage {
+    /// The price is reported as last updated the given number of seconds ago
+    Stale(u64),
+    /// The asset is reported as having no price
+    Missing,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    Data,
+    Price(Asset),
+    Schedule(Asset),
+    Outage(Asset),
+}
+
+/// A SEP-40 price feed with programmable prices for tests
+#[contract]
+pub struct MockOracle;
+
+#[contractimpl]
+impl MockOracle {
+    /// Set the oracle's metadata
+    ///
+    /// # Arguments
+    /// * 'admin' - Unused, kept for compatibility with the SEP-40 mock oracle
+    /// * 'base' - The base asset prices are reported in
+    /// * 'assets' - The assets with prices, in the order prices are set in
+    /// * 'decimals' - The decimals of the prices
+    /// * 'resolution' - The number of seconds between price updates
+    pub fn set_data(
+        e: Env,
+        _admin: Address,
+        base: Asset,
+        assets: Vec<Asset>,
+        decimals: u32,
+        resolution: u32,
+    ) {
+        e.storage()
+            .instance()
+            .extend_ttl(LEDGER_THRESHOLD, LEDGER_BUMP);
+        e.storage().instance().set(
+            &DataKey::Data,
+            &OracleData {
+                base,
+                assets,
+                decimals,
+                resolution,
+            },
+        );
+    }
+
+    /// Set the price of each asset, reported as updated at the current ledger timestamp
+    ///
+    /// # Arguments
+    /// * 'prices' - The prices, in the order of the oracle's assets
+    pub fn set_price_stable(e: Env, prices: Vec<i128>) {
+        store_prices(&e, prices, None);
+    }
+
+    /// Set the price of each asset, reported as updated at `timestamp`
+    ///
+    /// # Arguments
+    /// * 'prices' - The prices, in the order of the oracle's assets
+    /// * 'timestamp' - The timestamp the prices were updated at
+    pub fn set_price(e: Env, prices: Vec<i128>, timestamp: u64) {
+        store_prices(&e, prices, Some(timestamp));
+    }
+
+    /// Set a price schedule for an asset. Scheduled prices are reported as updated at the current
+    /// ledger timestamp, and take precedence over prices set with `set_price` once the first
+    /// scheduled ledger is reached.
+    ///
+    /// # Arguments
+    /// * 'asset' - The asset to schedule prices for
+    /// * 'schedule' - The scheduled prices, sorted by ledger
+    pub fn set_schedule(e: Env, asset: Asset, schedule: Vec<ScheduledPrice>) {
+        e.storage()
+            .instance()
+            .set(&DataKey::Schedule(asset), &schedule);
+    }
+
+    /// Set or clear an outage for an asset
+    ///
+    /// # Arguments
+    /// * 'asset' - The asset to set the outage for
+    /// * 'outage' - The outage, or None to clear it
+    pub fn set_outage(e: Env, asset: Asset, outage: Option<Outage>) {
+        let key = DataKey::Outage(asset);
+        match outage {
+            Some(outage) => e.storage().instance().set(&key, &outage),
+            None => e.storage().instance().remove(&key),
+        }
+    }
+
+    /********** SEP-40 **********/
+
+    pub fn base(e: Env) -> Asset {
+        get_data(&e).base
+    }
+
+    pub fn assets(e: Env) -> Vec<Asset> {
+        get_data(&e).assets
+    }
+
+    pub fn decimals(e: Env) -> u32 {
+        get_data(&e).decimals
+    }
+
+    pub fn resolution(e: Env) -> u32 {
+        get_data(&e).resolution
+    }
+
+    /// Only the last price is tracked, so it is returned for any timestamp
+    pub fn price(e: Env, asset: Asset, _timestamp: u64) -> Option<PriceData> {
+        MockOracle::lastprice(e, asset)
+    }
+
+    /// Only the last price is tracked, so at most one record is returned
+    pub fn prices(e: Env, asset: Asset, _records: u32) -> Option<Vec<PriceData>> {
+        let price = MockOracle::lastprice(e.clone(), asset)?;
+        Some(Vec::from_array(&e, [price]))
+    }
+
+    pub fn lastprice(e: Env, asset: Asset) -> Option<PriceData> {
+        let now = e.ledger().timestamp();
+        let outage = e
+            .storage()
+            .instance()
+            .get::<DataKey, Outage>(&DataKey::Outage(asset.clone()));
+        if let Some(Outage::Missing) = outage {
+            return None;
+        }
+
+        let (price, timestamp) = match get_scheduled_price(&e, &asset) {
+            Some(scheduled) => (scheduled?, now),
+            None => {
+                let (price, timestamp) = e
+                    .storage()
+                    .instance()
+                    .get::<DataKey, (i128, Option<u64>)>(&DataKey::Price(asset))?;
+                (price, timestamp.unwrap_or(now))
+            }
+        };
+        let timestamp = match outage {
+            Some(Outage::Stale(age)) => now.saturating_sub(age),
+            _ => timestamp,
+        };
+        Some(PriceData { price, timestamp })
+    }
+}
+
+fn get_data(e: &Env) -> OracleData {
+    e.storage().instance().get(&DataKey::Data).unwrap()
+}
+
+fn store_prices(e: &Env, prices: Vec<i128>, timestamp: Option<u64>) {
+    let assets = get_data(e).assets;
+    for (asset, price) in assets.iter().zip(prices.iter()) {
+        e.storage()
+            .instance()
+            .set(&DataKey::Price(asset), &(price, timestamp));
+    }
+}
+
+/// Fetch the scheduled price for the current ledger, or None if no scheduled price has been reached
+fn get_scheduled_price(e: &Env, asset: &Asset) -> Option<Option<i128>> {
+    let schedule = e
+        .storage()
+        .instance()
+        .get::<DataKey, Vec<ScheduledPrice>>(&DataKey::Schedule(asset.clone()))?;
+    let sequence = e.ledger().sequence();
+    let mut price = None;
+    for scheduled in schedule.iter() {
+        if scheduled.ledger > sequence {
+            break;
+        }
+        price = Some(scheduled.price);
+    }
+    price
+}
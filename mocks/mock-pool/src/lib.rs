@@ -12,10 +12,20 @@ pub struct Positions {
     pub supply: Map<u32, i128>,      // Map of Reserve Index to non-collateral supply share balance
 }
 
+#[derive(Clone)]
+#[contracttype]
+pub struct AuctionData {
+    pub bid: Map<Address, i128>,
+    pub lot: Map<Address, i128>,
+    pub block: u32,
+}
+
 #[derive(Clone)]
 #[contracttype]
 pub enum DataKey {
     Positions(Address),
+    Auction(u32, Address),
+    BadDebt,
 }
 
 #[contract]
@@ -56,4 +66,51 @@ impl MockPool {
             },
         }
     }
+
+    /// Set the bad debt held by the backstop
+    ///
+    /// # Arguments
+    /// * 'bad_debt' - The bad debt by reserve asset
+    pub fn set_bad_debt(e: Env, bad_debt: Map<Address, i128>) {
+        e.storage()
+            .instance()
+            .set::<DataKey, Map<Address, i128>>(&DataKey::BadDebt, &bad_debt);
+    }
+
+    /// Fetch the bad debt held by the backstop
+    pub fn get_bad_debt(e: Env) -> Map<Address, i128> {
+        e.storage()
+            .instance()
+            .get::<DataKey, Map<Address, i128>>(&DataKey::BadDebt)
+            .unwrap_or(map![&e])
+    }
+
+    /// Set an auction for a given address
+    ///
+    /// # Arguments
+    /// * 'auction_type' - The type of auction
+    /// * 'user' - The address involved in the auction
+    /// * 'auction' - The auction to set
+    pub fn set_auction(e: Env, auction_type: u32, user: Address, auction: AuctionData) {
+        let key = DataKey::Auction(auction_type, user);
+        e.storage()
+            .persistent()
+            .set::<DataKey, AuctionData>(&key, &auction);
+    }
+
+    /// Fetch an auction
+    ///
+    /// # Arguments
+    /// * 'auction_type' - The type of auction
+    /// * 'user' - The address involved in the auction
+    ///
+    /// # Panics
+    /// If the auction does not exist
+    pub fn get_auction(e: Env, auction_type: u32, user: Address) -> AuctionData {
+        let key = DataKey::Auction(auction_type, user);
+        e.storage()
+            .persistent()
+            .get::<DataKey, AuctionData>(&key)
+            .unwrap()
+    }
 }
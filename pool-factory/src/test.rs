@@ -99,7 +99,9 @@ fn test_pool_factory() {
                 min_collateral: min_collateral,
                 bstop_rate: backstop_rate,
                 status: 6,
-                max_positions: 6
+                max_positions: 6,
+                min_debt: 0
+                base: None,
             }
         );
         assert_eq!(
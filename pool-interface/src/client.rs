@@ -0,0 +1,99 @@
+use soroban_sdk::{contractclient, Address, Env, Vec};
+
+use crate::{AuctionData, PoolError, Positions, Request};
+
+/// The subset of the pool contract's interface used to manage positions and auctions
+#[contractclient(name = "PoolClient")]
+pub trait PoolInterface {
+    /// Fetch the positions for an address
+    ///
+    /// ### Arguments
+    /// * `address` - The address to fetch positions for
+    fn get_positions(e: Env, address: Address) -> Positions;
+
+    /// Submit a set of requests to the pool where `from` takes on the position, `spender` sends any
+    /// required tokens to the pool and `to` receives any tokens sent from the pool.
+    ///
+    /// Returns the new positions for `from`, or the error if an address is the pool or the new positions
+    /// are invalid, like an invalid health factor or an ongoing liquidation
+    ///
+    /// ### Arguments
+    /// * `from` - The address of the user whose positions are being modified
+    /// * `spender` - The address of the user who is sending tokens to the pool
+    /// * `to` - The address of the user who is receiving tokens from the pool
+    /// * `requests` - A vec of requests to be processed
+    fn submit(
+        e: Env,
+        from: Address,
+        spender: Address,
+        to: Address,
+        requests: Vec<Request>,
+    ) -> Result<Positions, PoolError>;
+
+    /// Submit a set of requests to the pool where `from` takes on the position, `spender` sends any
+    /// required tokens to the pool using transfer_from and `to` receives any tokens sent from the pool.
+    ///
+    /// Returns the new positions for `from`, or the error if an address is the pool or the new positions
+    /// are invalid, like an invalid health factor or an ongoing liquidation
+    ///
+    /// ### Arguments
+    /// * `from` - The address of the user whose positions are being modified
+    /// * `spender` - The address of the user who is sending tokens to the pool
+    /// * `to` - The address of the user who is receiving tokens from the pool
+    /// * `requests` - A vec of requests to be processed
+    fn submit_with_allowance(
+        e: Env,
+        from: Address,
+        spender: Address,
+        to: Address,
+        requests: Vec<Request>,
+    ) -> Result<Positions, PoolError>;
+
+    /// Claim outstanding emissions for the user for the given reserve token ids
+    ///
+    /// Returns the number of tokens claimed
+    ///
+    /// ### Arguments
+    /// * `from` - The address claiming
+    /// * `reserve_token_ids` - Vector of reserve token ids
+    /// * `to` - The Address to send the claimed tokens to
+    fn claim(e: Env, from: Address, reserve_token_ids: Vec<u32>, to: Address) -> i128;
+
+    /// Create a new auction
+    ///
+    /// ### Arguments
+    /// * `auction_type` - The type of auction, 0 for liquidation auction, 1 for bad debt auction, and 2 for interest auction
+    /// * `user` - The Address involved in the auction
+    /// * `bid` - The set of assets to include in the auction bid
+    /// * `lot` - The set of assets to include in the auction lot
+    /// * `percent` - The percent of the assets to be auctioned off as a percentage (15 => 15%)
+    fn new_auction(
+        e: Env,
+        auction_type: u32,
+        user: Address,
+        bid: Vec<Address>,
+        lot: Vec<Address>,
+        percent: u32,
+    ) -> AuctionData;
+
+    /// Fetch an auction from the ledger
+    ///
+    /// ### Arguments
+    /// * `auction_type` - The type of auction, 0 for liquidation auction, 1 for bad debt auction, and 2 for interest auction
+    /// * `user` - The Address involved in the auction
+    fn get_auction(e: Env, auction_type: u32, user: Address) -> AuctionData;
+
+    /// Delete a stale auction. A stale auction is one that has been running for 500 blocks
+    /// without being filled.
+    ///
+    /// ### Arguments
+    /// * `auction_type` - The type of auction, 0 for liquidation auction, 1 for bad debt auction, and 2 for interest auction
+    /// * `user` - The Address involved in the auction
+    fn del_auction(e: Env, auction_type: u32, user: Address);
+
+    /// Check and handle bad debt for a user
+    ///
+    /// ### Arguments
+    /// * `user` - The user to check for bad debt
+    fn bad_debt(e: Env, user: Address);
+}
@@ -0,0 +1,92 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+/// Error codes for the pool contract. Common errors are codes that match up with the built-in
+/// contracts error reporting. Pool specific errors start at 1200.
+pub enum PoolError {
+    // Common Errors
+    InternalError = 1,
+    AlreadyInitializedError = 3,
+
+    UnauthorizedError = 4,
+
+    NegativeAmountError = 8,
+    BalanceError = 10,
+    OverflowError = 12,
+
+    // Pool Request Errors (start at 1200)
+    BadRequest = 1200,
+    InvalidPoolConfigArgs = 1201,
+    InvalidReserveMetadata = 1202,
+    InitNotUnlocked = 1203,
+    StatusNotAllowed = 1204,
+
+    // Pool State Errors
+    InvalidHf = 1205,
+    InvalidPoolStatus = 1206,
+    InvalidUtilRate = 1207,
+    MaxPositionsExceeded = 1208,
+    InternalReserveNotFound = 1209,
+
+    // Oracle Errors
+    InvalidPrice = 1210,
+
+    // Auction Errors
+    InvalidLiquidation = 1211,
+    AuctionInProgress = 1212,
+    InvalidLiqTooLarge = 1213,
+    InvalidLiqTooSmall = 1214,
+    InterestTooSmall = 1215,
+
+    // Share Token Errors
+    InvalidBTokenMintAmount = 1216,
+    InvalidBTokenBurnAmount = 1217,
+    InvalidDTokenMintAmount = 1218,
+    InvalidDTokenBurnAmount = 1219,
+
+    // v2 Errors
+    ExceededSupplyCap = 1220,
+    InvalidBid = 1221,
+    InvalidLot = 1222,
+    ReserveDisabled = 1223,
+    MinCollateralNotMet = 1224,
+    AuctionNotStarted = 1225,
+    BorrowAllowanceExceeded = 1226,
+    InterestAuctionTooSoon = 1227,
+    MinDebtNotMet = 1228,
+    MaxQuoteExceeded = 1229,
+    QuoteSwapFailed = 1230,
+    AuctionNotFound = 1231,
+    InvalidReserveDecimals = 1232,
+    InvalidReserveFactors = 1233,
+    InvalidReserveUtil = 1234,
+    InvalidReserveRates = 1235,
+    InvalidReserveReactivity = 1236,
+    UserMinHfNotMet = 1237,
+    ReservePaused = 1238,
+    ActionNotUnlocked = 1239,
+    TimelockRequired = 1240,
+    OracleDeviation = 1241,
+    ReserveRateDecreased = 1242,
+    InvalidFillCommitment = 1252,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+/// Additional error codes for the pool contract. A contract spec supports at most 50 cases per
+/// error enum, so pool specific errors past that limit are defined here. The codes do not overlap
+/// with `PoolError`.
+pub enum PoolErrorExt {
+    ReserveRateExceeded = 1243,
+    ReserveRetired = 1244,
+    StableRateDisabled = 1245,
+    AddressBlocked = 1246,
+    FillHookFailed = 1247,
+    AuctionsPaused = 1248,
+    PositionsArchived = 1249,
+    ClaimConversionFailed = 1250,
+    KeeperNotRegistered = 1251,
+}
@@ -0,0 +1,20 @@
+#![no_std]
+
+//! Types and clients for interacting with a Blend pool from other Soroban contracts.
+//!
+//! The crate contains no contract code, so contracts that call into a pool can depend on it without
+//! pulling in the pool implementation or its test dependencies. The types are encoded identically
+//! to the pool's own, and the test suites check the client against the pool contract.
+
+#[cfg(test)]
+extern crate std;
+
+mod client;
+mod errors;
+mod request_builder;
+mod types;
+
+pub use client::{PoolClient, PoolInterface};
+pub use errors::{PoolError, PoolErrorExt};
+pub use request_builder::RequestBuilder;
+pub use types::{AuctionData, AuctionType, Positions, Request, RequestType};
@@ -0,0 +1,172 @@
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::{Request, RequestType};
+
+/// Builds the list of requests for a pool `submit`
+///
+/// ### Example
+/// ```ignore
+/// let requests = RequestBuilder::new(&e)
+///     .supply_collateral(&usdc, 100_0000000)
+///     .borrow(&xlm, 50_0000000)
+///     .build();
+/// pool_client.submit(&user, &user, &user, &requests);
+/// ```
+#[derive(Clone)]
+pub struct RequestBuilder {
+    requests: Vec<Request>,
+}
+
+impl RequestBuilder {
+    /// Create an empty request builder
+    pub fn new(e: &Env) -> Self {
+        RequestBuilder {
+            requests: Vec::new(e),
+        }
+    }
+
+    /// Add a request of any type
+    ///
+    /// ### Arguments
+    /// * `request_type` - The type of request
+    /// * `address` - The asset address, or the user for auction requests
+    /// * `amount` - The amount of tokens, or the percent for auction fills
+    pub fn request(mut self, request_type: RequestType, address: &Address, amount: i128) -> Self {
+        self.requests.push_back(Request {
+            request_type: request_type as u32,
+            address: address.clone(),
+            amount,
+        });
+        self
+    }
+
+    /// Supply `amount` of `asset` without using it as collateral
+    pub fn supply(self, asset: &Address, amount: i128) -> Self {
+        self.request(RequestType::Supply, asset, amount)
+    }
+
+    /// Withdraw `amount` of a non-collateral supply of `asset`
+    pub fn withdraw(self, asset: &Address, amount: i128) -> Self {
+        self.request(RequestType::Withdraw, asset, amount)
+    }
+
+    /// Supply `amount` of `asset` as collateral
+    pub fn supply_collateral(self, asset: &Address, amount: i128) -> Self {
+        self.request(RequestType::SupplyCollateral, asset, amount)
+    }
+
+    /// Withdraw `amount` of the collateral supply of `asset`
+    pub fn withdraw_collateral(self, asset: &Address, amount: i128) -> Self {
+        self.request(RequestType::WithdrawCollateral, asset, amount)
+    }
+
+    /// Borrow `amount` of `asset`
+    pub fn borrow(self, asset: &Address, amount: i128) -> Self {
+        self.request(RequestType::Borrow, asset, amount)
+    }
+
+    /// Borrow `amount` of `asset` at a stable rate
+    pub fn borrow_stable(self, asset: &Address, amount: i128) -> Self {
+        self.request(RequestType::BorrowStable, asset, amount)
+    }
+
+    /// Swap all of the stable rate liabilities of `asset` to the variable rate
+    pub fn swap_to_variable(self, asset: &Address) -> Self {
+        self.request(RequestType::SwapToVariable, asset, 0)
+    }
+
+    /// Repay `amount` of the liabilities of `asset`
+    pub fn repay(self, asset: &Address, amount: i128) -> Self {
+        self.request(RequestType::Repay, asset, amount)
+    }
+
+    /// Fill `percent` of the liquidation auction of `user` (i.e. 15 => 15%)
+    pub fn fill_user_liquidation_auction(self, user: &Address, percent: u32) -> Self {
+        self.request(
+            RequestType::FillUserLiquidationAuction,
+            user,
+            percent as i128,
+        )
+    }
+
+    /// Fill `percent` of the bad debt auction of the pool's `backstop` (i.e. 15 => 15%)
+    pub fn fill_bad_debt_auction(self, backstop: &Address, percent: u32) -> Self {
+        self.request(RequestType::FillBadDebtAuction, backstop, percent as i128)
+    }
+
+    /// Fill `percent` of the interest auction of the pool's `backstop` (i.e. 15 => 15%)
+    pub fn fill_interest_auction(self, backstop: &Address, percent: u32) -> Self {
+        self.request(RequestType::FillInterestAuction, backstop, percent as i128)
+    }
+
+    /// Delete the liquidation auction of the user submitting the requests, `user`
+    pub fn delete_liquidation_auction(self, user: &Address) -> Self {
+        self.request(RequestType::DeleteLiquidationAuction, user, 0)
+    }
+
+    /// Soft liquidate the entire position of `user`
+    pub fn soft_liquidation(self, user: &Address) -> Self {
+        self.request(RequestType::SoftLiquidation, user, 0)
+    }
+
+    /// Queue a withdrawal of `amount` of the supply of `asset`
+    pub fn queue_withdrawal(self, asset: &Address, amount: i128) -> Self {
+        self.request(RequestType::QueueWithdrawal, asset, amount)
+    }
+
+    /// Claim as much of the queued withdrawal of `asset` as possible
+    pub fn claim_withdrawal(self, asset: &Address) -> Self {
+        self.request(RequestType::ClaimWithdrawal, asset, 0)
+    }
+
+    /// Fetch the built requests
+    pub fn build(self) -> Vec<Request> {
+        self.requests
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::{testutils::Address as _, vec};
+
+    #[test]
+    fn test_build_requests() {
+        let e = Env::default();
+        let asset = Address::generate(&e);
+        let user = Address::generate(&e);
+
+        let requests = RequestBuilder::new(&e)
+            .supply_collateral(&asset, 100_0000000)
+            .borrow(&asset, 50_0000000)
+            .fill_user_liquidation_auction(&user, 25)
+            .claim_withdrawal(&asset)
+            .build();
+        assert_eq!(
+            requests,
+            vec![
+                &e,
+                Request {
+                    request_type: 2,
+                    address: asset.clone(),
+                    amount: 100_0000000,
+                },
+                Request {
+                    request_type: 4,
+                    address: asset.clone(),
+                    amount: 50_0000000,
+                },
+                Request {
+                    request_type: 6,
+                    address: user.clone(),
+                    amount: 25,
+                },
+                Request {
+                    request_type: 12,
+                    address: asset.clone(),
+                    amount: 0,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use soroban_sdk::{contracttype, Address, Env, Map};
+
+/// A request a user makes against the pool
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct Request {
+    pub request_type: u32,
+    pub address: Address, // asset address or liquidatee
+    pub amount: i128,
+}
+
+/// The type of request to be made against the pool
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(u32)]
+pub enum RequestType {
+    Supply = 0,
+    Withdraw = 1,
+    SupplyCollateral = 2,
+    WithdrawCollateral = 3,
+    Borrow = 4,
+    Repay = 5,
+    FillUserLiquidationAuction = 6,
+    FillBadDebtAuction = 7,
+    FillInterestAuction = 8,
+    DeleteLiquidationAuction = 9,
+    SoftLiquidation = 10,
+    QueueWithdrawal = 11,
+    ClaimWithdrawal = 12,
+    BorrowStable = 13,
+    SwapToVariable = 14,
+}
+
+/// The type of auction
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(u32)]
+pub enum AuctionType {
+    UserLiquidation = 0,
+    BadDebtAuction = 1,
+    InterestAuction = 2,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct AuctionData {
+    /// A map of the assets being bid on and the amount being bid. These are tokens spent
+    /// by the filler of the auction.
+    ///
+    /// The bid is different based on each auction type:
+    /// - UserLiquidation: dTokens
+    /// - BadDebtAuction: dTokens
+    /// - InterestAuction: Underlying assets (backstop token)
+    pub bid: Map<Address, i128>,
+    /// A map of the assets being auctioned off and the amount being auctioned. These are tokens
+    /// received by the filler of the auction.
+    ///
+    /// The lot is different based on each auction type:
+    /// - UserLiquidation: bTokens
+    /// - BadDebtAuction: Underlying assets (backstop token)
+    /// - InterestAuction: Underlying assets
+    pub lot: Map<Address, i128>,
+    /// The block the auction begins on. This is used to determine how the auction
+    /// should be scaled based on the number of blocks that have passed since the auction began.
+    pub block: u32,
+}
+
+/// A user / contracts position's with the pool, stored in the Reserve's decimals
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct Positions {
+    pub liabilities: Map<u32, i128>, // Map of Reserve Index to liability share balance
+    pub collateral: Map<u32, i128>,  // Map of Reserve Index to collateral supply share balance
+    pub supply: Map<u32, i128>,      // Map of Reserve Index to non-collateral supply share balance
+}
+
+impl Positions {
+    /// Create an empty Positions object in the environment
+    pub fn env_default(e: &Env) -> Self {
+        Positions {
+            liabilities: Map::new(e),
+            collateral: Map::new(e),
+            supply: Map::new(e),
+        }
+    }
+}
@@ -1,17 +1,28 @@
 use crate::{
     constants::SCALAR_7,
-    errors::PoolError,
-    pool::{Pool, User},
+    errors::{PoolError, PoolErrorExt},
+    pool::{execute_liquidation_hook, Pool, User},
     storage,
 };
+use auction_math::AuctionCurve;
 use cast::i128;
 use soroban_fixed_point_math::SorobanFixedPoint;
 use soroban_sdk::{contracttype, map, panic_with_error, Address, Env, Map, Vec};
 
 use super::{
-    backstop_interest_auction::{create_interest_auction_data, fill_interest_auction},
-    bad_debt_auction::{create_bad_debt_auction_data, fill_bad_debt_auction},
-    user_liquidation_auction::{create_user_liq_auction_data, fill_user_liq_auction},
+    backstop_interest_auction::{
+        build_interest_auction_data, create_interest_auction_data,
+        create_partial_interest_auction_data, fill_interest_auction, to_full_lot,
+    },
+    bad_debt_auction::{
+        build_bad_debt_auction_data, create_bad_debt_auction_data, fill_bad_debt_auction,
+    },
+    fill_commit::requires_fill_commitment,
+    filler_stats::record_fill,
+    user_liquidation_auction::{
+        build_user_liq_auction_data, cap_liq_fill_percent, create_user_liq_auction_data,
+        fill_user_liq_auction,
+    },
 };
 
 #[derive(Clone, PartialEq)]
@@ -31,6 +42,17 @@ impl AuctionType {
             _ => panic_with_error!(e, PoolError::BadRequest),
         }
     }
+
+    /// Fetch the curve auctions of this type scale along from the pool's auction config
+    pub fn curve(&self, e: &Env) -> AuctionCurve {
+        let config = storage::get_auction_config(e);
+        let curve = match self {
+            AuctionType::UserLiquidation => config.liq_curve,
+            AuctionType::BadDebtAuction => config.bad_debt_curve,
+            AuctionType::InterestAuction => config.interest_curve,
+        };
+        AuctionCurve::from_u32(curve).unwrap_or(AuctionCurve::Linear)
+    }
 }
 
 #[derive(Clone)]
@@ -57,7 +79,9 @@ pub struct AuctionData {
     pub block: u32,
 }
 
-/// Create a new auction. Stores the resulting auction to the ledger to begin on the next block.
+/// Create a new auction. Stores the resulting auction to the ledger to begin on the next block,
+/// plus the pool's configured auction start delay. For user liquidations, the user's liquidation
+/// protection hook is invoked before the auction is sized.
 ///
 /// Returns the AuctionData object created
 ///
@@ -72,6 +96,7 @@ pub struct AuctionData {
 /// * If the max positions are exceeded
 /// * If the user and percent are invalid for the auction type
 /// * If the auction is unable to be created
+/// * If the creation of new auctions is paused
 pub fn create_auction(
     e: &Env,
     auction_type: u32,
@@ -80,19 +105,110 @@ pub fn create_auction(
     lot: &Vec<Address>,
     percent: u32,
 ) -> AuctionData {
+    require_auction_creation_allowed(e);
     require_unique_addresses(e, bid);
     require_unique_addresses(e, lot);
     // panics if auction_type parameter is not valid
     let auction_type_enum = AuctionType::from_u32(e, auction_type);
-    let auction_data = match auction_type_enum {
-        AuctionType::UserLiquidation => create_user_liq_auction_data(e, user, bid, lot, percent),
+    let mut auction_data = match auction_type_enum {
+        AuctionType::UserLiquidation => {
+            // let the user's liquidation protection hook top up their positions before sizing the auction
+            execute_liquidation_hook(e, user);
+            create_user_liq_auction_data(e, user, bid, lot, percent)
+        }
         AuctionType::BadDebtAuction => create_bad_debt_auction_data(e, user, bid, lot, percent),
         AuctionType::InterestAuction => create_interest_auction_data(e, user, bid, lot, percent),
     };
+    // delay the start of the auction to give the user a window to respond before it can be filled
+    auction_data.block += storage::get_auction_config(e).start_delay;
     storage::set_auction(e, &auction_type, user, &auction_data);
     auction_data
 }
 
+/// Create a new interest auction that auctions at most the given amount of each reserve's backstop
+/// credit. Stores the resulting auction to the ledger to begin on the next block, plus the pool's
+/// configured auction start delay.
+///
+/// Returns the AuctionData object created
+///
+/// ### Arguments
+/// * `bid` - The assets being bid on
+/// * `lot` - A map of the assets being auctioned off to the maximum amount of each to auction
+///
+/// ### Panics
+/// * If a maximum lot amount is not positive
+/// * If the interest auction is unable to be created
+/// * If the creation of new auctions is paused
+pub fn create_partial_interest_auction(
+    e: &Env,
+    bid: &Vec<Address>,
+    lot: &Map<Address, i128>,
+) -> AuctionData {
+    require_auction_creation_allowed(e);
+    require_unique_addresses(e, bid);
+    let mut auction_data = create_partial_interest_auction_data(e, bid, lot);
+    auction_data.block += storage::get_auction_config(e).start_delay;
+    storage::set_auction(
+        e,
+        &(AuctionType::InterestAuction as u32),
+        &storage::get_backstop(e),
+        &auction_data,
+    );
+    auction_data
+}
+
+/// Require that new auctions can be created. Existing auctions can still be filled while the
+/// creation of new auctions is paused.
+///
+/// ### Panics
+/// If the creation of new auctions is paused
+pub fn require_auction_creation_allowed(e: &Env) {
+    if storage::get_auctions_paused(e) {
+        panic_with_error!(e, PoolErrorExt::AuctionsPaused);
+    }
+}
+
+/// Preview the auction that would be created, without writing to the ledger. The preview does not
+/// require the auction to be creatable yet, so a user liquidation can be previewed for a healthy user,
+/// and an auction can be previewed while one is in progress.
+///
+/// Returns the AuctionData object that would be created at the current ledger
+///
+/// ### Arguments
+/// * `auction_type` - The type of auction being previewed
+/// * `user` - The user involved in the auction
+/// * `bid` - The assets being bid on
+/// * `lot` - The assets being auctioned off
+/// * `percent` - The percentage of the user's positions being liquidated
+///
+/// ### Panics
+/// * If the max positions are exceeded
+/// * If the user, assets, or percent are invalid for the auction type
+pub fn preview_auction(
+    e: &Env,
+    auction_type: u32,
+    user: &Address,
+    bid: &Vec<Address>,
+    lot: &Vec<Address>,
+    percent: u32,
+) -> AuctionData {
+    require_unique_addresses(e, bid);
+    require_unique_addresses(e, lot);
+    let mut auction_data = match AuctionType::from_u32(e, auction_type) {
+        AuctionType::UserLiquidation => {
+            build_user_liq_auction_data(e, user, bid, lot, percent, true)
+        }
+        AuctionType::BadDebtAuction => {
+            build_bad_debt_auction_data(e, user, bid, lot, percent, true)
+        }
+        AuctionType::InterestAuction => {
+            build_interest_auction_data(e, user, bid, &to_full_lot(e, lot), percent, true)
+        }
+    };
+    auction_data.block += storage::get_auction_config(e).start_delay;
+    auction_data
+}
+
 /// Delete an auction if it is stale
 pub fn delete_stale_auction(e: &Env, auction_type: u32, user: &Address) {
     if !storage::has_auction(e, &auction_type, user) {
@@ -108,6 +224,27 @@ pub fn delete_stale_auction(e: &Env, auction_type: u32, user: &Address) {
     storage::del_auction(e, &auction_type, user);
 }
 
+/// Fetch every ongoing auction for a user
+///
+/// Returns a vec of the auction type and auction data of each ongoing auction, ordered by type
+///
+/// ### Arguments
+/// * `user` - The address involved in the auctions
+pub fn get_user_auctions(e: &Env, user: &Address) -> Vec<(u32, AuctionData)> {
+    let mut auctions = Vec::new(e);
+    for auction_type in [
+        AuctionType::UserLiquidation,
+        AuctionType::BadDebtAuction,
+        AuctionType::InterestAuction,
+    ] {
+        let auction_type = auction_type as u32;
+        if storage::has_auction(e, &auction_type, user) {
+            auctions.push_back((auction_type, storage::get_auction(e, &auction_type, user)));
+        }
+    }
+    auctions
+}
+
 /// Delete a liquidation auction if the user being liquidated
 ///
 /// NOTE: Does not verify if the user's positions are healthy. This must be done
@@ -134,24 +271,101 @@ pub fn delete_liquidation(e: &Env, user: &Address) {
 /// * `filler_state` - The Address filling the auction
 /// * `percent_filled` - The percentage being filled as a number (i.e. 15 => 15%)
 ///
+/// A partial fill of a user liquidation is capped at the percent that restores the user to the
+/// pool's liquidation target health factor, if one is set. A capped fill ends the auction.
+///
+/// ### Panics
+/// If the auction does not exist, if the auction can only be filled with a commitment, or if the
+/// pool is unable to fulfill either side of the auction quote
+pub fn fill(
+    e: &Env,
+    pool: &mut Pool,
+    auction_type: u32,
+    user: &Address,
+    filler_state: &mut User,
+    percent_filled: u64,
+) -> AuctionData {
+    fill_auction(
+        e,
+        pool,
+        auction_type,
+        user,
+        filler_state,
+        percent_filled,
+        false,
+    )
+}
+
+/// Fills a user liquidation auction from the invoker after the filler's commitment to the fill has
+/// been consumed. See `fill`.
+///
 /// ### Panics
 /// If the auction does not exist, or if the pool is unable to fulfill either side
 /// of the auction quote
-pub fn fill(
+pub fn fill_revealed(
+    e: &Env,
+    pool: &mut Pool,
+    user: &Address,
+    filler_state: &mut User,
+    percent_filled: u64,
+) -> AuctionData {
+    fill_auction(
+        e,
+        pool,
+        AuctionType::UserLiquidation as u32,
+        user,
+        filler_state,
+        percent_filled,
+        true,
+    )
+}
+
+/// Fills the auction from the invoker, requiring a commitment to fill large user liquidation
+/// auctions unless the fill was revealed
+fn fill_auction(
     e: &Env,
     pool: &mut Pool,
     auction_type: u32,
     user: &Address,
     filler_state: &mut User,
     percent_filled: u64,
+    revealed: bool,
 ) -> AuctionData {
     if user.clone() == filler_state.address {
         panic_with_error!(e, PoolError::InvalidLiquidation);
     }
     let auction_data = storage::get_auction(e, &auction_type, user);
-    let (to_fill_auction, remaining_auction) = scale_auction(e, &auction_data, percent_filled);
+    let auction_type_enum = AuctionType::from_u32(e, auction_type);
+    if !revealed && requires_fill_commitment(e, pool, &auction_type_enum, &auction_data) {
+        panic_with_error!(e, PoolError::InvalidFillCommitment);
+    }
+    let curve = auction_type_enum.curve(e);
+    let auction_config = storage::get_auction_config(e);
+    let mut min_fill_pct = auction_config.min_fill_pct;
+    let mut percent_filled = percent_filled;
+    let mut is_capped = false;
+    if auction_type_enum == AuctionType::UserLiquidation {
+        let capped_percent = cap_liq_fill_percent(
+            e,
+            pool,
+            &auction_data,
+            user,
+            curve,
+            percent_filled,
+            auction_config.liq_target_hf,
+        );
+        if capped_percent < percent_filled {
+            // a capped fill restores the user to the target health factor, so it ends the auction
+            // and the rest of the lot stays with the user
+            percent_filled = capped_percent;
+            min_fill_pct = 0;
+            is_capped = true;
+        }
+    }
+    let (to_fill_auction, remaining_auction) =
+        scale_auction(e, &auction_data, percent_filled, curve, min_fill_pct);
     let is_full_fill = remaining_auction.is_none();
-    match AuctionType::from_u32(e, auction_type) {
+    match auction_type_enum {
         AuctionType::UserLiquidation => {
             fill_user_liq_auction(e, pool, &to_fill_auction, user, filler_state, is_full_fill)
         }
@@ -162,11 +376,19 @@ pub fn fill(
             fill_interest_auction(e, pool, &to_fill_auction, &filler_state.address)
         }
     };
-
-    if let Some(auction_to_store) = remaining_auction {
-        storage::set_auction(e, &auction_type, user, &auction_to_store);
-    } else {
-        storage::del_auction(e, &auction_type, user);
+    record_fill(
+        e,
+        pool,
+        &auction_type_enum,
+        &filler_state.address,
+        &to_fill_auction,
+    );
+
+    match remaining_auction {
+        Some(auction_to_store) if !is_capped => {
+            storage::set_auction(e, &auction_type, user, &auction_to_store);
+        }
+        _ => storage::del_auction(e, &auction_type, user),
     }
 
     to_fill_auction
@@ -178,22 +400,34 @@ pub fn fill(
 /// ### Arguments
 /// * `auction_data` - The auction data to scale
 /// * `percent_filled` - The percentage being filled as a number (i.e. 15 => 15%)
+/// * `curve` - The curve the auction modifiers follow
+/// * `min_fill_pct` - The minimum percent a partial fill must take, or 0 if there is no minimum
 ///
 /// Returns the (Scaled Auction, Remaining Auction) such that:
 /// - Scaled Auction is the auction data scaled
 /// - Remaining Auction is the leftover auction data that will be stored in the ledger, or deleted if None
 ///
 /// ### Panics
-/// If the percent filled is greater than 100 or less than 0
-#[allow(clippy::zero_prefixed_literal)]
-fn scale_auction(
+/// * If the percent filled is greater than 100 or less than 0
+/// * If the percent filled is a partial fill below the minimum fill percent
+/// * If the auction has not started yet
+pub(super) fn scale_auction(
     e: &Env,
     auction_data: &AuctionData,
     percent_filled: u64,
+    curve: AuctionCurve,
+    min_fill_pct: u32,
 ) -> (AuctionData, Option<AuctionData>) {
     if percent_filled > 100 || percent_filled == 0 {
         panic_with_error!(e, PoolError::BadRequest);
     }
+    // the remainder of an auction can always be filled
+    if percent_filled < 100 && percent_filled < min_fill_pct as u64 {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    if e.ledger().sequence() < auction_data.block {
+        panic_with_error!(e, PoolError::AuctionNotStarted);
+    }
 
     let mut to_fill_auction = AuctionData {
         bid: map![e],
@@ -207,52 +441,49 @@ fn scale_auction(
     };
 
     // determine block based auction modifiers
-    let bid_modifier: i128;
-    let lot_modifier: i128;
-    let per_block_scalar: i128 = 0_0050000; // modifier moves 0.5% every block
-    let block_dif = i128(e.ledger().sequence() - auction_data.block);
-    if block_dif > 200 {
-        // lot 100%, bid scaling down from 100% to 0%
-        lot_modifier = SCALAR_7;
-        if block_dif < 400 {
-            bid_modifier = SCALAR_7 - (block_dif - 200) * per_block_scalar;
-        } else {
-            bid_modifier = 0;
-        }
-    } else {
-        // lot scaling from 0% to 100%, bid 100%
-        lot_modifier = block_dif * per_block_scalar;
-        bid_modifier = SCALAR_7;
-    }
+    let (bid_modifier, lot_modifier) =
+        auction_math::get_curve_modifiers(curve, e.ledger().sequence() - auction_data.block);
 
-    // scale the auction
+    // scale the auction. Amounts too large for auction-math's i128 math fall back to 256 bit math
     let percent_filled_i128 = i128(percent_filled) * 1_00000; // scale to decimal form in 7 decimals from percentage
     for (asset, amount) in auction_data.bid.iter() {
-        // apply percent scalar and store remainder to base auction
         // round up to avoid rounding exploits
-        let to_fill_base = amount.fixed_mul_ceil(e, &percent_filled_i128, &SCALAR_7);
-        let remaining_base = amount - to_fill_base;
-        if remaining_base > 0 {
-            remaining_auction.bid.set(asset.clone(), remaining_base);
+        let (to_fill, remaining) =
+            auction_math::scale_bid_amount(amount, percent_filled, bid_modifier).unwrap_or_else(
+                || {
+                    let to_fill_base = amount.fixed_mul_ceil(e, &percent_filled_i128, &SCALAR_7);
+                    (
+                        to_fill_base.fixed_mul_ceil(e, &bid_modifier, &SCALAR_7),
+                        amount - to_fill_base,
+                    )
+                },
+            );
+        // store remainder to base auction and don't store to_fill if 0
+        if remaining > 0 {
+            remaining_auction.bid.set(asset.clone(), remaining);
         }
-        // apply block scalar to to_fill auction and don't store if 0
-        let to_fill_scaled = to_fill_base.fixed_mul_ceil(e, &bid_modifier, &SCALAR_7);
-        if to_fill_scaled > 0 {
-            to_fill_auction.bid.set(asset, to_fill_scaled);
+        if to_fill > 0 {
+            to_fill_auction.bid.set(asset, to_fill);
         }
     }
     for (asset, amount) in auction_data.lot.iter() {
-        // apply percent scalar and store remainder to base auction
         // round down to avoid rounding exploits
-        let to_fill_base = amount.fixed_mul_floor(e, &percent_filled_i128, &SCALAR_7);
-        let remaining_base = amount - to_fill_base;
-        if remaining_base > 0 {
-            remaining_auction.lot.set(asset.clone(), remaining_base);
+        let (to_fill, remaining) =
+            auction_math::scale_lot_amount(amount, percent_filled, lot_modifier).unwrap_or_else(
+                || {
+                    let to_fill_base = amount.fixed_mul_floor(e, &percent_filled_i128, &SCALAR_7);
+                    (
+                        to_fill_base.fixed_mul_floor(e, &lot_modifier, &SCALAR_7),
+                        amount - to_fill_base,
+                    )
+                },
+            );
+        // store remainder to base auction and don't store to_fill if 0
+        if remaining > 0 {
+            remaining_auction.lot.set(asset.clone(), remaining);
         }
-        // apply block scalar to to_fill auction and don't store if 0
-        let to_fill_scaled = to_fill_base.fixed_mul_floor(e, &lot_modifier, &SCALAR_7);
-        if to_fill_scaled > 0 {
-            to_fill_auction.lot.set(asset, to_fill_scaled);
+        if to_fill > 0 {
+            to_fill_auction.lot.set(asset, to_fill);
         }
     }
 
@@ -281,7 +512,7 @@ fn require_unique_addresses(e: &Env, list: &Vec<Address>) {
 mod tests {
     use crate::{
         pool::Positions,
-        storage::PoolConfig,
+        storage::{AuctionConfig, PoolConfig},
         testutils::{self, create_comet_lp_pool, create_pool},
     };
 
@@ -412,6 +643,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -522,6 +755,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -635,6 +870,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_backstop(&e, &Address::generate(&e));
@@ -655,10 +892,9 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1211)")]
-    fn test_create_liquidation_for_pool() {
+    fn test_preview_liquidation_healthy_user() {
         let e = Env::default();
-        e.cost_estimate().budget().reset_unlimited();
+
         e.mock_all_auths();
         e.ledger().set(LedgerInfo {
             timestamp: 12345,
@@ -672,10 +908,13 @@ mod tests {
         });
 
         let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
 
         let pool_address = create_pool(&e);
         let (oracle_address, oracle_client) = testutils::create_mock_oracle(&e);
 
+        // creating reserves for a pool exhausts the budget
+        e.cost_estimate().budget().reset_unlimited();
         let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
         let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
         reserve_data_0.last_time = 12345;
@@ -740,7 +979,7 @@ mod tests {
                 (reserve_config_0.index, 90_9100000),
                 (reserve_config_1.index, 04_5800000),
             ],
-            liabilities: map![&e, (reserve_config_2.index, 02_7500000),],
+            liabilities: map![&e, (reserve_config_2.index, 0_5000000),],
             supply: map![&e],
         };
         let pool_config = PoolConfig {
@@ -749,28 +988,43 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_backstop(&e, &Address::generate(&e));
-            storage::set_user_positions(&e, &pool_address, &positions);
+            storage::set_user_positions(&e, &samwise, &positions);
             storage::set_pool_config(&e, &pool_config);
 
-            create_auction(
+            storage::set_auction_config(
+                &e,
+                &AuctionConfig {
+                    start_delay: 5,
+                    ..storage::get_auction_config(&e)
+                },
+            );
+
+            // the user is healthy, so the liquidation can be previewed but not created
+            e.cost_estimate().budget().reset_unlimited();
+            let auction_data = preview_auction(
                 &e,
                 0,
-                &pool_address,
-                &vec![&e, underlying_2],
+                &samwise,
+                &vec![&e, underlying_2.clone()],
                 &vec![&e, underlying_0, underlying_1],
                 liq_pct,
             );
+            assert_eq!(auction_data.block, 51 + 5);
+            assert_eq!(auction_data.bid.get_unchecked(underlying_2), 0_2250000);
+            assert_eq!(auction_data.lot.len(), 2);
+            assert!(!storage::has_auction(&e, &0, &samwise));
         });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1211)")]
-    fn test_create_liquidation_for_backstop() {
+    fn test_create_liquidation_with_start_delay() {
         let e = Env::default();
-        e.cost_estimate().budget().reset_unlimited();
+
         e.mock_all_auths();
         e.ledger().set(LedgerInfo {
             timestamp: 12345,
@@ -784,11 +1038,13 @@ mod tests {
         });
 
         let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
 
         let pool_address = create_pool(&e);
-        let backstop = Address::generate(&e);
         let (oracle_address, oracle_client) = testutils::create_mock_oracle(&e);
 
+        // creating reserves for a pool exhausts the budget
+        e.cost_estimate().budget().reset_unlimited();
         let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
         let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
         reserve_data_0.last_time = 12345;
@@ -862,30 +1118,51 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
-            storage::set_backstop(&e, &backstop);
-            storage::set_user_positions(&e, &backstop, &positions);
+            storage::set_backstop(&e, &Address::generate(&e));
+            storage::set_user_positions(&e, &samwise, &positions);
             storage::set_pool_config(&e, &pool_config);
+            storage::set_auction_config(
+                &e,
+                &AuctionConfig {
+                    start_delay: 10,
+                    soft_liq_max_value: 0,
+                    soft_liq_bonus: 0,
+                    interest_interval: 0,
+                    interest_min_value: 0,
+                    bad_debt_premium_cap: 0,
+                    bad_debt_bid_premium: 0,
+                    liq_curve: 0,
+                    bad_debt_curve: 0,
+                    interest_curve: 0,
+                    min_fill_pct: 0,
+                    liq_target_hf: 0,
+                },
+            );
 
-            create_auction(
+            e.cost_estimate().budget().reset_unlimited();
+            let auction_data = create_auction(
                 &e,
                 0,
-                &backstop,
+                &samwise,
                 &vec![&e, underlying_2],
                 &vec![&e, underlying_0, underlying_1],
                 liq_pct,
             );
+            assert_eq!(auction_data.block, 61);
+            assert_eq!(storage::get_auction(&e, &0, &samwise).block, 61);
         });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1200)")]
-    fn test_create_auction_invalid_type() {
+    #[should_panic(expected = "Error(Contract, #1211)")]
+    fn test_create_liquidation_for_pool() {
         let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
         e.mock_all_auths();
-        e.cost_estimate().budget().reset_unlimited(); // setup exhausts budget
-
         e.ledger().set(LedgerInfo {
             timestamp: 12345,
             protocol_version: 22,
@@ -900,21 +1177,14 @@ mod tests {
         let bombadil = Address::generate(&e);
 
         let pool_address = create_pool(&e);
-        let (usdc_id, _) = testutils::create_token_contract(&e, &bombadil);
-        let (blnd_id, _) = testutils::create_blnd_token(&e, &pool_address, &bombadil);
-
-        let (backstop_token_id, _) = create_comet_lp_pool(&e, &bombadil, &blnd_id, &usdc_id);
-        let (backstop_address, backstop_client) =
-            testutils::create_backstop(&e, &pool_address, &backstop_token_id, &usdc_id, &blnd_id);
-        backstop_client.deposit(&bombadil, &pool_address, &(50 * SCALAR_7));
-        let (oracle_id, oracle_client) = testutils::create_mock_oracle(&e);
+        let (oracle_address, oracle_client) = testutils::create_mock_oracle(&e);
 
         let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
         let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
         reserve_data_0.last_time = 12345;
-        reserve_data_0.backstop_credit = 200_0000000;
-        reserve_data_0.b_supply = 1000_0000000;
-        reserve_data_0.d_supply = 750_0000000;
+        reserve_data_0.b_rate = 1_100_000_000_000;
+        reserve_config_0.c_factor = 0_8500000;
+        reserve_config_0.l_factor = 0_9000000;
         reserve_config_0.index = 0;
         testutils::create_reserve(
             &e,
@@ -924,47 +1194,89 @@ mod tests {
             &reserve_data_0,
         );
 
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.b_rate = 1_200_000_000_000;
+        reserve_config_1.c_factor = 0_7500000;
+        reserve_config_1.l_factor = 0_7500000;
+        reserve_data_1.last_time = 12345;
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+
+        let (underlying_2, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_2, reserve_data_2) = testutils::default_reserve_meta();
+        reserve_config_2.c_factor = 0_0000000;
+        reserve_config_2.l_factor = 0_7000000;
+        reserve_config_2.index = 2;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_2,
+            &reserve_config_2,
+            &reserve_data_2,
+        );
+
         oracle_client.set_data(
             &bombadil,
             &Asset::Other(Symbol::new(&e, "USD")),
             &vec![
                 &e,
                 Asset::Stellar(underlying_0.clone()),
-                Asset::Stellar(usdc_id),
+                Asset::Stellar(underlying_1.clone()),
+                Asset::Stellar(underlying_2.clone()),
             ],
             &7,
             &300,
         );
-        oracle_client.set_price_stable(&vec![&e, 2_0000000, 1_0000000]);
+        oracle_client.set_price_stable(&vec![&e, 2_0000000, 4_0000000, 50_0000000]);
 
+        let liq_pct = 45;
+        let positions: Positions = Positions {
+            collateral: map![
+                &e,
+                (reserve_config_0.index, 90_9100000),
+                (reserve_config_1.index, 04_5800000),
+            ],
+            liabilities: map![&e, (reserve_config_2.index, 02_7500000),],
+            supply: map![&e],
+        };
         let pool_config = PoolConfig {
-            oracle: oracle_id,
+            oracle: oracle_address,
             min_collateral: 1_0000000,
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
+            storage::set_backstop(&e, &Address::generate(&e));
+            storage::set_user_positions(&e, &pool_address, &positions);
             storage::set_pool_config(&e, &pool_config);
 
             create_auction(
                 &e,
-                3,
-                &backstop_address,
-                &vec![&e, backstop_token_id],
-                &vec![&e, underlying_0],
-                100,
+                0,
+                &pool_address,
+                &vec![&e, underlying_2],
+                &vec![&e, underlying_0, underlying_1],
+                liq_pct,
             );
         });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1200)")]
-    fn test_create_auction_duplicate_bid() {
+    #[should_panic(expected = "Error(Contract, #1211)")]
+    fn test_create_liquidation_for_backstop() {
         let e = Env::default();
-        e.mock_all_auths_allowing_non_root_auth();
-        e.cost_estimate().budget().reset_unlimited(); // setup exhausts budget
-
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths();
         e.ledger().set(LedgerInfo {
             timestamp: 12345,
             protocol_version: 22,
@@ -977,33 +1289,17 @@ mod tests {
         });
 
         let bombadil = Address::generate(&e);
-        let samwise = Address::generate(&e);
-        let pool_address = create_pool(&e);
 
-        let (blnd, blnd_client) = testutils::create_blnd_token(&e, &pool_address, &bombadil);
-        let (usdc, usdc_client) = testutils::create_token_contract(&e, &bombadil);
-        let (lp_token, lp_token_client) =
-            testutils::create_comet_lp_pool(&e, &bombadil, &blnd, &usdc);
-        let (backstop_address, backstop_client) =
-            testutils::create_backstop(&e, &pool_address, &lp_token, &usdc, &blnd);
-        // mint lp tokens
-        blnd_client.mint(&samwise, &500_001_0000000);
-        blnd_client.approve(&samwise, &lp_token, &i128::MAX, &99999);
-        usdc_client.mint(&samwise, &12_501_0000000);
-        usdc_client.approve(&samwise, &lp_token, &i128::MAX, &99999);
-        lp_token_client.join_pool(
-            &50_000_0000000,
-            &vec![&e, 500_001_0000000, 12_501_0000000],
-            &samwise,
-        );
-        backstop_client.deposit(&samwise, &pool_address, &50_000_0000000);
-
-        let (oracle_id, oracle_client) = testutils::create_mock_oracle(&e);
+        let pool_address = create_pool(&e);
+        let backstop = Address::generate(&e);
+        let (oracle_address, oracle_client) = testutils::create_mock_oracle(&e);
 
         let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
         let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
-        reserve_data_0.d_rate = 1_100_000_000_000;
         reserve_data_0.last_time = 12345;
+        reserve_data_0.b_rate = 1_100_000_000_000;
+        reserve_config_0.c_factor = 0_8500000;
+        reserve_config_0.l_factor = 0_9000000;
         reserve_config_0.index = 0;
         testutils::create_reserve(
             &e,
@@ -1015,7 +1311,9 @@ mod tests {
 
         let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
         let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
-        reserve_data_1.d_rate = 1_200_000_000_000;
+        reserve_data_1.b_rate = 1_200_000_000_000;
+        reserve_config_1.c_factor = 0_7500000;
+        reserve_config_1.l_factor = 0_7500000;
         reserve_data_1.last_time = 12345;
         reserve_config_1.index = 1;
         testutils::create_reserve(
@@ -1027,8 +1325,245 @@ mod tests {
         );
 
         let (underlying_2, _) = testutils::create_token_contract(&e, &bombadil);
-        let (mut reserve_config_2, mut reserve_data_2) = testutils::default_reserve_meta();
-        reserve_data_2.b_rate = 1_100_000_000_000;
+        let (mut reserve_config_2, reserve_data_2) = testutils::default_reserve_meta();
+        reserve_config_2.c_factor = 0_0000000;
+        reserve_config_2.l_factor = 0_7000000;
+        reserve_config_2.index = 2;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_2,
+            &reserve_config_2,
+            &reserve_data_2,
+        );
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+                Asset::Stellar(underlying_2.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 2_0000000, 4_0000000, 50_0000000]);
+
+        let liq_pct = 45;
+        let positions: Positions = Positions {
+            collateral: map![
+                &e,
+                (reserve_config_0.index, 90_9100000),
+                (reserve_config_1.index, 04_5800000),
+            ],
+            liabilities: map![&e, (reserve_config_2.index, 02_7500000),],
+            supply: map![&e],
+        };
+        let pool_config = PoolConfig {
+            oracle: oracle_address,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_backstop(&e, &backstop);
+            storage::set_user_positions(&e, &backstop, &positions);
+            storage::set_pool_config(&e, &pool_config);
+
+            create_auction(
+                &e,
+                0,
+                &backstop,
+                &vec![&e, underlying_2],
+                &vec![&e, underlying_0, underlying_1],
+                liq_pct,
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_create_auction_invalid_type() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.cost_estimate().budget().reset_unlimited(); // setup exhausts budget
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 50,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+        let (usdc_id, _) = testutils::create_token_contract(&e, &bombadil);
+        let (blnd_id, _) = testutils::create_blnd_token(&e, &pool_address, &bombadil);
+
+        let (backstop_token_id, _) = create_comet_lp_pool(&e, &bombadil, &blnd_id, &usdc_id);
+        let (backstop_address, backstop_client) =
+            testutils::create_backstop(&e, &pool_address, &backstop_token_id, &usdc_id, &blnd_id);
+        backstop_client.deposit(&bombadil, &pool_address, &(50 * SCALAR_7));
+        let (oracle_id, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.last_time = 12345;
+        reserve_data_0.backstop_credit = 200_0000000;
+        reserve_data_0.b_supply = 1000_0000000;
+        reserve_data_0.d_supply = 750_0000000;
+        reserve_config_0.index = 0;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(usdc_id),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 2_0000000, 1_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle: oracle_id,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            create_auction(
+                &e,
+                3,
+                &backstop_address,
+                &vec![&e, backstop_token_id],
+                &vec![&e, underlying_0],
+                100,
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1248)")]
+    fn test_create_auction_paused() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool_address = create_pool(&e);
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+
+        e.as_contract(&pool_address, || {
+            storage::set_auctions_paused(&e, true);
+
+            create_auction(
+                &e,
+                AuctionType::UserLiquidation as u32,
+                &samwise,
+                &vec![&e, underlying_0],
+                &vec![&e, underlying_1],
+                50,
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_create_auction_duplicate_bid() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+        e.cost_estimate().budget().reset_unlimited(); // setup exhausts budget
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 50,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool_address = create_pool(&e);
+
+        let (blnd, blnd_client) = testutils::create_blnd_token(&e, &pool_address, &bombadil);
+        let (usdc, usdc_client) = testutils::create_token_contract(&e, &bombadil);
+        let (lp_token, lp_token_client) =
+            testutils::create_comet_lp_pool(&e, &bombadil, &blnd, &usdc);
+        let (backstop_address, backstop_client) =
+            testutils::create_backstop(&e, &pool_address, &lp_token, &usdc, &blnd);
+        // mint lp tokens
+        blnd_client.mint(&samwise, &500_001_0000000);
+        blnd_client.approve(&samwise, &lp_token, &i128::MAX, &99999);
+        usdc_client.mint(&samwise, &12_501_0000000);
+        usdc_client.approve(&samwise, &lp_token, &i128::MAX, &99999);
+        lp_token_client.join_pool(
+            &50_000_0000000,
+            &vec![&e, 500_001_0000000, 12_501_0000000],
+            &samwise,
+        );
+        backstop_client.deposit(&samwise, &pool_address, &50_000_0000000);
+
+        let (oracle_id, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.d_rate = 1_100_000_000_000;
+        reserve_data_0.last_time = 12345;
+        reserve_config_0.index = 0;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.d_rate = 1_200_000_000_000;
+        reserve_data_1.last_time = 12345;
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+
+        let (underlying_2, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_2, mut reserve_data_2) = testutils::default_reserve_meta();
+        reserve_data_2.b_rate = 1_100_000_000_000;
         reserve_data_2.last_time = 12345;
         reserve_config_2.index = 1;
         testutils::create_reserve(
@@ -1077,6 +1612,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -1124,11 +1661,214 @@ mod tests {
         let (oracle_id, oracle_client) = testutils::create_mock_oracle(&e);
 
         let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
-        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
-        reserve_data_0.last_time = 12345;
-        reserve_data_0.backstop_credit = 100_0000000;
-        reserve_data_0.b_supply = 1000_0000000;
-        reserve_data_0.d_supply = 750_0000000;
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.last_time = 12345;
+        reserve_data_0.backstop_credit = 100_0000000;
+        reserve_data_0.b_supply = 1000_0000000;
+        reserve_data_0.d_supply = 750_0000000;
+        reserve_config_0.index = 0;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.last_time = 12345;
+        reserve_data_1.backstop_credit = 25_0000000;
+        reserve_data_1.b_supply = 250_0000000;
+        reserve_data_1.d_supply = 187_5000000;
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+
+        let (underlying_2, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_2, mut reserve_data_2) = testutils::default_reserve_meta();
+        reserve_data_2.b_rate = 1_100_000_000_000;
+        reserve_data_2.last_time = 12345;
+        reserve_config_2.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_2,
+            &reserve_config_2,
+            &reserve_data_2,
+        );
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+                Asset::Stellar(underlying_2),
+                Asset::Stellar(usdc_id),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 2_0000000, 4_0000000, 100_0000000, 1_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle: oracle_id,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            create_auction(
+                &e,
+                2,
+                &backstop_address,
+                &vec![&e, backstop_token_id],
+                &vec![&e, underlying_0.clone(), underlying_1, underlying_0],
+                100,
+            );
+        });
+    }
+
+    #[test]
+    fn test_delete_user_liquidation() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let pool_id = create_pool(&e);
+        let samwise = Address::generate(&e);
+
+        let auction_data = AuctionData {
+            bid: map![&e],
+            lot: map![&e],
+            block: 100,
+        };
+        e.as_contract(&pool_id, || {
+            storage::set_auction(
+                &e,
+                &(AuctionType::UserLiquidation as u32),
+                &samwise,
+                &auction_data,
+            );
+
+            delete_liquidation(&e, &samwise);
+            assert!(!storage::has_auction(
+                &e,
+                &(AuctionType::UserLiquidation as u32),
+                &samwise
+            ));
+        });
+    }
+
+    #[test]
+    fn test_auction_survives_past_shared_ttl() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 9999999,
+        });
+
+        let pool_id = create_pool(&e);
+        let samwise = Address::generate(&e);
+
+        let auction_data = AuctionData {
+            bid: map![&e],
+            lot: map![&e],
+            block: 100,
+        };
+        e.as_contract(&pool_id, || {
+            storage::set_auction(
+                &e,
+                &(AuctionType::UserLiquidation as u32),
+                &samwise,
+                &auction_data,
+            );
+        });
+
+        // reading the auction ~40 days later extends it past the original ~46 day ttl
+        e.ledger().set_sequence_number(100 + 17280 * 40);
+        e.as_contract(&pool_id, || {
+            let auction =
+                storage::get_auction(&e, &(AuctionType::UserLiquidation as u32), &samwise);
+            assert_eq!(auction.block, 100);
+        });
+
+        e.ledger().set_sequence_number(100 + 17280 * 80);
+        e.as_contract(&pool_id, || {
+            assert!(storage::has_auction(
+                &e,
+                &(AuctionType::UserLiquidation as u32),
+                &samwise
+            ));
+            delete_liquidation(&e, &samwise);
+            assert!(!storage::has_auction(
+                &e,
+                &(AuctionType::UserLiquidation as u32),
+                &samwise
+            ));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_delete_user_liquidation_does_not_exist() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool_id = create_pool(&e);
+
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&pool_id, || {
+            delete_liquidation(&e, &samwise);
+        });
+    }
+
+    #[test]
+    fn test_fill() {
+        let e = Env::default();
+
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 175,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 172800,
+            min_persistent_entry_ttl: 172800,
+            max_entry_ttl: 9999999,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+
+        let (oracle_address, oracle_client) = testutils::create_mock_oracle(&e);
+
+        // creating reserves for a pool exhausts the budget
+        e.cost_estimate().budget().reset_unlimited();
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_0, reserve_data_0) = testutils::default_reserve_meta();
         reserve_config_0.index = 0;
         testutils::create_reserve(
             &e,
@@ -1139,11 +1879,7 @@ mod tests {
         );
 
         let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
-        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
-        reserve_data_1.last_time = 12345;
-        reserve_data_1.backstop_credit = 25_0000000;
-        reserve_data_1.b_supply = 250_0000000;
-        reserve_data_1.d_supply = 187_5000000;
+        let (mut reserve_config_1, reserve_data_1) = testutils::default_reserve_meta();
         reserve_config_1.index = 1;
         testutils::create_reserve(
             &e,
@@ -1154,10 +1890,8 @@ mod tests {
         );
 
         let (underlying_2, _) = testutils::create_token_contract(&e, &bombadil);
-        let (mut reserve_config_2, mut reserve_data_2) = testutils::default_reserve_meta();
-        reserve_data_2.b_rate = 1_100_000_000_000;
-        reserve_data_2.last_time = 12345;
-        reserve_config_2.index = 1;
+        let (mut reserve_config_2, reserve_data_2) = testutils::default_reserve_meta();
+        reserve_config_2.index = 2;
         testutils::create_reserve(
             &e,
             &pool_address,
@@ -1165,7 +1899,17 @@ mod tests {
             &reserve_config_2,
             &reserve_data_2,
         );
+        e.cost_estimate().budget().reset_unlimited();
 
+        let auction_data = AuctionData {
+            bid: map![&e, (underlying_2.clone(), 1_2375000)],
+            lot: map![
+                &e,
+                (underlying_0.clone(), 30_5595329),
+                (underlying_1.clone(), 1_5395739)
+            ],
+            block: 176,
+        };
         oracle_client.set_data(
             &bombadil,
             &Asset::Other(Symbol::new(&e, "USD")),
@@ -1173,81 +1917,56 @@ mod tests {
                 &e,
                 Asset::Stellar(underlying_0.clone()),
                 Asset::Stellar(underlying_1.clone()),
-                Asset::Stellar(underlying_2),
-                Asset::Stellar(usdc_id),
+                Asset::Stellar(underlying_2.clone()),
             ],
             &7,
             &300,
         );
-        oracle_client.set_price_stable(&vec![&e, 2_0000000, 4_0000000, 100_0000000, 1_0000000]);
-
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 1_0000000, 1_0000000]);
         let pool_config = PoolConfig {
-            oracle: oracle_id,
+            oracle: oracle_address,
             min_collateral: 1_0000000,
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
-        e.as_contract(&pool_address, || {
-            storage::set_pool_config(&e, &pool_config);
-
-            create_auction(
+        let positions: Positions = Positions {
+            collateral: map![
                 &e,
-                2,
-                &backstop_address,
-                &vec![&e, backstop_token_id],
-                &vec![&e, underlying_0.clone(), underlying_1, underlying_0],
-                100,
-            );
-        });
-    }
-
-    #[test]
-    fn test_delete_user_liquidation() {
-        let e = Env::default();
-        e.mock_all_auths();
-
-        let pool_id = create_pool(&e);
-        let samwise = Address::generate(&e);
-
-        let auction_data = AuctionData {
-            bid: map![&e],
-            lot: map![&e],
-            block: 100,
+                (reserve_config_0.index, 90_9100000),
+                (reserve_config_1.index, 04_5800000),
+            ],
+            liabilities: map![&e, (reserve_config_2.index, 02_7500000),],
+            supply: map![&e],
         };
-        e.as_contract(&pool_id, || {
-            storage::set_auction(
-                &e,
-                &(AuctionType::UserLiquidation as u32),
-                &samwise,
-                &auction_data,
-            );
-
-            delete_liquidation(&e, &samwise);
-            assert!(!storage::has_auction(
-                &e,
-                &(AuctionType::UserLiquidation as u32),
-                &samwise
-            ));
-        });
-    }
-
-    #[test]
-    #[should_panic(expected = "Error(Contract, #1200)")]
-    fn test_delete_user_liquidation_does_not_exist() {
-        let e = Env::default();
-        e.mock_all_auths();
-        let pool_id = create_pool(&e);
-
-        let samwise = Address::generate(&e);
+        e.as_contract(&pool_address, || {
+            storage::set_user_positions(&e, &samwise, &positions);
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_auction(&e, &0, &samwise, &auction_data);
 
-        e.as_contract(&pool_id, || {
-            delete_liquidation(&e, &samwise);
+            e.ledger().set(LedgerInfo {
+                timestamp: 12345 + 200 * 5,
+                protocol_version: 22,
+                sequence_number: 176 + 200,
+                network_id: Default::default(),
+                base_reserve: 10,
+                min_temp_entry_ttl: 172800,
+                min_persistent_entry_ttl: 172800,
+                max_entry_ttl: 9999999,
+            });
+            e.cost_estimate().budget().reset_unlimited();
+            let mut pool = Pool::load(&e);
+            let mut frodo_state = User::load(&e, &frodo);
+            fill(&e, &mut pool, 0, &samwise, &mut frodo_state, 100);
+            let has_auction = storage::has_auction(&e, &0, &samwise);
+            assert_eq!(has_auction, false);
         });
     }
 
     #[test]
-    fn test_fill() {
+    fn test_partial_fill() {
         let e = Env::default();
 
         e.mock_all_auths();
@@ -1268,7 +1987,7 @@ mod tests {
 
         let pool_address = create_pool(&e);
 
-        let (oracle_address, _) = testutils::create_mock_oracle(&e);
+        let (oracle_address, oracle_client) = testutils::create_mock_oracle(&e);
 
         // creating reserves for a pool exhausts the budget
         e.cost_estimate().budget().reset_unlimited();
@@ -1315,12 +2034,27 @@ mod tests {
             ],
             block: 176,
         };
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+                Asset::Stellar(underlying_2.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 1_0000000, 1_0000000]);
         let pool_config = PoolConfig {
             oracle: oracle_address,
             min_collateral: 1_0000000,
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         let positions: Positions = Positions {
             collateral: map![
@@ -1349,14 +2083,26 @@ mod tests {
             e.cost_estimate().budget().reset_unlimited();
             let mut pool = Pool::load(&e);
             let mut frodo_state = User::load(&e, &frodo);
-            fill(&e, &mut pool, 0, &samwise, &mut frodo_state, 100);
-            let has_auction = storage::has_auction(&e, &0, &samwise);
-            assert_eq!(has_auction, false);
+            fill(&e, &mut pool, 0, &samwise, &mut frodo_state, 25);
+
+            let expected_new_auction_data = AuctionData {
+                bid: map![&e, (underlying_2.clone(), 9281250)],
+                lot: map![
+                    &e,
+                    (underlying_0.clone(), 22_9196497),
+                    (underlying_1.clone(), 1_1546805)
+                ],
+                block: 176,
+            };
+            let new_auction = storage::get_auction(&e, &0, &samwise);
+            assert_eq!(new_auction.bid, expected_new_auction_data.bid);
+            assert_eq!(new_auction.lot, expected_new_auction_data.lot);
+            assert_eq!(new_auction.block, expected_new_auction_data.block);
         });
     }
 
     #[test]
-    fn test_partial_fill() {
+    fn test_fill_temporary_auction() {
         let e = Env::default();
 
         e.mock_all_auths();
@@ -1377,7 +2123,7 @@ mod tests {
 
         let pool_address = create_pool(&e);
 
-        let (oracle_address, _) = testutils::create_mock_oracle(&e);
+        let (oracle_address, oracle_client) = testutils::create_mock_oracle(&e);
 
         // creating reserves for a pool exhausts the budget
         e.cost_estimate().budget().reset_unlimited();
@@ -1424,12 +2170,27 @@ mod tests {
             ],
             block: 176,
         };
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+                Asset::Stellar(underlying_2.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 1_0000000, 1_0000000]);
         let pool_config = PoolConfig {
             oracle: oracle_address,
             min_collateral: 1_0000000,
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         let positions: Positions = Positions {
             collateral: map![
@@ -1441,38 +2202,184 @@ mod tests {
             supply: map![&e],
         };
         e.as_contract(&pool_address, || {
-            storage::set_user_positions(&e, &samwise, &positions);
-            storage::set_pool_config(&e, &pool_config);
-            storage::set_auction(&e, &0, &samwise, &auction_data);
+            storage::set_user_positions(&e, &samwise, &positions);
+            storage::set_pool_config(&e, &pool_config);
+            // an auction created before auctions moved to persistent storage
+            let key = storage::PoolDataKey::Auction(storage::AuctionKey {
+                user: samwise.clone(),
+                auct_type: 0,
+            });
+            e.storage().temporary().set(&key, &auction_data);
+            assert!(storage::has_auction(&e, &0, &samwise));
+
+            e.ledger().set(LedgerInfo {
+                timestamp: 12345 + 200 * 5,
+                protocol_version: 22,
+                sequence_number: 176 + 200,
+                network_id: Default::default(),
+                base_reserve: 10,
+                min_temp_entry_ttl: 172800,
+                min_persistent_entry_ttl: 172800,
+                max_entry_ttl: 9999999,
+            });
+            e.cost_estimate().budget().reset_unlimited();
+            let mut pool = Pool::load(&e);
+            let mut frodo_state = User::load(&e, &frodo);
+            fill(&e, &mut pool, 0, &samwise, &mut frodo_state, 25);
+
+            let expected_new_auction_data = AuctionData {
+                bid: map![&e, (underlying_2.clone(), 9281250)],
+                lot: map![
+                    &e,
+                    (underlying_0.clone(), 22_9196497),
+                    (underlying_1.clone(), 1_1546805)
+                ],
+                block: 176,
+            };
+            let new_auction = storage::get_auction(&e, &0, &samwise);
+            assert_eq!(new_auction.bid, expected_new_auction_data.bid);
+            assert_eq!(new_auction.lot, expected_new_auction_data.lot);
+            assert_eq!(new_auction.block, expected_new_auction_data.block);
+            // the partially filled auction is moved to persistent storage
+            assert!(e.storage().persistent().has(&key));
+            assert!(!e.storage().temporary().has(&key));
+
+            fill(&e, &mut pool, 0, &samwise, &mut frodo_state, 100);
+            assert!(!storage::has_auction(&e, &0, &samwise));
+        });
+    }
+
+    /// Setup a liquidation of samwise at a 1.02 health factor, with an auction of 60 bTokens of
+    /// underlying_0 for 40 dTokens of underlying_1 that has fully scaled in, and a liquidation target
+    /// health factor of 1.1.
+    ///
+    /// Returns (pool, samwise, frodo, underlying_0, underlying_1)
+    fn setup_target_hf_fill(e: &Env) -> (Address, Address, Address, Address, Address) {
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 376,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 172800,
+            min_persistent_entry_ttl: 172800,
+            max_entry_ttl: 9999999,
+        });
+
+        let bombadil = Address::generate(e);
+        let samwise = Address::generate(e);
+        let frodo = Address::generate(e);
+        let pool_address = create_pool(e);
+        let (oracle_address, oracle_client) = testutils::create_mock_oracle(e);
+
+        e.cost_estimate().budget().reset_unlimited();
+        let (underlying_0, _) = testutils::create_token_contract(e, &bombadil);
+        let (reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.last_time = 12345;
+        testutils::create_reserve(
+            e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+
+        let (underlying_1, _) = testutils::create_token_contract(e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_config_1.index = 1;
+        reserve_data_1.last_time = 12345;
+        testutils::create_reserve(
+            e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+        e.cost_estimate().budget().reset_unlimited();
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(e, "USD")),
+            &vec![
+                e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![e, 1_0000000, 1_0000000]);
+        let pool_config = PoolConfig {
+            oracle: oracle_address,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        let positions = Positions {
+            collateral: map![e, (0, 100_0000000)],
+            liabilities: map![e, (1, 55_0000000)],
+            supply: map![e],
+        };
+        let auction_data = AuctionData {
+            bid: map![e, (underlying_1.clone(), 40_0000000)],
+            lot: map![e, (underlying_0.clone(), 60_0000000)],
+            block: 176,
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_pool_config(e, &pool_config);
+            storage::set_auction_config(
+                e,
+                &AuctionConfig {
+                    liq_target_hf: 1_1000000,
+                    ..storage::get_auction_config(e)
+                },
+            );
+            storage::set_user_positions(e, &samwise, &positions);
+            storage::set_auction(e, &0, &samwise, &auction_data);
+        });
+        (pool_address, samwise, frodo, underlying_0, underlying_1)
+    }
 
-            e.ledger().set(LedgerInfo {
-                timestamp: 12345 + 200 * 5,
-                protocol_version: 22,
-                sequence_number: 176 + 200,
-                network_id: Default::default(),
-                base_reserve: 10,
-                min_temp_entry_ttl: 172800,
-                min_persistent_entry_ttl: 172800,
-                max_entry_ttl: 9999999,
-            });
-            e.cost_estimate().budget().reset_unlimited();
+    #[test]
+    fn test_partial_fill_capped_at_target_hf() {
+        let e = Env::default();
+        let (pool_address, samwise, frodo, underlying_0, underlying_1) = setup_target_hf_fill(&e);
+
+        e.as_contract(&pool_address, || {
             let mut pool = Pool::load(&e);
             let mut frodo_state = User::load(&e, &frodo);
-            fill(&e, &mut pool, 0, &samwise, &mut frodo_state, 25);
+            // a 42% fill restores samwise to a 1.1 health factor, so the 60% fill is capped
+            let filled = fill(&e, &mut pool, 0, &samwise, &mut frodo_state, 60);
+            assert_eq!(filled.lot, map![&e, (underlying_0.clone(), 25_2000000)]);
+            assert_eq!(filled.bid, map![&e, (underlying_1.clone(), 16_8000000)]);
 
-            let expected_new_auction_data = AuctionData {
-                bid: map![&e, (underlying_2.clone(), 9281250)],
-                lot: map![
-                    &e,
-                    (underlying_0.clone(), 22_9196497),
-                    (underlying_1.clone(), 1_1546805)
-                ],
-                block: 176,
-            };
-            let new_auction = storage::get_auction(&e, &0, &samwise);
-            assert_eq!(new_auction.bid, expected_new_auction_data.bid);
-            assert_eq!(new_auction.lot, expected_new_auction_data.lot);
-            assert_eq!(new_auction.block, expected_new_auction_data.block);
+            // the capped fill ends the auction, and the rest of the lot stays with samwise
+            assert!(!storage::has_auction(&e, &0, &samwise));
+            let samwise_positions = storage::get_user_positions(&e, &samwise);
+            assert_eq!(samwise_positions.collateral.get_unchecked(0), 74_8000000);
+            assert_eq!(samwise_positions.liabilities.get_unchecked(1), 38_2000000);
+        });
+    }
+
+    #[test]
+    fn test_partial_fill_under_target_hf() {
+        let e = Env::default();
+        let (pool_address, samwise, frodo, underlying_0, underlying_1) = setup_target_hf_fill(&e);
+
+        e.as_contract(&pool_address, || {
+            let mut pool = Pool::load(&e);
+            let mut frodo_state = User::load(&e, &frodo);
+            let filled = fill(&e, &mut pool, 0, &samwise, &mut frodo_state, 30);
+            assert_eq!(filled.lot, map![&e, (underlying_0.clone(), 18_0000000)]);
+            assert_eq!(filled.bid, map![&e, (underlying_1.clone(), 12_0000000)]);
+
+            let remaining = storage::get_auction(&e, &0, &samwise);
+            assert_eq!(remaining.lot, map![&e, (underlying_0.clone(), 42_0000000)]);
+            assert_eq!(remaining.bid, map![&e, (underlying_1.clone(), 28_0000000)]);
         });
     }
 
@@ -1499,7 +2406,7 @@ mod tests {
 
         let pool_address = create_pool(&e);
 
-        let (oracle_address, _) = testutils::create_mock_oracle(&e);
+        let (oracle_address, oracle_client) = testutils::create_mock_oracle(&e);
 
         let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
         let (mut reserve_config_0, reserve_data_0) = testutils::default_reserve_meta();
@@ -1546,12 +2453,27 @@ mod tests {
             ],
             block: 176,
         };
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+                Asset::Stellar(underlying_2.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 1_0000000, 1_0000000]);
         let pool_config = PoolConfig {
             oracle: oracle_address,
             min_collateral: 1_0000000,
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         let positions: Positions = Positions {
             collateral: map![
@@ -1742,6 +2664,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         let positions: Positions = Positions {
             collateral: map![
@@ -1865,6 +2789,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         let positions: Positions = Positions {
             collateral: map![
@@ -1986,6 +2912,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         let positions: Positions = Positions {
             collateral: map![
@@ -2056,6 +2984,62 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_get_user_auctions() {
+        let e = Env::default();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 1500,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 172800,
+            min_persistent_entry_ttl: 172800,
+            max_entry_ttl: 9999999,
+        });
+
+        let pool_address = create_pool(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let underlying_0 = Address::generate(&e);
+        let underlying_1 = Address::generate(&e);
+
+        let liq_auction = AuctionData {
+            bid: map![&e, (underlying_0.clone(), 10_0000000)],
+            lot: map![&e, (underlying_1.clone(), 12_0000000)],
+            block: 1000,
+        };
+        let interest_auction = AuctionData {
+            bid: map![&e, (underlying_1.clone(), 5_0000000)],
+            lot: map![&e, (underlying_0.clone(), 1_0000000)],
+            block: 1200,
+        };
+        e.as_contract(&pool_address, || {
+            assert_eq!(get_user_auctions(&e, &samwise).len(), 0);
+
+            storage::set_auction(&e, &2, &samwise, &interest_auction);
+            storage::set_auction(&e, &0, &samwise, &liq_auction);
+            storage::set_auction(&e, &1, &frodo, &liq_auction);
+
+            let auctions = get_user_auctions(&e, &samwise);
+            assert_eq!(auctions.len(), 2);
+            let (auction_type, auction) = auctions.get_unchecked(0);
+            assert_eq!(auction_type, 0);
+            assert_eq!(auction.bid, liq_auction.bid);
+            assert_eq!(auction.lot, liq_auction.lot);
+            assert_eq!(auction.block, 1000);
+            let (auction_type, auction) = auctions.get_unchecked(1);
+            assert_eq!(auction_type, 2);
+            assert_eq!(auction.bid, interest_auction.bid);
+            assert_eq!(auction.lot, interest_auction.lot);
+            assert_eq!(auction.block, 1200);
+
+            let auctions = get_user_auctions(&e, &frodo);
+            assert_eq!(auctions.len(), 1);
+            assert_eq!(auctions.get_unchecked(0).0, 1);
+        });
+    }
+
     // #[test]
     // fn test_delete_stale_auction_bad_debt() {
     //     let e = Env::default();
@@ -2585,7 +3569,8 @@ mod tests {
             min_persistent_entry_ttl: 172800,
             max_entry_ttl: 9999999,
         });
-        let (scaled_auction, remaining_auction) = scale_auction(&e, &base_auction_data, 100);
+        let (scaled_auction, remaining_auction) =
+            scale_auction(&e, &base_auction_data, 100, AuctionCurve::Linear, 0);
         assert_eq!(
             scaled_auction.bid.get_unchecked(underlying_0.clone()),
             100_0000000
@@ -2604,7 +3589,8 @@ mod tests {
             min_persistent_entry_ttl: 172800,
             max_entry_ttl: 9999999,
         });
-        let (scaled_auction, remaining_auction) = scale_auction(&e, &base_auction_data, 100);
+        let (scaled_auction, remaining_auction) =
+            scale_auction(&e, &base_auction_data, 100, AuctionCurve::Linear, 0);
         assert_eq!(
             scaled_auction.bid.get_unchecked(underlying_0.clone()),
             100_0000000
@@ -2626,7 +3612,8 @@ mod tests {
             min_persistent_entry_ttl: 172800,
             max_entry_ttl: 9999999,
         });
-        let (scaled_auction, remaining_auction) = scale_auction(&e, &base_auction_data, 100);
+        let (scaled_auction, remaining_auction) =
+            scale_auction(&e, &base_auction_data, 100, AuctionCurve::Linear, 0);
         assert_eq!(
             scaled_auction.bid.get_unchecked(underlying_0.clone()),
             100_0000000
@@ -2648,7 +3635,8 @@ mod tests {
             min_persistent_entry_ttl: 172800,
             max_entry_ttl: 9999999,
         });
-        let (scaled_auction, remaining_auction) = scale_auction(&e, &base_auction_data, 100);
+        let (scaled_auction, remaining_auction) =
+            scale_auction(&e, &base_auction_data, 100, AuctionCurve::Linear, 0);
         assert_eq!(
             scaled_auction.bid.get_unchecked(underlying_0.clone()),
             50_0000000
@@ -2670,7 +3658,8 @@ mod tests {
             min_persistent_entry_ttl: 172800,
             max_entry_ttl: 9999999,
         });
-        let (scaled_auction, remaining_auction) = scale_auction(&e, &base_auction_data, 100);
+        let (scaled_auction, remaining_auction) =
+            scale_auction(&e, &base_auction_data, 100, AuctionCurve::Linear, 0);
         assert_eq!(scaled_auction.bid.len(), 0);
         assert_eq!(
             scaled_auction.lot.get_unchecked(underlying_1.clone()),
@@ -2679,6 +3668,94 @@ mod tests {
         assert!(remaining_auction.is_none());
     }
 
+    #[test]
+    fn test_scale_auction_stepwise_curve() {
+        let e = Env::default();
+        let underlying_0 = Address::generate(&e);
+        let underlying_1 = Address::generate(&e);
+
+        let base_auction_data = AuctionData {
+            bid: map![&e, (underlying_0.clone(), 100_0000000)],
+            lot: map![&e, (underlying_1.clone(), 100_0000000)],
+            block: 1000,
+        };
+
+        // 119 blocks - lot holds at the 50% step
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 1119,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 172800,
+            min_persistent_entry_ttl: 172800,
+            max_entry_ttl: 9999999,
+        });
+        let (scaled_auction, remaining_auction) =
+            scale_auction(&e, &base_auction_data, 100, AuctionCurve::Stepwise, 0);
+        assert_eq!(
+            scaled_auction.bid.get_unchecked(underlying_0.clone()),
+            100_0000000
+        );
+        assert_eq!(
+            scaled_auction.lot.get_unchecked(underlying_1.clone()),
+            50_0000000
+        );
+        assert!(remaining_auction.is_none());
+
+        // 240 blocks - bid drops by two steps
+        e.ledger().set_sequence_number(1240);
+        let (scaled_auction, remaining_auction) =
+            scale_auction(&e, &base_auction_data, 100, AuctionCurve::Stepwise, 0);
+        assert_eq!(
+            scaled_auction.bid.get_unchecked(underlying_0.clone()),
+            80_0000000
+        );
+        assert_eq!(
+            scaled_auction.lot.get_unchecked(underlying_1.clone()),
+            100_0000000
+        );
+        assert!(remaining_auction.is_none());
+    }
+
+    #[test]
+    fn test_auction_type_curve() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool_address = create_pool(&e);
+
+        e.as_contract(&pool_address, || {
+            assert_eq!(AuctionType::UserLiquidation.curve(&e), AuctionCurve::Linear);
+
+            storage::set_auction_config(
+                &e,
+                &AuctionConfig {
+                    start_delay: 0,
+                    soft_liq_max_value: 0,
+                    soft_liq_bonus: 0,
+                    interest_interval: 0,
+                    interest_min_value: 0,
+                    bad_debt_premium_cap: 0,
+                    bad_debt_bid_premium: 0,
+                    liq_curve: 1,
+                    bad_debt_curve: 0,
+                    interest_curve: 2,
+                    min_fill_pct: 0,
+                    liq_target_hf: 0,
+                },
+            );
+            assert_eq!(
+                AuctionType::UserLiquidation.curve(&e),
+                AuctionCurve::Exponential
+            );
+            assert_eq!(AuctionType::BadDebtAuction.curve(&e), AuctionCurve::Linear);
+            assert_eq!(
+                AuctionType::InterestAuction.curve(&e),
+                AuctionCurve::Stepwise
+            );
+        });
+    }
+
     #[test]
     fn test_scale_auction_not_100_fill_pct() {
         // @dev: bids always round up, lots always round down
@@ -2704,7 +3781,8 @@ mod tests {
             min_persistent_entry_ttl: 172800,
             max_entry_ttl: 9999999,
         });
-        let (scaled_auction, remaining_auction_option) = scale_auction(&e, &base_auction_data, 50);
+        let (scaled_auction, remaining_auction_option) =
+            scale_auction(&e, &base_auction_data, 50, AuctionCurve::Linear, 0);
         let remaining_auction = remaining_auction_option.unwrap();
         assert_eq!(
             scaled_auction.bid.get_unchecked(underlying_0.clone()),
@@ -2732,7 +3810,8 @@ mod tests {
             max_entry_ttl: 9999999,
         });
 
-        let (scaled_auction, remaining_auction_option) = scale_auction(&e, &base_auction_data, 60);
+        let (scaled_auction, remaining_auction_option) =
+            scale_auction(&e, &base_auction_data, 60, AuctionCurve::Linear, 0);
         let remaining_auction = remaining_auction_option.unwrap();
         assert_eq!(
             scaled_auction.bid.get_unchecked(underlying_0.clone()),
@@ -2763,7 +3842,8 @@ mod tests {
             max_entry_ttl: 9999999,
         });
 
-        let (scaled_auction, remaining_auction_option) = scale_auction(&e, &base_auction_data, 60);
+        let (scaled_auction, remaining_auction_option) =
+            scale_auction(&e, &base_auction_data, 60, AuctionCurve::Linear, 0);
         let remaining_auction = remaining_auction_option.unwrap();
         assert_eq!(
             scaled_auction.bid.get_unchecked(underlying_0.clone()),
@@ -2793,7 +3873,8 @@ mod tests {
             min_persistent_entry_ttl: 172800,
             max_entry_ttl: 9999999,
         });
-        let (scaled_auction, remaining_auction_option) = scale_auction(&e, &base_auction_data, 50);
+        let (scaled_auction, remaining_auction_option) =
+            scale_auction(&e, &base_auction_data, 50, AuctionCurve::Linear, 0);
         let remaining_auction = remaining_auction_option.unwrap();
         assert_eq!(scaled_auction.bid.len(), 0);
         assert_eq!(
@@ -2835,7 +3916,7 @@ mod tests {
             max_entry_ttl: 9999999,
         });
 
-        let (_, _) = scale_auction(&e, &base_auction_data, 0);
+        let (_, _) = scale_auction(&e, &base_auction_data, 0, AuctionCurve::Linear, 0);
     }
 
     #[test]
@@ -2863,7 +3944,143 @@ mod tests {
             max_entry_ttl: 9999999,
         });
 
-        let (_, _) = scale_auction(&e, &base_auction_data, 101);
+        let (_, _) = scale_auction(&e, &base_auction_data, 101, AuctionCurve::Linear, 0);
+    }
+
+    #[test]
+    fn test_scale_auction_min_fill_pct() {
+        let e = Env::default();
+        let underlying_0 = Address::generate(&e);
+        let underlying_1 = Address::generate(&e);
+
+        let base_auction_data = AuctionData {
+            bid: map![&e, (underlying_0.clone(), 100_0000000)],
+            lot: map![&e, (underlying_1.clone(), 100_0000000)],
+            block: 1000,
+        };
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 1100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 172800,
+            min_persistent_entry_ttl: 172800,
+            max_entry_ttl: 9999999,
+        });
+
+        // fills at the minimum and full fills are allowed
+        let (scaled_auction, _) = scale_auction(&e, &base_auction_data, 5, AuctionCurve::Linear, 5);
+        assert_eq!(
+            scaled_auction.bid.get_unchecked(underlying_0.clone()),
+            5_0000000
+        );
+        let (_, remaining_auction) =
+            scale_auction(&e, &base_auction_data, 100, AuctionCurve::Linear, 5);
+        assert!(remaining_auction.is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_scale_auction_below_min_fill_pct() {
+        let e = Env::default();
+        let underlying_0 = Address::generate(&e);
+        let underlying_1 = Address::generate(&e);
+
+        let base_auction_data = AuctionData {
+            bid: map![&e, (underlying_0.clone(), 100_0000000)],
+            lot: map![&e, (underlying_1.clone(), 100_0000000)],
+            block: 1000,
+        };
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 1100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 172800,
+            min_persistent_entry_ttl: 172800,
+            max_entry_ttl: 9999999,
+        });
+
+        scale_auction(&e, &base_auction_data, 4, AuctionCurve::Linear, 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1225)")]
+    fn test_scale_auction_before_start() {
+        let e = Env::default();
+        let underlying_0 = Address::generate(&e);
+        let underlying_1 = Address::generate(&e);
+
+        let base_auction_data = AuctionData {
+            bid: map![&e, (underlying_0.clone(), 25_0000005)],
+            lot: map![&e, (underlying_1.clone(), 25_0000005)],
+            block: 1000,
+        };
+
+        // -1 blocks
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 999,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 172800,
+            min_persistent_entry_ttl: 172800,
+            max_entry_ttl: 9999999,
+        });
+
+        let (_, _) = scale_auction(&e, &base_auction_data, 100, AuctionCurve::Linear, 0);
+    }
+
+    #[test]
+    fn test_scale_auction_large_amounts() {
+        let e = Env::default();
+        let underlying_0 = Address::generate(&e);
+        let underlying_1 = Address::generate(&e);
+
+        // amounts whose intermediate products overflow i128
+        let amount = 10i128.pow(33);
+        let base_auction_data = AuctionData {
+            bid: map![&e, (underlying_0.clone(), amount)],
+            lot: map![&e, (underlying_1.clone(), amount)],
+            block: 1000,
+        };
+
+        // 100 blocks
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 1100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 172800,
+            min_persistent_entry_ttl: 172800,
+            max_entry_ttl: 9999999,
+        });
+
+        let (scaled_auction, remaining_auction_option) =
+            scale_auction(&e, &base_auction_data, 50, AuctionCurve::Linear, 0);
+        assert_eq!(
+            scaled_auction.bid.get_unchecked(underlying_0.clone()),
+            amount / 2
+        );
+        assert_eq!(
+            scaled_auction.lot.get_unchecked(underlying_1.clone()),
+            amount / 4
+        );
+        let remaining_auction = remaining_auction_option.unwrap();
+        assert_eq!(
+            remaining_auction.bid.get_unchecked(underlying_0.clone()),
+            amount / 2
+        );
+        assert_eq!(
+            remaining_auction.lot.get_unchecked(underlying_1.clone()),
+            amount / 2
+        );
     }
 
     #[test]
@@ -2891,7 +4108,8 @@ mod tests {
             min_persistent_entry_ttl: 172800,
             max_entry_ttl: 9999999,
         });
-        let (scaled_auction, remaining_auction_option) = scale_auction(&e, &base_auction_data, 99);
+        let (scaled_auction, remaining_auction_option) =
+            scale_auction(&e, &base_auction_data, 99, AuctionCurve::Linear, 0);
         assert_eq!(scaled_auction.bid.get_unchecked(underlying_0.clone()), 1);
         assert_eq!(scaled_auction.lot.len(), 0);
         let remaining_auction = remaining_auction_option.unwrap();
@@ -2910,12 +4128,14 @@ mod tests {
             max_entry_ttl: 9999999,
         });
 
-        let (scaled_auction, remaining_auction_option) = scale_auction(&e, &base_auction_data, 100);
+        let (scaled_auction, remaining_auction_option) =
+            scale_auction(&e, &base_auction_data, 100, AuctionCurve::Linear, 0);
         assert_eq!(scaled_auction.bid.get_unchecked(underlying_0.clone()), 1);
         assert_eq!(scaled_auction.lot.len(), 0);
         assert!(remaining_auction_option.is_none());
 
-        let (scaled_auction, remaining_auction_option) = scale_auction(&e, &base_auction_data, 99);
+        let (scaled_auction, remaining_auction_option) =
+            scale_auction(&e, &base_auction_data, 99, AuctionCurve::Linear, 0);
         assert_eq!(scaled_auction.bid.get_unchecked(underlying_0.clone()), 1);
         assert_eq!(scaled_auction.lot.len(), 0);
         let remaining_auction = remaining_auction_option.unwrap();
@@ -2934,7 +4154,8 @@ mod tests {
             max_entry_ttl: 9999999,
         });
 
-        let (scaled_auction, remaining_auction_option) = scale_auction(&e, &base_auction_data, 99);
+        let (scaled_auction, remaining_auction_option) =
+            scale_auction(&e, &base_auction_data, 99, AuctionCurve::Linear, 0);
         assert_eq!(scaled_auction.bid.get_unchecked(underlying_0.clone()), 1);
         assert_eq!(scaled_auction.lot.len(), 0);
         let remaining_auction = remaining_auction_option.unwrap();
@@ -2953,12 +4174,14 @@ mod tests {
             max_entry_ttl: 9999999,
         });
 
-        let (scaled_auction, remaining_auction_option) = scale_auction(&e, &base_auction_data, 100);
+        let (scaled_auction, remaining_auction_option) =
+            scale_auction(&e, &base_auction_data, 100, AuctionCurve::Linear, 0);
         assert_eq!(scaled_auction.bid.get_unchecked(underlying_0.clone()), 1);
         assert_eq!(scaled_auction.lot.get_unchecked(underlying_1.clone()), 1);
         assert!(remaining_auction_option.is_none());
 
-        let (scaled_auction, remaining_auction_option) = scale_auction(&e, &base_auction_data, 99);
+        let (scaled_auction, remaining_auction_option) =
+            scale_auction(&e, &base_auction_data, 99, AuctionCurve::Linear, 0);
         assert_eq!(scaled_auction.bid.get_unchecked(underlying_0.clone()), 1);
         assert_eq!(scaled_auction.lot.len(), 0);
         let remaining_auction = remaining_auction_option.unwrap();
@@ -2976,7 +4199,8 @@ mod tests {
             min_persistent_entry_ttl: 172800,
             max_entry_ttl: 9999999,
         });
-        let (scaled_auction, remaining_auction_option) = scale_auction(&e, &base_auction_data, 99);
+        let (scaled_auction, remaining_auction_option) =
+            scale_auction(&e, &base_auction_data, 99, AuctionCurve::Linear, 0);
         assert_eq!(scaled_auction.bid.get_unchecked(underlying_0.clone()), 1);
         assert_eq!(scaled_auction.lot.len(), 0);
         let remaining_auction = remaining_auction_option.unwrap();
@@ -2994,7 +4218,8 @@ mod tests {
             min_persistent_entry_ttl: 172800,
             max_entry_ttl: 9999999,
         });
-        let (scaled_auction, remaining_auction_option) = scale_auction(&e, &base_auction_data, 99);
+        let (scaled_auction, remaining_auction_option) =
+            scale_auction(&e, &base_auction_data, 99, AuctionCurve::Linear, 0);
         assert_eq!(scaled_auction.bid.len(), 0);
         assert_eq!(scaled_auction.lot.len(), 0);
         let remaining_auction = remaining_auction_option.unwrap();
@@ -3002,7 +4227,8 @@ mod tests {
         assert_eq!(remaining_auction.lot.get_unchecked(underlying_1.clone()), 1);
 
         // with 100 fill pct
-        let (scaled_auction, remaining_auction_option) = scale_auction(&e, &base_auction_data, 100);
+        let (scaled_auction, remaining_auction_option) =
+            scale_auction(&e, &base_auction_data, 100, AuctionCurve::Linear, 0);
         assert_eq!(scaled_auction.bid.len(), 0);
         assert_eq!(scaled_auction.lot.get_unchecked(underlying_1.clone()), 1);
         assert!(remaining_auction_option.is_none());
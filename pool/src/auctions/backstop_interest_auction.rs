@@ -1,12 +1,10 @@
-use crate::{
-    constants::SCALAR_7, dependencies::BackstopClient, errors::PoolError, pool::Pool, storage,
-};
+use crate::{constants::SCALAR_7, dependencies::Backstop, errors::PoolError, pool::Pool, storage};
 use cast::i128;
 use sep_41_token::TokenClient;
 use soroban_fixed_point_math::SorobanFixedPoint;
-use soroban_sdk::{map, panic_with_error, Address, Env, Vec};
+use soroban_sdk::{map, panic_with_error, Address, Env, Map, Vec};
 
-use super::{AuctionData, AuctionType};
+use super::{backstop_token_price::load_backstop_token_price, AuctionData, AuctionType};
 
 pub fn create_interest_auction_data(
     e: &Env,
@@ -14,6 +12,49 @@ pub fn create_interest_auction_data(
     bid: &Vec<Address>,
     lot: &Vec<Address>,
     percent: u32,
+) -> AuctionData {
+    build_interest_auction_data(e, user, bid, &to_full_lot(e, lot), percent, false)
+}
+
+/// Create an interest auction that auctions at most the given amount of each reserve's backstop
+/// credit, so part of the credit can be left on the reserve as a buffer.
+///
+/// ### Arguments
+/// * `bid` - The assets being bid on
+/// * `lot` - A map of the assets being auctioned off to the maximum amount of each to auction
+pub fn create_partial_interest_auction_data(
+    e: &Env,
+    bid: &Vec<Address>,
+    lot: &Map<Address, i128>,
+) -> AuctionData {
+    for (_, max_amount) in lot.iter() {
+        if max_amount <= 0 {
+            panic_with_error!(e, PoolError::BadRequest);
+        }
+    }
+    build_interest_auction_data(e, &storage::get_backstop(e), bid, lot, 100, false)
+}
+
+/// Map each lot asset to an unbounded maximum lot amount, so its entire backstop credit is auctioned
+pub fn to_full_lot(e: &Env, lot: &Vec<Address>) -> Map<Address, i128> {
+    let mut full_lot = map![e];
+    for asset in lot.iter() {
+        full_lot.set(asset, i128::MAX);
+    }
+    full_lot
+}
+
+/// Build the auction data for an interest auction. Each lot asset auctions its backstop credit, up to
+/// the maximum amount given for the asset. A preview does not require the backstop to have no interest
+/// auction in progress, the interest auction interval to have passed, or the interest to be over the
+/// minimum value, and does not record the auction's creation.
+pub fn build_interest_auction_data(
+    e: &Env,
+    user: &Address,
+    bid: &Vec<Address>,
+    lot: &Map<Address, i128>,
+    percent: u32,
+    preview: bool,
 ) -> AuctionData {
     let backstop = storage::get_backstop(e);
     if user != &backstop {
@@ -22,8 +63,17 @@ pub fn create_interest_auction_data(
     if percent != 100 {
         panic_with_error!(e, PoolError::BadRequest);
     }
-    if storage::has_auction(e, &(AuctionType::InterestAuction as u32), &backstop) {
-        panic_with_error!(e, PoolError::AuctionInProgress);
+    let auction_config = storage::get_auction_config(e);
+    if !preview {
+        if storage::has_auction(e, &(AuctionType::InterestAuction as u32), &backstop) {
+            panic_with_error!(e, PoolError::AuctionInProgress);
+        }
+        let last_created = storage::get_last_interest_auction(e);
+        if last_created != 0
+            && e.ledger().sequence() < last_created + auction_config.interest_interval
+        {
+            panic_with_error!(e, PoolError::InterestAuctionTooSoon);
+        }
     }
 
     let mut pool = Pool::load(e);
@@ -40,20 +90,15 @@ pub fn create_interest_auction_data(
 
     // validate and create lot auction data
     let mut interest_value = 0; // expressed in the oracle's decimals
-    for lot_asset in lot {
+    for (lot_asset, max_amount) in lot.iter() {
         // don't store updated reserve data back to ledger. This will occur on the the auction's fill.
         // `load_reserve` will panic if the reserve does not exist
         let reserve = pool.load_reserve(e, &lot_asset, false);
-        if reserve.data.backstop_credit > 0 {
+        let lot_amount = reserve.data.backstop_credit.min(max_amount);
+        if lot_amount > 0 {
             let asset_to_base = pool.load_price(e, &reserve.asset);
-            interest_value += i128(asset_to_base).fixed_mul_floor(
-                e,
-                &reserve.data.backstop_credit,
-                &reserve.scalar,
-            );
-            auction_data
-                .lot
-                .set(reserve.asset, reserve.data.backstop_credit);
+            interest_value += i128(asset_to_base).fixed_mul_floor(e, &lot_amount, &reserve.scalar);
+            auction_data.lot.set(reserve.asset, lot_amount);
         }
     }
 
@@ -61,25 +106,33 @@ pub fn create_interest_auction_data(
         panic_with_error!(e, PoolError::InvalidLot);
     }
 
-    // Ensure that the interest value is at least 200 USDC
-    if interest_value < 200 * oracle_scalar {
+    // Ensure that the interest value is at least 200 USDC, and at least the configured minimum
+    let interest_value_usd = pool.load_usd_value(e, interest_value);
+    if !preview
+        && (interest_value_usd < 200 * oracle_scalar
+            || interest_value < auction_config.interest_min_value)
+    {
         panic_with_error!(e, PoolError::InterestTooSmall);
     }
 
     // validate and create bid auction data
-    let backstop_client = BackstopClient::new(e, &backstop);
-    let backstop_token = backstop_client.backstop_token();
+    let backstop_client = Backstop::load(e);
+    let backstop_token = backstop_client.token();
     if bid.len() != 1 || bid.get_unchecked(0) != backstop_token {
         panic_with_error!(e, PoolError::InvalidBid);
     }
 
-    let pool_backstop_data = backstop_client.pool_data(&e.current_contract_address());
+    let pool_backstop_data = backstop_client.pool_data();
+    let token_price = load_backstop_token_price(e, &mut pool, &pool_backstop_data, false);
     // backstop tokens use 7 decimals
-    let bid_amount = interest_value // oracle_scalar
+    let bid_amount = interest_value_usd // oracle_scalar
         .fixed_mul_floor(e, &1_2000000, &oracle_scalar) // denom of oracle_scalar means result is SCALAR_7
-        .fixed_div_floor(e, &pool_backstop_data.token_spot_price, &SCALAR_7); // token_spot_price is SCALAR_7
+        .fixed_div_floor(e, &token_price, &SCALAR_7); // token_price is SCALAR_7
     auction_data.bid.set(backstop_token, bid_amount);
 
+    if !preview {
+        storage::set_last_interest_auction(e, e.ledger().sequence());
+    }
     auction_data
 }
 
@@ -90,19 +143,24 @@ pub fn fill_interest_auction(
     filler: &Address,
 ) {
     // bid only contains the Backstop token
-    let backstop = storage::get_backstop(e);
-    if filler.clone() == backstop {
+    let backstop_client = Backstop::load(e);
+    if filler.clone() == backstop_client.address {
         panic_with_error!(e, PoolError::BadRequest);
     }
-    let backstop_client = BackstopClient::new(&e, &backstop);
-    let backstop_token: Address = backstop_client.backstop_token();
-    let backstop_token_bid_amount = auction_data.bid.get(backstop_token).unwrap_or(0);
+    let backstop_token: Address = backstop_client.token();
+    let backstop_token_bid_amount = auction_data.bid.get(backstop_token.clone()).unwrap_or(0);
     if backstop_token_bid_amount > 0 {
-        backstop_client.donate(
-            &filler,
-            &e.current_contract_address(),
-            &backstop_token_bid_amount,
-        );
+        if storage::get_interest_deposit(e) {
+            // route the bid through the pool so it is deposited into the backstop as pool owned shares
+            TokenClient::new(e, &backstop_token).transfer(
+                filler,
+                &e.current_contract_address(),
+                &backstop_token_bid_amount,
+            );
+            backstop_client.deposit(e, &backstop_token, backstop_token_bid_amount);
+        } else {
+            backstop_client.donate(filler, backstop_token_bid_amount);
+        }
     }
 
     // lot contains underlying tokens, but the backstop credit must be updated on the reserve
@@ -122,7 +180,7 @@ pub fn fill_interest_auction(
 mod tests {
     use crate::{
         auctions::auction::AuctionType,
-        storage::{self, PoolConfig},
+        storage::{self, AuctionConfig, PoolConfig},
         testutils::{self, create_comet_lp_pool, create_pool},
     };
 
@@ -195,6 +253,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -237,6 +297,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -280,6 +342,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -388,6 +452,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -471,6 +537,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -549,6 +617,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -627,6 +697,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -735,6 +807,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 3,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -846,6 +920,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -863,11 +939,12 @@ mod tests {
             assert_eq!(result.lot.get_unchecked(underlying_0), 100_0000000);
             assert_eq!(result.lot.get_unchecked(underlying_1), 25_0000000);
             assert_eq!(result.lot.len(), 2);
+            assert_eq!(storage::get_last_interest_auction(&e), 50);
         });
     }
 
     #[test]
-    fn test_create_interest_auction_14_decimal_oracle() {
+    fn test_create_partial_interest_auction() {
         let e = Env::default();
         e.mock_all_auths();
         e.cost_estimate().budget().reset_unlimited(); // setup exhausts budget
@@ -890,7 +967,7 @@ mod tests {
         let (blnd_id, _) = testutils::create_blnd_token(&e, &pool_address, &bombadil);
 
         let (backstop_token_id, _) = create_comet_lp_pool(&e, &bombadil, &blnd_id, &usdc_id);
-        let (backstop_address, backstop_client) =
+        let (_, backstop_client) =
             testutils::create_backstop(&e, &pool_address, &backstop_token_id, &usdc_id, &blnd_id);
         backstop_client.deposit(&bombadil, &pool_address, &(50 * SCALAR_7));
         let (oracle_id, oracle_client) = testutils::create_mock_oracle(&e);
@@ -947,16 +1024,10 @@ mod tests {
                 Asset::Stellar(underlying_2),
                 Asset::Stellar(usdc_id.clone()),
             ],
-            &14,
+            &7,
             &300,
         );
-        oracle_client.set_price_stable(&vec![
-            &e,
-            2_0000000_0000000,
-            4_0000000_0000000,
-            100_0000000_0000000,
-            1_0000000_0000000,
-        ]);
+        oracle_client.set_price_stable(&vec![&e, 2_0000000, 4_0000000, 100_0000000, 1_0000000]);
 
         let pool_config = PoolConfig {
             oracle: oracle_id,
@@ -964,28 +1035,35 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
 
-            let result = create_interest_auction_data(
+            // leave 40 of reserve 0's backstop credit as a buffer, and auction all of reserve 1's
+            let result = create_partial_interest_auction_data(
                 &e,
-                &backstop_address,
                 &vec![&e, backstop_token_id.clone()],
-                &vec![&e, underlying_0.clone(), underlying_1.clone()],
-                100,
+                &map![
+                    &e,
+                    (underlying_0.clone(), 60_0000000),
+                    (underlying_1.clone(), 100_0000000)
+                ],
             );
             assert_eq!(result.block, 51);
-            assert_eq!(result.bid.get_unchecked(backstop_token_id), 288_0000000);
+            assert_eq!(result.bid.get_unchecked(backstop_token_id), 211_2000000);
             assert_eq!(result.bid.len(), 1);
-            assert_eq!(result.lot.get_unchecked(underlying_0), 100_0000000);
+            assert_eq!(result.lot.get_unchecked(underlying_0), 60_0000000);
             assert_eq!(result.lot.get_unchecked(underlying_1), 25_0000000);
             assert_eq!(result.lot.len(), 2);
+            assert_eq!(storage::get_last_interest_auction(&e), 50);
         });
     }
 
     #[test]
-    fn test_create_interest_auction_2_decimal_oracle() {
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_create_partial_interest_auction_max_not_positive() {
         let e = Env::default();
         e.mock_all_auths();
         e.cost_estimate().budget().reset_unlimited(); // setup exhausts budget
@@ -1008,7 +1086,7 @@ mod tests {
         let (blnd_id, _) = testutils::create_blnd_token(&e, &pool_address, &bombadil);
 
         let (backstop_token_id, _) = create_comet_lp_pool(&e, &bombadil, &blnd_id, &usdc_id);
-        let (backstop_address, backstop_client) =
+        let (_, backstop_client) =
             testutils::create_backstop(&e, &pool_address, &backstop_token_id, &usdc_id, &blnd_id);
         backstop_client.deposit(&bombadil, &pool_address, &(50 * SCALAR_7));
         let (oracle_id, oracle_client) = testutils::create_mock_oracle(&e);
@@ -1065,10 +1143,10 @@ mod tests {
                 Asset::Stellar(underlying_2),
                 Asset::Stellar(usdc_id.clone()),
             ],
-            &2,
+            &7,
             &300,
         );
-        oracle_client.set_price_stable(&vec![&e, 2_00, 4_00, 100_00, 1_00]);
+        oracle_client.set_price_stable(&vec![&e, 2_0000000, 4_0000000, 100_0000000, 1_0000000]);
 
         let pool_config = PoolConfig {
             oracle: oracle_id,
@@ -1076,28 +1154,26 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
 
-            let result = create_interest_auction_data(
+            create_partial_interest_auction_data(
                 &e,
-                &backstop_address,
                 &vec![&e, backstop_token_id.clone()],
-                &vec![&e, underlying_0.clone(), underlying_1.clone()],
-                100,
+                &map![
+                    &e,
+                    (underlying_0.clone(), 0),
+                    (underlying_1.clone(), 100_0000000)
+                ],
             );
-            assert_eq!(result.block, 51);
-            assert_eq!(result.bid.get_unchecked(backstop_token_id), 288_0000000);
-            assert_eq!(result.bid.len(), 1);
-            assert_eq!(result.lot.get_unchecked(underlying_0), 100_0000000);
-            assert_eq!(result.lot.get_unchecked(underlying_1), 25_0000000);
-            assert_eq!(result.lot.len(), 2);
         });
     }
 
     #[test]
-    fn test_create_interest_auction_applies_interest() {
+    fn test_create_interest_auction_after_interval() {
         let e = Env::default();
         e.mock_all_auths();
         e.cost_estimate().budget().reset_unlimited(); // setup exhausts budget
@@ -1105,7 +1181,7 @@ mod tests {
         e.ledger().set(LedgerInfo {
             timestamp: 12345,
             protocol_version: 22,
-            sequence_number: 150,
+            sequence_number: 50,
             network_id: Default::default(),
             base_reserve: 10,
             min_temp_entry_ttl: 10,
@@ -1123,12 +1199,11 @@ mod tests {
         let (backstop_address, backstop_client) =
             testutils::create_backstop(&e, &pool_address, &backstop_token_id, &usdc_id, &blnd_id);
         backstop_client.deposit(&bombadil, &pool_address, &(50 * SCALAR_7));
-
         let (oracle_id, oracle_client) = testutils::create_mock_oracle(&e);
 
         let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
         let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
-        reserve_data_0.last_time = 11845;
+        reserve_data_0.last_time = 12345;
         reserve_data_0.backstop_credit = 100_0000000;
         reserve_data_0.b_supply = 1000_0000000;
         reserve_data_0.d_supply = 750_0000000;
@@ -1143,7 +1218,7 @@ mod tests {
 
         let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
         let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
-        reserve_data_1.last_time = 11845;
+        reserve_data_1.last_time = 12345;
         reserve_data_1.backstop_credit = 25_0000000;
         reserve_data_1.b_supply = 250_0000000;
         reserve_data_1.d_supply = 187_5000000;
@@ -1158,8 +1233,8 @@ mod tests {
 
         let (underlying_2, _) = testutils::create_token_contract(&e, &bombadil);
         let (mut reserve_config_2, mut reserve_data_2) = testutils::default_reserve_meta();
-        reserve_data_2.last_time = 11845;
-        reserve_config_2.index = 2;
+        reserve_data_2.last_time = 12345;
+        reserve_config_2.index = 1;
         testutils::create_reserve(
             &e,
             &pool_address,
@@ -1175,7 +1250,7 @@ mod tests {
                 &e,
                 Asset::Stellar(underlying_0.clone()),
                 Asset::Stellar(underlying_1.clone()),
-                Asset::Stellar(underlying_2.clone()),
+                Asset::Stellar(underlying_2),
                 Asset::Stellar(usdc_id.clone()),
             ],
             &7,
@@ -1189,42 +1264,58 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
+            storage::set_auction_config(
+                &e,
+                &AuctionConfig {
+                    start_delay: 0,
+                    soft_liq_max_value: 0,
+                    soft_liq_bonus: 0,
+                    interest_interval: 20,
+                    interest_min_value: 300_0000000,
+                    bad_debt_premium_cap: 0,
+                    bad_debt_bid_premium: 0,
+                    liq_curve: 0,
+                    bad_debt_curve: 0,
+                    interest_curve: 0,
+                    min_fill_pct: 0,
+                    liq_target_hf: 0,
+                },
+            );
+            storage::set_last_interest_auction(&e, 30);
 
             let result = create_interest_auction_data(
                 &e,
                 &backstop_address,
                 &vec![&e, backstop_token_id.clone()],
-                &vec![
-                    &e,
-                    underlying_0.clone(),
-                    underlying_1.clone(),
-                    underlying_2.clone(),
-                ],
+                &vec![&e, underlying_0.clone(), underlying_1.clone()],
                 100,
             );
-            assert_eq!(result.block, 151);
-            assert_eq!(result.bid.get_unchecked(backstop_token_id), 288_0008868);
+            assert_eq!(result.block, 51);
+            assert_eq!(result.bid.get_unchecked(backstop_token_id), 288_0000000);
             assert_eq!(result.bid.len(), 1);
-            assert_eq!(result.lot.get_unchecked(underlying_0), 100_0000713);
-            assert_eq!(result.lot.get_unchecked(underlying_1), 25_0000178);
-            assert_eq!(result.lot.get_unchecked(underlying_2), 71);
-            assert_eq!(result.lot.len(), 3);
+            assert_eq!(result.lot.get_unchecked(underlying_0), 100_0000000);
+            assert_eq!(result.lot.get_unchecked(underlying_1), 25_0000000);
+            assert_eq!(result.lot.len(), 2);
+            assert_eq!(storage::get_last_interest_auction(&e), 50);
         });
     }
 
     #[test]
-    fn test_fill_interest_auction() {
+    #[should_panic(expected = "Error(Contract, #1227)")]
+    fn test_create_interest_auction_before_interval() {
         let e = Env::default();
-        e.mock_all_auths_allowing_non_root_auth();
-        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths();
+        e.cost_estimate().budget().reset_unlimited(); // setup exhausts budget
 
         e.ledger().set(LedgerInfo {
             timestamp: 12345,
             protocol_version: 22,
-            sequence_number: 301,
+            sequence_number: 50,
             network_id: Default::default(),
             base_reserve: 10,
             min_temp_entry_ttl: 10,
@@ -1233,36 +1324,23 @@ mod tests {
         });
 
         let bombadil = Address::generate(&e);
-        let samwise = Address::generate(&e);
 
         let pool_address = create_pool(&e);
+        let (usdc_id, _) = testutils::create_token_contract(&e, &bombadil);
+        let (blnd_id, _) = testutils::create_blnd_token(&e, &pool_address, &bombadil);
 
-        let (usdc_id, usdc_client) = testutils::create_token_contract(&e, &bombadil);
-        let (blnd_id, blnd_client) = testutils::create_blnd_token(&e, &pool_address, &bombadil);
-
-        let (backstop_token_id, backstop_token_client) =
-            create_comet_lp_pool(&e, &bombadil, &blnd_id, &usdc_id);
-        blnd_client.mint(&samwise, &10_000_0000000);
-        usdc_client.mint(&samwise, &250_0000000);
-        let exp_ledger = e.ledger().sequence() + 100;
-        blnd_client.approve(&bombadil, &backstop_token_id, &2_000_0000000, &exp_ledger);
-        usdc_client.approve(&bombadil, &backstop_token_id, &2_000_0000000, &exp_ledger);
-        backstop_token_client.join_pool(
-            &(100 * SCALAR_7),
-            &vec![&e, 10_000_0000000, 250_0000000],
-            &samwise,
-        );
+        let (backstop_token_id, _) = create_comet_lp_pool(&e, &bombadil, &blnd_id, &usdc_id);
         let (backstop_address, backstop_client) =
             testutils::create_backstop(&e, &pool_address, &backstop_token_id, &usdc_id, &blnd_id);
         backstop_client.deposit(&bombadil, &pool_address, &(50 * SCALAR_7));
+        let (oracle_id, oracle_client) = testutils::create_mock_oracle(&e);
 
-        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
         let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
-        reserve_data_0.b_rate = 1_100_000_000_000;
-        reserve_data_0.b_supply = 200_000_0000000;
-        reserve_data_0.d_supply = 100_000_0000000;
         reserve_data_0.last_time = 12345;
         reserve_data_0.backstop_credit = 100_0000000;
+        reserve_data_0.b_supply = 1000_0000000;
+        reserve_data_0.d_supply = 750_0000000;
         reserve_config_0.index = 0;
         testutils::create_reserve(
             &e,
@@ -1271,15 +1349,13 @@ mod tests {
             &reserve_config_0,
             &reserve_data_0,
         );
-        underlying_0_client.mint(&pool_address, &1_000_0000000);
 
-        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
         let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
-        reserve_data_1.b_rate = 1_100_000_000_000;
-        reserve_data_0.b_supply = 10_000_0000000;
-        reserve_data_0.b_supply = 7_000_0000000;
         reserve_data_1.last_time = 12345;
-        reserve_data_1.backstop_credit = 30_0000000;
+        reserve_data_1.backstop_credit = 25_0000000;
+        reserve_data_1.b_supply = 250_0000000;
+        reserve_data_1.d_supply = 187_5000000;
         reserve_config_1.index = 1;
         testutils::create_reserve(
             &e,
@@ -1288,59 +1364,831 @@ mod tests {
             &reserve_config_1,
             &reserve_data_1,
         );
-        underlying_1_client.mint(&pool_address, &1_000_0000000);
+
+        let (underlying_2, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_2, mut reserve_data_2) = testutils::default_reserve_meta();
+        reserve_data_2.last_time = 12345;
+        reserve_config_2.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_2,
+            &reserve_config_2,
+            &reserve_data_2,
+        );
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+                Asset::Stellar(underlying_2),
+                Asset::Stellar(usdc_id.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 2_0000000, 4_0000000, 100_0000000, 1_0000000]);
 
         let pool_config = PoolConfig {
-            oracle: Address::generate(&e),
+            oracle: oracle_id,
             min_collateral: 1_0000000,
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
-        let mut auction_data = AuctionData {
-            bid: map![&e, (backstop_token_id.clone(), 75_0000000)],
-            lot: map![
+        e.as_contract(&pool_address, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_auction_config(
                 &e,
-                (underlying_0.clone(), 100_0000000),
-                (underlying_1.clone(), 25_0000000)
-            ],
-            block: 51,
-        };
+                &AuctionConfig {
+                    start_delay: 0,
+                    soft_liq_max_value: 0,
+                    soft_liq_bonus: 0,
+                    interest_interval: 20,
+                    interest_min_value: 0,
+                    bad_debt_premium_cap: 0,
+                    bad_debt_bid_premium: 0,
+                    liq_curve: 0,
+                    bad_debt_curve: 0,
+                    interest_curve: 0,
+                    min_fill_pct: 0,
+                    liq_target_hf: 0,
+                },
+            );
+            storage::set_last_interest_auction(&e, 31);
 
-        backstop_token_client.approve(
-            &samwise,
-            &backstop_address,
-            &75_0000000,
-            &e.ledger().sequence(),
-        );
-        e.as_contract(&pool_address, || {
-            e.mock_all_auths_allowing_non_root_auth();
-            storage::set_auction(
+            create_interest_auction_data(
                 &e,
-                &(AuctionType::InterestAuction as u32),
                 &backstop_address,
-                &auction_data,
+                &vec![&e, backstop_token_id.clone()],
+                &vec![&e, underlying_0.clone(), underlying_1.clone()],
+                100,
             );
-            storage::set_pool_config(&e, &pool_config);
-            storage::set_backstop(&e, &backstop_address);
-            let mut pool = Pool::load(&e);
-            let backstop_token_balance_pre_fill = backstop_token_client.balance(&backstop_address);
-            fill_interest_auction(&e, &mut pool, &mut auction_data, &samwise);
-            pool.store_cached_reserves(&e);
+        });
+    }
 
-            assert_eq!(backstop_token_client.balance(&samwise), 25_0000000);
-            assert_eq!(
-                backstop_token_client.balance(&backstop_address),
-                backstop_token_balance_pre_fill + 75_0000000
-            );
-            assert_eq!(underlying_0_client.balance(&samwise), 100_0000000);
-            assert_eq!(underlying_1_client.balance(&samwise), 25_0000000);
-            // verify only filled backstop credits get deducted from total
-            let reserve_0_data = storage::get_res_data(&e, &underlying_0);
-            assert_eq!(reserve_0_data.backstop_credit, 0);
-            let reserve_1_data = storage::get_res_data(&e, &underlying_1);
-            assert_eq!(reserve_1_data.backstop_credit, 5_0000000);
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1215)")]
+    fn test_create_interest_auction_under_configured_min() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.cost_estimate().budget().reset_unlimited(); // setup exhausts budget
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 50,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
         });
+
+        let bombadil = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+        let (usdc_id, _) = testutils::create_token_contract(&e, &bombadil);
+        let (blnd_id, _) = testutils::create_blnd_token(&e, &pool_address, &bombadil);
+
+        let (backstop_token_id, _) = create_comet_lp_pool(&e, &bombadil, &blnd_id, &usdc_id);
+        let (backstop_address, backstop_client) =
+            testutils::create_backstop(&e, &pool_address, &backstop_token_id, &usdc_id, &blnd_id);
+        backstop_client.deposit(&bombadil, &pool_address, &(50 * SCALAR_7));
+        let (oracle_id, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.last_time = 12345;
+        reserve_data_0.backstop_credit = 100_0000000;
+        reserve_data_0.b_supply = 1000_0000000;
+        reserve_data_0.d_supply = 750_0000000;
+        reserve_config_0.index = 0;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.last_time = 12345;
+        reserve_data_1.backstop_credit = 25_0000000;
+        reserve_data_1.b_supply = 250_0000000;
+        reserve_data_1.d_supply = 187_5000000;
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+
+        let (underlying_2, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_2, mut reserve_data_2) = testutils::default_reserve_meta();
+        reserve_data_2.last_time = 12345;
+        reserve_config_2.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_2,
+            &reserve_config_2,
+            &reserve_data_2,
+        );
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+                Asset::Stellar(underlying_2),
+                Asset::Stellar(usdc_id.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 2_0000000, 4_0000000, 100_0000000, 1_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle: oracle_id,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_auction_config(
+                &e,
+                &AuctionConfig {
+                    start_delay: 0,
+                    soft_liq_max_value: 0,
+                    soft_liq_bonus: 0,
+                    interest_interval: 0,
+                    interest_min_value: 300_0000001,
+                    bad_debt_premium_cap: 0,
+                    bad_debt_bid_premium: 0,
+                    liq_curve: 0,
+                    bad_debt_curve: 0,
+                    interest_curve: 0,
+                    min_fill_pct: 0,
+                    liq_target_hf: 0,
+                },
+            );
+
+            create_interest_auction_data(
+                &e,
+                &backstop_address,
+                &vec![&e, backstop_token_id.clone()],
+                &vec![&e, underlying_0.clone(), underlying_1.clone()],
+                100,
+            );
+        });
+    }
+
+    #[test]
+    fn test_create_interest_auction_14_decimal_oracle() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.cost_estimate().budget().reset_unlimited(); // setup exhausts budget
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 50,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+        let (usdc_id, _) = testutils::create_token_contract(&e, &bombadil);
+        let (blnd_id, _) = testutils::create_blnd_token(&e, &pool_address, &bombadil);
+
+        let (backstop_token_id, _) = create_comet_lp_pool(&e, &bombadil, &blnd_id, &usdc_id);
+        let (backstop_address, backstop_client) =
+            testutils::create_backstop(&e, &pool_address, &backstop_token_id, &usdc_id, &blnd_id);
+        backstop_client.deposit(&bombadil, &pool_address, &(50 * SCALAR_7));
+        let (oracle_id, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.last_time = 12345;
+        reserve_data_0.backstop_credit = 100_0000000;
+        reserve_data_0.b_supply = 1000_0000000;
+        reserve_data_0.d_supply = 750_0000000;
+        reserve_config_0.index = 0;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.last_time = 12345;
+        reserve_data_1.backstop_credit = 25_0000000;
+        reserve_data_1.b_supply = 250_0000000;
+        reserve_data_1.d_supply = 187_5000000;
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+
+        let (underlying_2, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_2, mut reserve_data_2) = testutils::default_reserve_meta();
+        reserve_data_2.last_time = 12345;
+        reserve_config_2.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_2,
+            &reserve_config_2,
+            &reserve_data_2,
+        );
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+                Asset::Stellar(underlying_2),
+                Asset::Stellar(usdc_id.clone()),
+            ],
+            &14,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![
+            &e,
+            2_0000000_0000000,
+            4_0000000_0000000,
+            100_0000000_0000000,
+            1_0000000_0000000,
+        ]);
+
+        let pool_config = PoolConfig {
+            oracle: oracle_id,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            let result = create_interest_auction_data(
+                &e,
+                &backstop_address,
+                &vec![&e, backstop_token_id.clone()],
+                &vec![&e, underlying_0.clone(), underlying_1.clone()],
+                100,
+            );
+            assert_eq!(result.block, 51);
+            assert_eq!(result.bid.get_unchecked(backstop_token_id), 288_0000000);
+            assert_eq!(result.bid.len(), 1);
+            assert_eq!(result.lot.get_unchecked(underlying_0), 100_0000000);
+            assert_eq!(result.lot.get_unchecked(underlying_1), 25_0000000);
+            assert_eq!(result.lot.len(), 2);
+        });
+    }
+
+    #[test]
+    fn test_create_interest_auction_2_decimal_oracle() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.cost_estimate().budget().reset_unlimited(); // setup exhausts budget
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 50,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+        let (usdc_id, _) = testutils::create_token_contract(&e, &bombadil);
+        let (blnd_id, _) = testutils::create_blnd_token(&e, &pool_address, &bombadil);
+
+        let (backstop_token_id, _) = create_comet_lp_pool(&e, &bombadil, &blnd_id, &usdc_id);
+        let (backstop_address, backstop_client) =
+            testutils::create_backstop(&e, &pool_address, &backstop_token_id, &usdc_id, &blnd_id);
+        backstop_client.deposit(&bombadil, &pool_address, &(50 * SCALAR_7));
+        let (oracle_id, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.last_time = 12345;
+        reserve_data_0.backstop_credit = 100_0000000;
+        reserve_data_0.b_supply = 1000_0000000;
+        reserve_data_0.d_supply = 750_0000000;
+        reserve_config_0.index = 0;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.last_time = 12345;
+        reserve_data_1.backstop_credit = 25_0000000;
+        reserve_data_1.b_supply = 250_0000000;
+        reserve_data_1.d_supply = 187_5000000;
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+
+        let (underlying_2, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_2, mut reserve_data_2) = testutils::default_reserve_meta();
+        reserve_data_2.last_time = 12345;
+        reserve_config_2.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_2,
+            &reserve_config_2,
+            &reserve_data_2,
+        );
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+                Asset::Stellar(underlying_2),
+                Asset::Stellar(usdc_id.clone()),
+            ],
+            &2,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 2_00, 4_00, 100_00, 1_00]);
+
+        let pool_config = PoolConfig {
+            oracle: oracle_id,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            let result = create_interest_auction_data(
+                &e,
+                &backstop_address,
+                &vec![&e, backstop_token_id.clone()],
+                &vec![&e, underlying_0.clone(), underlying_1.clone()],
+                100,
+            );
+            assert_eq!(result.block, 51);
+            assert_eq!(result.bid.get_unchecked(backstop_token_id), 288_0000000);
+            assert_eq!(result.bid.len(), 1);
+            assert_eq!(result.lot.get_unchecked(underlying_0), 100_0000000);
+            assert_eq!(result.lot.get_unchecked(underlying_1), 25_0000000);
+            assert_eq!(result.lot.len(), 2);
+        });
+    }
+
+    #[test]
+    fn test_create_interest_auction_applies_interest() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.cost_estimate().budget().reset_unlimited(); // setup exhausts budget
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 150,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+        let (usdc_id, _) = testutils::create_token_contract(&e, &bombadil);
+        let (blnd_id, _) = testutils::create_blnd_token(&e, &pool_address, &bombadil);
+
+        let (backstop_token_id, _) = create_comet_lp_pool(&e, &bombadil, &blnd_id, &usdc_id);
+        let (backstop_address, backstop_client) =
+            testutils::create_backstop(&e, &pool_address, &backstop_token_id, &usdc_id, &blnd_id);
+        backstop_client.deposit(&bombadil, &pool_address, &(50 * SCALAR_7));
+
+        let (oracle_id, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.last_time = 11845;
+        reserve_data_0.backstop_credit = 100_0000000;
+        reserve_data_0.b_supply = 1000_0000000;
+        reserve_data_0.d_supply = 750_0000000;
+        reserve_config_0.index = 0;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.last_time = 11845;
+        reserve_data_1.backstop_credit = 25_0000000;
+        reserve_data_1.b_supply = 250_0000000;
+        reserve_data_1.d_supply = 187_5000000;
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+
+        let (underlying_2, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_2, mut reserve_data_2) = testutils::default_reserve_meta();
+        reserve_data_2.last_time = 11845;
+        reserve_config_2.index = 2;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_2,
+            &reserve_config_2,
+            &reserve_data_2,
+        );
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+                Asset::Stellar(underlying_2.clone()),
+                Asset::Stellar(usdc_id.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 2_0000000, 4_0000000, 100_0000000, 1_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle: oracle_id,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            let result = create_interest_auction_data(
+                &e,
+                &backstop_address,
+                &vec![&e, backstop_token_id.clone()],
+                &vec![
+                    &e,
+                    underlying_0.clone(),
+                    underlying_1.clone(),
+                    underlying_2.clone(),
+                ],
+                100,
+            );
+            assert_eq!(result.block, 151);
+            assert_eq!(result.bid.get_unchecked(backstop_token_id), 288_0008868);
+            assert_eq!(result.bid.len(), 1);
+            assert_eq!(result.lot.get_unchecked(underlying_0), 100_0000713);
+            assert_eq!(result.lot.get_unchecked(underlying_1), 25_0000178);
+            assert_eq!(result.lot.get_unchecked(underlying_2), 71);
+            assert_eq!(result.lot.len(), 3);
+        });
+    }
+
+    #[test]
+    fn test_fill_interest_auction() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+        e.cost_estimate().budget().reset_unlimited();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 301,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+
+        let (usdc_id, usdc_client) = testutils::create_token_contract(&e, &bombadil);
+        let (blnd_id, blnd_client) = testutils::create_blnd_token(&e, &pool_address, &bombadil);
+
+        let (backstop_token_id, backstop_token_client) =
+            create_comet_lp_pool(&e, &bombadil, &blnd_id, &usdc_id);
+        blnd_client.mint(&samwise, &10_000_0000000);
+        usdc_client.mint(&samwise, &250_0000000);
+        let exp_ledger = e.ledger().sequence() + 100;
+        blnd_client.approve(&bombadil, &backstop_token_id, &2_000_0000000, &exp_ledger);
+        usdc_client.approve(&bombadil, &backstop_token_id, &2_000_0000000, &exp_ledger);
+        backstop_token_client.join_pool(
+            &(100 * SCALAR_7),
+            &vec![&e, 10_000_0000000, 250_0000000],
+            &samwise,
+        );
+        let (backstop_address, backstop_client) =
+            testutils::create_backstop(&e, &pool_address, &backstop_token_id, &usdc_id, &blnd_id);
+        backstop_client.deposit(&bombadil, &pool_address, &(50 * SCALAR_7));
+
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.b_rate = 1_100_000_000_000;
+        reserve_data_0.b_supply = 200_000_0000000;
+        reserve_data_0.d_supply = 100_000_0000000;
+        reserve_data_0.last_time = 12345;
+        reserve_data_0.backstop_credit = 100_0000000;
+        reserve_config_0.index = 0;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+        underlying_0_client.mint(&pool_address, &1_000_0000000);
+
+        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.b_rate = 1_100_000_000_000;
+        reserve_data_0.b_supply = 10_000_0000000;
+        reserve_data_0.b_supply = 7_000_0000000;
+        reserve_data_1.last_time = 12345;
+        reserve_data_1.backstop_credit = 30_0000000;
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+        underlying_1_client.mint(&pool_address, &1_000_0000000);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        let mut auction_data = AuctionData {
+            bid: map![&e, (backstop_token_id.clone(), 75_0000000)],
+            lot: map![
+                &e,
+                (underlying_0.clone(), 100_0000000),
+                (underlying_1.clone(), 25_0000000)
+            ],
+            block: 51,
+        };
+
+        backstop_token_client.approve(
+            &samwise,
+            &backstop_address,
+            &75_0000000,
+            &e.ledger().sequence(),
+        );
+        e.as_contract(&pool_address, || {
+            e.mock_all_auths_allowing_non_root_auth();
+            storage::set_auction(
+                &e,
+                &(AuctionType::InterestAuction as u32),
+                &backstop_address,
+                &auction_data,
+            );
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_backstop(&e, &backstop_address);
+            let mut pool = Pool::load(&e);
+            let backstop_token_balance_pre_fill = backstop_token_client.balance(&backstop_address);
+            fill_interest_auction(&e, &mut pool, &mut auction_data, &samwise);
+            pool.store_cached_reserves(&e);
+
+            assert_eq!(backstop_token_client.balance(&samwise), 25_0000000);
+            assert_eq!(
+                backstop_token_client.balance(&backstop_address),
+                backstop_token_balance_pre_fill + 75_0000000
+            );
+            assert_eq!(underlying_0_client.balance(&samwise), 100_0000000);
+            assert_eq!(underlying_1_client.balance(&samwise), 25_0000000);
+            // verify only filled backstop credits get deducted from total
+            let reserve_0_data = storage::get_res_data(&e, &underlying_0);
+            assert_eq!(reserve_0_data.backstop_credit, 0);
+            let reserve_1_data = storage::get_res_data(&e, &underlying_1);
+            assert_eq!(reserve_1_data.backstop_credit, 5_0000000);
+        });
+        // verify the bid was deposited on behalf of the pool
+        assert_eq!(
+            backstop_client
+                .user_balance(&pool_address, &pool_address)
+                .shares,
+            75_0000000
+        );
+        assert_eq!(backstop_client.pool_data(&pool_address).shares, 125_0000000);
+    }
+
+    #[test]
+    fn test_fill_interest_auction_donate() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+        e.cost_estimate().budget().reset_unlimited();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 301,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+
+        let (usdc_id, usdc_client) = testutils::create_token_contract(&e, &bombadil);
+        let (blnd_id, blnd_client) = testutils::create_blnd_token(&e, &pool_address, &bombadil);
+
+        let (backstop_token_id, backstop_token_client) =
+            create_comet_lp_pool(&e, &bombadil, &blnd_id, &usdc_id);
+        blnd_client.mint(&samwise, &10_000_0000000);
+        usdc_client.mint(&samwise, &250_0000000);
+        let exp_ledger = e.ledger().sequence() + 100;
+        blnd_client.approve(&bombadil, &backstop_token_id, &2_000_0000000, &exp_ledger);
+        usdc_client.approve(&bombadil, &backstop_token_id, &2_000_0000000, &exp_ledger);
+        backstop_token_client.join_pool(
+            &(100 * SCALAR_7),
+            &vec![&e, 10_000_0000000, 250_0000000],
+            &samwise,
+        );
+        let (backstop_address, backstop_client) =
+            testutils::create_backstop(&e, &pool_address, &backstop_token_id, &usdc_id, &blnd_id);
+        backstop_client.deposit(&bombadil, &pool_address, &(50 * SCALAR_7));
+
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.b_rate = 1_100_000_000_000;
+        reserve_data_0.b_supply = 200_000_0000000;
+        reserve_data_0.d_supply = 100_000_0000000;
+        reserve_data_0.last_time = 12345;
+        reserve_data_0.backstop_credit = 100_0000000;
+        reserve_config_0.index = 0;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+        underlying_0_client.mint(&pool_address, &1_000_0000000);
+
+        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.b_rate = 1_100_000_000_000;
+        reserve_data_0.b_supply = 10_000_0000000;
+        reserve_data_0.b_supply = 7_000_0000000;
+        reserve_data_1.last_time = 12345;
+        reserve_data_1.backstop_credit = 30_0000000;
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+        underlying_1_client.mint(&pool_address, &1_000_0000000);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        let mut auction_data = AuctionData {
+            bid: map![&e, (backstop_token_id.clone(), 75_0000000)],
+            lot: map![
+                &e,
+                (underlying_0.clone(), 100_0000000),
+                (underlying_1.clone(), 25_0000000)
+            ],
+            block: 51,
+        };
+
+        backstop_token_client.approve(
+            &samwise,
+            &backstop_address,
+            &75_0000000,
+            &e.ledger().sequence(),
+        );
+        e.as_contract(&pool_address, || {
+            e.mock_all_auths_allowing_non_root_auth();
+            storage::set_auction(
+                &e,
+                &(AuctionType::InterestAuction as u32),
+                &backstop_address,
+                &auction_data,
+            );
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_backstop(&e, &backstop_address);
+            storage::set_interest_deposit(&e, false);
+            let mut pool = Pool::load(&e);
+            let backstop_token_balance_pre_fill = backstop_token_client.balance(&backstop_address);
+            fill_interest_auction(&e, &mut pool, &mut auction_data, &samwise);
+            pool.store_cached_reserves(&e);
+
+            assert_eq!(backstop_token_client.balance(&samwise), 25_0000000);
+            assert_eq!(
+                backstop_token_client.balance(&backstop_address),
+                backstop_token_balance_pre_fill + 75_0000000
+            );
+            assert_eq!(underlying_0_client.balance(&samwise), 100_0000000);
+            assert_eq!(underlying_1_client.balance(&samwise), 25_0000000);
+            // verify only filled backstop credits get deducted from total
+            let reserve_0_data = storage::get_res_data(&e, &underlying_0);
+            assert_eq!(reserve_0_data.backstop_credit, 0);
+            let reserve_1_data = storage::get_res_data(&e, &underlying_1);
+            assert_eq!(reserve_1_data.backstop_credit, 5_0000000);
+        });
+        // verify the bid was donated to the backstop
+        assert_eq!(
+            backstop_client
+                .user_balance(&pool_address, &pool_address)
+                .shares,
+            0
+        );
+        assert_eq!(backstop_client.pool_data(&pool_address).shares, 50_0000000);
+        assert_eq!(backstop_client.pool_data(&pool_address).tokens, 125_0000000);
     }
 
     #[test]
@@ -1424,6 +2272,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         let mut auction_data = AuctionData {
             bid: map![&e],
@@ -1526,6 +2376,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         let mut auction_data = AuctionData {
             bid: map![&e, (usdc_id.clone(), 95_0000000)],
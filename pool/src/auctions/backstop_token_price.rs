@@ -0,0 +1,128 @@
+use soroban_fixed_point_math::SorobanFixedPoint;
+use soroban_sdk::Env;
+
+use crate::{constants::SCALAR_7, dependencies::PoolBackstopData, pool::Pool, storage};
+
+/// Load the price of the backstop token used to size auctions, in USDC with 7 decimals.
+///
+/// If the backstop token price check is enabled, the LP's spot price is compared against the value of
+/// the BLND and USDC backing each backstop token at the pool's oracle prices, and the more conservative
+/// of the two is used. Otherwise, the LP's spot price is used as is.
+///
+/// ### Arguments
+/// * `pool_backstop_data` - The pool's backstop data
+/// * `prefer_high` - If the higher of the two prices is more conservative. This is the case when the
+///                   pool pays out backstop tokens, and not when the pool receives them.
+///
+/// ### Panics
+/// If the backstop token price check is enabled and the oracle can't price BLND or USDC
+pub fn load_backstop_token_price(
+    e: &Env,
+    pool: &mut Pool,
+    pool_backstop_data: &PoolBackstopData,
+    prefer_high: bool,
+) -> i128 {
+    let spot_price = pool_backstop_data.token_spot_price;
+    let usdc = match storage::get_bstop_price_usdc(e) {
+        Some(usdc) => usdc,
+        None => return spot_price,
+    };
+    if pool_backstop_data.tokens <= 0 {
+        return spot_price;
+    }
+
+    // BLND and USDC use 7 decimals
+    let oracle_scalar = 10i128.pow(pool.load_price_decimals(e));
+    let blnd_to_base = pool.load_price(e, &storage::get_blnd_token(e));
+    let usdc_to_base = pool.load_price(e, &usdc);
+    let backing_value = blnd_to_base.fixed_mul_floor(e, &pool_backstop_data.blnd, &SCALAR_7)
+        + usdc_to_base.fixed_mul_floor(e, &pool_backstop_data.usdc, &SCALAR_7);
+    let oracle_price = pool
+        .load_usd_value(e, backing_value) // oracle_scalar
+        .fixed_mul_floor(e, &SCALAR_7, &oracle_scalar) // denom of oracle_scalar means result is SCALAR_7
+        .fixed_div_floor(e, &pool_backstop_data.tokens, &SCALAR_7); // backstop tokens use 7 decimals
+
+    if prefer_high {
+        spot_price.max(oracle_price)
+    } else {
+        spot_price.min(oracle_price)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{storage::PoolConfig, testutils};
+
+    use super::*;
+    use sep_40_oracle::testutils::Asset;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger},
+        vec, Address, Symbol,
+    };
+
+    #[test]
+    fn test_load_backstop_token_price() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.ledger().set_timestamp(12345);
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+        let (blnd, _) = testutils::create_blnd_token(&e, &pool, &bombadil);
+        let (usdc, _) = testutils::create_token_contract(&e, &bombadil);
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(blnd.clone()),
+                Asset::Stellar(usdc.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 0_0500000, 1_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        // each backstop token is backed by 4 BLND and 0.1 USDC, worth 0.3 USDC at oracle prices,
+        // but the LP's spot price has been pushed up to 0.5 USDC
+        let pool_backstop_data = PoolBackstopData {
+            tokens: 100_0000000,
+            shares: 100_0000000,
+            q4w_pct: 0,
+            blnd: 400_0000000,
+            usdc: 10_0000000,
+            token_spot_price: 0_5000000,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            // the spot price is used if the check is disabled
+            let mut pool = Pool::load(&e);
+            assert_eq!(
+                load_backstop_token_price(&e, &mut pool, &pool_backstop_data, false),
+                0_5000000
+            );
+
+            storage::set_bstop_price_usdc(&e, &Some(usdc.clone()));
+            let mut pool = Pool::load(&e);
+            assert_eq!(
+                load_backstop_token_price(&e, &mut pool, &pool_backstop_data, true),
+                0_5000000
+            );
+            assert_eq!(
+                load_backstop_token_price(&e, &mut pool, &pool_backstop_data, false),
+                0_3000000
+            );
+        });
+    }
+}
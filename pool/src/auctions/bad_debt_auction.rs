@@ -1,15 +1,15 @@
 use crate::{
     constants::SCALAR_7,
-    dependencies::BackstopClient,
+    dependencies::Backstop,
     errors::PoolError,
-    pool::{check_and_handle_backstop_bad_debt, Pool, User},
+    pool::{check_and_handle_backstop_bad_debt, is_max_positions_exempt, Pool, User},
     storage,
 };
 use cast::i128;
 use soroban_fixed_point_math::SorobanFixedPoint;
-use soroban_sdk::{map, panic_with_error, Address, Env, Vec};
+use soroban_sdk::{map, panic_with_error, Address, Env, Map, Vec};
 
-use super::{AuctionData, AuctionType};
+use super::{backstop_token_price::load_backstop_token_price, AuctionData, AuctionType};
 
 pub fn create_bad_debt_auction_data(
     e: &Env,
@@ -17,6 +17,19 @@ pub fn create_bad_debt_auction_data(
     bid: &Vec<Address>,
     lot: &Vec<Address>,
     percent: u32,
+) -> AuctionData {
+    build_bad_debt_auction_data(e, user, bid, lot, percent, false)
+}
+
+/// Build the auction data for a bad debt auction. A preview does not require the backstop to have
+/// no bad debt auction in progress.
+pub fn build_bad_debt_auction_data(
+    e: &Env,
+    user: &Address,
+    bid: &Vec<Address>,
+    lot: &Vec<Address>,
+    percent: u32,
+    preview: bool,
 ) -> AuctionData {
     let backstop = storage::get_backstop(e);
     if user != &backstop {
@@ -25,7 +38,7 @@ pub fn create_bad_debt_auction_data(
     if percent != 100 {
         panic_with_error!(e, PoolError::BadRequest);
     }
-    if storage::has_auction(e, &(AuctionType::BadDebtAuction as u32), &backstop) {
+    if !preview && storage::has_auction(e, &(AuctionType::BadDebtAuction as u32), &backstop) {
         panic_with_error!(e, PoolError::AuctionInProgress);
     }
 
@@ -37,8 +50,9 @@ pub fn create_bad_debt_auction_data(
 
     // validate and create bid auction data
     let mut pool = Pool::load(e);
-    // lot is required to have 1 entry, so require bid to have less than max_positions entries
-    if pool.config.max_positions <= bid.len() {
+    // lot is required to have 1 entry, so require bid to have less than max_positions entries,
+    // unless the backstop is exempt so all of its bad debt can be auctioned at once
+    if !is_max_positions_exempt(e, &backstop) && pool.config.max_positions <= bid.len() {
         panic_with_error!(e, PoolError::MaxPositionsExceeded);
     }
 
@@ -66,14 +80,14 @@ pub fn create_bad_debt_auction_data(
     }
 
     // validate and create lot auction data
-    let backstop_client = BackstopClient::new(e, &backstop);
-    let backstop_token = backstop_client.backstop_token();
+    let backstop_client = Backstop::load(e);
+    let backstop_token = backstop_client.token();
     if lot.len() != 1 || lot.get_unchecked(0) != backstop_token {
         panic_with_error!(e, PoolError::InvalidLot);
     }
 
-    // get value of backstop_token (BLND-USDC LP token) to base
-    let pool_backstop_data = backstop_client.pool_data(&e.current_contract_address());
+    // get the USDC value of the backstop_token (BLND-USDC LP token)
+    let pool_backstop_data = backstop_client.pool_data();
 
     if pool_backstop_data.tokens <= 0 {
         // no tokens left in backstop to auction off
@@ -82,10 +96,11 @@ pub fn create_bad_debt_auction_data(
 
     // determine lot amount of backstop tokens needed to safely cover bad debt, or post
     // all backstop tokens if there isn't enough to cover the bad debt. backstop tokens use 7 decimals
-    let mut lot_amount =
-        debt_value // oracle_scalar
-            .fixed_mul_floor(e, &1_2000000, &oracle_scalar) // denom of oracle_scalar means result is SCALAR_7
-            .fixed_div_floor(e, &pool_backstop_data.token_spot_price, &SCALAR_7); // token_spot_price is SCALAR_7
+    let debt_value_usd = pool.load_usd_value(e, debt_value);
+    let token_price = load_backstop_token_price(e, &mut pool, &pool_backstop_data, true);
+    let mut lot_amount = debt_value_usd // oracle_scalar
+        .fixed_mul_floor(e, &1_2000000, &oracle_scalar) // denom of oracle_scalar means result is SCALAR_7
+        .fixed_div_floor(e, &token_price, &SCALAR_7); // token_price is SCALAR_7
     lot_amount = pool_backstop_data.tokens.min(lot_amount);
     auction_data.lot.set(backstop_token, lot_amount);
 
@@ -107,34 +122,114 @@ pub fn fill_bad_debt_auction(
     let mut backstop_state = User::load(e, &backstop_address);
 
     // bid only contains d_token asset amounts
+    let filler_bid = apply_bid_premium(e, pool, auction_data);
     backstop_state.rm_positions(e, pool, map![e], auction_data.bid.clone());
-    filler_state.add_positions(e, pool, map![e], auction_data.bid.clone());
-
-    let backstop_client = BackstopClient::new(e, &backstop_address);
-    let backstop_token_id = backstop_client.backstop_token();
-    let lot_amount = auction_data.lot.get(backstop_token_id).unwrap_or(0);
+    filler_state.add_positions(e, pool, map![e], filler_bid.clone());
+
+    let backstop_client = Backstop::load(e);
+    let backstop_token_id = backstop_client.token();
+    let mut lot_amount = auction_data.lot.get(backstop_token_id).unwrap_or(0);
+    let premium_cap = storage::get_auction_config(e).bad_debt_premium_cap;
+    if premium_cap > 0 && lot_amount > 0 {
+        let pool_backstop_data = backstop_client.pool_data();
+        let token_price = load_backstop_token_price(e, pool, &pool_backstop_data, true);
+        let max_lot_amount = calc_max_lot_amount(e, pool, &filler_bid, token_price, premium_cap);
+        lot_amount = lot_amount.min(max_lot_amount);
+    }
     if lot_amount > 0 {
-        backstop_client.draw(
-            &e.current_contract_address(),
-            &lot_amount,
-            &filler_state.address,
-        );
+        backstop_client.draw(lot_amount, &filler_state.address);
     }
 
     if is_full_fill {
         // defaults rest of bad debt if insufficient backstop tokens remain in the backstop
-        check_and_handle_backstop_bad_debt(e, pool, &backstop_address, &mut backstop_state);
+        check_and_handle_backstop_bad_debt(e, pool, &mut backstop_state);
     }
     backstop_state.store(e);
 }
 
+/// Apply the configured bid premium to a bad debt auction fill. The filler takes on the bid plus
+/// the premium, and the premium is credited to the backstop as the debt it adds to the reserve.
+///
+/// Returns the dTokens taken on by the filler
+///
+/// ### Arguments
+/// * `pool` - The pool
+/// * `auction_data` - The scaled auction being filled
+fn apply_bid_premium(e: &Env, pool: &mut Pool, auction_data: &AuctionData) -> Map<Address, i128> {
+    let filler_bid = calc_filler_bid(e, auction_data);
+    for (asset, d_tokens) in filler_bid.iter() {
+        let premium_d_tokens = d_tokens - auction_data.bid.get_unchecked(asset.clone());
+        if premium_d_tokens > 0 {
+            let mut reserve = pool.load_reserve(e, &asset, true);
+            reserve.data.backstop_credit += reserve.to_asset_from_d_token(e, premium_d_tokens);
+            pool.cache_reserve(reserve);
+        }
+    }
+    filler_bid
+}
+
+/// Calculate the dTokens a filler takes on for a bad debt auction fill, including the configured
+/// bid premium
+///
+/// ### Arguments
+/// * `auction_data` - The scaled auction being filled
+pub fn calc_filler_bid(e: &Env, auction_data: &AuctionData) -> Map<Address, i128> {
+    let start_premium = storage::get_auction_config(e).bad_debt_bid_premium;
+    let premium = auction_math::get_bid_premium(
+        e.ledger().sequence() - auction_data.block,
+        i128(start_premium),
+    );
+    if premium == 0 {
+        return auction_data.bid.clone();
+    }
+
+    let mut filler_bid = map![e];
+    for (asset, d_tokens) in auction_data.bid.iter() {
+        // round up to avoid rounding exploits
+        let premium_d_tokens = d_tokens.fixed_mul_ceil(e, &premium, &SCALAR_7);
+        filler_bid.set(asset, d_tokens + premium_d_tokens);
+    }
+    filler_bid
+}
+
+/// Calculate the maximum amount of backstop tokens that can be paid out for a bid, such that the value
+/// of the lot does not exceed the value of the bid by more than the premium cap
+///
+/// ### Arguments
+/// * `bid` - The dTokens being taken on by the filler
+/// * `token_price` - The price of the backstop token, expressed in 7 decimals
+/// * `premium_cap` - The maximum premium of the lot value over the bid value, expressed in 7 decimals
+fn calc_max_lot_amount(
+    e: &Env,
+    pool: &mut Pool,
+    bid: &Map<Address, i128>,
+    token_price: i128,
+    premium_cap: u32,
+) -> i128 {
+    let oracle_scalar = 10i128.pow(pool.load_price_decimals(e));
+    let mut bid_value = 0; // expressed in the oracle's decimals
+    for (asset, d_tokens) in bid.iter() {
+        let reserve = pool.load_reserve(e, &asset, false);
+        let asset_to_base = pool.load_price(e, &asset);
+        bid_value += asset_to_base.fixed_mul_floor(
+            e,
+            &reserve.to_asset_from_d_token(e, d_tokens),
+            &reserve.scalar,
+        );
+    }
+    let bid_value_usd = pool.load_usd_value(e, bid_value);
+    bid_value_usd // oracle_scalar
+        .fixed_mul_floor(e, &(SCALAR_7 + i128(premium_cap)), &oracle_scalar) // denom of oracle_scalar means result is SCALAR_7
+        .fixed_div_floor(e, &token_price, &SCALAR_7) // token_price is SCALAR_7
+}
+
 #[cfg(test)]
 mod tests {
 
     use crate::{
         auctions::auction::AuctionType,
         pool::Positions,
-        storage::PoolConfig,
+        storage::{AuctionConfig, PoolConfig},
         testutils::{self, create_pool},
     };
 
@@ -400,6 +495,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -508,6 +605,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -603,6 +702,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -698,6 +799,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -792,6 +895,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -920,6 +1025,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 3,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -940,6 +1047,143 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_create_bad_debt_auction_max_positions_exempt() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+        e.cost_estimate().budget().reset_unlimited(); // setup exhausts budget
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 50,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool_address = create_pool(&e);
+
+        let (blnd, blnd_client) = testutils::create_blnd_token(&e, &pool_address, &bombadil);
+        let (usdc, usdc_client) = testutils::create_token_contract(&e, &bombadil);
+        let (lp_token, lp_token_client) =
+            testutils::create_comet_lp_pool(&e, &bombadil, &blnd, &usdc);
+        let (backstop_address, backstop_client) =
+            testutils::create_backstop(&e, &pool_address, &lp_token, &usdc, &blnd);
+        // mint lp tokens
+        blnd_client.mint(&samwise, &500_001_0000000);
+        blnd_client.approve(&samwise, &lp_token, &i128::MAX, &99999);
+        usdc_client.mint(&samwise, &12_501_0000000);
+        usdc_client.approve(&samwise, &lp_token, &i128::MAX, &99999);
+        lp_token_client.join_pool(
+            &50_000_0000000,
+            &vec![&e, 500_001_0000000, 12_501_0000000],
+            &samwise,
+        );
+        backstop_client.deposit(&samwise, &pool_address, &50_000_0000000);
+
+        let (oracle_id, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.d_rate = 1_100_000_000_000;
+        reserve_data_0.last_time = 12345;
+        reserve_config_0.index = 0;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.d_rate = 1_200_000_000_000;
+        reserve_data_1.last_time = 12345;
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+
+        let (underlying_2, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_2, mut reserve_data_2) = testutils::default_reserve_meta();
+        reserve_data_2.b_rate = 1_100_000_000_000;
+        reserve_data_2.last_time = 12345;
+        reserve_config_2.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_2,
+            &reserve_config_2,
+            &reserve_data_2,
+        );
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+                Asset::Stellar(underlying_2.clone()),
+                Asset::Stellar(usdc),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 2_0000000, 4_0000000, 100_0000000, 1_0000000]);
+
+        let positions: Positions = Positions {
+            collateral: map![&e],
+            liabilities: map![
+                &e,
+                (reserve_config_0.index, 10_0000000),
+                (reserve_config_1.index, 2_5000000),
+                (reserve_config_2.index, 2_5000000)
+            ],
+            supply: map![&e],
+        };
+
+        let pool_config = PoolConfig {
+            oracle: oracle_id,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 3,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_user_positions(&e, &backstop_address, &positions);
+            storage::set_backstop_positions_exempt(&e, true);
+
+            let result = create_bad_debt_auction_data(
+                &e,
+                &backstop_address,
+                &vec![
+                    &e,
+                    underlying_0.clone(),
+                    underlying_1.clone(),
+                    underlying_2.clone(),
+                ],
+                &vec![&e, lp_token.clone()],
+                100,
+            );
+            assert_eq!(result.bid.len(), 3);
+            assert_eq!(result.lot.len(), 1);
+        });
+    }
+
     #[test]
     fn test_create_bad_debt_auction() {
         let e = Env::default();
@@ -1051,6 +1295,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 3,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -1190,6 +1436,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -1323,6 +1571,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -1456,6 +1706,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -1590,6 +1842,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -1724,6 +1978,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -1829,6 +2085,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         let mut auction_data = AuctionData {
             bid: map![&e, (underlying_0, 10_0000000), (underlying_1, 2_5000000)],
@@ -1883,6 +2141,362 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_fill_bad_debt_auction_caps_lot_premium() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+        e.cost_estimate().budget().reset_unlimited(); // setup exhausts budget
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 51,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+
+        let (blnd, blnd_client) = testutils::create_blnd_token(&e, &pool_address, &bombadil);
+        let (usdc, usdc_client) = testutils::create_token_contract(&e, &bombadil);
+        let (lp_token, lp_token_client) =
+            testutils::create_comet_lp_pool(&e, &bombadil, &blnd, &usdc);
+        let (backstop_address, backstop_client) =
+            testutils::create_backstop(&e, &pool_address, &lp_token, &usdc, &blnd);
+        // mint lp tokens
+        blnd_client.mint(&samwise, &500_001_0000000);
+        blnd_client.approve(&samwise, &lp_token, &i128::MAX, &99999);
+        usdc_client.mint(&samwise, &12_501_0000000);
+        usdc_client.approve(&samwise, &lp_token, &i128::MAX, &99999);
+        lp_token_client.join_pool(
+            &50_000_0000000,
+            &vec![&e, 500_001_0000000, 12_501_0000000],
+            &samwise,
+        );
+        backstop_client.deposit(&samwise, &pool_address, &50_000_0000000);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.d_rate = 1_100_000_000_000;
+        reserve_data_0.last_time = 12345;
+        reserve_config_0.index = 0;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.d_rate = 1_200_000_000_000;
+        reserve_data_1.last_time = 12345;
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+
+        let (underlying_2, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_2, mut reserve_data_2) = testutils::default_reserve_meta();
+        reserve_data_2.b_rate = 1_100_000_000_000;
+        reserve_data_2.last_time = 12345;
+        reserve_config_2.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_2,
+            &reserve_config_2,
+            &reserve_data_2,
+        );
+        let (oracle_id, oracle_client) = testutils::create_mock_oracle(&e);
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 1_0000000]);
+        let pool_config = PoolConfig {
+            oracle: oracle_id,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        let mut auction_data = AuctionData {
+            bid: map![&e, (underlying_0, 10_0000000), (underlying_1, 2_5000000)],
+            lot: map![&e, (lp_token.clone(), 47_6000000)],
+            block: 51,
+        };
+        let positions: Positions = Positions {
+            collateral: map![&e],
+            liabilities: map![
+                &e,
+                (reserve_config_0.index, 10_0000000),
+                (reserve_config_1.index, 2_5000000)
+            ],
+            supply: map![&e],
+        };
+
+        e.as_contract(&pool_address, || {
+            storage::set_auction(
+                &e,
+                &(AuctionType::BadDebtAuction as u32),
+                &backstop_address,
+                &auction_data,
+            );
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_user_positions(&e, &backstop_address, &positions);
+            storage::set_auction_config(
+                &e,
+                &AuctionConfig {
+                    start_delay: 0,
+                    soft_liq_max_value: 0,
+                    soft_liq_bonus: 0,
+                    interest_interval: 0,
+                    interest_min_value: 0,
+                    bad_debt_premium_cap: 0_1000000,
+                    bad_debt_bid_premium: 0,
+                    liq_curve: 0,
+                    bad_debt_curve: 0,
+                    interest_curve: 0,
+                    min_fill_pct: 0,
+                    liq_target_hf: 0,
+                },
+            );
+
+            let mut pool = Pool::load(&e);
+            let mut samwise_state = User::load(&e, &samwise);
+            fill_bad_debt_auction(&e, &mut pool, &mut auction_data, &mut samwise_state, true);
+            // bid value: 10 * 1.1 + 2.5 * 1.2 = 14, lot value capped at 14 * 1.1 = 15.4
+            // at a spot price of 1.25 per backstop token
+            assert_eq!(
+                lp_token_client.balance(&backstop_address),
+                50_000_0000000 - 12_3200000
+            );
+            assert_eq!(lp_token_client.balance(&samwise), 12_3200000);
+            let samwise_positions = samwise_state.positions;
+            assert_eq!(
+                samwise_positions
+                    .liabilities
+                    .get(reserve_config_0.index)
+                    .unwrap(),
+                10_0000000
+            );
+            assert_eq!(
+                samwise_positions
+                    .liabilities
+                    .get(reserve_config_1.index)
+                    .unwrap(),
+                2_5000000
+            );
+            let backstop_positions = storage::get_user_positions(&e, &backstop_address);
+            assert_eq!(backstop_positions.liabilities.len(), 0);
+        });
+    }
+
+    #[test]
+    fn test_fill_bad_debt_auction_bid_premium() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+        e.cost_estimate().budget().reset_unlimited(); // setup exhausts budget
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 51,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+
+        let (blnd, blnd_client) = testutils::create_blnd_token(&e, &pool_address, &bombadil);
+        let (usdc, usdc_client) = testutils::create_token_contract(&e, &bombadil);
+        let (lp_token, lp_token_client) =
+            testutils::create_comet_lp_pool(&e, &bombadil, &blnd, &usdc);
+        let (backstop_address, backstop_client) =
+            testutils::create_backstop(&e, &pool_address, &lp_token, &usdc, &blnd);
+        // mint lp tokens
+        blnd_client.mint(&samwise, &500_001_0000000);
+        blnd_client.approve(&samwise, &lp_token, &i128::MAX, &99999);
+        usdc_client.mint(&samwise, &12_501_0000000);
+        usdc_client.approve(&samwise, &lp_token, &i128::MAX, &99999);
+        lp_token_client.join_pool(
+            &50_000_0000000,
+            &vec![&e, 500_001_0000000, 12_501_0000000],
+            &samwise,
+        );
+        backstop_client.deposit(&samwise, &pool_address, &50_000_0000000);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.d_rate = 1_100_000_000_000;
+        reserve_data_0.last_time = 12345;
+        reserve_config_0.index = 0;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.d_rate = 1_200_000_000_000;
+        reserve_data_1.last_time = 12345;
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+
+        let (underlying_2, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_2, mut reserve_data_2) = testutils::default_reserve_meta();
+        reserve_data_2.b_rate = 1_100_000_000_000;
+        reserve_data_2.last_time = 12345;
+        reserve_config_2.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_2,
+            &reserve_config_2,
+            &reserve_data_2,
+        );
+        let (oracle_id, oracle_client) = testutils::create_mock_oracle(&e);
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 1_0000000]);
+        let pool_config = PoolConfig {
+            oracle: oracle_id,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        let mut auction_data = AuctionData {
+            bid: map![
+                &e,
+                (underlying_0.clone(), 10_0000000),
+                (underlying_1.clone(), 2_5000000)
+            ],
+            lot: map![&e, (lp_token.clone(), 47_6000000)],
+            block: 51,
+        };
+        let positions: Positions = Positions {
+            collateral: map![&e],
+            liabilities: map![
+                &e,
+                (reserve_config_0.index, 10_0000000),
+                (reserve_config_1.index, 2_5000000)
+            ],
+            supply: map![&e],
+        };
+
+        e.as_contract(&pool_address, || {
+            storage::set_auction(
+                &e,
+                &(AuctionType::BadDebtAuction as u32),
+                &backstop_address,
+                &auction_data,
+            );
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_user_positions(&e, &backstop_address, &positions);
+            storage::set_auction_config(
+                &e,
+                &AuctionConfig {
+                    start_delay: 0,
+                    soft_liq_max_value: 0,
+                    soft_liq_bonus: 0,
+                    interest_interval: 0,
+                    interest_min_value: 0,
+                    bad_debt_premium_cap: 0,
+                    bad_debt_bid_premium: 0_0500000,
+                    liq_curve: 0,
+                    bad_debt_curve: 0,
+                    interest_curve: 0,
+                    min_fill_pct: 0,
+                    liq_target_hf: 0,
+                },
+            );
+
+            let mut pool = Pool::load(&e);
+            let pre_reserve_0 = pool.load_reserve(&e, &underlying_0, false);
+            let pre_reserve_1 = pool.load_reserve(&e, &underlying_1, false);
+            let mut samwise_state = User::load(&e, &samwise);
+            fill_bad_debt_auction(&e, &mut pool, &mut auction_data, &mut samwise_state, true);
+            pool.store_cached_reserves(&e);
+            // auction is filled on its start block, so the filler takes on the bid plus 5%
+            assert_eq!(lp_token_client.balance(&samwise), 47_6000000);
+            let samwise_positions = samwise_state.positions;
+            assert_eq!(
+                samwise_positions.liabilities,
+                map![
+                    &e,
+                    (reserve_config_0.index, 10_5000000),
+                    (reserve_config_1.index, 2_6250000)
+                ]
+            );
+            let backstop_positions = storage::get_user_positions(&e, &backstop_address);
+            assert_eq!(backstop_positions.liabilities.len(), 0);
+
+            // the premium is credited to the backstop
+            let reserve_0 = pool.load_reserve(&e, &underlying_0, false);
+            let reserve_1 = pool.load_reserve(&e, &underlying_1, false);
+            assert_eq!(
+                reserve_0.data.backstop_credit - pre_reserve_0.data.backstop_credit,
+                0_5500000
+            );
+            assert_eq!(
+                reserve_1.data.backstop_credit - pre_reserve_1.data.backstop_credit,
+                0_1500000
+            );
+            assert_eq!(
+                reserve_0.data.d_supply - pre_reserve_0.data.d_supply,
+                0_5000000
+            );
+        });
+    }
+
     #[test]
     fn test_fill_bad_debt_auction_leftover_debt_small_backstop_burns() {
         let e = Env::default();
@@ -1966,6 +2580,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         let mut auction_data = AuctionData {
             bid: map![
@@ -2125,6 +2741,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         let mut auction_data = AuctionData {
             bid: map![
@@ -2286,6 +2904,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         let mut auction_data = AuctionData {
             bid: map![
@@ -2456,6 +3076,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         let mut auction_data = AuctionData {
             bid: map![&e],
@@ -2595,6 +3217,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         let mut auction_data = AuctionData {
             bid: map![&e, (underlying_0, 10_0000000), (underlying_1, 2_5000000)],
@@ -0,0 +1,310 @@
+use sep_41_token::TokenClient;
+use soroban_sdk::{Address, Env, Map};
+
+use crate::{
+    dependencies::Backstop,
+    errors::PoolError,
+    pool::{Pool, PositionData, Positions, RequestType},
+    storage,
+};
+
+use super::{
+    bad_debt_auction::calc_filler_bid, fill_commit::requires_fill_commitment, scale_auction,
+    user_liquidation_auction::cap_liq_fill_percent, AuctionType,
+};
+
+/// Check if a fill of an auction would succeed, without modifying any state.
+///
+/// Returns Ok if the fill would succeed, or the error the fill would fail with. The filler's
+/// positions are validated as if the fill was the only request submitted.
+///
+/// ### Arguments
+/// * `auction_type` - The type of auction to fill
+/// * `user` - The user involved in the auction
+/// * `filler` - The address filling the auction
+/// * `percent_filled` - The percentage being filled as a number (i.e. 15 => 15%)
+pub fn check_fill(
+    e: &Env,
+    auction_type: u32,
+    user: &Address,
+    filler: &Address,
+    percent_filled: u64,
+) -> Result<(), PoolError> {
+    if auction_type > AuctionType::InterestAuction as u32
+        || percent_filled > 100
+        || percent_filled == 0
+    {
+        return Err(PoolError::BadRequest);
+    }
+    if user == filler {
+        return Err(PoolError::InvalidLiquidation);
+    }
+    if !storage::has_auction(e, &auction_type, user) {
+        return Err(PoolError::AuctionNotFound);
+    }
+    let auction_data = storage::get_auction(e, &auction_type, user);
+    if e.ledger().sequence() < auction_data.block {
+        return Err(PoolError::AuctionNotStarted);
+    }
+
+    let mut pool = Pool::load(e);
+    // fill request types are offset from the auction types by the user liquidation fill request
+    if !pool.is_action_allowed(RequestType::FillUserLiquidationAuction as u32 + auction_type) {
+        return Err(PoolError::InvalidPoolStatus);
+    }
+
+    let auction_type = AuctionType::from_u32(e, auction_type);
+    if requires_fill_commitment(e, &mut pool, &auction_type, &auction_data) {
+        return Err(PoolError::InvalidFillCommitment);
+    }
+    let curve = auction_type.curve(e);
+    let auction_config = storage::get_auction_config(e);
+    let mut min_fill_pct = auction_config.min_fill_pct;
+    let mut percent_filled = percent_filled;
+    if auction_type == AuctionType::UserLiquidation {
+        let capped_percent = cap_liq_fill_percent(
+            e,
+            &mut pool,
+            &auction_data,
+            user,
+            curve,
+            percent_filled,
+            auction_config.liq_target_hf,
+        );
+        if capped_percent < percent_filled {
+            percent_filled = capped_percent;
+            min_fill_pct = 0;
+        }
+    }
+    let (to_fill_auction, _) = scale_auction(e, &auction_data, percent_filled, curve, min_fill_pct);
+    match auction_type {
+        AuctionType::UserLiquidation => {
+            check_filler_positions(e, pool, filler, &to_fill_auction.lot, &to_fill_auction.bid)
+        }
+        AuctionType::BadDebtAuction => {
+            if filler == &storage::get_backstop(e) {
+                return Err(PoolError::BadRequest);
+            }
+            let filler_bid = calc_filler_bid(e, &to_fill_auction);
+            check_filler_positions(e, pool, filler, &Map::new(e), &filler_bid)
+        }
+        AuctionType::InterestAuction => {
+            let backstop = Backstop::load(e);
+            if filler == &backstop.address {
+                return Err(PoolError::BadRequest);
+            }
+            let backstop_token = backstop.token();
+            let bid_amount = to_fill_auction.bid.get(backstop_token.clone()).unwrap_or(0);
+            if TokenClient::new(e, &backstop_token).balance(filler) < bid_amount {
+                return Err(PoolError::BalanceError);
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Check the filler's positions would be valid after taking on the collateral and liabilities
+///
+/// ### Arguments
+/// * `pool` - The pool
+/// * `filler` - The address filling the auction
+/// * `collateral` - The bTokens the filler receives as collateral
+/// * `liabilities` - The dTokens the filler takes on
+fn check_filler_positions(
+    e: &Env,
+    mut pool: Pool,
+    filler: &Address,
+    collateral: &Map<Address, i128>,
+    liabilities: &Map<Address, i128>,
+) -> Result<(), PoolError> {
+    if storage::has_auction(e, &(AuctionType::UserLiquidation as u32), filler) {
+        return Err(PoolError::AuctionInProgress);
+    }
+
+    let mut positions: Positions = storage::get_user_positions(e, filler);
+    let prev_positions_count = positions.effective_count();
+    for (asset, amount) in collateral.iter() {
+        let index = pool.load_reserve(e, &asset, false).config.index;
+        positions
+            .collateral
+            .set(index, positions.collateral.get(index).unwrap_or(0) + amount);
+    }
+    for (asset, amount) in liabilities.iter() {
+        let index = pool.load_reserve(e, &asset, false).config.index;
+        positions.liabilities.set(
+            index,
+            positions.liabilities.get(index).unwrap_or(0) + amount,
+        );
+    }
+
+    let new_positions_count = positions.effective_count();
+    if new_positions_count > prev_positions_count && pool.config.max_positions < new_positions_count
+    {
+        return Err(PoolError::MaxPositionsExceeded);
+    }
+
+    if !positions.liabilities.is_empty() {
+        let position_data = PositionData::calculate_from_positions(e, &mut pool, &positions);
+        // min is 1.0000100 to prevent rounding errors
+        if position_data.is_hf_under(e, 1_0000100) {
+            return Err(PoolError::InvalidHf);
+        } else if position_data.collateral_base < pool.config.min_collateral {
+            return Err(PoolError::MinCollateralNotMet);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{auctions::AuctionData, storage::PoolConfig, testutils};
+
+    use super::*;
+    use sep_40_oracle::testutils::Asset;
+    use soroban_sdk::{
+        map,
+        testutils::{Address as _, Ledger, LedgerInfo},
+        vec, Symbol,
+    };
+
+    /// Setup a pool with two reserves and a liquidation auction for samwise starting on block 100
+    ///
+    /// Returns (pool, samwise)
+    fn setup_liquidation_auction(e: &Env, max_positions: u32) -> (Address, Address) {
+        e.mock_all_auths();
+        e.cost_estimate().budget().reset_unlimited();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(e);
+        let samwise = Address::generate(e);
+        let pool = testutils::create_pool(e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(e);
+
+        let (underlying_0, _) = testutils::create_token_contract(e, &bombadil);
+        let (reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.last_time = 12345;
+        testutils::create_reserve(e, &pool, &underlying_0, &reserve_config_0, &reserve_data_0);
+
+        let (underlying_1, _) = testutils::create_token_contract(e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_config_1.index = 1;
+        reserve_data_1.last_time = 12345;
+        testutils::create_reserve(e, &pool, &underlying_1, &reserve_config_1, &reserve_data_1);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(e, "USD")),
+            &vec![
+                e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![e, 1_0000000, 1_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(e, &pool_config);
+            storage::set_auction(
+                e,
+                &(AuctionType::UserLiquidation as u32),
+                &samwise,
+                &AuctionData {
+                    bid: map![e, (underlying_1.clone(), 5_0000000)],
+                    lot: map![e, (underlying_0.clone(), 10_0000000)],
+                    block: 100,
+                },
+            );
+        });
+        (pool, samwise)
+    }
+
+    #[test]
+    fn test_check_fill() {
+        let e = Env::default();
+        let (pool, samwise) = setup_liquidation_auction(&e, 4);
+        let frodo = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            // no lot is available on the start block, so the filler only takes on debt
+            assert_eq!(
+                check_fill(&e, 0, &samwise, &frodo, 100),
+                Err(PoolError::InvalidHf)
+            );
+
+            e.ledger().set_sequence_number(300);
+            assert_eq!(check_fill(&e, 0, &samwise, &frodo, 100), Ok(()));
+            assert_eq!(check_fill(&e, 0, &samwise, &frodo, 50), Ok(()));
+        });
+    }
+
+    #[test]
+    fn test_check_fill_invalid_requests() {
+        let e = Env::default();
+        let (pool, samwise) = setup_liquidation_auction(&e, 4);
+        let frodo = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            assert_eq!(
+                check_fill(&e, 3, &samwise, &frodo, 100),
+                Err(PoolError::BadRequest)
+            );
+            assert_eq!(
+                check_fill(&e, 0, &samwise, &frodo, 101),
+                Err(PoolError::BadRequest)
+            );
+            assert_eq!(
+                check_fill(&e, 0, &samwise, &frodo, 0),
+                Err(PoolError::BadRequest)
+            );
+            assert_eq!(
+                check_fill(&e, 0, &samwise, &samwise, 100),
+                Err(PoolError::InvalidLiquidation)
+            );
+            assert_eq!(
+                check_fill(&e, 0, &frodo, &samwise, 100),
+                Err(PoolError::AuctionNotFound)
+            );
+
+            e.ledger().set_sequence_number(99);
+            assert_eq!(
+                check_fill(&e, 0, &samwise, &frodo, 100),
+                Err(PoolError::AuctionNotStarted)
+            );
+        });
+    }
+
+    #[test]
+    fn test_check_fill_max_positions() {
+        let e = Env::default();
+        let (pool, samwise) = setup_liquidation_auction(&e, 1);
+        let frodo = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            e.ledger().set_sequence_number(300);
+            assert_eq!(
+                check_fill(&e, 0, &samwise, &frodo, 100),
+                Err(PoolError::MaxPositionsExceeded)
+            );
+        });
+    }
+}
@@ -0,0 +1,260 @@
+use soroban_fixed_point_math::SorobanFixedPoint;
+use soroban_sdk::{panic_with_error, xdr::ToXdr, Address, BytesN, Env};
+
+use crate::{
+    constants::MAX_FILL_COMMIT_WINDOW,
+    errors::PoolError,
+    pool::Pool,
+    storage::{self, FillCommitConfig, FillCommitment},
+};
+
+use super::{AuctionData, AuctionType};
+
+/// Set or remove the pool's commit-reveal fill config
+///
+/// ### Arguments
+/// * `config` - The commit-reveal fill config, or None to disable commit-reveal fills
+///
+/// ### Panics
+/// If the min value is negative, or the window is 0 or greater than the max fill commit window
+pub fn execute_set_fill_commit_config(e: &Env, config: &Option<FillCommitConfig>) {
+    if let Some(config) = config {
+        if config.min_value < 0 || config.window == 0 || config.window > MAX_FILL_COMMIT_WINDOW {
+            panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+        }
+    }
+    storage::set_fill_commit_config(e, config);
+}
+
+/// Commit to filling a user liquidation auction. The commitment can be revealed in the blocks after
+/// the current block, up to the pool's fill commit window. Overwrites any existing commitment for the
+/// filler.
+///
+/// ### Arguments
+/// * `filler` - The address committing to the fill
+/// * `hash` - The hash of the fill, as computed by `hash_fill`
+pub fn execute_commit_fill(e: &Env, filler: &Address, hash: &BytesN<32>) {
+    storage::set_fill_commitment(
+        e,
+        filler,
+        &Some(FillCommitment {
+            hash: hash.clone(),
+            block: e.ledger().sequence(),
+        }),
+    );
+}
+
+/// Hash a fill of a user liquidation auction for a fill commitment
+///
+/// ### Arguments
+/// * `user` - The user being liquidated
+/// * `percent_filled` - The percentage being filled as a number (i.e. 15 => 15%)
+/// * `salt` - A secret chosen by the filler, so the fill can't be guessed from the hash
+pub fn hash_fill(e: &Env, user: &Address, percent_filled: u64, salt: &BytesN<32>) -> BytesN<32> {
+    e.crypto()
+        .sha256(&(user.clone(), percent_filled, salt.clone()).to_xdr(e))
+        .to_bytes()
+}
+
+/// Consume a filler's commitment to a fill of a user liquidation auction
+///
+/// ### Arguments
+/// * `filler` - The address filling the auction
+/// * `user` - The user being liquidated
+/// * `percent_filled` - The percentage being filled as a number (i.e. 15 => 15%)
+/// * `salt` - The salt the fill was committed to with
+///
+/// ### Panics
+/// If commit-reveal fills are disabled, the filler has no commitment, the commitment does not match
+/// the fill, or the commitment can't be revealed in the current block
+pub fn consume_fill_commitment(
+    e: &Env,
+    filler: &Address,
+    user: &Address,
+    percent_filled: u64,
+    salt: &BytesN<32>,
+) {
+    let (config, commitment) = match (
+        storage::get_fill_commit_config(e),
+        storage::get_fill_commitment(e, filler),
+    ) {
+        (Some(config), Some(commitment)) => (config, commitment),
+        _ => panic_with_error!(e, PoolError::InvalidFillCommitment),
+    };
+    let block = e.ledger().sequence();
+    if block <= commitment.block
+        || block > commitment.block + config.window
+        || commitment.hash != hash_fill(e, user, percent_filled, salt)
+    {
+        panic_with_error!(e, PoolError::InvalidFillCommitment);
+    }
+    storage::set_fill_commitment(e, filler, &None);
+}
+
+/// Check if filling an auction requires a commitment. User liquidation auctions do if commit-reveal
+/// fills are enabled and the value of the auction's bid is at least the config's min value.
+///
+/// ### Arguments
+/// * `pool` - The pool
+/// * `auction_type` - The type of auction being filled
+/// * `auction_data` - The auction being filled
+pub fn requires_fill_commitment(
+    e: &Env,
+    pool: &mut Pool,
+    auction_type: &AuctionType,
+    auction_data: &AuctionData,
+) -> bool {
+    let config = match storage::get_fill_commit_config(e) {
+        Some(config) if auction_type == &AuctionType::UserLiquidation => config,
+        _ => return false,
+    };
+    let mut bid_value = 0;
+    for (asset, d_tokens) in auction_data.bid.iter() {
+        let reserve = pool.load_reserve(e, &asset, false);
+        let asset_to_base = pool.load_price(e, &asset);
+        bid_value += asset_to_base.fixed_mul_ceil(
+            e,
+            &reserve.to_asset_from_d_token(e, d_tokens),
+            &reserve.scalar,
+        );
+    }
+    bid_value >= config.min_value
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testutils;
+
+    use super::*;
+    use soroban_sdk::testutils::{Address as _, Ledger};
+
+    #[test]
+    fn test_commit_and_consume_fill_commitment() {
+        let e = Env::default();
+        e.ledger().set_sequence_number(100);
+
+        let pool = testutils::create_pool(&e);
+        let frodo = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let salt = BytesN::from_array(&e, &[7u8; 32]);
+
+        e.as_contract(&pool, || {
+            execute_set_fill_commit_config(
+                &e,
+                &Some(FillCommitConfig {
+                    min_value: 1000_0000000,
+                    window: 5,
+                }),
+            );
+            let hash = hash_fill(&e, &samwise, 50, &salt);
+            execute_commit_fill(&e, &frodo, &hash);
+            assert_eq!(
+                storage::get_fill_commitment(&e, &frodo),
+                Some(FillCommitment { hash, block: 100 })
+            );
+        });
+
+        e.ledger().set_sequence_number(105);
+        e.as_contract(&pool, || {
+            consume_fill_commitment(&e, &frodo, &samwise, 50, &salt);
+            assert!(storage::get_fill_commitment(&e, &frodo).is_none());
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1252)")]
+    fn test_consume_fill_commitment_same_block() {
+        let e = Env::default();
+        e.ledger().set_sequence_number(100);
+
+        let pool = testutils::create_pool(&e);
+        let frodo = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let salt = BytesN::from_array(&e, &[7u8; 32]);
+
+        e.as_contract(&pool, || {
+            execute_set_fill_commit_config(
+                &e,
+                &Some(FillCommitConfig {
+                    min_value: 1000_0000000,
+                    window: 5,
+                }),
+            );
+            execute_commit_fill(&e, &frodo, &hash_fill(&e, &samwise, 50, &salt));
+            consume_fill_commitment(&e, &frodo, &samwise, 50, &salt);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1252)")]
+    fn test_consume_fill_commitment_mismatch() {
+        let e = Env::default();
+        e.ledger().set_sequence_number(100);
+
+        let pool = testutils::create_pool(&e);
+        let frodo = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let salt = BytesN::from_array(&e, &[7u8; 32]);
+
+        e.as_contract(&pool, || {
+            execute_set_fill_commit_config(
+                &e,
+                &Some(FillCommitConfig {
+                    min_value: 1000_0000000,
+                    window: 5,
+                }),
+            );
+            execute_commit_fill(&e, &frodo, &hash_fill(&e, &samwise, 50, &salt));
+        });
+
+        e.ledger().set_sequence_number(101);
+        e.as_contract(&pool, || {
+            consume_fill_commitment(&e, &frodo, &samwise, 100, &salt);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1252)")]
+    fn test_consume_fill_commitment_after_window() {
+        let e = Env::default();
+        e.ledger().set_sequence_number(100);
+
+        let pool = testutils::create_pool(&e);
+        let frodo = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let salt = BytesN::from_array(&e, &[7u8; 32]);
+
+        e.as_contract(&pool, || {
+            execute_set_fill_commit_config(
+                &e,
+                &Some(FillCommitConfig {
+                    min_value: 1000_0000000,
+                    window: 5,
+                }),
+            );
+            execute_commit_fill(&e, &frodo, &hash_fill(&e, &samwise, 50, &salt));
+        });
+
+        e.ledger().set_sequence_number(106);
+        e.as_contract(&pool, || {
+            consume_fill_commitment(&e, &frodo, &samwise, 50, &salt);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_set_fill_commit_config_window_over_max() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_fill_commit_config(
+                &e,
+                &Some(FillCommitConfig {
+                    min_value: 0,
+                    window: MAX_FILL_COMMIT_WINDOW + 1,
+                }),
+            );
+        });
+    }
+}
@@ -0,0 +1,241 @@
+use soroban_fixed_point_math::SorobanFixedPoint;
+use soroban_sdk::{Address, Env, Map, Vec};
+
+use crate::{
+    constants::MAX_FILLER_PAGE,
+    pool::Pool,
+    storage::{self, FillerStats},
+};
+
+use super::{AuctionData, AuctionType};
+
+/// Record a fill in the filler's cumulative auction fills, adding the filler to the filler index
+/// if this is their first fill
+///
+/// ### Arguments
+/// * `pool` - The pool
+/// * `auction_type` - The type of auction filled
+/// * `filler` - The address of the filler
+/// * `filled_auction` - The auction data of the portion of the auction filled
+pub fn record_fill(
+    e: &Env,
+    pool: &mut Pool,
+    auction_type: &AuctionType,
+    filler: &Address,
+    filled_auction: &AuctionData,
+) {
+    let mut stats = match storage::get_filler_stats(e, filler) {
+        Some(stats) => stats,
+        None => {
+            let count = storage::get_filler_count(e);
+            storage::set_filler(e, count, filler);
+            storage::set_filler_count(e, count + 1);
+            FillerStats::default()
+        }
+    };
+
+    let fill_stats = match auction_type {
+        AuctionType::UserLiquidation => {
+            stats.liquidation.value += calc_d_token_value(e, pool, &filled_auction.bid);
+            &mut stats.liquidation
+        }
+        AuctionType::BadDebtAuction => {
+            stats.bad_debt.value += calc_d_token_value(e, pool, &filled_auction.bid);
+            &mut stats.bad_debt
+        }
+        AuctionType::InterestAuction => {
+            stats.interest.value += calc_underlying_value(e, pool, &filled_auction.lot);
+            &mut stats.interest
+        }
+    };
+    fill_stats.count += 1;
+    storage::set_filler_stats(e, filler, &stats);
+}
+
+/// Fetch a page of the filler index with each filler's cumulative auction fills
+///
+/// Returns up to `limit` fillers starting at slot `start`, capped at the max filler page size
+///
+/// ### Arguments
+/// * `start` - The first slot of the page
+/// * `limit` - The maximum number of fillers to return
+pub fn get_fillers(e: &Env, start: u32, limit: u32) -> Vec<(Address, FillerStats)> {
+    let count = storage::get_filler_count(e);
+    let end = start.saturating_add(limit.min(MAX_FILLER_PAGE)).min(count);
+    let mut fillers = Vec::new(e);
+    for slot in start..end {
+        if let Some(filler) = storage::get_filler(e, slot) {
+            let stats = storage::get_filler_stats(e, &filler).unwrap_or_default();
+            fillers.push_back((filler, stats));
+        }
+    }
+    fillers
+}
+
+/// Calculate the value of dTokens in the oracle's base asset
+fn calc_d_token_value(e: &Env, pool: &mut Pool, d_tokens: &Map<Address, i128>) -> i128 {
+    let mut value = 0;
+    for (asset, amount) in d_tokens.iter() {
+        let reserve = pool.load_reserve(e, &asset, false);
+        let asset_to_base = pool.load_price(e, &asset);
+        value += asset_to_base.fixed_mul_floor(
+            e,
+            &reserve.to_asset_from_d_token(e, amount),
+            &reserve.scalar,
+        );
+    }
+    value
+}
+
+/// Calculate the value of underlying tokens in the oracle's base asset
+fn calc_underlying_value(e: &Env, pool: &mut Pool, underlying: &Map<Address, i128>) -> i128 {
+    let mut value = 0;
+    for (asset, amount) in underlying.iter() {
+        let reserve = pool.load_reserve(e, &asset, false);
+        let asset_to_base = pool.load_price(e, &asset);
+        value += asset_to_base.fixed_mul_floor(e, &amount, &reserve.scalar);
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        storage::{FillStats, PoolConfig},
+        testutils,
+    };
+
+    use super::*;
+    use sep_40_oracle::testutils::Asset;
+    use soroban_sdk::{
+        map,
+        testutils::{Address as _, Ledger, LedgerInfo},
+        vec, Symbol,
+    };
+
+    #[test]
+    fn test_record_fill() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.cost_estimate().budget().reset_unlimited();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.d_rate = 1_100_000_000_000;
+        reserve_data_0.last_time = 12345;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config_0, &reserve_data_0);
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_config_1.index = 1;
+        reserve_data_1.last_time = 12345;
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config_1, &reserve_data_1);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 2_0000000, 0_5000000]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            let mut pool = Pool::load(&e);
+
+            // liquidations are valued by the liabilities repaid: 10 * 1.1 * 2 = 22
+            let liquidation = AuctionData {
+                bid: map![&e, (underlying_0.clone(), 10_0000000)],
+                lot: map![&e, (underlying_1.clone(), 50_0000000)],
+                block: 100,
+            };
+            record_fill(
+                &e,
+                &mut pool,
+                &AuctionType::UserLiquidation,
+                &samwise,
+                &liquidation,
+            );
+            record_fill(
+                &e,
+                &mut pool,
+                &AuctionType::UserLiquidation,
+                &samwise,
+                &liquidation,
+            );
+
+            // interest auctions are valued by the interest received: 10 * 2 + 20 * 0.5 = 30
+            let interest = AuctionData {
+                bid: map![&e, (Address::generate(&e), 100_0000000)],
+                lot: map![
+                    &e,
+                    (underlying_0.clone(), 10_0000000),
+                    (underlying_1.clone(), 20_0000000)
+                ],
+                block: 100,
+            };
+            record_fill(
+                &e,
+                &mut pool,
+                &AuctionType::InterestAuction,
+                &frodo,
+                &interest,
+            );
+
+            assert_eq!(
+                storage::get_filler_stats(&e, &samwise),
+                Some(FillerStats {
+                    liquidation: FillStats {
+                        count: 2,
+                        value: 44_0000000,
+                    },
+                    bad_debt: FillStats::default(),
+                    interest: FillStats::default(),
+                })
+            );
+            assert_eq!(storage::get_filler_count(&e), 2);
+            let fillers = get_fillers(&e, 1, 10);
+            assert_eq!(fillers.len(), 1);
+            let (filler, stats) = fillers.get_unchecked(0);
+            assert_eq!(filler, frodo);
+            assert_eq!(
+                stats.interest,
+                FillStats {
+                    count: 1,
+                    value: 30_0000000,
+                }
+            );
+            assert_eq!(get_fillers(&e, 2, 10).len(), 0);
+        });
+    }
+}
@@ -1,6 +1,17 @@
 mod auction;
 mod backstop_interest_auction;
+mod backstop_token_price;
 mod bad_debt_auction;
+mod fill_check;
+mod fill_commit;
+mod filler_stats;
+mod soft_liquidation;
 mod user_liquidation_auction;
 
 pub use auction::*;
+pub use fill_check::check_fill;
+pub use fill_commit::{
+    consume_fill_commitment, execute_commit_fill, execute_set_fill_commit_config, hash_fill,
+};
+pub use filler_stats::get_fillers;
+pub use soft_liquidation::soft_liquidate;
@@ -0,0 +1,358 @@
+use crate::{
+    auctions::auction::AuctionData,
+    constants::SCALAR_7,
+    errors::PoolError,
+    pool::{is_liq_protected, Pool, PositionData, User},
+    storage,
+};
+use cast::i128;
+use soroban_fixed_point_math::SorobanFixedPoint;
+use soroban_sdk::{map, panic_with_error, Address, Env};
+
+use super::AuctionType;
+
+/// Soft liquidate a user with a small position. The filler takes on all of the user's liabilities
+/// and instantly receives the user's collateral valued at the oracle price plus the configured bonus,
+/// skipping the dutch auction entirely.
+///
+/// Returns the liquidated positions as AuctionData, where the bid is the dTokens taken on by the filler
+/// and the lot is the bTokens received by the filler
+///
+/// ### Arguments
+/// * `pool` - The pool
+/// * `user` - The user being liquidated
+/// * `filler_state` - The user filling the liquidation
+///
+/// ### Panics
+/// * If the user is not eligible to be liquidated or has no collateral
+/// * If the user's liabilities exceed the soft liquidation threshold
+/// * If a liquidation auction is already in progress for the user
+pub fn soft_liquidate(
+    e: &Env,
+    pool: &mut Pool,
+    user: &Address,
+    filler_state: &mut User,
+) -> AuctionData {
+    if user == &filler_state.address
+        || user == &e.current_contract_address()
+        || user == &storage::get_backstop(e)
+    {
+        panic_with_error!(e, PoolError::InvalidLiquidation);
+    }
+    if storage::has_auction(e, &(AuctionType::UserLiquidation as u32), user) {
+        panic_with_error!(e, PoolError::AuctionInProgress);
+    }
+
+    let mut user_state = User::load(e, user);
+    let position_data = PositionData::calculate_from_positions(e, pool, &user_state.positions);
+    // ensure the user has less collateral than liabilities, and is not protected by insurance. Users
+    // without collateral have bad debt, which is handled by the backstop instead
+    if position_data.liability_base <= position_data.collateral_base
+        || position_data.collateral_raw == 0
+        || is_liq_protected(e, user, &position_data)
+    {
+        panic_with_error!(e, PoolError::InvalidLiquidation);
+    }
+    let auction_config = storage::get_auction_config(e);
+    if position_data.liability_raw > auction_config.soft_liq_max_value {
+        panic_with_error!(e, PoolError::InvalidLiqTooLarge);
+    }
+
+    // determine the percent of the user's collateral required to cover their liabilities plus the bonus
+    let seized_value = position_data.liability_raw.fixed_mul_ceil(
+        e,
+        &(SCALAR_7 + i128(auction_config.soft_liq_bonus)),
+        &SCALAR_7,
+    );
+    let seized_pct = seized_value
+        .fixed_div_floor(e, &position_data.collateral_raw, &position_data.scalar)
+        .min(position_data.scalar);
+
+    let reserve_list = storage::get_res_list(e);
+    let mut liquidated = AuctionData {
+        bid: map![e],
+        lot: map![e],
+        block: e.ledger().sequence(),
+    };
+    for (index, amount) in user_state.positions.liabilities.iter() {
+        liquidated
+            .bid
+            .set(reserve_list.get_unchecked(index), amount);
+    }
+    for (index, amount) in user_state.positions.collateral.iter() {
+        let seized = amount.fixed_mul_floor(e, &seized_pct, &position_data.scalar);
+        if seized > 0 {
+            liquidated
+                .lot
+                .set(reserve_list.get_unchecked(index), seized);
+        }
+    }
+
+    user_state.rm_positions(e, pool, liquidated.lot.clone(), liquidated.bid.clone());
+    filler_state.add_positions(e, pool, liquidated.lot.clone(), liquidated.bid.clone());
+    user_state.store(e);
+
+    liquidated
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        storage::{AuctionConfig, PoolConfig},
+        testutils, Positions,
+    };
+
+    use super::*;
+    use sep_40_oracle::testutils::Asset;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger, LedgerInfo},
+        vec, Symbol,
+    };
+
+    /// Setup a pool with an unhealthy user with a small position
+    ///
+    /// Returns (pool, samwise, underlying_0, underlying_1)
+    fn setup_small_position(
+        e: &Env,
+        soft_liq_max_value: i128,
+    ) -> (Address, Address, Address, Address) {
+        e.mock_all_auths();
+        e.cost_estimate().budget().reset_unlimited();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(e);
+        let samwise = Address::generate(e);
+        let pool = testutils::create_pool(e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(e);
+
+        let (underlying_0, _) = testutils::create_token_contract(e, &bombadil);
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_config_0.c_factor = 0_8500000;
+        reserve_data_0.last_time = 12345;
+        testutils::create_reserve(e, &pool, &underlying_0, &reserve_config_0, &reserve_data_0);
+
+        let (underlying_1, _) = testutils::create_token_contract(e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_config_1.index = 1;
+        reserve_data_1.last_time = 12345;
+        testutils::create_reserve(e, &pool, &underlying_1, &reserve_config_1, &reserve_data_1);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(e, "USD")),
+            &vec![
+                e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![e, 2_0000000, 4_0000000]);
+
+        // collateral base: 15 * 2 * 0.85 = 25.5, liability base: 5 * 4 / 0.75 = 26.67
+        let positions = Positions {
+            collateral: map![e, (0, 15_0000000)],
+            liabilities: map![e, (1, 5_0000000)],
+            supply: map![e],
+        };
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_backstop(e, &Address::generate(e));
+            storage::set_pool_config(e, &pool_config);
+            storage::set_user_positions(e, &samwise, &positions);
+            storage::set_auction_config(
+                e,
+                &AuctionConfig {
+                    start_delay: 0,
+                    soft_liq_max_value,
+                    soft_liq_bonus: 0_1000000,
+                    interest_interval: 0,
+                    interest_min_value: 0,
+                    bad_debt_premium_cap: 0,
+                    bad_debt_bid_premium: 0,
+                    liq_curve: 0,
+                    bad_debt_curve: 0,
+                    interest_curve: 0,
+                    min_fill_pct: 0,
+                    liq_target_hf: 0,
+                },
+            );
+        });
+        (pool, samwise, underlying_0, underlying_1)
+    }
+
+    #[test]
+    fn test_soft_liquidate() {
+        let e = Env::default();
+        let (pool_address, samwise, underlying_0, underlying_1) =
+            setup_small_position(&e, 100_0000000);
+        let frodo = Address::generate(&e);
+
+        e.as_contract(&pool_address, || {
+            let mut pool = Pool::load(&e);
+            let mut frodo_state = User::load(&e, &frodo);
+
+            let liquidated = soft_liquidate(&e, &mut pool, &samwise, &mut frodo_state);
+            // seized value: 20 * 1.1 = 22, seized pct: 22 / 30 = 0.7333333
+            assert_eq!(liquidated.bid, map![&e, (underlying_1.clone(), 5_0000000)]);
+            assert_eq!(liquidated.lot, map![&e, (underlying_0.clone(), 10_9999995)]);
+            assert_eq!(liquidated.block, 100);
+
+            assert_eq!(frodo_state.positions.liabilities, map![&e, (1, 5_0000000)]);
+            assert_eq!(frodo_state.positions.collateral, map![&e, (0, 10_9999995)]);
+            let samwise_positions = storage::get_user_positions(&e, &samwise);
+            assert_eq!(samwise_positions.liabilities.len(), 0);
+            assert_eq!(samwise_positions.collateral, map![&e, (0, 4_0000005)]);
+        });
+    }
+
+    #[test]
+    fn test_soft_liquidate_caps_at_all_collateral() {
+        let e = Env::default();
+        let (pool_address, samwise, underlying_0, underlying_1) =
+            setup_small_position(&e, 100_0000000);
+        let frodo = Address::generate(&e);
+
+        e.as_contract(&pool_address, || {
+            storage::set_user_positions(
+                &e,
+                &samwise,
+                &Positions {
+                    collateral: map![&e, (0, 10_0000000)],
+                    liabilities: map![&e, (1, 5_0000000)],
+                    supply: map![&e],
+                },
+            );
+            let mut pool = Pool::load(&e);
+            let mut frodo_state = User::load(&e, &frodo);
+
+            let liquidated = soft_liquidate(&e, &mut pool, &samwise, &mut frodo_state);
+            assert_eq!(liquidated.bid, map![&e, (underlying_1.clone(), 5_0000000)]);
+            assert_eq!(liquidated.lot, map![&e, (underlying_0.clone(), 10_0000000)]);
+
+            let samwise_positions = storage::get_user_positions(&e, &samwise);
+            assert_eq!(samwise_positions.liabilities.len(), 0);
+            assert_eq!(samwise_positions.collateral.len(), 0);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1213)")]
+    fn test_soft_liquidate_too_large() {
+        let e = Env::default();
+        let (pool_address, samwise, _, _) = setup_small_position(&e, 19_9999999);
+        let frodo = Address::generate(&e);
+
+        e.as_contract(&pool_address, || {
+            let mut pool = Pool::load(&e);
+            let mut frodo_state = User::load(&e, &frodo);
+
+            soft_liquidate(&e, &mut pool, &samwise, &mut frodo_state);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1211)")]
+    fn test_soft_liquidate_healthy_user() {
+        let e = Env::default();
+        let (pool_address, samwise, _, _) = setup_small_position(&e, 100_0000000);
+        let frodo = Address::generate(&e);
+
+        e.as_contract(&pool_address, || {
+            storage::set_user_positions(
+                &e,
+                &samwise,
+                &Positions {
+                    collateral: map![&e, (0, 16_0000000)],
+                    liabilities: map![&e, (1, 5_0000000)],
+                    supply: map![&e],
+                },
+            );
+            let mut pool = Pool::load(&e);
+            let mut frodo_state = User::load(&e, &frodo);
+
+            soft_liquidate(&e, &mut pool, &samwise, &mut frodo_state);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1211)")]
+    fn test_soft_liquidate_no_collateral() {
+        let e = Env::default();
+        let (pool_address, samwise, _, _) = setup_small_position(&e, 100_0000000);
+        let frodo = Address::generate(&e);
+
+        e.as_contract(&pool_address, || {
+            storage::set_user_positions(
+                &e,
+                &samwise,
+                &Positions {
+                    collateral: map![&e],
+                    liabilities: map![&e, (1, 5_0000000)],
+                    supply: map![&e],
+                },
+            );
+            let mut pool = Pool::load(&e);
+            let mut frodo_state = User::load(&e, &frodo);
+
+            soft_liquidate(&e, &mut pool, &samwise, &mut frodo_state);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1211)")]
+    fn test_soft_liquidate_self() {
+        let e = Env::default();
+        let (pool_address, samwise, _, _) = setup_small_position(&e, 100_0000000);
+
+        e.as_contract(&pool_address, || {
+            let mut pool = Pool::load(&e);
+            let mut samwise_state = User::load(&e, &samwise);
+
+            soft_liquidate(&e, &mut pool, &samwise, &mut samwise_state);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1212)")]
+    fn test_soft_liquidate_auction_in_progress() {
+        let e = Env::default();
+        let (pool_address, samwise, _, _) = setup_small_position(&e, 100_0000000);
+        let frodo = Address::generate(&e);
+
+        e.as_contract(&pool_address, || {
+            storage::set_auction(
+                &e,
+                &(AuctionType::UserLiquidation as u32),
+                &samwise,
+                &AuctionData {
+                    bid: map![&e],
+                    lot: map![&e],
+                    block: 100,
+                },
+            );
+            let mut pool = Pool::load(&e);
+            let mut frodo_state = User::load(&e, &frodo);
+
+            soft_liquidate(&e, &mut pool, &samwise, &mut frodo_state);
+        });
+    }
+}
@@ -1,13 +1,15 @@
+use auction_math::AuctionCurve;
 use cast::i128;
 use soroban_fixed_point_math::SorobanFixedPoint;
 use soroban_sdk::{map, panic_with_error, Address, Env, Vec};
 
 use crate::auctions::auction::AuctionData;
-use crate::pool::{check_and_handle_user_bad_debt, Pool, PositionData, User};
+use crate::constants::SCALAR_7;
+use crate::pool::{check_and_handle_user_bad_debt, is_liq_protected, Pool, PositionData, User};
 use crate::Positions;
 use crate::{errors::PoolError, storage};
 
-use super::AuctionType;
+use super::{scale_auction, AuctionType};
 
 pub fn create_user_liq_auction_data(
     e: &Env,
@@ -15,11 +17,25 @@ pub fn create_user_liq_auction_data(
     bid: &Vec<Address>,
     lot: &Vec<Address>,
     percent: u32,
+) -> AuctionData {
+    build_user_liq_auction_data(e, user, bid, lot, percent, false)
+}
+
+/// Build the auction data for a user liquidation. A preview does not require the user to be
+/// liquidatable, to have no liquidation in progress, or the liquidation to leave the user's
+/// health factor between 1.03 and 1.15.
+pub fn build_user_liq_auction_data(
+    e: &Env,
+    user: &Address,
+    bid: &Vec<Address>,
+    lot: &Vec<Address>,
+    percent: u32,
+    preview: bool,
 ) -> AuctionData {
     if user == &e.current_contract_address() || user == &storage::get_backstop(e) {
         panic_with_error!(e, PoolError::InvalidLiquidation);
     }
-    if storage::has_auction(e, &(AuctionType::UserLiquidation as u32), user) {
+    if !preview && storage::has_auction(e, &(AuctionType::UserLiquidation as u32), user) {
         panic_with_error!(e, PoolError::AuctionInProgress);
     }
     if percent > 100 || percent == 0 {
@@ -46,8 +62,11 @@ pub fn create_user_liq_auction_data(
     let reserve_list = storage::get_res_list(e);
     let position_data = PositionData::calculate_from_positions(e, &mut pool, &user_state.positions);
 
-    // ensure the user has less collateral than liabilities
-    if position_data.liability_base <= position_data.collateral_base {
+    // ensure the user has less collateral than liabilities, and is not protected by insurance
+    if !preview
+        && (position_data.liability_base <= position_data.collateral_base
+            || is_liq_protected(e, user, &position_data))
+    {
         panic_with_error!(e, PoolError::InvalidLiquidation);
     }
 
@@ -149,6 +168,23 @@ pub fn create_user_liq_auction_data(
         }
     }
 
+    // the lot must hold at least the share of the user's collateral that the bid removes from the
+    // user's liabilities, so the lot can't be cherry-picked from the user's best collateral
+    if !is_all_collateral {
+        let lot_share = position_data_inc.collateral_raw.fixed_div_floor(
+            e,
+            &position_data.collateral_raw,
+            &position_data.scalar,
+        );
+        let bid_share = position_data_inc
+            .liability_raw
+            .fixed_mul_ceil(e, &percent_liquidated_i128_scaled, &position_data.scalar)
+            .fixed_div_ceil(e, &position_data.liability_raw, &position_data.scalar);
+        if lot_share < bid_share {
+            panic_with_error!(e, PoolError::InvalidLot);
+        }
+    }
+
     for (asset, amount) in positions_auctioned.collateral.iter() {
         let res_asset_address = reserve_list.get_unchecked(asset);
         let b_tokens_removed =
@@ -183,12 +219,15 @@ pub fn create_user_liq_auction_data(
         // 95% liquidation is not too large. That is, if a user can be liquidated to 95%, they can
         // be liquidated fully. This helps prevent edge cases due to liquidation percentages
         // being harder to calculate between as it approaches 100.
-        if est_withdrawn_collateral < position_data.collateral_raw
+        if !preview
+            && est_withdrawn_collateral < position_data.collateral_raw
             && new_data.is_hf_over(e, 1_1500000)
         {
             panic_with_error!(e, PoolError::InvalidLiqTooLarge)
         };
         full_liquidation_quote
+    } else if preview {
+        liquidation_quote
     } else {
         // Post-liq health factor must be under 1.15
         if new_data.is_hf_over(e, 1_1500000) {
@@ -203,6 +242,67 @@ pub fn create_user_liq_auction_data(
     }
 }
 
+/// Cap the percent of a user liquidation auction a partial fill can take, so the fill can't push the
+/// user's health factor far above the target health factor. The percent is rounded up, so a capped
+/// fill reaches the target.
+///
+/// Returns the percent to fill, which is at most `percent_filled`. Returns 1 if the user is already
+/// at or above the target.
+///
+/// ### Arguments
+/// * `auction_data` - The unscaled auction being filled
+/// * `user` - The user being liquidated
+/// * `curve` - The curve the auction scales along
+/// * `percent_filled` - The percentage being filled as a number (i.e. 15 => 15%)
+/// * `target_hf` - The target health factor, expressed in 7 decimals, or 0 to disable the cap
+pub fn cap_liq_fill_percent(
+    e: &Env,
+    pool: &mut Pool,
+    auction_data: &AuctionData,
+    user: &Address,
+    curve: AuctionCurve,
+    percent_filled: u64,
+    target_hf: u32,
+) -> u64 {
+    if target_hf == 0 || percent_filled == 0 || percent_filled >= 100 {
+        return percent_filled;
+    }
+    let (full_auction, _) = scale_auction(e, auction_data, 100, curve, 0);
+    let mut positions_auctioned = Positions::env_default(e);
+    for (asset, b_tokens) in full_auction.lot.iter() {
+        let reserve = pool.load_reserve(e, &asset, false);
+        positions_auctioned
+            .collateral
+            .set(reserve.config.index, b_tokens);
+    }
+    for (asset, d_tokens) in full_auction.bid.iter() {
+        let reserve = pool.load_reserve(e, &asset, false);
+        positions_auctioned
+            .liabilities
+            .set(reserve.config.index, d_tokens);
+    }
+    let user_state = User::load(e, user);
+    let position_data = PositionData::calculate_from_positions(e, pool, &user_state.positions);
+    let position_data_inc = PositionData::calculate_from_positions(e, pool, &positions_auctioned);
+
+    // filling p of the auction leaves the user with a health factor of
+    // (collateral - p * lot) / (liabilities - p * bid), so solve for the p that reaches the target
+    let target_hf = i128(target_hf);
+    let to_target = target_hf.fixed_mul_floor(e, &position_data.liability_base, &SCALAR_7)
+        - position_data.collateral_base;
+    let per_fill = target_hf.fixed_mul_floor(e, &position_data_inc.liability_base, &SCALAR_7)
+        - position_data_inc.collateral_base;
+    if per_fill <= 0 {
+        // filling the auction does not move the user towards the target
+        return percent_filled;
+    }
+    if to_target <= 0 {
+        return 1;
+    }
+    let max_percent = to_target.fixed_div_ceil(e, &per_fill, &100).max(1);
+    percent_filled.min(max_percent as u64)
+}
+
 pub fn fill_user_liq_auction(
     e: &Env,
     pool: &mut Pool,
@@ -212,8 +312,26 @@ pub fn fill_user_liq_auction(
     is_full_fill: bool,
 ) {
     let mut user_state = User::load(e, user);
-    user_state.rm_positions(e, pool, auction_data.lot.clone(), auction_data.bid.clone());
-    filler_state.add_positions(e, pool, auction_data.lot.clone(), auction_data.bid.clone());
+    if auction_data.lot.len() == 1 && auction_data.bid.len() == 1 {
+        // fast path for the common single asset auction. Each position is moved from the user to
+        // the filler with a single reserve load, instead of iterating the auction once per user.
+        let lot_asset = auction_data.lot.keys().get_unchecked(0);
+        let lot_amount = auction_data.lot.values().get_unchecked(0);
+        let mut lot_reserve = pool.load_reserve(e, &lot_asset, true);
+        user_state.remove_collateral(e, &mut lot_reserve, lot_amount);
+        filler_state.add_collateral(e, &mut lot_reserve, lot_amount);
+        pool.cache_reserve(lot_reserve);
+
+        let bid_asset = auction_data.bid.keys().get_unchecked(0);
+        let bid_amount = auction_data.bid.values().get_unchecked(0);
+        let mut bid_reserve = pool.load_reserve(e, &bid_asset, true);
+        user_state.remove_liabilities(e, &mut bid_reserve, bid_amount);
+        filler_state.add_liabilities(e, &mut bid_reserve, bid_amount);
+        pool.cache_reserve(bid_reserve);
+    } else {
+        user_state.rm_positions(e, pool, auction_data.lot.clone(), auction_data.bid.clone());
+        filler_state.add_positions(e, pool, auction_data.lot.clone(), auction_data.bid.clone());
+    }
 
     if is_full_fill {
         check_and_handle_user_bad_debt(e, pool, user, &mut user_state);
@@ -274,6 +392,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -316,6 +436,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -352,6 +474,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -388,6 +512,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -424,6 +550,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -515,6 +643,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_user_positions(&e, &samwise, &positions);
@@ -614,6 +744,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_user_positions(&e, &samwise, &positions);
@@ -713,6 +845,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_user_positions(&e, &samwise, &positions);
@@ -812,6 +946,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_user_positions(&e, &samwise, &positions);
@@ -927,6 +1063,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_user_positions(&e, &samwise, &positions);
@@ -1041,6 +1179,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_user_positions(&e, &samwise, &positions);
@@ -1142,6 +1282,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_user_positions(&e, &samwise, &positions);
@@ -1243,6 +1385,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_user_positions(&e, &samwise, &positions);
@@ -1350,6 +1494,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_user_positions(&e, &samwise, &positions);
@@ -1461,6 +1607,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_user_positions(&e, &samwise, &positions);
@@ -1616,6 +1764,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         let positions: Positions = Positions {
             collateral: map![
@@ -1732,6 +1882,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         let positions: Positions = Positions {
             collateral: map![
@@ -1848,6 +2000,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         let positions: Positions = Positions {
             collateral: map![
@@ -1962,6 +2116,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_user_positions(&e, &samwise, &positions);
@@ -1984,6 +2140,112 @@ mod tests {
         });
     }
 
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1222)")]
+    fn test_create_user_liquidation_partial_cherry_picked_lot_panics() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.cost_estimate().budget().reset_unlimited();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 50,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+        let (oracle_address, oracle_client) = testutils::create_mock_oracle(&e);
+        let backstop_address = Address::generate(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.last_time = 12345;
+        reserve_data_0.b_rate = 1_100_000_000_000;
+        reserve_data_0.d_rate = 1_150_000_000_000;
+        reserve_config_0.c_factor = 0_8500000;
+        reserve_config_0.l_factor = 0_9000000;
+        reserve_config_0.index = 0;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.last_time = 12345;
+        reserve_data_1.b_rate = 1_200_000_000_000;
+        reserve_data_1.d_rate = 1_300_000_000_000;
+        reserve_config_1.c_factor = 0_8000000;
+        reserve_config_1.l_factor = 0_7500000;
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 2_0000000, 4_0000000]);
+
+        // user holds most collateral in asset 0, but the liquidator only includes asset 1 in the lot
+        // -> lot holds ~30% of the collateral, bid removes 35% of the liabilities
+        let liq_pct = 35;
+        let positions: Positions = Positions {
+            collateral: map![
+                &e,
+                (reserve_config_0.index, 100_0000000),
+                (reserve_config_1.index, 20_0000000),
+            ],
+            liabilities: map![&e, (reserve_config_1.index, 40_0000000),],
+            supply: map![&e],
+        };
+        let pool_config = PoolConfig {
+            oracle: oracle_address,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_user_positions(&e, &samwise, &positions);
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_backstop(&e, &backstop_address);
+
+            create_user_liq_auction_data(
+                &e,
+                &samwise,
+                &vec![&e, underlying_1.clone()],
+                &vec![&e, underlying_1.clone()],
+                liq_pct,
+            );
+        });
+    }
+
     #[test]
     fn test_create_user_liquidation_partial_100() {
         let e = Env::default();
@@ -2073,6 +2335,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_user_positions(&e, &samwise, &positions);
@@ -2182,6 +2446,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_user_positions(&e, &samwise, &positions);
@@ -2293,6 +2559,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_user_positions(&e, &samwise, &positions);
@@ -2397,6 +2665,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_user_positions(&e, &samwise, &positions);
@@ -2492,6 +2762,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_address, || {
             storage::set_user_positions(&e, &samwise, &positions);
@@ -2609,6 +2881,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         let positions: Positions = Positions {
             collateral: map![
@@ -2690,6 +2964,181 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_fill_user_liquidation_auction_single_asset() {
+        let e = Env::default();
+
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 175,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 17280,
+            min_persistent_entry_ttl: 17280,
+            max_entry_ttl: 9999999,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+
+        let (oracle_address, oracle_client) = testutils::create_mock_oracle(&e);
+
+        // creating reserves for a pool exhausts the budget
+        e.cost_estimate().budget().reset_unlimited();
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.last_time = 12345;
+        reserve_data_0.b_rate = 1_100_000_000_000;
+        reserve_config_0.c_factor = 0_8500000;
+        reserve_config_0.l_factor = 0_9000000;
+        reserve_config_0.index = 0;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.b_rate = 1_200_000_000_000;
+        reserve_config_1.c_factor = 0_7500000;
+        reserve_config_1.l_factor = 0_7500000;
+        reserve_data_1.last_time = 12345;
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+
+        let (underlying_2, reserve_2_asset) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_2, reserve_data_2) = testutils::default_reserve_meta();
+        reserve_config_2.c_factor = 0_0000000;
+        reserve_config_2.l_factor = 0_7000000;
+        reserve_config_2.index = 2;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_2,
+            &reserve_config_2,
+            &reserve_data_2,
+        );
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+                Asset::Stellar(underlying_2.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 2_0000000, 4_0000000, 50_0000000]);
+
+        reserve_2_asset.mint(&frodo, &0_8000000);
+        reserve_2_asset.approve(&frodo, &pool_address, &i128::MAX, &1000000);
+
+        let mut auction_data = AuctionData {
+            bid: map![&e, (underlying_2.clone(), 1_2375000)],
+            lot: map![&e, (underlying_0.clone(), 30_5595329)],
+            block: 176,
+        };
+        let pool_config = PoolConfig {
+            oracle: oracle_address,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        let positions: Positions = Positions {
+            collateral: map![
+                &e,
+                (reserve_config_0.index, 90_9100000),
+                (reserve_config_1.index, 04_5800000),
+            ],
+            liabilities: map![&e, (reserve_config_2.index, 02_7500000),],
+            supply: map![&e],
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_user_positions(&e, &samwise, &positions);
+            storage::set_pool_config(&e, &pool_config);
+
+            e.ledger().set(LedgerInfo {
+                timestamp: 12345 + 200 * 5,
+                protocol_version: 22,
+                sequence_number: 176 + 200,
+                network_id: Default::default(),
+                base_reserve: 10,
+                min_temp_entry_ttl: 17280,
+                min_persistent_entry_ttl: 17280,
+                max_entry_ttl: 9999999,
+            });
+            let mut pool = Pool::load(&e);
+            let mut frodo_state = User::load(&e, &frodo);
+            fill_user_liq_auction(
+                &e,
+                &mut pool,
+                &mut auction_data,
+                &samwise,
+                &mut frodo_state,
+                true,
+            );
+            let frodo_positions = frodo_state.positions;
+            assert_eq!(
+                frodo_positions
+                    .collateral
+                    .get(reserve_config_0.index)
+                    .unwrap(),
+                30_5595329
+            );
+            assert_eq!(frodo_positions.collateral.get(reserve_config_1.index), None);
+            assert_eq!(
+                frodo_positions
+                    .liabilities
+                    .get(reserve_config_2.index)
+                    .unwrap(),
+                1_2375000
+            );
+            let samwise_positions = storage::get_user_positions(&e, &samwise);
+            assert_eq!(samwise_positions.collateral.len(), 2);
+            assert_eq!(
+                samwise_positions
+                    .collateral
+                    .get(reserve_config_0.index)
+                    .unwrap(),
+                90_9100000 - 30_5595329
+            );
+            assert_eq!(
+                samwise_positions
+                    .collateral
+                    .get(reserve_config_1.index)
+                    .unwrap(),
+                04_5800000
+            );
+            assert_eq!(
+                samwise_positions
+                    .liabilities
+                    .get(reserve_config_2.index)
+                    .unwrap(),
+                02_7500000 - 1_2375000
+            );
+        });
+    }
+
     #[test]
     fn test_fill_user_liquidation_auction_hits_target() {
         let e = Env::default();
@@ -2791,6 +3240,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         let positions: Positions = Positions {
             collateral: map![
@@ -2934,6 +3385,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         let positions: Positions = Positions {
             collateral: map![
@@ -3110,6 +3563,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         let positions: Positions = Positions {
             collateral: map![&e, (reserve_config_0.index, 90_9100000),],
@@ -3298,6 +3753,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         let positions: Positions = Positions {
             collateral: map![
@@ -3497,6 +3954,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         let positions: Positions = Positions {
             collateral: map![&e, (reserve_config_0.index, 90_9100000),],
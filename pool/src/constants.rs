@@ -3,14 +3,106 @@
 /// Fixed-point scalar for 12 decimal numbers
 pub const SCALAR_12: i128 = 1_000_000_000_000;
 
+/// Fixed-point scalar for 18 decimal numbers
+pub const SCALAR_18: i128 = 1_000_000_000_000_000_000;
+
+/// Scalar between a 12 decimal reserve rate and the same rate with 18 decimals
+pub const RATE_EXT_SCALAR: i128 = SCALAR_18 / SCALAR_12;
+
 /// Fixed-point scalar for 7 decimal numbers
 pub const SCALAR_7: i128 = 1_0000000;
 
+/// The maximum b_rate or d_rate of a reserve, a 1,000,000x growth from the initial rate
+pub const MAX_RESERVE_RATE: i128 = 1_000_000 * SCALAR_12;
+
 /// Seconds per year
 pub const SECONDS_PER_YEAR: i128 = 31536000;
 
+/// Seconds per day
+pub const SECONDS_PER_DAY: u64 = 86400;
+
 /// Seconds per week
 pub const SECONDS_PER_WEEK: u64 = 604800;
 
+/// The timelock on updating the pool once the pool has left setup
+pub const UPDATE_POOL_DELAY: u64 = 3 * 24 * 60 * 60;
+
+/// The timelock on changing the emission configuration once the pool has left setup
+pub const EMISSIONS_CONFIG_DELAY: u64 = 24 * 60 * 60;
+
+/// The timelock on changing how a reserve is priced once the pool has left setup
+pub const ORACLE_ASSET_DELAY: u64 = SECONDS_PER_WEEK;
+
+/// The timelock on changing the pool's oracle once the pool has left setup
+pub const ORACLE_DELAY: u64 = SECONDS_PER_WEEK;
+
+/// The timelock on rescuing tokens sent to the pool by mistake once the pool has left setup
+pub const RESCUE_DELAY: u64 = SECONDS_PER_WEEK;
+
+/// The timelock on delegating a reserve's borrow rate to a rate strategy once the pool has left setup
+pub const RATE_STRATEGY_DELAY: u64 = SECONDS_PER_WEEK;
+
+/// The timelock on changing how interest and fees are charged once the pool has left setup
+pub const RATE_CONFIG_DELAY: u64 = 3 * 24 * 60 * 60;
+
+/// Max annual borrow rate a rate strategy can set before the built-in curve is used instead (7 decimals)
+pub const MAX_STRATEGY_BORROW_RATE: i128 = 10 * SCALAR_7;
+
+/// The max deviation a new oracle's price can have from the current oracle's price for a reserve (7 decimals)
+pub const ORACLE_PRICE_TOLERANCE: i128 = 0_0500000;
+
 /// Max amount of reserves that can be added to a pool
 pub const MAX_RESERVES: u32 = 30;
+
+/// Max amount of position checkpoints that can be retained per user
+pub const MAX_CHECKPOINT_RETENTION: u32 = 30;
+
+/// Max amount of days of activity counters that can be retained
+pub const MAX_ANALYTICS_WINDOW: u32 = 30;
+
+/// Max amount of ledgers a collateral factor decrease can be ramped over (~30 days)
+pub const MAX_C_FACTOR_RAMP_LEDGERS: u32 = 17280 * 30;
+
+/// The minimum decrease to a reserve's collateral factor that gets ramped instead of applied at once (7 decimals)
+pub const C_FACTOR_RAMP_THRESHOLD: u32 = 0_0500000;
+
+/// Min amount of seconds a reserve can accrue interest for in a single accrual, if capped (1 hour)
+pub const MIN_MAX_ACCRUAL_PERIOD: u32 = 60 * 60;
+
+/// The time a reserve proposal can be approved or rejected for before the proposer can reclaim the bond (1 week)
+pub const RESERVE_PROPOSAL_PERIOD: u64 = SECONDS_PER_WEEK;
+
+/// Max amount of consecutive accruals the utilization circuit breaker can require before tripping
+pub const MAX_UTIL_BREAKER_ACCRUALS: u32 = 100;
+
+/// Max health factor buffer new borrows can be required to leave above the liquidation threshold
+pub const MAX_BORROW_BUFFER: u32 = 0_5000000;
+
+/// Max fee that can be charged on collateral withdrawals while the pool is on-ice or frozen (7 decimals)
+pub const MAX_WITHDRAW_FEE: u32 = 0_0100000;
+
+/// Max annual premium that can be charged on an insured user's liabilities (7 decimals)
+pub const MAX_INSURANCE_PREMIUM: u32 = 0_2000000;
+
+/// Max amount an insured user's health factor can be allowed to fall below 1 before liquidation (7 decimals)
+pub const MAX_INSURANCE_HF_BUFFER: u32 = 0_1000000;
+
+/// Max number of blocks after a fill commitment that it can be revealed in (~1 hour)
+pub const MAX_FILL_COMMIT_WINDOW: u32 = 720;
+
+/// The minimum number of ledgers an admin freeze can expire after (1 day)
+pub const MIN_FREEZE_EXPIRY_LEDGERS: u32 = 17280;
+
+/// Max amount of borrowers returned by a single page of the borrower index
+pub const MAX_BORROWER_PAGE: u32 = 100;
+
+/// Max amount of fillers returned by a single page of the filler index
+pub const MAX_FILLER_PAGE: u32 = 100;
+
+/// Max amount of steps in the backstop take rate schedule
+pub const MAX_BSTOP_RATE_STEPS: u32 = 12;
+
+/// Amount of underlying tokens, in 7 decimals, the admin supplies to a reserve when it is initialized
+/// (0.01 tokens). It is rescaled to the reserve's decimals with `reserve_seed_amount`. The bTokens
+/// minted are held by the pool and can never be withdrawn.
+pub const RESERVE_SEED_AMOUNT: i128 = 100_000;
@@ -1,13 +1,25 @@
 use crate::{
-    auctions::{self, AuctionData},
+    auctions::{self, AuctionData, AuctionType},
     emissions::{self, ReserveEmissionMetadata},
     events::PoolEvents,
-    pool::{self, FlashLoan, Positions, Request, Reserve},
-    storage::{self, ReserveConfig},
+    pool::{
+        self, ConfigSnapshot, Deleverage, FlashLoan, HookFill, KeeperTask, PoolConfigExport,
+        PoolTotals, Positions, QuoteFill, RateProjection, Request, Reserve, ReserveState,
+        ReserveSummary,
+    },
+    storage::{
+        self, AdminAction, AuctionConfig, BstopRateStep, CreditLine, DailyStats, EmissionDecay,
+        FillCommitConfig, FillerStats, InsuranceConfig, KeeperConfig, PositionCheckpoint,
+        PriceBand, QueuedAdminAction, QueuedWithdrawal, ReserveConfig, ReserveDeficit,
+        ReservePause, ReserveProposal, ReserveRateExt, ReserveStats, RiskPremium, StableLoan,
+        StableRateConfig, UtilBreaker, UtilBreakerState, WithdrawFee, WithdrawQueue,
+    },
     PoolConfig, PoolError, ReserveEmissionData, UserEmissionData,
 };
+use sep_40_oracle::Asset;
 use soroban_sdk::{
-    contract, contractclient, contractimpl, panic_with_error, Address, Env, String, Vec,
+    contract, contractclient, contractimpl, contracttype, panic_with_error,
+    unwrap::UnwrapOptimized, Address, BytesN, Env, Map, String, Vec,
 };
 
 /// ### Pool
@@ -16,6 +28,103 @@ use soroban_sdk::{
 #[contract]
 pub struct PoolContract;
 
+/// An admin operation that can be batched with `admin_multicall`
+#[derive(Clone)]
+#[contracttype]
+pub enum AdminOp {
+    /// Update the pool - (backstop_take_rate, max_positions, min_collateral, min_debt)
+    UpdatePool(u32, u32, i128, i128),
+    /// Set the backstop take rate schedule - (schedule)
+    SetBstopRateSchedule(Vec<BstopRateStep>),
+    /// Set the auction config
+    SetAuctionConfig(AuctionConfig),
+    /// Set or remove the price band for a reserve - (asset, band)
+    SetPriceBand(Address, Option<PriceBand>),
+    /// Pause or unpause supplying to and borrowing from a reserve - (asset, supply_paused, borrow_paused)
+    SetReservePaused(Address, bool, bool),
+    /// Set the oracle asset used to price a reserve, or remove it to price the reserve by its own
+    /// address - (asset, oracle_asset)
+    SetOracleAsset(Address, Option<Asset>),
+    /// Delegate a reserve's borrow rate to a rate strategy, or remove it to use the built-in
+    /// interest rate curve - (asset, strategy)
+    SetRateStrategy(Address, Option<Address>),
+    /// Set or remove the max annual borrow rate for a reserve - (asset, max_apr)
+    SetMaxApr(Address, Option<u32>),
+    /// Queue setting data for a reserve - (asset, metadata)
+    QueueSetReserve(Address, ReserveConfig),
+    /// Cancel the queued set of a reserve - (asset)
+    CancelSetReserve(Address),
+    /// Execute the queued set of a reserve - (asset)
+    SetReserve(Address),
+    /// Retire a wound down reserve - (asset)
+    RetireReserve(Address),
+    /// Queue an admin action behind its timelock - (action)
+    QueueAdminAction(AdminAction),
+    /// Cancel a queued admin action - (id)
+    CancelQueuedAction(u32),
+    /// Execute a queued admin action - (id)
+    ExecuteQueuedAction(u32),
+    /// Set the pool status - (pool_status)
+    SetStatus(u32),
+    /// Set the emission configuration - (res_emission_metadata)
+    SetEmissionsConfig(Vec<ReserveEmissionMetadata>),
+    /// Set the number of position checkpoints retained per user - (retention)
+    SetCheckpointRetention(u32),
+    /// Set the number of days of activity counters retained - (window)
+    SetAnalyticsWindow(u32),
+    /// Adjust the emission share of a single reserve token - (res_token_id, new_share)
+    AdjustEmissionShare(u32, u64),
+    /// Set the emission decay of a single reserve token - (res_token_id, factor)
+    SetEmissionDecay(u32, u32),
+    /// Whitelist a quote asset with its swap adapter, or remove it from the whitelist - (quote_asset, adapter)
+    SetQuoteAdapter(Address, Option<Address>),
+    /// Set or remove the risk premium - (risk_premium)
+    SetRiskPremium(Option<RiskPremium>),
+    /// Set the liquidation protection insurance config, or remove it to disable insurance - (config)
+    SetInsuranceConfig(Option<InsuranceConfig>),
+    /// Set the maximum number of seconds reserves can accrue interest for per ledger - (max_ledger_time)
+    SetMaxLedgerTime(u32),
+    /// Set the maximum number of seconds a reserve can accrue interest for in a single accrual - (max_accrual_period)
+    SetMaxAccrualPeriod(u32),
+    /// Enable or disable recording addresses with liabilities in the borrower index - (borrower_index)
+    SetBorrowerIndex(bool),
+    /// Enable or disable exempting the backstop from the maximum positions - (exempt)
+    SetBackstopPositionsExempt(bool),
+    /// Enable or disable gulping emissions automatically on submit - (auto_gulp)
+    SetAutoGulp(bool),
+    /// Enable or disable depositing interest auction bids into the backstop - (interest_deposit)
+    SetInterestDeposit(bool),
+    /// Set the number of ledgers collateral factor decreases are ramped over - (ledgers)
+    SetCFactorRamp(u32),
+    /// Set or remove the utilization circuit breaker - (util_breaker)
+    SetUtilBreaker(Option<UtilBreaker>),
+    /// Set the number of ledgers an admin freeze expires after - (ledgers)
+    SetFreezeExpiry(u32),
+    /// Set the health factor buffer new borrows must leave - (buffer)
+    SetBorrowBuffer(u32),
+    /// Set how interest is charged while the pool is frozen - (mode)
+    SetFrozenFees(u32),
+    /// Set the fee charged on collateral withdrawals while the pool is on-ice or frozen - (fee)
+    SetWithdrawFee(WithdrawFee),
+    /// Set the rebate paid to keepers for performing pool maintenance - (token, rebate)
+    SetKeeperConfig(Address, i128),
+    /// Register or unregister an address as a keeper - (keeper, registered)
+    SetKeeper(Address, bool),
+    /// Enable the backstop token price check with the backstop token's USDC token, or disable it - (usdc)
+    SetBackstopPriceCheck(Option<Address>),
+    /// Pause or unpause the creation of new auctions - (paused)
+    SetAuctionsPaused(bool),
+    /// Set the commit-reveal fill config, or remove it to disable commit-reveal fills - (config)
+    SetFillCommitConfig(Option<FillCommitConfig>),
+    /// Set or remove the converter claimed emissions can be routed through - (converter)
+    SetClaimConverter(Option<Address>),
+    /// Set the BLND bond required to propose a reserve - (bond)
+    SetListingBond(i128),
+    /// Set the council that decides reserve proposals, or remove it so the admin decides reserve
+    /// proposals - (council)
+    SetListingCouncil(Option<Address>),
+}
+
 #[contractclient(name = "PoolClient")]
 pub trait Pool {
     /// (Admin only) Set a new address to become the admin of the pool. This
@@ -35,313 +144,1682 @@ pub trait Pool {
     /// If the caller is not the proposed admin
     fn accept_admin(e: Env);
 
-    /// (Admin only) Update the pool
+    /// (Admin only) Update the pool. Once the pool has left setup, this must be queued with
+    /// `queue_admin_action` instead.
     ///
     /// ### Arguments
     /// * `backstop_take_rate` - The new take rate for the backstop (7 decimals)
     /// * `max_positions` - The new maximum number of allowed positions for a single user's account
     /// * `min_collateral` - The new minimum collateral required to open a borrow position,
     ///                      in the oracles base asset decimals
+    /// * `min_debt` - The new minimum value of liabilities a user must hold after a borrow,
+    ///                in the oracles base asset decimals
     ///
     /// ### Panics
-    /// If the caller is not the admin
-    fn update_pool(e: Env, backstop_take_rate: u32, max_positions: u32, min_collateral: i128);
+    /// If the caller is not the admin or the pool has left setup
+    fn update_pool(
+        e: Env,
+        backstop_take_rate: u32,
+        max_positions: u32,
+        min_collateral: i128,
+        min_debt: i128,
+    );
 
-    /// (Admin only) Queues setting data for a reserve in the pool
+    /// (Admin only) Set a stepwise schedule for the backstop take rate. Once a step's start time is
+    /// reached its rate is used for interest accrual instead of the pool's `bstop_rate`, until the next
+    /// step starts. Once the pool has left setup, this must be queued with `queue_admin_action` instead.
     ///
     /// ### Arguments
-    /// * `asset` - The underlying asset to add as a reserve
-    /// * `config` - The ReserveConfig for the reserve
+    /// * `schedule` - The steps of the schedule ordered by start time, or an empty Vec to remove it
     ///
     /// ### Panics
-    /// If the caller is not the admin
-    fn queue_set_reserve(e: Env, asset: Address, metadata: ReserveConfig);
+    /// If the caller is not the admin, the schedule is invalid, or the pool has left setup
+    fn set_bstop_rate_schedule(e: Env, schedule: Vec<BstopRateStep>);
 
-    /// (Admin only) Cancels the queued set of a reserve in the pool
+    /// Fetch the backstop take rate schedule
+    fn get_bstop_rate_schedule(e: Env) -> Vec<BstopRateStep>;
+
+    /// (Admin only) Set the auction config for the pool. Once the pool has left setup, this must be
+    /// queued with `queue_admin_action` instead.
     ///
     /// ### Arguments
-    /// * `asset` - The underlying asset to add as a reserve
+    /// * `config` - The new AuctionConfig
     ///
     /// ### Panics
-    /// If the caller is not the admin or the reserve is not queued for initialization
-    fn cancel_set_reserve(e: Env, asset: Address);
+    /// If the caller is not the admin, the config is invalid, or the pool has left setup
+    fn set_auction_config(e: Env, config: AuctionConfig);
 
-    /// Executes the queued set of a reserve in the pool
+    /// (Admin only) Set or remove the price band for a reserve. Oracle prices outside of the
+    /// band are clamped to it. Once the pool has left setup, this must be queued with
+    /// `queue_admin_action` instead.
     ///
     /// ### Arguments
-    /// * `asset` - The underlying asset to add as a reserve
+    /// * `asset` - The underlying asset of the reserve
+    /// * `band` - The new PriceBand, or None to remove it
     ///
     /// ### Panics
-    /// If the reserve is not queued for initialization
-    /// or is already setup
-    /// or has invalid metadata
-    fn set_reserve(e: Env, asset: Address) -> u32;
+    /// If the caller is not the admin, the reserve does not exist, the band is invalid, or the pool
+    /// has left setup
+    fn set_price_band(e: Env, asset: Address, band: Option<PriceBand>);
 
-    /// Fetch the pool configuration
-    fn get_config(e: Env) -> PoolConfig;
+    /// (Admin only) Pause or unpause supplying to and borrowing from a reserve. Unlike `queue_set_reserve`,
+    /// this takes effect immediately, and cannot modify any of the reserve's risk parameters.
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the reserve
+    /// * `supply_paused` - If supplying to the reserve, as collateral or otherwise, is paused
+    /// * `borrow_paused` - If borrowing from the reserve is paused
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the reserve does not exist
+    fn set_reserve_paused(e: Env, asset: Address, supply_paused: bool, borrow_paused: bool);
 
-    /// Fetch the admin address of the pool
-    fn get_admin(e: Env) -> Address;
+    /// (Admin only) Set or remove the oracle asset used to price a reserve. By default a reserve is
+    /// priced as `Asset::Stellar(asset)`, which can be overridden to price it with a different feed,
+    /// like the feed for a wrapped token's underlying asset. Once the pool has left setup, this must be
+    /// queued with `queue_admin_action` instead.
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the reserve
+    /// * `oracle_asset` - The oracle asset to price the reserve with, or None to remove the override
+    ///
+    /// ### Panics
+    /// If the caller is not the admin, the reserve does not exist, or the pool has left setup
+    fn set_oracle_asset(e: Env, asset: Address, oracle_asset: Option<Asset>);
 
-    /// Fetch the a vec addresses of all reserves in the pool. The index of the reserve
-    /// in this vec defines the index of the reserve in the pool, used in places like `Positions`.
-    fn get_reserve_list(e: Env) -> Vec<Address>;
+    /// Fetch the oracle asset override for a reserve, if one is set
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the reserve
+    fn get_oracle_asset(e: Env, asset: Address) -> Option<Asset>;
 
-    /// Fetch information about a reserve, updated to the current ledger
+    /// (Admin only) Delegate a reserve's borrow rate to a rate strategy contract, or remove the
+    /// strategy to use the built-in interest rate curve. See `RateStrategy` for the strategy interface.
+    /// If the strategy fails or returns a rate over 1000%, the built-in curve is used. The reserve's rate
+    /// modifier is still updated by the reserve config, and is passed to the strategy.
+    ///
+    /// Once the pool has left setup, setting a strategy must be queued with `queue_admin_action`
+    /// instead. A strategy can always be removed.
     ///
     /// ### Arguments
-    /// * `asset` - The address of the reserve asset
-    fn get_reserve(e: Env, asset: Address) -> Reserve;
+    /// * `asset` - The underlying asset of the reserve
+    /// * `strategy` - The rate strategy, or None to remove it
+    ///
+    /// ### Panics
+    /// If the caller is not the admin, the reserve does not exist, or a strategy is set after the pool
+    /// has left setup
+    fn set_rate_strategy(e: Env, asset: Address, strategy: Option<Address>);
 
-    /// Fetch the positions for an address. For each position type, there is a map of the reserve index
-    /// to the position for that reserve, if it exists.
+    /// Fetch the rate strategy a reserve's borrow rate is delegated to, if one is set
     ///
     /// ### Arguments
-    /// * `address` - The address to fetch positions for
-    fn get_positions(e: Env, address: Address) -> Positions;
+    /// * `asset` - The underlying asset of the reserve
+    fn get_rate_strategy(e: Env, asset: Address) -> Option<Address>;
 
-    /// Submit a set of requests to the pool where `from` takes on the position, `spender` sends any
-    /// required tokens to the pool and `to` receives any tokens sent from the pool.
+    /// (Admin only) Set or remove the max annual borrow rate for a reserve. While the reserve's
+    /// borrow rate is over the max, interest accrues at the max instead and a `borrow_rate_capped`
+    /// event is emitted. The reserve's rate modifier is not affected by the cap.
     ///
-    /// Returns the new positions for `from`
+    /// Once the pool has left setup, this must be queued with `queue_admin_action` instead.
     ///
     /// ### Arguments
-    /// * `from` - The address of the user whose positions are being modified
-    /// * `spender` - The address of the user who is sending tokens to the pool
-    /// * `to` - The address of the user who is receiving tokens from the pool
-    /// * `requests` - A vec of requests to be processed
+    /// * `asset` - The underlying asset of the reserve
+    /// * `max_apr` - The max annual borrow rate (7 decimals), or None to remove it
     ///
     /// ### Panics
-    /// If the request is not able to be completed for cases like insufficient funds or invalid health factor
-    fn submit(
-        e: Env,
-        from: Address,
-        spender: Address,
-        to: Address,
-        requests: Vec<Request>,
-    ) -> Positions;
+    /// If the caller is not the admin, the reserve does not exist, the max APR is zero, or the pool
+    /// has left setup
+    fn set_max_apr(e: Env, asset: Address, max_apr: Option<u32>);
 
-    /// Submit a set of requests to the pool where `from` takes on the position, `spender` sends any
-    /// required tokens to the pool using transfer_from and `to` receives any tokens sent from the pool.
+    /// Fetch the max annual borrow rate for a reserve, if one is set
     ///
-    /// Returns the new positions for `from`
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the reserve
+    fn get_max_apr(e: Env, asset: Address) -> Option<u32>;
+
+    /// (Admin only) Set the number of position checkpoints retained per user. A checkpoint of the
+    /// user's positions and reserve rates is recorded at the end of each submit.
     ///
     /// ### Arguments
-    /// * `from` - The address of the user whose positions are being modified
-    /// * `spender` - The address of the user who is sending tokens to the pool
-    /// * `to` - The address of the user who is receiving tokens from the pool
-    /// * `requests` - A vec of requests to be processed
+    /// * `retention` - The number of checkpoints to retain, or 0 to stop recording checkpoints
     ///
     /// ### Panics
-    /// If the request is not able to be completed for cases like insufficient funds, insufficient allowance, or invalid health factor
-    fn submit_with_allowance(
-        e: Env,
-        from: Address,
-        spender: Address,
-        to: Address,
-        requests: Vec<Request>,
-    ) -> Positions;
+    /// If the caller is not the admin or the retention is greater than 30
+    fn set_checkpoint_retention(e: Env, retention: u32);
 
-    /// Submit flash loan and a set of requests to the pool where `from` takes on the position. The flash loan will be invoked using
-    /// the `flash_loan` arguments and `from` as the caller. For the requests, `from` sends any required tokens to the pool
-    /// using transfer_from and receives any tokens sent from the pool.
+    /// (Admin only) Set the number of days of activity counters retained. Each submit is counted
+    /// against the current day's active users, supplies, borrows, and volume per asset.
     ///
-    /// Returns the new positions for `from`
+    /// ### Arguments
+    /// * `window` - The number of days to retain, or 0 to stop recording activity
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the window is greater than 30
+    fn set_analytics_window(e: Env, window: u32);
+
+    /// Fetch the number of days of activity counters retained
+    fn get_analytics_window(e: Env) -> u32;
+
+    /// (Admin only) Set the maximum number of seconds reserves can accrue interest for per ledger. While set,
+    /// an anomalous jump in the ledger timestamp is accrued over the following ledgers instead of at once.
+    /// Once the pool has left setup, this must be queued with `queue_admin_action` instead.
     ///
     /// ### Arguments
-    /// * `from` - The address of the user whose positions are being modified and also the address of
-    /// the user who is sending and receiving the tokens to the pool.
-    /// * `flash_loan` - Arguments relative to the flash loan: receiver contract, asset and borroed amount.
-    /// * `requests` - A vec of requests to be processed
+    /// * `max_ledger_time` - The maximum number of seconds per ledger, or 0 to disable the guard
     ///
     /// ### Panics
-    /// If the request is not able to be completed for cases like insufficient funds ,insufficient allowance, or invalid health factor
-    fn flash_loan(
-        e: Env,
-        from: Address,
-        flash_loan: FlashLoan,
-        requests: Vec<Request>,
-    ) -> Positions;
+    /// If the caller is not the admin, the max ledger time is not 0 and not between 5 and 60 seconds,
+    /// or the pool has left setup
+    fn set_max_ledger_time(e: Env, max_ledger_time: u32);
 
-    /// Update the pool status based on the backstop state - backstop triggered status' are odd numbers
-    /// * 1 = backstop active - if the minimum backstop deposit has been reached
-    ///                and 30% of backstop deposits are not queued for withdrawal
-    ///                then all pool operations are permitted
-    /// * 3 = backstop on-ice - if the minimum backstop deposit has not been reached
-    ///                or 30% of backstop deposits are queued for withdrawal and admin active isn't set
-    ///                or 50% of backstop deposits are queued for withdrawal
-    ///                then borrowing and cancelling liquidations are not permitted
-    /// * 5 = backstop frozen - if 60% of backstop deposits are queued for withdrawal and admin on-ice isn't set
-    ///                or 75% of backstop deposits are queued for withdrawal
-    ///                then all borrowing, cancelling liquidations, and supplying are not permitted
+    /// Fetch the maximum number of seconds reserves can accrue interest for per ledger, or 0 if the guard is disabled
+    fn get_max_ledger_time(e: Env) -> u32;
+
+    /// (Admin only) Set the maximum number of seconds a reserve can accrue interest for in a single accrual. While set,
+    /// a reserve that sat idle for longer, like after being restored from archival, catches up on its interest over
+    /// the following interactions instead of at once, so positions aren't made unhealthy by a single accrual.
+    /// Once the pool has left setup, this must be queued with `queue_admin_action` instead.
+    ///
+    /// ### Arguments
+    /// * `max_accrual_period` - The maximum number of seconds per accrual, or 0 to disable the cap
     ///
     /// ### Panics
-    /// If the pool is currently on status 4, "admin-freeze", where only the admin
-    /// can perform a status update via `set_status`
-    fn update_status(e: Env) -> u32;
+    /// If the caller is not the admin, the max accrual period is not 0 and less than 1 hour, or the
+    /// pool has left setup
+    fn set_max_accrual_period(e: Env, max_accrual_period: u32);
 
-    /// (Admin only) Pool status is changed to `pool_status`
-    /// * 0 = admin active - requires that the backstop threshold is met
-    ///                 and less than 50% of backstop deposits are queued for withdrawal
-    /// * 2 = admin on-ice - requires that less than 75% of backstop deposits are queued for withdrawal
-    /// * 4 = admin frozen - can always be set
+    /// Fetch the maximum number of seconds a reserve can accrue interest for in a single accrual, or 0 if the cap is disabled
+    fn get_max_accrual_period(e: Env) -> u32;
+
+    /// (Admin only) Set the number of ledgers a reserve's collateral factor is ramped down over when a
+    /// queued reserve config lowers it by 5% or more, so tightening risk parameters doesn't make positions
+    /// liquidatable at once. Disabling ramping applies the lowered collateral factors of any in progress
+    /// ramps immediately.
     ///
     /// ### Arguments
-    /// * `pool_status` - The pool status to be set
+    /// * `ledgers` - The number of ledgers to ramp over, or 0 to disable ramping
     ///
     /// ### Panics
-    /// If the caller is not the admin
-    /// If the specified conditions are not met for the status to be set
-    fn set_status(e: Env, pool_status: u32);
+    /// If the caller is not the admin or the number of ledgers is greater than 518400 (~30 days)
+    fn set_c_factor_ramp(e: Env, ledgers: u32);
 
-    /// Gulps unaccounted for tokens to the backstop credit so they aren't lost. This is most relevant
-    /// for rebasing tokens where the token balance of the pool can increase without any corresponding
-    /// transfer.
+    /// Fetch the number of ledgers collateral factor decreases are ramped over, or 0 if ramping is disabled
+    fn get_c_factor_ramp(e: Env) -> u32;
+
+    /// (Admin only) Set the number of ledgers after which an admin freeze (status 4) expires. Once expired,
+    /// `update_status` sets the pool to the status derived from the backstop, so the pool can't be left
+    /// frozen forever. Only applies to freezes set after the update.
     ///
-    /// Blend Pools do not support fee-on-transaction tokens, or any tokens in which the pools balance
-    /// can decrease without any corresponding withdraw. Thus, negative token deltas are ignored.
+    /// ### Arguments
+    /// * `ledgers` - The number of ledgers, or 0 if freezes do not expire
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the number of ledgers is less than 17280 (~1 day)
+    fn set_freeze_expiry(e: Env, ledgers: u32);
+
+    /// Fetch the number of ledgers after which an admin freeze expires, or 0 if freezes do not expire
+    fn get_freeze_expiry(e: Env) -> u32;
+
+    /// Fetch the ledger the current admin freeze expires at, or None if the pool is not frozen by the
+    /// admin or the freeze does not expire
+    fn get_freeze_expiry_ledger(e: Env) -> Option<u32>;
+
+    /// (Admin only) Set the health factor buffer new borrows must leave above the liquidation threshold.
+    /// Submits that borrow must leave the user with a health factor of at least `1 + buffer`, so a small
+    /// price move can't make a fresh borrow liquidatable. Other requests are only held to the liquidation
+    /// threshold.
     ///
     /// ### Arguments
-    /// * `asset` - The address of the asset to gulp
+    /// * `buffer` - The buffer in 7 decimals, or 0 to disable
     ///
-    /// Returns the amount of tokens gulped
-    fn gulp(e: Env, asset: Address) -> i128;
+    /// ### Panics
+    /// If the caller is not the admin or the buffer is greater than 0_5000000
+    fn set_borrow_buffer(e: Env, buffer: u32);
 
-    /********* Emission Functions **********/
+    /// Fetch the health factor buffer new borrows must leave above the liquidation threshold, or 0 if disabled
+    fn get_borrow_buffer(e: Env) -> u32;
 
-    /// Consume emissions from the backstop and distribute to the reserves based
-    /// on the reserve emission configuration.
+    /// (Admin only) Set how interest is charged while the pool is frozen (status 4 or 5), so users are not
+    /// charged for time they are unable to manage their positions. Stable rate liabilities keep accruing
+    /// at their stable rate.
     ///
-    /// Returns amount of new tokens emitted
-    fn gulp_emissions(e: Env) -> i128;
+    /// * 0 - Interest accrues as normal
+    /// * 1 - The backstop take rate is halted, and suppliers earn all interest
+    /// * 2 - Borrower interest is halted entirely
+    ///
+    /// Once the pool has left setup, this must be queued with `queue_admin_action` instead.
+    ///
+    /// ### Arguments
+    /// * `mode` - The frozen fee mode
+    ///
+    /// ### Panics
+    /// If the caller is not the admin, the mode is not 0, 1, or 2, or the pool has left setup
+    fn set_frozen_fees(e: Env, mode: u32);
 
-    /// (Admin only) Set the emission configuration for the pool
+    /// Fetch how interest is charged while the pool is frozen
+    fn get_frozen_fees(e: Env) -> u32;
+
+    /// (Admin only) Set the fee charged on collateral withdrawals while the pool is on-ice or frozen.
+    /// The fee is taken from the tokens withdrawn and credited to the backstop, discouraging a run on
+    /// the pool while the backstop is stressed.
     ///
-    /// Changes will be applied in the next pool `update_emissions`, and affect the next emission cycle
+    /// Once the pool has left setup, this must be queued with `queue_admin_action` instead.
     ///
     /// ### Arguments
-    /// * `res_emission_metadata` - A vector of ReserveEmissionMetadata to update metadata to
+    /// * `fee` - The withdrawal fee while the pool is on-ice and while it is frozen, in 7 decimals
     ///
     /// ### Panics
-    /// * If the caller is not the admin
-    fn set_emissions_config(e: Env, res_emission_metadata: Vec<ReserveEmissionMetadata>);
+    /// If the caller is not the admin, either fee is greater than 1%, or the pool has left setup
+    fn set_withdraw_fee(e: Env, fee: WithdrawFee);
 
-    /// Claims outstanding emissions for the caller for the given reserve's.
+    /// Fetch the fee charged on collateral withdrawals while the pool is on-ice or frozen
+    fn get_withdraw_fee(e: Env) -> WithdrawFee;
+
+    /// (Admin only) Add or remove an address from the blocklist. A `submit` that supplies to the pool fails
+    /// if the address whose positions are modified or the address sending tokens is blocked, and liquidation
+    /// hooks can't supply collateral for a blocked address. Withdrawals and repayments are always allowed.
     ///
-    /// A reserve token id is a unique identifier for a position in a pool.
-    /// - For a reserve's dTokens (liabilities), reserve_token_id = reserve_index * 2
-    /// - For a reserve's bTokens (supply/collateral), reserve_token_id = reserve_index * 2 + 1
+    /// ### Arguments
+    /// * `user` - The address
+    /// * `blocked` - If the address is blocked from depositing into the pool
     ///
-    /// Returns the number of tokens claimed
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_blocked(e: Env, user: Address, blocked: bool);
+
+    /// Fetch if an address is blocked from depositing into the pool
     ///
     /// ### Arguments
-    /// * `from` - The address claiming
-    /// * `reserve_token_ids` - Vector of reserve token ids
-    /// * `to` - The Address to send the claimed tokens to
-    fn claim(e: Env, from: Address, reserve_token_ids: Vec<u32>, to: Address) -> i128;
+    /// * `user` - The address
+    fn is_blocked(e: Env, user: Address) -> bool;
 
-    /// Get the emissions data for a reserve token
+    /// (Admin only) Set or remove the pool's risk premium. Borrowers that stay below the premium's
+    /// health factor threshold between checkpoints are charged additional interest on their liabilities.
     ///
-    /// A reserve token id is a unique identifier for a position in a pool.
-    /// - For a reserve's dTokens (liabilities), reserve_token_id = reserve_index * 2
-    /// - For a reserve's bTokens (supply/collateral), reserve_token_id = reserve_index * 2 + 1
+    /// Once the pool has left setup, setting a risk premium must be queued with `queue_admin_action`
+    /// instead. The risk premium can always be removed, as removing it only stops charging borrowers.
     ///
     /// ### Arguments
-    /// * `reserve_token_id` - The reserve token id
-    fn get_reserve_emissions(e: Env, reserve_token_id: u32) -> Option<ReserveEmissionData>;
+    /// * `risk_premium` - The new RiskPremium, or None to remove it
+    ///
+    /// ### Panics
+    /// If the caller is not the admin, the risk premium is invalid, or a risk premium is set after the
+    /// pool has left setup
+    fn set_risk_premium(e: Env, risk_premium: Option<RiskPremium>);
 
-    /// Get the emissions data for a user
+    /// Fetch the pool's risk premium, if one is set
+    fn get_risk_premium(e: Env) -> Option<RiskPremium>;
+
+    /// (Admin only) Set or remove the pool's liquidation protection insurance. Insured users pay the
+    /// premium on their liabilities to the backstop, and can't be liquidated until their health factor
+    /// falls below 1 by more than the buffer. While insurance is removed, insured users are neither
+    /// charged nor protected.
     ///
-    /// A reserve token id is a unique identifier for a position in a pool.
-    /// - For a reserve's dTokens (liabilities), reserve_token_id = reserve_index * 2
-    /// - For a reserve's bTokens (supply/collateral), reserve_token_id = reserve_index * 2 + 1
+    /// Once the pool has left setup, this must be queued with `queue_admin_action` instead.
     ///
     /// ### Arguments
-    /// * `user` - The address of the user
-    /// * `reserve_token_id` - The reserve token id
-    fn get_user_emissions(e: Env, user: Address, reserve_token_id: u32)
-        -> Option<UserEmissionData>;
+    /// * `config` - The new InsuranceConfig, or None to disable insurance
+    ///
+    /// ### Panics
+    /// If the caller is not the admin, the config is invalid, or the pool has left setup
+    fn set_insurance_config(e: Env, config: Option<InsuranceConfig>);
 
-    /***** Auction / Liquidation Functions *****/
+    /// Fetch the pool's liquidation protection insurance config, if one is set
+    fn get_insurance_config(e: Env) -> Option<InsuranceConfig>;
 
-    /// Create a new auction. Auctions are used to process liquidations, bad debt, and interest.
+    /// (Admin only) Set or remove the pool's commit-reveal fill config. While set, user liquidation
+    /// auctions with a bid worth at least the config's min value, in the oracle's base asset, can only
+    /// be filled with `reveal_fill`.
     ///
     /// ### Arguments
-    /// * `auction_type` - The type of auction, 0 for liquidation auction, 1 for bad debt auction, and 2 for interest auction
-    /// * `user` - The Address involved in the auction. This is generally the source of the assets being auctioned.
-    ///            For bad debt and interest auctions, this is expected to be the backstop address.
-    /// * `bid` - The set of assets to include in the auction bid, or what the filler spends when filling the auction.
-    /// * `lot` - The set of assets to include in the auction lot, or what the filler receives when filling the auction.
-    /// * `percent` - The percent of the assets to be auctioned off as a percentage (15 => 15%). For bad debt and interest auctions.
-    ///               this is expected to be 100.
-    fn new_auction(
-        e: Env,
-        auction_type: u32,
-        user: Address,
-        bid: Vec<Address>,
-        lot: Vec<Address>,
-        percent: u32,
-    ) -> AuctionData;
+    /// * `config` - The new FillCommitConfig, or None to disable commit-reveal fills
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the config is invalid
+    fn set_fill_commit_config(e: Env, config: Option<FillCommitConfig>);
 
-    /// Fetch an auction from the ledger. Returns the base auction. On fill, this will be scaled based on the
-    /// number of blocks that have passed since the auction was created.
+    /// Fetch the pool's commit-reveal fill config, if one is set
+    fn get_fill_commit_config(e: Env) -> Option<FillCommitConfig>;
+
+    /// (Admin only) Set or remove the pool's utilization circuit breaker. Once a reserve's utilization, or the
+    /// pool's aggregate utilization, is at or above its critical threshold for the breaker's number of
+    /// consecutive accruals, an active pool is put on-ice. The pool returns to its previous status once
+    /// utilization is back under the thresholds. The breaker is evaluated at most once per ledger.
+    ///
+    /// Once the pool has left setup, this must be queued with `queue_admin_action` instead.
     ///
     /// ### Arguments
-    /// * `auction_type` - The type of auction, 0 for liquidation auction, 1 for bad debt auction, and 2 for interest auction
-    /// * `user` - The Address involved in the auction
+    /// * `util_breaker` - The new UtilBreaker, or None to remove it
     ///
     /// ### Panics
-    /// If the auction does not exist
-    fn get_auction(e: Env, auction_type: u32, user: Address) -> AuctionData;
+    /// If the caller is not the admin, the breaker has no thresholds set, a threshold over 100%, or
+    /// requires 0 or more than 100 accruals, or the pool has left setup
+    fn set_util_breaker(e: Env, util_breaker: Option<UtilBreaker>);
 
-    /// Delete a stale auction. A stale auction is one that has been running for 500 blocks
-    /// without being filled. This likely means something went wrong with the auction creation,
-    /// and it should be re-created.
+    /// Fetch the pool's utilization circuit breaker
+    fn get_util_breaker(e: Env) -> Option<UtilBreaker>;
+
+    /// Fetch the state of the pool's utilization circuit breaker
+    fn get_util_breaker_state(e: Env) -> UtilBreakerState;
+
+    /// (Admin only) Enable or disable the backstop token price check. While enabled, bad debt and
+    /// interest auctions value the backstop token at both the LP's spot price and the value of the
+    /// BLND and USDC backing it at the pool's oracle prices, and use whichever value favors the pool
+    /// and backstop. This prevents manipulating the LP's spot price from skewing auction sizes.
+    ///
+    /// The pool's oracle must be able to price BLND and USDC while the check is enabled.
     ///
     /// ### Arguments
-    /// * `auction_type` - The type of auction, 0 for liquidation auction, 1 for bad debt auction, and 2 for interest auction
-    /// * `user` - The Address involved in the auction
+    /// * `usdc` - The USDC token of the backstop token, or None to disable the check
     ///
     /// ### Panics
-    /// * If the auction does not exist
-    /// * If the auction is not stale
-    fn del_auction(e: Env, auction_type: u32, user: Address);
+    /// If the caller is not the admin
+    fn set_backstop_price_check(e: Env, usdc: Option<Address>);
 
-    /// Check and handle bad debt for a user.
-    /// * If the user is not the backstop and they have bad debt, the backstop will take over the debt.
-    /// * If the user is the backstop, the backstop health will be checked, and if it is unhealthy, the backstop will default it's
-    /// remaining debt.
+    /// Fetch the USDC token used by the backstop token price check, if the check is enabled
+    fn get_backstop_price_check(e: Env) -> Option<Address>;
+
+    /// (Admin only) Set or remove the converter claimed emissions can be routed through with
+    /// `claim_converted`. The converter must implement the `ClaimConverter` interface.
+    ///
+    /// Once the pool has left setup, setting a converter must be queued with `queue_admin_action`
+    /// instead. The converter can always be removed, as users can still claim directly with `claim`.
     ///
     /// ### Arguments
-    /// * `user` - The address of the user to check for bad debt
+    /// * `converter` - The claim converter, or None to disable converted claims
     ///
     /// ### Panics
-    /// * If there is no bad debt to handle
-    /// * If there is an ongoing auction for the user
-    fn bad_debt(e: Env, user: Address);
-}
+    /// If the caller is not the admin or a converter is set after the pool has left setup
+    fn set_claim_converter(e: Env, converter: Option<Address>);
 
-#[contractimpl]
-impl PoolContract {
-    /// Initialize the pool
+    /// Fetch the claim converter, if one is set
+    fn get_claim_converter(e: Env) -> Option<Address>;
+
+    /// (Admin only) Set the credit line a lender extends to a reserve. When a withdrawal pushes the
+    /// reserve's utilization above its maximum, the pool draws from the lender to bring it back to the
+    /// maximum, up to the line's cap. Draws accrue the reserve's supply interest, and are repaid from
+    /// later inflows to the reserve once its utilization is back under the maximum.
+    ///
+    /// The lender must authorize the line and approve the pool to transfer up to the cap of its tokens.
+    /// Outstanding draws are kept when a line is updated. A cap of 0 stops further draws, and removes
+    /// the line if it has been repaid.
     ///
     /// ### Arguments
-    /// Creator supplied:
-    /// * `admin` - The Address for the admin
-    /// * `name` - The name of the pool
-    /// * `oracle` - The contract address of the oracle
-    /// * `backstop_take_rate` - The take rate for the backstop (7 decimals)
-    /// * `max_positions` - The maximum number of positions a user is permitted to have
-    /// * `min_collateral` - The minimum collateral required to open a borrow position in the oracles base asset
+    /// * `asset` - The underlying asset of the reserve
+    /// * `lender` - The address lending to the reserve
+    /// * `cap` - The maximum amount of underlying that can be drawn from the line
     ///
-    /// Pool Factory supplied:
-    /// * `backstop_id` - The contract address of the pool's backstop module
-    /// * `blnd_id` - The contract ID of the BLND token
-    pub fn __constructor(
-        e: Env,
-        admin: Address,
-        name: String,
-        oracle: Address,
+    /// ### Panics
+    /// If the caller is not the admin, the lender has not authorized the line, the reserve does not
+    /// exist, the cap is negative, or the line has outstanding draws from a different lender
+    fn set_credit_line(e: Env, asset: Address, lender: Address, cap: i128);
+
+    /// Fetch the credit line extended to a reserve
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the reserve
+    fn get_credit_line(e: Env, asset: Address) -> Option<CreditLine>;
+
+    /// Checkpoint a user's health factor for the risk premium. If the user has stayed below the
+    /// threshold since their last checkpoint, the premium is charged on their liabilities. Checkpoints
+    /// also occur on every submit by the user.
+    ///
+    /// Returns the user's positions
+    ///
+    /// ### Arguments
+    /// * `user` - The user to checkpoint
+    fn apply_risk_premium(e: Env, user: Address) -> Positions;
+
+    /// Charge an insured user the insurance premium owed since it was last charged. The premium is
+    /// also charged on every submit by the user.
+    ///
+    /// Returns the user's positions
+    ///
+    /// ### Arguments
+    /// * `user` - The user to charge
+    fn apply_insurance_premium(e: Env, user: Address) -> Positions;
+
+    /// (Admin only) Set or remove the stable rate borrowing config of a reserve. Stable rate borrows are
+    /// priced at the reserve's borrow rate plus the config's premium when they are made, and keep that
+    /// rate until they are swapped to the variable rate or rebalanced.
+    ///
+    /// Removing the config stops new stable rate borrows. Existing stable rate liabilities keep their rate.
+    ///
+    /// Once the pool has left setup, setting a config must be queued with `queue_admin_action` instead.
+    /// The config can always be removed, as removing it does not change existing liabilities.
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the reserve
+    /// * `config` - The stable rate borrowing config, or None to disable stable rate borrowing
+    ///
+    /// ### Panics
+    /// If the caller is not the admin, the reserve does not exist, the config is invalid, or a config
+    /// is set after the pool has left setup
+    fn set_stable_rate(e: Env, asset: Address, config: Option<StableRateConfig>);
+
+    /// Fetch the stable rate borrowing config of a reserve, if stable rate borrowing is enabled
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the reserve
+    fn get_stable_rate(e: Env, asset: Address) -> Option<StableRateConfig>;
+
+    /// Fetch the stable rate a user borrows from a reserve at, if they have stable rate liabilities
+    /// for the reserve
+    ///
+    /// ### Arguments
+    /// * `user` - The address of the user
+    /// * `asset` - The underlying asset of the reserve
+    fn get_stable_loan(e: Env, user: Address, asset: Address) -> Option<StableLoan>;
+
+    /// Fetch the portion of a user's liabilities borrowed at a stable rate, as a map of reserve index
+    /// to dTokens. The dTokens are included in the liabilities of the user's positions.
+    ///
+    /// ### Arguments
+    /// * `user` - The address of the user
+    fn get_stable_liabilities(e: Env, user: Address) -> Map<u32, i128>;
+
+    /// Rebalance a user's stable rate to the reserve's borrow rate plus the premium. Anyone can
+    /// rebalance a user once the reserve's utilization is at or above the config's rebalance
+    /// utilization and the user's stable rate is below the reserve's borrow rate.
+    ///
+    /// Returns the user's positions
+    ///
+    /// ### Arguments
+    /// * `user` - The user to rebalance
+    /// * `asset` - The underlying asset of the reserve
+    ///
+    /// ### Panics
+    /// If stable rate borrowing is disabled for the reserve, the user has no stable rate liabilities
+    /// for the reserve, or the rebalance conditions are not met
+    fn rebalance_stable_rate(e: Env, user: Address, asset: Address) -> Positions;
+
+    /// (Admin only) Whitelist a quote asset that can be used to fill user liquidation auctions with
+    /// `fill_with_quote`, or remove it from the whitelist. See `SwapAdapter` for the adapter interface.
+    ///
+    /// Once the pool has left setup, whitelisting a quote asset must be queued with `queue_admin_action`
+    /// instead. A quote asset can always be removed from the whitelist.
+    ///
+    /// ### Arguments
+    /// * `quote_asset` - The quote asset
+    /// * `adapter` - The swap adapter used to convert the quote asset into bid assets, or None to remove it
+    ///
+    /// ### Panics
+    /// If the caller is not the admin, the adapter is the pool, or a quote asset is whitelisted after
+    /// the pool has left setup
+    fn set_quote_adapter(e: Env, quote_asset: Address, adapter: Option<Address>);
+
+    /// Fetch the swap adapter for a quote asset, if the quote asset is whitelisted
+    ///
+    /// ### Arguments
+    /// * `quote_asset` - The quote asset
+    fn get_quote_adapter(e: Env, quote_asset: Address) -> Option<Address>;
+
+    /// (Admin only) Execute a set of admin operations atomically. Each operation is applied in order
+    /// with the same validation and events as its standalone admin function.
+    ///
+    /// ### Arguments
+    /// * `ops` - The admin operations to execute
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or any operation fails
+    fn admin_multicall(e: Env, ops: Vec<AdminOp>);
+
+    /// (Admin only) Queue an admin action behind a timelock. Updating the pool, changing the emission
+    /// configuration, changing the oracle or how a reserve is priced, changing how interest and fees
+    /// are charged, and rescuing tokens sent to the pool by mistake can only be applied through the
+    /// queue once the pool has left setup, which gives users time to react to a compromised admin.
+    /// The timelock is 1 day for emission and claim converter changes, 3 days for updating the pool,
+    /// the backstop rate schedule, the auction config and quote adapters and for rate, fee, insurance,
+    /// accrual guard and utilization breaker changes, and 1 week for oracle changes, rate strategies
+    /// and rescues. Actions queued while the pool is in setup can be executed immediately.
+    ///
+    /// Returns the id of the queued action
+    ///
+    /// ### Arguments
+    /// * `action` - The admin action to queue
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn queue_admin_action(e: Env, action: AdminAction) -> u32;
+
+    /// (Admin only) Cancel a queued admin action
+    ///
+    /// ### Arguments
+    /// * `id` - The id of the queued action
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the action is not queued
+    fn cancel_queued_action(e: Env, id: u32);
+
+    /// Execute a queued admin action once its timelock has passed
+    ///
+    /// ### Arguments
+    /// * `id` - The id of the queued action
+    ///
+    /// ### Panics
+    /// If the action is not queued, the timelock has not passed, or the action is invalid. A new oracle
+    /// is invalid if it prices any reserve more than 5% away from the current oracle.
+    fn execute_queued_action(e: Env, id: u32);
+
+    /// Fetch a queued admin action, if it exists
+    ///
+    /// ### Arguments
+    /// * `id` - The id of the queued action
+    fn get_queued_action(e: Env, id: u32) -> Option<QueuedAdminAction>;
+
+    /// Fetch the pool's config, the config of each reserve, and the pool's emission shares as a single
+    /// struct, so parameter sets can be diffed and re-applied with `queue_import_config`
+    fn export_config(e: Env) -> PoolConfigExport;
+
+    /// Fetch a snapshot of the pool's configuration, as the sha256 hashes of the XDR encoded pool config and
+    /// each reserve's config. The same snapshot is emitted as a `config_snapshot` event whenever the pool is
+    /// updated, a reserve is set, or a queued admin action is executed.
+    fn get_config_snapshot(e: Env) -> ConfigSnapshot;
+
+    /// (Admin only) Queue the admin actions and reserve sets required to apply an exported set of pool
+    /// parameters. Only parameters that differ from the pool are queued, and each is timelocked as if
+    /// it was queued on its own. The pool's status is not imported.
+    ///
+    /// Returns the ids of the queued admin actions
+    ///
+    /// ### Arguments
+    /// * `export` - The pool parameters to apply
+    ///
+    /// ### Panics
+    /// If the caller is not the admin, any queued change is invalid, or a reserve being changed
+    /// already has a queued change
+    fn queue_import_config(e: Env, export: PoolConfigExport) -> Vec<u32>;
+
+    /// (Admin only) Queues setting data for a reserve in the pool
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset to add as a reserve
+    /// * `config` - The ReserveConfig for the reserve
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn queue_set_reserve(e: Env, asset: Address, metadata: ReserveConfig);
+
+    /// (Admin only) Cancels the queued set of a reserve in the pool
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset to add as a reserve
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the reserve is not queued for initialization
+    fn cancel_set_reserve(e: Env, asset: Address);
+
+    /// (Admin only) Set the BLND bond required to propose a reserve with `propose_reserve`
+    ///
+    /// ### Arguments
+    /// * `bond` - The amount of BLND to bond, or 0 to disable reserve proposals
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the bond is negative
+    fn set_listing_bond(e: Env, bond: i128);
+
+    /// Fetch the BLND bond required to propose a reserve. Reserve proposals are disabled if it is 0.
+    fn get_listing_bond(e: Env) -> i128;
+
+    /// (Admin only) Set or remove the council that decides reserve proposals
+    ///
+    /// ### Arguments
+    /// * `council` - The listing council, or None to have the admin decide reserve proposals
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_listing_council(e: Env, council: Option<Address>);
+
+    /// Fetch the listing council, if one is set
+    fn get_listing_council(e: Env) -> Option<Address>;
+
+    /// Propose adding an asset as a reserve. The proposer bonds the listing bond in BLND, which is
+    /// returned if the proposal is approved, rejected without a slash, or expires undecided.
+    ///
+    /// ### Arguments
+    /// * `proposer` - The address proposing the reserve and posting the bond
+    /// * `asset` - The underlying asset of the proposed reserve
+    /// * `config` - The proposed ReserveConfig
+    ///
+    /// ### Panics
+    /// If reserve proposals are disabled
+    /// or the asset is already a reserve, or has a pending proposal or queued reserve set
+    /// or the config is invalid
+    fn propose_reserve(e: Env, proposer: Address, asset: Address, config: ReserveConfig);
+
+    /// (Listing council or admin only) Approve a reserve proposal. The proposed reserve is queued
+    /// as if by `queue_set_reserve`, and the bond is returned to the proposer.
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the proposed reserve
+    ///
+    /// ### Panics
+    /// If the caller is not the listing council, or the admin if no council is set
+    /// or the asset has no pending proposal
+    fn approve_reserve_proposal(e: Env, asset: Address);
+
+    /// (Listing council or admin only) Reject a reserve proposal
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the proposed reserve
+    /// * `slash` - If the bond is sent to the backstop instead of being returned to the proposer
+    ///
+    /// ### Panics
+    /// If the caller is not the listing council, or the admin if no council is set
+    /// or the asset has no pending proposal
+    fn reject_reserve_proposal(e: Env, asset: Address, slash: bool);
+
+    /// Return the bond of a reserve proposal that expired undecided to its proposer
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the proposed reserve
+    ///
+    /// ### Panics
+    /// If the asset has no proposal or the proposal has not expired
+    fn reclaim_reserve_proposal(e: Env, asset: Address);
+
+    /// Fetch the reserve proposal for an asset, if one exists
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the proposed reserve
+    fn get_reserve_proposal(e: Env, asset: Address) -> Option<ReserveProposal>;
+
+    /// Executes the queued set of a reserve in the pool. If the reserve is new, the admin must authorize
+    /// supplying 0.01 tokens of the asset to the reserve, or 1 base unit if the asset has fewer than 2 decimals.
+    /// The bTokens minted are held by the pool.
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset to add as a reserve
+    ///
+    /// ### Panics
+    /// If the reserve is not queued for initialization
+    /// or is already setup
+    /// or has invalid metadata
+    /// or the admin cannot seed a new reserve
+    fn set_reserve(e: Env, asset: Address) -> u32;
+
+    /// (Admin only) Permanently retire a disabled reserve that has been fully wound down. The reserve
+    /// keeps its index in the reserve list so the index is never reused, but it can no longer be set
+    /// or targeted by pool emissions. Emissions already accrued to its tokens can still be claimed.
+    ///
+    /// Returns the index of the retired reserve
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the reserve
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    /// or the reserve does not exist or is already retired
+    /// or the reserve is enabled
+    /// or the reserve has liabilities, backstop credit, or bTokens held by anyone but the pool
+    fn retire_reserve(e: Env, asset: Address) -> u32;
+
+    /// Fetch if the reserve at an index has been retired
+    ///
+    /// ### Arguments
+    /// * `index` - The index of the reserve
+    fn get_reserve_retired(e: Env, index: u32) -> bool;
+
+    /// Fetch the pool configuration
+    fn get_config(e: Env) -> PoolConfig;
+
+    /// Fetch the auction configuration
+    fn get_auction_config(e: Env) -> AuctionConfig;
+
+    /// Fetch the admin address of the pool
+    fn get_admin(e: Env) -> Address;
+
+    /// Fetch the a vec addresses of all reserves in the pool. The index of the reserve
+    /// in this vec defines the index of the reserve in the pool, used in places like `Positions`.
+    fn get_reserve_list(e: Env) -> Vec<Address>;
+
+    /// Fetch information about a reserve, updated to the current ledger
+    ///
+    /// ### Arguments
+    /// * `asset` - The address of the reserve asset
+    fn get_reserve(e: Env, asset: Address) -> Reserve;
+
+    /// Fetch the total supplied, borrowed, and backstop credit balances of the pool, and the pool's
+    /// TVL, across all reserves. All values are updated to the current ledger and expressed in the
+    /// oracle's base asset.
+    fn get_pool_totals(e: Env) -> PoolTotals;
+
+    /// Fetch a summary of a reserve's balances in underlying tokens, updated to the current ledger.
+    /// This is part of the minimal view of the pool's state used by the backstop.
+    ///
+    /// ### Arguments
+    /// * `asset` - The address of the reserve asset
+    fn get_reserve_summary(e: Env, asset: Address) -> ReserveSummary;
+
+    /// Fetch the bad debt held by the backstop in underlying tokens, updated to the current ledger,
+    /// by reserve asset. This is part of the minimal view of the pool's state used by the backstop.
+    fn get_bad_debt(e: Env) -> Map<Address, i128>;
+
+    /// Project the utilization and rates of a reserve if the given amounts were supplied and
+    /// borrowed, so the rate impact of an action can be shown before it is submitted. Negative
+    /// amounts project withdrawals and repayments. Rates are annual and expressed in 7 decimals.
+    ///
+    /// ### Arguments
+    /// * `asset` - The address of the reserve asset
+    /// * `supply_delta` - The amount of the asset supplied, or withdrawn if negative
+    /// * `borrow_delta` - The amount of the asset borrowed, or repaid if negative
+    fn project_rates(
+        e: Env,
+        asset: Address,
+        supply_delta: i128,
+        borrow_delta: i128,
+    ) -> RateProjection;
+
+    /// Fetch the lifetime interest paid by borrowers and accrued to suppliers of a reserve, updated
+    /// to the current ledger and expressed in the reserve's underlying asset
+    ///
+    /// ### Arguments
+    /// * `asset` - The address of the reserve asset
+    fn get_reserve_stats(e: Env, asset: Address) -> ReserveStats;
+
+    /// Fetch the digits of a reserve's bRate and dRate beyond 12 decimals, updated to the current
+    /// ledger. These extend the rates returned by `get_reserve` to 18 decimals.
+    ///
+    /// ### Arguments
+    /// * `asset` - The address of the reserve asset
+    fn get_reserve_rate_ext(e: Env, asset: Address) -> ReserveRateExt;
+
+    /// Fetch the rounding dust held by a reserve that has not yet been gulped, updated to the
+    /// current ledger, in underlying tokens
+    ///
+    /// ### Arguments
+    /// * `asset` - The address of the reserve asset
+    fn get_reserve_dust(e: Env, asset: Address) -> i128;
+
+    /// Fetch the price band for a reserve, if one is set
+    ///
+    /// ### Arguments
+    /// * `asset` - The address of the reserve asset
+    fn get_price_band(e: Env, asset: Address) -> Option<PriceBand>;
+
+    /// Fetch the actions paused for a reserve
+    ///
+    /// ### Arguments
+    /// * `asset` - The address of the reserve asset
+    fn get_reserve_paused(e: Env, asset: Address) -> ReservePause;
+
+    /// Fetch the positions for an address. For each position type, there is a map of the reserve index
+    /// to the position for that reserve, if it exists.
+    ///
+    /// ### Arguments
+    /// * `address` - The address to fetch positions for
+    fn get_positions(e: Env, address: Address) -> Positions;
+
+    /// Fetch the most recent position checkpoints for a user, oldest first. Each checkpoint contains
+    /// the user's b/dToken balances and the rates of the reserves they hold at the end of a submit.
+    ///
+    /// ### Arguments
+    /// * `user` - The address of the user
+    fn get_position_history_checkpoint(e: Env, user: Address) -> Vec<PositionCheckpoint>;
+
+    /// Fetch the pool's activity counters for the most recent days, capped at the analytics window.
+    /// Days are numbered from the unix epoch, and days without activity are omitted.
+    ///
+    /// ### Arguments
+    /// * `days` - The number of days to fetch, including the current day
+    fn get_daily_stats(e: Env, days: u32) -> Map<u64, DailyStats>;
+
+    /// Fetch the withdrawal queued by a user for a reserve, if one exists. Queued bTokens remain in
+    /// the user's supply position until they are claimed.
+    ///
+    /// ### Arguments
+    /// * `user` - The address of the user
+    /// * `asset` - The address of the reserve asset
+    fn get_queued_withdrawal(e: Env, user: Address, asset: Address) -> Option<QueuedWithdrawal>;
+
+    /// Fetch the lifetime totals of a reserve's withdrawal queue. A queued withdrawal can be
+    /// claimed once the queue's `removed` total reaches the withdrawal's `seq`.
+    ///
+    /// ### Arguments
+    /// * `asset` - The address of the reserve asset
+    fn get_withdraw_queue(e: Env, asset: Address) -> WithdrawQueue;
+
+    /// Submit a set of requests to the pool where `from` takes on the position, `spender` sends any
+    /// required tokens to the pool and `to` receives any tokens sent from the pool.
+    ///
+    /// Returns the new positions for `from`, or the error if an address is the pool or the new positions
+    /// are invalid, like an invalid health factor or an ongoing liquidation
+    ///
+    /// Requires authorization from `spender`, and from `from` unless every request is a supply or supply
+    /// collateral request and `from` allows supplying on its behalf
+    ///
+    /// ### Arguments
+    /// * `from` - The address of the user whose positions are being modified
+    /// * `spender` - The address of the user who is sending tokens to the pool
+    /// * `to` - The address of the user who is receiving tokens from the pool
+    /// * `requests` - A vec of requests to be processed
+    ///
+    /// ### Panics
+    /// If a request is not able to be completed for cases like insufficient funds
+    fn submit(
+        e: Env,
+        from: Address,
+        spender: Address,
+        to: Address,
+        requests: Vec<Request>,
+    ) -> Result<Positions, PoolError>;
+
+    /// Submit a set of requests to the pool where `from` takes on the position, `spender` sends any
+    /// required tokens to the pool using transfer_from and `to` receives any tokens sent from the pool.
+    ///
+    /// Returns the new positions for `from`, or the error if an address is the pool or the new positions
+    /// are invalid, like an invalid health factor or an ongoing liquidation
+    ///
+    /// Requires authorization from `spender`, and from `from` unless every request is a supply or supply
+    /// collateral request and `from` allows supplying on its behalf
+    ///
+    /// ### Arguments
+    /// * `from` - The address of the user whose positions are being modified
+    /// * `spender` - The address of the user who is sending tokens to the pool
+    /// * `to` - The address of the user who is receiving tokens from the pool
+    /// * `requests` - A vec of requests to be processed
+    ///
+    /// ### Panics
+    /// If a request is not able to be completed for cases like insufficient funds or insufficient allowance
+    fn submit_with_allowance(
+        e: Env,
+        from: Address,
+        spender: Address,
+        to: Address,
+        requests: Vec<Request>,
+    ) -> Result<Positions, PoolError>;
+
+    /// Submit flash loan and a set of requests to the pool where `from` takes on the position. The flash loan will be invoked using
+    /// the `flash_loan` arguments and `from` as the caller. For the requests, `from` sends any required tokens to the pool
+    /// using transfer_from and receives any tokens sent from the pool.
+    ///
+    /// Returns the new positions for `from`, or the error if `from` is the pool or the new positions
+    /// are invalid, like an invalid health factor or an ongoing liquidation
+    ///
+    /// ### Arguments
+    /// * `from` - The address of the user whose positions are being modified and also the address of
+    /// the user who is sending and receiving the tokens to the pool.
+    /// * `flash_loan` - Arguments relative to the flash loan: receiver contract, asset and borroed amount.
+    /// * `requests` - A vec of requests to be processed
+    ///
+    /// ### Panics
+    /// If the request is not able to be completed for cases like insufficient funds or insufficient allowance
+    fn flash_loan(
+        e: Env,
+        from: Address,
+        flash_loan: FlashLoan,
+        requests: Vec<Request>,
+    ) -> Result<Positions, PoolError>;
+
+    /// Fill a user liquidation auction where `filler` pays for the bid with a whitelisted quote asset.
+    /// The pool swaps the quote asset into each bid asset through the quote asset's adapter and repays
+    /// the liabilities, so `filler` only receives the lot. Any unused quote asset is returned to `filler`.
+    ///
+    /// Returns the new positions for `filler`
+    ///
+    /// ### Arguments
+    /// * `filler` - The address filling the auction
+    /// * `quote_fill` - The auction being filled, the quote asset, and the max amount of it to pay
+    ///
+    /// ### Panics
+    /// If the quote asset is not whitelisted, the fill costs more than the max quote amount,
+    /// or `filler` has an invalid health factor after the fill
+    fn fill_with_quote(e: Env, filler: Address, quote_fill: QuoteFill) -> Positions;
+
+    /// Fill a user liquidation auction where the lot is sold by a hook contract to fund the bid, so a
+    /// filler can fill without holding the bid assets. The lot collateral is withdrawn to the hook, and
+    /// the hook's `on_fill` is invoked before the bid is settled. The hook must send the bid to the
+    /// pool before returning, and the bid liabilities the filler took on are repaid with it.
+    ///
+    /// Returns the new positions for `filler`
+    ///
+    /// ### Arguments
+    /// * `filler` - The address filling the auction
+    /// * `hook_fill` - The auction being filled and the hook funding the bid
+    ///
+    /// ### Panics
+    /// If the hook does not send the bid to the pool, withdrawing the lot leaves a reserve over 100%
+    /// utilization, or `filler` has an invalid health factor after the fill
+    fn fill_with_hook(e: Env, filler: Address, hook_fill: HookFill) -> Positions;
+
+    /// (Filler only) Commit to filling a user liquidation auction with `reveal_fill` in a later block.
+    /// The hash is the sha256 hash of the XDR encoded `(user, percent_filled, salt)` tuple, where the
+    /// salt is a secret 32 bytes. Overwrites any existing commitment for the filler.
+    ///
+    /// ### Arguments
+    /// * `filler` - The address committing to the fill
+    /// * `hash` - The hash of the fill
+    ///
+    /// ### Panics
+    /// If the caller is not the filler
+    fn commit_fill(e: Env, filler: Address, hash: BytesN<32>);
+
+    /// Fill a user liquidation auction by revealing the fill `filler` committed to with `commit_fill`.
+    /// The fill can be revealed in the blocks after the commitment, up to the pool's fill commit window.
+    ///
+    /// Returns the new positions for `filler`
+    ///
+    /// ### Arguments
+    /// * `filler` - The address filling the auction
+    /// * `user` - The user being liquidated
+    /// * `percent_filled` - The percentage being filled as a number (i.e. 15 => 15%)
+    /// * `salt` - The salt the fill was committed to with
+    ///
+    /// ### Panics
+    /// If the fill does not match the filler's commitment, the commitment can't be revealed in the
+    /// current block, or `filler` has an invalid health factor after the fill
+    fn reveal_fill(
+        e: Env,
+        filler: Address,
+        user: Address,
+        percent_filled: u64,
+        salt: BytesN<32>,
+    ) -> Positions;
+
+    /// Sell a portion of `from`'s collateral through the collateral asset's whitelisted swap adapter
+    /// and repay `from`'s liabilities with the proceeds, without requiring any tokens from `from`.
+    ///
+    /// Returns the new positions for `from`
+    ///
+    /// ### Arguments
+    /// * `from` - The address of the user deleveraging their positions
+    /// * `deleverage` - The collateral asset to sell, the liability asset and amount to repay, and the
+    ///                  max amount of collateral to sell
+    ///
+    /// ### Panics
+    /// If the collateral asset is not whitelisted, the repayment costs more than the max collateral amount,
+    /// or `from` has an invalid health factor after the deleverage
+    fn deleverage(e: Env, from: Address, deleverage: Deleverage) -> Positions;
+
+    /// (Delegator only) Approve a delegatee to borrow an asset against the delegator's positions. Overwrites
+    /// any existing allowance for the delegatee and asset.
+    ///
+    /// ### Arguments
+    /// * `delegator` - The address whose positions back the borrow
+    /// * `delegatee` - The address allowed to borrow
+    /// * `asset` - The reserve asset that can be borrowed
+    /// * `amount` - The amount of the asset the delegatee can borrow
+    ///
+    /// ### Panics
+    /// If the caller is not the delegator, the amount is negative, or the asset is not a reserve
+    fn approve_borrow(e: Env, delegator: Address, delegatee: Address, asset: Address, amount: i128);
+
+    /// Fetch the amount of an asset a delegatee is allowed to borrow against the delegator's positions
+    ///
+    /// ### Arguments
+    /// * `delegator` - The address whose positions back the borrow
+    /// * `delegatee` - The address allowed to borrow
+    /// * `asset` - The reserve asset
+    fn get_borrow_allowance(e: Env, delegator: Address, delegatee: Address, asset: Address)
+        -> i128;
+
+    /// (Delegatee only) Borrow an asset against the delegator's positions, using the delegatee's allowance.
+    /// The delegator takes on the liability and the borrowed tokens are sent to the delegatee.
+    ///
+    /// Returns the new positions for `delegator`
+    ///
+    /// ### Arguments
+    /// * `delegator` - The address whose positions back the borrow
+    /// * `delegatee` - The address borrowing
+    /// * `asset` - The reserve asset to borrow
+    /// * `amount` - The amount of the asset to borrow
+    ///
+    /// ### Panics
+    /// If the caller is not the delegatee, the amount exceeds the allowance, or the borrow
+    /// is not able to be completed for cases like invalid health factor
+    fn delegated_borrow(
+        e: Env,
+        delegator: Address,
+        delegatee: Address,
+        asset: Address,
+        amount: i128,
+    ) -> Positions;
+
+    /// Update the pool status based on the backstop state - backstop triggered status' are odd numbers
+    /// * 1 = backstop active - if the minimum backstop deposit has been reached
+    ///                and 30% of backstop deposits are not queued for withdrawal
+    ///                then all pool operations are permitted
+    /// * 3 = backstop on-ice - if the minimum backstop deposit has not been reached
+    ///                or 30% of backstop deposits are queued for withdrawal and admin active isn't set
+    ///                or 50% of backstop deposits are queued for withdrawal
+    ///                then borrowing and cancelling liquidations are not permitted
+    /// * 5 = backstop frozen - if 60% of backstop deposits are queued for withdrawal and admin on-ice isn't set
+    ///                or 75% of backstop deposits are queued for withdrawal
+    ///                then all borrowing, cancelling liquidations, and supplying are not permitted
+    ///
+    /// ### Panics
+    /// If the pool is currently on status 4, "admin-freeze", where only the admin
+    /// can perform a status update via `set_status`, unless the freeze has expired
+    fn update_status(e: Env) -> u32;
+
+    /// (Admin only) Pool status is changed to `pool_status`
+    /// * 0 = admin active - requires that the backstop threshold is met
+    ///                 and less than 50% of backstop deposits are queued for withdrawal
+    /// * 2 = admin on-ice - requires that less than 75% of backstop deposits are queued for withdrawal
+    /// * 4 = admin frozen - can always be set
+    ///
+    /// ### Arguments
+    /// * `pool_status` - The pool status to be set
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    /// If the specified conditions are not met for the status to be set
+    fn set_status(e: Env, pool_status: u32);
+
+    /// Gulps unaccounted for tokens to the backstop credit so they aren't lost. This is most relevant
+    /// for rebasing tokens where the token balance of the pool can increase without any corresponding
+    /// transfer.
+    ///
+    /// Blend Pools do not support fee-on-transaction tokens, or any tokens in which the pools balance
+    /// can decrease without any corresponding withdraw. Thus, negative token deltas are ignored.
+    ///
+    /// The tokens lost to rounding when the reserve's bRate is accrued are tracked as the reserve's
+    /// dust, and are credited to the backstop credit first.
+    ///
+    /// ### Arguments
+    /// * `asset` - The address of the asset to gulp
+    ///
+    /// Returns the amount of tokens gulped, including the reserve's dust
+    fn gulp(e: Env, asset: Address) -> i128;
+
+    /********* Emission Functions **********/
+
+    /// Consume emissions from the backstop and distribute to the reserves based
+    /// on the reserve emission configuration.
+    ///
+    /// Returns amount of new tokens emitted
+    fn gulp_emissions(e: Env) -> i128;
+
+    /// (Admin only) Set the emission configuration for the pool
+    ///
+    /// Changes will be applied in the next pool `update_emissions`, and affect the next emission cycle.
+    /// Once the pool has left setup, this must be queued with `queue_admin_action` instead.
+    ///
+    /// ### Arguments
+    /// * `res_emission_metadata` - A vector of ReserveEmissionMetadata to update metadata to
+    ///
+    /// ### Panics
+    /// * If the caller is not the admin
+    /// * If the pool has left setup
+    fn set_emissions_config(e: Env, res_emission_metadata: Vec<ReserveEmissionMetadata>);
+
+    /// (Admin only) Adjust the emission share of a single reserve token without resetting the rest
+    /// of the emission configuration. Shares are relative to the total of all shares.
+    ///
+    /// Changes will be applied in the next pool `update_emissions`, and affect the next emission cycle.
+    /// Once the pool has left setup, this must be queued with `queue_admin_action` instead.
+    ///
+    /// ### Arguments
+    /// * `res_token_id` - The reserve token id to adjust
+    /// * `new_share` - The new share of the reserve token, or 0 to remove it
+    ///
+    /// ### Panics
+    /// * If the caller is not the admin
+    /// * If the reserve token id is invalid
+    /// * If no reserve tokens would be left to receive emissions
+    /// * If the pool has left setup
+    fn adjust_emission_share(e: Env, res_token_id: u32, new_share: u64);
+
+    /// (Admin only) Set the emission decay of a single reserve token. Each emission epoch, the share
+    /// of the reserve token is reduced to `factor` of its previous share when emissions are gulped, so
+    /// incentive programs taper without repeated updates to the emission configuration.
+    ///
+    /// Once the pool has left setup, this must be queued with `queue_admin_action` instead.
+    ///
+    /// ### Arguments
+    /// * `res_token_id` - The reserve token id to decay
+    /// * `factor` - The fraction of the share kept each epoch in 7 decimals, or 0 to remove the decay
+    ///
+    /// ### Panics
+    /// * If the caller is not the admin
+    /// * If the reserve token id is invalid
+    /// * If the factor is not below 100%
+    /// * If the pool has left setup
+    fn set_emission_decay(e: Env, res_token_id: u32, factor: u32);
+
+    /// Fetch the emission decay of a reserve token, if one is set
+    ///
+    /// ### Arguments
+    /// * `res_token_id` - The reserve token id
+    fn get_emission_decay(e: Env, res_token_id: u32) -> Option<EmissionDecay>;
+
+    /// (Admin only) Enable or disable gulping emissions automatically. While enabled, the first `submit`,
+    /// `submit_with_allowance` or `flash_loan` of each emission cycle gulps emissions from the backstop
+    /// before processing the requests. A failed gulp does not block the submission.
+    ///
+    /// ### Arguments
+    /// * `auto_gulp` - If emissions should be gulped automatically
+    ///
+    /// ### Panics
+    /// * If the caller is not the admin
+    fn set_auto_gulp(e: Env, auto_gulp: bool);
+
+    /// Fetch if emissions are gulped automatically on submit
+    fn get_auto_gulp(e: Env) -> bool;
+
+    /// (Admin only) Enable or disable the borrower index. While enabled, any address whose positions are
+    /// stored with liabilities is added to the index. Addresses are removed from the index once they
+    /// have no liabilities, even while the index is disabled. Addresses that borrowed before the index
+    /// was enabled are added the next time their positions change.
+    ///
+    /// ### Arguments
+    /// * `borrower_index` - If addresses with liabilities should be recorded in the borrower index
+    ///
+    /// ### Panics
+    /// * If the caller is not the admin
+    fn set_borrower_index(e: Env, borrower_index: bool);
+
+    /// Fetch if addresses with liabilities are recorded in the borrower index
+    fn get_borrower_index(e: Env) -> bool;
+
+    /// (Admin only) Enable or disable exempting the backstop from the pool's maximum positions. Bad debt
+    /// from any number of reserves can be assigned to the backstop, so while exempt, bad debt auctions
+    /// can bid on all of it at once and the backstop's own positions are not limited.
+    ///
+    /// ### Arguments
+    /// * `exempt` - If the backstop should be exempt from the pool's maximum positions
+    ///
+    /// ### Panics
+    /// * If the caller is not the admin
+    fn set_backstop_positions_exempt(e: Env, exempt: bool);
+
+    /// Fetch if the backstop is exempt from the pool's maximum positions
+    fn get_backstop_positions_exempt(e: Env) -> bool;
+
+    /// Fetch the number of addresses in the borrower index
+    fn get_borrower_count(e: Env) -> u32;
+
+    /// Fetch a page of addresses from the borrower index, so keepers can scan for positions to
+    /// liquidate. The order of the index changes as borrowers are removed.
+    ///
+    /// Returns at most 100 addresses
+    ///
+    /// ### Arguments
+    /// * `start` - The index of the first address to return
+    /// * `limit` - The maximum number of addresses to return
+    fn get_borrowers(e: Env, start: u32, limit: u32) -> Vec<Address>;
+
+    /// (Admin only) Enable or disable depositing interest auction bids into the backstop. While enabled,
+    /// the backstop tokens bid to fill an interest auction are deposited into the backstop on behalf of
+    /// the pool, so the pool holds backstop shares that compound with future interest. While disabled,
+    /// the bids are donated to the backstop. Enabled by default.
+    ///
+    /// ### Arguments
+    /// * `interest_deposit` - If interest auction bids should be deposited into the backstop
+    ///
+    /// ### Panics
+    /// * If the caller is not the admin
+    fn set_interest_deposit(e: Env, interest_deposit: bool);
+
+    /// Fetch if interest auction bids are deposited into the backstop
+    fn get_interest_deposit(e: Env) -> bool;
+
+    /// Claims outstanding emissions for the caller for the given reserve's.
+    ///
+    /// A reserve token id is a unique identifier for a position in a pool.
+    /// - For a reserve's dTokens (liabilities), reserve_token_id = reserve_index * 2
+    /// - For a reserve's bTokens (supply/collateral), reserve_token_id = reserve_index * 2 + 1
+    ///
+    /// Returns the number of tokens claimed
+    ///
+    /// ### Arguments
+    /// * `from` - The address claiming
+    /// * `reserve_token_ids` - Vector of reserve token ids
+    /// * `to` - The Address to send the claimed tokens to
+    fn claim(e: Env, from: Address, reserve_token_ids: Vec<u32>, to: Address) -> i128;
+
+    /// Fetch the emissions a user can claim for the given reserve token ids at the current ledger
+    /// timestamp, including emissions accrued since each reserve token was last updated
+    ///
+    /// ### Arguments
+    /// * `user` - The address of the user
+    /// * `reserve_token_ids` - Vector of reserve token ids
+    fn get_claimable_emissions(e: Env, user: Address, reserve_token_ids: Vec<u32>) -> i128;
+
+    /// Claims outstanding emissions for the caller for the given reserve's, and converts the claimed
+    /// BLND through the pool's claim converter. The converted asset is sent to `to`.
+    ///
+    /// Returns the amount of the converted asset received
+    ///
+    /// ### Arguments
+    /// * `from` - The address claiming
+    /// * `reserve_token_ids` - Vector of reserve token ids
+    /// * `to` - The Address to send the converted tokens to
+    /// * `min_amount_out` - The minimum amount of the converted asset to receive
+    ///
+    /// ### Panics
+    /// * If no claim converter is set
+    /// * If less than `min_amount_out` of the converted asset is received
+    fn claim_converted(
+        e: Env,
+        from: Address,
+        reserve_token_ids: Vec<u32>,
+        to: Address,
+        min_amount_out: i128,
+    ) -> i128;
+
+    /// Get the emissions data for a reserve token
+    ///
+    /// A reserve token id is a unique identifier for a position in a pool.
+    /// - For a reserve's dTokens (liabilities), reserve_token_id = reserve_index * 2
+    /// - For a reserve's bTokens (supply/collateral), reserve_token_id = reserve_index * 2 + 1
+    ///
+    /// ### Arguments
+    /// * `reserve_token_id` - The reserve token id
+    fn get_reserve_emissions(e: Env, reserve_token_id: u32) -> Option<ReserveEmissionData>;
+
+    /// Get the undistributed emissions carried over to the next emission epoch of a reserve token.
+    /// Emissions are carried over when a reserve token has no supply to distribute them to, and are
+    /// added to the reserve token's emissions the next time emissions are gulped while it has supply.
+    ///
+    /// ### Arguments
+    /// * `reserve_token_id` - The reserve token id
+    fn get_emission_carryover(e: Env, reserve_token_id: u32) -> i128;
+
+    /// Get the emissions data for a user
+    ///
+    /// A reserve token id is a unique identifier for a position in a pool.
+    /// - For a reserve's dTokens (liabilities), reserve_token_id = reserve_index * 2
+    /// - For a reserve's bTokens (supply/collateral), reserve_token_id = reserve_index * 2 + 1
+    ///
+    /// ### Arguments
+    /// * `user` - The address of the user
+    /// * `reserve_token_id` - The reserve token id
+    fn get_user_emissions(e: Env, user: Address, reserve_token_id: u32)
+        -> Option<UserEmissionData>;
+
+    /***** Auction / Liquidation Functions *****/
+
+    /// Create a new auction. Auctions are used to process liquidations, bad debt, and interest.
+    ///
+    /// ### Arguments
+    /// * `auction_type` - The type of auction, 0 for liquidation auction, 1 for bad debt auction, and 2 for interest auction
+    /// * `user` - The Address involved in the auction. This is generally the source of the assets being auctioned.
+    ///            For bad debt and interest auctions, this is expected to be the backstop address.
+    /// * `bid` - The set of assets to include in the auction bid, or what the filler spends when filling the auction.
+    /// * `lot` - The set of assets to include in the auction lot, or what the filler receives when filling the auction.
+    /// * `percent` - The percent of the assets to be auctioned off as a percentage (15 => 15%). For bad debt and interest auctions.
+    ///               this is expected to be 100.
+    ///
+    /// For liquidation auctions, if the lot does not include all of the user's collateral, the lot must
+    /// hold at least the share of the user's collateral value that the auction removes from the user's
+    /// liability value. The user's liquidation protection hook is invoked before the auction is created,
+    /// and the auction is sized on the positions it leaves.
+    fn new_auction(
+        e: Env,
+        auction_type: u32,
+        user: Address,
+        bid: Vec<Address>,
+        lot: Vec<Address>,
+        percent: u32,
+    ) -> AuctionData;
+
+    /// Create a new interest auction that auctions at most the given amount of each reserve's backstop
+    /// credit. Any backstop credit over the maximum is left on the reserve, so a reserve's ongoing credit
+    /// buffer can be kept intact while the excess is auctioned.
+    ///
+    /// ### Arguments
+    /// * `bid` - The set of assets to include in the auction bid. This is expected to be the backstop token.
+    /// * `lot` - A map of the assets to include in the auction lot to the maximum amount of each to auction
+    ///
+    /// ### Panics
+    /// If a maximum lot amount is not positive
+    /// or the interest auction is unable to be created
+    fn new_interest_auction(e: Env, bid: Vec<Address>, lot: Map<Address, i128>) -> AuctionData;
+
+    /// Preview the auction `new_auction` would create at the current ledger, without writing to the ledger.
+    /// The position does not need to be liquidatable yet, so bots and UIs can plan liquidations or warn
+    /// users ahead of time. The preview also ignores any auction in progress, and for interest auctions
+    /// the auction interval and minimum interest value. Liquidation hooks are not invoked.
+    ///
+    /// ### Arguments
+    /// * `auction_type` - The type of auction, 0 for liquidation auction, 1 for bad debt auction, and 2 for interest auction
+    /// * `user` - The Address involved in the auction
+    /// * `bid` - The set of assets to include in the auction bid
+    /// * `lot` - The set of assets to include in the auction lot
+    /// * `percent` - The percent of the assets to be auctioned off as a percentage (15 => 15%)
+    ///
+    /// ### Panics
+    /// If the assets or percent are invalid for the auction type
+    fn preview_new_auction(
+        e: Env,
+        auction_type: u32,
+        user: Address,
+        bid: Vec<Address>,
+        lot: Vec<Address>,
+        percent: u32,
+    ) -> AuctionData;
+
+    /// (Admin only) Pause or unpause the creation of new auctions of all types. Auctions that already
+    /// exist can still be filled and deleted while creation is paused, so in-flight liquidations are
+    /// not stranded while an issue like an oracle anomaly is investigated.
+    ///
+    /// ### Arguments
+    /// * `paused` - If the creation of new auctions should be paused
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_auctions_paused(e: Env, paused: bool);
+
+    /// Fetch if the creation of new auctions is paused
+    fn get_auctions_paused(e: Env) -> bool;
+
+    /// Fetch the cumulative auction fills of a filler, by auction type, if they have filled an auction
+    ///
+    /// ### Arguments
+    /// * `filler` - The address of the filler
+    fn get_filler_stats(e: Env, filler: Address) -> Option<FillerStats>;
+
+    /// Fetch the number of addresses that have filled an auction
+    fn get_filler_count(e: Env) -> u32;
+
+    /// Fetch a page of the addresses that have filled an auction, in the order of their first fill,
+    /// with their cumulative auction fills
+    ///
+    /// Returns at most 100 fillers
+    ///
+    /// ### Arguments
+    /// * `start` - The index of the first filler to return
+    /// * `limit` - The maximum number of fillers to return
+    fn get_fillers(e: Env, start: u32, limit: u32) -> Vec<(Address, FillerStats)>;
+
+    /// (User only) Set or remove a liquidation protection hook for the user. The hook is invoked
+    /// before a user liquidation auction is created against the user and by `run_liquidation_hook`,
+    /// and can supply collateral or repay liabilities on the user's behalf. See `LiquidationHook` for
+    /// the interface.
+    ///
+    /// ### Arguments
+    /// * `user` - The user setting the hook
+    /// * `hook` - The address of the hook contract, or None to remove it
+    ///
+    /// ### Panics
+    /// If the caller is not the user
+    fn set_liquidation_hook(e: Env, user: Address, hook: Option<Address>);
+
+    /// Fetch the liquidation protection hook for a user, if one is set
+    ///
+    /// ### Arguments
+    /// * `user` - The address of the user
+    fn get_liquidation_hook(e: Env, user: Address) -> Option<Address>;
+
+    /// Invoke a user's liquidation protection hook, if one is set, and apply the requests it returns
+    /// to the user's positions. Anyone can run the hook ahead of creating a liquidation auction against
+    /// the user. Creating the auction also invokes the hook, but ignores it if it fails, so a failing
+    /// hook can't block a liquidation.
+    ///
+    /// Returns true if the user is no longer eligible to be liquidated
+    ///
+    /// ### Arguments
+    /// * `user` - The address of the user
+    fn run_liquidation_hook(e: Env, user: Address) -> bool;
+
+    /// (User only) Opt in or out of the pool's liquidation protection insurance. Any premium owed
+    /// for the user's current coverage is charged first.
+    ///
+    /// Returns the user's positions
+    ///
+    /// ### Arguments
+    /// * `user` - The user
+    /// * `insured` - If the user is insured
+    ///
+    /// ### Panics
+    /// If the caller is not the user, or the user opts in while the pool does not offer insurance
+    fn set_insured(e: Env, user: Address, insured: bool) -> Positions;
+
+    /// Fetch if a user is insured
+    ///
+    /// ### Arguments
+    /// * `user` - The address of the user
+    fn is_insured(e: Env, user: Address) -> bool;
+
+    /// (User only) Allow or disallow others to supply to the user's positions without the user's authorization.
+    /// When allowed, a `submit` where `from` is the user and every request is a supply or supply collateral
+    /// request only requires authorization from `spender`.
+    ///
+    /// ### Arguments
+    /// * `user` - The address of the user
+    /// * `allowed` - If supplying on behalf of the user is allowed
+    fn set_supply_on_behalf(e: Env, user: Address, allowed: bool);
+
+    /// Fetch if others can supply to the user's positions without the user's authorization
+    ///
+    /// ### Arguments
+    /// * `user` - The address of the user
+    fn get_supply_on_behalf(e: Env, user: Address) -> bool;
+
+    /// (User only) Enable or disable auto-claiming emissions. When enabled, the emissions of every reserve
+    /// token whose balance a `submit` or `flash_loan` changes for the user are claimed and sent to the user.
+    ///
+    /// ### Arguments
+    /// * `user` - The address of the user
+    /// * `auto_claim` - If emissions are claimed automatically when the user submits requests
+    fn set_auto_claim(e: Env, user: Address, auto_claim: bool);
+
+    /// Fetch if the user's emissions are claimed automatically when they submit requests
+    ///
+    /// ### Arguments
+    /// * `user` - The address of the user
+    fn get_auto_claim(e: Env, user: Address) -> bool;
+
+    /// (User only) Enable or disable request planning. When enabled, each run of supply, withdraw, borrow
+    /// and repay requests in a `submit` or `flash_loan` executes every supply and repay before any withdrawal
+    /// or borrow. Other requests keep their place. If the same address sends and receives tokens, a withdrawal
+    /// or repayment that offsets an earlier supply or borrow of the same asset is also netted against it.
+    ///
+    /// ### Arguments
+    /// * `user` - The address of the user
+    /// * `plan_requests` - If the user's requests are reordered before they are executed
+    fn set_plan_requests(e: Env, user: Address, plan_requests: bool);
+
+    /// Fetch if the user's requests are reordered before they are executed
+    ///
+    /// ### Arguments
+    /// * `user` - The address of the user
+    fn get_plan_requests(e: Env, user: Address) -> bool;
+
+    /// (User only) Set the minimum health factor the user allows their own requests to leave them at. Any
+    /// request that requires a health check, like a borrow or a collateral withdrawal, fails if it would leave
+    /// the user below it, even if the pool would allow it.
+    ///
+    /// ### Arguments
+    /// * `user` - The address of the user
+    /// * `min_hf` - The minimum health factor in 7 decimals, or 0 to remove the limit
+    ///
+    /// ### Panics
+    /// If the caller is not the user or the minimum health factor is not 0 and not between 1 and 100
+    fn set_min_hf(e: Env, user: Address, min_hf: u32);
+
+    /// Fetch the minimum health factor the user allows their own requests to leave them at, or 0 if there is no limit
+    ///
+    /// ### Arguments
+    /// * `user` - The address of the user
+    fn get_min_hf(e: Env, user: Address) -> u32;
+
+    /// Fetch an auction from the ledger. Returns the base auction. On fill, this will be scaled based on the
+    /// number of blocks that have passed since the auction was created.
+    ///
+    /// ### Arguments
+    /// * `auction_type` - The type of auction, 0 for liquidation auction, 1 for bad debt auction, and 2 for interest auction
+    /// * `user` - The Address involved in the auction
+    ///
+    /// ### Panics
+    /// If the auction does not exist
+    fn get_auction(e: Env, auction_type: u32, user: Address) -> AuctionData;
+
+    /// Fetch every ongoing auction for a user, such as to check if the user is being liquidated
+    ///
+    /// Returns a vec of the auction type and auction data of each ongoing auction, ordered by type
+    ///
+    /// ### Arguments
+    /// * `user` - The Address involved in the auctions
+    fn get_user_auctions(e: Env, user: Address) -> Vec<(u32, AuctionData)>;
+
+    /// Check if a fill of an auction would succeed at the current ledger without filling it. The filler's
+    /// positions are validated as if the fill was the only request submitted.
+    ///
+    /// Returns Ok if the fill would succeed, or the error the fill would fail with
+    ///
+    /// ### Arguments
+    /// * `auction_type` - The type of auction, 0 for liquidation auction, 1 for bad debt auction, and 2 for interest auction
+    /// * `user` - The Address involved in the auction
+    /// * `filler` - The Address filling the auction
+    /// * `percent_filled` - The percentage being filled as a number (i.e. 15 => 15%)
+    fn can_fill(
+        e: Env,
+        auction_type: u32,
+        user: Address,
+        filler: Address,
+        percent_filled: u64,
+    ) -> Result<(), PoolError>;
+
+    /// Delete a stale auction. A stale auction is one that has been running for 500 blocks
+    /// without being filled. This likely means something went wrong with the auction creation,
+    /// and it should be re-created.
+    ///
+    /// ### Arguments
+    /// * `auction_type` - The type of auction, 0 for liquidation auction, 1 for bad debt auction, and 2 for interest auction
+    /// * `user` - The Address involved in the auction
+    ///
+    /// ### Panics
+    /// * If the auction does not exist
+    /// * If the auction is not stale
+    fn del_auction(e: Env, auction_type: u32, user: Address);
+
+    /// Check and handle bad debt for a user.
+    /// * If the user is not the backstop and they have bad debt, the backstop will take over the debt.
+    /// * If the user is the backstop, the backstop health will be checked, and if it is unhealthy, the backstop will default it's
+    /// remaining debt.
+    ///
+    /// ### Arguments
+    /// * `user` - The address of the user to check for bad debt
+    ///
+    /// ### Panics
+    /// * If there is no bad debt to handle
+    /// * If there is an ongoing auction for the user
+    fn bad_debt(e: Env, user: Address);
+
+    /// Repay a reserve's unrecovered bad debt, such as when a user whose debt was passed to the backstop
+    /// repays it, or when funds are recovered from them. The tokens are split between the reserve's suppliers
+    /// and the backstop pro rata to the unrecovered bad debt each has taken on.
+    ///
+    /// Returns the amount of tokens repaid, which is capped at the reserve's unrecovered bad debt
+    ///
+    /// ### Arguments
+    /// * `from` - The address repaying the bad debt
+    /// * `asset` - The underlying asset of the reserve
+    /// * `amount` - The amount of tokens to repay
+    ///
+    /// ### Panics
+    /// * If the amount is not positive
+    /// * If the reserve has no unrecovered bad debt
+    fn repay_deficit(e: Env, from: Address, asset: Address, amount: i128) -> i128;
+
+    /// Fetch the unrecovered bad debt of a reserve, and the bad debt recovered over its lifetime
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the reserve
+    fn get_deficit(e: Env, asset: Address) -> ReserveDeficit;
+
+    /// Fetch the deficit of each reserve that has taken on bad debt, keyed by the reserve's asset
+    fn get_deficits(e: Env) -> Map<Address, ReserveDeficit>;
+
+    /********* Keeper Functions **********/
+
+    /// (Admin only) Set the rebate paid to registered keepers for each maintenance task they perform
+    /// through `upkeep`. Rebates are paid from the keeper pot.
+    ///
+    /// ### Arguments
+    /// * `token` - The token rebates are paid in
+    /// * `rebate` - The amount of tokens paid for each task
+    ///
+    /// ### Panics
+    /// * If the caller is not the admin
+    /// * If the rebate is not positive
+    /// * If the token is changed while the keeper pot still holds tokens
+    fn set_keeper_config(e: Env, token: Address, rebate: i128);
+
+    /// Fetch the keeper rebate config, if one has been set
+    fn get_keeper_config(e: Env) -> Option<KeeperConfig>;
+
+    /// (Admin only) Register or unregister an address as a keeper
+    ///
+    /// ### Arguments
+    /// * `keeper` - The address of the keeper
+    /// * `registered` - If the address is registered as a keeper
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_keeper(e: Env, keeper: Address, registered: bool);
+
+    /// Check if an address is registered as a keeper
+    ///
+    /// ### Arguments
+    /// * `keeper` - The address
+    fn is_keeper(e: Env, keeper: Address) -> bool;
+
+    /// Fund the keeper pot with the rebate token
+    ///
+    /// Returns the new amount of tokens in the pot
+    ///
+    /// ### Arguments
+    /// * `from` - The address funding the pot
+    /// * `amount` - The amount of tokens to add
+    ///
+    /// ### Panics
+    /// If the amount is not positive or keeper rebates are not configured
+    fn fund_keeper_pot(e: Env, from: Address, amount: i128) -> i128;
+
+    /// (Admin only) Withdraw tokens from the keeper pot to the admin
+    ///
+    /// Returns the remaining amount of tokens in the pot
+    ///
+    /// ### Arguments
+    /// * `amount` - The amount of tokens to withdraw
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the amount is not positive or is more than the pot holds
+    fn withdraw_keeper_pot(e: Env, amount: i128) -> i128;
+
+    /// Fetch the amount of tokens available to pay keeper rebates
+    fn get_keeper_pot(e: Env) -> i128;
+
+    /// (Keeper only) Perform a maintenance task, paying the keeper the rebate if the task had an effect.
+    /// A rebate is paid when emissions are gulped, when the pool status changes, or when a stale auction
+    /// is deleted. The rebate is capped at the tokens left in the keeper pot.
+    ///
+    /// Returns the rebate paid to the keeper
+    ///
+    /// ### Arguments
+    /// * `keeper` - The address of the keeper
+    /// * `task` - The maintenance task to perform
+    ///
+    /// ### Panics
+    /// * If the keeper is not registered
+    /// * If the task panics when called through its own entrypoint
+    fn upkeep(e: Env, keeper: Address, task: KeeperTask) -> i128;
+}
+
+#[contractimpl]
+impl PoolContract {
+    /// Initialize the pool
+    ///
+    /// ### Arguments
+    /// Creator supplied:
+    /// * `admin` - The Address for the admin
+    /// * `name` - The name of the pool
+    /// * `oracle` - The contract address of the oracle
+    /// * `backstop_take_rate` - The take rate for the backstop (7 decimals)
+    /// * `max_positions` - The maximum number of positions a user is permitted to have
+    /// * `min_collateral` - The minimum collateral required to open a borrow position in the oracles base asset
+    ///
+    /// Pool Factory supplied:
+    /// * `backstop_id` - The contract address of the pool's backstop module
+    /// * `blnd_id` - The contract ID of the BLND token
+    pub fn __constructor(
+        e: Env,
+        admin: Address,
+        name: String,
+        oracle: Address,
         bstop_rate: u32,
         max_positions: u32,
         min_collateral: i128,
@@ -350,53 +1828,779 @@ impl PoolContract {
     ) {
         admin.require_auth();
 
-        pool::execute_initialize(
-            &e,
-            &admin,
-            &name,
-            &oracle,
-            &bstop_rate,
-            &max_positions,
-            &min_collateral,
-            &backstop_id,
-            &blnd_id,
-        );
+        pool::execute_initialize(
+            &e,
+            &admin,
+            &name,
+            &oracle,
+            &bstop_rate,
+            &max_positions,
+            &min_collateral,
+            &backstop_id,
+            &blnd_id,
+        );
+    }
+}
+
+#[contractimpl]
+impl Pool for PoolContract {
+    fn propose_admin(e: Env, new_admin: Address) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        storage::set_proposed_admin(&e, &new_admin);
+    }
+
+    fn accept_admin(e: Env) {
+        storage::extend_instance(&e);
+
+        if let Some(proposed_admin) = storage::get_proposed_admin(&e) {
+            proposed_admin.require_auth();
+            let cur_admin = storage::get_admin(&e);
+
+            storage::set_admin(&e, &proposed_admin);
+
+            PoolEvents::set_admin(&e, cur_admin, proposed_admin);
+        } else {
+            panic_with_error!(&e, PoolError::BadRequest);
+        }
+    }
+
+    fn update_pool(
+        e: Env,
+        backstop_take_rate: u32,
+        max_positions: u32,
+        min_collateral: i128,
+        min_debt: i128,
+    ) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        pool::require_no_timelock(&e);
+
+        pool::execute_update_pool(
+            &e,
+            backstop_take_rate,
+            max_positions,
+            min_collateral,
+            min_debt,
+        );
+
+        PoolEvents::update_pool(
+            &e,
+            admin,
+            backstop_take_rate,
+            max_positions,
+            min_collateral,
+            min_debt,
+        );
+        PoolEvents::config_snapshot(&e, pool::calc_config_snapshot(&e));
+    }
+
+    fn set_bstop_rate_schedule(e: Env, schedule: Vec<BstopRateStep>) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        pool::require_no_timelock(&e);
+
+        pool::execute_set_bstop_rate_schedule(&e, &schedule);
+
+        PoolEvents::set_bstop_rate_schedule(&e, admin, schedule);
+    }
+
+    fn get_bstop_rate_schedule(e: Env) -> Vec<BstopRateStep> {
+        storage::get_bstop_rate_schedule(&e)
+    }
+
+    fn set_auction_config(e: Env, config: AuctionConfig) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        pool::require_no_timelock(&e);
+
+        pool::execute_set_auction_config(&e, &config);
+
+        PoolEvents::set_auction_config(&e, admin, config);
+    }
+
+    fn set_price_band(e: Env, asset: Address, band: Option<PriceBand>) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        pool::require_no_timelock(&e);
+
+        pool::execute_set_price_band(&e, &asset, &band);
+
+        PoolEvents::set_price_band(&e, admin, asset, band);
+    }
+
+    fn set_reserve_paused(e: Env, asset: Address, supply_paused: bool, borrow_paused: bool) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        let pause = ReservePause {
+            supply: supply_paused,
+            borrow: borrow_paused,
+        };
+        pool::execute_set_reserve_paused(&e, &asset, &pause);
+
+        PoolEvents::set_reserve_paused(&e, admin, asset, pause);
+    }
+
+    fn set_oracle_asset(e: Env, asset: Address, oracle_asset: Option<Asset>) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        pool::require_no_timelock(&e);
+
+        pool::execute_set_oracle_asset(&e, &asset, &oracle_asset);
+
+        PoolEvents::set_oracle_asset(&e, admin, asset, oracle_asset);
+    }
+
+    fn get_oracle_asset(e: Env, asset: Address) -> Option<Asset> {
+        storage::get_oracle_asset(&e, &asset)
+    }
+
+    fn set_rate_strategy(e: Env, asset: Address, strategy: Option<Address>) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if strategy.is_some() {
+            pool::require_no_timelock(&e);
+        }
+
+        pool::execute_set_rate_strategy(&e, &asset, &strategy);
+
+        PoolEvents::set_rate_strategy(&e, admin, asset, strategy);
+    }
+
+    fn get_rate_strategy(e: Env, asset: Address) -> Option<Address> {
+        storage::get_rate_strategy(&e, &asset)
+    }
+
+    fn set_max_apr(e: Env, asset: Address, max_apr: Option<u32>) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        pool::require_no_timelock(&e);
+
+        pool::execute_set_max_apr(&e, &asset, &max_apr);
+
+        PoolEvents::set_max_apr(&e, admin, asset, max_apr);
+    }
+
+    fn get_max_apr(e: Env, asset: Address) -> Option<u32> {
+        storage::get_max_apr(&e, &asset)
+    }
+
+    fn set_checkpoint_retention(e: Env, retention: u32) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        pool::execute_set_checkpoint_retention(&e, retention);
+
+        PoolEvents::set_checkpoint_retention(&e, admin, retention);
+    }
+
+    fn set_analytics_window(e: Env, window: u32) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        pool::execute_set_analytics_window(&e, window);
+
+        PoolEvents::set_analytics_window(&e, admin, window);
+    }
+
+    fn get_analytics_window(e: Env) -> u32 {
+        storage::get_analytics_window(&e)
+    }
+
+    fn set_max_ledger_time(e: Env, max_ledger_time: u32) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        pool::require_no_timelock(&e);
+
+        pool::execute_set_max_ledger_time(&e, max_ledger_time);
+
+        PoolEvents::set_max_ledger_time(&e, admin, max_ledger_time);
+    }
+
+    fn get_max_ledger_time(e: Env) -> u32 {
+        storage::get_max_ledger_time(&e)
+    }
+
+    fn set_max_accrual_period(e: Env, max_accrual_period: u32) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        pool::require_no_timelock(&e);
+
+        pool::execute_set_max_accrual_period(&e, max_accrual_period);
+
+        PoolEvents::set_max_accrual_period(&e, admin, max_accrual_period);
+    }
+
+    fn get_max_accrual_period(e: Env) -> u32 {
+        storage::get_max_accrual_period(&e)
+    }
+
+    fn set_c_factor_ramp(e: Env, ledgers: u32) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        pool::execute_set_c_factor_ramp(&e, ledgers);
+
+        PoolEvents::set_c_factor_ramp(&e, admin, ledgers);
+    }
+
+    fn get_c_factor_ramp(e: Env) -> u32 {
+        storage::get_c_factor_ramp_ledgers(&e)
+    }
+
+    fn set_freeze_expiry(e: Env, ledgers: u32) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        pool::execute_set_freeze_expiry(&e, ledgers);
+
+        PoolEvents::set_freeze_expiry(&e, admin, ledgers);
+    }
+
+    fn get_freeze_expiry(e: Env) -> u32 {
+        storage::get_freeze_ledgers(&e)
+    }
+
+    fn get_freeze_expiry_ledger(e: Env) -> Option<u32> {
+        storage::get_freeze_expiry(&e)
+    }
+
+    fn set_borrow_buffer(e: Env, buffer: u32) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        pool::execute_set_borrow_buffer(&e, buffer);
+
+        PoolEvents::set_borrow_buffer(&e, admin, buffer);
+    }
+
+    fn get_borrow_buffer(e: Env) -> u32 {
+        storage::get_borrow_buffer(&e)
+    }
+
+    fn set_frozen_fees(e: Env, mode: u32) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        pool::require_no_timelock(&e);
+
+        pool::execute_set_frozen_fees(&e, mode);
+
+        PoolEvents::set_frozen_fees(&e, admin, mode);
     }
-}
 
-#[contractimpl]
-impl Pool for PoolContract {
-    fn propose_admin(e: Env, new_admin: Address) {
+    fn get_frozen_fees(e: Env) -> u32 {
+        storage::get_frozen_fees(&e)
+    }
+
+    fn set_withdraw_fee(e: Env, fee: WithdrawFee) {
         storage::extend_instance(&e);
         let admin = storage::get_admin(&e);
         admin.require_auth();
+        pool::require_no_timelock(&e);
 
-        storage::set_proposed_admin(&e, &new_admin);
+        pool::execute_set_withdraw_fee(&e, &fee);
+
+        PoolEvents::set_withdraw_fee(&e, admin, fee);
     }
 
-    fn accept_admin(e: Env) {
+    fn get_withdraw_fee(e: Env) -> WithdrawFee {
+        storage::get_withdraw_fee(&e)
+    }
+
+    fn set_blocked(e: Env, user: Address, blocked: bool) {
         storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
 
-        if let Some(proposed_admin) = storage::get_proposed_admin(&e) {
-            proposed_admin.require_auth();
-            let cur_admin = storage::get_admin(&e);
+        storage::set_blocked(&e, &user, blocked);
 
-            storage::set_admin(&e, &proposed_admin);
+        PoolEvents::set_blocked(&e, admin, user, blocked);
+    }
 
-            PoolEvents::set_admin(&e, cur_admin, proposed_admin);
-        } else {
-            panic_with_error!(&e, PoolError::BadRequest);
+    fn is_blocked(e: Env, user: Address) -> bool {
+        storage::get_blocked(&e, &user)
+    }
+
+    fn set_risk_premium(e: Env, risk_premium: Option<RiskPremium>) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if risk_premium.is_some() {
+            pool::require_no_timelock(&e);
+        }
+
+        pool::execute_set_risk_premium(&e, &risk_premium);
+
+        PoolEvents::set_risk_premium(&e, admin, risk_premium);
+    }
+
+    fn get_risk_premium(e: Env) -> Option<RiskPremium> {
+        storage::get_risk_premium(&e)
+    }
+
+    fn set_insurance_config(e: Env, config: Option<InsuranceConfig>) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        pool::require_no_timelock(&e);
+
+        pool::execute_set_insurance_config(&e, &config);
+
+        PoolEvents::set_insurance_config(&e, admin, config);
+    }
+
+    fn get_insurance_config(e: Env) -> Option<InsuranceConfig> {
+        storage::get_insurance_config(&e)
+    }
+
+    fn set_fill_commit_config(e: Env, config: Option<FillCommitConfig>) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        auctions::execute_set_fill_commit_config(&e, &config);
+
+        PoolEvents::set_fill_commit_config(&e, admin, config);
+    }
+
+    fn get_fill_commit_config(e: Env) -> Option<FillCommitConfig> {
+        storage::get_fill_commit_config(&e)
+    }
+
+    fn set_util_breaker(e: Env, util_breaker: Option<UtilBreaker>) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        pool::require_no_timelock(&e);
+
+        pool::execute_set_util_breaker(&e, &util_breaker);
+
+        PoolEvents::set_util_breaker(&e, admin, util_breaker);
+    }
+
+    fn get_util_breaker(e: Env) -> Option<UtilBreaker> {
+        storage::get_util_breaker(&e)
+    }
+
+    fn get_util_breaker_state(e: Env) -> UtilBreakerState {
+        storage::get_util_breaker_state(&e)
+    }
+
+    fn set_backstop_price_check(e: Env, usdc: Option<Address>) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        storage::set_bstop_price_usdc(&e, &usdc);
+
+        PoolEvents::set_backstop_price_check(&e, admin, usdc);
+    }
+
+    fn get_backstop_price_check(e: Env) -> Option<Address> {
+        storage::get_bstop_price_usdc(&e)
+    }
+
+    fn set_claim_converter(e: Env, converter: Option<Address>) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if converter.is_some() {
+            pool::require_no_timelock(&e);
+        }
+
+        storage::set_claim_converter(&e, &converter);
+
+        PoolEvents::set_claim_converter(&e, admin, converter);
+    }
+
+    fn get_claim_converter(e: Env) -> Option<Address> {
+        storage::get_claim_converter(&e)
+    }
+
+    fn set_credit_line(e: Env, asset: Address, lender: Address, cap: i128) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        lender.require_auth();
+
+        pool::execute_set_credit_line(&e, &asset, &lender, cap);
+
+        PoolEvents::set_credit_line(&e, admin, asset, lender, cap);
+    }
+
+    fn get_credit_line(e: Env, asset: Address) -> Option<CreditLine> {
+        storage::get_credit_line(&e, &asset)
+    }
+
+    fn apply_risk_premium(e: Env, user: Address) -> Positions {
+        storage::extend_instance(&e);
+        pool::execute_apply_risk_premium(&e, &user)
+    }
+
+    fn apply_insurance_premium(e: Env, user: Address) -> Positions {
+        storage::extend_instance(&e);
+        pool::execute_apply_insurance_premium(&e, &user)
+    }
+
+    fn set_stable_rate(e: Env, asset: Address, config: Option<StableRateConfig>) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if config.is_some() {
+            pool::require_no_timelock(&e);
+        }
+
+        pool::execute_set_stable_rate(&e, &asset, &config);
+
+        PoolEvents::set_stable_rate(&e, admin, asset, config);
+    }
+
+    fn get_stable_rate(e: Env, asset: Address) -> Option<StableRateConfig> {
+        storage::get_stable_rate_config(&e, &asset)
+    }
+
+    fn get_stable_loan(e: Env, user: Address, asset: Address) -> Option<StableLoan> {
+        let reserve_config = storage::get_res_config(&e, &asset);
+        match storage::get_user_stable_liabilities(&e, &user) {
+            Some(stable_liabilities) if stable_liabilities.contains_key(reserve_config.index) => {
+                storage::get_stable_loan(&e, &user, reserve_config.index)
+            }
+            _ => None,
+        }
+    }
+
+    fn get_stable_liabilities(e: Env, user: Address) -> Map<u32, i128> {
+        storage::get_user_stable_liabilities(&e, &user).unwrap_or_else(|| Map::new(&e))
+    }
+
+    fn rebalance_stable_rate(e: Env, user: Address, asset: Address) -> Positions {
+        storage::extend_instance(&e);
+        pool::execute_rebalance_stable_rate(&e, &user, &asset)
+    }
+
+    fn set_quote_adapter(e: Env, quote_asset: Address, adapter: Option<Address>) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if adapter.is_some() {
+            pool::require_no_timelock(&e);
+        }
+
+        pool::execute_set_quote_adapter(&e, &quote_asset, &adapter);
+
+        PoolEvents::set_quote_adapter(&e, admin, quote_asset, adapter);
+    }
+
+    fn get_quote_adapter(e: Env, quote_asset: Address) -> Option<Address> {
+        storage::get_quote_adapter(&e, &quote_asset)
+    }
+
+    fn admin_multicall(e: Env, ops: Vec<AdminOp>) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        for op in ops.iter() {
+            match op {
+                AdminOp::UpdatePool(
+                    backstop_take_rate,
+                    max_positions,
+                    min_collateral,
+                    min_debt,
+                ) => {
+                    pool::require_no_timelock(&e);
+                    pool::execute_update_pool(
+                        &e,
+                        backstop_take_rate,
+                        max_positions,
+                        min_collateral,
+                        min_debt,
+                    );
+                    PoolEvents::update_pool(
+                        &e,
+                        admin.clone(),
+                        backstop_take_rate,
+                        max_positions,
+                        min_collateral,
+                        min_debt,
+                    );
+                    PoolEvents::config_snapshot(&e, pool::calc_config_snapshot(&e));
+                }
+                AdminOp::SetBstopRateSchedule(schedule) => {
+                    pool::require_no_timelock(&e);
+                    pool::execute_set_bstop_rate_schedule(&e, &schedule);
+                    PoolEvents::set_bstop_rate_schedule(&e, admin.clone(), schedule);
+                }
+                AdminOp::SetAuctionConfig(config) => {
+                    pool::require_no_timelock(&e);
+                    pool::execute_set_auction_config(&e, &config);
+                    PoolEvents::set_auction_config(&e, admin.clone(), config);
+                }
+                AdminOp::SetPriceBand(asset, band) => {
+                    pool::require_no_timelock(&e);
+                    pool::execute_set_price_band(&e, &asset, &band);
+                    PoolEvents::set_price_band(&e, admin.clone(), asset, band);
+                }
+                AdminOp::SetReservePaused(asset, supply_paused, borrow_paused) => {
+                    let pause = ReservePause {
+                        supply: supply_paused,
+                        borrow: borrow_paused,
+                    };
+                    pool::execute_set_reserve_paused(&e, &asset, &pause);
+                    PoolEvents::set_reserve_paused(&e, admin.clone(), asset, pause);
+                }
+                AdminOp::SetOracleAsset(asset, oracle_asset) => {
+                    pool::require_no_timelock(&e);
+                    pool::execute_set_oracle_asset(&e, &asset, &oracle_asset);
+                    PoolEvents::set_oracle_asset(&e, admin.clone(), asset, oracle_asset);
+                }
+                AdminOp::SetRateStrategy(asset, strategy) => {
+                    if strategy.is_some() {
+                        pool::require_no_timelock(&e);
+                    }
+                    pool::execute_set_rate_strategy(&e, &asset, &strategy);
+                    PoolEvents::set_rate_strategy(&e, admin.clone(), asset, strategy);
+                }
+                AdminOp::SetMaxApr(asset, max_apr) => {
+                    pool::require_no_timelock(&e);
+                    pool::execute_set_max_apr(&e, &asset, &max_apr);
+                    PoolEvents::set_max_apr(&e, admin.clone(), asset, max_apr);
+                }
+                AdminOp::QueueSetReserve(asset, metadata) => {
+                    pool::execute_queue_set_reserve(&e, &asset, &metadata);
+                    PoolEvents::queue_set_reserve(&e, admin.clone(), asset, metadata);
+                }
+                AdminOp::CancelSetReserve(asset) => {
+                    pool::execute_cancel_queued_set_reserve(&e, &asset);
+                    PoolEvents::cancel_set_reserve(&e, admin.clone(), asset);
+                }
+                AdminOp::SetReserve(asset) => {
+                    let index = pool::execute_set_reserve(&e, &asset);
+                    PoolEvents::set_reserve(&e, asset, index);
+                    PoolEvents::config_snapshot(&e, pool::calc_config_snapshot(&e));
+                }
+                AdminOp::RetireReserve(asset) => {
+                    let index = pool::execute_retire_reserve(&e, &asset);
+                    PoolEvents::retire_reserve(&e, admin.clone(), asset, index);
+                }
+                AdminOp::QueueAdminAction(action) => {
+                    let (id, unlock_time) = pool::execute_queue_admin_action(&e, &action);
+                    PoolEvents::queue_admin_action(&e, admin.clone(), id, action, unlock_time);
+                }
+                AdminOp::CancelQueuedAction(id) => {
+                    pool::execute_cancel_queued_admin_action(&e, id);
+                    PoolEvents::cancel_queued_action(&e, admin.clone(), id);
+                }
+                AdminOp::ExecuteQueuedAction(id) => {
+                    pool::execute_queued_admin_action(&e, id);
+                    PoolEvents::execute_queued_action(&e, id);
+                    PoolEvents::config_snapshot(&e, pool::calc_config_snapshot(&e));
+                }
+                AdminOp::SetStatus(pool_status) => {
+                    pool::execute_set_pool_status(&e, pool_status);
+                    PoolEvents::set_status_admin(&e, admin.clone(), pool_status);
+                }
+                AdminOp::SetEmissionsConfig(res_emission_metadata) => {
+                    pool::require_no_timelock(&e);
+                    emissions::set_pool_emissions(&e, res_emission_metadata);
+                }
+                AdminOp::SetCheckpointRetention(retention) => {
+                    pool::execute_set_checkpoint_retention(&e, retention);
+                    PoolEvents::set_checkpoint_retention(&e, admin.clone(), retention);
+                }
+                AdminOp::SetAnalyticsWindow(window) => {
+                    pool::execute_set_analytics_window(&e, window);
+                    PoolEvents::set_analytics_window(&e, admin.clone(), window);
+                }
+                AdminOp::AdjustEmissionShare(res_token_id, new_share) => {
+                    pool::require_no_timelock(&e);
+                    emissions::adjust_pool_emission_share(&e, res_token_id, new_share);
+                    PoolEvents::adjust_emission_share(&e, admin.clone(), res_token_id, new_share);
+                }
+                AdminOp::SetEmissionDecay(res_token_id, factor) => {
+                    pool::require_no_timelock(&e);
+                    emissions::set_emission_decay(&e, res_token_id, factor);
+                    PoolEvents::set_emission_decay(&e, admin.clone(), res_token_id, factor);
+                }
+                AdminOp::SetQuoteAdapter(quote_asset, adapter) => {
+                    if adapter.is_some() {
+                        pool::require_no_timelock(&e);
+                    }
+                    pool::execute_set_quote_adapter(&e, &quote_asset, &adapter);
+                    PoolEvents::set_quote_adapter(&e, admin.clone(), quote_asset, adapter);
+                }
+                AdminOp::SetRiskPremium(risk_premium) => {
+                    if risk_premium.is_some() {
+                        pool::require_no_timelock(&e);
+                    }
+                    pool::execute_set_risk_premium(&e, &risk_premium);
+                    PoolEvents::set_risk_premium(&e, admin.clone(), risk_premium);
+                }
+                AdminOp::SetInsuranceConfig(config) => {
+                    pool::require_no_timelock(&e);
+                    pool::execute_set_insurance_config(&e, &config);
+                    PoolEvents::set_insurance_config(&e, admin.clone(), config);
+                }
+                AdminOp::SetMaxLedgerTime(max_ledger_time) => {
+                    pool::require_no_timelock(&e);
+                    pool::execute_set_max_ledger_time(&e, max_ledger_time);
+                    PoolEvents::set_max_ledger_time(&e, admin.clone(), max_ledger_time);
+                }
+                AdminOp::SetMaxAccrualPeriod(max_accrual_period) => {
+                    pool::require_no_timelock(&e);
+                    pool::execute_set_max_accrual_period(&e, max_accrual_period);
+                    PoolEvents::set_max_accrual_period(&e, admin.clone(), max_accrual_period);
+                }
+                AdminOp::SetBorrowerIndex(borrower_index) => {
+                    storage::set_borrower_index(&e, borrower_index);
+                    PoolEvents::set_borrower_index(&e, admin.clone(), borrower_index);
+                }
+                AdminOp::SetBackstopPositionsExempt(exempt) => {
+                    storage::set_backstop_positions_exempt(&e, exempt);
+                    PoolEvents::set_backstop_positions_exempt(&e, admin.clone(), exempt);
+                }
+                AdminOp::SetAutoGulp(auto_gulp) => {
+                    storage::set_auto_gulp(&e, auto_gulp);
+                    PoolEvents::set_auto_gulp(&e, admin.clone(), auto_gulp);
+                }
+                AdminOp::SetInterestDeposit(interest_deposit) => {
+                    storage::set_interest_deposit(&e, interest_deposit);
+                    PoolEvents::set_interest_deposit(&e, admin.clone(), interest_deposit);
+                }
+                AdminOp::SetCFactorRamp(ledgers) => {
+                    pool::execute_set_c_factor_ramp(&e, ledgers);
+                    PoolEvents::set_c_factor_ramp(&e, admin.clone(), ledgers);
+                }
+                AdminOp::SetUtilBreaker(util_breaker) => {
+                    pool::require_no_timelock(&e);
+                    pool::execute_set_util_breaker(&e, &util_breaker);
+                    PoolEvents::set_util_breaker(&e, admin.clone(), util_breaker);
+                }
+                AdminOp::SetFreezeExpiry(ledgers) => {
+                    pool::execute_set_freeze_expiry(&e, ledgers);
+                    PoolEvents::set_freeze_expiry(&e, admin.clone(), ledgers);
+                }
+                AdminOp::SetBorrowBuffer(buffer) => {
+                    pool::execute_set_borrow_buffer(&e, buffer);
+                    PoolEvents::set_borrow_buffer(&e, admin.clone(), buffer);
+                }
+                AdminOp::SetFrozenFees(mode) => {
+                    pool::require_no_timelock(&e);
+                    pool::execute_set_frozen_fees(&e, mode);
+                    PoolEvents::set_frozen_fees(&e, admin.clone(), mode);
+                }
+                AdminOp::SetWithdrawFee(fee) => {
+                    pool::require_no_timelock(&e);
+                    pool::execute_set_withdraw_fee(&e, &fee);
+                    PoolEvents::set_withdraw_fee(&e, admin.clone(), fee);
+                }
+                AdminOp::SetKeeperConfig(token, rebate) => {
+                    let config = KeeperConfig { token, rebate };
+                    pool::execute_set_keeper_config(&e, &config);
+                    PoolEvents::set_keeper_config(&e, admin.clone(), config);
+                }
+                AdminOp::SetKeeper(keeper, registered) => {
+                    storage::set_keeper(&e, &keeper, registered);
+                    PoolEvents::set_keeper(&e, admin.clone(), keeper, registered);
+                }
+                AdminOp::SetBackstopPriceCheck(usdc) => {
+                    storage::set_bstop_price_usdc(&e, &usdc);
+                    PoolEvents::set_backstop_price_check(&e, admin.clone(), usdc);
+                }
+                AdminOp::SetAuctionsPaused(paused) => {
+                    storage::set_auctions_paused(&e, paused);
+                    PoolEvents::set_auctions_paused(&e, admin.clone(), paused);
+                }
+                AdminOp::SetFillCommitConfig(config) => {
+                    auctions::execute_set_fill_commit_config(&e, &config);
+                    PoolEvents::set_fill_commit_config(&e, admin.clone(), config);
+                }
+                AdminOp::SetClaimConverter(converter) => {
+                    if converter.is_some() {
+                        pool::require_no_timelock(&e);
+                    }
+                    storage::set_claim_converter(&e, &converter);
+                    PoolEvents::set_claim_converter(&e, admin.clone(), converter);
+                }
+                AdminOp::SetListingBond(bond) => {
+                    pool::execute_set_listing_bond(&e, bond);
+                    PoolEvents::set_listing_bond(&e, admin.clone(), bond);
+                }
+                AdminOp::SetListingCouncil(council) => {
+                    storage::set_listing_council(&e, &council);
+                    PoolEvents::set_listing_council(&e, admin.clone(), council);
+                }
+            }
         }
     }
 
-    fn update_pool(e: Env, backstop_take_rate: u32, max_positions: u32, min_collateral: i128) {
+    fn queue_admin_action(e: Env, action: AdminAction) -> u32 {
         storage::extend_instance(&e);
         let admin = storage::get_admin(&e);
         admin.require_auth();
 
-        pool::execute_update_pool(&e, backstop_take_rate, max_positions, min_collateral);
+        let (id, unlock_time) = pool::execute_queue_admin_action(&e, &action);
+
+        PoolEvents::queue_admin_action(&e, admin, id, action, unlock_time);
+        id
+    }
+
+    fn cancel_queued_action(e: Env, id: u32) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        pool::execute_cancel_queued_admin_action(&e, id);
+
+        PoolEvents::cancel_queued_action(&e, admin, id);
+    }
+
+    fn execute_queued_action(e: Env, id: u32) {
+        storage::extend_instance(&e);
+
+        pool::execute_queued_admin_action(&e, id);
+
+        PoolEvents::execute_queued_action(&e, id);
+        PoolEvents::config_snapshot(&e, pool::calc_config_snapshot(&e));
+    }
+
+    fn get_queued_action(e: Env, id: u32) -> Option<QueuedAdminAction> {
+        storage::get_queued_admin_action(&e, id)
+    }
+
+    fn export_config(e: Env) -> PoolConfigExport {
+        pool::export_config(&e)
+    }
+
+    fn get_config_snapshot(e: Env) -> ConfigSnapshot {
+        pool::calc_config_snapshot(&e)
+    }
+
+    fn queue_import_config(e: Env, export: PoolConfigExport) -> Vec<u32> {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
 
-        PoolEvents::update_pool(&e, admin, backstop_take_rate, max_positions, min_collateral);
+        pool::execute_queue_import_config(&e, &admin, &export)
     }
 
     fn queue_set_reserve(e: Env, asset: Address, metadata: ReserveConfig) {
@@ -419,34 +2623,198 @@ impl Pool for PoolContract {
         PoolEvents::cancel_set_reserve(&e, admin, asset);
     }
 
+    fn set_listing_bond(e: Env, bond: i128) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        pool::execute_set_listing_bond(&e, bond);
+
+        PoolEvents::set_listing_bond(&e, admin, bond);
+    }
+
+    fn get_listing_bond(e: Env) -> i128 {
+        storage::get_listing_bond(&e)
+    }
+
+    fn set_listing_council(e: Env, council: Option<Address>) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        storage::set_listing_council(&e, &council);
+
+        PoolEvents::set_listing_council(&e, admin, council);
+    }
+
+    fn get_listing_council(e: Env) -> Option<Address> {
+        storage::get_listing_council(&e)
+    }
+
+    fn propose_reserve(e: Env, proposer: Address, asset: Address, config: ReserveConfig) {
+        storage::extend_instance(&e);
+        proposer.require_auth();
+
+        let proposal = pool::execute_propose_reserve(&e, &proposer, &asset, &config);
+
+        PoolEvents::propose_reserve(&e, proposer, asset, config, proposal.bond);
+    }
+
+    fn approve_reserve_proposal(e: Env, asset: Address) {
+        storage::extend_instance(&e);
+        let approver = pool::get_proposal_approver(&e);
+        approver.require_auth();
+
+        let proposal = pool::execute_approve_reserve_proposal(&e, &asset);
+
+        PoolEvents::approve_reserve_proposal(
+            &e,
+            approver.clone(),
+            asset.clone(),
+            proposal.proposer,
+        );
+        PoolEvents::queue_set_reserve(&e, approver, asset, proposal.config);
+    }
+
+    fn reject_reserve_proposal(e: Env, asset: Address, slash: bool) {
+        storage::extend_instance(&e);
+        let approver = pool::get_proposal_approver(&e);
+        approver.require_auth();
+
+        pool::execute_reject_reserve_proposal(&e, &asset, slash);
+
+        PoolEvents::reject_reserve_proposal(&e, approver, asset, slash);
+    }
+
+    fn reclaim_reserve_proposal(e: Env, asset: Address) {
+        storage::extend_instance(&e);
+
+        let proposal = pool::execute_reclaim_reserve_proposal(&e, &asset);
+
+        PoolEvents::reclaim_reserve_proposal(&e, proposal.proposer, asset, proposal.bond);
+    }
+
+    fn get_reserve_proposal(e: Env, asset: Address) -> Option<ReserveProposal> {
+        storage::get_reserve_proposal(&e, &asset)
+    }
+
     fn set_reserve(e: Env, asset: Address) -> u32 {
         storage::extend_instance(&e);
+        // new reserves are seeded by the admin
+        if !storage::has_res(&e, &asset) {
+            storage::get_admin(&e).require_auth();
+        }
 
         let index = pool::execute_set_reserve(&e, &asset);
 
         PoolEvents::set_reserve(&e, asset, index);
+        PoolEvents::config_snapshot(&e, pool::calc_config_snapshot(&e));
+        index
+    }
+
+    fn retire_reserve(e: Env, asset: Address) -> u32 {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        let index = pool::execute_retire_reserve(&e, &asset);
+
+        PoolEvents::retire_reserve(&e, admin, asset, index);
         index
     }
 
+    fn get_reserve_retired(e: Env, index: u32) -> bool {
+        storage::is_res_retired(&e, index)
+    }
+
     fn get_config(e: Env) -> PoolConfig {
         storage::get_pool_config(&e)
     }
 
+    fn get_auction_config(e: Env) -> AuctionConfig {
+        storage::get_auction_config(&e)
+    }
+
     fn get_admin(e: Env) -> Address {
         storage::get_admin(&e)
     }
 
-    fn get_reserve_list(e: Env) -> Vec<Address> {
-        storage::get_res_list(&e)
+    fn get_reserve_list(e: Env) -> Vec<Address> {
+        storage::get_res_list(&e)
+    }
+
+    fn get_reserve(e: Env, asset: Address) -> Reserve {
+        let pool_config = storage::get_pool_config(&e);
+        ReserveState::load(&e, &pool_config, &asset).to_reserve()
+    }
+
+    fn get_pool_totals(e: Env) -> PoolTotals {
+        pool::calc_pool_totals(&e)
+    }
+
+    fn get_reserve_summary(e: Env, asset: Address) -> ReserveSummary {
+        pool::calc_reserve_summary(&e, &asset)
+    }
+
+    fn get_bad_debt(e: Env) -> Map<Address, i128> {
+        pool::calc_bad_debt(&e)
+    }
+
+    fn project_rates(
+        e: Env,
+        asset: Address,
+        supply_delta: i128,
+        borrow_delta: i128,
+    ) -> RateProjection {
+        pool::calc_projected_rates(&e, &asset, supply_delta, borrow_delta)
+    }
+
+    fn get_reserve_stats(e: Env, asset: Address) -> ReserveStats {
+        let reserve = pool::Pool::load(&e).load_reserve(&e, &asset, false);
+        let mut stats = storage::get_res_stats(&e, &asset);
+        stats.interest_paid += reserve.accrued.interest_paid;
+        stats.supplier_interest += reserve.accrued.supplier_interest;
+        stats
+    }
+
+    fn get_reserve_rate_ext(e: Env, asset: Address) -> ReserveRateExt {
+        let reserve = pool::Pool::load(&e).load_reserve(&e, &asset, false);
+        reserve.rate_ext
+    }
+
+    fn get_reserve_dust(e: Env, asset: Address) -> i128 {
+        let reserve = pool::Pool::load(&e).load_reserve(&e, &asset, false);
+        storage::get_res_dust(&e, &asset) + reserve.dust
+    }
+
+    fn get_price_band(e: Env, asset: Address) -> Option<PriceBand> {
+        storage::get_price_band(&e, &asset)
+    }
+
+    fn get_reserve_paused(e: Env, asset: Address) -> ReservePause {
+        storage::get_reserve_pause(&e, &asset)
+    }
+
+    fn get_positions(e: Env, address: Address) -> Positions {
+        storage::get_user_positions(&e, &address)
+    }
+
+    fn get_position_history_checkpoint(e: Env, user: Address) -> Vec<PositionCheckpoint> {
+        storage::get_position_history(&e, &user)
+    }
+
+    fn get_daily_stats(e: Env, days: u32) -> Map<u64, DailyStats> {
+        pool::get_daily_stats(&e, days)
     }
 
-    fn get_reserve(e: Env, asset: Address) -> Reserve {
-        let pool_config = storage::get_pool_config(&e);
-        Reserve::load(&e, &pool_config, &asset)
+    fn get_queued_withdrawal(e: Env, user: Address, asset: Address) -> Option<QueuedWithdrawal> {
+        let reserve_config = storage::get_res_config(&e, &asset);
+        storage::get_queued_withdrawal(&e, &user, reserve_config.index)
     }
 
-    fn get_positions(e: Env, address: Address) -> Positions {
-        storage::get_user_positions(&e, &address)
+    fn get_withdraw_queue(e: Env, asset: Address) -> WithdrawQueue {
+        let reserve_config = storage::get_res_config(&e, &asset);
+        storage::get_withdraw_queue(&e, reserve_config.index)
     }
 
     fn submit(
@@ -455,12 +2823,13 @@ impl Pool for PoolContract {
         spender: Address,
         to: Address,
         requests: Vec<Request>,
-    ) -> Positions {
+    ) -> Result<Positions, PoolError> {
         storage::extend_instance(&e);
         spender.require_auth();
-        if from != spender {
+        if from != spender && !pool::is_supply_on_behalf(&e, &from, &requests) {
             from.require_auth();
         }
+        emissions::auto_gulp_emissions(&e);
 
         pool::execute_submit(&e, &from, &spender, &to, requests, false)
     }
@@ -471,12 +2840,13 @@ impl Pool for PoolContract {
         spender: Address,
         to: Address,
         requests: Vec<Request>,
-    ) -> Positions {
+    ) -> Result<Positions, PoolError> {
         storage::extend_instance(&e);
         spender.require_auth();
-        if from != spender {
+        if from != spender && !pool::is_supply_on_behalf(&e, &from, &requests) {
             from.require_auth();
         }
+        emissions::auto_gulp_emissions(&e);
 
         pool::execute_submit(&e, &from, &spender, &to, requests, true)
     }
@@ -486,13 +2856,94 @@ impl Pool for PoolContract {
         from: Address,
         flash_loan: FlashLoan,
         requests: Vec<Request>,
-    ) -> Positions {
+    ) -> Result<Positions, PoolError> {
         storage::extend_instance(&e);
         from.require_auth();
+        emissions::auto_gulp_emissions(&e);
 
         pool::execute_submit_with_flash_loan(&e, &from, flash_loan, requests)
     }
 
+    fn fill_with_quote(e: Env, filler: Address, quote_fill: QuoteFill) -> Positions {
+        storage::extend_instance(&e);
+        filler.require_auth();
+
+        pool::execute_fill_with_quote(&e, &filler, quote_fill)
+    }
+
+    fn fill_with_hook(e: Env, filler: Address, hook_fill: HookFill) -> Positions {
+        storage::extend_instance(&e);
+        filler.require_auth();
+
+        pool::execute_fill_with_hook(&e, &filler, hook_fill)
+    }
+
+    fn commit_fill(e: Env, filler: Address, hash: BytesN<32>) {
+        storage::extend_instance(&e);
+        filler.require_auth();
+
+        auctions::execute_commit_fill(&e, &filler, &hash);
+
+        PoolEvents::commit_fill(&e, filler, hash);
+    }
+
+    fn reveal_fill(
+        e: Env,
+        filler: Address,
+        user: Address,
+        percent_filled: u64,
+        salt: BytesN<32>,
+    ) -> Positions {
+        storage::extend_instance(&e);
+        filler.require_auth();
+
+        pool::execute_reveal_fill(&e, &filler, &user, percent_filled, &salt)
+    }
+
+    fn deleverage(e: Env, from: Address, deleverage: Deleverage) -> Positions {
+        storage::extend_instance(&e);
+        from.require_auth();
+
+        pool::execute_deleverage(&e, &from, deleverage)
+    }
+
+    fn approve_borrow(
+        e: Env,
+        delegator: Address,
+        delegatee: Address,
+        asset: Address,
+        amount: i128,
+    ) {
+        storage::extend_instance(&e);
+        delegator.require_auth();
+
+        pool::execute_approve_borrow(&e, &delegator, &delegatee, &asset, amount);
+
+        PoolEvents::approve_borrow(&e, delegator, delegatee, asset, amount);
+    }
+
+    fn get_borrow_allowance(
+        e: Env,
+        delegator: Address,
+        delegatee: Address,
+        asset: Address,
+    ) -> i128 {
+        storage::get_borrow_allowance(&e, &delegator, &delegatee, &asset)
+    }
+
+    fn delegated_borrow(
+        e: Env,
+        delegator: Address,
+        delegatee: Address,
+        asset: Address,
+        amount: i128,
+    ) -> Positions {
+        storage::extend_instance(&e);
+        delegatee.require_auth();
+
+        pool::execute_delegated_borrow(&e, &delegator, &delegatee, &asset, amount)
+    }
+
     fn update_status(e: Env) -> u32 {
         storage::extend_instance(&e);
         let new_status = pool::execute_update_pool_status(&e);
@@ -532,10 +2983,101 @@ impl Pool for PoolContract {
         storage::extend_instance(&e);
         let admin = storage::get_admin(&e);
         admin.require_auth();
+        pool::require_no_timelock(&e);
 
         emissions::set_pool_emissions(&e, res_emission_metadata);
     }
 
+    fn adjust_emission_share(e: Env, res_token_id: u32, new_share: u64) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        pool::require_no_timelock(&e);
+
+        emissions::adjust_pool_emission_share(&e, res_token_id, new_share);
+
+        PoolEvents::adjust_emission_share(&e, admin, res_token_id, new_share);
+    }
+
+    fn set_emission_decay(e: Env, res_token_id: u32, factor: u32) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        pool::require_no_timelock(&e);
+
+        emissions::set_emission_decay(&e, res_token_id, factor);
+
+        PoolEvents::set_emission_decay(&e, admin, res_token_id, factor);
+    }
+
+    fn get_emission_decay(e: Env, res_token_id: u32) -> Option<EmissionDecay> {
+        storage::get_emission_decay(&e, res_token_id)
+    }
+
+    fn set_auto_gulp(e: Env, auto_gulp: bool) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        storage::set_auto_gulp(&e, auto_gulp);
+
+        PoolEvents::set_auto_gulp(&e, admin, auto_gulp);
+    }
+
+    fn get_auto_gulp(e: Env) -> bool {
+        storage::get_auto_gulp(&e)
+    }
+
+    fn set_borrower_index(e: Env, borrower_index: bool) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        storage::set_borrower_index(&e, borrower_index);
+
+        PoolEvents::set_borrower_index(&e, admin, borrower_index);
+    }
+
+    fn get_borrower_index(e: Env) -> bool {
+        storage::get_borrower_index(&e)
+    }
+
+    fn set_backstop_positions_exempt(e: Env, exempt: bool) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        storage::set_backstop_positions_exempt(&e, exempt);
+
+        PoolEvents::set_backstop_positions_exempt(&e, admin, exempt);
+    }
+
+    fn get_backstop_positions_exempt(e: Env) -> bool {
+        storage::get_backstop_positions_exempt(&e)
+    }
+
+    fn get_borrower_count(e: Env) -> u32 {
+        storage::get_borrower_count(&e)
+    }
+
+    fn get_borrowers(e: Env, start: u32, limit: u32) -> Vec<Address> {
+        pool::get_borrowers(&e, start, limit)
+    }
+
+    fn set_interest_deposit(e: Env, interest_deposit: bool) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        storage::set_interest_deposit(&e, interest_deposit);
+
+        PoolEvents::set_interest_deposit(&e, admin, interest_deposit);
+    }
+
+    fn get_interest_deposit(e: Env) -> bool {
+        storage::get_interest_deposit(&e)
+    }
+
     fn claim(e: Env, from: Address, reserve_token_ids: Vec<u32>, to: Address) -> i128 {
         storage::extend_instance(&e);
         from.require_auth();
@@ -547,10 +3089,45 @@ impl Pool for PoolContract {
         amount_claimed
     }
 
+    fn get_claimable_emissions(e: Env, user: Address, reserve_token_ids: Vec<u32>) -> i128 {
+        emissions::calc_claimable_emissions(&e, &user, &reserve_token_ids)
+    }
+
+    fn claim_converted(
+        e: Env,
+        from: Address,
+        reserve_token_ids: Vec<u32>,
+        to: Address,
+        min_amount_out: i128,
+    ) -> i128 {
+        storage::extend_instance(&e);
+        from.require_auth();
+
+        let (amount_claimed, amount_out) =
+            emissions::execute_claim_converted(&e, &from, &reserve_token_ids, &to, min_amount_out);
+
+        PoolEvents::claim(&e, from.clone(), reserve_token_ids, amount_claimed);
+        if amount_claimed > 0 {
+            PoolEvents::claim_converted(
+                &e,
+                from,
+                storage::get_claim_converter(&e).unwrap_optimized(),
+                amount_claimed,
+                amount_out,
+            );
+        }
+
+        amount_out
+    }
+
     fn get_reserve_emissions(e: Env, reserve_token_index: u32) -> Option<ReserveEmissionData> {
         storage::get_res_emis_data(&e, &reserve_token_index)
     }
 
+    fn get_emission_carryover(e: Env, reserve_token_id: u32) -> i128 {
+        storage::get_emission_carryover(&e, reserve_token_id)
+    }
+
     fn get_user_emissions(
         e: Env,
         user: Address,
@@ -571,16 +3148,179 @@ impl Pool for PoolContract {
     ) -> AuctionData {
         storage::extend_instance(&e);
 
+        // charge any insurance premium owed before sizing the liquidation
+        if auction_type == AuctionType::UserLiquidation as u32
+            && storage::get_insured(&e, &user).is_some()
+        {
+            pool::execute_apply_insurance_premium(&e, &user);
+        }
+
         let auction_data = auctions::create_auction(&e, auction_type, &user, &bid, &lot, percent);
 
         PoolEvents::new_auction(&e, auction_type, user, percent, auction_data.clone());
         auction_data
     }
 
+    fn new_interest_auction(e: Env, bid: Vec<Address>, lot: Map<Address, i128>) -> AuctionData {
+        storage::extend_instance(&e);
+
+        let auction_data = auctions::create_partial_interest_auction(&e, &bid, &lot);
+
+        PoolEvents::new_auction(
+            &e,
+            AuctionType::InterestAuction as u32,
+            storage::get_backstop(&e),
+            100,
+            auction_data.clone(),
+        );
+        auction_data
+    }
+
+    fn preview_new_auction(
+        e: Env,
+        auction_type: u32,
+        user: Address,
+        bid: Vec<Address>,
+        lot: Vec<Address>,
+        percent: u32,
+    ) -> AuctionData {
+        auctions::preview_auction(&e, auction_type, &user, &bid, &lot, percent)
+    }
+
+    fn set_auctions_paused(e: Env, paused: bool) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        storage::set_auctions_paused(&e, paused);
+
+        PoolEvents::set_auctions_paused(&e, admin, paused);
+    }
+
+    fn get_auctions_paused(e: Env) -> bool {
+        storage::get_auctions_paused(&e)
+    }
+
+    fn get_filler_stats(e: Env, filler: Address) -> Option<FillerStats> {
+        storage::get_filler_stats(&e, &filler)
+    }
+
+    fn get_filler_count(e: Env) -> u32 {
+        storage::get_filler_count(&e)
+    }
+
+    fn get_fillers(e: Env, start: u32, limit: u32) -> Vec<(Address, FillerStats)> {
+        auctions::get_fillers(&e, start, limit)
+    }
+
+    fn set_liquidation_hook(e: Env, user: Address, hook: Option<Address>) {
+        storage::extend_instance(&e);
+        user.require_auth();
+
+        storage::set_liquidation_hook(&e, &user, &hook);
+
+        PoolEvents::set_liquidation_hook(&e, user, hook);
+    }
+
+    fn get_liquidation_hook(e: Env, user: Address) -> Option<Address> {
+        storage::get_liquidation_hook(&e, &user)
+    }
+
+    fn run_liquidation_hook(e: Env, user: Address) -> bool {
+        storage::extend_instance(&e);
+
+        let averted = pool::execute_liquidation_hook(&e, &user);
+        if averted {
+            let hook = storage::get_liquidation_hook(&e, &user).unwrap_optimized();
+            PoolEvents::liquidation_averted(&e, user, hook);
+        }
+        averted
+    }
+
+    fn set_insured(e: Env, user: Address, insured: bool) -> Positions {
+        storage::extend_instance(&e);
+        user.require_auth();
+
+        let positions = pool::execute_set_insured(&e, &user, insured);
+
+        PoolEvents::set_insured(&e, user, insured);
+        positions
+    }
+
+    fn is_insured(e: Env, user: Address) -> bool {
+        storage::get_insured(&e, &user).is_some()
+    }
+
+    fn set_supply_on_behalf(e: Env, user: Address, allowed: bool) {
+        storage::extend_instance(&e);
+        user.require_auth();
+
+        storage::set_supply_on_behalf(&e, &user, allowed);
+
+        PoolEvents::set_supply_on_behalf(&e, user, allowed);
+    }
+
+    fn get_supply_on_behalf(e: Env, user: Address) -> bool {
+        storage::get_supply_on_behalf(&e, &user)
+    }
+
+    fn set_auto_claim(e: Env, user: Address, auto_claim: bool) {
+        storage::extend_instance(&e);
+        user.require_auth();
+
+        storage::set_auto_claim(&e, &user, auto_claim);
+
+        PoolEvents::set_auto_claim(&e, user, auto_claim);
+    }
+
+    fn get_auto_claim(e: Env, user: Address) -> bool {
+        storage::get_auto_claim(&e, &user)
+    }
+
+    fn set_plan_requests(e: Env, user: Address, plan_requests: bool) {
+        storage::extend_instance(&e);
+        user.require_auth();
+
+        storage::set_plan_requests(&e, &user, plan_requests);
+
+        PoolEvents::set_plan_requests(&e, user, plan_requests);
+    }
+
+    fn get_plan_requests(e: Env, user: Address) -> bool {
+        storage::get_plan_requests(&e, &user)
+    }
+
+    fn set_min_hf(e: Env, user: Address, min_hf: u32) {
+        storage::extend_instance(&e);
+        user.require_auth();
+
+        pool::execute_set_user_min_hf(&e, &user, min_hf);
+
+        PoolEvents::set_min_hf(&e, user, min_hf);
+    }
+
+    fn get_min_hf(e: Env, user: Address) -> u32 {
+        storage::get_user_min_hf(&e, &user)
+    }
+
     fn get_auction(e: Env, auction_type: u32, user: Address) -> AuctionData {
         storage::get_auction(&e, &auction_type, &user)
     }
 
+    fn get_user_auctions(e: Env, user: Address) -> Vec<(u32, AuctionData)> {
+        auctions::get_user_auctions(&e, &user)
+    }
+
+    fn can_fill(
+        e: Env,
+        auction_type: u32,
+        user: Address,
+        filler: Address,
+        percent_filled: u64,
+    ) -> Result<(), PoolError> {
+        auctions::check_fill(&e, auction_type, &user, &filler, percent_filled)
+    }
+
     fn del_auction(e: Env, auction_type: u32, user: Address) {
         storage::extend_instance(&e);
 
@@ -594,4 +3334,105 @@ impl Pool for PoolContract {
 
         pool::bad_debt(&e, &user);
     }
+
+    fn repay_deficit(e: Env, from: Address, asset: Address, amount: i128) -> i128 {
+        storage::extend_instance(&e);
+        from.require_auth();
+
+        pool::execute_repay_deficit(&e, &from, &asset, amount)
+    }
+
+    fn get_deficit(e: Env, asset: Address) -> ReserveDeficit {
+        storage::get_res_deficit(&e, &asset)
+    }
+
+    fn get_deficits(e: Env) -> Map<Address, ReserveDeficit> {
+        pool::get_deficits(&e)
+    }
+
+    /********* Keeper Functions **********/
+
+    fn set_keeper_config(e: Env, token: Address, rebate: i128) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        let config = KeeperConfig { token, rebate };
+        pool::execute_set_keeper_config(&e, &config);
+
+        PoolEvents::set_keeper_config(&e, admin, config);
+    }
+
+    fn get_keeper_config(e: Env) -> Option<KeeperConfig> {
+        storage::get_keeper_config(&e)
+    }
+
+    fn set_keeper(e: Env, keeper: Address, registered: bool) {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        storage::set_keeper(&e, &keeper, registered);
+
+        PoolEvents::set_keeper(&e, admin, keeper, registered);
+    }
+
+    fn is_keeper(e: Env, keeper: Address) -> bool {
+        storage::get_keeper(&e, &keeper)
+    }
+
+    fn fund_keeper_pot(e: Env, from: Address, amount: i128) -> i128 {
+        storage::extend_instance(&e);
+        from.require_auth();
+
+        let pot = pool::execute_fund_keeper_pot(&e, &from, amount);
+
+        PoolEvents::fund_keeper_pot(&e, from, amount);
+        pot
+    }
+
+    fn withdraw_keeper_pot(e: Env, amount: i128) -> i128 {
+        storage::extend_instance(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        let pot = pool::execute_withdraw_keeper_pot(&e, &admin, amount);
+
+        PoolEvents::withdraw_keeper_pot(&e, admin, amount);
+        pot
+    }
+
+    fn get_keeper_pot(e: Env) -> i128 {
+        storage::get_keeper_pot(&e)
+    }
+
+    fn upkeep(e: Env, keeper: Address, task: KeeperTask) -> i128 {
+        storage::extend_instance(&e);
+        keeper.require_auth();
+        pool::require_keeper(&e, &keeper);
+
+        let performed = match task {
+            KeeperTask::GulpEmissions => {
+                let emissions = emissions::gulp_emissions(&e);
+                PoolEvents::gulp_emissions(&e, emissions);
+                emissions > 0
+            }
+            KeeperTask::UpdateStatus => {
+                let prev_status = storage::get_pool_config(&e).status;
+                let new_status = pool::execute_update_pool_status(&e);
+                PoolEvents::set_status(&e, new_status);
+                new_status != prev_status
+            }
+            KeeperTask::DelAuction(auction_type, user) => {
+                auctions::delete_stale_auction(&e, auction_type, &user);
+                PoolEvents::delete_auction(&e, auction_type, user);
+                true
+            }
+        };
+        if performed {
+            pool::pay_keeper_rebate(&e, &keeper)
+        } else {
+            0
+        }
+    }
 }
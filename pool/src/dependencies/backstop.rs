@@ -1,3 +1,198 @@
-use soroban_sdk::contractimport;
+use soroban_sdk::{
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    contractclient, contracttype, vec, Address, Env, IntoVal, Symbol,
+};
 
-contractimport!(file = "../target/wasm32-unknown-unknown/release/backstop.wasm");
+use crate::storage;
+
+/// The pool's backstop data
+#[derive(Clone)]
+#[contracttype]
+pub struct PoolBackstopData {
+    pub tokens: i128,  // the number of backstop tokens held in the pool's backstop
+    pub shares: i128,  // the number of shares the pool's backstop has issued
+    pub q4w_pct: i128, // the percentage of shares/tokens queued for withdrawal
+    pub blnd: i128,    // the amount of blnd held in the pool's backstop via backstop tokens
+    pub usdc: i128,    // the amount of usdc held in the pool's backstop via backstop tokens
+    pub token_spot_price: i128, // the spot price sans fees in USDC of the backstop token (7 decimals)
+}
+
+/// ### PoolBackstop
+///
+/// The interface a backstop must implement to insure a pool. Any contract implementing it can be used
+/// as a pool's backstop.
+#[contractclient(name = "PoolBackstopClient")]
+pub trait PoolBackstop {
+    /// Fetch the backstop data for a pool
+    ///
+    /// ### Arguments
+    /// * `pool` - The address of the pool
+    fn pool_data(e: Env, pool: Address) -> PoolBackstopData;
+
+    /// Fetch the backstop token for the backstop
+    fn backstop_token(e: Env) -> Address;
+
+    /// (Only Pool) Claim the pool's share of emissions from the backstop
+    ///
+    /// Returns the amount of emissions distributed to the pool
+    ///
+    /// ### Arguments
+    /// * `pool` - The address of the pool
+    fn gulp_emissions(e: Env, pool: Address) -> i128;
+
+    /// (Only Pool) Draw backstop tokens from the pool's backstop
+    ///
+    /// ### Arguments
+    /// * `pool_address` - The address of the pool
+    /// * `amount` - The amount of backstop tokens to draw
+    /// * `to` - The address receiving the backstop tokens
+    fn draw(e: Env, pool_address: Address, amount: i128, to: Address);
+
+    /// (Only Pool) Send backstop tokens from `from` to the pool's backstop
+    ///
+    /// ### Arguments
+    /// * `from` - The address sending the backstop tokens
+    /// * `pool_address` - The address of the pool
+    /// * `amount` - The amount of backstop tokens to send
+    fn donate(e: Env, from: Address, pool_address: Address, amount: i128);
+
+    /// (Only Pool) Deposit backstop tokens held by the pool into the pool's backstop
+    ///
+    /// Returns the number of backstop pool shares minted to the pool
+    ///
+    /// ### Arguments
+    /// * `pool_address` - The address of the pool
+    /// * `amount` - The amount of backstop tokens to deposit
+    fn deposit_from_pool(e: Env, pool_address: Address, amount: i128) -> i128;
+}
+
+/// Adapter for the pool's calls to its backstop
+pub struct Backstop<'a> {
+    pub address: Address,
+    pool: Address,
+    client: PoolBackstopClient<'a>,
+}
+
+impl<'a> Backstop<'a> {
+    /// Load the pool's backstop
+    pub fn load(e: &Env) -> Self {
+        let address = storage::get_backstop(e);
+        Backstop {
+            client: PoolBackstopClient::new(e, &address),
+            pool: e.current_contract_address(),
+            address,
+        }
+    }
+
+    /// Fetch the backstop token
+    pub fn token(&self) -> Address {
+        self.client.backstop_token()
+    }
+
+    /// Fetch the pool's backstop data
+    pub fn pool_data(&self) -> PoolBackstopData {
+        self.client.pool_data(&self.pool)
+    }
+
+    /// Claim the pool's emissions from the backstop
+    ///
+    /// Returns the amount of emissions distributed to the pool
+    pub fn gulp_emissions(&self) -> i128 {
+        self.client.gulp_emissions(&self.pool)
+    }
+
+    /// Attempt to claim the pool's emissions from the backstop
+    ///
+    /// Returns the amount of emissions distributed to the pool, or None if the backstop rejected the claim
+    pub fn try_gulp_emissions(&self) -> Option<i128> {
+        match self.client.try_gulp_emissions(&self.pool) {
+            Ok(Ok(emissions)) => Some(emissions),
+            _ => None,
+        }
+    }
+
+    /// Draw `amount` backstop tokens from the pool's backstop and send them to `to`
+    pub fn draw(&self, amount: i128, to: &Address) {
+        self.client.draw(&self.pool, &amount, to);
+    }
+
+    /// Send `amount` backstop tokens from `from` to the pool's backstop
+    pub fn donate(&self, from: &Address, amount: i128) {
+        self.client.donate(from, &self.pool, &amount);
+    }
+
+    /// Deposit `amount` backstop tokens held by the pool into the pool's backstop on behalf of the pool
+    ///
+    /// Returns the number of backstop pool shares minted to the pool
+    pub fn deposit(&self, e: &Env, token: &Address, amount: i128) -> i128 {
+        e.authorize_as_current_contract(vec![
+            e,
+            InvokerContractAuthEntry::Contract(SubContractInvocation {
+                context: ContractContext {
+                    contract: token.clone(),
+                    fn_name: Symbol::new(e, "transfer"),
+                    args: vec![
+                        e,
+                        self.pool.into_val(e),
+                        self.address.into_val(e),
+                        amount.into_val(e),
+                    ],
+                },
+                sub_invocations: vec![e],
+            }),
+        ]);
+        self.client.deposit_from_pool(&self.pool, &amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testutils;
+
+    use super::*;
+    use soroban_sdk::{testutils::Address as _, vec};
+
+    #[test]
+    fn test_backstop_adapter() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+        let pool = testutils::create_pool(&e);
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let (blnd, blnd_client) = testutils::create_token_contract(&e, &bombadil);
+        let (usdc, usdc_client) = testutils::create_token_contract(&e, &bombadil);
+        let (lp_token, lp_token_client) =
+            testutils::create_comet_lp_pool(&e, &bombadil, &blnd, &usdc);
+        let (backstop_address, backstop_client) =
+            testutils::create_backstop(&e, &pool, &lp_token, &usdc, &blnd);
+
+        blnd_client.mint(&samwise, &500_001_0000000);
+        blnd_client.approve(&samwise, &lp_token, &i128::MAX, &99999);
+        usdc_client.mint(&samwise, &12_501_0000000);
+        usdc_client.approve(&samwise, &lp_token, &i128::MAX, &99999);
+        lp_token_client.join_pool(
+            &50_000_0000000,
+            &vec![&e, 500_001_0000000, 12_501_0000000],
+            &samwise,
+        );
+        backstop_client.deposit(&samwise, &pool, &40_000_0000000);
+        lp_token_client.approve(&samwise, &backstop_address, &i128::MAX, &99999);
+
+        e.as_contract(&pool, || {
+            let backstop = Backstop::load(&e);
+            assert_eq!(backstop.address, backstop_address);
+            assert_eq!(backstop.token(), lp_token);
+            assert_eq!(backstop.pool_data().tokens, 40_000_0000000);
+
+            backstop.draw(1_000_0000000, &samwise);
+            assert_eq!(backstop.pool_data().tokens, 39_000_0000000);
+
+            backstop.donate(&samwise, 500_0000000);
+            assert_eq!(backstop.pool_data().tokens, 39_500_0000000);
+        });
+        assert_eq!(lp_token_client.balance(&samwise), 10_500_0000000);
+    }
+}
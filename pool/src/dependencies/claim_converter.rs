@@ -0,0 +1,27 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+/// ### ClaimConverter
+///
+/// The interface a converter must implement to convert claimed BLND emissions into another asset.
+#[contractclient(name = "ClaimConverterClient")]
+pub trait ClaimConverter {
+    /// Convert the converter's balance of `token_in` into the converter's output asset.
+    ///
+    /// The pool transfers the claimed `token_in` to the converter before invoking it. The converter
+    /// must send at least `min_amount_out` of its output asset to `to`.
+    ///
+    /// Returns the amount of the output asset sent to `to`
+    ///
+    /// ### Arguments
+    /// * `token_in` - The asset being converted
+    /// * `amount_in` - The amount of `token_in` to convert
+    /// * `min_amount_out` - The minimum amount of the output asset to send
+    /// * `to` - The address receiving the output asset
+    fn convert(
+        e: Env,
+        token_in: Address,
+        amount_in: i128,
+        min_amount_out: i128,
+        to: Address,
+    ) -> i128;
+}
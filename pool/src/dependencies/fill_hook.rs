@@ -0,0 +1,25 @@
+use soroban_sdk::{contractclient, Address, Env, Map};
+
+/// ### FillHook
+///
+/// The interface a filler's contract must implement to fund the bid of a user liquidation auction
+/// with the auction's lot.
+#[contractclient(name = "FillHookClient")]
+pub trait FillHook {
+    /// Invoked by the pool after the lot of a filled user liquidation auction is sent to the hook, and
+    /// before the bid is settled. The hook must send at least the bid amount of each bid asset to the
+    /// pool before returning.
+    ///
+    /// ### Arguments
+    /// * `pool` - The address of the pool
+    /// * `filler` - The address filling the auction
+    /// * `lot` - The amount of each underlying asset sent to the hook
+    /// * `bid` - The amount of each underlying asset the hook must send to the pool
+    fn on_fill(
+        e: Env,
+        pool: Address,
+        filler: Address,
+        lot: Map<Address, i128>,
+        bid: Map<Address, i128>,
+    );
+}
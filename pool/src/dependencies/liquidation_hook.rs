@@ -0,0 +1,20 @@
+use soroban_sdk::{contractclient, Address, Env, Vec};
+
+use crate::pool::Request;
+
+/// ### LiquidationHook
+///
+/// The interface a user's liquidation protection hook must implement.
+#[contractclient(name = "LiquidationHookClient")]
+pub trait LiquidationHook {
+    /// Invoked by the pool before a user liquidation auction is created against `user`, or when
+    /// anyone runs the liquidation hook for `user`.
+    ///
+    /// Returns a set of `SupplyCollateral` and `Repay` requests to apply to the user's positions, where
+    /// the hook contract is the spender. The hook must authorize the token transfers to the pool.
+    ///
+    /// ### Arguments
+    /// * `pool` - The address of the pool
+    /// * `user` - The user being liquidated
+    fn on_liquidation(e: Env, pool: Address, user: Address) -> Vec<Request>;
+}
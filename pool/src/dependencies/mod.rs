@@ -1,2 +1,17 @@
 mod backstop;
-pub use backstop::{Client as BackstopClient, PoolBackstopData};
+pub use backstop::{Backstop, PoolBackstop, PoolBackstopClient, PoolBackstopData};
+
+mod claim_converter;
+pub use claim_converter::{ClaimConverter, ClaimConverterClient};
+
+mod fill_hook;
+pub use fill_hook::{FillHook, FillHookClient};
+
+mod liquidation_hook;
+pub use liquidation_hook::{LiquidationHook, LiquidationHookClient};
+
+mod rate_strategy;
+pub use rate_strategy::{RateStrategy, RateStrategyClient};
+
+mod swap_adapter;
+pub use swap_adapter::{SwapAdapter, SwapAdapterClient};
@@ -0,0 +1,19 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+/// ### RateStrategy
+///
+/// The interface a rate strategy must implement to compute the borrow rate of a reserve in place of
+/// the pool's built-in interest rate curve.
+#[contractclient(name = "RateStrategyClient")]
+pub trait RateStrategy {
+    /// Fetch the annual borrow rate of a reserve.
+    ///
+    /// Returns the annual borrow rate, in 7 decimals
+    ///
+    /// ### Arguments
+    /// * `pool` - The address of the pool
+    /// * `asset` - The underlying asset of the reserve
+    /// * `util` - The utilization of the reserve, in 7 decimals
+    /// * `ir_mod` - The reserve's interest rate modifier, in 7 decimals
+    fn borrow_rate(e: Env, pool: Address, asset: Address, util: i128, ir_mod: i128) -> i128;
+}
@@ -0,0 +1,30 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+/// ### SwapAdapter
+///
+/// The interface an AMM adapter must implement to convert a whitelisted quote asset into the bid
+/// assets of a liquidation auction.
+#[contractclient(name = "SwapAdapterClient")]
+pub trait SwapAdapter {
+    /// Swap the adapter's balance of `token_in` for exactly `amount_out` of `token_out`.
+    ///
+    /// The pool transfers the `token_in` available for the swap to the adapter before invoking it. The
+    /// adapter must send `amount_out` of `token_out` and any unused `token_in` to `to`.
+    ///
+    /// Returns the amount of `token_in` spent
+    ///
+    /// ### Arguments
+    /// * `token_in` - The asset being sold
+    /// * `token_out` - The asset being bought
+    /// * `amount_out` - The exact amount of `token_out` to receive
+    /// * `max_amount_in` - The maximum amount of `token_in` that can be spent
+    /// * `to` - The address receiving `token_out` and any unused `token_in`
+    fn swap_exact_out(
+        e: Env,
+        token_in: Address,
+        token_out: Address,
+        amount_out: i128,
+        max_amount_in: i128,
+        to: Address,
+    ) -> i128;
+}
@@ -5,7 +5,8 @@ use soroban_sdk::{panic_with_error, Address, Env, Vec};
 
 use crate::{
     constants::SCALAR_7,
-    errors::PoolError,
+    dependencies::ClaimConverterClient,
+    errors::{PoolError, PoolErrorExt},
     pool::User,
     storage::{self, ReserveEmissionData, UserEmissionData},
     validator::require_nonnegative,
@@ -17,36 +18,16 @@ pub fn execute_claim(e: &Env, from: &Address, reserve_token_ids: &Vec<u32>, to:
     let reserve_list = storage::get_res_list(e);
     let mut to_claim = 0;
     for reserve_token_id in reserve_token_ids.clone() {
-        let reserve_index = reserve_token_id / 2;
-        let reserve_addr = reserve_list.get(reserve_index);
-        match reserve_addr {
-            Some(res_address) => {
-                let reserve_config = storage::get_res_config(e, &res_address);
-                let reserve_data = storage::get_res_data(e, &res_address);
-                let (user_balance, supply) = match reserve_token_id % 2 {
-                    0 => (
-                        from_state.get_liabilities(reserve_index),
-                        reserve_data.d_supply,
-                    ),
-                    1 => (
-                        from_state.get_total_supply(reserve_index),
-                        reserve_data.b_supply,
-                    ),
-                    _ => panic_with_error!(e, PoolError::BadRequest),
-                };
-                to_claim += claim_emissions(
-                    e,
-                    reserve_token_id,
-                    supply,
-                    10i128.pow(reserve_config.decimals),
-                    from,
-                    user_balance,
-                );
-            }
-            None => {
-                panic_with_error!(e, PoolError::BadRequest)
-            }
-        }
+        let (user_balance, supply, supply_scalar) =
+            load_res_token_balances(e, &reserve_list, &from_state, reserve_token_id);
+        to_claim += claim_emissions(
+            e,
+            reserve_token_id,
+            supply,
+            supply_scalar,
+            from,
+            user_balance,
+        );
     }
 
     if to_claim > 0 {
@@ -62,6 +43,115 @@ pub fn execute_claim(e: &Env, from: &Address, reserve_token_ids: &Vec<u32>, to:
     to_claim
 }
 
+/// Performs a claim against the given "reserve_token_ids" for "from", and converts the claimed BLND
+/// through the pool's claim converter. The claimed BLND is sent to the converter, which sends the
+/// converted asset to "to".
+///
+/// Returns (amount_claimed, amount_out)
+///
+/// ### Panics
+/// * If no claim converter is set
+/// * If the converter sends less than "min_amount_out" of the converted asset
+pub fn execute_claim_converted(
+    e: &Env,
+    from: &Address,
+    reserve_token_ids: &Vec<u32>,
+    to: &Address,
+    min_amount_out: i128,
+) -> (i128, i128) {
+    let converter = match storage::get_claim_converter(e) {
+        Some(converter) => converter,
+        None => panic_with_error!(e, PoolError::BadRequest),
+    };
+    require_nonnegative(e, &min_amount_out);
+
+    let to_claim = execute_claim(e, from, reserve_token_ids, &converter);
+    if to_claim == 0 {
+        if min_amount_out > 0 {
+            panic_with_error!(e, PoolErrorExt::ClaimConversionFailed);
+        }
+        return (0, 0);
+    }
+
+    let amount_out = ClaimConverterClient::new(e, &converter).convert(
+        &storage::get_blnd_token(e),
+        &to_claim,
+        &min_amount_out,
+        to,
+    );
+    if amount_out < min_amount_out {
+        panic_with_error!(e, PoolErrorExt::ClaimConversionFailed);
+    }
+    (to_claim, amount_out)
+}
+
+/// Calculate the emissions "user" can claim for the given "reserve_token_ids" at the current ledger
+/// timestamp, including emissions accrued since each reserve token's last update, without
+/// modifying any emission data.
+///
+/// ### Arguments
+/// * `user` - The user to calculate claimable emissions for
+/// * `reserve_token_ids` - The reserve tokens to include
+///
+/// ### Panics
+/// If a reserve token id does not belong to a reserve
+pub fn calc_claimable_emissions(e: &Env, user: &Address, reserve_token_ids: &Vec<u32>) -> i128 {
+    let user_state = User::load(e, user);
+    let reserve_list = storage::get_res_list(e);
+    let mut claimable = 0;
+    for reserve_token_id in reserve_token_ids.iter() {
+        let (user_balance, supply, supply_scalar) =
+            load_res_token_balances(e, &reserve_list, &user_state, reserve_token_id);
+        if let Some(res_emis_data) = storage::get_res_emis_data(e, &reserve_token_id) {
+            let res_emis_data = accrue_emission_index(e, &res_emis_data, supply, supply_scalar);
+            claimable += calc_user_accrued(
+                e,
+                &storage::get_user_emissions(e, user, &reserve_token_id),
+                res_emis_data.index,
+                supply_scalar,
+                user_balance,
+            );
+        }
+    }
+    claimable
+}
+
+/// Load the user's balance, the total supply, and the supply scalar of a reserve token
+///
+/// Returns (user_balance, supply, supply_scalar)
+///
+/// ### Panics
+/// If the reserve token id does not belong to a reserve
+fn load_res_token_balances(
+    e: &Env,
+    reserve_list: &Vec<Address>,
+    user_state: &User,
+    reserve_token_id: u32,
+) -> (i128, i128, i128) {
+    let reserve_index = reserve_token_id / 2;
+    match reserve_list.get(reserve_index) {
+        Some(res_address) => {
+            let reserve_config = storage::get_res_config(e, &res_address);
+            let reserve_data = storage::get_res_data(e, &res_address);
+            let (user_balance, supply) = match reserve_token_id % 2 {
+                0 => (
+                    user_state.get_liabilities(reserve_index),
+                    reserve_data.d_supply,
+                ),
+                1 => (
+                    user_state.get_total_supply(reserve_index),
+                    reserve_data.b_supply,
+                ),
+                _ => panic_with_error!(e, PoolError::BadRequest),
+            };
+            (user_balance, supply, 10i128.pow(reserve_config.decimals))
+        }
+        None => {
+            panic_with_error!(e, PoolError::BadRequest)
+        }
+    }
+}
+
 /// Update the emissions information about a reserve token. Must be called before any update
 /// is made to the supply of debtTokens or blendTokens.
 ///
@@ -149,41 +239,61 @@ fn claim_emissions(
 ///
 /// ### Panics
 /// If the reserve update failed
-pub(super) fn update_emission_data(
+pub fn update_emission_data(
     e: &Env,
     res_token_id: u32,
     supply: i128,
     supply_scalar: i128,
 ) -> Option<ReserveEmissionData> {
     match storage::get_res_emis_data(e, &res_token_id) {
-        Some(mut res_emission_data) => {
-            if res_emission_data.last_time >= res_emission_data.expiration
-                || e.ledger().timestamp() == res_emission_data.last_time
-                || res_emission_data.eps == 0
-                || supply == 0
-            {
-                return Some(res_emission_data);
+        Some(res_emission_data) => {
+            let new_emission_data =
+                accrue_emission_index(e, &res_emission_data, supply, supply_scalar);
+            if new_emission_data.last_time != res_emission_data.last_time {
+                storage::set_res_emis_data(e, &res_token_id, &new_emission_data);
             }
-
-            let ledger_timestamp = if e.ledger().timestamp() > res_emission_data.expiration {
-                res_emission_data.expiration
-            } else {
-                e.ledger().timestamp()
-            };
-
-            let additional_idx = (i128(ledger_timestamp - res_emission_data.last_time)
-                * i128(res_emission_data.eps))
-            .fixed_div_floor(&e, &supply, &supply_scalar);
-
-            res_emission_data.index += additional_idx;
-            res_emission_data.last_time = ledger_timestamp;
-            storage::set_res_emis_data(e, &res_token_id, &res_emission_data);
-            Some(res_emission_data)
+            Some(new_emission_data)
         }
         None => return None, // no emission exist, no update is required
     }
 }
 
+/// Calculate the reserve token emission data at the current ledger timestamp
+///
+/// ### Arguments
+/// * `res_emission_data` - The current reserve token emission data
+/// * `supply` - The current supply of the reserve token
+/// * `supply_scalar` - The scalar of the reserve token
+fn accrue_emission_index(
+    e: &Env,
+    res_emission_data: &ReserveEmissionData,
+    supply: i128,
+    supply_scalar: i128,
+) -> ReserveEmissionData {
+    let mut res_emission_data = res_emission_data.clone();
+    if res_emission_data.last_time >= res_emission_data.expiration
+        || e.ledger().timestamp() == res_emission_data.last_time
+        || res_emission_data.eps == 0
+        || supply == 0
+    {
+        return res_emission_data;
+    }
+
+    let ledger_timestamp = if e.ledger().timestamp() > res_emission_data.expiration {
+        res_emission_data.expiration
+    } else {
+        e.ledger().timestamp()
+    };
+
+    let additional_idx = (i128(ledger_timestamp - res_emission_data.last_time)
+        * i128(res_emission_data.eps))
+    .fixed_div_floor(&e, &supply, &supply_scalar);
+
+    res_emission_data.index += additional_idx;
+    res_emission_data.last_time = ledger_timestamp;
+    res_emission_data
+}
+
 fn update_user_emissions(
     e: &Env,
     res_emis_data: &ReserveEmissionData,
@@ -193,30 +303,47 @@ fn update_user_emissions(
     balance: i128,
     claim: bool,
 ) -> i128 {
-    if let Some(user_data) = storage::get_user_emissions(e, user, &res_token_id) {
-        if user_data.index != res_emis_data.index || claim {
+    let user_data = storage::get_user_emissions(e, user, &res_token_id);
+    if let Some(user_data) = &user_data {
+        if user_data.index == res_emis_data.index && !claim {
+            return 0;
+        }
+    }
+    let accrued = calc_user_accrued(e, &user_data, res_emis_data.index, supply_scalar, balance);
+    set_user_emissions(e, user, res_token_id, res_emis_data.index, accrued, claim)
+}
+
+/// Calculate the emissions accrued by a user up to the reserve token's emission index
+///
+/// ### Arguments
+/// * `user_data` - The user's emission data for the reserve token, if any
+/// * `index` - The reserve token's emission index
+/// * `supply_scalar` - The scalar of the reserve token
+/// * `balance` - The current balance of the user
+fn calc_user_accrued(
+    e: &Env,
+    user_data: &Option<UserEmissionData>,
+    index: i128,
+    supply_scalar: i128,
+    balance: i128,
+) -> i128 {
+    match user_data {
+        Some(user_data) => {
             let mut accrual = user_data.accrued;
-            if balance != 0 {
-                let delta_index = res_emis_data.index - user_data.index;
+            // nothing has accrued if the index has not moved since the user was last updated
+            if balance != 0 && index != user_data.index {
+                let delta_index = index - user_data.index;
                 require_nonnegative(e, &delta_index);
-                let to_accrue = balance.fixed_mul_floor(
-                    e,
-                    &(res_emis_data.index - user_data.index),
-                    &(supply_scalar * SCALAR_7),
-                );
+                let to_accrue =
+                    balance.fixed_mul_floor(e, &delta_index, &(supply_scalar * SCALAR_7));
                 accrual += to_accrue;
             }
-            return set_user_emissions(e, user, res_token_id, res_emis_data.index, accrual, claim);
+            accrual
         }
-        0
-    } else if balance == 0 {
         // first time the user registered an action with the asset since emissions were added
-        return set_user_emissions(e, user, res_token_id, res_emis_data.index, 0, claim);
-    } else {
+        None if balance == 0 => 0,
         // user had tokens before emissions began, they are due any historical emissions
-        let to_accrue =
-            balance.fixed_mul_floor(e, &res_emis_data.index, &(supply_scalar * SCALAR_7));
-        return set_user_emissions(e, user, res_token_id, res_emis_data.index, to_accrue, claim);
+        None => balance.fixed_mul_floor(e, &index, &(supply_scalar * SCALAR_7)),
     }
 }
 
@@ -248,10 +375,10 @@ mod tests {
 
     use super::*;
     use soroban_sdk::{
-        map,
+        contract, contractimpl, map,
         testutils::{Address as AddressTestTrait, Ledger, LedgerInfo},
         unwrap::UnwrapOptimized,
-        vec,
+        vec, Symbol,
     };
 
     /********** update_emissions **********/
@@ -1408,7 +1535,15 @@ mod tests {
             storage::set_user_emissions(&e, &samwise, &res_token_index_1, &user_emission_data_1);
 
             let reserve_token_ids: Vec<u32> = vec![&e, res_token_index_0, res_token_index_1];
+            let claimable = calc_claimable_emissions(&e, &samwise, &reserve_token_ids);
+            assert_eq!(
+                storage::get_res_emis_data(&e, &res_token_index_0)
+                    .unwrap_optimized()
+                    .last_time,
+                1500000000
+            );
             let result = execute_claim(&e, &samwise, &reserve_token_ids, &merry);
+            assert_eq!(claimable, result);
 
             let new_reserve_emission_data =
                 storage::get_res_emis_data(&e, &res_token_index_0).unwrap_optimized();
@@ -1442,6 +1577,240 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_execute_claim_retired_reserve() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+        e.cost_estimate().budget().reset_unlimited();
+
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let merry = Address::generate(&e);
+
+        let (blnd, blnd_token_client) = testutils::create_blnd_token(&e, &pool, &bombadil);
+        let (backstop, _) = testutils::create_backstop(
+            &e,
+            &pool,
+            &Address::generate(&e),
+            &Address::generate(&e),
+            &blnd,
+        );
+        e.as_contract(&backstop, || {
+            blnd_token_client.approve(&backstop, &pool, &100_000_0000000_i128, &1000000);
+        });
+        blnd_token_client.mint(&backstop, &100_000_0000000);
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 1501000000,
+            protocol_version: 22,
+            sequence_number: 123,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        // a wound down reserve, only holding the pool's seed bTokens
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_config.enabled = false;
+        reserve_data.b_supply = 1000;
+        reserve_data.d_supply = 0;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            storage::set_backstop(&e, &backstop);
+            storage::set_res_retired(&e, 0);
+
+            let res_token_index = 1; // b_token for reserve 0
+            storage::set_res_emis_data(
+                &e,
+                &res_token_index,
+                &ReserveEmissionData {
+                    expiration: 1500000000,
+                    eps: 0_01000000000000,
+                    index: 23456780000000,
+                    last_time: 1500000000,
+                },
+            );
+            storage::set_user_emissions(
+                &e,
+                &samwise,
+                &res_token_index,
+                &UserEmissionData {
+                    index: 23456780000000,
+                    accrued: 1_0000000,
+                },
+            );
+
+            let result = execute_claim(&e, &samwise, &vec![&e, res_token_index], &merry);
+            assert_eq!(result, 1_0000000);
+            assert_eq!(blnd_token_client.balance(&merry), 1_0000000);
+        });
+    }
+
+    /// Converts BLND into its balance of an output token at half the BLND amount
+    #[contract]
+    pub struct MockClaimConverter;
+
+    #[contractimpl]
+    impl MockClaimConverter {
+        pub fn set_token_out(e: Env, token_out: Address) {
+            e.storage()
+                .instance()
+                .set(&Symbol::new(&e, "TokenOut"), &token_out);
+        }
+
+        pub fn convert(
+            e: Env,
+            _token_in: Address,
+            amount_in: i128,
+            _min_amount_out: i128,
+            to: Address,
+        ) -> i128 {
+            let token_out: Address = e
+                .storage()
+                .instance()
+                .get(&Symbol::new(&e, "TokenOut"))
+                .unwrap();
+            let amount_out = amount_in / 2;
+            TokenClient::new(&e, &token_out).transfer(
+                &e.current_contract_address(),
+                &to,
+                &amount_out,
+            );
+            amount_out
+        }
+    }
+
+    /// Setup a pool with 1 BLND of claimable emissions for samwise and a mock claim converter
+    ///
+    /// Returns (pool, samwise, converter, blnd, token_out)
+    fn setup_claim_converted(e: &Env) -> (Address, Address, Address, Address, Address) {
+        e.mock_all_auths_allowing_non_root_auth();
+        e.cost_estimate().budget().reset_unlimited();
+
+        let pool = testutils::create_pool(e);
+        let bombadil = Address::generate(e);
+        let samwise = Address::generate(e);
+        let converter = e.register(MockClaimConverter {}, ());
+
+        let (blnd, blnd_token_client) = testutils::create_blnd_token(e, &pool, &bombadil);
+        let (backstop, _) = testutils::create_backstop(
+            e,
+            &pool,
+            &Address::generate(e),
+            &Address::generate(e),
+            &blnd,
+        );
+        e.as_contract(&backstop, || {
+            blnd_token_client.approve(&backstop, &pool, &100_000_0000000_i128, &1000000);
+        });
+        blnd_token_client.mint(&backstop, &100_000_0000000);
+        let (token_out, token_out_client) = testutils::create_token_contract(e, &bombadil);
+        token_out_client.mint(&converter, &100_0000000);
+        MockClaimConverterClient::new(e, &converter).set_token_out(&token_out);
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 1501000000,
+            protocol_version: 22,
+            sequence_number: 123,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let (underlying_0, _) = testutils::create_token_contract(e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            storage::set_backstop(e, &backstop);
+            // emissions have expired, so only the accrued emissions are claimable
+            storage::set_res_emis_data(
+                e,
+                &1,
+                &ReserveEmissionData {
+                    expiration: 1500000000,
+                    eps: 0_01000000000000,
+                    index: 23456780000000,
+                    last_time: 1500000000,
+                },
+            );
+            storage::set_user_emissions(
+                e,
+                &samwise,
+                &1,
+                &UserEmissionData {
+                    index: 23456780000000,
+                    accrued: 1_0000000,
+                },
+            );
+        });
+        (pool, samwise, converter, blnd, token_out)
+    }
+
+    #[test]
+    fn test_execute_claim_converted() {
+        let e = Env::default();
+        let (pool, samwise, converter, blnd, token_out) = setup_claim_converted(&e);
+        let merry = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_claim_converter(&e, &Some(converter.clone()));
+
+            let (amount_in, amount_out) =
+                execute_claim_converted(&e, &samwise, &vec![&e, 1], &merry, 0_5000000);
+            assert_eq!(amount_in, 1_0000000);
+            assert_eq!(amount_out, 0_5000000);
+            assert_eq!(
+                storage::get_user_emissions(&e, &samwise, &1)
+                    .unwrap_optimized()
+                    .accrued,
+                0
+            );
+
+            // nothing left to claim
+            let (amount_in, amount_out) =
+                execute_claim_converted(&e, &samwise, &vec![&e, 1], &merry, 0);
+            assert_eq!(amount_in, 0);
+            assert_eq!(amount_out, 0);
+        });
+        assert_eq!(TokenClient::new(&e, &token_out).balance(&merry), 0_5000000);
+        assert_eq!(TokenClient::new(&e, &blnd).balance(&merry), 0);
+        assert_eq!(TokenClient::new(&e, &blnd).balance(&converter), 1_0000000);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1250)")]
+    fn test_execute_claim_converted_below_min_amount_out() {
+        let e = Env::default();
+        let (pool, samwise, converter, _, _) = setup_claim_converted(&e);
+        let merry = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_claim_converter(&e, &Some(converter.clone()));
+
+            execute_claim_converted(&e, &samwise, &vec![&e, 1], &merry, 0_5000001);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_execute_claim_converted_no_converter() {
+        let e = Env::default();
+        let (pool, samwise, _, _, _) = setup_claim_converted(&e);
+        let merry = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            execute_claim_converted(&e, &samwise, &vec![&e, 1], &merry, 0);
+        });
+    }
+
     #[test]
     fn test_execute_claim_with_already_claimed_reserve() {
         let e = Env::default();
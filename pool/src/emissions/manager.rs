@@ -1,9 +1,9 @@
 use crate::{
-    constants::SCALAR_7,
-    dependencies::BackstopClient,
-    errors::PoolError,
+    constants::{SCALAR_7, SECONDS_PER_WEEK},
+    dependencies::Backstop,
+    errors::{PoolError, PoolErrorExt},
     events::PoolEvents,
-    storage::{self, ReserveConfig, ReserveEmissionData},
+    storage::{self, EmissionDecay, ReserveConfig, ReserveEmissionData},
 };
 use cast::{i128, u64};
 use soroban_fixed_point_math::SorobanFixedPoint;
@@ -16,6 +16,7 @@ use super::distributor;
 // Types
 
 /// Metadata for a pool's reserve emission configuration
+#[derive(Clone)]
 #[contracttype]
 pub struct ReserveEmissionMetadata {
     pub res_index: u32,
@@ -32,8 +33,8 @@ pub struct ReserveEmissionMetadata {
 ///                             if the total pool eps
 ///
 /// ### Panics
-/// If any res_emission_metadata is included where share is 0, the reserve index is invalid,
-/// or the reserve type is invalid
+/// If any res_emission_metadata is included where share is 0, the reserve index is invalid
+/// or retired, or the reserve type is invalid
 pub fn set_pool_emissions(e: &Env, res_emission_metadata: Vec<ReserveEmissionMetadata>) {
     let mut pool_emissions: Map<u32, u64> = map![e];
 
@@ -46,12 +47,83 @@ pub fn set_pool_emissions(e: &Env, res_emission_metadata: Vec<ReserveEmissionMet
         {
             panic_with_error!(e, PoolError::BadRequest);
         }
+        if storage::is_res_retired(e, metadata.res_index) {
+            panic_with_error!(e, PoolErrorExt::ReserveRetired);
+        }
         pool_emissions.set(key, metadata.share);
     }
 
     storage::set_pool_emissions(e, &pool_emissions);
 }
 
+/// Adjust the share of a single reserve token in the pool emissions, leaving the other shares as is.
+/// Shares are relative, so the emissions of each reserve token are re-normalized against the new total.
+///
+/// These will not be applied until the next `update_emissions` is run
+///
+/// ### Arguments
+/// * `res_token_id` - The reserve token id to adjust
+/// * `new_share` - The new share of the reserve token, or 0 to remove it from the pool emissions
+///
+/// ### Panics
+/// If the reserve token id is invalid, the share of a retired reserve's token is increased, or no
+/// reserve tokens would be left to receive emissions
+pub fn adjust_pool_emission_share(e: &Env, res_token_id: u32, new_share: u64) {
+    let reserve_list = storage::get_res_list(e);
+    if reserve_list.get(res_token_id / 2).is_none() {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+
+    let mut pool_emissions = storage::get_pool_emissions(e);
+    if new_share == 0 {
+        pool_emissions.remove(res_token_id);
+    } else {
+        if storage::is_res_retired(e, res_token_id / 2) {
+            panic_with_error!(e, PoolErrorExt::ReserveRetired);
+        }
+        pool_emissions.set(res_token_id, new_share);
+    }
+    // the remaining shares must be able to be normalized
+    if pool_emissions.is_empty() {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+
+    storage::set_pool_emissions(e, &pool_emissions);
+}
+
+/// Set the emission decay of a single reserve token. Each emission epoch, the share of the reserve
+/// token in the pool emissions is reduced to `factor` of its previous share when emissions are gulped,
+/// so its emissions taper without further updates to the emission configuration. Shares are relative,
+/// so the decayed emissions are re-normalized to the other reserve tokens receiving emissions.
+///
+/// The first decay is applied to the first gulp at least one epoch after the decay is set.
+///
+/// ### Arguments
+/// * `res_token_id` - The reserve token id to decay
+/// * `factor` - The fraction of the share kept each epoch in 7 decimals, or 0 to remove the decay
+///
+/// ### Panics
+/// If the reserve token id is invalid, or the factor is not below 100%
+pub fn set_emission_decay(e: &Env, res_token_id: u32, factor: u32) {
+    let reserve_list = storage::get_res_list(e);
+    if reserve_list.get(res_token_id / 2).is_none() {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    if i128(factor) >= SCALAR_7 {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+
+    let decay = if factor == 0 {
+        None
+    } else {
+        Some(EmissionDecay {
+            factor,
+            last_time: e.ledger().timestamp(),
+        })
+    };
+    storage::set_emission_decay(e, res_token_id, &decay);
+}
+
 /// Consume emitted tokens from the backstop and distribute them to reserves
 ///
 /// Returns the number of new tokens distributed for emissions
@@ -59,19 +131,41 @@ pub fn set_pool_emissions(e: &Env, res_emission_metadata: Vec<ReserveEmissionMet
 /// ### Panics
 /// If the pool is not in the backstop reward zone
 pub fn gulp_emissions(e: &Env) -> i128 {
-    let backstop = storage::get_backstop(e);
-    let new_emissions =
-        BackstopClient::new(e, &backstop).gulp_emissions(&e.current_contract_address());
+    let new_emissions = Backstop::load(e).gulp_emissions();
     do_gulp_emissions(e, new_emissions);
+    storage::set_last_gulp(e, e.ledger().timestamp());
     new_emissions
 }
 
+/// Gulp emissions if auto gulp is enabled and emissions have not been gulped during the
+/// current emission cycle. Emissions are attempted at most once per cycle, and a failed gulp is
+/// ignored so it never blocks the calling action.
+///
+/// Returns the number of new tokens distributed for emissions, or None if no emissions were gulped
+pub fn auto_gulp_emissions(e: &Env) -> Option<i128> {
+    // the backstop only accrues emissions to a pool once per day
+    let timestamp = e.ledger().timestamp();
+    if !storage::get_auto_gulp(e) || timestamp < storage::get_last_gulp(e) + 24 * 60 * 60 {
+        return None;
+    }
+    storage::set_last_gulp(e, timestamp);
+
+    let new_emissions = Backstop::load(e).try_gulp_emissions()?;
+    if new_emissions < SCALAR_7 {
+        // too few emissions to distribute without rounding issues, leave them unallocated
+        return None;
+    }
+    do_gulp_emissions(e, new_emissions);
+    PoolEvents::gulp_emissions(e, new_emissions);
+    Some(new_emissions)
+}
+
 fn do_gulp_emissions(e: &Env, new_emissions: i128) {
     // ensure enough tokens are being emitted to avoid rounding issues
     if new_emissions < SCALAR_7 {
         panic_with_error!(e, PoolError::BadRequest)
     }
-    let pool_emissions = storage::get_pool_emissions(e);
+    let pool_emissions = decay_pool_emissions(e, storage::get_pool_emissions(e));
     let reserve_list = storage::get_res_list(e);
     let mut pool_emis_enabled: Vec<(ReserveConfig, Address, u32, u64)> = Vec::new(e);
 
@@ -106,6 +200,39 @@ fn do_gulp_emissions(e: &Env, new_emissions: i128) {
     }
 }
 
+/// Decay the share of each reserve token with an emission decay by the number of emission epochs
+/// passed since it was last decayed. Shares are kept above 0 so the pool emissions can always be
+/// normalized.
+///
+/// Returns the pool emissions after the decay
+fn decay_pool_emissions(e: &Env, mut pool_emissions: Map<u32, u64>) -> Map<u32, u64> {
+    let timestamp = e.ledger().timestamp();
+    let mut decayed = false;
+    for (res_token_id, share) in pool_emissions.iter() {
+        if let Some(mut decay) = storage::get_emission_decay(e, res_token_id) {
+            let epochs = (timestamp - decay.last_time) / SECONDS_PER_WEEK;
+            if epochs == 0 {
+                continue;
+            }
+            let mut new_share = i128(share);
+            for _ in 0..epochs {
+                if new_share <= 1 {
+                    break;
+                }
+                new_share = new_share.fixed_mul_floor(e, &i128(decay.factor), &SCALAR_7);
+            }
+            decay.last_time += epochs * SECONDS_PER_WEEK;
+            storage::set_emission_decay(e, res_token_id, &Some(decay));
+            pool_emissions.set(res_token_id, u64(new_share.max(1)).unwrap_optimized());
+            decayed = true;
+        }
+    }
+    if decayed {
+        storage::set_pool_emissions(e, &pool_emissions);
+    }
+    pool_emissions
+}
+
 fn update_reserve_emission_eps(
     e: &Env,
     reserve_config: &ReserveConfig,
@@ -130,6 +257,24 @@ fn update_reserve_emission_eps(
     ) {
         // data exists - update it with old config
 
+        // emissions could not be distributed since the last accrual if the reserve token had no supply,
+        // so carry them forward instead of dropping them when the emission data is forced forward
+        let mut carryover = storage::get_emission_carryover(e, res_token_id);
+        let accrued_until = emission_data.expiration.min(e.ledger().timestamp());
+        if emission_data.last_time < accrued_until {
+            // Eps is scaled by 14 decimals
+            carryover += i128(emission_data.eps).fixed_mul_floor(
+                e,
+                &i128(accrued_until - emission_data.last_time),
+                &SCALAR_7,
+            );
+        }
+        if supply > 0 {
+            tokens_left_to_emit += carryover;
+            carryover = 0;
+        }
+        storage::set_emission_carryover(e, res_token_id, carryover);
+
         if emission_data.last_time != e.ledger().timestamp() {
             // force the emission data to be updated to the current timestamp
             emission_data.last_time = e.ledger().timestamp();
@@ -420,6 +565,76 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_gulp_emissions_carries_over_zero_supply() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 1500000000,
+            protocol_version: 22,
+            sequence_number: 20100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let new_emissions: i128 = 604_800_0000000;
+        let pool_emissions: Map<u32, u64> = map![&e, (0, 1_0000000)];
+
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.last_time = 1499900000;
+        reserve_data.d_supply = 0;
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        // setup reserve_0 liability to have expired without any supply to distribute to
+        let old_r_0_l_data = ReserveEmissionData {
+            eps: 0_10000000000000,
+            expiration: 1499990000,
+            index: 999990000000,
+            last_time: 1499900000,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_emissions(&e, &pool_emissions);
+            storage::set_res_emis_data(&e, &0, &old_r_0_l_data);
+
+            // undistributed emissions are held while the reserve token has no supply
+            do_gulp_emissions(&e, new_emissions);
+            assert_eq!(storage::get_emission_carryover(&e, 0), 9_000_0000000);
+            let r_0_l_data = storage::get_res_emis_data(&e, &0).unwrap_optimized();
+            assert_eq!(r_0_l_data.eps, 1_00000000000000);
+            assert_eq!(r_0_l_data.index, 999990000000);
+            assert_eq!(r_0_l_data.last_time, 1500000000);
+        });
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 1500000000 + 7 * 24 * 60 * 60,
+            protocol_version: 22,
+            sequence_number: 20100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        e.as_contract(&pool, || {
+            reserve_data.d_supply = 100_000_0000000;
+            storage::set_res_data(&e, &underlying_0, &reserve_data);
+
+            // the carryover is added to the next epoch once there is supply to distribute to
+            do_gulp_emissions(&e, new_emissions);
+            assert_eq!(storage::get_emission_carryover(&e, 0), 0);
+            let r_0_l_data = storage::get_res_emis_data(&e, &0).unwrap_optimized();
+            assert_eq!(r_0_l_data.eps, 1_01488095238095);
+            assert_eq!(r_0_l_data.expiration, 1500000000 + 14 * 24 * 60 * 60);
+        });
+    }
+
     #[test]
     #[should_panic(expected = "Error(Contract, #1200)")]
     fn test_gulp_emissions_too_small() {
@@ -482,6 +697,54 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_auto_gulp_emissions_ignores_failed_gulp() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+        e.cost_estimate().budget().reset_unlimited();
+        e.ledger().set(LedgerInfo {
+            timestamp: 1500000000,
+            protocol_version: 22,
+            sequence_number: 20100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+        let (blnd, _) = testutils::create_token_contract(&e, &bombadil);
+        let (usdc, _) = testutils::create_token_contract(&e, &bombadil);
+        let (lp_token, _) = testutils::create_comet_lp_pool(&e, &bombadil, &blnd, &usdc);
+        testutils::create_backstop(&e, &pool, &lp_token, &usdc, &blnd);
+
+        e.as_contract(&pool, || {
+            // disabled by default
+            assert_eq!(auto_gulp_emissions(&e), None);
+            assert_eq!(storage::get_last_gulp(&e), 0);
+
+            // the pool has no emissions to gulp from the backstop
+            storage::set_auto_gulp(&e, true);
+            assert_eq!(auto_gulp_emissions(&e), None);
+            assert_eq!(storage::get_last_gulp(&e), 1500000000);
+        });
+
+        // only attempted once per day
+        e.ledger().set_timestamp(1500000000 + 24 * 60 * 60 - 1);
+        e.as_contract(&pool, || {
+            assert_eq!(auto_gulp_emissions(&e), None);
+            assert_eq!(storage::get_last_gulp(&e), 1500000000);
+        });
+
+        e.ledger().set_timestamp(1500000000 + 24 * 60 * 60);
+        e.as_contract(&pool, || {
+            assert_eq!(auto_gulp_emissions(&e), None);
+            assert_eq!(storage::get_last_gulp(&e), 1500000000 + 24 * 60 * 60);
+        });
+    }
+
     /********** set_pool_emissions **********/
 
     #[test]
@@ -653,4 +916,198 @@ mod tests {
             assert_eq!(new_pool_emissions.get(6).unwrap_optimized(), 0_6500000);
         });
     }
+
+    /********** adjust_pool_emission_share **********/
+
+    /// Setup a pool with two reserves
+    fn setup_emission_share_pool(e: &Env) -> Address {
+        e.mock_all_auths();
+        e.cost_estimate().budget().reset_unlimited();
+        e.ledger().set(LedgerInfo {
+            timestamp: 1500000000,
+            protocol_version: 22,
+            sequence_number: 20100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let pool = testutils::create_pool(e);
+        let bombadil = Address::generate(e);
+
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        let (underlying_0, _) = testutils::create_token_contract(e, &bombadil);
+        testutils::create_reserve(e, &pool, &underlying_0, &reserve_config, &reserve_data);
+        let (underlying_1, _) = testutils::create_token_contract(e, &bombadil);
+        testutils::create_reserve(e, &pool, &underlying_1, &reserve_config, &reserve_data);
+        pool
+    }
+
+    #[test]
+    fn test_adjust_pool_emission_share() {
+        let e = Env::default();
+        let pool = setup_emission_share_pool(&e);
+
+        let pool_emissions: Map<u32, u64> = map![&e, (0, 0_2500000), (3, 0_7500000)];
+        e.as_contract(&pool, || {
+            storage::set_pool_emissions(&e, &pool_emissions);
+
+            // update an existing share
+            adjust_pool_emission_share(&e, 3, 0_5000000);
+            assert_eq!(
+                storage::get_pool_emissions(&e),
+                map![&e, (0, 0_2500000), (3, 0_5000000)]
+            );
+
+            // add a new share
+            adjust_pool_emission_share(&e, 1, 0_2500000);
+            assert_eq!(
+                storage::get_pool_emissions(&e),
+                map![&e, (0, 0_2500000), (1, 0_2500000), (3, 0_5000000)]
+            );
+
+            // remove a share
+            adjust_pool_emission_share(&e, 0, 0);
+            assert_eq!(
+                storage::get_pool_emissions(&e),
+                map![&e, (1, 0_2500000), (3, 0_5000000)]
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_adjust_pool_emission_share_invalid_reserve() {
+        let e = Env::default();
+        let pool = setup_emission_share_pool(&e);
+
+        let pool_emissions: Map<u32, u64> = map![&e, (0, 0_2500000)];
+        e.as_contract(&pool, || {
+            storage::set_pool_emissions(&e, &pool_emissions);
+
+            adjust_pool_emission_share(&e, 4, 0_5000000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1244)")]
+    fn test_adjust_pool_emission_share_retired_reserve() {
+        let e = Env::default();
+        let pool = setup_emission_share_pool(&e);
+
+        let pool_emissions: Map<u32, u64> = map![&e, (0, 0_2500000), (3, 0_7500000)];
+        e.as_contract(&pool, || {
+            storage::set_pool_emissions(&e, &pool_emissions);
+            storage::set_res_retired(&e, 1);
+
+            // a retired reserve's share can be removed
+            adjust_pool_emission_share(&e, 3, 0);
+            assert_eq!(storage::get_pool_emissions(&e), map![&e, (0, 0_2500000)]);
+
+            // but not re-targeted
+            adjust_pool_emission_share(&e, 3, 0_5000000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1244)")]
+    fn test_set_pool_emissions_retired_reserve() {
+        let e = Env::default();
+        let pool = setup_emission_share_pool(&e);
+
+        let res_emission_metadata: Vec<ReserveEmissionMetadata> = vec![
+            &e,
+            ReserveEmissionMetadata {
+                res_index: 0,
+                res_type: 1,
+                share: 0_5000000,
+            },
+            ReserveEmissionMetadata {
+                res_index: 1,
+                res_type: 0,
+                share: 0_5000000,
+            },
+        ];
+        e.as_contract(&pool, || {
+            storage::set_res_retired(&e, 1);
+
+            set_pool_emissions(&e, res_emission_metadata);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_adjust_pool_emission_share_removes_last_share() {
+        let e = Env::default();
+        let pool = setup_emission_share_pool(&e);
+
+        let pool_emissions: Map<u32, u64> = map![&e, (0, 0_2500000)];
+        e.as_contract(&pool, || {
+            storage::set_pool_emissions(&e, &pool_emissions);
+
+            adjust_pool_emission_share(&e, 0, 0);
+        });
+    }
+
+    #[test]
+    fn test_emission_decay() {
+        let e = Env::default();
+        let pool = setup_emission_share_pool(&e);
+
+        let pool_emissions: Map<u32, u64> = map![&e, (0, 0_5000000), (3, 0_5000000)];
+        e.as_contract(&pool, || {
+            storage::set_pool_emissions(&e, &pool_emissions);
+            set_emission_decay(&e, 0, 0_5000000);
+            assert_eq!(
+                storage::get_emission_decay(&e, 0),
+                Some(EmissionDecay {
+                    factor: 0_5000000,
+                    last_time: 1500000000,
+                })
+            );
+
+            // no epoch has passed, so the share is not decayed
+            e.ledger().set_timestamp(1500000000 + SECONDS_PER_WEEK - 1);
+            do_gulp_emissions(&e, 100_0000000);
+            assert_eq!(storage::get_pool_emissions(&e), pool_emissions);
+
+            // two epochs have passed, so the share is decayed twice
+            e.ledger()
+                .set_timestamp(1500000000 + 2 * SECONDS_PER_WEEK + 24 * 60 * 60);
+            do_gulp_emissions(&e, 100_0000000);
+            assert_eq!(
+                storage::get_pool_emissions(&e),
+                map![&e, (0, 0_1250000), (3, 0_5000000)]
+            );
+            let decay = storage::get_emission_decay(&e, 0).unwrap_optimized();
+            assert_eq!(decay.last_time, 1500000000 + 2 * SECONDS_PER_WEEK);
+            // reserve_0 liability receives 0.125 / 0.625 of the new emissions
+            let r_0_l_data = storage::get_res_emis_data(&e, &0).unwrap_optimized();
+            let r_1_s_data = storage::get_res_emis_data(&e, &3).unwrap_optimized();
+            assert!(r_0_l_data.eps < r_1_s_data.eps);
+
+            // removing the decay keeps the decayed share
+            set_emission_decay(&e, 0, 0);
+            assert!(storage::get_emission_decay(&e, 0).is_none());
+            e.ledger().set_timestamp(1500000000 + 4 * SECONDS_PER_WEEK);
+            do_gulp_emissions(&e, 100_0000000);
+            assert_eq!(
+                storage::get_pool_emissions(&e),
+                map![&e, (0, 0_1250000), (3, 0_5000000)]
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_emission_decay_invalid_factor() {
+        let e = Env::default();
+        let pool = setup_emission_share_pool(&e);
+
+        e.as_contract(&pool, || {
+            set_emission_decay(&e, 0, 1_0000000);
+        });
+    }
 }
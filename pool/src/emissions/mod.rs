@@ -1,5 +1,11 @@
 mod manager;
-pub use manager::{gulp_emissions, set_pool_emissions, ReserveEmissionMetadata};
+pub use manager::{
+    adjust_pool_emission_share, auto_gulp_emissions, gulp_emissions, set_emission_decay,
+    set_pool_emissions, ReserveEmissionMetadata,
+};
 
 mod distributor;
-pub use distributor::{execute_claim, update_emissions};
+pub use distributor::{
+    calc_claimable_emissions, execute_claim, execute_claim_converted, update_emission_data,
+    update_emissions,
+};
@@ -52,4 +52,41 @@ pub enum PoolError {
     InvalidLot = 1222,
     ReserveDisabled = 1223,
     MinCollateralNotMet = 1224,
+    AuctionNotStarted = 1225,
+    BorrowAllowanceExceeded = 1226,
+    InterestAuctionTooSoon = 1227,
+    MinDebtNotMet = 1228,
+    MaxQuoteExceeded = 1229,
+    QuoteSwapFailed = 1230,
+    AuctionNotFound = 1231,
+    InvalidReserveDecimals = 1232,
+    InvalidReserveFactors = 1233,
+    InvalidReserveUtil = 1234,
+    InvalidReserveRates = 1235,
+    InvalidReserveReactivity = 1236,
+    UserMinHfNotMet = 1237,
+    ReservePaused = 1238,
+    ActionNotUnlocked = 1239,
+    TimelockRequired = 1240,
+    OracleDeviation = 1241,
+    ReserveRateDecreased = 1242,
+    InvalidFillCommitment = 1252,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+/// Additional error codes for the pool contract. A contract spec supports at most 50 cases per
+/// error enum, so pool specific errors past that limit are defined here. The codes do not overlap
+/// with `PoolError`.
+pub enum PoolErrorExt {
+    ReserveRateExceeded = 1243,
+    ReserveRetired = 1244,
+    StableRateDisabled = 1245,
+    AddressBlocked = 1246,
+    FillHookFailed = 1247,
+    AuctionsPaused = 1248,
+    PositionsArchived = 1249,
+    ClaimConversionFailed = 1250,
+    KeeperNotRegistered = 1251,
 }
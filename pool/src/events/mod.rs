@@ -0,0 +1,1561 @@
+//! Pool events. Topic names are registered in [`topics`] and multi-value payloads are typed in
+//! [`payloads`]. Any change to an event's layout must bump [`topics::EVENT_VERSION`].
+
+pub mod payloads;
+pub mod topics;
+
+use payloads::*;
+
+use sep_40_oracle::Asset;
+use soroban_sdk::{Address, BytesN, Env, Symbol, Vec};
+
+use crate::{
+    AdminAction, AuctionConfig, AuctionData, BstopRateStep, ConfigSnapshot, FillCommitConfig,
+    InsuranceConfig, KeeperConfig, PoolBase, PriceBand, ReserveConfig, ReservePause, RiskPremium,
+    StableRateConfig, UtilBreaker, WithdrawFee,
+};
+
+pub struct PoolEvents {}
+
+impl PoolEvents {
+    /// Emitted when a new admin is set for a pool
+    ///
+    /// - topics - `["set_admin", admin: Address]`
+    /// - data - `new_admin: Address`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * new_admin - The new admin of the pool
+    pub fn set_admin(e: &Env, admin: Address, new_admin: Address) {
+        let topics = (Symbol::new(e, topics::SET_ADMIN), admin);
+        e.events().publish(topics, new_admin);
+    }
+
+    /// Emitted when pool parameters are updated
+    ///
+    /// - topics - `["update_pool", admin: Address]`
+    /// - data - `[backstop_take_rate: u32, max_positions: u32, min_collateral: i128, min_debt: i128]`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * backstop_take_rate - The new backstop take rate
+    /// * max_positions - The new maximum number of positions
+    /// * min_collateral - The new minimum collateral
+    /// * min_debt - The new minimum debt
+    pub fn update_pool(
+        e: &Env,
+        admin: Address,
+        backstop_take_rate: u32,
+        max_positions: u32,
+        min_collateral: i128,
+        min_debt: i128,
+    ) {
+        let topics = (Symbol::new(e, topics::UPDATE_POOL), admin);
+        e.events().publish(
+            topics,
+            UpdatePoolEvent(backstop_take_rate, max_positions, min_collateral, min_debt),
+        );
+    }
+
+    /// Emitted when the backstop take rate schedule is updated
+    ///
+    /// - topics - `["set_bstop_rate_schedule", admin: Address]`
+    /// - data - `schedule: Vec<BstopRateStep>`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * schedule - The new backstop take rate schedule
+    pub fn set_bstop_rate_schedule(e: &Env, admin: Address, schedule: Vec<BstopRateStep>) {
+        let topics = (Symbol::new(e, topics::SET_BSTOP_RATE_SCHEDULE), admin);
+        e.events().publish(topics, schedule);
+    }
+
+    /// Emitted when the auction config is updated
+    ///
+    /// - topics - `["set_auction_config", admin: Address]`
+    /// - data - `config: AuctionConfig`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * config - The new auction config
+    pub fn set_auction_config(e: &Env, admin: Address, config: AuctionConfig) {
+        let topics = (Symbol::new(e, topics::SET_AUCTION_CONFIG), admin);
+        e.events().publish(topics, config);
+    }
+
+    /// Emitted when the price band for a reserve is updated
+    ///
+    /// - topics - `["set_price_band", admin: Address]`
+    /// - data - `[asset: Address, band: Option<PriceBand>]`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * asset - The reserve asset
+    /// * band - The new price band, or None if it was removed
+    pub fn set_price_band(e: &Env, admin: Address, asset: Address, band: Option<PriceBand>) {
+        let topics = (Symbol::new(e, topics::SET_PRICE_BAND), admin);
+        let data: SetPriceBandEvent = (asset, band);
+        e.events().publish(topics, data);
+    }
+
+    /// Emitted when supplying to or borrowing from a reserve is paused or unpaused
+    ///
+    /// - topics - `["set_reserve_paused", admin: Address]`
+    /// - data - `[asset: Address, supply_paused: bool, borrow_paused: bool]`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * asset - The reserve asset
+    /// * pause - The actions paused for the reserve
+    pub fn set_reserve_paused(e: &Env, admin: Address, asset: Address, pause: ReservePause) {
+        let topics = (Symbol::new(e, topics::SET_RESERVE_PAUSED), admin);
+        e.events().publish(
+            topics,
+            SetReservePausedEvent(asset, pause.supply, pause.borrow),
+        );
+    }
+
+    /// Emitted when the oracle of the pool is updated
+    ///
+    /// - topics - `["set_oracle", admin: Address]`
+    /// - data - `oracle: Address`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * oracle - The new oracle of the pool
+    pub fn set_oracle(e: &Env, admin: Address, oracle: Address) {
+        let topics = (Symbol::new(e, topics::SET_ORACLE), admin);
+        e.events().publish(topics, oracle);
+    }
+
+    /// Emitted when the oracle asset used to price a reserve is updated
+    ///
+    /// - topics - `["set_oracle_asset", admin: Address]`
+    /// - data - `[asset: Address, oracle_asset: Option<Asset>]`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * asset - The reserve asset
+    /// * oracle_asset - The new oracle asset, or None if the reserve is priced by its own address
+    pub fn set_oracle_asset(e: &Env, admin: Address, asset: Address, oracle_asset: Option<Asset>) {
+        let topics = (Symbol::new(e, topics::SET_ORACLE_ASSET), admin);
+        let data: SetOracleAssetEvent = (asset, oracle_asset);
+        e.events().publish(topics, data);
+    }
+
+    /// Emitted when the base asset of the pool's oracle is updated
+    ///
+    /// - topics - `["set_base", admin: Address]`
+    /// - data - `base: Option<PoolBase>`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * base - The new base asset, or None if the oracle prices assets in USD
+    pub fn set_base(e: &Env, admin: Address, base: Option<PoolBase>) {
+        let topics = (Symbol::new(e, topics::SET_BASE), admin);
+        e.events().publish(topics, base);
+    }
+
+    /// Emitted when an oracle price is outside of a reserve's price band and gets clamped
+    ///
+    /// - topics - `["price_clamped", asset: Address]`
+    /// - data - `[oracle_price: i128, clamped_price: i128]`
+    ///
+    /// ### Arguments
+    /// * asset - The reserve asset
+    /// * oracle_price - The price reported by the oracle
+    /// * clamped_price - The price used by the pool
+    pub fn price_clamped(e: &Env, asset: Address, oracle_price: i128, clamped_price: i128) {
+        let topics = (Symbol::new(e, topics::PRICE_CLAMPED), asset);
+        e.events()
+            .publish(topics, PriceClampedEvent(oracle_price, clamped_price));
+    }
+
+    /// Emitted when a new reserve configuration change is queued
+    ///
+    /// - topics - `["queue_set_reserve", admin: Address]`
+    /// - data - `[asset: Address, metadata: ReserveMetadata]`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * asset - The asset to change the reserve configuration of
+    /// * metadata - The new reserve configuration
+    pub fn queue_set_reserve(e: &Env, admin: Address, asset: Address, metadata: ReserveConfig) {
+        let topics = (Symbol::new(e, topics::QUEUE_SET_RESERVE), admin);
+        e.events()
+            .publish(topics, QueueSetReserveEvent(asset, metadata));
+    }
+
+    /// Emitted when a queued reserve configuration change is cancelled
+    ///
+    /// - topics - `["cancel_set_reserve", admin: Address]`
+    /// - data - `asset: Address`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * asset - The asset to cancel the reserve configuration change of
+    pub fn cancel_set_reserve(e: &Env, admin: Address, asset: Address) {
+        let topics = (Symbol::new(e, topics::CANCEL_SET_RESERVE), admin);
+        e.events().publish(topics, asset);
+    }
+
+    /// Emitted when the BLND bond required to propose a reserve is updated
+    ///
+    /// - topics - `["set_listing_bond", admin: Address]`
+    /// - data - `bond: i128`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * bond - The amount of BLND to bond, or 0 if proposals were disabled
+    pub fn set_listing_bond(e: &Env, admin: Address, bond: i128) {
+        let topics = (Symbol::new(e, topics::SET_LISTING_BOND), admin);
+        e.events().publish(topics, bond);
+    }
+
+    /// Emitted when the council that decides reserve proposals is updated
+    ///
+    /// - topics - `["set_listing_council", admin: Address]`
+    /// - data - `council: Option<Address>`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * council - The council, or None if the admin decides proposals
+    pub fn set_listing_council(e: &Env, admin: Address, council: Option<Address>) {
+        let topics = (Symbol::new(e, topics::SET_LISTING_COUNCIL), admin);
+        e.events().publish(topics, council);
+    }
+
+    /// Emitted when a reserve is proposed
+    ///
+    /// - topics - `["propose_reserve", proposer: Address, asset: Address]`
+    /// - data - `[config: ReserveConfig, bond: i128]`
+    ///
+    /// ### Arguments
+    /// * proposer - The address proposing the reserve
+    /// * asset - The underlying asset of the proposed reserve
+    /// * config - The proposed reserve config
+    /// * bond - The amount of BLND bonded
+    pub fn propose_reserve(
+        e: &Env,
+        proposer: Address,
+        asset: Address,
+        config: ReserveConfig,
+        bond: i128,
+    ) {
+        let topics = (Symbol::new(e, topics::PROPOSE_RESERVE), proposer, asset);
+        e.events()
+            .publish(topics, ProposeReserveEvent(config, bond));
+    }
+
+    /// Emitted when a reserve proposal is approved
+    ///
+    /// - topics - `["approve_reserve_proposal", approver: Address, asset: Address]`
+    /// - data - `proposer: Address`
+    ///
+    /// ### Arguments
+    /// * approver - The address that decided the proposal
+    /// * asset - The underlying asset of the proposed reserve
+    /// * proposer - The address that proposed the reserve
+    pub fn approve_reserve_proposal(e: &Env, approver: Address, asset: Address, proposer: Address) {
+        let topics = (
+            Symbol::new(e, topics::APPROVE_RESERVE_PROPOSAL),
+            approver,
+            asset,
+        );
+        e.events().publish(topics, proposer);
+    }
+
+    /// Emitted when a reserve proposal is rejected
+    ///
+    /// - topics - `["reject_reserve_proposal", approver: Address, asset: Address]`
+    /// - data - `slash: bool`
+    ///
+    /// ### Arguments
+    /// * approver - The address that decided the proposal
+    /// * asset - The underlying asset of the proposed reserve
+    /// * slash - If the bond was slashed
+    pub fn reject_reserve_proposal(e: &Env, approver: Address, asset: Address, slash: bool) {
+        let topics = (
+            Symbol::new(e, topics::REJECT_RESERVE_PROPOSAL),
+            approver,
+            asset,
+        );
+        e.events().publish(topics, slash);
+    }
+
+    /// Emitted when the bond of an expired reserve proposal is returned to the proposer
+    ///
+    /// - topics - `["reclaim_reserve_proposal", proposer: Address, asset: Address]`
+    /// - data - `bond: i128`
+    ///
+    /// ### Arguments
+    /// * proposer - The address that proposed the reserve
+    /// * asset - The underlying asset of the proposed reserve
+    /// * bond - The amount of BLND returned
+    pub fn reclaim_reserve_proposal(e: &Env, proposer: Address, asset: Address, bond: i128) {
+        let topics = (
+            Symbol::new(e, topics::RECLAIM_RESERVE_PROPOSAL),
+            proposer,
+            asset,
+        );
+        e.events().publish(topics, bond);
+    }
+
+    /// Emitted when an admin action is queued
+    ///
+    /// - topics - `["queue_admin_action", admin: Address]`
+    /// - data - `[id: u32, action: AdminAction, unlock_time: u64]`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * id - The id of the queued action
+    /// * action - The queued action
+    /// * unlock_time - The timestamp the action can be executed at
+    pub fn queue_admin_action(
+        e: &Env,
+        admin: Address,
+        id: u32,
+        action: AdminAction,
+        unlock_time: u64,
+    ) {
+        let topics = (Symbol::new(e, topics::QUEUE_ADMIN_ACTION), admin);
+        e.events()
+            .publish(topics, QueueAdminActionEvent(id, action, unlock_time));
+    }
+
+    /// Emitted when a queued admin action is cancelled
+    ///
+    /// - topics - `["cancel_queued_action", admin: Address]`
+    /// - data - `id: u32`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * id - The id of the cancelled action
+    pub fn cancel_queued_action(e: &Env, admin: Address, id: u32) {
+        let topics = (Symbol::new(e, topics::CANCEL_QUEUED_ACTION), admin);
+        e.events().publish(topics, id);
+    }
+
+    /// Emitted when a queued admin action is executed
+    ///
+    /// - topics - `["execute_queued_action"]`
+    /// - data - `id: u32`
+    ///
+    /// ### Arguments
+    /// * id - The id of the executed action
+    pub fn execute_queued_action(e: &Env, id: u32) {
+        let topics = (Symbol::new(e, topics::EXECUTE_QUEUED_ACTION),);
+        e.events().publish(topics, id);
+    }
+
+    /// Emitted when a reserve configuration change is set
+    ///
+    /// - topics - `["set_reserve"]`
+    /// - data - `[asset: Address, index: u32]`
+    ///
+    /// ### Arguments
+    /// * asset - The asset to change the reserve configuration of
+    /// * index - The reserve index
+    pub fn set_reserve(e: &Env, asset: Address, index: u32) {
+        let topics = (Symbol::new(e, topics::SET_RESERVE),);
+        e.events().publish(topics, SetReserveEvent(asset, index));
+    }
+
+    /// Emitted after the pool config or a reserve config is updated, with a snapshot of the pool's configuration
+    ///
+    /// - topics - `["config_snapshot"]`
+    /// - data - `snapshot: ConfigSnapshot`
+    ///
+    /// ### Arguments
+    /// * snapshot - The hashes of the pool config and each reserve's config
+    pub fn config_snapshot(e: &Env, snapshot: ConfigSnapshot) {
+        let topics = (Symbol::new(e, topics::CONFIG_SNAPSHOT),);
+        e.events().publish(topics, snapshot);
+    }
+
+    /// Emitted when a reserve is retired
+    ///
+    /// - topics - `["retire_reserve", admin: Address]`
+    /// - data - `[asset: Address, index: u32]`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * asset - The asset of the retired reserve
+    /// * index - The reserve index, which is never reused
+    pub fn retire_reserve(e: &Env, admin: Address, asset: Address, index: u32) {
+        let topics = (Symbol::new(e, topics::RETIRE_RESERVE), admin);
+        e.events().publish(topics, RetireReserveEvent(asset, index));
+    }
+
+    /// Emitted when pool status is updated (non-admin)
+    ///
+    /// - topics - `["set_status"]`
+    /// - data - `new_status: PoolStatus`
+    ///
+    /// ### Arguments
+    /// * new_status - The new pool status
+    pub fn set_status(e: &Env, new_status: u32) {
+        let topics = (Symbol::new(e, topics::SET_STATUS),);
+        e.events().publish(topics, new_status);
+    }
+
+    /// Emitted when the utilization circuit breaker puts the pool on-ice, or recovers the pool once
+    /// utilization is back under the critical thresholds
+    ///
+    /// - topics - `["util_breaker"]`
+    /// - data - `[tripped: bool, new_status: u32]`
+    ///
+    /// ### Arguments
+    /// * tripped - If the breaker was tripped, or false if the pool recovered
+    /// * new_status - The pool status after the breaker was evaluated
+    pub fn util_breaker(e: &Env, tripped: bool, new_status: u32) {
+        let topics = (Symbol::new(e, topics::UTIL_BREAKER),);
+        e.events()
+            .publish(topics, UtilBreakerEvent(tripped, new_status));
+    }
+
+    /// Emitted when the pool's utilization circuit breaker is updated
+    ///
+    /// - topics - `["set_util_breaker", admin: Address]`
+    /// - data - `util_breaker: Option<UtilBreaker>`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * util_breaker - The new utilization circuit breaker, or None if it was removed
+    pub fn set_util_breaker(e: &Env, admin: Address, util_breaker: Option<UtilBreaker>) {
+        let topics = (Symbol::new(e, topics::SET_UTIL_BREAKER), admin);
+        e.events().publish(topics, util_breaker);
+    }
+
+    /// Emitted when the backstop token price check is updated
+    ///
+    /// - topics - `["set_backstop_price_check", admin: Address]`
+    /// - data - `usdc: Option<Address>`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * usdc - The USDC token of the backstop token, or None if the check was disabled
+    pub fn set_backstop_price_check(e: &Env, admin: Address, usdc: Option<Address>) {
+        let topics = (Symbol::new(e, topics::SET_BACKSTOP_PRICE_CHECK), admin);
+        e.events().publish(topics, usdc);
+    }
+
+    /// Emitted when the creation of new auctions is paused or unpaused
+    ///
+    /// - topics - `["set_auctions_paused", admin: Address]`
+    /// - data - `paused: bool`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * paused - If the creation of new auctions is paused
+    pub fn set_auctions_paused(e: &Env, admin: Address, paused: bool) {
+        let topics = (Symbol::new(e, topics::SET_AUCTIONS_PAUSED), admin);
+        e.events().publish(topics, paused);
+    }
+
+    /// Emitted when the commit-reveal fill config is updated
+    ///
+    /// - topics - `["set_fill_commit_config", admin: Address]`
+    /// - data - `config: Option<FillCommitConfig>`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * config - The new commit-reveal fill config, or None if commit-reveal fills were disabled
+    pub fn set_fill_commit_config(e: &Env, admin: Address, config: Option<FillCommitConfig>) {
+        let topics = (Symbol::new(e, topics::SET_FILL_COMMIT_CONFIG), admin);
+        e.events().publish(topics, config);
+    }
+
+    /// Emitted when the claim converter is updated
+    ///
+    /// - topics - `["set_claim_converter", admin: Address]`
+    /// - data - `converter: Option<Address>`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * converter - The claim converter, or None if converted claims were disabled
+    pub fn set_claim_converter(e: &Env, admin: Address, converter: Option<Address>) {
+        let topics = (Symbol::new(e, topics::SET_CLAIM_CONVERTER), admin);
+        e.events().publish(topics, converter);
+    }
+
+    /// Emitted when pool status is updated by admin
+    ///
+    /// - topics - `["set_status", admin: Address]`
+    /// - data - `pool_status: PoolStatus`
+    ///
+    /// ### Arguments
+    /// * admin - The admin setting the pool status
+    /// * pool_status - The new pool status
+    pub fn set_status_admin(e: &Env, admin: Address, pool_status: u32) {
+        let topics = (Symbol::new(e, topics::SET_STATUS), admin);
+        e.events().publish(topics, pool_status);
+    }
+
+    /// Emitted when the number of position checkpoints retained per user is updated
+    ///
+    /// - topics - `["set_checkpoint_retention", admin: Address]`
+    /// - data - `retention: u32`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * retention - The new number of checkpoints retained per user
+    pub fn set_checkpoint_retention(e: &Env, admin: Address, retention: u32) {
+        let topics = (Symbol::new(e, topics::SET_CHECKPOINT_RETENTION), admin);
+        e.events().publish(topics, retention);
+    }
+
+    /// Emitted when the number of days of activity counters retained is updated
+    ///
+    /// - topics - `["set_analytics_window", admin: Address]`
+    /// - data - `window: u32`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * window - The new number of days of activity counters retained
+    pub fn set_analytics_window(e: &Env, admin: Address, window: u32) {
+        let topics = (Symbol::new(e, topics::SET_ANALYTICS_WINDOW), admin);
+        e.events().publish(topics, window);
+    }
+
+    /// Emitted when the maximum number of seconds reserves can accrue interest for per ledger is updated
+    ///
+    /// - topics - `["set_max_ledger_time", admin: Address]`
+    /// - data - `max_ledger_time: u32`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * max_ledger_time - The new maximum number of seconds per ledger, or 0 if the guard was disabled
+    pub fn set_max_ledger_time(e: &Env, admin: Address, max_ledger_time: u32) {
+        let topics = (Symbol::new(e, topics::SET_MAX_LEDGER_TIME), admin);
+        e.events().publish(topics, max_ledger_time);
+    }
+
+    /// Emitted when the maximum number of seconds a reserve can accrue interest for in a single accrual is updated
+    ///
+    /// - topics - `["set_max_accrual_period", admin: Address]`
+    /// - data - `max_accrual_period: u32`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * max_accrual_period - The new maximum number of seconds per accrual, or 0 if the cap was disabled
+    pub fn set_max_accrual_period(e: &Env, admin: Address, max_accrual_period: u32) {
+        let topics = (Symbol::new(e, topics::SET_MAX_ACCRUAL_PERIOD), admin);
+        e.events().publish(topics, max_accrual_period);
+    }
+
+    /// Emitted when the number of ledgers collateral factor decreases are ramped over is updated
+    ///
+    /// - topics - `["set_c_factor_ramp", admin: Address]`
+    /// - data - `ledgers: u32`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * ledgers - The new number of ledgers to ramp over, or 0 if ramping is disabled
+    pub fn set_c_factor_ramp(e: &Env, admin: Address, ledgers: u32) {
+        let topics = (Symbol::new(e, topics::SET_C_FACTOR_RAMP), admin);
+        e.events().publish(topics, ledgers);
+    }
+
+    /// Emitted when the number of ledgers an admin freeze expires after is updated
+    ///
+    /// - topics - `["set_freeze_expiry", admin: Address]`
+    /// - data - `ledgers: u32`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * ledgers - The new number of ledgers an admin freeze expires after, or 0 if freezes do not expire
+    pub fn set_freeze_expiry(e: &Env, admin: Address, ledgers: u32) {
+        let topics = (Symbol::new(e, topics::SET_FREEZE_EXPIRY), admin);
+        e.events().publish(topics, ledgers);
+    }
+
+    /// Emitted when the health factor buffer required of new borrows is updated
+    ///
+    /// - topics - `["set_borrow_buffer", admin: Address]`
+    /// - data - `buffer: u32`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * buffer - The new health factor buffer in 7 decimals, or 0 if disabled
+    pub fn set_borrow_buffer(e: &Env, admin: Address, buffer: u32) {
+        let topics = (Symbol::new(e, topics::SET_BORROW_BUFFER), admin);
+        e.events().publish(topics, buffer);
+    }
+
+    /// Emitted when the fee mode applied while the pool is frozen is updated
+    ///
+    /// - topics - `["set_frozen_fees", admin: Address]`
+    /// - data - `mode: u32`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * mode - The new frozen fee mode
+    pub fn set_frozen_fees(e: &Env, admin: Address, mode: u32) {
+        let topics = (Symbol::new(e, topics::SET_FROZEN_FEES), admin);
+        e.events().publish(topics, mode);
+    }
+
+    /// Emitted when the fee charged on collateral withdrawals while the pool is on-ice or frozen is updated
+    ///
+    /// - topics - `["set_withdraw_fee", admin: Address]`
+    /// - data - `fee: WithdrawFee`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * fee - The new withdrawal fee
+    pub fn set_withdraw_fee(e: &Env, admin: Address, fee: WithdrawFee) {
+        let topics = (Symbol::new(e, topics::SET_WITHDRAW_FEE), admin);
+        e.events().publish(topics, fee);
+    }
+
+    /// Emitted when the rebate paid to keepers is updated
+    ///
+    /// - topics - `["set_keeper_config", admin: Address]`
+    /// - data - `config: KeeperConfig`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * config - The new keeper rebate config
+    pub fn set_keeper_config(e: &Env, admin: Address, config: KeeperConfig) {
+        let topics = (Symbol::new(e, topics::SET_KEEPER_CONFIG), admin);
+        e.events().publish(topics, config);
+    }
+
+    /// Emitted when an address is registered or unregistered as a keeper
+    ///
+    /// - topics - `["set_keeper", admin: Address, keeper: Address]`
+    /// - data - `registered: bool`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * keeper - The address of the keeper
+    /// * registered - If the address is registered as a keeper
+    pub fn set_keeper(e: &Env, admin: Address, keeper: Address, registered: bool) {
+        let topics = (Symbol::new(e, topics::SET_KEEPER), admin, keeper);
+        e.events().publish(topics, registered);
+    }
+
+    /// Emitted when the keeper pot is funded
+    ///
+    /// - topics - `["fund_keeper_pot", from: Address]`
+    /// - data - `amount: i128`
+    ///
+    /// ### Arguments
+    /// * from - The address funding the pot
+    /// * amount - The amount of tokens added to the pot
+    pub fn fund_keeper_pot(e: &Env, from: Address, amount: i128) {
+        let topics = (Symbol::new(e, topics::FUND_KEEPER_POT), from);
+        e.events().publish(topics, amount);
+    }
+
+    /// Emitted when tokens are withdrawn from the keeper pot
+    ///
+    /// - topics - `["withdraw_keeper_pot", admin: Address]`
+    /// - data - `amount: i128`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * amount - The amount of tokens withdrawn from the pot
+    pub fn withdraw_keeper_pot(e: &Env, admin: Address, amount: i128) {
+        let topics = (Symbol::new(e, topics::WITHDRAW_KEEPER_POT), admin);
+        e.events().publish(topics, amount);
+    }
+
+    /// Emitted when a keeper is paid a rebate for performing a maintenance task
+    ///
+    /// - topics - `["keeper_rebate", keeper: Address]`
+    /// - data - `[token: Address, amount: i128]`
+    ///
+    /// ### Arguments
+    /// * keeper - The address of the keeper
+    /// * token - The token the rebate was paid in
+    /// * amount - The amount of tokens paid
+    pub fn keeper_rebate(e: &Env, keeper: Address, token: Address, amount: i128) {
+        let topics = (Symbol::new(e, topics::KEEPER_REBATE), keeper);
+        e.events().publish(topics, KeeperRebateEvent(token, amount));
+    }
+
+    /// Emitted when an address is added to or removed from the blocklist
+    ///
+    /// - topics - `["set_blocked", admin: Address, user: Address]`
+    /// - data - `blocked: bool`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * user - The address
+    /// * blocked - If the address is blocked from depositing into the pool
+    pub fn set_blocked(e: &Env, admin: Address, user: Address, blocked: bool) {
+        let topics = (Symbol::new(e, topics::SET_BLOCKED), admin, user);
+        e.events().publish(topics, blocked);
+    }
+
+    /// Emitted when tokens sent to the pool by mistake are rescued
+    ///
+    /// - topics - `["rescue", admin: Address, asset: Address]`
+    /// - data - `[to: Address, amount: i128]`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * asset - The token rescued
+    /// * to - The address receiving the tokens
+    /// * amount - The amount of tokens rescued
+    pub fn rescue(e: &Env, admin: Address, asset: Address, to: Address, amount: i128) {
+        let topics = (Symbol::new(e, topics::RESCUE), admin, asset);
+        e.events().publish(topics, RescueEvent(to, amount));
+    }
+
+    /// Emitted when the credit line extended to a reserve is set or removed
+    ///
+    /// - topics - `["set_credit_line", admin: Address, asset: Address]`
+    /// - data - `[lender: Address, cap: i128]`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * asset - The reserve asset
+    /// * lender - The address lending to the reserve
+    /// * cap - The maximum amount that can be drawn from the line
+    pub fn set_credit_line(e: &Env, admin: Address, asset: Address, lender: Address, cap: i128) {
+        let topics = (Symbol::new(e, topics::SET_CREDIT_LINE), admin, asset);
+        e.events().publish(topics, SetCreditLineEvent(lender, cap));
+    }
+
+    /// Emitted when the stable rate borrowing config of a reserve is set or removed
+    ///
+    /// - topics - `["set_stable_rate", admin: Address, asset: Address]`
+    /// - data - `config: Option<StableRateConfig>`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * asset - The reserve asset
+    /// * config - The stable rate borrowing config, or None if stable rate borrowing is disabled
+    pub fn set_stable_rate(
+        e: &Env,
+        admin: Address,
+        asset: Address,
+        config: Option<StableRateConfig>,
+    ) {
+        let topics = (Symbol::new(e, topics::SET_STABLE_RATE), admin, asset);
+        e.events().publish(topics, config);
+    }
+
+    /// Emitted when the borrower index is enabled or disabled
+    ///
+    /// - topics - `["set_borrower_index", admin: Address]`
+    /// - data - `borrower_index: bool`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * borrower_index - If addresses with liabilities are recorded in the borrower index
+    pub fn set_borrower_index(e: &Env, admin: Address, borrower_index: bool) {
+        let topics = (Symbol::new(e, topics::SET_BORROWER_INDEX), admin);
+        e.events().publish(topics, borrower_index);
+    }
+
+    /// Emitted when the backstop's exemption from the maximum positions is enabled or disabled
+    ///
+    /// - topics - `["set_backstop_positions_exempt", admin: Address]`
+    /// - data - `exempt: bool`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * exempt - If the backstop is exempt from the pool's maximum positions
+    pub fn set_backstop_positions_exempt(e: &Env, admin: Address, exempt: bool) {
+        let topics = (Symbol::new(e, topics::SET_BACKSTOP_POSITIONS_EXEMPT), admin);
+        e.events().publish(topics, exempt);
+    }
+
+    /// Emitted when the pool's risk premium is updated
+    ///
+    /// - topics - `["set_risk_premium", admin: Address]`
+    /// - data - `risk_premium: Option<RiskPremium>`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * risk_premium - The new risk premium, or None if it was removed
+    pub fn set_risk_premium(e: &Env, admin: Address, risk_premium: Option<RiskPremium>) {
+        let topics = (Symbol::new(e, topics::SET_RISK_PREMIUM), admin);
+        e.events().publish(topics, risk_premium);
+    }
+
+    /// Emitted when the pool's liquidation protection insurance is updated
+    ///
+    /// - topics - `["set_insurance_config", admin: Address]`
+    /// - data - `config: Option<InsuranceConfig>`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * config - The new insurance config, or None if insurance was disabled
+    pub fn set_insurance_config(e: &Env, admin: Address, config: Option<InsuranceConfig>) {
+        let topics = (Symbol::new(e, topics::SET_INSURANCE_CONFIG), admin);
+        e.events().publish(topics, config);
+    }
+
+    /// Emitted when the swap adapter for a quote asset is updated
+    ///
+    /// - topics - `["set_quote_adapter", admin: Address]`
+    /// - data - `[quote_asset: Address, adapter: Option<Address>]`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * quote_asset - The quote asset
+    /// * adapter - The new swap adapter, or None if the quote asset was removed from the whitelist
+    pub fn set_quote_adapter(
+        e: &Env,
+        admin: Address,
+        quote_asset: Address,
+        adapter: Option<Address>,
+    ) {
+        let topics = (Symbol::new(e, topics::SET_QUOTE_ADAPTER), admin);
+        let data: SetQuoteAdapterEvent = (quote_asset, adapter);
+        e.events().publish(topics, data);
+    }
+
+    /// Emitted when the rate strategy a reserve's borrow rate is delegated to is updated
+    ///
+    /// - topics - `["set_rate_strategy", admin: Address]`
+    /// - data - `[asset: Address, strategy: Option<Address>]`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * asset - The underlying asset of the reserve
+    /// * strategy - The new rate strategy, or None if the reserve uses the built-in curve
+    pub fn set_rate_strategy(e: &Env, admin: Address, asset: Address, strategy: Option<Address>) {
+        let topics = (Symbol::new(e, topics::SET_RATE_STRATEGY), admin);
+        let data: SetRateStrategyEvent = (asset, strategy);
+        e.events().publish(topics, data);
+    }
+
+    /// Emitted when the max annual borrow rate for a reserve is updated
+    ///
+    /// - topics - `["set_max_apr", admin: Address]`
+    /// - data - `[asset: Address, max_apr: Option<u32>]`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * asset - The underlying asset of the reserve
+    /// * max_apr - The new max annual borrow rate, or None if it was removed
+    pub fn set_max_apr(e: &Env, admin: Address, asset: Address, max_apr: Option<u32>) {
+        let topics = (Symbol::new(e, topics::SET_MAX_APR), admin);
+        let data: SetMaxAprEvent = (asset, max_apr);
+        e.events().publish(topics, data);
+    }
+
+    /// Emitted when a reserve's borrow rate is over its max APR and gets capped while accruing interest
+    ///
+    /// - topics - `["borrow_rate_capped", asset: Address]`
+    /// - data - `[borrow_rate: i128, capped_rate: i128]`
+    ///
+    /// ### Arguments
+    /// * asset - The underlying asset of the reserve
+    /// * borrow_rate - The annual borrow rate before the cap
+    /// * capped_rate - The annual borrow rate interest accrued at
+    pub fn borrow_rate_capped(e: &Env, asset: Address, borrow_rate: i128, capped_rate: i128) {
+        let topics = (Symbol::new(e, topics::BORROW_RATE_CAPPED), asset);
+        e.events()
+            .publish(topics, BorrowRateCappedEvent(borrow_rate, capped_rate));
+    }
+
+    /// Emitted when reserve emissions are updated
+    ///
+    /// - topics - `["reserve_emission_update"]`
+    /// - data - `[res_token_id: u32, eps: u64, expiration: u64]`
+    ///
+    /// ### Arguments
+    /// * res_token_id - The reserve token ID
+    /// * eps - The new emissions per second
+    /// * expiration - The new expiration time
+    pub fn reserve_emission_update(e: &Env, res_token_id: u32, eps: u64, expiration: u64) {
+        let topics = (Symbol::new(e, topics::RESERVE_EMISSION_UPDATE),);
+        e.events().publish(
+            topics,
+            ReserveEmissionUpdateEvent(res_token_id, eps, expiration),
+        );
+    }
+
+    /// Emitted when the emission share of a single reserve token is adjusted
+    ///
+    /// - topics - `["adjust_emission_share", admin: Address]`
+    /// - data - `[res_token_id: u32, new_share: u64]`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * res_token_id - The reserve token ID
+    /// * new_share - The new share of the reserve token, or 0 if it was removed
+    pub fn adjust_emission_share(e: &Env, admin: Address, res_token_id: u32, new_share: u64) {
+        let topics = (Symbol::new(e, topics::ADJUST_EMISSION_SHARE), admin);
+        e.events()
+            .publish(topics, AdjustEmissionShareEvent(res_token_id, new_share));
+    }
+
+    /// Emitted when the emission decay of a single reserve token is set
+    ///
+    /// - topics - `["set_emission_decay", admin: Address]`
+    /// - data - `[res_token_id: u32, factor: u32]`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * res_token_id - The reserve token ID
+    /// * factor - The fraction of the share kept each epoch, or 0 if the decay was removed
+    pub fn set_emission_decay(e: &Env, admin: Address, res_token_id: u32, factor: u32) {
+        let topics = (Symbol::new(e, topics::SET_EMISSION_DECAY), admin);
+        e.events()
+            .publish(topics, SetEmissionDecayEvent(res_token_id, factor));
+    }
+
+    /// Emitted when emissions are gulped
+    ///
+    /// - topics - `["gulp_emissions"]`
+    /// - data - `emissions: i128`
+    ///
+    /// ### Arguments
+    /// * emissions - The amount of emissions gulped
+    pub fn gulp_emissions(e: &Env, emissions: i128) {
+        let topics = (Symbol::new(e, topics::GULP_EMISSIONS),);
+        e.events().publish(topics, emissions);
+    }
+
+    /// Emitted when auto gulping emissions is enabled or disabled
+    ///
+    /// - topics - `["set_auto_gulp", admin: Address]`
+    /// - data - `auto_gulp: bool`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * auto_gulp - If emissions are gulped automatically
+    pub fn set_auto_gulp(e: &Env, admin: Address, auto_gulp: bool) {
+        let topics = (Symbol::new(e, topics::SET_AUTO_GULP), admin);
+        e.events().publish(topics, auto_gulp);
+    }
+
+    /// Emitted when emissions are claimed
+    ///
+    /// - topics - `["claim", from: Address]`
+    /// - data - `[reserve_token_ids: Vec<u32>, amount_claimed: i128]`
+    ///
+    /// ### Arguments
+    /// * from - The address claiming the emissions
+    /// * reserve_token_ids - The reserve token IDs claimed
+    /// * amount_claimed - The amount claimed
+    pub fn claim(e: &Env, from: Address, reserve_token_ids: Vec<u32>, amount_claimed: i128) {
+        let topics = (Symbol::new(e, topics::CLAIM), from);
+        e.events()
+            .publish(topics, ClaimEvent(reserve_token_ids, amount_claimed));
+    }
+
+    /// Emitted when claimed emissions are converted through the claim converter
+    ///
+    /// - topics - `["claim_converted", from: Address, converter: Address]`
+    /// - data - `[amount_in: i128, amount_out: i128]`
+    ///
+    /// ### Arguments
+    /// * from - The address claiming the emissions
+    /// * converter - The claim converter
+    /// * amount_in - The amount of BLND converted
+    /// * amount_out - The amount of the converted asset received
+    pub fn claim_converted(
+        e: &Env,
+        from: Address,
+        converter: Address,
+        amount_in: i128,
+        amount_out: i128,
+    ) {
+        let topics = (Symbol::new(e, topics::CLAIM_CONVERTED), from, converter);
+        e.events()
+            .publish(topics, ClaimConvertedEvent(amount_in, amount_out));
+    }
+
+    /// Emitted when bad debt is recorded
+    ///
+    /// - topics - `["bad_debt", user: Address, asset: Address]`
+    /// - data - `[d_tokens: i128]`
+    ///
+    /// ### Arguments
+    /// * user - The user with bad debt
+    /// * asset - The asset with bad debt
+    /// * d_tokens - The amount of bad debt
+    pub fn bad_debt(e: &Env, user: Address, asset: Address, d_tokens: i128) {
+        let topics = (Symbol::new(e, topics::BAD_DEBT), user, asset);
+        e.events().publish(topics, d_tokens);
+    }
+
+    /// Emitted when bad debt is defaulted
+    ///
+    /// - topics - `["defaulted_debt", asset: Address]`
+    /// - data - `[d_tokens_burnt: i128]`
+    ///
+    /// ### Arguments
+    /// * asset - The asset with defaulted debt
+    /// * d_tokens_burnt - The amount of defaulted d_tokens
+    pub fn defaulted_debt(e: &Env, asset: Address, d_tokens_burnt: i128) {
+        let topics = (Symbol::new(e, topics::DEFAULTED_DEBT), asset);
+        e.events().publish(topics, d_tokens_burnt);
+    }
+
+    /// Emitted when bad debt in a reserve is recovered
+    ///
+    /// - topics - `["repay_deficit", asset: Address, from: Address]`
+    /// - data - `[to_suppliers: i128, to_backstop: i128]`
+    ///
+    /// ### Arguments
+    /// * asset - The asset with recovered bad debt
+    /// * from - The address the recovered tokens were sent from
+    /// * to_suppliers - The amount of tokens returned to the reserve's suppliers
+    /// * to_backstop - The amount of tokens credited to the backstop
+    pub fn repay_deficit(
+        e: &Env,
+        asset: Address,
+        from: Address,
+        to_suppliers: i128,
+        to_backstop: i128,
+    ) {
+        let topics = (Symbol::new(e, topics::REPAY_DEFICIT), asset, from);
+        e.events()
+            .publish(topics, RepayDeficitEvent(to_suppliers, to_backstop));
+    }
+
+    /// Emitted when tokens are supplied
+    ///
+    /// - topics - `["supply", asset: Address, from: Address]`
+    /// - data - `[tokens_in: i128, b_tokens_minted: i128]`
+    ///
+    /// ### Arguments
+    /// * asset - The asset
+    /// * from - The address whose position is being modified
+    /// * tokens_in - The amount of tokens sent to the pool
+    /// * b_tokens_minted - The amount of b_tokens minted
+    pub fn supply(e: &Env, asset: Address, from: Address, tokens_in: i128, b_tokens_minted: i128) {
+        let topics = (Symbol::new(e, topics::SUPPLY), asset, from);
+        e.events()
+            .publish(topics, SupplyEvent(tokens_in, b_tokens_minted));
+    }
+
+    /// Emitted when tokens are withdrawn
+    ///
+    /// - topics - `["withdraw", asset: Address, from: Address]`
+    /// - data - `[tokens_out: i128, b_tokens_burnt: i128]`
+    ///
+    /// ### Arguments
+    /// * asset - The asset
+    /// * from - The address whose position is being modified
+    /// * tokens_out - The amount of tokens withdrawn from the pool
+    /// * b_tokens_burnt - The amount of b_tokens burnt
+    pub fn withdraw(
+        e: &Env,
+        asset: Address,
+        from: Address,
+        tokens_out: i128,
+        b_tokens_burnt: i128,
+    ) {
+        let topics = (Symbol::new(e, topics::WITHDRAW), asset, from);
+        e.events()
+            .publish(topics, WithdrawEvent(tokens_out, b_tokens_burnt));
+    }
+
+    /// Emitted when a withdrawal is queued until the reserve has enough liquidity
+    ///
+    /// - topics - `["queue_withdrawal", asset: Address, from: Address]`
+    /// - data - `b_tokens_queued: i128`
+    ///
+    /// ### Arguments
+    /// * asset - The asset
+    /// * from - The address whose position is being modified
+    /// * b_tokens_queued - The amount of b_tokens added to the queued withdrawal
+    pub fn queue_withdrawal(e: &Env, asset: Address, from: Address, b_tokens_queued: i128) {
+        let topics = (Symbol::new(e, topics::QUEUE_WITHDRAWAL), asset, from);
+        e.events().publish(topics, b_tokens_queued);
+    }
+
+    /// Emitted when a reserve draws from its credit line to honor withdrawals
+    ///
+    /// - topics - `["credit_line_draw", asset: Address, lender: Address]`
+    /// - data - `[tokens_in: i128, b_tokens_minted: i128]`
+    ///
+    /// ### Arguments
+    /// * asset - The asset
+    /// * lender - The address lending to the reserve
+    /// * tokens_in - The amount of tokens drawn from the lender
+    /// * b_tokens_minted - The amount of b_tokens minted to the line
+    pub fn credit_line_draw(
+        e: &Env,
+        asset: Address,
+        lender: Address,
+        tokens_in: i128,
+        b_tokens_minted: i128,
+    ) {
+        let topics = (Symbol::new(e, topics::CREDIT_LINE_DRAW), asset, lender);
+        e.events()
+            .publish(topics, CreditLineDrawEvent(tokens_in, b_tokens_minted));
+    }
+
+    /// Emitted when a reserve repays its credit line from inflows
+    ///
+    /// - topics - `["credit_line_repay", asset: Address, lender: Address]`
+    /// - data - `[tokens_out: i128, b_tokens_burnt: i128]`
+    ///
+    /// ### Arguments
+    /// * asset - The asset
+    /// * lender - The address lending to the reserve
+    /// * tokens_out - The amount of tokens repaid to the lender
+    /// * b_tokens_burnt - The amount of b_tokens burnt from the line
+    pub fn credit_line_repay(
+        e: &Env,
+        asset: Address,
+        lender: Address,
+        tokens_out: i128,
+        b_tokens_burnt: i128,
+    ) {
+        let topics = (Symbol::new(e, topics::CREDIT_LINE_REPAY), asset, lender);
+        e.events()
+            .publish(topics, CreditLineRepayEvent(tokens_out, b_tokens_burnt));
+    }
+
+    /// Emitted when a queued withdrawal is claimed
+    ///
+    /// - topics - `["claim_withdrawal", asset: Address, from: Address]`
+    /// - data - `[tokens_out: i128, b_tokens_burnt: i128]`
+    ///
+    /// ### Arguments
+    /// * asset - The asset
+    /// * from - The address whose position is being modified
+    /// * tokens_out - The amount of tokens withdrawn from the pool
+    /// * b_tokens_burnt - The amount of b_tokens burnt
+    pub fn claim_withdrawal(
+        e: &Env,
+        asset: Address,
+        from: Address,
+        tokens_out: i128,
+        b_tokens_burnt: i128,
+    ) {
+        let topics = (Symbol::new(e, topics::CLAIM_WITHDRAWAL), asset, from);
+        e.events()
+            .publish(topics, WithdrawEvent(tokens_out, b_tokens_burnt));
+    }
+
+    /// Emitted when collateral is supplied
+    ///
+    /// - topics - `["supply_collateral", asset: Address, from: Address]`
+    /// - data - `[tokens_in: i128, b_tokens_minted: i128]`
+    ///
+    /// ### Arguments
+    /// * asset - The asset
+    /// * from - The address whose position is being modified
+    /// * tokens_in - The amount of tokens sent to the pool
+    /// * b_tokens_minted - The amount of b_tokens minted
+    pub fn supply_collateral(
+        e: &Env,
+        asset: Address,
+        from: Address,
+        tokens_in: i128,
+        b_tokens_minted: i128,
+    ) {
+        let topics = (Symbol::new(e, topics::SUPPLY_COLLATERAL), asset, from);
+        e.events()
+            .publish(topics, SupplyEvent(tokens_in, b_tokens_minted));
+    }
+
+    /// Emitted when collateral is withdrawn
+    ///
+    /// - topics - `["withdraw_collateral", asset: Address, from: Address]`
+    /// - data - `[tokens_out: i128, b_tokens_burnt: i128]`
+    ///
+    /// ### Arguments
+    /// * asset - The asset
+    /// * from - The address whose position is being modified
+    /// * tokens_out - The amount of tokens withdrawn from the pool
+    /// * b_tokens_burnt - The amount of b_tokens burnt
+    pub fn withdraw_collateral(
+        e: &Env,
+        asset: Address,
+        from: Address,
+        tokens_out: i128,
+        b_tokens_burnt: i128,
+    ) {
+        let topics = (Symbol::new(e, topics::WITHDRAW_COLLATERAL), asset, from);
+        e.events()
+            .publish(topics, WithdrawEvent(tokens_out, b_tokens_burnt));
+    }
+
+    /// Emitted when tokens are borrowed
+    ///
+    /// - topics - `["borrow", asset: Address, from: Address]`
+    /// - data - `[tokens_out: i128, d_tokens_minted: i128]`
+    ///
+    /// ### Arguments
+    /// * asset - The asset
+    /// * from - The address whose position is being modified
+    /// * tokens_out - The amount of tokens sent from the pool
+    /// * d_tokens_burnt - The amount of d_tokens burnt
+    pub fn borrow(e: &Env, asset: Address, from: Address, tokens_out: i128, d_tokens_minted: i128) {
+        let topics = (Symbol::new(e, topics::BORROW), asset, from);
+        e.events()
+            .publish(topics, BorrowEvent(tokens_out, d_tokens_minted));
+    }
+
+    /// Emitted when tokens are borrowed at a stable rate
+    ///
+    /// - topics - `["borrow_stable", asset: Address, from: Address]`
+    /// - data - `[tokens_out: i128, d_tokens_minted: i128, rate: u32]`
+    ///
+    /// ### Arguments
+    /// * asset - The asset
+    /// * from - The address whose position is being modified
+    /// * tokens_out - The amount of tokens sent from the pool
+    /// * d_tokens_minted - The amount of d_tokens minted
+    /// * rate - The stable rate the user's stable liabilities now accrue at
+    pub fn borrow_stable(
+        e: &Env,
+        asset: Address,
+        from: Address,
+        tokens_out: i128,
+        d_tokens_minted: i128,
+        rate: u32,
+    ) {
+        let topics = (Symbol::new(e, topics::BORROW_STABLE), asset, from);
+        e.events()
+            .publish(topics, BorrowStableEvent(tokens_out, d_tokens_minted, rate));
+    }
+
+    /// Emitted when a user's stable rate liabilities are swapped to the variable rate
+    ///
+    /// - topics - `["swap_to_variable", asset: Address, from: Address]`
+    /// - data - `d_tokens: i128`
+    ///
+    /// ### Arguments
+    /// * asset - The asset
+    /// * from - The address whose position is being modified
+    /// * d_tokens - The amount of d_tokens swapped to the variable rate
+    pub fn swap_to_variable(e: &Env, asset: Address, from: Address, d_tokens: i128) {
+        let topics = (Symbol::new(e, topics::SWAP_TO_VARIABLE), asset, from);
+        e.events().publish(topics, d_tokens);
+    }
+
+    /// Emitted when a user's stable rate is rebalanced to the reserve's current rates
+    ///
+    /// - topics - `["rebalance_stable_rate", asset: Address, user: Address]`
+    /// - data - `rate: u32`
+    ///
+    /// ### Arguments
+    /// * asset - The asset
+    /// * user - The user whose stable rate was rebalanced
+    /// * rate - The new stable rate
+    pub fn rebalance_stable_rate(e: &Env, asset: Address, user: Address, rate: u32) {
+        let topics = (Symbol::new(e, topics::REBALANCE_STABLE_RATE), asset, user);
+        e.events().publish(topics, rate);
+    }
+
+    /// Emitted when a loan is repaid
+    ///
+    /// - topics - `["repay", asset: Address, from: Address]`
+    /// - data - `[tokens_in: i128, d_tokens_burnt: i128]`
+    ///
+    /// ### Arguments
+    /// * asset - The asset
+    /// * from - The address whose position is being modified
+    /// * tokens_in - The amount of tokens sent to the pool
+    /// * d_tokens_burnt - The amount of d_tokens burnt
+    pub fn repay(e: &Env, asset: Address, from: Address, tokens_in: i128, d_tokens_burnt: i128) {
+        let topics = (Symbol::new(e, topics::REPAY), asset, from);
+        e.events()
+            .publish(topics, RepayEvent(tokens_in, d_tokens_burnt));
+    }
+
+    /// Emitted when a risk premium is charged on a user's liabilities
+    ///
+    /// - topics - `["risk_premium", asset: Address, user: Address]`
+    /// - data - `[tokens_charged: i128, d_tokens_minted: i128]`
+    ///
+    /// ### Arguments
+    /// * asset - The reserve asset
+    /// * user - The user charged the premium
+    /// * tokens_charged - The number of tokens added to the user's liabilities
+    /// * d_tokens_minted - The number of dTokens minted to the user
+    pub fn risk_premium(
+        e: &Env,
+        asset: Address,
+        user: Address,
+        tokens_charged: i128,
+        d_tokens_minted: i128,
+    ) {
+        let topics = (Symbol::new(e, topics::RISK_PREMIUM), asset, user);
+        e.events()
+            .publish(topics, RiskPremiumEvent(tokens_charged, d_tokens_minted));
+    }
+
+    /// Emitted when the insurance premium is charged on an insured user's liabilities
+    ///
+    /// - topics - `["insurance_premium", asset: Address, user: Address]`
+    /// - data - `[tokens_charged: i128, d_tokens_minted: i128]`
+    ///
+    /// ### Arguments
+    /// * asset - The reserve asset
+    /// * user - The user charged the premium
+    /// * tokens_charged - The number of tokens added to the user's liabilities and credited to the backstop
+    /// * d_tokens_minted - The number of dTokens minted to the user
+    pub fn insurance_premium(
+        e: &Env,
+        asset: Address,
+        user: Address,
+        tokens_charged: i128,
+        d_tokens_minted: i128,
+    ) {
+        let topics = (Symbol::new(e, topics::INSURANCE_PREMIUM), asset, user);
+        e.events().publish(
+            topics,
+            InsurancePremiumEvent(tokens_charged, d_tokens_minted),
+        );
+    }
+
+    /// Emitted when a delegator approves a delegatee to borrow against their positions
+    ///
+    /// - topics - `["approve_borrow", delegator: Address, delegatee: Address]`
+    /// - data - `[asset: Address, amount: i128]`
+    ///
+    /// ### Arguments
+    /// * delegator - The address whose positions back the borrow
+    /// * delegatee - The address allowed to borrow
+    /// * asset - The reserve asset
+    /// * amount - The new allowance
+    pub fn approve_borrow(
+        e: &Env,
+        delegator: Address,
+        delegatee: Address,
+        asset: Address,
+        amount: i128,
+    ) {
+        let topics = (Symbol::new(e, topics::APPROVE_BORROW), delegator, delegatee);
+        e.events()
+            .publish(topics, ApproveBorrowEvent(asset, amount));
+    }
+
+    /// Emitted during a flash loan
+    ///
+    /// - topics - `["flash_loan", asset: Address, from: Address]`
+    /// - data - `[tokens_out: i128, d_tokens_minted: i128]`
+    ///
+    /// ### Arguments
+    /// * asset - The asset
+    /// * from - The address whose position is being modified
+    /// * contract - The address of the flash loan contract
+    /// * tokens_out - The amount of tokens sent from the pool
+    /// * d_tokens_burnt - The amount of d_tokens burnt
+    pub fn flash_loan(
+        e: &Env,
+        asset: Address,
+        from: Address,
+        contract: Address,
+        tokens_out: i128,
+        d_tokens_minted: i128,
+    ) {
+        let topics = (Symbol::new(e, topics::FLASH_LOAN), asset, from, contract);
+        e.events()
+            .publish(topics, BorrowEvent(tokens_out, d_tokens_minted));
+    }
+
+    /// Emitted when a reserve gulps excess tokens
+    ///
+    /// - topics - `["gulp", asset: Address]`
+    /// - data - `[token_delta: i128]`
+    ///
+    /// ### Arguments
+    /// * asset - The asset
+    /// * token_delta - The number of tokens gulped
+    pub fn gulp(e: &Env, asset: Address, token_delta: i128) {
+        let topics = (Symbol::new(e, topics::GULP), asset);
+        e.events().publish(topics, token_delta);
+    }
+
+    /// Emitted when a user opts in or out of others supplying to their positions without their authorization
+    ///
+    /// - topics - `["set_supply_on_behalf", user: Address]`
+    /// - data - `allowed: bool`
+    ///
+    /// ### Arguments
+    /// * user - The user
+    /// * allowed - If supplying on behalf of the user is allowed
+    pub fn set_supply_on_behalf(e: &Env, user: Address, allowed: bool) {
+        let topics = (Symbol::new(e, topics::SET_SUPPLY_ON_BEHALF), user);
+        e.events().publish(topics, allowed);
+    }
+
+    /// Emitted when a user enables or disables auto-claiming emissions on submit
+    ///
+    /// - topics - `["set_auto_claim", user: Address]`
+    /// - data - `auto_claim: bool`
+    ///
+    /// ### Arguments
+    /// * user - The user
+    /// * auto_claim - If emissions are claimed automatically when the user submits requests
+    pub fn set_auto_claim(e: &Env, user: Address, auto_claim: bool) {
+        let topics = (Symbol::new(e, topics::SET_AUTO_CLAIM), user);
+        e.events().publish(topics, auto_claim);
+    }
+
+    /// Emitted when a user enables or disables request planning
+    ///
+    /// - topics - `["set_plan_requests", user: Address]`
+    /// - data - `plan_requests: bool`
+    ///
+    /// ### Arguments
+    /// * user - The user
+    /// * plan_requests - If the user's requests are reordered before they are executed
+    pub fn set_plan_requests(e: &Env, user: Address, plan_requests: bool) {
+        let topics = (Symbol::new(e, topics::SET_PLAN_REQUESTS), user);
+        e.events().publish(topics, plan_requests);
+    }
+
+    /// Emitted when a user sets or removes their minimum health factor
+    ///
+    /// - topics - `["set_min_hf", user: Address]`
+    /// - data - `min_hf: u32`
+    ///
+    /// ### Arguments
+    /// * user - The user
+    /// * min_hf - The minimum health factor, or 0 if it was removed
+    pub fn set_min_hf(e: &Env, user: Address, min_hf: u32) {
+        let topics = (Symbol::new(e, topics::SET_MIN_HF), user);
+        e.events().publish(topics, min_hf);
+    }
+
+    /// Emitted when a user sets or removes their liquidation hook
+    ///
+    /// - topics - `["set_liquidation_hook", user: Address]`
+    /// - data - `hook: Option<Address>`
+    ///
+    /// ### Arguments
+    /// * user - The user
+    /// * hook - The liquidation hook contract, or None if it was removed
+    pub fn set_liquidation_hook(e: &Env, user: Address, hook: Option<Address>) {
+        let topics = (Symbol::new(e, topics::SET_LIQUIDATION_HOOK), user);
+        e.events().publish(topics, hook);
+    }
+
+    /// Emitted when a user opts in or out of liquidation protection insurance
+    ///
+    /// - topics - `["set_insured", user: Address]`
+    /// - data - `insured: bool`
+    ///
+    /// ### Arguments
+    /// * user - The user
+    /// * insured - If the user is insured
+    pub fn set_insured(e: &Env, user: Address, insured: bool) {
+        let topics = (Symbol::new(e, topics::SET_INSURED), user);
+        e.events().publish(topics, insured);
+    }
+
+    /// Emitted when depositing interest auction bids into the backstop is enabled or disabled
+    ///
+    /// - topics - `["set_interest_deposit", admin: Address]`
+    /// - data - `interest_deposit: bool`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * interest_deposit - If interest auction bids are deposited into the backstop
+    pub fn set_interest_deposit(e: &Env, admin: Address, interest_deposit: bool) {
+        let topics = (Symbol::new(e, topics::SET_INTEREST_DEPOSIT), admin);
+        e.events().publish(topics, interest_deposit);
+    }
+
+    /// Emitted when a new auction is created
+    ///
+    /// - topics - `["new_auction", auction_type: u32, user: Address]`
+    /// - data - `[percent: u32, auction_data: AuctionData]`
+    ///
+    /// ### Arguments
+    /// * auction_type - The type of auction
+    /// * user - The auction user
+    /// * percent - The percent of assets auctioned off
+    /// * auction_data - The auction data
+    pub fn new_auction(
+        e: &Env,
+        auction_type: u32,
+        user: Address,
+        percent: u32,
+        auction_data: AuctionData,
+    ) {
+        let topics = (Symbol::new(e, topics::NEW_AUCTION), auction_type, user);
+        e.events()
+            .publish(topics, NewAuctionEvent(percent, auction_data));
+    }
+
+    /// Emitted when a user with a small position is soft liquidated
+    ///
+    /// - topics - `["soft_liquidation", user: Address]`
+    /// - data - `[filler: Address, liquidated: AuctionData]`
+    ///
+    /// ### Arguments
+    /// * user - The user being liquidated
+    /// * filler - The address filling the liquidation
+    /// * liquidated - The positions transferred to the filler, where the bid is dTokens and the lot is bTokens
+    pub fn soft_liquidation(e: &Env, user: Address, filler: Address, liquidated: AuctionData) {
+        let topics = (Symbol::new(e, topics::SOFT_LIQUIDATION), user);
+        e.events()
+            .publish(topics, SoftLiquidationEvent(filler, liquidated));
+    }
+
+    /// Emitted when a user's liquidation hook restores their positions before a liquidation
+    /// auction could be created
+    ///
+    /// - topics - `["liquidation_averted", user: Address]`
+    /// - data - `hook: Address`
+    ///
+    /// ### Arguments
+    /// * user - The user who was going to be liquidated
+    /// * hook - The liquidation hook contract
+    pub fn liquidation_averted(e: &Env, user: Address, hook: Address) {
+        let topics = (Symbol::new(e, topics::LIQUIDATION_AVERTED), user);
+        e.events().publish(topics, hook);
+    }
+
+    /// Emitted when a filler commits to filling a user liquidation auction
+    ///
+    /// - topics - `["commit_fill", filler: Address]`
+    /// - data - `hash: BytesN<32>`
+    ///
+    /// ### Arguments
+    /// * filler - The address committing to the fill
+    /// * hash - The hash of the fill
+    pub fn commit_fill(e: &Env, filler: Address, hash: BytesN<32>) {
+        let topics = (Symbol::new(e, topics::COMMIT_FILL), filler);
+        e.events().publish(topics, hash);
+    }
+
+    /// Emitted when an auction is filled
+    ///
+    /// - topics - `["fill_auction", auction_type: u32, user: Address]`
+    /// - data - `[filler: Address, fill_percent: i128, filled_auction_data: AuctionData]`
+    ///
+    /// ### Arguments
+    /// * auction_type - The type of auction
+    /// * user - The auction user
+    /// * filler - The address of the filler
+    /// * fill_percent - The percentage of the auction filled
+    /// * filled_auction_data - The filled auction data
+    pub fn fill_auction(
+        e: &Env,
+        auction_type: u32,
+        user: Address,
+        filler: Address,
+        fill_percent: i128,
+        filled_auction_data: AuctionData,
+    ) {
+        let topics = (Symbol::new(e, topics::FILL_AUCTION), auction_type, user);
+        e.events().publish(
+            topics,
+            FillAuctionEvent(filler, fill_percent, filled_auction_data),
+        );
+    }
+
+    /// Emitted when an auction is deleted
+    ///
+    /// - topics - `["delete_auction", auction_type: u32, user: Address]`
+    /// - data - `()`
+    ///
+    /// ### Arguments
+    /// * auction_type - The type of auction
+    /// * user - The address of the user
+    pub fn delete_auction(e: &Env, auction_type: u32, user: Address) {
+        let topics = (Symbol::new(e, topics::DELETE_AUCTION), auction_type, user);
+        e.events().publish(topics, ());
+    }
+}
@@ -0,0 +1,211 @@
+use sep_40_oracle::Asset;
+use soroban_sdk::{contracttype, Address, Vec};
+
+use crate::{AdminAction, AuctionData, PriceBand, ReserveConfig};
+
+// Payloads are tuple structs so they encode as the same `Vec` an indexer would see from a tuple.
+// Events with a single value publish that value directly.
+
+/// Data for `update_pool` -
+/// `[backstop_take_rate: u32, max_positions: u32, min_collateral: i128, min_debt: i128]`
+#[derive(Clone)]
+#[contracttype]
+pub struct UpdatePoolEvent(pub u32, pub u32, pub i128, pub i128);
+
+/// Data for `set_price_band` - `[asset: Address, band: Option<PriceBand>]`
+///
+/// Tuple structs can't hold an `Option`, so this payload is a tuple alias
+pub type SetPriceBandEvent = (Address, Option<PriceBand>);
+
+/// Data for `set_reserve_paused` - `[asset: Address, supply_paused: bool, borrow_paused: bool]`
+#[derive(Clone)]
+#[contracttype]
+pub struct SetReservePausedEvent(pub Address, pub bool, pub bool);
+
+/// Data for `set_oracle_asset` - `[asset: Address, oracle_asset: Option<Asset>]`
+pub type SetOracleAssetEvent = (Address, Option<Asset>);
+
+/// Data for `set_quote_adapter` - `[quote_asset: Address, adapter: Option<Address>]`
+pub type SetQuoteAdapterEvent = (Address, Option<Address>);
+
+/// Data for `set_rate_strategy` - `[asset: Address, strategy: Option<Address>]`
+pub type SetRateStrategyEvent = (Address, Option<Address>);
+
+/// Data for `set_max_apr` - `[asset: Address, max_apr: Option<u32>]`
+pub type SetMaxAprEvent = (Address, Option<u32>);
+
+/// Data for `borrow_rate_capped` - `[borrow_rate: i128, capped_rate: i128]`
+#[derive(Clone)]
+#[contracttype]
+pub struct BorrowRateCappedEvent(pub i128, pub i128);
+
+/// Data for `rescue` - `[to: Address, amount: i128]`
+#[derive(Clone)]
+#[contracttype]
+pub struct RescueEvent(pub Address, pub i128);
+
+/// Data for `set_credit_line` - `[lender: Address, cap: i128]`
+#[derive(Clone)]
+#[contracttype]
+pub struct SetCreditLineEvent(pub Address, pub i128);
+
+/// Data for `price_clamped` - `[oracle_price: i128, clamped_price: i128]`
+#[derive(Clone)]
+#[contracttype]
+pub struct PriceClampedEvent(pub i128, pub i128);
+
+/// Data for `queue_set_reserve` - `[asset: Address, metadata: ReserveConfig]`
+#[derive(Clone)]
+#[contracttype]
+pub struct QueueSetReserveEvent(pub Address, pub ReserveConfig);
+
+/// Data for `queue_admin_action` - `[id: u32, action: AdminAction, unlock_time: u64]`
+#[derive(Clone)]
+#[contracttype]
+pub struct QueueAdminActionEvent(pub u32, pub AdminAction, pub u64);
+
+/// Data for `propose_reserve` - `[config: ReserveConfig, bond: i128]`
+#[derive(Clone)]
+#[contracttype]
+pub struct ProposeReserveEvent(pub ReserveConfig, pub i128);
+
+/// Data for `set_reserve` - `[asset: Address, index: u32]`
+#[derive(Clone)]
+#[contracttype]
+pub struct SetReserveEvent(pub Address, pub u32);
+
+/// Data for `retire_reserve` - `[asset: Address, index: u32]`
+#[derive(Clone)]
+#[contracttype]
+pub struct RetireReserveEvent(pub Address, pub u32);
+
+/// Data for `reserve_emission_update` - `[res_token_id: u32, eps: u64, expiration: u64]`
+#[derive(Clone)]
+#[contracttype]
+pub struct ReserveEmissionUpdateEvent(pub u32, pub u64, pub u64);
+
+/// Data for `adjust_emission_share` - `[res_token_id: u32, new_share: u64]`
+#[derive(Clone)]
+#[contracttype]
+pub struct AdjustEmissionShareEvent(pub u32, pub u64);
+
+/// Data for `set_emission_decay` - `[res_token_id: u32, factor: u32]`
+#[derive(Clone)]
+#[contracttype]
+pub struct SetEmissionDecayEvent(pub u32, pub u32);
+
+/// Data for `claim` - `[reserve_token_ids: Vec<u32>, amount_claimed: i128]`
+#[derive(Clone)]
+#[contracttype]
+pub struct ClaimEvent(pub Vec<u32>, pub i128);
+
+/// Data for `claim_converted` - `[amount_in: i128, amount_out: i128]`
+#[derive(Clone)]
+#[contracttype]
+pub struct ClaimConvertedEvent(pub i128, pub i128);
+
+/// Data for `supply` and `supply_collateral` - `[tokens_in: i128, b_tokens_minted: i128]`
+#[derive(Clone)]
+#[contracttype]
+pub struct SupplyEvent(pub i128, pub i128);
+
+/// Data for `withdraw`, `withdraw_collateral` and `claim_withdrawal` -
+/// `[tokens_out: i128, b_tokens_burnt: i128]`
+#[derive(Clone)]
+#[contracttype]
+pub struct WithdrawEvent(pub i128, pub i128);
+
+/// Data for `credit_line_draw` - `[tokens_in: i128, b_tokens_minted: i128]`
+#[derive(Clone)]
+#[contracttype]
+pub struct CreditLineDrawEvent(pub i128, pub i128);
+
+/// Data for `credit_line_repay` - `[tokens_out: i128, b_tokens_burnt: i128]`
+#[derive(Clone)]
+#[contracttype]
+pub struct CreditLineRepayEvent(pub i128, pub i128);
+
+/// Data for `borrow` and `flash_loan` - `[tokens_out: i128, d_tokens_minted: i128]`
+#[derive(Clone)]
+#[contracttype]
+pub struct BorrowEvent(pub i128, pub i128);
+
+/// Data for `borrow_stable` - `[tokens_out: i128, d_tokens_minted: i128, rate: u32]`
+#[derive(Clone)]
+#[contracttype]
+pub struct BorrowStableEvent(pub i128, pub i128, pub u32);
+
+/// Data for `repay` - `[tokens_in: i128, d_tokens_burnt: i128]`
+#[derive(Clone)]
+#[contracttype]
+pub struct RepayEvent(pub i128, pub i128);
+
+/// Data for `risk_premium` - `[tokens_charged: i128, d_tokens_minted: i128]`
+#[derive(Clone)]
+#[contracttype]
+pub struct RiskPremiumEvent(pub i128, pub i128);
+
+/// Data for `insurance_premium` - `[tokens_charged: i128, d_tokens_minted: i128]`
+#[derive(Clone)]
+#[contracttype]
+pub struct InsurancePremiumEvent(pub i128, pub i128);
+
+/// Data for `approve_borrow` - `[asset: Address, amount: i128]`
+#[derive(Clone)]
+#[contracttype]
+pub struct ApproveBorrowEvent(pub Address, pub i128);
+
+/// Data for `repay_deficit` - `[to_suppliers: i128, to_backstop: i128]`
+#[derive(Clone)]
+#[contracttype]
+pub struct RepayDeficitEvent(pub i128, pub i128);
+
+/// Data for `new_auction` - `[percent: u32, auction_data: AuctionData]`
+#[derive(Clone)]
+#[contracttype]
+pub struct NewAuctionEvent(pub u32, pub AuctionData);
+
+/// Data for `soft_liquidation` - `[filler: Address, liquidated: AuctionData]`
+#[derive(Clone)]
+#[contracttype]
+pub struct SoftLiquidationEvent(pub Address, pub AuctionData);
+
+/// Data for `fill_auction` - `[filler: Address, fill_percent: i128, filled_auction_data: AuctionData]`
+#[derive(Clone)]
+#[contracttype]
+pub struct FillAuctionEvent(pub Address, pub i128, pub AuctionData);
+
+/// Data for `keeper_rebate` - `[token: Address, amount: i128]`
+#[derive(Clone)]
+#[contracttype]
+pub struct KeeperRebateEvent(pub Address, pub i128);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::{map, testutils::Address as _, vec, Env, IntoVal, Val};
+
+    #[test]
+    fn test_payloads_encode_as_tuples() {
+        let e = Env::default();
+        let asset = Address::generate(&e);
+        let auction_data = AuctionData {
+            bid: map![&e, (asset.clone(), 10)],
+            lot: map![&e, (asset.clone(), 20)],
+            block: 100,
+        };
+
+        let payload: Val = SupplyEvent(1, 2).into_val(&e);
+        let tuple: Val = (1i128, 2i128).into_val(&e);
+        assert_eq!(vec![&e, payload], vec![&e, tuple]);
+
+        let payload: Val = FillAuctionEvent(asset.clone(), 50, auction_data.clone()).into_val(&e);
+        let tuple: Val = (asset.clone(), 50i128, auction_data).into_val(&e);
+        assert_eq!(vec![&e, payload], vec![&e, tuple]);
+    }
+}
+
+/// Data for `util_breaker` - `[tripped: bool, new_status: u32]`
+#[derive(Clone)]
+#[contracttype]
+pub struct UtilBreakerEvent(pub bool, pub u32);
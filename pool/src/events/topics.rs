@@ -0,0 +1,239 @@
+/// The version of the pool event schema. This must be incremented whenever an event's topic name,
+/// topic layout, or payload layout changes, so indexers can detect the change.
+pub const EVENT_VERSION: u32 = 2;
+
+/********** Admin **********/
+
+pub const SET_ADMIN: &str = "set_admin";
+pub const UPDATE_POOL: &str = "update_pool";
+pub const SET_BSTOP_RATE_SCHEDULE: &str = "set_bstop_rate_schedule";
+pub const SET_AUCTION_CONFIG: &str = "set_auction_config";
+pub const SET_PRICE_BAND: &str = "set_price_band";
+pub const SET_RESERVE_PAUSED: &str = "set_reserve_paused";
+pub const SET_ORACLE: &str = "set_oracle";
+pub const SET_ORACLE_ASSET: &str = "set_oracle_asset";
+pub const SET_BASE: &str = "set_base";
+pub const QUEUE_SET_RESERVE: &str = "queue_set_reserve";
+pub const CANCEL_SET_RESERVE: &str = "cancel_set_reserve";
+pub const SET_LISTING_BOND: &str = "set_listing_bond";
+pub const SET_LISTING_COUNCIL: &str = "set_listing_council";
+pub const PROPOSE_RESERVE: &str = "propose_reserve";
+pub const APPROVE_RESERVE_PROPOSAL: &str = "approve_reserve_proposal";
+pub const REJECT_RESERVE_PROPOSAL: &str = "reject_reserve_proposal";
+pub const RECLAIM_RESERVE_PROPOSAL: &str = "reclaim_reserve_proposal";
+pub const QUEUE_ADMIN_ACTION: &str = "queue_admin_action";
+pub const CANCEL_QUEUED_ACTION: &str = "cancel_queued_action";
+pub const EXECUTE_QUEUED_ACTION: &str = "execute_queued_action";
+pub const SET_RESERVE: &str = "set_reserve";
+pub const CONFIG_SNAPSHOT: &str = "config_snapshot";
+pub const RETIRE_RESERVE: &str = "retire_reserve";
+pub const SET_STATUS: &str = "set_status";
+pub const SET_UTIL_BREAKER: &str = "set_util_breaker";
+pub const UTIL_BREAKER: &str = "util_breaker";
+pub const SET_CHECKPOINT_RETENTION: &str = "set_checkpoint_retention";
+pub const SET_ANALYTICS_WINDOW: &str = "set_analytics_window";
+pub const SET_QUOTE_ADAPTER: &str = "set_quote_adapter";
+pub const SET_RISK_PREMIUM: &str = "set_risk_premium";
+pub const SET_INSURANCE_CONFIG: &str = "set_insurance_config";
+pub const SET_MAX_LEDGER_TIME: &str = "set_max_ledger_time";
+pub const SET_MAX_ACCRUAL_PERIOD: &str = "set_max_accrual_period";
+pub const SET_C_FACTOR_RAMP: &str = "set_c_factor_ramp";
+pub const SET_BORROWER_INDEX: &str = "set_borrower_index";
+pub const SET_BACKSTOP_POSITIONS_EXEMPT: &str = "set_backstop_positions_exempt";
+pub const SET_FREEZE_EXPIRY: &str = "set_freeze_expiry";
+pub const SET_BORROW_BUFFER: &str = "set_borrow_buffer";
+pub const SET_FROZEN_FEES: &str = "set_frozen_fees";
+pub const SET_WITHDRAW_FEE: &str = "set_withdraw_fee";
+pub const SET_KEEPER_CONFIG: &str = "set_keeper_config";
+pub const SET_KEEPER: &str = "set_keeper";
+pub const FUND_KEEPER_POT: &str = "fund_keeper_pot";
+pub const WITHDRAW_KEEPER_POT: &str = "withdraw_keeper_pot";
+pub const KEEPER_REBATE: &str = "keeper_rebate";
+pub const SET_RATE_STRATEGY: &str = "set_rate_strategy";
+pub const SET_MAX_APR: &str = "set_max_apr";
+pub const BORROW_RATE_CAPPED: &str = "borrow_rate_capped";
+pub const SET_BLOCKED: &str = "set_blocked";
+pub const RESCUE: &str = "rescue";
+pub const SET_CREDIT_LINE: &str = "set_credit_line";
+pub const SET_STABLE_RATE: &str = "set_stable_rate";
+pub const SET_BACKSTOP_PRICE_CHECK: &str = "set_backstop_price_check";
+pub const SET_AUCTIONS_PAUSED: &str = "set_auctions_paused";
+pub const SET_FILL_COMMIT_CONFIG: &str = "set_fill_commit_config";
+pub const SET_CLAIM_CONVERTER: &str = "set_claim_converter";
+
+/********** Oracle **********/
+
+pub const PRICE_CLAMPED: &str = "price_clamped";
+
+/********** Emissions **********/
+
+pub const RESERVE_EMISSION_UPDATE: &str = "reserve_emission_update";
+pub const ADJUST_EMISSION_SHARE: &str = "adjust_emission_share";
+pub const SET_EMISSION_DECAY: &str = "set_emission_decay";
+pub const GULP_EMISSIONS: &str = "gulp_emissions";
+pub const SET_AUTO_GULP: &str = "set_auto_gulp";
+pub const CLAIM: &str = "claim";
+pub const CLAIM_CONVERTED: &str = "claim_converted";
+
+/********** Positions **********/
+
+pub const SUPPLY: &str = "supply";
+pub const WITHDRAW: &str = "withdraw";
+pub const QUEUE_WITHDRAWAL: &str = "queue_withdrawal";
+pub const CLAIM_WITHDRAWAL: &str = "claim_withdrawal";
+pub const CREDIT_LINE_DRAW: &str = "credit_line_draw";
+pub const CREDIT_LINE_REPAY: &str = "credit_line_repay";
+pub const SUPPLY_COLLATERAL: &str = "supply_collateral";
+pub const WITHDRAW_COLLATERAL: &str = "withdraw_collateral";
+pub const BORROW: &str = "borrow";
+pub const BORROW_STABLE: &str = "borrow_stable";
+pub const SWAP_TO_VARIABLE: &str = "swap_to_variable";
+pub const REBALANCE_STABLE_RATE: &str = "rebalance_stable_rate";
+pub const REPAY: &str = "repay";
+pub const RISK_PREMIUM: &str = "risk_premium";
+pub const INSURANCE_PREMIUM: &str = "insurance_premium";
+pub const APPROVE_BORROW: &str = "approve_borrow";
+pub const SET_SUPPLY_ON_BEHALF: &str = "set_supply_on_behalf";
+pub const SET_MIN_HF: &str = "set_min_hf";
+pub const SET_AUTO_CLAIM: &str = "set_auto_claim";
+pub const SET_PLAN_REQUESTS: &str = "set_plan_requests";
+pub const FLASH_LOAN: &str = "flash_loan";
+pub const GULP: &str = "gulp";
+
+/********** Bad Debt **********/
+
+pub const BAD_DEBT: &str = "bad_debt";
+pub const DEFAULTED_DEBT: &str = "defaulted_debt";
+pub const REPAY_DEFICIT: &str = "repay_deficit";
+
+/********** Auctions **********/
+
+pub const SET_LIQUIDATION_HOOK: &str = "set_liquidation_hook";
+pub const SET_INSURED: &str = "set_insured";
+pub const SET_INTEREST_DEPOSIT: &str = "set_interest_deposit";
+pub const NEW_AUCTION: &str = "new_auction";
+pub const SOFT_LIQUIDATION: &str = "soft_liquidation";
+pub const LIQUIDATION_AVERTED: &str = "liquidation_averted";
+pub const COMMIT_FILL: &str = "commit_fill";
+pub const FILL_AUCTION: &str = "fill_auction";
+pub const DELETE_AUCTION: &str = "delete_auction";
+
+/// Every topic name emitted by the pool
+pub const ALL: [&str; 98] = [
+    SET_ADMIN,
+    UPDATE_POOL,
+    SET_BSTOP_RATE_SCHEDULE,
+    SET_AUCTION_CONFIG,
+    SET_PRICE_BAND,
+    SET_RESERVE_PAUSED,
+    SET_ORACLE,
+    SET_ORACLE_ASSET,
+    SET_BASE,
+    QUEUE_SET_RESERVE,
+    CANCEL_SET_RESERVE,
+    SET_LISTING_BOND,
+    SET_LISTING_COUNCIL,
+    PROPOSE_RESERVE,
+    APPROVE_RESERVE_PROPOSAL,
+    REJECT_RESERVE_PROPOSAL,
+    RECLAIM_RESERVE_PROPOSAL,
+    QUEUE_ADMIN_ACTION,
+    CANCEL_QUEUED_ACTION,
+    EXECUTE_QUEUED_ACTION,
+    SET_RESERVE,
+    CONFIG_SNAPSHOT,
+    RETIRE_RESERVE,
+    SET_STATUS,
+    SET_UTIL_BREAKER,
+    UTIL_BREAKER,
+    SET_CHECKPOINT_RETENTION,
+    SET_ANALYTICS_WINDOW,
+    SET_QUOTE_ADAPTER,
+    SET_RISK_PREMIUM,
+    SET_INSURANCE_CONFIG,
+    SET_MAX_LEDGER_TIME,
+    SET_MAX_ACCRUAL_PERIOD,
+    SET_C_FACTOR_RAMP,
+    SET_BORROWER_INDEX,
+    SET_BACKSTOP_POSITIONS_EXEMPT,
+    SET_FREEZE_EXPIRY,
+    SET_BORROW_BUFFER,
+    SET_FROZEN_FEES,
+    SET_WITHDRAW_FEE,
+    SET_KEEPER_CONFIG,
+    SET_KEEPER,
+    FUND_KEEPER_POT,
+    WITHDRAW_KEEPER_POT,
+    KEEPER_REBATE,
+    SET_RATE_STRATEGY,
+    SET_MAX_APR,
+    BORROW_RATE_CAPPED,
+    SET_BLOCKED,
+    RESCUE,
+    SET_CREDIT_LINE,
+    SET_STABLE_RATE,
+    SET_BACKSTOP_PRICE_CHECK,
+    SET_AUCTIONS_PAUSED,
+    SET_FILL_COMMIT_CONFIG,
+    SET_CLAIM_CONVERTER,
+    PRICE_CLAMPED,
+    RESERVE_EMISSION_UPDATE,
+    ADJUST_EMISSION_SHARE,
+    SET_EMISSION_DECAY,
+    GULP_EMISSIONS,
+    SET_AUTO_GULP,
+    CLAIM,
+    CLAIM_CONVERTED,
+    SUPPLY,
+    WITHDRAW,
+    QUEUE_WITHDRAWAL,
+    CLAIM_WITHDRAWAL,
+    CREDIT_LINE_DRAW,
+    CREDIT_LINE_REPAY,
+    SUPPLY_COLLATERAL,
+    WITHDRAW_COLLATERAL,
+    BORROW,
+    BORROW_STABLE,
+    SWAP_TO_VARIABLE,
+    REBALANCE_STABLE_RATE,
+    REPAY,
+    RISK_PREMIUM,
+    INSURANCE_PREMIUM,
+    APPROVE_BORROW,
+    SET_SUPPLY_ON_BEHALF,
+    SET_MIN_HF,
+    SET_AUTO_CLAIM,
+    SET_PLAN_REQUESTS,
+    FLASH_LOAN,
+    GULP,
+    BAD_DEBT,
+    DEFAULTED_DEBT,
+    REPAY_DEFICIT,
+    SET_LIQUIDATION_HOOK,
+    SET_INSURED,
+    SET_INTEREST_DEPOSIT,
+    NEW_AUCTION,
+    SOFT_LIQUIDATION,
+    LIQUIDATION_AVERTED,
+    COMMIT_FILL,
+    FILL_AUCTION,
+    DELETE_AUCTION,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::{Env, Symbol};
+
+    #[test]
+    fn test_topics_are_unique_symbols() {
+        let e = Env::default();
+        for (i, topic) in ALL.iter().enumerate() {
+            // panics if the topic is not a valid symbol
+            Symbol::new(&e, topic);
+            for other in ALL.iter().skip(i + 1) {
+                assert_ne!(topic, other);
+            }
+        }
+    }
+}
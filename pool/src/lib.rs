@@ -4,7 +4,7 @@
 extern crate std;
 
 #[cfg(any(test, feature = "testutils"))]
-pub use pool::{Pool as PoolState, PositionData, Reserve};
+pub use pool::{calc_accrual, Pool as PoolState, PositionData, Reserve, ReserveState, User};
 
 mod auctions;
 mod constants;
@@ -19,11 +19,27 @@ mod testutils;
 mod validator;
 
 pub use auctions::{AuctionData, AuctionType};
+pub use constants::RESERVE_SEED_AMOUNT;
 pub use contract::*;
+pub use dependencies::{
+    ClaimConverter, ClaimConverterClient, FillHook, FillHookClient, LiquidationHook,
+    LiquidationHookClient, PoolBackstop, PoolBackstopClient, PoolBackstopData, RateStrategy,
+    RateStrategyClient, SwapAdapter, SwapAdapterClient,
+};
 pub use emissions::ReserveEmissionMetadata;
-pub use errors::PoolError;
-pub use pool::{FlashLoan, Positions, Request, RequestType};
+pub use errors::{PoolError, PoolErrorExt};
+pub use events::{payloads as event_payloads, topics as event_topics};
+pub use pool::{
+    reserve_seed_amount, ConfigSnapshot, Deleverage, FlashLoan, HookFill, KeeperTask,
+    PoolConfigExport, PoolTotals, Positions, QuoteFill, RateProjection, Request, RequestType,
+    ReserveSummary,
+};
 pub use storage::{
-    AuctionKey, PoolConfig, PoolDataKey, PoolEmissionConfig, ReserveConfig, ReserveData,
-    ReserveEmissionData, UserEmissionData, UserReserveKey,
+    AccrualClock, AdminAction, AuctionConfig, AuctionKey, BorrowAllowanceKey, BstopRateStep,
+    CreditLine, DailyStats, EmissionDecay, FillCommitConfig, FillCommitment, FillStats,
+    FillerStats, InsuranceConfig, KeeperConfig, PoolBase, PoolConfig, PoolDataKey,
+    PoolEmissionConfig, PositionCheckpoint, PriceBand, QueuedAdminAction, QueuedWithdrawal,
+    ReserveConfig, ReserveData, ReserveDeficit, ReserveEmissionData, ReservePause, ReserveProposal,
+    ReserveRateExt, ReserveStats, RiskPremium, StableLoan, StableRateConfig, UserEmissionData,
+    UserReserveKey, UtilBreaker, UtilBreakerState, WithdrawFee, WithdrawQueue,
 };
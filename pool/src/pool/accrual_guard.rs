@@ -0,0 +1,207 @@
+use soroban_sdk::{panic_with_error, Env};
+
+use crate::{
+    constants::MIN_MAX_ACCRUAL_PERIOD,
+    errors::PoolError,
+    storage::{self, AccrualClock},
+};
+
+/// Set the maximum number of seconds reserves can accrue interest for per ledger. While set, reserves
+/// accrue to at most the last accrual time plus the max ledger time for each ledger closed since,
+/// so an anomalous jump in the ledger timestamp is accrued over the following ledgers instead of at once.
+///
+/// ### Arguments
+/// * `max_ledger_time` - The maximum number of seconds per ledger, or 0 to disable the guard
+///
+/// ### Panics
+/// If the max ledger time is not 0 and is not between 5 seconds and 1 minute
+pub fn execute_set_max_ledger_time(e: &Env, max_ledger_time: u32) {
+    // verify the guard allows at least the 5s target ledger close time, and bounds accrual to at most 1 minute per ledger
+    if max_ledger_time != 0 && !(5..=60).contains(&max_ledger_time) {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+    if max_ledger_time != 0 && storage::get_max_ledger_time(e) == 0 {
+        // start bounding accrual from the current ledger
+        storage::set_accrual_clock(
+            e,
+            &AccrualClock {
+                sequence: e.ledger().sequence(),
+                timestamp: e.ledger().timestamp(),
+            },
+        );
+    }
+    storage::set_max_ledger_time(e, max_ledger_time);
+}
+
+/// Set the maximum number of seconds a reserve can accrue interest for in a single accrual. While set,
+/// a reserve that has not accrued for longer than the max accrual period, like after sitting idle or being
+/// restored from archival, only catches up by the max accrual period each time it is loaded. The catch up is
+/// spread over the following interactions instead of landing on the first one.
+///
+/// ### Arguments
+/// * `max_accrual_period` - The maximum number of seconds per accrual, or 0 to disable the cap
+///
+/// ### Panics
+/// If the max accrual period is not 0 and is less than 1 hour
+pub fn execute_set_max_accrual_period(e: &Env, max_accrual_period: u32) {
+    if max_accrual_period != 0 && max_accrual_period < MIN_MAX_ACCRUAL_PERIOD {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+    storage::set_max_accrual_period(e, max_accrual_period);
+}
+
+/// Cap the time a reserve accrues interest to so it accrues for at most the max accrual period
+///
+/// Returns the accrual time if the cap is disabled, otherwise the accrual time clamped to the
+/// reserve's last accrual time plus the max accrual period
+///
+/// ### Arguments
+/// * `last_time` - The last time the reserve accrued interest
+/// * `accrual_time` - The time the reserve can accrue interest to at the current ledger
+pub fn cap_accrual_time(e: &Env, last_time: u64, accrual_time: u64) -> u64 {
+    let max_accrual_period = storage::get_max_accrual_period(e);
+    if max_accrual_period == 0 {
+        return accrual_time;
+    }
+    accrual_time.min(last_time + max_accrual_period as u64)
+}
+
+/// Fetch the time reserves can accrue interest to at the current ledger
+///
+/// Returns the ledger timestamp if the guard is disabled, otherwise the ledger timestamp clamped
+/// to the last accrual time plus the max ledger time for each ledger closed since
+pub fn get_accrual_time(e: &Env) -> u64 {
+    let timestamp = e.ledger().timestamp();
+    let max_ledger_time = storage::get_max_ledger_time(e);
+    if max_ledger_time == 0 {
+        return timestamp;
+    }
+    match storage::get_accrual_clock(e) {
+        Some(clock) => {
+            let ledgers = e.ledger().sequence().saturating_sub(clock.sequence);
+            let max_timestamp = clock.timestamp + ledgers as u64 * max_ledger_time as u64;
+            timestamp.min(max_timestamp)
+        }
+        None => timestamp,
+    }
+}
+
+/// Record that reserves were accrued to the current accrual time
+///
+/// Does nothing if the guard is disabled
+pub fn record_accrual_time(e: &Env) {
+    if storage::get_max_ledger_time(e) == 0 {
+        return;
+    }
+    let sequence = e.ledger().sequence();
+    let timestamp = get_accrual_time(e);
+    if let Some(clock) = storage::get_accrual_clock(e) {
+        if clock.sequence == sequence && clock.timestamp == timestamp {
+            return;
+        }
+    }
+    storage::set_accrual_clock(
+        e,
+        &AccrualClock {
+            sequence,
+            timestamp,
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testutils;
+
+    use super::*;
+    use soroban_sdk::testutils::{Ledger, LedgerInfo};
+
+    fn set_ledger(e: &Env, sequence_number: u32, timestamp: u64) {
+        e.ledger().set(LedgerInfo {
+            timestamp,
+            protocol_version: 22,
+            sequence_number,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+    }
+
+    #[test]
+    fn test_get_accrual_time() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        set_ledger(&e, 100, 10000);
+
+        e.as_contract(&pool, || {
+            // disabled by default
+            set_ledger(&e, 101, 20000);
+            assert_eq!(get_accrual_time(&e), 20000);
+
+            execute_set_max_ledger_time(&e, 10);
+            set_ledger(&e, 102, 20008);
+            assert_eq!(get_accrual_time(&e), 20008);
+
+            // timestamp jumps by a day in a single ledger
+            set_ledger(&e, 103, 20008 + 86400);
+            assert_eq!(get_accrual_time(&e), 20020);
+            record_accrual_time(&e);
+
+            // the jump is accrued over the following ledgers
+            set_ledger(&e, 113, 20008 + 86400 + 50);
+            assert_eq!(get_accrual_time(&e), 20120);
+            record_accrual_time(&e);
+            assert_eq!(storage::get_accrual_clock(&e).unwrap().timestamp, 20120);
+
+            execute_set_max_ledger_time(&e, 0);
+            assert_eq!(get_accrual_time(&e), 20008 + 86400 + 50);
+        });
+    }
+
+    #[test]
+    fn test_cap_accrual_time() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        set_ledger(&e, 100, 10000);
+
+        e.as_contract(&pool, || {
+            // disabled by default
+            assert_eq!(cap_accrual_time(&e, 1000, 10000), 10000);
+
+            execute_set_max_accrual_period(&e, 3600);
+            assert_eq!(cap_accrual_time(&e, 1000, 4000), 4000);
+            assert_eq!(cap_accrual_time(&e, 1000, 10000), 4600);
+
+            execute_set_max_accrual_period(&e, 0);
+            assert_eq!(cap_accrual_time(&e, 1000, 10000), 10000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_set_max_accrual_period_too_small() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_max_accrual_period(&e, 3599);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_set_max_ledger_time_too_small() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_max_ledger_time(&e, 4);
+        });
+    }
+}
@@ -1,11 +1,18 @@
+use cast::i128;
+use soroban_fixed_point_math::SorobanFixedPoint;
 use soroban_sdk::Map;
 use soroban_sdk::{contracttype, panic_with_error, Address, Env, Vec};
 
+use crate::constants::SCALAR_7;
 use crate::events::PoolEvents;
+use crate::storage::{self, QueuedWithdrawal};
 use crate::AuctionType;
 use crate::{auctions, errors::PoolError, validator::require_nonnegative};
 
+use super::credit_line::{draw_credit_line, repay_credit_line};
 use super::pool::Pool;
+use super::stable_rate::{add_stable_liabilities, swap_to_variable};
+use super::status::get_withdraw_fee_rate;
 use super::User;
 
 /// A request a user makes against the pool
@@ -31,6 +38,11 @@ pub enum RequestType {
     FillBadDebtAuction = 7,
     FillInterestAuction = 8,
     DeleteLiquidationAuction = 9,
+    SoftLiquidation = 10,
+    QueueWithdrawal = 11,
+    ClaimWithdrawal = 12,
+    BorrowStable = 13,
+    SwapToVariable = 14,
 }
 
 impl RequestType {
@@ -50,6 +62,11 @@ impl RequestType {
             7 => RequestType::FillBadDebtAuction,
             8 => RequestType::FillInterestAuction,
             9 => RequestType::DeleteLiquidationAuction,
+            10 => RequestType::SoftLiquidation,
+            11 => RequestType::QueueWithdrawal,
+            12 => RequestType::ClaimWithdrawal,
+            13 => RequestType::BorrowStable,
+            14 => RequestType::SwapToVariable,
             _ => panic_with_error!(e, PoolError::BadRequest),
         }
     }
@@ -62,12 +79,51 @@ pub struct FlashLoan {
     pub amount: i128,
 }
 
+/// A fill of a user liquidation auction where the bid is repaid with a whitelisted quote asset
+#[contracttype]
+pub struct QuoteFill {
+    /// The user being liquidated
+    pub user: Address,
+    /// The percentage of the auction being filled as a number (i.e. 15 => 15%)
+    pub percent: u64,
+    /// The asset the filler pays the bid with
+    pub quote_asset: Address,
+    /// The maximum amount of the quote asset the filler is willing to pay
+    pub max_quote_in: i128,
+}
+
+/// A fill of a user liquidation auction where the lot is sent to a hook contract that funds the bid
+#[contracttype]
+pub struct HookFill {
+    /// The user being liquidated
+    pub user: Address,
+    /// The percentage of the auction being filled as a number (i.e. 15 => 15%)
+    pub percent: u64,
+    /// The contract the lot is sent to, which must send the bid to the pool
+    pub hook: Address,
+}
+
+/// A sale of a user's own collateral through a whitelisted swap adapter to repay their own liabilities
+#[contracttype]
+pub struct Deleverage {
+    /// The collateral asset being sold. Must be whitelisted as a quote asset.
+    pub collateral_asset: Address,
+    /// The liability asset being repaid
+    pub liability_asset: Address,
+    /// The amount of the liability asset to repay
+    pub amount: i128,
+    /// The maximum amount of the collateral asset the user is willing to sell
+    pub max_collateral_in: i128,
+}
+
 /// Transfer actions to be taken by the sender and pool
 pub struct Actions {
     pub spender_transfer: Map<Address, i128>,
     pub pool_transfer: Map<Address, i128>,
     pub check_health: bool,
+    pub check_min_debt: bool,
     pub check_max_util: Vec<Address>,
+    pub credit_line_transfer: Map<Address, i128>,
 }
 
 impl Actions {
@@ -77,7 +133,9 @@ impl Actions {
             spender_transfer: Map::new(e),
             pool_transfer: Map::new(e),
             check_health: false,
+            check_min_debt: false,
             check_max_util: Vec::new(e),
+            credit_line_transfer: Map::new(e),
         }
     }
 
@@ -97,12 +155,25 @@ impl Actions {
         );
     }
 
+    // Add tokens drawn from (positive) or repaid to (negative) the reserve's credit line
+    pub fn add_for_credit_line_transfer(&mut self, asset: &Address, amount: i128) {
+        self.credit_line_transfer.set(
+            asset.clone(),
+            amount + self.credit_line_transfer.get(asset.clone()).unwrap_or(0),
+        );
+    }
+
     // just a simple flag since we won't need
     // to switch it back to false once set to true.
     pub fn do_check_health(&mut self) {
         self.check_health = true
     }
 
+    // flag that a borrow occurred, so the user's liabilities must meet the pool's min debt
+    pub fn do_check_min_debt(&mut self) {
+        self.check_min_debt = true
+    }
+
     // Add "reserve" to the list of reserves to check max utilization for
     pub fn do_check_max_util(&mut self, reserve: &Address) {
         if self.check_max_util.contains(reserve) {
@@ -193,6 +264,28 @@ pub fn build_actions_from_request(
                     d_tokens_minted,
                 );
             }
+            RequestType::BorrowStable => {
+                let (d_tokens_minted, rate) =
+                    apply_borrow_stable(e, &mut actions, pool, from_state, &request);
+                PoolEvents::borrow_stable(
+                    e,
+                    request.address.clone(),
+                    from_state.address.clone(),
+                    request.amount,
+                    d_tokens_minted,
+                    rate,
+                );
+            }
+            RequestType::SwapToVariable => {
+                // Note: request amount is ignored, all of the user's stable rate liabilities are swapped
+                let d_tokens = apply_swap_to_variable(e, pool, from_state, &request);
+                PoolEvents::swap_to_variable(
+                    e,
+                    request.address.clone(),
+                    from_state.address.clone(),
+                    d_tokens,
+                );
+            }
             RequestType::Repay => {
                 let (tokens_in, d_tokens_burnt) =
                     apply_repay(e, &mut actions, pool, from_state, &request);
@@ -274,6 +367,50 @@ pub fn build_actions_from_request(
                     from_state.address.clone(),
                 );
             }
+            RequestType::SoftLiquidation => {
+                // Note: request amount is ignored, the user's entire position is liquidated
+                let liquidated = auctions::soft_liquidate(e, pool, &request.address, from_state);
+                actions.do_check_health();
+                PoolEvents::soft_liquidation(
+                    e,
+                    request.address.clone(),
+                    from_state.address.clone(),
+                    liquidated,
+                );
+            }
+            RequestType::QueueWithdrawal => {
+                let (tokens_out, b_tokens_burnt, b_tokens_queued) =
+                    apply_queue_withdrawal(e, &mut actions, pool, from_state, &request);
+                if b_tokens_burnt > 0 {
+                    PoolEvents::withdraw(
+                        e,
+                        request.address.clone(),
+                        from_state.address.clone(),
+                        tokens_out,
+                        b_tokens_burnt,
+                    );
+                }
+                if b_tokens_queued > 0 {
+                    PoolEvents::queue_withdrawal(
+                        e,
+                        request.address.clone(),
+                        from_state.address.clone(),
+                        b_tokens_queued,
+                    );
+                }
+            }
+            RequestType::ClaimWithdrawal => {
+                // Note: request amount is ignored, as much of the queued withdrawal as possible is claimed
+                let (tokens_out, b_tokens_burnt) =
+                    apply_claim_withdrawal(e, &mut actions, pool, from_state, &request);
+                PoolEvents::claim_withdrawal(
+                    e,
+                    request.address.clone(),
+                    from_state.address.clone(),
+                    tokens_out,
+                    b_tokens_burnt,
+                );
+            }
         }
     }
 
@@ -300,6 +437,7 @@ fn apply_supply(
     if reserve.total_supply(e) > reserve.config.supply_cap {
         panic_with_error!(e, PoolError::ExceededSupplyCap);
     }
+    repay_credit_line(e, actions, &mut reserve);
     pool.cache_reserve(reserve);
     b_tokens_minted
 }
@@ -325,12 +463,136 @@ fn apply_withdraw(
         tokens_out = reserve.to_asset_from_b_token(e, cur_b_tokens);
     }
     user.remove_supply(e, &mut reserve, to_burn);
+    draw_credit_line(e, actions, &mut reserve);
     reserve.require_utilization_below_100(e);
     actions.add_for_pool_transfer(&reserve.asset, tokens_out);
     pool.cache_reserve(reserve);
     (tokens_out, to_burn)
 }
 
+/// Apply a "queue_withdrawal" request to the pool
+///
+/// Withdraws as much of the request as possible without pushing the reserve's utilization above
+/// its maximum, and queues the remaining supply to be claimed once liquidity returns. Liquidity is
+/// only withdrawn immediately if no withdrawals are queued ahead of the request.
+///
+/// Returns the amount of tokens withdrawn, b_tokens burnt, and b_tokens queued
+///
+/// ### Panics
+/// If the user already has a queued withdrawal for the reserve that is not last in the queue
+fn apply_queue_withdrawal(
+    e: &Env,
+    actions: &mut Actions,
+    pool: &mut Pool,
+    user: &mut User,
+    request: &Request,
+) -> (i128, i128, i128) {
+    let mut reserve = pool.load_reserve(e, &request.address, true);
+    let cur_b_tokens = user.get_supply(reserve.config.index);
+    let mut to_remove = reserve.to_b_token_up(e, request.amount);
+    let mut tokens_requested = request.amount;
+    if to_remove > cur_b_tokens {
+        to_remove = cur_b_tokens;
+        tokens_requested = reserve.to_asset_from_b_token(e, cur_b_tokens);
+    }
+
+    let mut queue = storage::get_withdraw_queue(e, reserve.config.index);
+    // liquidity that returns belongs to the withdrawals already queued
+    let mut tokens_out = 0;
+    if queue.removed == queue.queued {
+        tokens_out = tokens_requested.min(reserve.available_under_max_util(e));
+    }
+    let mut to_burn = 0;
+    if tokens_out > 0 {
+        to_burn = reserve.to_b_token_up(e, tokens_out).min(to_remove);
+        user.remove_supply(e, &mut reserve, to_burn);
+        reserve.require_utilization_below_100(e);
+        actions.add_for_pool_transfer(&reserve.asset, tokens_out);
+    }
+
+    let to_queue = to_remove - to_burn;
+    if to_queue > 0 {
+        let mut queued =
+            match storage::get_queued_withdrawal(e, &user.address, reserve.config.index) {
+                // an existing withdrawal can only be added to if it is last in the queue
+                Some(queued) if queued.seq + queued.b_tokens == queue.queued => queued,
+                Some(_) => panic_with_error!(e, PoolError::BadRequest),
+                None => QueuedWithdrawal {
+                    b_tokens: 0,
+                    ledger: e.ledger().sequence(),
+                    seq: queue.queued,
+                },
+            };
+        queued.b_tokens += to_queue;
+        queue.queued += to_queue;
+        storage::set_queued_withdrawal(e, &user.address, reserve.config.index, &queued);
+        storage::set_withdraw_queue(e, reserve.config.index, &queue);
+    }
+    pool.cache_reserve(reserve);
+    (tokens_out, to_burn, to_queue)
+}
+
+/// Apply a "claim_withdrawal" request to the pool
+///
+/// Withdraws as much of the user's queued withdrawal as possible without pushing the reserve's
+/// utilization above its maximum. Any unclaimed amount remains queued. Queued withdrawals are
+/// claimed in the order they were queued.
+///
+/// Returns the amount of tokens withdrawn and b_tokens burnt
+///
+/// ### Panics
+/// If the user does not have a queued withdrawal for the reserve, or if another withdrawal is
+/// queued ahead of it
+fn apply_claim_withdrawal(
+    e: &Env,
+    actions: &mut Actions,
+    pool: &mut Pool,
+    user: &mut User,
+    request: &Request,
+) -> (i128, i128) {
+    let mut reserve = pool.load_reserve(e, &request.address, true);
+    let mut queued = match storage::get_queued_withdrawal(e, &user.address, reserve.config.index) {
+        Some(queued) => queued,
+        None => panic_with_error!(e, PoolError::BadRequest),
+    };
+    let mut queue = storage::get_withdraw_queue(e, reserve.config.index);
+    if queued.seq != queue.removed {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    // the queued bTokens remain in the user's supply position, so cap the claim at the current balance
+    let to_claim = queued.b_tokens.min(user.get_supply(reserve.config.index));
+    let mut to_burn = to_claim;
+    let mut tokens_out = reserve.to_asset_from_b_token(e, to_claim);
+    let available = reserve.available_under_max_util(e);
+    if tokens_out > available {
+        tokens_out = available;
+        to_burn = reserve.to_b_token_up(e, tokens_out).min(to_claim);
+    }
+
+    if to_burn > 0 && tokens_out > 0 {
+        user.remove_supply(e, &mut reserve, to_burn);
+        reserve.require_utilization_below_100(e);
+        actions.add_for_pool_transfer(&reserve.asset, tokens_out);
+    } else {
+        to_burn = 0;
+        tokens_out = 0;
+    }
+
+    // any queued bTokens the user no longer holds are removed from the queue with the claim
+    let remaining = to_claim - to_burn;
+    queue.removed += queued.b_tokens - remaining;
+    if remaining == 0 {
+        storage::del_queued_withdrawal(e, &user.address, reserve.config.index);
+    } else {
+        queued.seq = queue.removed;
+        queued.b_tokens = remaining;
+        storage::set_queued_withdrawal(e, &user.address, reserve.config.index, &queued);
+    }
+    storage::set_withdraw_queue(e, reserve.config.index, &queue);
+    pool.cache_reserve(reserve);
+    (tokens_out, to_burn)
+}
+
 /// Apply a "supply_collateral" request to the pool
 ///
 /// Appends any necessary actions to the actions list, updates the user and pool's state
@@ -351,6 +613,7 @@ fn apply_supply_collateral(
     if reserve.total_supply(e) > reserve.config.supply_cap {
         panic_with_error!(e, PoolError::ExceededSupplyCap);
     }
+    repay_credit_line(e, actions, &mut reserve);
     pool.cache_reserve(reserve);
     b_tokens_minted
 }
@@ -359,7 +622,7 @@ fn apply_supply_collateral(
 ///
 /// Appends any necessary actions to the actions list, updates the user and pool's state
 ///
-/// Returns the amount of tokens withdrawn and b_tokens burnt
+/// Returns the amount of tokens withdrawn, net of any withdrawal fee, and b_tokens burnt
 fn apply_withdraw_collateral(
     e: &Env,
     actions: &mut Actions,
@@ -376,6 +639,13 @@ fn apply_withdraw_collateral(
         tokens_out = reserve.to_asset_from_b_token(e, cur_b_tokens);
     }
     user.remove_collateral(e, &mut reserve, to_burn);
+    let fee_rate = get_withdraw_fee_rate(e, &pool.config);
+    if fee_rate > 0 {
+        let fee = tokens_out.fixed_mul_ceil(e, &i128(fee_rate), &SCALAR_7);
+        reserve.data.backstop_credit += fee;
+        tokens_out -= fee;
+    }
+    draw_credit_line(e, actions, &mut reserve);
     reserve.require_utilization_below_100(e);
     actions.add_for_pool_transfer(&reserve.asset, tokens_out);
     actions.do_check_health();
@@ -403,10 +673,47 @@ fn apply_borrow(
     actions.do_check_max_util(&reserve.asset);
     actions.add_for_pool_transfer(&reserve.asset, request.amount);
     actions.do_check_health();
+    actions.do_check_min_debt();
     pool.cache_reserve(reserve);
     d_tokens_minted
 }
 
+/// Apply a "borrow_stable" request to the pool
+///
+/// Appends any necessary actions to the actions list, updates the user and pool's state
+///
+/// Returns the amount of d_tokens minted and the user's new stable rate
+fn apply_borrow_stable(
+    e: &Env,
+    actions: &mut Actions,
+    pool: &mut Pool,
+    user: &mut User,
+    request: &Request,
+) -> (i128, u32) {
+    let mut reserve = pool.load_reserve(e, &request.address, true);
+    reserve.require_action_allowed(e, request.request_type);
+    let d_tokens_minted = reserve.to_d_token_up(e, request.amount);
+    user.add_liabilities(e, &mut reserve, d_tokens_minted);
+    let rate = add_stable_liabilities(e, user, &reserve, d_tokens_minted);
+    reserve.require_utilization_below_100(e);
+    actions.do_check_max_util(&reserve.asset);
+    actions.add_for_pool_transfer(&reserve.asset, request.amount);
+    actions.do_check_health();
+    actions.do_check_min_debt();
+    pool.cache_reserve(reserve);
+    (d_tokens_minted, rate)
+}
+
+/// Apply a "swap_to_variable" request to the pool
+///
+/// Updates the user's state
+///
+/// Returns the amount of d_tokens swapped to the variable rate
+fn apply_swap_to_variable(e: &Env, pool: &mut Pool, user: &mut User, request: &Request) -> i128 {
+    let reserve = pool.load_reserve(e, &request.address, false);
+    swap_to_variable(e, user, &reserve)
+}
+
 /// Apply a "repay" request to the pool
 ///
 /// Appends any necessary actions to the actions list, updates the user and pool's state
@@ -430,11 +737,13 @@ fn apply_repay(
         actions.add_for_spender_transfer(&reserve.asset, request.amount);
         actions.add_for_pool_transfer(&reserve.asset, amount_to_refund);
         user.remove_liabilities(e, &mut reserve, cur_d_tokens);
+        repay_credit_line(e, actions, &mut reserve);
         pool.cache_reserve(reserve);
         (cur_underlying_borrowed, cur_d_tokens)
     } else {
         actions.add_for_spender_transfer(&reserve.asset, request.amount);
         user.remove_liabilities(e, &mut reserve, d_tokens_burnt);
+        repay_credit_line(e, actions, &mut reserve);
         pool.cache_reserve(reserve);
         (repayment_amount, d_tokens_burnt)
     }
@@ -444,22 +753,331 @@ fn apply_repay(
 mod tests {
     use crate::{
         constants::SCALAR_7,
-        storage::{self, PoolConfig},
+        storage::{self, PoolConfig, WithdrawQueue},
         testutils::{self, create_comet_lp_pool, create_pool},
         AuctionData, AuctionType, Positions,
     };
 
     use super::*;
+    use cast::i128;
+    use sep_40_oracle::testutils::Asset;
     use soroban_sdk::{
         map,
         testutils::{Address as _, Ledger, LedgerInfo},
-        vec,
+        vec, Symbol,
     };
 
-    /***** supply *****/
+    /***** supply *****/
+
+    #[test]
+    fn test_build_actions_from_request_supply() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            let mut pool = Pool::load(&e);
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::Supply as u32,
+                    address: underlying.clone(),
+                    amount: 10_1234567,
+                },
+            ];
+
+            let mut user = User::load(&e, &samwise);
+            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
+
+            assert_eq!(actions.check_health, false);
+
+            let spender_transfer = actions.spender_transfer;
+            let pool_transfer = actions.pool_transfer;
+            assert_eq!(spender_transfer.len(), 1);
+            assert_eq!(
+                spender_transfer.get_unchecked(underlying.clone()),
+                10_1234567
+            );
+            assert_eq!(pool_transfer.len(), 0);
+
+            let positions = user.positions.clone();
+            assert_eq!(positions.liabilities.len(), 0);
+            assert_eq!(positions.collateral.len(), 0);
+            assert_eq!(positions.supply.len(), 1);
+            assert_eq!(user.get_supply(0), 10_1234488);
+
+            let reserve = pool.load_reserve(&e, &underlying, false);
+            assert_eq!(
+                reserve.data.b_supply,
+                reserve_data.b_supply + user.get_supply(0)
+            );
+        });
+    }
+
+    /***** withdraw *****/
+
+    #[test]
+    fn test_build_actions_from_request_withdraw() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+            min_debt: 0,
+            base: None,
+        };
+
+        let user_positions = Positions {
+            liabilities: map![&e],
+            collateral: map![&e],
+            supply: map![&e, (0, 20_0000000)],
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_user_positions(&e, &samwise, &user_positions);
+
+            let mut pool = Pool::load(&e);
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::Withdraw as u32,
+                    address: underlying.clone(),
+                    amount: 10_1234567,
+                },
+            ];
+            let mut user = User::load(&e, &samwise);
+            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
+
+            assert_eq!(actions.check_health, false);
+            assert_eq!(actions.check_max_util.len(), 0);
+
+            let spender_transfer = actions.spender_transfer;
+            let pool_transfer = actions.pool_transfer;
+            assert_eq!(spender_transfer.len(), 0);
+            assert_eq!(pool_transfer.len(), 1);
+            assert_eq!(pool_transfer.get_unchecked(underlying.clone()), 10_1234567);
+
+            let positions = user.positions.clone();
+            assert_eq!(positions.liabilities.len(), 0);
+            assert_eq!(positions.collateral.len(), 0);
+            assert_eq!(positions.supply.len(), 1);
+            assert_eq!(user.get_supply(0), 9_8765502);
+
+            let reserve = pool.load_reserve(&e, &underlying, false);
+            assert_eq!(
+                reserve.data.b_supply,
+                reserve_data.b_supply - (20_0000000 - 9_8765502)
+            );
+        });
+    }
+
+    #[test]
+    fn test_build_actions_from_request_withdraw_over_balance() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+            min_debt: 0,
+            base: None,
+        };
+        let user_positions = Positions {
+            liabilities: map![&e],
+            collateral: map![&e],
+            supply: map![&e, (0, 20_0000000)],
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_user_positions(&e, &samwise, &user_positions);
+
+            let mut pool = Pool::load(&e);
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::Withdraw as u32,
+                    address: underlying.clone(),
+                    amount: 21_0000000,
+                },
+            ];
+            let mut user = User::load(&e, &samwise);
+            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
+
+            assert_eq!(actions.check_health, false);
+            assert_eq!(actions.check_max_util.len(), 0);
+
+            let spender_transfer = actions.spender_transfer;
+            let pool_transfer = actions.pool_transfer;
+            assert_eq!(spender_transfer.len(), 0);
+            assert_eq!(pool_transfer.len(), 1);
+            assert_eq!(pool_transfer.get_unchecked(underlying.clone()), 20_0000137);
+
+            let positions = user.positions.clone();
+            assert_eq!(positions.liabilities.len(), 0);
+            assert_eq!(positions.collateral.len(), 0);
+            assert_eq!(positions.supply.len(), 0);
+
+            let reserve = pool.load_reserve(&e, &underlying.clone(), false);
+            assert_eq!(reserve.data.b_supply, reserve_data.b_supply - 20_0000000);
+        });
+    }
+
+    #[test]
+    fn test_build_actions_from_request_queue_withdrawal() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.d_supply = 90_0000000;
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+            min_debt: 0,
+            base: None,
+        };
+
+        let user_positions = Positions {
+            liabilities: map![&e],
+            collateral: map![&e],
+            supply: map![&e, (0, 20_0000000)],
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_user_positions(&e, &samwise, &user_positions);
+
+            let mut pool = Pool::load(&e);
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::QueueWithdrawal as u32,
+                    address: underlying.clone(),
+                    amount: 10_0000000,
+                },
+            ];
+            let mut user = User::load(&e, &samwise);
+            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
+
+            assert_eq!(actions.check_health, false);
+            assert_eq!(actions.check_max_util.len(), 0);
+
+            let pool_transfer = actions.pool_transfer;
+            assert_eq!(actions.spender_transfer.len(), 0);
+            assert_eq!(pool_transfer.len(), 1);
+            assert_eq!(pool_transfer.get_unchecked(underlying.clone()), 5_2629696);
+
+            // queued bTokens remain in the user's supply position
+            assert_eq!(user.get_supply(0), 14_7370617);
+            let queued = storage::get_queued_withdrawal(&e, &samwise, 0).unwrap();
+            assert_eq!(queued.b_tokens, 4_7370022);
+            assert_eq!(queued.ledger, 1234);
+            assert_eq!(queued.seq, 0);
+            let queue = storage::get_withdraw_queue(&e, 0);
+            assert_eq!(queue.queued, 4_7370022);
+            assert_eq!(queue.removed, 0);
+
+            let reserve = pool.load_reserve(&e, &underlying, false);
+            assert!(reserve.utilization(&e) <= i128(reserve.config.max_util));
+            assert_eq!(reserve.data.b_supply, 94_7370617);
+        });
+    }
 
     #[test]
-    fn test_build_actions_from_request_supply() {
+    fn test_build_actions_from_request_queue_withdrawal_behind_queue() {
         let e = Env::default();
         e.mock_all_auths();
 
@@ -468,7 +1086,8 @@ mod tests {
         let pool = testutils::create_pool(&e);
 
         let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
-        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.d_supply = 90_0000000;
         testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
 
         e.ledger().set(LedgerInfo {
@@ -484,56 +1103,61 @@ mod tests {
         let pool_config = PoolConfig {
             oracle: Address::generate(&e),
             min_collateral: 1_0000000,
-            bstop_rate: 0_1000000,
+            bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
+        };
+
+        let user_positions = Positions {
+            liabilities: map![&e],
+            collateral: map![&e],
+            supply: map![&e, (0, 20_0000000)],
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
+            storage::set_user_positions(&e, &samwise, &user_positions);
+
+            // another user's withdrawal is queued ahead
+            storage::set_withdraw_queue(
+                &e,
+                0,
+                &WithdrawQueue {
+                    queued: 5_0000000,
+                    removed: 0,
+                },
+            );
 
             let mut pool = Pool::load(&e);
 
             let requests = vec![
                 &e,
                 Request {
-                    request_type: RequestType::Supply as u32,
+                    request_type: RequestType::QueueWithdrawal as u32,
                     address: underlying.clone(),
-                    amount: 10_1234567,
+                    amount: 10_0000000,
                 },
             ];
-
             let mut user = User::load(&e, &samwise);
             let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
 
-            assert_eq!(actions.check_health, false);
-
-            let spender_transfer = actions.spender_transfer;
-            let pool_transfer = actions.pool_transfer;
-            assert_eq!(spender_transfer.len(), 1);
-            assert_eq!(
-                spender_transfer.get_unchecked(underlying.clone()),
-                10_1234567
-            );
-            assert_eq!(pool_transfer.len(), 0);
-
-            let positions = user.positions.clone();
-            assert_eq!(positions.liabilities.len(), 0);
-            assert_eq!(positions.collateral.len(), 0);
-            assert_eq!(positions.supply.len(), 1);
-            assert_eq!(user.get_supply(0), 10_1234488);
-
-            let reserve = pool.load_reserve(&e, &underlying, false);
-            assert_eq!(
-                reserve.data.b_supply,
-                reserve_data.b_supply + user.get_supply(0)
-            );
+            // the freed liquidity is left for the withdrawal queued ahead
+            assert_eq!(actions.pool_transfer.len(), 0);
+            assert_eq!(actions.spender_transfer.len(), 0);
+            assert_eq!(user.get_supply(0), 20_0000000);
+            let queued = storage::get_queued_withdrawal(&e, &samwise, 0).unwrap();
+            assert_eq!(queued.b_tokens, 9_9999405);
+            assert_eq!(queued.ledger, 1234);
+            assert_eq!(queued.seq, 5_0000000);
+            let queue = storage::get_withdraw_queue(&e, 0);
+            assert_eq!(queue.queued, 5_0000000 + 9_9999405);
+            assert_eq!(queue.removed, 0);
         });
     }
 
-    /***** withdraw *****/
-
     #[test]
-    fn test_build_actions_from_request_withdraw() {
+    fn test_build_actions_from_request_claim_withdrawal() {
         let e = Env::default();
         e.mock_all_auths();
 
@@ -561,6 +1185,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
 
         let user_positions = Positions {
@@ -571,45 +1197,52 @@ mod tests {
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
             storage::set_user_positions(&e, &samwise, &user_positions);
+            storage::set_queued_withdrawal(
+                &e,
+                &samwise,
+                0,
+                &QueuedWithdrawal {
+                    b_tokens: 5_0000000,
+                    ledger: 1000,
+                    seq: 0,
+                },
+            );
+            storage::set_withdraw_queue(
+                &e,
+                0,
+                &WithdrawQueue {
+                    queued: 5_0000000,
+                    removed: 0,
+                },
+            );
 
             let mut pool = Pool::load(&e);
 
             let requests = vec![
                 &e,
                 Request {
-                    request_type: RequestType::Withdraw as u32,
+                    request_type: RequestType::ClaimWithdrawal as u32,
                     address: underlying.clone(),
-                    amount: 10_1234567,
+                    amount: 0,
                 },
             ];
             let mut user = User::load(&e, &samwise);
             let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
 
-            assert_eq!(actions.check_health, false);
-            assert_eq!(actions.check_max_util.len(), 0);
-
-            let spender_transfer = actions.spender_transfer;
             let pool_transfer = actions.pool_transfer;
-            assert_eq!(spender_transfer.len(), 0);
             assert_eq!(pool_transfer.len(), 1);
-            assert_eq!(pool_transfer.get_unchecked(underlying.clone()), 10_1234567);
-
-            let positions = user.positions.clone();
-            assert_eq!(positions.liabilities.len(), 0);
-            assert_eq!(positions.collateral.len(), 0);
-            assert_eq!(positions.supply.len(), 1);
-            assert_eq!(user.get_supply(0), 9_8765502);
+            assert_eq!(pool_transfer.get_unchecked(underlying.clone()), 5_0000034);
+            assert_eq!(user.get_supply(0), 15_0000000);
+            assert!(storage::get_queued_withdrawal(&e, &samwise, 0).is_none());
+            assert_eq!(storage::get_withdraw_queue(&e, 0).removed, 5_0000000);
 
             let reserve = pool.load_reserve(&e, &underlying, false);
-            assert_eq!(
-                reserve.data.b_supply,
-                reserve_data.b_supply - (20_0000000 - 9_8765502)
-            );
+            assert_eq!(reserve.data.b_supply, reserve_data.b_supply - 5_0000000);
         });
     }
 
     #[test]
-    fn test_build_actions_from_request_withdraw_over_balance() {
+    fn test_build_actions_from_request_claim_withdrawal_partial() {
         let e = Env::default();
         e.mock_all_auths();
 
@@ -618,7 +1251,8 @@ mod tests {
         let pool = testutils::create_pool(&e);
 
         let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
-        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.d_supply = 90_0000000;
         testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
 
         e.ledger().set(LedgerInfo {
@@ -637,7 +1271,10 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
+
         let user_positions = Positions {
             liabilities: map![&e],
             collateral: map![&e],
@@ -646,36 +1283,152 @@ mod tests {
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
             storage::set_user_positions(&e, &samwise, &user_positions);
+            storage::set_queued_withdrawal(
+                &e,
+                &samwise,
+                0,
+                &QueuedWithdrawal {
+                    b_tokens: 10_0000000,
+                    ledger: 1000,
+                    seq: 0,
+                },
+            );
+            storage::set_withdraw_queue(
+                &e,
+                0,
+                &WithdrawQueue {
+                    queued: 10_0000000,
+                    removed: 0,
+                },
+            );
 
             let mut pool = Pool::load(&e);
 
             let requests = vec![
                 &e,
                 Request {
-                    request_type: RequestType::Withdraw as u32,
+                    request_type: RequestType::ClaimWithdrawal as u32,
                     address: underlying.clone(),
-                    amount: 21_0000000,
+                    amount: 0,
                 },
             ];
             let mut user = User::load(&e, &samwise);
             let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
 
-            assert_eq!(actions.check_health, false);
-            assert_eq!(actions.check_max_util.len(), 0);
-
-            let spender_transfer = actions.spender_transfer;
             let pool_transfer = actions.pool_transfer;
-            assert_eq!(spender_transfer.len(), 0);
             assert_eq!(pool_transfer.len(), 1);
-            assert_eq!(pool_transfer.get_unchecked(underlying.clone()), 20_0000137);
+            assert_eq!(pool_transfer.get_unchecked(underlying.clone()), 5_2629696);
+            assert_eq!(user.get_supply(0), 14_7370617);
+            let queued = storage::get_queued_withdrawal(&e, &samwise, 0).unwrap();
+            assert_eq!(queued.b_tokens, 4_7370617);
+            assert_eq!(queued.ledger, 1000);
+            assert_eq!(queued.seq, 5_2629383);
+            assert_eq!(storage::get_withdraw_queue(&e, 0).removed, 5_2629383);
 
-            let positions = user.positions.clone();
-            assert_eq!(positions.liabilities.len(), 0);
-            assert_eq!(positions.collateral.len(), 0);
-            assert_eq!(positions.supply.len(), 0);
+            let reserve = pool.load_reserve(&e, &underlying, false);
+            assert!(reserve.utilization(&e) <= i128(reserve.config.max_util));
+        });
+    }
 
-            let reserve = pool.load_reserve(&e, &underlying.clone(), false);
-            assert_eq!(reserve.data.b_supply, reserve_data.b_supply - 20_0000000);
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_build_actions_from_request_claim_withdrawal_none_queued() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            let mut pool = Pool::load(&e);
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::ClaimWithdrawal as u32,
+                    address: underlying.clone(),
+                    amount: 0,
+                },
+            ];
+            let mut user = User::load(&e, &samwise);
+            build_actions_from_request(&e, &mut pool, &mut user, requests);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_build_actions_from_request_claim_withdrawal_out_of_order() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            storage::set_queued_withdrawal(
+                &e,
+                &samwise,
+                0,
+                &QueuedWithdrawal {
+                    b_tokens: 5_0000000,
+                    ledger: 1000,
+                    seq: 5_0000000,
+                },
+            );
+            // another user's withdrawal is queued ahead
+            storage::set_withdraw_queue(
+                &e,
+                0,
+                &WithdrawQueue {
+                    queued: 10_0000000,
+                    removed: 0,
+                },
+            );
+
+            let mut pool = Pool::load(&e);
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::ClaimWithdrawal as u32,
+                    address: underlying.clone(),
+                    amount: 0,
+                },
+            ];
+            let mut user = User::load(&e, &samwise);
+            build_actions_from_request(&e, &mut pool, &mut user, requests);
         });
     }
 
@@ -713,6 +1466,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
 
         let user_positions = Positions {
@@ -770,6 +1525,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -843,6 +1600,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         let user_positions = Positions {
             liabilities: map![&e],
@@ -889,6 +1648,82 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_build_actions_from_request_withdraw_collateral_on_ice_fee() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 3,
+            max_positions: 2,
+            min_debt: 0,
+            base: None,
+        };
+        let user_positions = Positions {
+            liabilities: map![&e],
+            collateral: map![&e, (0, 20_0000000)],
+            supply: map![&e],
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_user_positions(&e, &samwise, &user_positions);
+            storage::set_withdraw_fee(
+                &e,
+                &storage::WithdrawFee {
+                    on_ice: 0_0050000,
+                    frozen: 0,
+                },
+            );
+
+            let mut pool = Pool::load(&e);
+            let backstop_credit = pool
+                .load_reserve(&e, &underlying, false)
+                .data
+                .backstop_credit;
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::WithdrawCollateral as u32,
+                    address: underlying.clone(),
+                    amount: 10_1234567,
+                },
+            ];
+            let mut user = User::load(&e, &samwise);
+            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
+
+            // the full request is withdrawn from the user's collateral, less a 0.5% fee
+            let pool_transfer = actions.pool_transfer;
+            assert_eq!(pool_transfer.len(), 1);
+            assert_eq!(pool_transfer.get_unchecked(underlying.clone()), 10_0728394);
+            assert_eq!(user.get_collateral(0), 9_8765502);
+
+            let reserve = pool.load_reserve(&e, &underlying, false);
+            assert_eq!(reserve.data.backstop_credit, backstop_credit + 0_0506173);
+        });
+    }
+
     #[test]
     fn test_build_actions_from_request_withdraw_collateral_over_balance() {
         let e = Env::default();
@@ -918,6 +1753,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         let user_positions = Positions {
             liabilities: map![&e],
@@ -994,6 +1831,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
 
         let user_positions = Positions {
@@ -1050,6 +1889,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -1120,6 +1961,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -1208,6 +2051,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
 
         let user_positions = Positions {
@@ -1265,6 +2110,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         let user_positions = Positions {
             liabilities: map![&e, (0, 20_0000000)],
@@ -1343,6 +2190,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         let user_positions = Positions {
             liabilities: map![&e, (0, 20_0000000)],
@@ -1424,6 +2273,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         let user_positions = Positions::env_default(&e);
         e.as_contract(&pool, || {
@@ -1514,7 +2365,7 @@ mod tests {
 
         let pool_address = create_pool(&e);
 
-        let (oracle_address, _) = testutils::create_mock_oracle(&e);
+        let (oracle_address, oracle_client) = testutils::create_mock_oracle(&e);
 
         // creating reserves for a pool exhausts the budget
         e.cost_estimate().budget().reset_unlimited();
@@ -1570,12 +2421,27 @@ mod tests {
             ],
             block: 176,
         };
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+                Asset::Stellar(underlying_2.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 1_0000000, 1_0000000]);
         let pool_config = PoolConfig {
             oracle: oracle_address,
             min_collateral: 1_0000000,
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         let positions: Positions = Positions {
             collateral: map![
@@ -1651,7 +2517,7 @@ mod tests {
 
         let pool_address = create_pool(&e);
 
-        let (oracle_address, _) = testutils::create_mock_oracle(&e);
+        let (oracle_address, oracle_client) = testutils::create_mock_oracle(&e);
 
         // creating reserves for a pool exhausts the budget
         e.cost_estimate().budget().reset_unlimited();
@@ -1693,12 +2559,26 @@ mod tests {
             &reserve_config_1,
             &reserve_data_1,
         );
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 1_0000000]);
         let pool_config = PoolConfig {
             oracle: oracle_address,
             min_collateral: 1_0000000,
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         let auction_data = AuctionData {
             bid: map![&e, (underlying_0, 10_0000000), (underlying_1, 2_5000000)],
@@ -1771,6 +2651,7 @@ mod tests {
         let samwise = Address::generate(&e);
 
         let pool_address = create_pool(&e);
+        let (oracle_address, oracle_client) = testutils::create_mock_oracle(&e);
         let (usdc_id, usdc_client) = testutils::create_token_contract(&e, &bombadil);
         let (blnd_id, blnd_client) = testutils::create_blnd_token(&e, &pool_address, &bombadil);
 
@@ -1832,12 +2713,27 @@ mod tests {
         );
         underlying_2_client.mint(&pool_address, &1_000_0000000);
 
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+                Asset::Stellar(underlying_2.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 1_0000000, 1_0000000]);
         let pool_config = PoolConfig {
-            oracle: Address::generate(&e),
+            oracle: oracle_address,
             min_collateral: 1_0000000,
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         let auction_data = AuctionData {
             bid: map![&e, (backstop_token_id.clone(), 100_0000000)],
@@ -1932,6 +2828,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         let auction_data = AuctionData {
             bid: map![&e, (underlying_0.clone(), 952_0000000)],
@@ -1999,6 +2897,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 1,
+            min_debt: 0,
+            base: None,
         };
 
         let requests = vec![
@@ -2040,6 +2940,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 1,
+            min_debt: 0,
+            base: None,
         };
 
         let requests = vec![
@@ -2081,6 +2983,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 1,
+            min_debt: 0,
+            base: None,
         };
 
         let requests = vec![
@@ -2122,6 +3026,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 1,
+            min_debt: 0,
+            base: None,
         };
 
         let requests = vec![
@@ -0,0 +1,558 @@
+use soroban_sdk::{panic_with_error, Env};
+
+use crate::{
+    constants::{
+        EMISSIONS_CONFIG_DELAY, ORACLE_ASSET_DELAY, ORACLE_DELAY, RATE_CONFIG_DELAY,
+        RATE_STRATEGY_DELAY, RESCUE_DELAY, UPDATE_POOL_DELAY,
+    },
+    emissions,
+    errors::PoolError,
+    events::PoolEvents,
+    storage::{self, AdminAction, QueuedAdminAction},
+};
+
+use super::{
+    execute_rescue, execute_set_auction_config, execute_set_base, execute_set_bstop_rate_schedule,
+    execute_set_frozen_fees, execute_set_insurance_config, execute_set_max_accrual_period,
+    execute_set_max_apr, execute_set_max_ledger_time, execute_set_oracle, execute_set_oracle_asset,
+    execute_set_price_band, execute_set_quote_adapter, execute_set_rate_strategy,
+    execute_set_risk_premium, execute_set_stable_rate, execute_set_util_breaker,
+    execute_set_withdraw_fee, execute_update_pool,
+};
+
+/// Require that admin actions can be applied without a timelock, which is only the case while
+/// the pool is in setup
+///
+/// ### Panics
+/// If the pool has left setup
+pub fn require_no_timelock(e: &Env) {
+    if storage::get_pool_config(e).status != 6 {
+        panic_with_error!(e, PoolError::TimelockRequired);
+    }
+}
+
+/// Queue an admin action. The action can be executed once its timelock has passed, or immediately
+/// if the pool is in setup.
+///
+/// Returns the id of the queued action and the timestamp it unlocks at
+///
+/// ### Arguments
+/// * `action` - The admin action to queue
+pub fn execute_queue_admin_action(e: &Env, action: &AdminAction) -> (u32, u64) {
+    let mut unlock_time = e.ledger().timestamp();
+    if storage::get_pool_config(e).status != 6 {
+        unlock_time += match action {
+            AdminAction::UpdatePool(..)
+            | AdminAction::SetBstopRateSchedule(..)
+            | AdminAction::SetAuctionConfig(..)
+            | AdminAction::SetQuoteAdapter(..)
+            | AdminAction::SetUtilBreaker(..)
+            | AdminAction::RemoveUtilBreaker => UPDATE_POOL_DELAY,
+            AdminAction::SetEmissionsConfig(..)
+            | AdminAction::AdjustEmissionShare(..)
+            | AdminAction::SetEmissionDecay(..)
+            | AdminAction::SetClaimConverter(..) => EMISSIONS_CONFIG_DELAY,
+            AdminAction::SetOracleAsset(..)
+            | AdminAction::RemoveOracleAsset(..)
+            | AdminAction::SetPriceBand(..)
+            | AdminAction::RemovePriceBand(..) => ORACLE_ASSET_DELAY,
+            AdminAction::SetOracle(..) | AdminAction::SetBase(..) | AdminAction::RemoveBase => {
+                ORACLE_DELAY
+            }
+            AdminAction::Rescue(..) => RESCUE_DELAY,
+            AdminAction::SetRateStrategy(..) => RATE_STRATEGY_DELAY,
+            AdminAction::SetMaxApr(..)
+            | AdminAction::RemoveMaxApr(..)
+            | AdminAction::SetStableRate(..)
+            | AdminAction::SetRiskPremium(..)
+            | AdminAction::SetInsuranceConfig(..)
+            | AdminAction::RemoveInsuranceConfig
+            | AdminAction::SetFrozenFees(..)
+            | AdminAction::SetWithdrawFee(..)
+            | AdminAction::SetMaxLedgerTime(..)
+            | AdminAction::SetMaxAccrualPeriod(..) => RATE_CONFIG_DELAY,
+        };
+    }
+
+    let id = storage::get_next_admin_action_id(e);
+    storage::set_queued_admin_action(
+        e,
+        id,
+        &QueuedAdminAction {
+            action: action.clone(),
+            unlock_time,
+        },
+    );
+    storage::set_next_admin_action_id(e, id + 1);
+    (id, unlock_time)
+}
+
+/// Cancel a queued admin action
+///
+/// ### Arguments
+/// * `id` - The id of the queued action
+///
+/// ### Panics
+/// If the action is not queued
+pub fn execute_cancel_queued_admin_action(e: &Env, id: u32) {
+    if storage::get_queued_admin_action(e, id).is_none() {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    storage::del_queued_admin_action(e, id);
+}
+
+/// Execute a queued admin action whose timelock has passed, and emit the event of the action
+///
+/// ### Arguments
+/// * `id` - The id of the queued action
+///
+/// ### Panics
+/// If the action is not queued, the timelock has not passed, or the action is invalid
+pub fn execute_queued_admin_action(e: &Env, id: u32) {
+    let queued_action = match storage::get_queued_admin_action(e, id) {
+        Some(queued_action) => queued_action,
+        None => panic_with_error!(e, PoolError::BadRequest),
+    };
+    if queued_action.unlock_time > e.ledger().timestamp() {
+        panic_with_error!(e, PoolError::ActionNotUnlocked);
+    }
+    storage::del_queued_admin_action(e, id);
+
+    let admin = storage::get_admin(e);
+    match queued_action.action {
+        AdminAction::UpdatePool(backstop_take_rate, max_positions, min_collateral, min_debt) => {
+            execute_update_pool(
+                e,
+                backstop_take_rate,
+                max_positions,
+                min_collateral,
+                min_debt,
+            );
+            PoolEvents::update_pool(
+                e,
+                admin,
+                backstop_take_rate,
+                max_positions,
+                min_collateral,
+                min_debt,
+            );
+        }
+        AdminAction::SetEmissionsConfig(res_emission_metadata) => {
+            emissions::set_pool_emissions(e, res_emission_metadata);
+        }
+        AdminAction::AdjustEmissionShare(res_token_id, new_share) => {
+            emissions::adjust_pool_emission_share(e, res_token_id, new_share);
+            PoolEvents::adjust_emission_share(e, admin, res_token_id, new_share);
+        }
+        AdminAction::SetEmissionDecay(res_token_id, factor) => {
+            emissions::set_emission_decay(e, res_token_id, factor);
+            PoolEvents::set_emission_decay(e, admin, res_token_id, factor);
+        }
+        AdminAction::SetOracleAsset(asset, oracle_asset) => {
+            let oracle_asset = Some(oracle_asset);
+            execute_set_oracle_asset(e, &asset, &oracle_asset);
+            PoolEvents::set_oracle_asset(e, admin, asset, oracle_asset);
+        }
+        AdminAction::RemoveOracleAsset(asset) => {
+            execute_set_oracle_asset(e, &asset, &None);
+            PoolEvents::set_oracle_asset(e, admin, asset, None);
+        }
+        AdminAction::SetOracle(oracle) => {
+            execute_set_oracle(e, &oracle);
+            PoolEvents::set_oracle(e, admin, oracle);
+        }
+        AdminAction::SetBase(base) => {
+            let base = Some(base);
+            execute_set_base(e, &base);
+            PoolEvents::set_base(e, admin, base);
+        }
+        AdminAction::RemoveBase => {
+            execute_set_base(e, &None);
+            PoolEvents::set_base(e, admin, None);
+        }
+        AdminAction::Rescue(asset, to) => {
+            let amount = execute_rescue(e, &asset, &to);
+            PoolEvents::rescue(e, admin, asset, to, amount);
+        }
+        AdminAction::SetRateStrategy(asset, strategy) => {
+            let strategy = Some(strategy);
+            execute_set_rate_strategy(e, &asset, &strategy);
+            PoolEvents::set_rate_strategy(e, admin, asset, strategy);
+        }
+        AdminAction::SetPriceBand(asset, band) => {
+            let band = Some(band);
+            execute_set_price_band(e, &asset, &band);
+            PoolEvents::set_price_band(e, admin, asset, band);
+        }
+        AdminAction::RemovePriceBand(asset) => {
+            execute_set_price_band(e, &asset, &None);
+            PoolEvents::set_price_band(e, admin, asset, None);
+        }
+        AdminAction::SetMaxApr(asset, max_apr) => {
+            let max_apr = Some(max_apr);
+            execute_set_max_apr(e, &asset, &max_apr);
+            PoolEvents::set_max_apr(e, admin, asset, max_apr);
+        }
+        AdminAction::RemoveMaxApr(asset) => {
+            execute_set_max_apr(e, &asset, &None);
+            PoolEvents::set_max_apr(e, admin, asset, None);
+        }
+        AdminAction::SetStableRate(asset, config) => {
+            let config = Some(config);
+            execute_set_stable_rate(e, &asset, &config);
+            PoolEvents::set_stable_rate(e, admin, asset, config);
+        }
+        AdminAction::SetRiskPremium(risk_premium) => {
+            let risk_premium = Some(risk_premium);
+            execute_set_risk_premium(e, &risk_premium);
+            PoolEvents::set_risk_premium(e, admin, risk_premium);
+        }
+        AdminAction::SetInsuranceConfig(config) => {
+            let config = Some(config);
+            execute_set_insurance_config(e, &config);
+            PoolEvents::set_insurance_config(e, admin, config);
+        }
+        AdminAction::RemoveInsuranceConfig => {
+            execute_set_insurance_config(e, &None);
+            PoolEvents::set_insurance_config(e, admin, None);
+        }
+        AdminAction::SetFrozenFees(mode) => {
+            execute_set_frozen_fees(e, mode);
+            PoolEvents::set_frozen_fees(e, admin, mode);
+        }
+        AdminAction::SetWithdrawFee(fee) => {
+            execute_set_withdraw_fee(e, &fee);
+            PoolEvents::set_withdraw_fee(e, admin, fee);
+        }
+        AdminAction::SetUtilBreaker(util_breaker) => {
+            let util_breaker = Some(util_breaker);
+            execute_set_util_breaker(e, &util_breaker);
+            PoolEvents::set_util_breaker(e, admin, util_breaker);
+        }
+        AdminAction::RemoveUtilBreaker => {
+            execute_set_util_breaker(e, &None);
+            PoolEvents::set_util_breaker(e, admin, None);
+        }
+        AdminAction::SetClaimConverter(converter) => {
+            let converter = Some(converter);
+            storage::set_claim_converter(e, &converter);
+            PoolEvents::set_claim_converter(e, admin, converter);
+        }
+        AdminAction::SetBstopRateSchedule(schedule) => {
+            execute_set_bstop_rate_schedule(e, &schedule);
+            PoolEvents::set_bstop_rate_schedule(e, admin, schedule);
+        }
+        AdminAction::SetAuctionConfig(config) => {
+            execute_set_auction_config(e, &config);
+            PoolEvents::set_auction_config(e, admin, config);
+        }
+        AdminAction::SetMaxLedgerTime(max_ledger_time) => {
+            execute_set_max_ledger_time(e, max_ledger_time);
+            PoolEvents::set_max_ledger_time(e, admin, max_ledger_time);
+        }
+        AdminAction::SetMaxAccrualPeriod(max_accrual_period) => {
+            execute_set_max_accrual_period(e, max_accrual_period);
+            PoolEvents::set_max_accrual_period(e, admin, max_accrual_period);
+        }
+        AdminAction::SetQuoteAdapter(quote_asset, adapter) => {
+            let adapter = Some(adapter);
+            execute_set_quote_adapter(e, &quote_asset, &adapter);
+            PoolEvents::set_quote_adapter(e, admin, quote_asset, adapter);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        storage::{AuctionConfig, BstopRateStep, PoolConfig, PriceBand, WithdrawFee},
+        testutils,
+    };
+
+    use super::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger},
+        vec, Address,
+    };
+
+    fn pool_config(e: &Env, status: u32) -> PoolConfig {
+        PoolConfig {
+            oracle: Address::generate(e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status,
+            max_positions: 2,
+            min_debt: 0,
+            base: None,
+        }
+    }
+
+    #[test]
+    fn test_execute_queued_admin_action() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.ledger().set_timestamp(1000);
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config(&e, 0));
+
+            let (id, unlock_time) = execute_queue_admin_action(
+                &e,
+                &AdminAction::UpdatePool(0_2000000, 4, 2_0000000, 5_0000000),
+            );
+            assert_eq!(id, 0);
+            assert_eq!(unlock_time, 1000 + UPDATE_POOL_DELAY);
+            let (id, unlock_time) =
+                execute_queue_admin_action(&e, &AdminAction::AdjustEmissionShare(0, 1_0000000));
+            assert_eq!(id, 1);
+            assert_eq!(unlock_time, 1000 + EMISSIONS_CONFIG_DELAY);
+
+            e.ledger().set_timestamp(1000 + UPDATE_POOL_DELAY);
+            execute_queued_admin_action(&e, 0);
+            let new_pool_config = storage::get_pool_config(&e);
+            assert_eq!(new_pool_config.bstop_rate, 0_2000000);
+            assert_eq!(new_pool_config.max_positions, 4);
+            assert_eq!(new_pool_config.min_collateral, 2_0000000);
+            assert_eq!(new_pool_config.min_debt, 5_0000000);
+            assert!(storage::get_queued_admin_action(&e, 0).is_none());
+            assert!(storage::get_queued_admin_action(&e, 1).is_some());
+        });
+    }
+
+    #[test]
+    fn test_execute_queue_admin_action_setup_has_no_timelock() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.ledger().set_timestamp(1000);
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config(&e, 6));
+            require_no_timelock(&e);
+
+            let asset = Address::generate(&e);
+            let (id, unlock_time) =
+                execute_queue_admin_action(&e, &AdminAction::RemoveOracleAsset(asset));
+            assert_eq!(id, 0);
+            assert_eq!(unlock_time, 1000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1239)")]
+    fn test_execute_queued_admin_action_not_unlocked() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.ledger().set_timestamp(1000);
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config(&e, 0));
+
+            let asset = Address::generate(&e);
+            let (id, _) = execute_queue_admin_action(&e, &AdminAction::RemoveOracleAsset(asset));
+
+            e.ledger().set_timestamp(1000 + ORACLE_ASSET_DELAY - 1);
+            execute_queued_admin_action(&e, id);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1239)")]
+    fn test_execute_queued_rescue_not_unlocked() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.ledger().set_timestamp(1000);
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config(&e, 0));
+
+            let action = AdminAction::Rescue(Address::generate(&e), Address::generate(&e));
+            let (id, unlock_time) = execute_queue_admin_action(&e, &action);
+            assert_eq!(unlock_time, 1000 + RESCUE_DELAY);
+
+            e.ledger().set_timestamp(1000 + RESCUE_DELAY - 1);
+            execute_queued_admin_action(&e, id);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_execute_cancel_queued_admin_action() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.ledger().set_timestamp(1000);
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config(&e, 0));
+
+            let (id, _) = execute_queue_admin_action(
+                &e,
+                &AdminAction::UpdatePool(0_2000000, 4, 2_0000000, 5_0000000),
+            );
+            execute_cancel_queued_admin_action(&e, id);
+            assert!(storage::get_queued_admin_action(&e, id).is_none());
+
+            e.ledger().set_timestamp(1000 + UPDATE_POOL_DELAY);
+            execute_queued_admin_action(&e, id);
+        });
+    }
+
+    #[test]
+    fn test_execute_queued_set_price_band() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.ledger().set_timestamp(1000);
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config(&e, 0));
+
+            let band = PriceBand {
+                min_price: 0_9000000,
+                max_price: 1_1000000,
+            };
+            let (id, unlock_time) = execute_queue_admin_action(
+                &e,
+                &AdminAction::SetPriceBand(underlying.clone(), band),
+            );
+            assert_eq!(unlock_time, 1000 + ORACLE_ASSET_DELAY);
+
+            e.ledger().set_timestamp(1000 + ORACLE_ASSET_DELAY);
+            execute_queued_admin_action(&e, id);
+            let band = storage::get_price_band(&e, &underlying).unwrap();
+            assert_eq!(band.min_price, 0_9000000);
+            assert_eq!(band.max_price, 1_1000000);
+        });
+    }
+
+    #[test]
+    fn test_execute_queued_rate_config() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.ledger().set_timestamp(1000);
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config(&e, 0));
+
+            let (apr_id, unlock_time) = execute_queue_admin_action(
+                &e,
+                &AdminAction::SetMaxApr(underlying.clone(), 1_0000000),
+            );
+            assert_eq!(unlock_time, 1000 + RATE_CONFIG_DELAY);
+            let fee = WithdrawFee {
+                on_ice: 0_0010000,
+                frozen: 0_0050000,
+            };
+            let (fee_id, unlock_time) =
+                execute_queue_admin_action(&e, &AdminAction::SetWithdrawFee(fee.clone()));
+            assert_eq!(unlock_time, 1000 + RATE_CONFIG_DELAY);
+
+            e.ledger().set_timestamp(1000 + RATE_CONFIG_DELAY);
+            execute_queued_admin_action(&e, apr_id);
+            execute_queued_admin_action(&e, fee_id);
+            assert_eq!(storage::get_max_apr(&e, &underlying), Some(1_0000000));
+            assert_eq!(storage::get_withdraw_fee(&e), fee);
+        });
+    }
+
+    #[test]
+    fn test_execute_queued_pool_config() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.ledger().set_timestamp(1000);
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config(&e, 1));
+
+            let schedule = vec![
+                &e,
+                BstopRateStep {
+                    start_time: 5000,
+                    rate: 0_2000000,
+                },
+            ];
+            let (schedule_id, unlock_time) = execute_queue_admin_action(
+                &e,
+                &AdminAction::SetBstopRateSchedule(schedule.clone()),
+            );
+            assert_eq!(unlock_time, 1000 + UPDATE_POOL_DELAY);
+            let auction_config = AuctionConfig {
+                start_delay: 10,
+                ..AuctionConfig::default()
+            };
+            let (auction_id, unlock_time) = execute_queue_admin_action(
+                &e,
+                &AdminAction::SetAuctionConfig(auction_config.clone()),
+            );
+            assert_eq!(unlock_time, 1000 + UPDATE_POOL_DELAY);
+            let (ledger_time_id, unlock_time) =
+                execute_queue_admin_action(&e, &AdminAction::SetMaxLedgerTime(30));
+            assert_eq!(unlock_time, 1000 + RATE_CONFIG_DELAY);
+
+            e.ledger().set_timestamp(1000 + UPDATE_POOL_DELAY);
+            execute_queued_admin_action(&e, schedule_id);
+            execute_queued_admin_action(&e, auction_id);
+            execute_queued_admin_action(&e, ledger_time_id);
+
+            let stored_schedule = storage::get_bstop_rate_schedule(&e);
+            assert_eq!(stored_schedule.len(), 1);
+            assert_eq!(stored_schedule.get_unchecked(0).start_time, 5000);
+            assert_eq!(stored_schedule.get_unchecked(0).rate, 0_2000000);
+            assert_eq!(storage::get_auction_config(&e).start_delay, 10);
+            assert_eq!(storage::get_max_ledger_time(&e), 30);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_queued_bstop_rate_schedule_validates_steps() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.ledger().set_timestamp(1000);
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config(&e, 0));
+
+            let schedule = vec![
+                &e,
+                BstopRateStep {
+                    start_time: 5000,
+                    rate: 1_0000000,
+                },
+            ];
+            let (id, _) =
+                execute_queue_admin_action(&e, &AdminAction::SetBstopRateSchedule(schedule));
+            execute_queued_admin_action(&e, id);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1240)")]
+    fn test_require_no_timelock_after_setup() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config(&e, 1));
+            require_no_timelock(&e);
+        });
+    }
+}
@@ -0,0 +1,214 @@
+use soroban_sdk::{map, panic_with_error, Address, Env, Map, Vec};
+
+use crate::{
+    constants::{MAX_ANALYTICS_WINDOW, SECONDS_PER_DAY},
+    errors::PoolError,
+    storage::{self, DailyStats},
+};
+
+use super::{actions::Request, RequestType};
+
+/// Set the number of days of activity counters retained. Counters already recorded keep the
+/// retention they were last written with.
+///
+/// ### Arguments
+/// * `window` - The number of days to retain, or 0 to stop recording activity
+///
+/// ### Panics
+/// If the window is greater than the max analytics window
+pub fn execute_set_analytics_window(e: &Env, window: u32) {
+    if window > MAX_ANALYTICS_WINDOW {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    storage::set_analytics_window(e, window);
+}
+
+/// Record a submit against the current day's activity counters. Supplies and borrows are counted
+/// per request, and the user is counted once per day.
+///
+/// Does nothing if analytics are disabled.
+///
+/// ### Arguments
+/// * `from` - The address of the user whose positions are being modified
+/// * `requests` - The requests being processed
+pub fn record_activity(e: &Env, from: &Address, requests: &Vec<Request>) {
+    let window = storage::get_analytics_window(e);
+    if window == 0 {
+        return;
+    }
+
+    let day = e.ledger().timestamp() / SECONDS_PER_DAY;
+    let mut stats = storage::get_daily_stats(e, day).unwrap_or(DailyStats {
+        actors: 0,
+        supplies: 0,
+        borrows: 0,
+        supply_volume: map![e],
+        borrow_volume: map![e],
+    });
+    if storage::get_daily_actor(e, from) != Some(day) {
+        stats.actors += 1;
+        storage::set_daily_actor(e, from, day);
+    }
+    for request in requests.iter() {
+        match RequestType::from_u32(e, request.request_type) {
+            RequestType::Supply | RequestType::SupplyCollateral => {
+                stats.supplies += 1;
+                let volume = stats
+                    .supply_volume
+                    .get(request.address.clone())
+                    .unwrap_or(0);
+                stats
+                    .supply_volume
+                    .set(request.address, volume + request.amount);
+            }
+            RequestType::Borrow | RequestType::BorrowStable => {
+                stats.borrows += 1;
+                let volume = stats
+                    .borrow_volume
+                    .get(request.address.clone())
+                    .unwrap_or(0);
+                stats
+                    .borrow_volume
+                    .set(request.address, volume + request.amount);
+            }
+            _ => {}
+        }
+    }
+    storage::set_daily_stats(e, day, &stats, window);
+}
+
+/// Fetch the activity counters for the most recent days, capped at the analytics window
+///
+/// Returns a map of the number of days since the unix epoch to the counters for that day, for each
+/// day that had activity
+///
+/// ### Arguments
+/// * `days` - The number of days to fetch, including the current day
+pub fn get_daily_stats(e: &Env, days: u32) -> Map<u64, DailyStats> {
+    let days = days.min(storage::get_analytics_window(e)) as u64;
+    let today = e.ledger().timestamp() / SECONDS_PER_DAY;
+    let mut daily_stats = map![e];
+    for day in (today + 1).saturating_sub(days)..=today {
+        if let Some(stats) = storage::get_daily_stats(e, day) {
+            daily_stats.set(day, stats);
+        }
+    }
+    daily_stats
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testutils;
+
+    use super::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger},
+        vec,
+    };
+
+    #[test]
+    fn test_record_activity() {
+        let e = Env::default();
+        e.ledger().set_timestamp(10 * SECONDS_PER_DAY + 100);
+
+        let pool = testutils::create_pool(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let asset_0 = Address::generate(&e);
+        let asset_1 = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            // disabled by default
+            record_activity(
+                &e,
+                &samwise,
+                &vec![
+                    &e,
+                    Request {
+                        request_type: RequestType::Supply as u32,
+                        address: asset_0.clone(),
+                        amount: 10_0000000,
+                    },
+                ],
+            );
+            assert!(storage::get_daily_stats(&e, 10).is_none());
+
+            execute_set_analytics_window(&e, 7);
+            record_activity(
+                &e,
+                &samwise,
+                &vec![
+                    &e,
+                    Request {
+                        request_type: RequestType::SupplyCollateral as u32,
+                        address: asset_0.clone(),
+                        amount: 10_0000000,
+                    },
+                    Request {
+                        request_type: RequestType::Borrow as u32,
+                        address: asset_1.clone(),
+                        amount: 5_0000000,
+                    },
+                ],
+            );
+            record_activity(
+                &e,
+                &samwise,
+                &vec![
+                    &e,
+                    Request {
+                        request_type: RequestType::Repay as u32,
+                        address: asset_1.clone(),
+                        amount: 5_0000000,
+                    },
+                ],
+            );
+            record_activity(
+                &e,
+                &frodo,
+                &vec![
+                    &e,
+                    Request {
+                        request_type: RequestType::Supply as u32,
+                        address: asset_0.clone(),
+                        amount: 2_0000000,
+                    },
+                ],
+            );
+
+            let stats = storage::get_daily_stats(&e, 10).unwrap();
+            assert_eq!(stats.actors, 2);
+            assert_eq!(stats.supplies, 2);
+            assert_eq!(stats.borrows, 1);
+            assert_eq!(stats.supply_volume, map![&e, (asset_0.clone(), 12_0000000)]);
+            assert_eq!(stats.borrow_volume, map![&e, (asset_1.clone(), 5_0000000)]);
+        });
+
+        // the next day starts new counters
+        e.ledger().set_timestamp(11 * SECONDS_PER_DAY);
+        e.as_contract(&pool, || {
+            record_activity(&e, &samwise, &vec![&e]);
+
+            let daily_stats = get_daily_stats(&e, 30);
+            assert_eq!(daily_stats.len(), 2);
+            assert_eq!(daily_stats.get_unchecked(10).actors, 2);
+            let stats = daily_stats.get_unchecked(11);
+            assert_eq!(stats.actors, 1);
+            assert_eq!(stats.supplies, 0);
+            assert_eq!(stats.borrows, 0);
+
+            assert_eq!(get_daily_stats(&e, 1).keys(), vec![&e, 11]);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_set_analytics_window_over_max() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_analytics_window(&e, MAX_ANALYTICS_WINDOW + 1);
+        });
+    }
+}
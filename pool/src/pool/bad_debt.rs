@@ -1,8 +1,12 @@
 use soroban_sdk::{panic_with_error, Address, Env};
 
-use crate::{dependencies::BackstopClient, events::PoolEvents, storage, AuctionType, PoolError};
+use crate::{dependencies::Backstop, events::PoolEvents, storage, AuctionType, PoolError};
 
-use super::{calc_pool_backstop_threshold, Pool, User};
+use super::{
+    calc_pool_backstop_threshold,
+    deficit::{record_backstop_deficit, record_supplier_deficit},
+    Pool, User,
+};
 
 /// Handles any bad debt that exists for "user"
 pub fn bad_debt(e: &Env, user: &Address) {
@@ -15,7 +19,7 @@ pub fn bad_debt(e: &Env, user: &Address) {
         if storage::has_auction(e, &(AuctionType::BadDebtAuction as u32), &backstop) {
             panic_with_error!(e, PoolError::AuctionInProgress);
         }
-        check_and_handle_backstop_bad_debt(e, &mut pool, user, &mut user_state)
+        check_and_handle_backstop_bad_debt(e, &mut pool, &mut user_state)
     } else {
         if storage::has_auction(e, &(AuctionType::UserLiquidation as u32), &user) {
             panic_with_error!(e, PoolError::AuctionInProgress);
@@ -31,6 +35,15 @@ pub fn bad_debt(e: &Env, user: &Address) {
     }
 }
 
+/// Check if an address is exempt from the pool's maximum positions. Only the backstop can be exempt,
+/// as bad debt from any number of reserves can be assigned to it.
+///
+/// ### Arguments
+/// * `address` - The address to check
+pub fn is_max_positions_exempt(e: &Env, address: &Address) -> bool {
+    storage::get_backstop_positions_exempt(e) && address == &storage::get_backstop(e)
+}
+
 /// Check if a user has bad debt.
 ///
 /// If they do, pass the bad debt off to the backstop.
@@ -65,6 +78,8 @@ pub fn check_and_handle_user_bad_debt(
         for (reserve_index, liability_balance) in user_state.positions.liabilities.iter() {
             let asset = reserve_list.get_unchecked(reserve_index);
             let mut reserve = pool.load_reserve(e, &asset, true);
+            let bad_debt = reserve.to_asset_from_d_token(e, liability_balance);
+            record_backstop_deficit(e, &asset, bad_debt);
             backstop_state.add_liabilities(e, &mut reserve, liability_balance);
             user_state.remove_liabilities(e, &mut reserve, liability_balance);
             pool.cache_reserve(reserve);
@@ -100,12 +115,10 @@ pub fn check_and_handle_user_bad_debt(
 pub fn check_and_handle_backstop_bad_debt(
     e: &Env,
     pool: &mut Pool,
-    backstop_address: &Address,
     backstop_state: &mut User,
 ) -> bool {
     if backstop_state.has_liabilities() {
-        let backstop_client = BackstopClient::new(e, backstop_address);
-        let pool_backstop_data = backstop_client.pool_data(&e.current_contract_address());
+        let pool_backstop_data = Backstop::load(e).pool_data();
         let threshold = calc_pool_backstop_threshold(&pool_backstop_data);
         if threshold < 0_0000003 {
             // ~5% of threshold
@@ -113,6 +126,8 @@ pub fn check_and_handle_backstop_bad_debt(
             for (reserve_index, liability_balance) in backstop_state.positions.liabilities.iter() {
                 let res_asset_address = reserve_list.get_unchecked(reserve_index);
                 let mut reserve = pool.load_reserve(e, &res_asset_address, true);
+                let defaulted = reserve.to_asset_from_d_token(e, liability_balance);
+                record_supplier_deficit(e, &res_asset_address, defaulted);
                 backstop_state.default_liabilities(e, &mut reserve, liability_balance);
                 pool.cache_reserve(reserve);
 
@@ -129,6 +144,7 @@ mod tests {
     use super::*;
     use crate::{
         auctions::AuctionData,
+        pool::ReserveState,
         storage::PoolConfig,
         testutils::{
             self, create_backstop, create_blnd_token, create_comet_lp_pool, create_pool,
@@ -142,6 +158,25 @@ mod tests {
         vec, Address,
     };
 
+    /***** is_max_positions_exempt *****/
+
+    #[test]
+    fn test_is_max_positions_exempt() {
+        let e = Env::default();
+        let pool = create_pool(&e);
+        let backstop = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_backstop(&e, &backstop);
+            assert!(!is_max_positions_exempt(&e, &backstop));
+
+            storage::set_backstop_positions_exempt(&e, true);
+            assert!(is_max_positions_exempt(&e, &backstop));
+            assert!(!is_max_positions_exempt(&e, &samwise));
+        });
+    }
+
     /***** bad_debt *****/
 
     #[test]
@@ -198,6 +233,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 1,
             max_positions: 5,
+            min_debt: 0,
+            base: None,
         };
         let positions = Positions {
             liabilities: map![&e, (0, 1_5000000), (1, 50_987_654_321)],
@@ -266,6 +303,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 1,
             max_positions: 5,
+            min_debt: 0,
+            base: None,
         };
         let positions = Positions {
             liabilities: map![&e, (0, 1_5000000), (1, 50_987_654_321)],
@@ -282,6 +321,12 @@ mod tests {
             storage::set_user_positions(&e, &samwise, &positions);
             storage::set_user_positions(&e, &backstop_address, &backstop_positions);
 
+            // the bad debt is valued at the reserves' dRates accrued to the current ledger
+            let bad_debt_0 = ReserveState::load(&e, &pool_config, &underlying_0)
+                .to_asset_from_d_token(&e, 1_5000000);
+            let bad_debt_1 = ReserveState::load(&e, &pool_config, &underlying_1)
+                .to_asset_from_d_token(&e, 50_987_654_321);
+
             bad_debt(&e, &samwise);
 
             // assert user forgiven liabilities and assigned to backstop
@@ -298,6 +343,14 @@ mod tests {
             assert_eq!(post_backstop_positions.collateral.len(), 0);
             assert_eq!(post_backstop_positions.supply.len(), 0);
 
+            // assert the bad debt is recorded against the backstop
+            let deficit_0 = storage::get_res_deficit(&e, &underlying_0);
+            assert_eq!(deficit_0.backstop, bad_debt_0);
+            assert_eq!(deficit_0.suppliers, 0);
+            let deficit_1 = storage::get_res_deficit(&e, &underlying_1);
+            assert_eq!(deficit_1.backstop, bad_debt_1);
+            assert_eq!(deficit_1.suppliers, 0);
+
             // assert pool reserves updated
             let post_reserve_data_0 = storage::get_res_data(&e, &underlying_0);
             assert_eq!(post_reserve_data_0.last_time, 100);
@@ -369,6 +422,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 1,
             max_positions: 5,
+            min_debt: 0,
+            base: None,
         };
         let positions = Positions {
             liabilities: map![&e, (0, 1_5000000), (1, 50_987_654_321)],
@@ -454,6 +509,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 1,
             max_positions: 5,
+            min_debt: 0,
+            base: None,
         };
         let backstop_positions = Positions {
             liabilities: map![&e, (0, 1_5000000), (1, 3_5000000)],
@@ -521,6 +578,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 1,
             max_positions: 5,
+            min_debt: 0,
+            base: None,
         };
         let backstop_positions = Positions {
             liabilities: map![&e, (0, 1_5000000), (1, 3_5000000)],
@@ -612,6 +671,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 1,
             max_positions: 5,
+            min_debt: 0,
+            base: None,
         };
         let backstop_positions = Positions {
             liabilities: map![&e, (0, 1_5000000), (1, 3_5000000)],
@@ -692,6 +753,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 1,
             max_positions: 5,
+            min_debt: 0,
+            base: None,
         };
         let positions = Positions {
             liabilities: map![&e, (0, 1_5000000), (1, 50_987_654_321)],
@@ -772,6 +835,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 1,
             max_positions: 5,
+            min_debt: 0,
+            base: None,
         };
         let positions = Positions {
             liabilities: map![&e, (0, 1_5000000), (1, 50_987_654_321)],
@@ -788,6 +853,12 @@ mod tests {
             storage::set_user_positions(&e, &samwise, &positions);
             storage::set_user_positions(&e, &backstop_address, &backstop_positions);
 
+            // the bad debt is valued at the reserves' dRates accrued to the current ledger
+            let bad_debt_0 = ReserveState::load(&e, &pool_config, &underlying_0)
+                .to_asset_from_d_token(&e, 1_5000000);
+            let bad_debt_1 = ReserveState::load(&e, &pool_config, &underlying_1)
+                .to_asset_from_d_token(&e, 50_987_654_321);
+
             let mut pool = Pool::load(&e);
             let mut user = User::load(&e, &samwise);
 
@@ -807,6 +878,14 @@ mod tests {
             assert_eq!(post_backstop_positions.collateral.len(), 0);
             assert_eq!(post_backstop_positions.supply.len(), 0);
 
+            // assert the bad debt is recorded against the backstop
+            let deficit_0 = storage::get_res_deficit(&e, &underlying_0);
+            assert_eq!(deficit_0.backstop, bad_debt_0);
+            assert_eq!(deficit_0.suppliers, 0);
+            let deficit_1 = storage::get_res_deficit(&e, &underlying_1);
+            assert_eq!(deficit_1.backstop, bad_debt_1);
+            assert_eq!(deficit_1.suppliers, 0);
+
             // store pool reserves and assert they got updated
             pool.store_cached_reserves(&e);
             let post_reserve_data_0 = storage::get_res_data(&e, &underlying_0);
@@ -879,6 +958,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 1,
             max_positions: 5,
+            min_debt: 0,
+            base: None,
         };
         let backstop_positions = Positions {
             liabilities: map![&e, (0, 1_5000000), (1, 3_5000000)],
@@ -892,12 +973,7 @@ mod tests {
             let mut pool = Pool::load(&e);
             let mut backstop_user = User::load(&e, &backstop_address);
 
-            let result = check_and_handle_backstop_bad_debt(
-                &e,
-                &mut pool,
-                &backstop_address,
-                &mut backstop_user,
-            );
+            let result = check_and_handle_backstop_bad_debt(&e, &mut pool, &mut backstop_user);
             assert_eq!(result, false);
 
             // assert nothing happens to backstop position
@@ -969,6 +1045,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 1,
             max_positions: 5,
+            min_debt: 0,
+            base: None,
         };
         let backstop_positions = Positions {
             liabilities: map![&e],
@@ -982,12 +1060,7 @@ mod tests {
             let mut pool = Pool::load(&e);
             let mut backstop_user = User::load(&e, &backstop_address);
 
-            let result = check_and_handle_backstop_bad_debt(
-                &e,
-                &mut pool,
-                &backstop_address,
-                &mut backstop_user,
-            );
+            let result = check_and_handle_backstop_bad_debt(&e, &mut pool, &mut backstop_user);
             assert_eq!(result, false);
 
             // assert nothing happens to backstop position
@@ -1059,6 +1132,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 1,
             max_positions: 5,
+            min_debt: 0,
+            base: None,
         };
         let backstop_positions = Positions {
             liabilities: map![&e, (0, 1_5000000), (1, 3_5000000)],
@@ -1069,15 +1144,16 @@ mod tests {
             storage::set_pool_config(&e, &pool_config);
             storage::set_user_positions(&e, &backstop_address, &backstop_positions);
 
+            // the defaulted debt is valued at the reserves' dRates accrued to the current ledger
+            let defaulted_0 = ReserveState::load(&e, &pool_config, &underlying_0)
+                .to_asset_from_d_token(&e, 1_5000000);
+            let defaulted_1 = ReserveState::load(&e, &pool_config, &underlying_1)
+                .to_asset_from_d_token(&e, 3_5000000);
+
             let mut pool = Pool::load(&e);
             let mut backstop_user = User::load(&e, &backstop_address);
 
-            let result = check_and_handle_backstop_bad_debt(
-                &e,
-                &mut pool,
-                &backstop_address,
-                &mut backstop_user,
-            );
+            let result = check_and_handle_backstop_bad_debt(&e, &mut pool, &mut backstop_user);
             assert_eq!(result, true);
 
             // assert backstop user updated
@@ -1088,6 +1164,13 @@ mod tests {
             );
             assert_eq!(backstop_user.positions.supply, backstop_positions.supply);
 
+            // assert the defaulted debt is recorded against suppliers
+            let deficit_0 = storage::get_res_deficit(&e, &underlying_0);
+            assert_eq!(deficit_0.suppliers, defaulted_0);
+            assert_eq!(deficit_0.backstop, 0);
+            let deficit_1 = storage::get_res_deficit(&e, &underlying_1);
+            assert_eq!(deficit_1.suppliers, defaulted_1);
+            assert_eq!(deficit_1.backstop, 0);
             // store pool reserves and assert they got updated
             pool.store_cached_reserves(&e);
             let post_reserve_data_0 = storage::get_res_data(&e, &underlying_0);
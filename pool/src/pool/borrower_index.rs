@@ -0,0 +1,158 @@
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::{constants::MAX_BORROWER_PAGE, storage};
+
+use super::User;
+
+/// Update the borrower index for a user whose positions are being stored. Users with liabilities are
+/// added while the index is enabled, and users without liabilities are always removed, so the index
+/// never holds addresses that have fully repaid.
+///
+/// ### Arguments
+/// * `user` - The user whose positions are being stored
+pub fn update_borrower_index(e: &Env, user: &User) {
+    let count = storage::get_borrower_count(e);
+    if user.has_liabilities() {
+        if storage::get_borrower_index(e) && storage::get_borrower_slot(e, &user.address).is_none()
+        {
+            storage::set_borrower(e, count, &user.address);
+            storage::set_borrower_count(e, count + 1);
+        }
+    } else if count > 0 {
+        if let Some(slot) = storage::get_borrower_slot(e, &user.address) {
+            // move the last borrower into the vacated slot to keep the index dense
+            let last_slot = count - 1;
+            storage::remove_borrower(e, slot, &user.address);
+            if slot != last_slot {
+                let last_borrower = storage::get_borrower(e, last_slot).unwrap();
+                storage::remove_borrower(e, last_slot, &last_borrower);
+                storage::set_borrower(e, slot, &last_borrower);
+            }
+            storage::set_borrower_count(e, last_slot);
+        }
+    }
+}
+
+/// Fetch a page of the borrower index
+///
+/// Returns up to `limit` borrowers starting at slot `start`, capped at the max borrower page size
+///
+/// ### Arguments
+/// * `start` - The first slot of the page
+/// * `limit` - The maximum number of borrowers to return
+pub fn get_borrowers(e: &Env, start: u32, limit: u32) -> Vec<Address> {
+    let count = storage::get_borrower_count(e);
+    let end = start
+        .saturating_add(limit.min(MAX_BORROWER_PAGE))
+        .min(count);
+    let mut borrowers = Vec::new(e);
+    for slot in start..end {
+        if let Some(borrower) = storage::get_borrower(e, slot) {
+            borrowers.push_back(borrower);
+        }
+    }
+    borrowers
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{pool::Positions, testutils};
+
+    use super::*;
+    use soroban_sdk::{map, testutils::Address as _, vec};
+
+    fn borrower(e: &Env) -> User {
+        User {
+            address: Address::generate(e),
+            positions: Positions {
+                liabilities: map![e, (0, 10_0000000)],
+                collateral: map![e, (1, 20_0000000)],
+                supply: map![e],
+            },
+            stable_liabilities: None,
+            is_legacy: false,
+        }
+    }
+
+    #[test]
+    fn test_update_borrower_index() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        let mut samwise = borrower(&e);
+        let frodo = borrower(&e);
+        let pippin = borrower(&e);
+        e.as_contract(&pool, || {
+            storage::set_borrower_index(&e, true);
+            update_borrower_index(&e, &samwise);
+            update_borrower_index(&e, &frodo);
+            update_borrower_index(&e, &pippin);
+            // storing an indexed borrower again does not duplicate them
+            update_borrower_index(&e, &frodo);
+            assert_eq!(storage::get_borrower_count(&e), 3);
+            assert_eq!(
+                get_borrowers(&e, 0, 10),
+                vec![
+                    &e,
+                    samwise.address.clone(),
+                    frodo.address.clone(),
+                    pippin.address.clone()
+                ]
+            );
+            assert_eq!(get_borrowers(&e, 1, 1), vec![&e, frodo.address.clone()]);
+            assert_eq!(get_borrowers(&e, 3, 10), vec![&e]);
+
+            // fully repaying removes the borrower and moves the last borrower into their slot
+            samwise.positions.liabilities = map![&e];
+            update_borrower_index(&e, &samwise);
+            assert_eq!(storage::get_borrower_count(&e), 2);
+            assert_eq!(storage::get_borrower_slot(&e, &samwise.address), None);
+            assert_eq!(storage::get_borrower_slot(&e, &pippin.address), Some(0));
+            assert_eq!(
+                get_borrowers(&e, 0, 10),
+                vec![&e, pippin.address.clone(), frodo.address.clone()]
+            );
+        });
+    }
+
+    #[test]
+    fn test_update_borrower_index_disabled() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        let samwise = borrower(&e);
+        let mut frodo = borrower(&e);
+        e.as_contract(&pool, || {
+            storage::set_borrower_index(&e, true);
+            update_borrower_index(&e, &frodo);
+
+            // no new borrowers are added while disabled, but repaid borrowers are still removed
+            storage::set_borrower_index(&e, false);
+            update_borrower_index(&e, &samwise);
+            assert_eq!(get_borrowers(&e, 0, 10), vec![&e, frodo.address.clone()]);
+
+            frodo.positions.liabilities = map![&e];
+            update_borrower_index(&e, &frodo);
+            assert_eq!(storage::get_borrower_count(&e), 0);
+            assert_eq!(get_borrowers(&e, 0, 10), vec![&e]);
+        });
+    }
+
+    #[test]
+    fn test_get_borrowers_page_capped() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_borrower_index(&e, true);
+            for _ in 0..MAX_BORROWER_PAGE + 5 {
+                update_borrower_index(&e, &borrower(&e));
+            }
+            assert_eq!(get_borrowers(&e, 0, u32::MAX).len(), MAX_BORROWER_PAGE);
+            assert_eq!(get_borrowers(&e, MAX_BORROWER_PAGE, u32::MAX).len(), 5);
+        });
+    }
+}
@@ -0,0 +1,160 @@
+use soroban_sdk::{panic_with_error, Address, Env};
+
+use crate::{
+    constants::{C_FACTOR_RAMP_THRESHOLD, MAX_C_FACTOR_RAMP_LEDGERS},
+    errors::PoolError,
+    storage::{self, CFactorRamp},
+};
+
+use super::reserve::ReserveState;
+
+/// Set the number of ledgers significant collateral factor decreases are ramped over. Disabling
+/// ramping applies the reserve config's collateral factor to any in progress ramps at once.
+///
+/// ### Arguments
+/// * `ledgers` - The number of ledgers to ramp over, or 0 to disable ramping
+///
+/// ### Panics
+/// If the number of ledgers is greater than the max collateral factor ramp
+pub fn execute_set_c_factor_ramp(e: &Env, ledgers: u32) {
+    if ledgers > MAX_C_FACTOR_RAMP_LEDGERS {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+    storage::set_c_factor_ramp_ledgers(e, ledgers);
+}
+
+/// Start ramping a reserve's collateral factor if it is being lowered by at least the ramp threshold,
+/// otherwise remove any in progress ramp so the new collateral factor applies at once.
+///
+/// ### Arguments
+/// * `asset` - The reserve asset
+/// * `current_c_factor` - The collateral factor currently applied to the reserve
+/// * `new_c_factor` - The collateral factor being set in the reserve config
+pub fn update_c_factor_ramp(e: &Env, asset: &Address, current_c_factor: u32, new_c_factor: u32) {
+    let ledgers = storage::get_c_factor_ramp_ledgers(e);
+    let ramp = if ledgers > 0 && current_c_factor >= new_c_factor + C_FACTOR_RAMP_THRESHOLD {
+        let start_ledger = e.ledger().sequence();
+        Some(CFactorRamp {
+            from_c_factor: current_c_factor,
+            start_ledger,
+            end_ledger: start_ledger + ledgers,
+        })
+    } else {
+        None
+    };
+    storage::set_c_factor_ramp(e, asset, &ramp);
+}
+
+/// Apply any in progress collateral factor ramp to a reserve's config
+///
+/// Does nothing if ramping is disabled
+///
+/// ### Arguments
+/// * `reserve` - The reserve, with the config as stored on the ledger
+pub fn apply_c_factor_ramp(e: &Env, reserve: &mut ReserveState) {
+    if storage::get_c_factor_ramp_ledgers(e) == 0 {
+        return;
+    }
+    if let Some(ramp) = storage::get_c_factor_ramp(e, &reserve.asset) {
+        reserve.config.c_factor =
+            ramped_c_factor(&ramp, reserve.config.c_factor, e.ledger().sequence());
+    }
+}
+
+/// Calculate the collateral factor of a ramp at a ledger
+fn ramped_c_factor(ramp: &CFactorRamp, to_c_factor: u32, sequence: u32) -> u32 {
+    if sequence >= ramp.end_ledger || ramp.from_c_factor <= to_c_factor {
+        return to_c_factor;
+    }
+    let elapsed = sequence.saturating_sub(ramp.start_ledger) as u64;
+    let duration = (ramp.end_ledger - ramp.start_ledger) as u64;
+    let decrease = (ramp.from_c_factor - to_c_factor) as u64 * elapsed / duration;
+    ramp.from_c_factor - decrease as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{storage::PoolConfig, testutils};
+
+    use super::*;
+    use soroban_sdk::testutils::{Address as _, Ledger};
+
+    #[test]
+    fn test_ramped_c_factor() {
+        let ramp = CFactorRamp {
+            from_c_factor: 0_8000000,
+            start_ledger: 100,
+            end_ledger: 200,
+        };
+        assert_eq!(ramped_c_factor(&ramp, 0_5000000, 100), 0_8000000);
+        assert_eq!(ramped_c_factor(&ramp, 0_5000000, 125), 0_7250000);
+        assert_eq!(ramped_c_factor(&ramp, 0_5000000, 150), 0_6500000);
+        assert_eq!(ramped_c_factor(&ramp, 0_5000000, 200), 0_5000000);
+        assert_eq!(ramped_c_factor(&ramp, 0_5000000, 300), 0_5000000);
+        // a target above the start of the ramp applies at once
+        assert_eq!(ramped_c_factor(&ramp, 0_9000000, 150), 0_9000000);
+    }
+
+    #[test]
+    fn test_update_c_factor_ramp() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.ledger().set_sequence_number(100);
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, reserve_data) = testutils::default_reserve_meta();
+        reserve_config.c_factor = 0_5000000;
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            execute_set_c_factor_ramp(&e, 1000);
+
+            // decreases smaller than the threshold are not ramped
+            update_c_factor_ramp(&e, &underlying, 0_5400000, 0_5000000);
+            assert_eq!(storage::get_c_factor_ramp(&e, &underlying), None);
+
+            update_c_factor_ramp(&e, &underlying, 0_9000000, 0_5000000);
+            assert_eq!(
+                storage::get_c_factor_ramp(&e, &underlying),
+                Some(CFactorRamp {
+                    from_c_factor: 0_9000000,
+                    start_ledger: 100,
+                    end_ledger: 1100,
+                })
+            );
+
+            e.ledger().set_sequence_number(600);
+            let reserve = ReserveState::load(&e, &pool_config, &underlying);
+            assert_eq!(reserve.config.c_factor, 0_7000000);
+
+            // disabling ramping applies the reserve config's collateral factor at once
+            execute_set_c_factor_ramp(&e, 0);
+            let reserve = ReserveState::load(&e, &pool_config, &underlying);
+            assert_eq!(reserve.config.c_factor, 0_5000000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_set_c_factor_ramp_too_long() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_c_factor_ramp(&e, MAX_C_FACTOR_RAMP_LEDGERS + 1);
+        });
+    }
+}
@@ -0,0 +1,215 @@
+use soroban_sdk::{map, panic_with_error, Env};
+
+use crate::{
+    constants::MAX_CHECKPOINT_RETENTION,
+    errors::PoolError,
+    storage::{self, PositionCheckpoint},
+};
+
+use super::{Pool, User};
+
+/// Set the number of position checkpoints retained per user. Histories longer than the new
+/// retention are trimmed the next time the user submits.
+///
+/// ### Arguments
+/// * `retention` - The number of checkpoints to retain, or 0 to stop recording checkpoints
+///
+/// ### Panics
+/// If the retention is greater than the max checkpoint retention
+pub fn execute_set_checkpoint_retention(e: &Env, retention: u32) {
+    if retention > MAX_CHECKPOINT_RETENTION {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    storage::set_checkpoint_retention(e, retention);
+}
+
+/// Record a checkpoint of the user's positions and the current rates of the reserves they hold,
+/// dropping the oldest checkpoints past the configured retention.
+///
+/// Does nothing if checkpoint retention is disabled.
+///
+/// ### Arguments
+/// * `pool` - The pool
+/// * `user` - The user whose positions are being recorded
+pub fn record_checkpoint(e: &Env, pool: &mut Pool, user: &User) {
+    let retention = storage::get_checkpoint_retention(e);
+    if retention == 0 {
+        return;
+    }
+
+    let reserve_list = storage::get_res_list(e);
+    let mut b_rates = map![e];
+    let mut d_rates = map![e];
+    let positions = &user.positions;
+    for index in positions
+        .collateral
+        .keys()
+        .iter()
+        .chain(positions.supply.keys().iter())
+    {
+        if !b_rates.contains_key(index) {
+            let reserve = pool.load_reserve(e, &reserve_list.get_unchecked(index), false);
+            b_rates.set(index, reserve.data.b_rate);
+        }
+    }
+    for index in positions.liabilities.keys().iter() {
+        let reserve = pool.load_reserve(e, &reserve_list.get_unchecked(index), false);
+        d_rates.set(index, reserve.data.d_rate);
+    }
+
+    let mut history = storage::get_position_history(e, &user.address);
+    history.push_back(PositionCheckpoint {
+        ledger: e.ledger().sequence(),
+        timestamp: e.ledger().timestamp(),
+        positions: positions.clone(),
+        b_rates,
+        d_rates,
+    });
+    while history.len() > retention {
+        history.pop_front();
+    }
+    storage::set_position_history(e, &user.address, &history);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{pool::Positions, storage::PoolConfig, testutils};
+
+    use super::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger, LedgerInfo},
+        Address,
+    };
+
+    /// Setup a pool with two reserves
+    ///
+    /// Returns (pool, underlying_0, underlying_1)
+    fn setup_pool(e: &Env) -> (Address, Address, Address) {
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(e);
+        let pool = testutils::create_pool(e);
+        let (oracle, _) = testutils::create_mock_oracle(e);
+
+        let (underlying_0, _) = testutils::create_token_contract(e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, _) = testutils::create_token_contract(e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(e, &pool_config);
+        });
+        (pool, underlying_0, underlying_1)
+    }
+
+    #[test]
+    fn test_record_checkpoint() {
+        let e = Env::default();
+        let (pool_address, underlying_0, underlying_1) = setup_pool(&e);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&pool_address, || {
+            execute_set_checkpoint_retention(&e, 2);
+
+            let mut pool = Pool::load(&e);
+            let mut user = User {
+                address: samwise.clone(),
+                positions: Positions {
+                    collateral: map![&e, (0, 10_0000000)],
+                    liabilities: map![&e, (1, 2_0000000)],
+                    supply: map![&e, (0, 1_0000000)],
+                },
+                stable_liabilities: None,
+                is_legacy: false,
+            };
+            record_checkpoint(&e, &mut pool, &user);
+
+            let reserve_0 = pool.load_reserve(&e, &underlying_0, false);
+            let reserve_1 = pool.load_reserve(&e, &underlying_1, false);
+            let history = storage::get_position_history(&e, &samwise);
+            assert_eq!(history.len(), 1);
+            let checkpoint = history.get_unchecked(0);
+            assert_eq!(checkpoint.ledger, 1234);
+            assert_eq!(checkpoint.timestamp, 600);
+            assert_eq!(checkpoint.positions.collateral, user.positions.collateral);
+            assert_eq!(checkpoint.positions.liabilities, user.positions.liabilities);
+            assert_eq!(checkpoint.positions.supply, user.positions.supply);
+            assert_eq!(checkpoint.b_rates, map![&e, (0, reserve_0.data.b_rate)]);
+            assert_eq!(checkpoint.d_rates, map![&e, (1, reserve_1.data.d_rate)]);
+
+            // retention drops the oldest checkpoints
+            for i in 1..4 {
+                user.positions.collateral.set(0, 10_0000000 + i);
+                record_checkpoint(&e, &mut pool, &user);
+            }
+            let history = storage::get_position_history(&e, &samwise);
+            assert_eq!(history.len(), 2);
+            assert_eq!(
+                history.get_unchecked(0).positions.collateral,
+                map![&e, (0, 10_0000002)]
+            );
+            assert_eq!(
+                history.get_unchecked(1).positions.collateral,
+                map![&e, (0, 10_0000003)]
+            );
+        });
+    }
+
+    #[test]
+    fn test_record_checkpoint_disabled() {
+        let e = Env::default();
+        let (pool_address, _, _) = setup_pool(&e);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&pool_address, || {
+            let mut pool = Pool::load(&e);
+            let user = User {
+                address: samwise.clone(),
+                positions: Positions {
+                    collateral: map![&e, (0, 10_0000000)],
+                    liabilities: map![&e],
+                    supply: map![&e],
+                },
+                stable_liabilities: None,
+                is_legacy: false,
+            };
+            record_checkpoint(&e, &mut pool, &user);
+
+            assert_eq!(storage::get_position_history(&e, &samwise).len(), 0);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_set_checkpoint_retention_too_large() {
+        let e = Env::default();
+        let (pool_address, _, _) = setup_pool(&e);
+
+        e.as_contract(&pool_address, || {
+            execute_set_checkpoint_retention(&e, MAX_CHECKPOINT_RETENTION + 1);
+        });
+    }
+}
@@ -1,13 +1,25 @@
 use crate::{
-    constants::{MAX_RESERVES, SCALAR_12, SCALAR_7, SECONDS_PER_WEEK},
-    errors::PoolError,
+    constants::{
+        MAX_BSTOP_RATE_STEPS, MAX_RESERVES, ORACLE_PRICE_TOLERANCE, RESERVE_SEED_AMOUNT, SCALAR_12,
+        SCALAR_7, SECONDS_PER_WEEK,
+    },
+    errors::{PoolError, PoolErrorExt},
     storage::{
-        self, has_queued_reserve_set, PoolConfig, QueuedReserveInit, ReserveConfig, ReserveData,
+        self, has_queued_reserve_set, AuctionConfig, BstopRateStep, PoolBase, PoolConfig,
+        PriceBand, QueuedReserveInit, ReserveConfig, ReserveData, ReservePause,
     },
 };
-use soroban_sdk::{panic_with_error, Address, Env, String};
-
-use super::{pool::Pool, Reserve};
+use auction_math::AuctionCurve;
+use sep_40_oracle::{Asset, PriceFeedClient};
+use sep_41_token::TokenClient;
+use soroban_fixed_point_math::SorobanFixedPoint;
+use soroban_sdk::{panic_with_error, Address, Env, String, Vec};
+
+use super::{
+    c_factor_ramp::update_c_factor_ramp,
+    pool::Pool,
+    reserve_validation::{require_valid_reserve_config, require_valid_reserve_config_changes},
+};
 
 /// Initialize the pool
 ///
@@ -30,6 +42,8 @@ pub fn execute_initialize(
         bstop_rate: *bstop_rate,
         status: 6,
         max_positions: *max_positions,
+        min_debt: 0,
+        base: None,
     };
     require_valid_pool_config(e, &pool_config);
 
@@ -46,33 +60,225 @@ pub fn execute_update_pool(
     backstop_take_rate: u32,
     max_positions: u32,
     min_collateral: i128,
+    min_debt: i128,
 ) {
     let mut pool_config = storage::get_pool_config(e);
-    let res_list = storage::get_res_list(e);
     if pool_config.bstop_rate != backstop_take_rate {
-        for res in res_list {
-            let reserve = Reserve::load(e, &pool_config, &res);
-            reserve.store(e);
-        }
+        accrue_all_reserves(e);
     }
     pool_config.bstop_rate = backstop_take_rate;
     pool_config.max_positions = max_positions;
     pool_config.min_collateral = min_collateral;
+    pool_config.min_debt = min_debt;
 
     require_valid_pool_config(e, &pool_config);
     storage::set_pool_config(e, &pool_config);
 }
 
+/// Set the pool's backstop take rate schedule. Once a step's start time is reached, its rate
+/// replaces the pool's `bstop_rate` for interest accrual until the next step starts.
+///
+/// All reserves are accrued with the currently active rate before the schedule is replaced.
+///
+/// ### Arguments
+/// * `schedule` - The schedule, ordered by start time, or an empty schedule to remove it
+///
+/// ### Panics
+/// If the schedule has too many steps, is not strictly ordered by start time, or has an invalid rate
+pub fn execute_set_bstop_rate_schedule(e: &Env, schedule: &Vec<BstopRateStep>) {
+    if schedule.len() > MAX_BSTOP_RATE_STEPS {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+    let mut last_start_time: Option<u64> = None;
+    for step in schedule.iter() {
+        require_valid_bstop_rate(e, step.rate);
+        if last_start_time.is_some_and(|t| step.start_time <= t) {
+            panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+        }
+        last_start_time = Some(step.start_time);
+    }
+
+    accrue_all_reserves(e);
+    storage::set_bstop_rate_schedule(e, schedule);
+}
+
+/// Fetch the backstop take rate active at the current ledger timestamp. This is the rate of the
+/// latest schedule step that has started, or the pool's `bstop_rate` if no step has started.
+///
+/// ### Arguments
+/// * `pool_config` - The pool's config
+pub fn get_active_bstop_rate(e: &Env, pool_config: &PoolConfig) -> u32 {
+    let now = e.ledger().timestamp();
+    let mut rate = pool_config.bstop_rate;
+    for step in storage::get_bstop_rate_schedule(e).iter() {
+        if step.start_time > now {
+            break;
+        }
+        rate = step.rate;
+    }
+    rate
+}
+
+/// Accrue interest for every reserve with the active backstop take rate and store the results
+fn accrue_all_reserves(e: &Env) {
+    let mut pool = Pool::load(e);
+    for res in storage::get_res_list(e) {
+        let reserve = pool.load_reserve(e, &res, true);
+        pool.cache_reserve(reserve);
+    }
+    pool.store_cached_reserves(e);
+}
+
+/// Update the pool's auction config
+pub fn execute_set_auction_config(e: &Env, config: &AuctionConfig) {
+    require_valid_auction_config(e, config);
+    storage::set_auction_config(e, config);
+}
+
+/// Set or remove the price band for a reserve
+pub fn execute_set_price_band(e: &Env, asset: &Address, band: &Option<PriceBand>) {
+    if !storage::has_res(e, asset) {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    if let Some(band) = band {
+        // verify the band is positive and not inverted
+        if band.min_price <= 0 || band.min_price > band.max_price {
+            panic_with_error!(e, PoolError::InvalidReserveMetadata);
+        }
+    }
+    storage::set_price_band(e, asset, band);
+}
+
+/// Pause or unpause supplying to and borrowing from a reserve. Takes effect immediately, and does not
+/// modify the reserve's config.
+///
+/// ### Panics
+/// If the reserve does not exist
+pub fn execute_set_reserve_paused(e: &Env, asset: &Address, pause: &ReservePause) {
+    if !storage::has_res(e, asset) {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    storage::set_reserve_pause(e, asset, pause);
+}
+
+/// Set or remove the oracle asset used to price a reserve
+pub fn execute_set_oracle_asset(e: &Env, asset: &Address, oracle_asset: &Option<Asset>) {
+    if !storage::has_res(e, asset) {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    storage::set_oracle_asset(e, asset, oracle_asset);
+}
+
+/// Set or remove the rate strategy a reserve's borrow rate is delegated to. The reserve is accrued
+/// first, so interest up to the current ledger is charged at the previous rate.
+///
+/// ### Panics
+/// If the reserve does not exist or the strategy is the pool
+pub fn execute_set_rate_strategy(e: &Env, asset: &Address, strategy: &Option<Address>) {
+    if !storage::has_res(e, asset) || strategy.as_ref() == Some(&e.current_contract_address()) {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    let mut pool = Pool::load(e);
+    let reserve = pool.load_reserve(e, asset, true);
+    pool.cache_reserve(reserve);
+    pool.store_cached_reserves(e);
+    storage::set_rate_strategy(e, asset, strategy);
+}
+
+/// Set or remove the max annual borrow rate for a reserve. The reserve is accrued first, so interest
+/// up to the current ledger is charged at the previous rate.
+///
+/// ### Panics
+/// If the reserve does not exist or the max APR is zero
+pub fn execute_set_max_apr(e: &Env, asset: &Address, max_apr: &Option<u32>) {
+    if !storage::has_res(e, asset) {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    if *max_apr == Some(0) {
+        panic_with_error!(e, PoolError::InvalidReserveMetadata);
+    }
+    let mut pool = Pool::load(e);
+    let reserve = pool.load_reserve(e, asset, true);
+    pool.cache_reserve(reserve);
+    pool.store_cached_reserves(e);
+    storage::set_max_apr(e, asset, max_apr);
+}
+
+/// Set the oracle of the pool. Once the pool has left setup, the new oracle must price every reserve
+/// within the oracle price tolerance of the current oracle, so swapping the oracle can't instantly
+/// make positions liquidatable.
+///
+/// ### Panics
+/// If the new oracle reports an invalid price for a reserve, or a price that deviates from the current
+/// oracle by more than the tolerance
+pub fn execute_set_oracle(e: &Env, oracle: &Address) {
+    let mut pool_config = storage::get_pool_config(e);
+    // the new oracle must price assets in the pool's base asset
+    if let Some(base) = &pool_config.base {
+        if PriceFeedClient::new(e, oracle).base() != base.asset {
+            panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+        }
+    }
+    if pool_config.status != 6 {
+        let mut current_pool = Pool::load(e);
+        let mut new_pool = Pool::load(e);
+        new_pool.config.oracle = oracle.clone();
+        let current_scalar = 10i128.pow(current_pool.load_price_decimals(e));
+        let new_scalar = 10i128.pow(new_pool.load_price_decimals(e));
+        for asset in storage::get_res_list(e).iter() {
+            let current_price =
+                current_pool
+                    .load_price(e, &asset)
+                    .fixed_mul_floor(e, &new_scalar, &current_scalar);
+            let new_price = new_pool.load_price(e, &asset);
+            let max_deviation =
+                current_price.fixed_mul_floor(e, &ORACLE_PRICE_TOLERANCE, &SCALAR_7);
+            if (new_price - current_price).abs() > max_deviation {
+                panic_with_error!(e, PoolError::OracleDeviation);
+            }
+        }
+    }
+    pool_config.oracle = oracle.clone();
+    storage::set_pool_config(e, &pool_config);
+}
+
+/// Set or remove the base asset of the pool's oracle. Values in the base asset are converted to
+/// USD with the oracle's price of the base's USD asset where the pool requires a USD value.
+///
+/// ### Arguments
+/// * `base` - The base asset, or None if the oracle prices assets in USD
+///
+/// ### Panics
+/// If the base asset is not the oracle's base asset, or the oracle does not have a valid price
+/// for the base's USD asset
+pub fn execute_set_base(e: &Env, base: &Option<PoolBase>) {
+    let mut pool = Pool::load(e);
+    if let Some(base) = base {
+        if PriceFeedClient::new(e, &pool.config.oracle).base() != base.asset {
+            panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+        }
+        // panics if the USD asset does not have a valid price
+        pool.load_price(e, &base.usd_asset);
+    }
+    let mut pool_config = storage::get_pool_config(e);
+    pool_config.base = base.clone();
+    storage::set_pool_config(e, &pool_config);
+}
+
 /// Execute a queueing a reserve initialization for the pool
 pub fn execute_queue_set_reserve(e: &Env, asset: &Address, metadata: &ReserveConfig) {
     if has_queued_reserve_set(e, asset) {
         panic_with_error!(&e, PoolError::BadRequest)
     }
-    require_valid_reserve_metadata(e, metadata);
+    require_valid_reserve_config(e, metadata);
 
     // if the reserve config exists, ensure there are no invalid changes
     if storage::has_res(e, asset) {
-        require_valid_reserve_metadata_changes(e, &storage::get_res_config(e, asset), metadata);
+        let reserve_config = storage::get_res_config(e, asset);
+        if storage::is_res_retired(e, reserve_config.index) {
+            panic_with_error!(e, PoolErrorExt::ReserveRetired);
+        }
+        require_valid_reserve_config_changes(e, &reserve_config, metadata);
     }
 
     let mut unlock_time = e.ledger().timestamp();
@@ -110,6 +316,70 @@ pub fn execute_set_reserve(e: &Env, asset: &Address) -> u32 {
     initialize_reserve(e, asset, &queued_init.new_config)
 }
 
+/// Permanently retire a fully wound down reserve. The reserve keeps its index in the reserve list, so
+/// the index is never reused, but it can no longer be set or targeted by pool emissions. Emissions
+/// already accrued to the reserve's tokens can still be claimed.
+///
+/// Returns the index of the retired reserve
+///
+/// ### Panics
+/// If the reserve does not exist, is already retired, is enabled, or still has liabilities, backstop
+/// credit, or bTokens held by anyone other than the pool
+pub fn execute_retire_reserve(e: &Env, asset: &Address) -> u32 {
+    if !storage::has_res(e, asset) {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    let mut pool = Pool::load(e);
+    // @dev: Store the reserve to ledger manually
+    let reserve = pool.load_reserve(e, asset, false);
+    let index = reserve.config.index;
+    if storage::is_res_retired(e, index) {
+        panic_with_error!(e, PoolErrorExt::ReserveRetired);
+    }
+    if reserve.config.enabled {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+
+    // only the pool's seed bTokens can remain
+    let pool_b_tokens = storage::get_user_positions(e, &e.current_contract_address())
+        .supply
+        .get(index)
+        .unwrap_or(0);
+    if reserve.data.d_supply != 0
+        || reserve.data.backstop_credit != 0
+        || reserve.data.b_supply > pool_b_tokens
+    {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    reserve.store(e);
+
+    // stop targeting the reserve's tokens with future emissions
+    let mut pool_emissions = storage::get_pool_emissions(e);
+    if pool_emissions.contains_key(index * 2) || pool_emissions.contains_key(index * 2 + 1) {
+        pool_emissions.remove(index * 2);
+        pool_emissions.remove(index * 2 + 1);
+        storage::set_pool_emissions(e, &pool_emissions);
+    }
+
+    storage::del_queued_reserve_set(e, asset);
+    storage::set_res_retired(e, index);
+    index
+}
+
+/// Fetch the amount of underlying tokens, in the asset's base units, the admin supplies to a reserve
+/// when it is initialized. This is `RESERVE_SEED_AMOUNT` rescaled from 7 decimals to the reserve's
+/// decimals, and is at least 1 base unit.
+///
+/// ### Arguments
+/// * `decimals` - The decimals of the reserve asset
+pub fn reserve_seed_amount(decimals: u32) -> i128 {
+    if decimals >= 7 {
+        RESERVE_SEED_AMOUNT * 10i128.pow(decimals - 7)
+    } else {
+        (RESERVE_SEED_AMOUNT / 10i128.pow(7 - decimals)).max(1)
+    }
+}
+
 /// sets reserve data for the pool
 fn initialize_reserve(e: &Env, asset: &Address, config: &ReserveConfig) -> u32 {
     let index: u32;
@@ -120,8 +390,13 @@ fn initialize_reserve(e: &Env, asset: &Address, config: &ReserveConfig) -> u32 {
         // @dev: Store the reserve to ledger manually
         let mut reserve = pool.load_reserve(e, asset, false);
         index = reserve.config.index;
+        if storage::is_res_retired(e, index) {
+            panic_with_error!(e, PoolErrorExt::ReserveRetired);
+        }
         let reserve_config = storage::get_res_config(e, asset);
-        require_valid_reserve_metadata_changes(e, &reserve_config, config);
+        require_valid_reserve_config_changes(e, &reserve_config, config);
+        // the loaded reserve has any in progress collateral factor ramp applied
+        update_c_factor_ramp(e, asset, reserve.config.c_factor, config.c_factor);
         // if any of the IR parameters were changed reset the IR modifier
         if reserve_config.r_base != config.r_base
             || reserve_config.r_one != config.r_one
@@ -134,12 +409,25 @@ fn initialize_reserve(e: &Env, asset: &Address, config: &ReserveConfig) -> u32 {
         reserve.store(e);
     } else {
         index = storage::push_res_list(e, asset);
+
+        // seed the reserve with dead bTokens owned by the pool to prevent the first depositor
+        // from inflating the b_rate of an empty reserve
+        let pool_address = e.current_contract_address();
+        let seed_amount = reserve_seed_amount(config.decimals);
+        TokenClient::new(e, asset).transfer(&storage::get_admin(e), &pool_address, &seed_amount);
+        let (mut pool_positions, is_legacy) = storage::get_user_positions_entry(e, &pool_address);
+        pool_positions.supply.set(index, seed_amount);
+        storage::set_user_positions(e, &pool_address, &pool_positions);
+        if is_legacy {
+            storage::remove_legacy_user_positions(e, &pool_address);
+        }
+
         let init_data = ReserveData {
             b_rate: SCALAR_12,
             d_rate: SCALAR_12,
             ir_mod: SCALAR_7,
             d_supply: 0,
-            b_supply: 0,
+            b_supply: seed_amount,
             last_time: e.ledger().timestamp(),
             backstop_credit: 0,
         };
@@ -166,49 +454,65 @@ fn initialize_reserve(e: &Env, asset: &Address, config: &ReserveConfig) -> u32 {
     index
 }
 
-#[allow(clippy::zero_prefixed_literal)]
-fn require_valid_reserve_metadata(e: &Env, metadata: &ReserveConfig) {
-    const SCALAR_7_U32: u32 = SCALAR_7 as u32;
-    if metadata.decimals > 18
-        || metadata.c_factor > SCALAR_7_U32
-        || metadata.l_factor > SCALAR_7_U32
-        || metadata.util > 0_9000000
-        || (metadata.max_util > SCALAR_7_U32 || metadata.max_util <= metadata.util)
-        || metadata.r_base >= 1_0000000
-        || metadata.r_base < 0_0001000
-        || (metadata.r_one > metadata.r_two || metadata.r_two > metadata.r_three)
-        || (metadata.reactivity > 0_0001000)
-    {
-        panic_with_error!(e, PoolError::InvalidReserveMetadata);
+fn require_valid_pool_config(e: &Env, config: &PoolConfig) {
+    require_valid_bstop_rate(e, config.bstop_rate);
+
+    // verify max positions is at least 2 and less than 2 * max reserves
+    if config.max_positions < 2 || config.max_positions > 2 * MAX_RESERVES {
+        panic_with_error!(&e, PoolError::InvalidPoolConfigArgs);
     }
-}
 
-fn require_valid_reserve_metadata_changes(
-    e: &Env,
-    cur_config: &ReserveConfig,
-    metadata: &ReserveConfig,
-) {
-    if cur_config.decimals != metadata.decimals
-        || (cur_config.l_factor != 0 && metadata.l_factor == 0)
-    {
-        panic_with_error!(e, PoolError::InvalidReserveMetadata);
+    // verify min collateral and min debt are at least 0
+    if config.min_collateral < 0 || config.min_debt < 0 {
+        panic_with_error!(&e, PoolError::InvalidPoolConfigArgs);
     }
 }
 
-fn require_valid_pool_config(e: &Env, config: &PoolConfig) {
+fn require_valid_bstop_rate(e: &Env, bstop_rate: u32) {
     // ensure backstop is [0,1)
-    if config.bstop_rate >= SCALAR_7 as u32 {
+    if bstop_rate >= SCALAR_7 as u32 {
         panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
     }
+}
 
-    // verify max positions is at least 2 and less than 2 * max reserves
-    if config.max_positions < 2 || config.max_positions > 2 * MAX_RESERVES {
-        panic_with_error!(&e, PoolError::InvalidPoolConfigArgs);
+#[allow(clippy::zero_prefixed_literal)]
+fn require_valid_auction_config(e: &Env, config: &AuctionConfig) {
+    // verify the start delay is at most 100 blocks (~8 minutes) so liquidations can't be stalled
+    if config.start_delay > 100 {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
     }
 
-    // verify min collateral is at least 0
-    if config.min_collateral < 0 {
-        panic_with_error!(&e, PoolError::InvalidPoolConfigArgs);
+    // verify the soft liquidation threshold is at least 0 and the bonus is at most 25%
+    if config.soft_liq_max_value < 0 || config.soft_liq_bonus > 0_2500000 {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+
+    // verify the interest auction interval is at most 1 week so backstop interest can't be locked up
+    if config.interest_interval > 17280 * 7 || config.interest_min_value < 0 {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+    // verify the bad debt premium cap is at most 100%
+    if config.bad_debt_premium_cap > 1_0000000 {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+    // verify the bad debt bid premium is at most 25% so fillers are not priced out of early fills
+    if config.bad_debt_bid_premium > 0_2500000 {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+    // verify the minimum fill percent is at most 100%
+    if config.min_fill_pct > 100 {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+    // verify the liquidation target health factor is at least the 1.03 liquidations must restore
+    if config.liq_target_hf != 0 && config.liq_target_hf < 1_0300000 {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+    // verify each auction type uses a supported curve
+    if AuctionCurve::from_u32(config.liq_curve).is_none()
+        || AuctionCurve::from_u32(config.bad_debt_curve).is_none()
+        || AuctionCurve::from_u32(config.interest_curve).is_none()
+    {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
     }
 }
 
@@ -218,7 +522,11 @@ mod tests {
     use crate::testutils;
 
     use super::*;
-    use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
+    use soroban_sdk::{
+        map,
+        testutils::{Address as _, Ledger, LedgerInfo},
+        vec,
+    };
 
     #[test]
     fn test_execute_initialize() {
@@ -334,18 +642,96 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
 
             // happy path
-            execute_update_pool(&e, 0_2000000, 4u32, 2_0000000);
+            execute_update_pool(&e, 0_2000000, 4u32, 2_0000000, 5_0000000);
             let new_pool_config = storage::get_pool_config(&e);
             assert_eq!(new_pool_config.bstop_rate, 0_2000000);
             assert_eq!(new_pool_config.oracle, pool_config.oracle);
             assert_eq!(new_pool_config.status, pool_config.status);
             assert_eq!(new_pool_config.max_positions, 4u32);
             assert_eq!(new_pool_config.min_collateral, 2_0000000);
+            assert_eq!(new_pool_config.min_debt, 5_0000000);
+        });
+    }
+
+    #[test]
+    fn test_execute_set_bstop_rate_schedule() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.ledger().set_timestamp(1000);
+        let pool = testutils::create_pool(&e);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            execute_set_bstop_rate_schedule(
+                &e,
+                &vec![
+                    &e,
+                    BstopRateStep {
+                        start_time: 2000,
+                        rate: 0_2000000,
+                    },
+                    BstopRateStep {
+                        start_time: 3000,
+                        rate: 0_3000000,
+                    },
+                ],
+            );
+            assert_eq!(storage::get_bstop_rate_schedule(&e).len(), 2);
+            assert_eq!(Pool::load(&e).config.bstop_rate, 0_1000000);
+        });
+
+        e.ledger().set_timestamp(2500);
+        e.as_contract(&pool, || {
+            assert_eq!(Pool::load(&e).config.bstop_rate, 0_2000000);
+        });
+
+        e.ledger().set_timestamp(3000);
+        e.as_contract(&pool, || {
+            assert_eq!(Pool::load(&e).config.bstop_rate, 0_3000000);
+            // the stored rate is unchanged
+            assert_eq!(storage::get_pool_config(&e).bstop_rate, 0_1000000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_set_bstop_rate_schedule_unordered() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_bstop_rate_schedule(
+                &e,
+                &vec![
+                    &e,
+                    BstopRateStep {
+                        start_time: 2000,
+                        rate: 0_2000000,
+                    },
+                    BstopRateStep {
+                        start_time: 2000,
+                        rate: 0_3000000,
+                    },
+                ],
+            );
         });
     }
 
@@ -390,6 +776,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
 
         e.ledger().set(LedgerInfo {
@@ -405,7 +793,7 @@ mod tests {
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
 
-            execute_update_pool(&e, 0_2000000, 4u32, 2_0000000);
+            execute_update_pool(&e, 0_2000000, 4u32, 2_0000000, 0);
 
             let new_pool_config = storage::get_pool_config(&e);
             assert_eq!(new_pool_config.bstop_rate, 0_2000000);
@@ -438,11 +826,13 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
 
-            execute_update_pool(&e, 1_0000000, 4u32, 1_0000000);
+            execute_update_pool(&e, 1_0000000, 4u32, 1_0000000, 0);
         });
     }
 
@@ -459,17 +849,19 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
 
-            execute_update_pool(&e, 0_2000000, 4u32, -1);
+            execute_update_pool(&e, 0_2000000, 4u32, -1, 0);
         });
     }
 
     #[test]
     #[should_panic(expected = "Error(Contract, #1201)")]
-    fn test_execute_update_pool_validates_max_positions() {
+    fn test_execute_update_pool_validates_min_debt() {
         let e = Env::default();
         e.mock_all_auths();
         let pool = testutils::create_pool(&e);
@@ -480,127 +872,631 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
 
-            execute_update_pool(&e, 0_2000000, 1 + 2 * MAX_RESERVES, 2_0000000);
+            execute_update_pool(&e, 0_2000000, 4u32, 1_0000000, -1);
         });
     }
 
     #[test]
-    fn test_queue_set_reserve_status_6() {
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_update_pool_validates_max_positions() {
         let e = Env::default();
         e.mock_all_auths();
         let pool = testutils::create_pool(&e);
-        let bombadil = Address::generate(&e);
-
-        let (asset_id_0, _) = testutils::create_token_contract(&e, &bombadil);
 
-        let metadata = ReserveConfig {
-            index: 0,
-            decimals: 7,
-            c_factor: 0_7500000,
-            l_factor: 0_7500000,
-            util: 0_5000000,
-            max_util: 0_9500000,
-            r_base: 0_0100000,
-            r_one: 0_0500000,
-            r_two: 0_5000000,
-            r_three: 1_5000000,
-            reactivity: 100,
-            supply_cap: 1000000000000000000,
-            enabled: true,
-        };
         let pool_config = PoolConfig {
             oracle: Address::generate(&e),
             min_collateral: 1_0000000,
             bstop_rate: 0_1000000,
-            status: 6,
+            status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
-            execute_queue_set_reserve(&e, &asset_id_0, &metadata);
-            let queued_res = storage::get_queued_reserve_set(&e, &asset_id_0);
-            let res_config_0 = queued_res.new_config;
-            assert_eq!(res_config_0.decimals, metadata.decimals);
-            assert_eq!(res_config_0.c_factor, metadata.c_factor);
-            assert_eq!(res_config_0.l_factor, metadata.l_factor);
-            assert_eq!(res_config_0.util, metadata.util);
-            assert_eq!(res_config_0.r_base, metadata.r_base);
-            assert_eq!(res_config_0.r_one, metadata.r_one);
-            assert_eq!(res_config_0.r_one, metadata.r_one);
-            assert_eq!(res_config_0.r_two, metadata.r_two);
-            assert_eq!(res_config_0.r_three, metadata.r_three);
-            assert_eq!(res_config_0.reactivity, metadata.reactivity);
-            assert_eq!(res_config_0.index, 0);
-            assert_eq!(queued_res.unlock_time, e.ledger().timestamp());
+
+            execute_update_pool(&e, 0_2000000, 1 + 2 * MAX_RESERVES, 2_0000000, 0);
         });
     }
 
     #[test]
-    fn test_queue_set_reserve() {
+    fn test_execute_set_auction_config() {
         let e = Env::default();
         e.mock_all_auths();
         let pool = testutils::create_pool(&e);
-        let bombadil = Address::generate(&e);
-
-        let (asset_id_0, _) = testutils::create_token_contract(&e, &bombadil);
 
-        let metadata = ReserveConfig {
-            index: 0,
-            decimals: 7,
-            c_factor: 0_7500000,
-            l_factor: 0_7500000,
-            util: 0_5000000,
-            max_util: 0_9500000,
-            r_base: 0_0100000,
-            r_one: 0_0500000,
-            r_two: 0_5000000,
-            r_three: 1_5000000,
-            reactivity: 100,
-            supply_cap: 1000000000000000000,
-            enabled: true,
-        };
-        let pool_config = PoolConfig {
-            oracle: Address::generate(&e),
-            min_collateral: 1_0000000,
-            bstop_rate: 0_1000000,
-            status: 0,
-            max_positions: 2,
-        };
         e.as_contract(&pool, || {
-            storage::set_pool_config(&e, &pool_config);
-            execute_queue_set_reserve(&e, &asset_id_0, &metadata);
-            let queued_init = storage::get_queued_reserve_set(&e, &asset_id_0);
-            assert_eq!(queued_init.new_config.decimals, metadata.decimals);
-            assert_eq!(queued_init.new_config.c_factor, metadata.c_factor);
-            assert_eq!(queued_init.new_config.l_factor, metadata.l_factor);
-            assert_eq!(queued_init.new_config.util, metadata.util);
-            assert_eq!(queued_init.new_config.max_util, metadata.max_util);
-            assert_eq!(queued_init.new_config.r_base, metadata.r_base);
-            assert_eq!(queued_init.new_config.r_one, metadata.r_one);
-            assert_eq!(queued_init.new_config.r_two, metadata.r_two);
-            assert_eq!(queued_init.new_config.r_three, metadata.r_three);
-            assert_eq!(queued_init.new_config.reactivity, metadata.reactivity);
-            assert_eq!(queued_init.new_config.index, 0);
-            assert_eq!(
-                queued_init.unlock_time,
-                e.ledger().timestamp() + SECONDS_PER_WEEK
+            assert_eq!(storage::get_auction_config(&e).start_delay, 0);
+
+            execute_set_auction_config(
+                &e,
+                &AuctionConfig {
+                    start_delay: 100,
+                    soft_liq_max_value: 100_0000000,
+                    soft_liq_bonus: 0_2500000,
+                    interest_interval: 17280 * 7,
+                    interest_min_value: 500_0000000,
+                    bad_debt_premium_cap: 1_0000000,
+                    bad_debt_bid_premium: 0_2500000,
+                    liq_curve: 0,
+                    bad_debt_curve: 0,
+                    interest_curve: 0,
+                    min_fill_pct: 0,
+                    liq_target_hf: 0,
+                },
             );
+            let auction_config = storage::get_auction_config(&e);
+            assert_eq!(auction_config.start_delay, 100);
+            assert_eq!(auction_config.soft_liq_max_value, 100_0000000);
+            assert_eq!(auction_config.soft_liq_bonus, 0_2500000);
+            assert_eq!(auction_config.interest_interval, 17280 * 7);
+            assert_eq!(auction_config.interest_min_value, 500_0000000);
+            assert_eq!(auction_config.bad_debt_premium_cap, 1_0000000);
+            assert_eq!(auction_config.bad_debt_bid_premium, 0_2500000);
         });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1200)")]
-    fn test_queue_set_reserve_duplicate() {
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_set_auction_config_validates_start_delay() {
         let e = Env::default();
         e.mock_all_auths();
         let pool = testutils::create_pool(&e);
-        let bombadil = Address::generate(&e);
-
-        let (asset_id_0, _) = testutils::create_token_contract(&e, &bombadil);
+
+        e.as_contract(&pool, || {
+            execute_set_auction_config(
+                &e,
+                &AuctionConfig {
+                    start_delay: 101,
+                    soft_liq_max_value: 0,
+                    soft_liq_bonus: 0,
+                    interest_interval: 0,
+                    interest_min_value: 0,
+                    bad_debt_premium_cap: 0,
+                    bad_debt_bid_premium: 0,
+                    liq_curve: 0,
+                    bad_debt_curve: 0,
+                    interest_curve: 0,
+                    min_fill_pct: 0,
+                    liq_target_hf: 0,
+                },
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_set_auction_config_validates_soft_liq_max_value() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_auction_config(
+                &e,
+                &AuctionConfig {
+                    start_delay: 0,
+                    soft_liq_max_value: -1,
+                    soft_liq_bonus: 0,
+                    interest_interval: 0,
+                    interest_min_value: 0,
+                    bad_debt_premium_cap: 0,
+                    bad_debt_bid_premium: 0,
+                    liq_curve: 0,
+                    bad_debt_curve: 0,
+                    interest_curve: 0,
+                    min_fill_pct: 0,
+                    liq_target_hf: 0,
+                },
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_set_auction_config_validates_soft_liq_bonus() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_auction_config(
+                &e,
+                &AuctionConfig {
+                    start_delay: 0,
+                    soft_liq_max_value: 100_0000000,
+                    soft_liq_bonus: 0_2500001,
+                    interest_interval: 0,
+                    interest_min_value: 0,
+                    bad_debt_premium_cap: 0,
+                    bad_debt_bid_premium: 0,
+                    liq_curve: 0,
+                    bad_debt_curve: 0,
+                    interest_curve: 0,
+                    min_fill_pct: 0,
+                    liq_target_hf: 0,
+                },
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_set_auction_config_validates_interest_interval() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_auction_config(
+                &e,
+                &AuctionConfig {
+                    start_delay: 0,
+                    soft_liq_max_value: 0,
+                    soft_liq_bonus: 0,
+                    interest_interval: 17280 * 7 + 1,
+                    interest_min_value: 0,
+                    bad_debt_premium_cap: 0,
+                    bad_debt_bid_premium: 0,
+                    liq_curve: 0,
+                    bad_debt_curve: 0,
+                    interest_curve: 0,
+                    min_fill_pct: 0,
+                    liq_target_hf: 0,
+                },
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_set_auction_config_validates_bad_debt_premium_cap() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_auction_config(
+                &e,
+                &AuctionConfig {
+                    start_delay: 0,
+                    soft_liq_max_value: 0,
+                    soft_liq_bonus: 0,
+                    interest_interval: 0,
+                    interest_min_value: 0,
+                    bad_debt_premium_cap: 1_0000001,
+                    bad_debt_bid_premium: 0,
+                    liq_curve: 0,
+                    bad_debt_curve: 0,
+                    interest_curve: 0,
+                    min_fill_pct: 0,
+                    liq_target_hf: 0,
+                },
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_set_auction_config_validates_bad_debt_bid_premium() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_auction_config(
+                &e,
+                &AuctionConfig {
+                    start_delay: 0,
+                    soft_liq_max_value: 0,
+                    soft_liq_bonus: 0,
+                    interest_interval: 0,
+                    interest_min_value: 0,
+                    bad_debt_premium_cap: 0,
+                    bad_debt_bid_premium: 0_2500001,
+                    liq_curve: 0,
+                    bad_debt_curve: 0,
+                    interest_curve: 0,
+                    min_fill_pct: 0,
+                    liq_target_hf: 0,
+                },
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_set_auction_config_validates_curves() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_auction_config(
+                &e,
+                &AuctionConfig {
+                    start_delay: 0,
+                    soft_liq_max_value: 0,
+                    soft_liq_bonus: 0,
+                    interest_interval: 0,
+                    interest_min_value: 0,
+                    bad_debt_premium_cap: 0,
+                    bad_debt_bid_premium: 0,
+                    liq_curve: 0,
+                    bad_debt_curve: 3,
+                    interest_curve: 0,
+                    min_fill_pct: 0,
+                    liq_target_hf: 0,
+                },
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_set_auction_config_validates_min_fill_pct() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_auction_config(
+                &e,
+                &AuctionConfig {
+                    start_delay: 0,
+                    soft_liq_max_value: 0,
+                    soft_liq_bonus: 0,
+                    interest_interval: 0,
+                    interest_min_value: 0,
+                    bad_debt_premium_cap: 0,
+                    bad_debt_bid_premium: 0,
+                    liq_curve: 0,
+                    bad_debt_curve: 0,
+                    interest_curve: 0,
+                    min_fill_pct: 101,
+                    liq_target_hf: 0,
+                },
+            );
+        });
+    }
+
+    #[test]
+    fn test_execute_set_price_band() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            execute_set_price_band(
+                &e,
+                &underlying,
+                &Some(PriceBand {
+                    min_price: 0_9000000,
+                    max_price: 1_1000000,
+                }),
+            );
+            let band = storage::get_price_band(&e, &underlying).unwrap();
+            assert_eq!(band.min_price, 0_9000000);
+            assert_eq!(band.max_price, 1_1000000);
+
+            execute_set_price_band(&e, &underlying, &None);
+            assert!(storage::get_price_band(&e, &underlying).is_none());
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1202)")]
+    fn test_execute_set_price_band_validates_band() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            execute_set_price_band(
+                &e,
+                &underlying,
+                &Some(PriceBand {
+                    min_price: 1_1000000,
+                    max_price: 0_9000000,
+                }),
+            );
+        });
+    }
+
+    #[test]
+    fn test_execute_set_reserve_paused() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            let pause = ReservePause {
+                supply: false,
+                borrow: true,
+            };
+            execute_set_reserve_paused(&e, &underlying, &pause);
+            assert_eq!(storage::get_reserve_pause(&e, &underlying), pause);
+            // the reserve config is unchanged
+            let config = storage::get_res_config(&e, &underlying);
+            assert!(config.enabled);
+            assert_eq!(config.c_factor, reserve_config.c_factor);
+
+            execute_set_reserve_paused(&e, &underlying, &ReservePause::default());
+            assert_eq!(
+                storage::get_reserve_pause(&e, &underlying),
+                ReservePause::default()
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_execute_set_reserve_paused_requires_reserve() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_reserve_paused(
+                &e,
+                &Address::generate(&e),
+                &ReservePause {
+                    supply: true,
+                    borrow: true,
+                },
+            );
+        });
+    }
+
+    #[test]
+    fn test_execute_set_rate_strategy() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+        let strategy = Address::generate(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 6,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            execute_set_rate_strategy(&e, &underlying, &Some(strategy.clone()));
+            assert_eq!(storage::get_rate_strategy(&e, &underlying), Some(strategy));
+
+            execute_set_rate_strategy(&e, &underlying, &None);
+            assert_eq!(storage::get_rate_strategy(&e, &underlying), None);
+        });
+    }
+
+    #[test]
+    fn test_execute_set_max_apr() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            execute_set_max_apr(&e, &underlying, &Some(0_5000000));
+            assert_eq!(storage::get_max_apr(&e, &underlying), Some(0_5000000));
+
+            execute_set_max_apr(&e, &underlying, &None);
+            assert_eq!(storage::get_max_apr(&e, &underlying), None);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1202)")]
+    fn test_execute_set_max_apr_zero() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            execute_set_max_apr(&e, &underlying, &Some(0));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_execute_set_rate_strategy_requires_reserve() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_rate_strategy(&e, &Address::generate(&e), &Some(Address::generate(&e)));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_execute_set_price_band_requires_reserve() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_price_band(
+                &e,
+                &Address::generate(&e),
+                &Some(PriceBand {
+                    min_price: 0_9000000,
+                    max_price: 1_1000000,
+                }),
+            );
+        });
+    }
+
+    #[test]
+    fn test_queue_set_reserve_status_6() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (asset_id_0, _) = testutils::create_token_contract(&e, &bombadil);
+
+        let metadata = ReserveConfig {
+            index: 0,
+            decimals: 7,
+            c_factor: 0_7500000,
+            l_factor: 0_7500000,
+            util: 0_5000000,
+            max_util: 0_9500000,
+            r_base: 0_0100000,
+            r_one: 0_0500000,
+            r_two: 0_5000000,
+            r_three: 1_5000000,
+            reactivity: 100,
+            supply_cap: 1000000000000000000,
+            enabled: true,
+        };
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 6,
+            max_positions: 2,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            execute_queue_set_reserve(&e, &asset_id_0, &metadata);
+            let queued_res = storage::get_queued_reserve_set(&e, &asset_id_0);
+            let res_config_0 = queued_res.new_config;
+            assert_eq!(res_config_0.decimals, metadata.decimals);
+            assert_eq!(res_config_0.c_factor, metadata.c_factor);
+            assert_eq!(res_config_0.l_factor, metadata.l_factor);
+            assert_eq!(res_config_0.util, metadata.util);
+            assert_eq!(res_config_0.r_base, metadata.r_base);
+            assert_eq!(res_config_0.r_one, metadata.r_one);
+            assert_eq!(res_config_0.r_one, metadata.r_one);
+            assert_eq!(res_config_0.r_two, metadata.r_two);
+            assert_eq!(res_config_0.r_three, metadata.r_three);
+            assert_eq!(res_config_0.reactivity, metadata.reactivity);
+            assert_eq!(res_config_0.index, 0);
+            assert_eq!(queued_res.unlock_time, e.ledger().timestamp());
+        });
+    }
+
+    #[test]
+    fn test_queue_set_reserve() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (asset_id_0, _) = testutils::create_token_contract(&e, &bombadil);
+
+        let metadata = ReserveConfig {
+            index: 0,
+            decimals: 7,
+            c_factor: 0_7500000,
+            l_factor: 0_7500000,
+            util: 0_5000000,
+            max_util: 0_9500000,
+            r_base: 0_0100000,
+            r_one: 0_0500000,
+            r_two: 0_5000000,
+            r_three: 1_5000000,
+            reactivity: 100,
+            supply_cap: 1000000000000000000,
+            enabled: true,
+        };
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            execute_queue_set_reserve(&e, &asset_id_0, &metadata);
+            let queued_init = storage::get_queued_reserve_set(&e, &asset_id_0);
+            assert_eq!(queued_init.new_config.decimals, metadata.decimals);
+            assert_eq!(queued_init.new_config.c_factor, metadata.c_factor);
+            assert_eq!(queued_init.new_config.l_factor, metadata.l_factor);
+            assert_eq!(queued_init.new_config.util, metadata.util);
+            assert_eq!(queued_init.new_config.max_util, metadata.max_util);
+            assert_eq!(queued_init.new_config.r_base, metadata.r_base);
+            assert_eq!(queued_init.new_config.r_one, metadata.r_one);
+            assert_eq!(queued_init.new_config.r_two, metadata.r_two);
+            assert_eq!(queued_init.new_config.r_three, metadata.r_three);
+            assert_eq!(queued_init.new_config.reactivity, metadata.reactivity);
+            assert_eq!(queued_init.new_config.index, 0);
+            assert_eq!(
+                queued_init.unlock_time,
+                e.ledger().timestamp() + SECONDS_PER_WEEK
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_queue_set_reserve_duplicate() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (asset_id_0, _) = testutils::create_token_contract(&e, &bombadil);
 
         let metadata = ReserveConfig {
             index: 0,
@@ -623,6 +1519,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 6,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -637,7 +1535,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1202)")]
+    #[should_panic(expected = "Error(Contract, #1233)")]
     fn test_queue_set_reserve_validates_metadata() {
         let e = Env::default();
         e.mock_all_auths();
@@ -666,6 +1564,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -718,6 +1618,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 5,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -743,7 +1645,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1202)")]
+    #[should_panic(expected = "Error(Contract, #1232)")]
     fn test_queue_set_reserve_decimals_changed() {
         let e = Env::default();
         e.mock_all_auths();
@@ -788,6 +1690,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 6,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -797,7 +1701,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1202)")]
+    #[should_panic(expected = "Error(Contract, #1233)")]
     fn test_queue_set_reserve_lf_removed() {
         let e = Env::default();
         e.mock_all_auths();
@@ -842,6 +1746,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 6,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -890,14 +1796,68 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_reserve_seed_amount() {
+        assert_eq!(reserve_seed_amount(0), 1);
+        assert_eq!(reserve_seed_amount(2), 1);
+        assert_eq!(reserve_seed_amount(6), 10_000);
+        assert_eq!(reserve_seed_amount(7), RESERVE_SEED_AMOUNT);
+        assert_eq!(reserve_seed_amount(9), 10_000_000);
+        assert_eq!(reserve_seed_amount(18), 10i128.pow(16));
+    }
+
+    #[test]
+    fn test_execute_set_reserve_seed_scales_with_decimals() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (asset_id_0, asset_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (asset_id_1, asset_1_client) = testutils::create_token_contract(&e, &bombadil);
+        asset_0_client.mint(&bombadil, &1);
+        asset_1_client.mint(&bombadil, &10i128.pow(16));
+
+        let (mut config_0, _) = testutils::default_reserve_meta();
+        config_0.decimals = 0;
+        let mut config_1 = config_0.clone();
+        config_1.decimals = 18;
+        e.as_contract(&pool, || {
+            storage::set_admin(&e, &bombadil);
+            for (asset, config) in [(&asset_id_0, &config_0), (&asset_id_1, &config_1)] {
+                storage::set_queued_reserve_set(
+                    &e,
+                    &QueuedReserveInit {
+                        new_config: config.clone(),
+                        unlock_time: e.ledger().timestamp(),
+                    },
+                    asset,
+                );
+                execute_set_reserve(&e, asset);
+            }
+
+            assert_eq!(storage::get_res_data(&e, &asset_id_0).b_supply, 1);
+            assert_eq!(
+                storage::get_res_data(&e, &asset_id_1).b_supply,
+                10i128.pow(16)
+            );
+            let pool_positions = storage::get_user_positions(&e, &pool);
+            assert_eq!(pool_positions.supply.get_unchecked(0), 1);
+            assert_eq!(pool_positions.supply.get_unchecked(1), 10i128.pow(16));
+        });
+        assert_eq!(asset_0_client.balance(&pool), 1);
+        assert_eq!(asset_1_client.balance(&pool), 10i128.pow(16));
+    }
+
     #[test]
     fn test_execute_set_reserve_first_reserve() {
         let e = Env::default();
-        e.mock_all_auths();
+        e.mock_all_auths_allowing_non_root_auth();
         let pool = testutils::create_pool(&e);
         let bombadil = Address::generate(&e);
 
-        let (asset_id_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (asset_id_0, asset_0_client) = testutils::create_token_contract(&e, &bombadil);
+        asset_0_client.mint(&bombadil, &RESERVE_SEED_AMOUNT);
 
         let metadata = ReserveConfig {
             index: 0,
@@ -915,6 +1875,7 @@ mod tests {
             enabled: true,
         };
         e.as_contract(&pool, || {
+            storage::set_admin(&e, &bombadil);
             storage::set_queued_reserve_set(
                 &e,
                 &QueuedReserveInit {
@@ -935,7 +1896,16 @@ mod tests {
             assert_eq!(res_config_0.r_three, metadata.r_three);
             assert_eq!(res_config_0.reactivity, metadata.reactivity);
             assert_eq!(res_config_0.index, 0);
+
+            // the reserve is seeded with dead bTokens owned by the pool
+            let res_data_0 = storage::get_res_data(&e, &asset_id_0);
+            assert_eq!(res_data_0.b_supply, RESERVE_SEED_AMOUNT);
+            assert_eq!(res_data_0.d_supply, 0);
+            let pool_positions = storage::get_user_positions(&e, &pool);
+            assert_eq!(pool_positions.supply.get_unchecked(0), RESERVE_SEED_AMOUNT);
         });
+        assert_eq!(asset_0_client.balance(&bombadil), 0);
+        assert_eq!(asset_0_client.balance(&pool), RESERVE_SEED_AMOUNT);
     }
 
     #[test]
@@ -1023,6 +1993,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -1058,6 +2030,67 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_execute_set_reserve_update_ramps_c_factor() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 500,
+            protocol_version: 22,
+            sequence_number: 100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        let mut new_metadata = reserve_config.clone();
+        new_metadata.c_factor = reserve_config.c_factor - 0_2000000;
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_c_factor_ramp_ledgers(&e, 1000);
+
+            storage::set_queued_reserve_set(
+                &e,
+                &QueuedReserveInit {
+                    new_config: new_metadata.clone(),
+                    unlock_time: e.ledger().timestamp(),
+                },
+                &underlying,
+            );
+            execute_set_reserve(&e, &underlying);
+            let res_config_updated = storage::get_res_config(&e, &underlying);
+            assert_eq!(res_config_updated.c_factor, new_metadata.c_factor);
+
+            // the decrease is ramped from the previous collateral factor
+            let ramp = storage::get_c_factor_ramp(&e, &underlying).unwrap();
+            assert_eq!(ramp.from_c_factor, reserve_config.c_factor);
+            assert_eq!(ramp.start_ledger, 100);
+            assert_eq!(ramp.end_ledger, 1100);
+            let mut pool = Pool::load(&e);
+            let reserve = pool.load_reserve(&e, &underlying, false);
+            assert_eq!(reserve.config.c_factor, reserve_config.c_factor);
+        });
+    }
+
     #[test]
     fn test_execute_set_reserve_update_resets_ir_mod() {
         let e = Env::default();
@@ -1101,6 +2134,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -1136,7 +2171,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1202)")]
+    #[should_panic(expected = "Error(Contract, #1232)")]
     fn test_execute_set_reserve_validates_decimals_stay_same() {
         let e = Env::default();
         e.mock_all_auths();
@@ -1180,6 +2215,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -1199,12 +2236,14 @@ mod tests {
     #[test]
     fn test_initialize_reserve_sets_index() {
         let e = Env::default();
-        e.mock_all_auths();
+        e.mock_all_auths_allowing_non_root_auth();
         let pool = testutils::create_pool(&e);
         let bombadil = Address::generate(&e);
 
-        let (asset_id_0, _) = testutils::create_token_contract(&e, &bombadil);
-        let (asset_id_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (asset_id_0, asset_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (asset_id_1, asset_1_client) = testutils::create_token_contract(&e, &bombadil);
+        asset_0_client.mint(&bombadil, &RESERVE_SEED_AMOUNT);
+        asset_1_client.mint(&bombadil, &RESERVE_SEED_AMOUNT);
 
         let metadata = ReserveConfig {
             index: 0,
@@ -1222,254 +2261,404 @@ mod tests {
             enabled: true,
         };
         e.as_contract(&pool, || {
-            initialize_reserve(&e, &asset_id_0, &metadata);
+            storage::set_admin(&e, &bombadil);
+            initialize_reserve(&e, &asset_id_0, &metadata);
+
+            initialize_reserve(&e, &asset_id_1, &metadata);
+            let res_config_0 = storage::get_res_config(&e, &asset_id_0);
+            let res_config_1 = storage::get_res_config(&e, &asset_id_1);
+            assert_eq!(res_config_0.decimals, metadata.decimals);
+            assert_eq!(res_config_0.c_factor, metadata.c_factor);
+            assert_eq!(res_config_0.l_factor, metadata.l_factor);
+            assert_eq!(res_config_0.util, metadata.util);
+            assert_eq!(res_config_0.max_util, metadata.max_util);
+            assert_eq!(res_config_0.r_one, metadata.r_one);
+            assert_eq!(res_config_0.r_two, metadata.r_two);
+            assert_eq!(res_config_0.r_three, metadata.r_three);
+            assert_eq!(res_config_0.reactivity, metadata.reactivity);
+            assert_eq!(res_config_0.index, 0);
+            assert_eq!(res_config_1.index, 1);
+
+            let pool_positions = storage::get_user_positions(&e, &pool);
+            assert_eq!(pool_positions.supply.get_unchecked(0), RESERVE_SEED_AMOUNT);
+            assert_eq!(pool_positions.supply.get_unchecked(1), RESERVE_SEED_AMOUNT);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_push_res_list_duplicate_asset() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+        let asset = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            assert_eq!(storage::push_res_list(&e, &asset), 0);
+            storage::push_res_list(&e, &asset);
+        });
+    }
+
+    /// Setup a pool with an enabled reserve and a disabled reserve only holding the pool's seed bTokens
+    fn setup_retire_reserve_pool(e: &Env) -> (Address, Address, Address) {
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        let pool = testutils::create_pool(e);
+        let bombadil = Address::generate(e);
+
+        let (underlying_0, _) = testutils::create_token_contract(e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.last_time = 12345;
+        testutils::create_reserve(e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, _) = testutils::create_token_contract(e, &bombadil);
+        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_config.enabled = false;
+        reserve_data.b_supply = RESERVE_SEED_AMOUNT;
+        reserve_data.d_supply = 0;
+        reserve_data.last_time = 12345;
+        testutils::create_reserve(e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(e, &pool_config);
+            let mut pool_positions = storage::get_user_positions(e, &pool);
+            pool_positions.supply.set(1, RESERVE_SEED_AMOUNT);
+            storage::set_user_positions(e, &pool, &pool_positions);
+        });
+        (pool, underlying_0, underlying_1)
+    }
 
-            initialize_reserve(&e, &asset_id_1, &metadata);
-            let res_config_0 = storage::get_res_config(&e, &asset_id_0);
-            let res_config_1 = storage::get_res_config(&e, &asset_id_1);
-            assert_eq!(res_config_0.decimals, metadata.decimals);
-            assert_eq!(res_config_0.c_factor, metadata.c_factor);
-            assert_eq!(res_config_0.l_factor, metadata.l_factor);
-            assert_eq!(res_config_0.util, metadata.util);
-            assert_eq!(res_config_0.max_util, metadata.max_util);
-            assert_eq!(res_config_0.r_one, metadata.r_one);
-            assert_eq!(res_config_0.r_two, metadata.r_two);
-            assert_eq!(res_config_0.r_three, metadata.r_three);
-            assert_eq!(res_config_0.reactivity, metadata.reactivity);
-            assert_eq!(res_config_0.index, 0);
-            assert_eq!(res_config_1.index, 1);
+    #[test]
+    fn test_execute_retire_reserve() {
+        let e = Env::default();
+        let (pool, _, underlying_1) = setup_retire_reserve_pool(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_pool_emissions(
+                &e,
+                &map![&e, (0, 0_5000000), (2, 0_2500000), (3, 0_2500000)],
+            );
+
+            let index = execute_retire_reserve(&e, &underlying_1);
+            assert_eq!(index, 1);
+            assert!(storage::is_res_retired(&e, 1));
+            assert!(!storage::is_res_retired(&e, 0));
+            // the index is kept in the reserve list
+            assert_eq!(storage::get_res_list(&e).get_unchecked(1), underlying_1);
+            assert_eq!(storage::get_pool_emissions(&e), map![&e, (0, 0_5000000)]);
         });
     }
 
     #[test]
-    fn test_validate_reserve_metadata() {
+    #[should_panic(expected = "Error(Contract, #1244)")]
+    fn test_execute_retire_reserve_twice() {
         let e = Env::default();
+        let (pool, _, underlying_1) = setup_retire_reserve_pool(&e);
 
-        // valid
-        let metadata = ReserveConfig {
-            index: 0,
-            decimals: 18,
-            c_factor: 0_7500000,
-            l_factor: 0_7500000,
-            util: 0_5000000,
-            max_util: 0_9500000,
-            r_base: 0_0001000,
-            r_one: 0_0500000,
-            r_two: 0_5000000,
-            r_three: 1_5000000,
-            reactivity: 100,
-            supply_cap: 1000000000000000000,
-            enabled: true,
-        };
-        require_valid_reserve_metadata(&e, &metadata);
-        // no panic
-        assert!(true);
+        e.as_contract(&pool, || {
+            execute_retire_reserve(&e, &underlying_1);
+            execute_retire_reserve(&e, &underlying_1);
+        });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1202)")]
-    fn test_validate_reserve_metadata_validates_decimals() {
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_execute_retire_reserve_enabled() {
         let e = Env::default();
+        let (pool, underlying_0, _) = setup_retire_reserve_pool(&e);
 
-        let metadata = ReserveConfig {
-            index: 0,
-            decimals: 19,
-            c_factor: 0_7500000,
-            l_factor: 0_7500000,
-            util: 0_5000000,
-            max_util: 0_9500000,
-            r_base: 0_0001000,
-            r_one: 0_0500000,
-            r_two: 0_5000000,
-            r_three: 1_5000000,
-            reactivity: 100,
-            supply_cap: 1000000000000000000,
-            enabled: true,
-        };
-        require_valid_reserve_metadata(&e, &metadata);
+        e.as_contract(&pool, || {
+            execute_retire_reserve(&e, &underlying_0);
+        });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1202)")]
-    fn test_validate_reserve_metadata_validates_c_factor() {
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_execute_retire_reserve_with_supply() {
         let e = Env::default();
+        let (pool, _, underlying_1) = setup_retire_reserve_pool(&e);
 
-        let metadata = ReserveConfig {
-            index: 0,
-            decimals: 18,
-            c_factor: 1_0000001,
-            l_factor: 0_7500000,
-            util: 0_5000000,
-            max_util: 0_9500000,
-            r_base: 0_0001000,
-            r_one: 0_0500000,
-            r_two: 0_5000000,
-            r_three: 1_5000000,
-            reactivity: 100,
-            supply_cap: 1000000000000000000,
-            enabled: true,
-        };
-        require_valid_reserve_metadata(&e, &metadata);
+        e.as_contract(&pool, || {
+            // a user still holds bTokens
+            let mut reserve_data = storage::get_res_data(&e, &underlying_1);
+            reserve_data.b_supply += 1;
+            storage::set_res_data(&e, &underlying_1, &reserve_data);
+
+            execute_retire_reserve(&e, &underlying_1);
+        });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1202)")]
-    fn test_validate_reserve_metadata_validates_l_factor() {
+    #[should_panic(expected = "Error(Contract, #1244)")]
+    fn test_queue_set_reserve_retired() {
         let e = Env::default();
+        let (pool, _, underlying_1) = setup_retire_reserve_pool(&e);
 
-        let metadata = ReserveConfig {
-            index: 0,
-            decimals: 18,
-            c_factor: 0_7500000,
-            l_factor: 1_0000001,
-            util: 0_5000000,
-            max_util: 0_9500000,
-            r_base: 0_0001000,
-            r_one: 0_0500000,
-            r_two: 0_5000000,
-            r_three: 1_5000000,
-            reactivity: 100,
-            supply_cap: 1000000000000000000,
-            enabled: true,
-        };
-        require_valid_reserve_metadata(&e, &metadata);
+        e.as_contract(&pool, || {
+            execute_retire_reserve(&e, &underlying_1);
+
+            let mut metadata = storage::get_res_config(&e, &underlying_1);
+            metadata.enabled = true;
+            execute_queue_set_reserve(&e, &underlying_1, &metadata);
+        });
+    }
+
+    fn setup_oracle(e: &Env, assets: &Vec<Address>, decimals: u32, prices: Vec<i128>) -> Address {
+        let base = sep_40_oracle::testutils::Asset::Other(soroban_sdk::Symbol::new(e, "USD"));
+        setup_oracle_with_base(e, &base, assets, decimals, prices)
+    }
+
+    fn setup_oracle_with_base(
+        e: &Env,
+        base: &sep_40_oracle::testutils::Asset,
+        assets: &Vec<Address>,
+        decimals: u32,
+        prices: Vec<i128>,
+    ) -> Address {
+        use sep_40_oracle::testutils::Asset;
+
+        let (oracle, oracle_client) = testutils::create_mock_oracle(e);
+        let mut oracle_assets = vec![e];
+        for asset in assets.iter() {
+            oracle_assets.push_back(Asset::Stellar(asset));
+        }
+        oracle_client.set_data(&Address::generate(e), base, &oracle_assets, &decimals, &300);
+        oracle_client.set_price_stable(&prices);
+        oracle
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1202)")]
-    fn test_validate_reserve_metadata_validates_util() {
+    fn test_execute_set_oracle() {
         let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
 
-        let metadata = ReserveConfig {
-            index: 0,
-            decimals: 18,
-            c_factor: 0_7500000,
-            l_factor: 0_7500000,
-            util: 0_9000001,
-            max_util: 0_9500000,
-            r_base: 0_0001000,
-            r_one: 0_0500000,
-            r_two: 0_5000000,
-            r_three: 1_5000000,
-            reactivity: 100,
-            supply_cap: 1000000000000000000,
-            enabled: true,
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+        let assets = vec![&e, underlying_0, underlying_1];
+
+        let oracle = setup_oracle(&e, &assets, 7, vec![&e, 1_0000000, 2000_0000000]);
+        // prices in 9 decimals within 5% of the current oracle
+        let new_oracle = setup_oracle(&e, &assets, 9, vec![&e, 1_040000000, 1910_000000000]);
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
-        require_valid_reserve_metadata(&e, &metadata);
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            execute_set_oracle(&e, &new_oracle);
+            assert_eq!(storage::get_pool_config(&e).oracle, new_oracle);
+        });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1202)")]
-    fn test_validate_reserve_metadata_validates_max_util() {
+    #[should_panic(expected = "Error(Contract, #1241)")]
+    fn test_execute_set_oracle_price_deviation() {
         let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
 
-        let metadata = ReserveConfig {
-            index: 0,
-            decimals: 18,
-            c_factor: 0_7500000,
-            l_factor: 0_7500000,
-            util: 0_5000000,
-            max_util: 1_0000001,
-            r_base: 0_0001000,
-            r_one: 0_0500000,
-            r_two: 0_5000000,
-            r_three: 1_5000000,
-            reactivity: 100,
-            supply_cap: 1000000000000000000,
-            enabled: true,
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+        let assets = vec![&e, underlying_0, underlying_1];
+
+        let oracle = setup_oracle(&e, &assets, 7, vec![&e, 1_0000000, 2000_0000000]);
+        let new_oracle = setup_oracle(&e, &assets, 7, vec![&e, 1_0000000, 1899_0000000]);
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
-        require_valid_reserve_metadata(&e, &metadata);
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            execute_set_oracle(&e, &new_oracle);
+        });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1202)")]
-    fn test_validate_reserve_metadata_validates_r_base_too_high() {
+    fn test_execute_set_oracle_setup_skips_price_check() {
         let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
 
-        let metadata = ReserveConfig {
-            index: 0,
-            decimals: 18,
-            c_factor: 0_7500000,
-            l_factor: 0_7500000,
-            util: 0_5000000,
-            max_util: 0_9500000,
-            r_base: 1_0000000,
-            r_one: 0_0500000,
-            r_two: 0_5000000,
-            r_three: 1_5000000,
-            reactivity: 100,
-            supply_cap: 1000000000000000000,
-            enabled: true,
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 6,
+            max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
-        require_valid_reserve_metadata(&e, &metadata);
+        let new_oracle = Address::generate(&e);
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            execute_set_oracle(&e, &new_oracle);
+            assert_eq!(storage::get_pool_config(&e).oracle, new_oracle);
+        });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1202)")]
-    fn test_validate_reserve_metadata_validates_r_base_too_low() {
+    fn test_execute_set_base() {
         let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
 
-        let metadata = ReserveConfig {
-            index: 0,
-            decimals: 18,
-            c_factor: 0_7500000,
-            l_factor: 0_7500000,
-            util: 0_5000000,
-            max_util: 0_9500000,
-            r_base: 0_0000999,
-            r_one: 0_0500000,
-            r_two: 0_5000000,
-            r_three: 1_5000000,
-            reactivity: 100,
-            supply_cap: 1000000000000000000,
-            enabled: true,
+        let xlm = Address::generate(&e);
+        let (usdc, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &usdc, &reserve_config, &reserve_data);
+
+        // prices are denominated in XLM
+        let oracle = setup_oracle_with_base(
+            &e,
+            &sep_40_oracle::testutils::Asset::Stellar(xlm.clone()),
+            &vec![&e, usdc.clone()],
+            7,
+            vec![&e, 10_0000000],
+        );
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
-        require_valid_reserve_metadata(&e, &metadata);
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            let base = PoolBase {
+                asset: Asset::Stellar(xlm.clone()),
+                usd_asset: usdc.clone(),
+            };
+            execute_set_base(&e, &Some(base));
+            let new_base = storage::get_pool_config(&e).base.unwrap();
+            assert_eq!(new_base.asset, Asset::Stellar(xlm.clone()));
+            assert_eq!(new_base.usd_asset, usdc);
+
+            execute_set_base(&e, &None);
+            assert!(storage::get_pool_config(&e).base.is_none());
+        });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1202)")]
-    fn test_validate_reserve_metadata_validates_r_order() {
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_set_base_not_oracle_base() {
         let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
 
-        let metadata = ReserveConfig {
-            index: 0,
-            decimals: 18,
-            c_factor: 0_7500000,
-            l_factor: 0_7500000,
-            util: 0_5000000,
-            max_util: 0_9500000,
-            r_base: 0_0000100,
-            r_one: 0_5000001,
-            r_two: 0_5000000,
-            r_three: 1_5000000,
-            reactivity: 100,
-            supply_cap: 1000000000000000000,
-            enabled: true,
+        let xlm = Address::generate(&e);
+        let (usdc, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &usdc, &reserve_config, &reserve_data);
+
+        // prices are denominated in USD
+        let oracle = setup_oracle(&e, &vec![&e, usdc.clone()], 7, vec![&e, 1_0000000]);
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
-        require_valid_reserve_metadata(&e, &metadata);
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            let base = PoolBase {
+                asset: Asset::Stellar(xlm),
+                usd_asset: usdc,
+            };
+            execute_set_base(&e, &Some(base));
+        });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1202)")]
-    fn test_validate_reserve_metadata_validates_reactivity() {
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_set_oracle_base_mismatch() {
         let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
 
-        let metadata = ReserveConfig {
-            index: 0,
-            decimals: 18,
-            c_factor: 0_7500000,
-            l_factor: 0_7500000,
-            util: 0_5000000,
-            max_util: 0_9500000,
-            r_base: 0_0100000,
-            r_one: 0_0500000,
-            r_two: 0_5000000,
-            r_three: 1_5000000,
-            reactivity: 0_0001001,
-            supply_cap: 1000000000000000000,
-            enabled: true,
+        let xlm = Address::generate(&e);
+        let (usdc, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &usdc, &reserve_config, &reserve_data);
+
+        let oracle = setup_oracle_with_base(
+            &e,
+            &sep_40_oracle::testutils::Asset::Stellar(xlm.clone()),
+            &vec![&e, usdc.clone()],
+            7,
+            vec![&e, 10_0000000],
+        );
+        // the new oracle prices assets in USD
+        let new_oracle = setup_oracle(&e, &vec![&e, usdc.clone()], 7, vec![&e, 10_0000000]);
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 6,
+            max_positions: 2,
+            min_debt: 0,
+            base: Some(PoolBase {
+                asset: Asset::Stellar(xlm),
+                usd_asset: usdc,
+            }),
         };
-        require_valid_reserve_metadata(&e, &metadata);
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            execute_set_oracle(&e, &new_oracle);
+        });
     }
 }
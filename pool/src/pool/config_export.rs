@@ -0,0 +1,176 @@
+use soroban_sdk::{contracttype, map, vec, Address, Env, Map, Vec};
+
+use crate::{
+    emissions::ReserveEmissionMetadata,
+    events::PoolEvents,
+    storage::{self, AdminAction, PoolConfig, ReserveConfig},
+};
+
+use super::{execute_queue_admin_action, execute_queue_set_reserve};
+
+/// A complete set of the pool's parameters, used to diff and apply pool parameter sets
+#[derive(Clone)]
+#[contracttype]
+pub struct PoolConfigExport {
+    pub config: PoolConfig,                    // the pool's config
+    pub reserves: Map<Address, ReserveConfig>, // the config of each reserve, keyed by the reserve's asset
+    pub emission_shares: Map<u32, u64>, // the emission share of each reserve token, keyed by reserve token id
+}
+
+/// Export the pool's config, the config of each reserve, and the pool's emission shares
+pub fn export_config(e: &Env) -> PoolConfigExport {
+    let mut reserves = map![e];
+    for asset in storage::get_res_list(e).iter() {
+        let reserve_config = storage::get_res_config(e, &asset);
+        reserves.set(asset, reserve_config);
+    }
+    PoolConfigExport {
+        config: storage::get_pool_config(e),
+        reserves,
+        emission_shares: storage::get_pool_emissions(e),
+    }
+}
+
+/// Queue the changes required to apply an exported set of pool parameters. Each change is queued
+/// through the existing admin action and reserve queues, so it is validated and timelocked the same
+/// as if it was queued on its own. Parameters that already match the pool are skipped.
+///
+/// The pool's status is not imported, and reserves missing from the export are left as is.
+///
+/// Returns the ids of the queued admin actions
+///
+/// ### Arguments
+/// * `admin` - The admin of the pool
+/// * `export` - The pool parameters to apply
+///
+/// ### Panics
+/// If any of the changes are invalid, or a reserve being changed already has a queued change
+pub fn execute_queue_import_config(
+    e: &Env,
+    admin: &Address,
+    export: &PoolConfigExport,
+) -> Vec<u32> {
+    let cur_config = storage::get_pool_config(e);
+    let new_config = &export.config;
+    let mut actions = vec![e];
+    if cur_config.bstop_rate != new_config.bstop_rate
+        || cur_config.max_positions != new_config.max_positions
+        || cur_config.min_collateral != new_config.min_collateral
+        || cur_config.min_debt != new_config.min_debt
+    {
+        actions.push_back(AdminAction::UpdatePool(
+            new_config.bstop_rate,
+            new_config.max_positions,
+            new_config.min_collateral,
+            new_config.min_debt,
+        ));
+    }
+    if cur_config.oracle != new_config.oracle {
+        actions.push_back(AdminAction::SetOracle(new_config.oracle.clone()));
+    }
+    if cur_config.base != new_config.base {
+        match &new_config.base {
+            Some(base) => actions.push_back(AdminAction::SetBase(base.clone())),
+            None => actions.push_back(AdminAction::RemoveBase),
+        }
+    }
+    if storage::get_pool_emissions(e) != export.emission_shares {
+        let mut res_emission_metadata = vec![e];
+        for (res_token_id, share) in export.emission_shares.iter() {
+            res_emission_metadata.push_back(ReserveEmissionMetadata {
+                res_index: res_token_id / 2,
+                res_type: res_token_id % 2,
+                share,
+            });
+        }
+        actions.push_back(AdminAction::SetEmissionsConfig(res_emission_metadata));
+    }
+
+    let mut ids = vec![e];
+    for action in actions.iter() {
+        let (id, unlock_time) = execute_queue_admin_action(e, &action);
+        PoolEvents::queue_admin_action(e, admin.clone(), id, action, unlock_time);
+        ids.push_back(id);
+    }
+
+    for (asset, reserve_config) in export.reserves.iter() {
+        if storage::has_res(e, &asset) && storage::get_res_config(e, &asset) == reserve_config {
+            continue;
+        }
+        execute_queue_set_reserve(e, &asset, &reserve_config);
+        PoolEvents::queue_set_reserve(e, admin.clone(), asset, reserve_config);
+    }
+    ids
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{constants::UPDATE_POOL_DELAY, testutils};
+
+    use super::*;
+    use soroban_sdk::testutils::{Address as _, Ledger};
+
+    #[test]
+    fn test_export_and_import_config() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.ledger().set_timestamp(1000);
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, _) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config_0, reserve_data_0) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config_0, &reserve_data_0);
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, reserve_data_1) = testutils::default_reserve_meta();
+        reserve_config_1.index = 1;
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config_1, &reserve_data_1);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_pool_emissions(&e, &map![&e, (1, 0_5000000)]);
+
+            let export = export_config(&e);
+            assert_eq!(export.config.bstop_rate, 0_1000000);
+            assert_eq!(export.reserves.len(), 2);
+            assert_eq!(export.reserves.get_unchecked(underlying_1.clone()).index, 1);
+            assert_eq!(export.emission_shares, map![&e, (1, 0_5000000)]);
+
+            // importing the current config queues nothing
+            let ids = execute_queue_import_config(&e, &bombadil, &export);
+            assert_eq!(ids.len(), 0);
+            assert!(!storage::has_queued_reserve_set(&e, &underlying_0));
+            assert!(!storage::has_queued_reserve_set(&e, &underlying_1));
+
+            // only the changed parameters are queued
+            let mut new_export = export.clone();
+            new_export.config.bstop_rate = 0_2000000;
+            let mut new_reserve_config_1 = reserve_config_1.clone();
+            new_reserve_config_1.c_factor = 0_7000000;
+            new_export
+                .reserves
+                .set(underlying_1.clone(), new_reserve_config_1.clone());
+            let ids = execute_queue_import_config(&e, &bombadil, &new_export);
+            assert_eq!(ids, vec![&e, 0]);
+            let queued = storage::get_queued_admin_action(&e, 0).unwrap();
+            assert_eq!(queued.unlock_time, 1000 + UPDATE_POOL_DELAY);
+            assert!(!storage::has_queued_reserve_set(&e, &underlying_0));
+            assert_eq!(
+                storage::get_queued_reserve_set(&e, &underlying_1).new_config,
+                new_reserve_config_1
+            );
+        });
+    }
+}
@@ -0,0 +1,95 @@
+use soroban_sdk::{contracttype, map, xdr::ToXdr, Address, BytesN, Env, Map};
+
+use crate::storage;
+
+/// A snapshot of the pool's configuration, as hashes of the pool config and each reserve's config
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ConfigSnapshot {
+    pub pool: BytesN<32>,                   // the sha256 hash of the pool config
+    pub reserves: Map<Address, BytesN<32>>, // the sha256 hash of each reserve's config, keyed by the reserve's asset
+}
+
+/// Calculate a snapshot of the pool's current configuration. Each config is hashed from its XDR
+/// encoding, so the snapshot can be checked against configs read from storage or events.
+pub fn calc_config_snapshot(e: &Env) -> ConfigSnapshot {
+    let mut reserves = map![e];
+    for asset in storage::get_res_list(e).iter() {
+        let reserve_config = storage::get_res_config(e, &asset);
+        reserves.set(
+            asset,
+            e.crypto().sha256(&reserve_config.to_xdr(e)).to_bytes(),
+        );
+    }
+    let pool_config = storage::get_pool_config(e);
+    ConfigSnapshot {
+        pool: e.crypto().sha256(&pool_config.to_xdr(e)).to_bytes(),
+        reserves,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{storage::PoolConfig, testutils};
+
+    use super::*;
+    use soroban_sdk::testutils::Address as _;
+
+    #[test]
+    fn test_calc_config_snapshot() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, _) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config_0, reserve_data_0) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config_0, &reserve_data_0);
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, reserve_data_1) = testutils::default_reserve_meta();
+        reserve_config_1.index = 1;
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config_1, &reserve_data_1);
+
+        let mut pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            let snapshot = calc_config_snapshot(&e);
+            assert_eq!(snapshot.reserves.len(), 2);
+            assert_eq!(
+                snapshot.reserves.get_unchecked(underlying_0.clone()),
+                e.crypto().sha256(&reserve_config_0.to_xdr(&e)).to_bytes()
+            );
+            assert_eq!(calc_config_snapshot(&e), snapshot);
+
+            // only the changed config's hash changes
+            reserve_config_1.c_factor = 0_7000000;
+            storage::set_res_config(&e, &underlying_1, &reserve_config_1);
+            let new_snapshot = calc_config_snapshot(&e);
+            assert_eq!(new_snapshot.pool, snapshot.pool);
+            assert_eq!(
+                new_snapshot.reserves.get_unchecked(underlying_0.clone()),
+                snapshot.reserves.get_unchecked(underlying_0.clone())
+            );
+            assert_ne!(
+                new_snapshot.reserves.get_unchecked(underlying_1.clone()),
+                snapshot.reserves.get_unchecked(underlying_1.clone())
+            );
+
+            pool_config.bstop_rate = 0_2000000;
+            storage::set_pool_config(&e, &pool_config);
+            assert_ne!(calc_config_snapshot(&e).pool, snapshot.pool);
+        });
+    }
+}
@@ -0,0 +1,425 @@
+use cast::i128;
+use sep_41_token::TokenClient;
+use soroban_fixed_point_math::SorobanFixedPoint;
+use soroban_sdk::{panic_with_error, Address, Env};
+
+use crate::{
+    constants::SCALAR_7,
+    emissions,
+    errors::PoolError,
+    events::PoolEvents,
+    storage::{self, CreditLine},
+};
+
+use super::{actions::Actions, ReserveState};
+
+/// Set the credit line a lender extends to a reserve. The line is drawn from when withdrawals push
+/// the reserve's utilization above its maximum, and repaid from later inflows. Outstanding draws are
+/// kept when the line is updated. A cap of 0 stops further draws, and removes the line if it has
+/// been repaid.
+///
+/// ### Arguments
+/// * `asset` - The underlying asset of the reserve
+/// * `lender` - The address lending to the reserve
+/// * `cap` - The maximum amount of underlying that can be drawn from the line
+///
+/// ### Panics
+/// If the reserve does not exist, the cap is negative, or the line has outstanding draws from
+/// a different lender
+pub fn execute_set_credit_line(e: &Env, asset: &Address, lender: &Address, cap: i128) {
+    if !storage::has_res(e, asset) {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    if cap < 0 {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+    let b_tokens = match storage::get_credit_line(e, asset) {
+        Some(credit_line) => {
+            if credit_line.b_tokens > 0 && credit_line.lender != *lender {
+                panic_with_error!(e, PoolError::BadRequest);
+            }
+            credit_line.b_tokens
+        }
+        None => 0,
+    };
+    if cap == 0 && b_tokens == 0 {
+        storage::set_credit_line(e, asset, &None);
+    } else {
+        storage::set_credit_line(
+            e,
+            asset,
+            &Some(CreditLine {
+                lender: lender.clone(),
+                cap,
+                b_tokens,
+            }),
+        );
+    }
+}
+
+/// Draw from the reserve's credit line if its utilization is above the maximum, supplying enough
+/// to bring it back to the maximum or as much as the line's cap allows. The draw is held as bTokens
+/// by the line, so the lender earns the reserve's supply interest until it is repaid.
+///
+/// Does nothing if the reserve is at or below its maximum utilization or has no credit line
+///
+/// ### Arguments
+/// * `actions` - The actions to add the draw's transfer to
+/// * `reserve` - The reserve, after the withdrawal has been applied
+pub fn draw_credit_line(e: &Env, actions: &mut Actions, reserve: &mut ReserveState) {
+    if reserve.utilization(e) <= i128(reserve.config.max_util) {
+        return;
+    }
+    let mut credit_line = match storage::get_credit_line(e, &reserve.asset) {
+        Some(credit_line) => credit_line,
+        None => return,
+    };
+
+    let min_supply =
+        reserve
+            .total_liabilities(e)
+            .fixed_div_ceil(e, &i128(reserve.config.max_util), &SCALAR_7);
+    let remaining = credit_line.cap - reserve.to_asset_from_b_token(e, credit_line.b_tokens);
+    let tokens_in = (min_supply - reserve.total_supply(e)).min(remaining);
+    if tokens_in <= 0 {
+        return;
+    }
+    let b_tokens_minted = reserve.to_b_token_down(e, tokens_in);
+    if b_tokens_minted <= 0 {
+        return;
+    }
+
+    update_b_emissions(e, reserve);
+    reserve.data.b_supply += b_tokens_minted;
+    credit_line.b_tokens += b_tokens_minted;
+    storage::set_credit_line(e, &reserve.asset, &Some(credit_line.clone()));
+    actions.add_for_credit_line_transfer(&reserve.asset, tokens_in);
+
+    PoolEvents::credit_line_draw(
+        e,
+        reserve.asset.clone(),
+        credit_line.lender,
+        tokens_in,
+        b_tokens_minted,
+    );
+}
+
+/// Repay the reserve's credit line with any liquidity above the reserve's maximum utilization
+///
+/// Does nothing if the reserve has no outstanding draws
+///
+/// ### Arguments
+/// * `actions` - The actions to add the repayment's transfer to
+/// * `reserve` - The reserve, after the inflow has been applied
+pub fn repay_credit_line(e: &Env, actions: &mut Actions, reserve: &mut ReserveState) {
+    let mut credit_line = match storage::get_credit_line(e, &reserve.asset) {
+        Some(credit_line) if credit_line.b_tokens > 0 => credit_line,
+        _ => return,
+    };
+
+    let mut b_tokens_burnt = credit_line.b_tokens;
+    let mut tokens_out = reserve.to_asset_from_b_token(e, b_tokens_burnt);
+    let available = reserve.available_under_max_util(e);
+    if tokens_out > available {
+        tokens_out = available;
+        b_tokens_burnt = reserve
+            .to_b_token_up(e, tokens_out)
+            .min(credit_line.b_tokens);
+    }
+    if tokens_out <= 0 || b_tokens_burnt <= 0 {
+        return;
+    }
+
+    update_b_emissions(e, reserve);
+    reserve.data.b_supply -= b_tokens_burnt;
+    credit_line.b_tokens -= b_tokens_burnt;
+    storage::set_credit_line(e, &reserve.asset, &Some(credit_line.clone()));
+    actions.add_for_credit_line_transfer(&reserve.asset, -tokens_out);
+
+    PoolEvents::credit_line_repay(
+        e,
+        reserve.asset.clone(),
+        credit_line.lender,
+        tokens_out,
+        b_tokens_burnt,
+    );
+}
+
+/// Transfer the tokens drawn from credit lines to the pool. This must be done before the pool
+/// transfers tokens out.
+pub fn transfer_credit_line_draws(e: &Env, actions: &Actions) {
+    for (asset, amount) in actions.credit_line_transfer.iter() {
+        if amount > 0 {
+            let lender = storage::get_credit_line(e, &asset).unwrap().lender;
+            TokenClient::new(e, &asset).transfer_from(
+                &e.current_contract_address(),
+                &lender,
+                &e.current_contract_address(),
+                &amount,
+            );
+        }
+    }
+}
+
+/// Transfer credit line repayments to their lenders. This must be done after the tokens repaying
+/// the lines are transferred to the pool.
+pub fn transfer_credit_line_repayments(e: &Env, actions: &Actions) {
+    for (asset, amount) in actions.credit_line_transfer.iter() {
+        if amount < 0 {
+            let lender = storage::get_credit_line(e, &asset).unwrap().lender;
+            TokenClient::new(e, &asset).transfer(
+                &e.current_contract_address(),
+                &lender,
+                &amount.abs(),
+            );
+        }
+    }
+}
+
+/// Accrue the reserve's bToken emissions before the line changes its supply. The line's bTokens
+/// don't earn emissions.
+fn update_b_emissions(e: &Env, reserve: &ReserveState) {
+    emissions::update_emission_data(
+        e,
+        reserve.config.index * 2 + 1,
+        reserve.data.b_supply,
+        reserve.scalar,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{storage::PoolConfig, testutils};
+
+    use super::*;
+    use soroban_sdk::testutils::Address as _;
+
+    /// Create a pool with a reserve that is 75% utilized, returning the pool, reserve asset and pool config
+    fn setup_pool(e: &Env) -> (Address, Address, PoolConfig) {
+        let bombadil = Address::generate(e);
+        let pool = testutils::create_pool(e);
+        let (underlying, _) = testutils::create_token_contract(e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(e, &pool, &underlying, &reserve_config, &reserve_data);
+        let pool_config = PoolConfig {
+            oracle: Address::generate(e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(e, &pool_config);
+        });
+        (pool, underlying, pool_config)
+    }
+
+    #[test]
+    fn test_execute_set_credit_line() {
+        let e = Env::default();
+        let (pool, underlying, _) = setup_pool(&e);
+        let lender = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_credit_line(&e, &underlying, &lender, 10_0000000);
+            let credit_line = storage::get_credit_line(&e, &underlying).unwrap();
+            assert_eq!(credit_line.lender, lender);
+            assert_eq!(credit_line.cap, 10_0000000);
+            assert_eq!(credit_line.b_tokens, 0);
+
+            // outstanding draws are kept when the line is updated
+            storage::set_credit_line(
+                &e,
+                &underlying,
+                &Some(CreditLine {
+                    lender: lender.clone(),
+                    cap: 10_0000000,
+                    b_tokens: 2_0000000,
+                }),
+            );
+            execute_set_credit_line(&e, &underlying, &lender, 0);
+            let credit_line = storage::get_credit_line(&e, &underlying).unwrap();
+            assert_eq!(credit_line.cap, 0);
+            assert_eq!(credit_line.b_tokens, 2_0000000);
+
+            // a repaid line is removed once its cap is 0
+            storage::set_credit_line(
+                &e,
+                &underlying,
+                &Some(CreditLine {
+                    lender: lender.clone(),
+                    cap: 0,
+                    b_tokens: 0,
+                }),
+            );
+            execute_set_credit_line(&e, &underlying, &lender, 0);
+            assert!(storage::get_credit_line(&e, &underlying).is_none());
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_execute_set_credit_line_new_lender_with_draws() {
+        let e = Env::default();
+        let (pool, underlying, _) = setup_pool(&e);
+        let lender = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_credit_line(
+                &e,
+                &underlying,
+                &Some(CreditLine {
+                    lender,
+                    cap: 10_0000000,
+                    b_tokens: 2_0000000,
+                }),
+            );
+            execute_set_credit_line(&e, &underlying, &Address::generate(&e), 10_0000000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_set_credit_line_negative_cap() {
+        let e = Env::default();
+        let (pool, underlying, _) = setup_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_credit_line(&e, &underlying, &Address::generate(&e), -1);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_execute_set_credit_line_no_reserve() {
+        let e = Env::default();
+        let (pool, _, _) = setup_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_credit_line(&e, &Address::generate(&e), &Address::generate(&e), 1);
+        });
+    }
+
+    #[test]
+    fn test_draw_credit_line() {
+        let e = Env::default();
+        let (pool, underlying, pool_config) = setup_pool(&e);
+        let lender = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_credit_line(&e, &underlying, &lender, 10_0000000);
+            let mut reserve = ReserveState::load(&e, &pool_config, &underlying);
+            let mut actions = Actions::new(&e);
+
+            // below the maximum utilization nothing is drawn
+            draw_credit_line(&e, &mut actions, &mut reserve);
+            assert_eq!(actions.credit_line_transfer.len(), 0);
+
+            // a withdrawal pushes utilization above the 95% maximum
+            reserve.data.b_supply = 77_0000000;
+            draw_credit_line(&e, &mut actions, &mut reserve);
+            assert_eq!(
+                actions
+                    .credit_line_transfer
+                    .get_unchecked(underlying.clone()),
+                1_9473685
+            );
+            assert_eq!(reserve.data.b_supply, 78_9473685);
+            assert_eq!(reserve.utilization(&e), 0_9500000);
+            let credit_line = storage::get_credit_line(&e, &underlying).unwrap();
+            assert_eq!(credit_line.b_tokens, 1_9473685);
+        });
+    }
+
+    #[test]
+    fn test_draw_credit_line_capped() {
+        let e = Env::default();
+        let (pool, underlying, pool_config) = setup_pool(&e);
+        let lender = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_credit_line(
+                &e,
+                &underlying,
+                &Some(CreditLine {
+                    lender,
+                    cap: 10_0000000,
+                    b_tokens: 9_0000000,
+                }),
+            );
+            let mut reserve = ReserveState::load(&e, &pool_config, &underlying);
+            let mut actions = Actions::new(&e);
+
+            reserve.data.b_supply = 77_0000000;
+            draw_credit_line(&e, &mut actions, &mut reserve);
+            assert_eq!(
+                actions
+                    .credit_line_transfer
+                    .get_unchecked(underlying.clone()),
+                1_0000000
+            );
+            assert_eq!(reserve.data.b_supply, 78_0000000);
+            let credit_line = storage::get_credit_line(&e, &underlying).unwrap();
+            assert_eq!(credit_line.b_tokens, 10_0000000);
+
+            // the line is exhausted
+            draw_credit_line(&e, &mut actions, &mut reserve);
+            assert_eq!(
+                actions
+                    .credit_line_transfer
+                    .get_unchecked(underlying.clone()),
+                1_0000000
+            );
+            assert_eq!(reserve.data.b_supply, 78_0000000);
+        });
+    }
+
+    #[test]
+    fn test_repay_credit_line() {
+        let e = Env::default();
+        let (pool, underlying, pool_config) = setup_pool(&e);
+        let lender = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_credit_line(
+                &e,
+                &underlying,
+                &Some(CreditLine {
+                    lender,
+                    cap: 10_0000000,
+                    b_tokens: 5_0000000,
+                }),
+            );
+            let mut reserve = ReserveState::load(&e, &pool_config, &underlying);
+            let mut actions = Actions::new(&e);
+
+            // only the liquidity above the maximum utilization is repaid
+            reserve.data.b_supply = 80_0000000;
+            repay_credit_line(&e, &mut actions, &mut reserve);
+            assert_eq!(
+                actions
+                    .credit_line_transfer
+                    .get_unchecked(underlying.clone()),
+                -1_0526315
+            );
+            assert_eq!(reserve.data.b_supply, 78_9473685);
+            let credit_line = storage::get_credit_line(&e, &underlying).unwrap();
+            assert_eq!(credit_line.b_tokens, 3_9473685);
+
+            // an inflow repays the rest of the line
+            reserve.data.b_supply += 20_0000000;
+            repay_credit_line(&e, &mut actions, &mut reserve);
+            assert_eq!(
+                actions
+                    .credit_line_transfer
+                    .get_unchecked(underlying.clone()),
+                -5_0000000
+            );
+            assert_eq!(reserve.data.b_supply, 95_0000000);
+            let credit_line = storage::get_credit_line(&e, &underlying).unwrap();
+            assert_eq!(credit_line.b_tokens, 0);
+        });
+    }
+}
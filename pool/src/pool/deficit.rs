@@ -0,0 +1,214 @@
+use sep_41_token::TokenClient;
+use soroban_fixed_point_math::SorobanFixedPoint;
+use soroban_sdk::{map, panic_with_error, Address, Env, Map};
+
+use crate::{
+    constants::SCALAR_18,
+    errors::PoolError,
+    events::PoolEvents,
+    storage::{self, ReserveDeficit},
+};
+
+use super::{Pool, ReserveState};
+
+/// Record bad debt taken on by the backstop in the reserve's deficit
+///
+/// ### Arguments
+/// * `asset` - The underlying asset of the reserve
+/// * `amount` - The amount of bad debt in underlying tokens
+pub fn record_backstop_deficit(e: &Env, asset: &Address, amount: i128) {
+    let mut deficit = storage::get_res_deficit(e, asset);
+    deficit.backstop += amount;
+    storage::set_res_deficit(e, asset, &deficit);
+}
+
+/// Record bad debt defaulted onto the reserve's suppliers in the reserve's deficit. Defaulted debt
+/// was held by the backstop, so it is moved from the backstop's share of the deficit.
+///
+/// ### Arguments
+/// * `asset` - The underlying asset of the reserve
+/// * `amount` - The amount of defaulted debt in underlying tokens
+pub fn record_supplier_deficit(e: &Env, asset: &Address, amount: i128) {
+    let mut deficit = storage::get_res_deficit(e, asset);
+    deficit.backstop = (deficit.backstop - amount).max(0);
+    deficit.suppliers += amount;
+    storage::set_res_deficit(e, asset, &deficit);
+}
+
+/// Repay a reserve's unrecovered bad debt. The tokens are split between the reserve's suppliers and
+/// the backstop pro rata to the bad debt each has taken on. The suppliers' share is added to the
+/// reserve's bRate, and the backstop's share is added to the reserve's backstop credit. If the
+/// reserve has no suppliers, the suppliers' share is credited to the backstop.
+///
+/// Returns the amount of tokens repaid, which is capped at the reserve's unrecovered bad debt
+///
+/// ### Arguments
+/// * `from` - The address repaying the bad debt
+/// * `asset` - The underlying asset of the reserve
+/// * `amount` - The amount of tokens to repay
+///
+/// ### Panics
+/// If the amount is not positive or the reserve has no unrecovered bad debt
+pub fn execute_repay_deficit(e: &Env, from: &Address, asset: &Address, amount: i128) -> i128 {
+    let mut deficit = storage::get_res_deficit(e, asset);
+    let outstanding = deficit.backstop + deficit.suppliers;
+    if amount <= 0 || outstanding <= 0 {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    let amount = amount.min(outstanding);
+    let supplier_share = amount.fixed_mul_floor(e, &deficit.suppliers, &outstanding);
+    let backstop_share = amount - supplier_share;
+    deficit.suppliers -= supplier_share;
+    deficit.backstop -= backstop_share;
+    deficit.recovered += amount;
+
+    let pool = Pool::load(e);
+    let mut reserve = ReserveState::load(e, &pool.config, asset);
+    TokenClient::new(e, asset).transfer(from, &e.current_contract_address(), &amount);
+
+    let (to_suppliers, to_backstop) = if reserve.data.b_supply > 0 {
+        let b_rate = reserve.b_rate_18()
+            + supplier_share.fixed_div_floor(e, &reserve.data.b_supply, &SCALAR_18);
+        reserve.set_b_rate_18(b_rate);
+        (supplier_share, backstop_share)
+    } else {
+        (0, amount)
+    };
+    reserve.data.backstop_credit += to_backstop;
+    reserve.store(e);
+    storage::set_res_deficit(e, asset, &deficit);
+
+    PoolEvents::repay_deficit(e, asset.clone(), from.clone(), to_suppliers, to_backstop);
+    amount
+}
+
+/// Fetch the deficit of each reserve that has taken on bad debt
+pub fn get_deficits(e: &Env) -> Map<Address, ReserveDeficit> {
+    let mut deficits = map![e];
+    for asset in storage::get_res_list(e).iter() {
+        let deficit = storage::get_res_deficit(e, &asset);
+        if deficit != ReserveDeficit::default() {
+            deficits.set(asset, deficit);
+        }
+    }
+    deficits
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{storage::PoolConfig, testutils};
+
+    use super::*;
+    use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
+
+    fn setup_reserve(e: &Env, b_supply: i128) -> (Address, Address, Address) {
+        e.ledger().set(LedgerInfo {
+            timestamp: 100,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        let bombadil = Address::generate(e);
+        let samwise = Address::generate(e);
+        let pool = testutils::create_pool(e);
+        let (oracle, _) = testutils::create_mock_oracle(e);
+
+        let (underlying, underlying_client) = testutils::create_token_contract(e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_rate = 1_000_000_000_000;
+        reserve_data.d_rate = 1_000_000_000_000;
+        reserve_data.b_supply = b_supply;
+        reserve_data.d_supply = 0;
+        reserve_data.backstop_credit = 0;
+        reserve_data.last_time = 100;
+        testutils::create_reserve(e, &pool, &underlying, &reserve_config, &reserve_data);
+        underlying_client.mint(&samwise, &100_0000000);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(e, &pool_config);
+        });
+        (pool, underlying, samwise)
+    }
+
+    #[test]
+    fn test_repay_deficit() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let (pool, underlying, samwise) = setup_reserve(&e, 100_0000000);
+
+        e.as_contract(&pool, || {
+            record_backstop_deficit(&e, &underlying, 40_0000000);
+            // 10 of the backstop's bad debt is defaulted onto suppliers
+            record_supplier_deficit(&e, &underlying, 10_0000000);
+            let deficit = storage::get_res_deficit(&e, &underlying);
+            assert_eq!(deficit.backstop, 30_0000000);
+            assert_eq!(deficit.suppliers, 10_0000000);
+            assert_eq!(get_deficits(&e), map![&e, (underlying.clone(), deficit)]);
+
+            let repaid = execute_repay_deficit(&e, &samwise, &underlying, 20_0000000);
+            assert_eq!(repaid, 20_0000000);
+
+            let deficit = storage::get_res_deficit(&e, &underlying);
+            assert_eq!(deficit.backstop, 15_0000000);
+            assert_eq!(deficit.suppliers, 5_0000000);
+            assert_eq!(deficit.recovered, 20_0000000);
+
+            let reserve_data = storage::get_res_data(&e, &underlying);
+            assert_eq!(reserve_data.b_rate, 1_050_000_000_000);
+            assert_eq!(reserve_data.backstop_credit, 15_0000000);
+
+            // repayments are capped at the unrecovered bad debt
+            let repaid = execute_repay_deficit(&e, &samwise, &underlying, 50_0000000);
+            assert_eq!(repaid, 20_0000000);
+            let deficit = storage::get_res_deficit(&e, &underlying);
+            assert_eq!(deficit.backstop, 0);
+            assert_eq!(deficit.suppliers, 0);
+            assert_eq!(deficit.recovered, 40_0000000);
+        });
+        let token_client = TokenClient::new(&e, &underlying);
+        assert_eq!(token_client.balance(&samwise), 60_0000000);
+    }
+
+    #[test]
+    fn test_repay_deficit_no_suppliers_credits_backstop() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let (pool, underlying, samwise) = setup_reserve(&e, 0);
+
+        e.as_contract(&pool, || {
+            record_supplier_deficit(&e, &underlying, 10_0000000);
+
+            execute_repay_deficit(&e, &samwise, &underlying, 10_0000000);
+
+            let reserve_data = storage::get_res_data(&e, &underlying);
+            assert_eq!(reserve_data.b_rate, 1_000_000_000_000);
+            assert_eq!(reserve_data.backstop_credit, 10_0000000);
+            assert_eq!(storage::get_res_deficit(&e, &underlying).suppliers, 0);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_repay_deficit_without_deficit_panics() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let (pool, underlying, samwise) = setup_reserve(&e, 100_0000000);
+
+        e.as_contract(&pool, || {
+            execute_repay_deficit(&e, &samwise, &underlying, 10_0000000);
+        });
+    }
+}
@@ -0,0 +1,240 @@
+use soroban_sdk::{panic_with_error, vec, Address, Env};
+
+use crate::{errors::PoolError, storage, validator::require_nonnegative};
+
+use super::{execute_submit, Positions, Request, RequestType};
+
+/// Approve a delegatee to borrow an asset against the delegator's positions
+///
+/// ### Arguments
+/// * `delegator` - The address whose positions back the borrow
+/// * `delegatee` - The address allowed to borrow
+/// * `asset` - The reserve asset
+/// * `amount` - The amount of the asset the delegatee can borrow
+///
+/// ### Panics
+/// If the amount is negative or the asset is not a reserve
+pub fn execute_approve_borrow(
+    e: &Env,
+    delegator: &Address,
+    delegatee: &Address,
+    asset: &Address,
+    amount: i128,
+) {
+    require_nonnegative(e, &amount);
+    if delegator == delegatee || !storage::has_res(e, asset) {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    storage::set_borrow_allowance(e, delegator, delegatee, asset, amount);
+}
+
+/// Borrow an asset against the delegator's positions, decrementing the delegatee's allowance.
+/// The borrowed tokens are always sent to the delegatee.
+///
+/// Returns the delegator's new positions
+///
+/// ### Arguments
+/// * `delegator` - The address whose positions back the borrow
+/// * `delegatee` - The address borrowing
+/// * `asset` - The reserve asset
+/// * `amount` - The amount of the asset to borrow
+///
+/// ### Panics
+/// If the amount exceeds the delegatee's allowance, or the borrow is unable to be executed
+pub fn execute_delegated_borrow(
+    e: &Env,
+    delegator: &Address,
+    delegatee: &Address,
+    asset: &Address,
+    amount: i128,
+) -> Positions {
+    let allowance = storage::get_borrow_allowance(e, delegator, delegatee, asset);
+    if amount > allowance {
+        panic_with_error!(e, PoolError::BorrowAllowanceExceeded);
+    }
+    storage::set_borrow_allowance(e, delegator, delegatee, asset, allowance - amount);
+
+    let requests = vec![
+        e,
+        Request {
+            request_type: RequestType::Borrow as u32,
+            address: asset.clone(),
+            amount,
+        },
+    ];
+    execute_submit(e, delegator, delegatee, delegatee, requests, false)
+        .unwrap_or_else(|error| panic_with_error!(e, error))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{storage::PoolConfig, testutils};
+
+    use super::*;
+    use sep_40_oracle::testutils::Asset;
+    use soroban_sdk::{
+        map,
+        testutils::{Address as _, Ledger, LedgerInfo},
+        Symbol,
+    };
+
+    /// Setup a pool with two reserves where samwise has collateral supplied to reserve 0
+    ///
+    /// Returns (pool, samwise, underlying_0, underlying_1)
+    fn setup_delegator(e: &Env) -> (Address, Address, Address, Address) {
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(e);
+        let samwise = Address::generate(e);
+        let pool = testutils::create_pool(e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(e);
+
+        let (underlying_0, _) = testutils::create_token_contract(e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, _) = testutils::create_token_contract(e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(e, "USD")),
+            &vec![
+                e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![e, 1_0000000, 5_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(e, &pool_config);
+            storage::set_user_positions(
+                e,
+                &samwise,
+                &Positions {
+                    collateral: map![e, (0, 15_0000000)],
+                    liabilities: map![e],
+                    supply: map![e],
+                },
+            );
+        });
+        (pool, samwise, underlying_0, underlying_1)
+    }
+
+    #[test]
+    fn test_approve_borrow() {
+        let e = Env::default();
+        let (pool, samwise, _, underlying_1) = setup_delegator(&e);
+        let frodo = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            execute_approve_borrow(&e, &samwise, &frodo, &underlying_1, 1_0000000);
+            assert_eq!(
+                storage::get_borrow_allowance(&e, &samwise, &frodo, &underlying_1),
+                1_0000000
+            );
+
+            execute_approve_borrow(&e, &samwise, &frodo, &underlying_1, 0);
+            assert_eq!(
+                storage::get_borrow_allowance(&e, &samwise, &frodo, &underlying_1),
+                0
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_approve_borrow_not_reserve() {
+        let e = Env::default();
+        let (pool, samwise, _, _) = setup_delegator(&e);
+        let frodo = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            execute_approve_borrow(&e, &samwise, &frodo, &Address::generate(&e), 1_0000000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_approve_borrow_self() {
+        let e = Env::default();
+        let (pool, samwise, _, underlying_1) = setup_delegator(&e);
+
+        e.as_contract(&pool, || {
+            execute_approve_borrow(&e, &samwise, &samwise, &underlying_1, 1_0000000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #8)")]
+    fn test_approve_borrow_negative() {
+        let e = Env::default();
+        let (pool, samwise, _, underlying_1) = setup_delegator(&e);
+        let frodo = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            execute_approve_borrow(&e, &samwise, &frodo, &underlying_1, -1);
+        });
+    }
+
+    #[test]
+    fn test_delegated_borrow() {
+        let e = Env::default();
+        let (pool, samwise, _, underlying_1) = setup_delegator(&e);
+        let frodo = Address::generate(&e);
+        let underlying_1_client = soroban_sdk::token::Client::new(&e, &underlying_1);
+
+        e.as_contract(&pool, || {
+            execute_approve_borrow(&e, &samwise, &frodo, &underlying_1, 2_0000000);
+
+            let positions =
+                execute_delegated_borrow(&e, &samwise, &frodo, &underlying_1, 1_5000000);
+            assert_eq!(positions.liabilities.get_unchecked(1), 1_4999983);
+            assert_eq!(positions.collateral.get_unchecked(0), 15_0000000);
+            assert_eq!(
+                storage::get_borrow_allowance(&e, &samwise, &frodo, &underlying_1),
+                0_5000000
+            );
+            assert_eq!(underlying_1_client.balance(&frodo), 1_5000000);
+            assert_eq!(storage::get_user_positions(&e, &frodo).liabilities.len(), 0);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1226)")]
+    fn test_delegated_borrow_exceeds_allowance() {
+        let e = Env::default();
+        let (pool, samwise, _, underlying_1) = setup_delegator(&e);
+        let frodo = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            execute_approve_borrow(&e, &samwise, &frodo, &underlying_1, 1_0000000);
+
+            execute_delegated_borrow(&e, &samwise, &frodo, &underlying_1, 1_0000001);
+        });
+    }
+}
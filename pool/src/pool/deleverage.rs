@@ -0,0 +1,343 @@
+use sep_41_token::TokenClient;
+use soroban_sdk::{panic_with_error, vec, Address, Env};
+
+use crate::{errors::PoolError, events::PoolEvents, storage, validator::require_nonnegative};
+
+use super::{
+    actions::Deleverage, checkpoint::record_checkpoint, insurance::apply_insurance_premium,
+    pool::Pool, quote_fill::swap_quote_for_asset, risk_premium::apply_risk_premium,
+    stable_rate::apply_stable_rates, submit::validate_submit, Positions, RequestType, User,
+};
+
+/// Sell a portion of a user's collateral to repay their own liabilities in a single step, so a user
+/// can lower their leverage without external capital.
+///
+/// The pool withdraws up to `max_collateral_in` of the user's collateral, swaps it through the collateral
+/// asset's whitelisted swap adapter for the liability asset, and repays the user's liabilities. Only the
+/// collateral spent on the swap is removed from the user's positions. If the collateral and liability
+/// asset are the same, the collateral is used to repay the liabilities directly.
+///
+/// Returns the new positions for the user
+///
+/// ### Arguments
+/// * `from` - The user deleveraging their positions
+/// * `deleverage` - The collateral to sell and the liability to repay
+///
+/// ### Panics
+/// * If the collateral asset is not whitelisted as a quote asset
+/// * If the user has no liabilities for the liability asset
+/// * If the repayment requires more than `max_collateral_in` or the user's collateral
+/// * If the adapter does not deliver the requested amount of the liability asset
+/// * If the user is unhealthy after the deleverage
+pub fn execute_deleverage(e: &Env, from: &Address, deleverage: Deleverage) -> Positions {
+    if from == &e.current_contract_address() {
+        panic_with_error!(e, &PoolError::BadRequest);
+    }
+    require_nonnegative(e, &deleverage.amount);
+    require_nonnegative(e, &deleverage.max_collateral_in);
+
+    let mut pool = Pool::load(e);
+    pool.require_action_allowed(e, RequestType::WithdrawCollateral as u32);
+    pool.require_action_allowed(e, RequestType::Repay as u32);
+    let mut from_state = User::load(e, from);
+    apply_stable_rates(e, &mut pool, &mut from_state);
+    apply_risk_premium(e, &mut pool, &mut from_state);
+    apply_insurance_premium(e, &mut pool, &mut from_state);
+
+    let prev_positions_count = from_state.positions.effective_count();
+
+    // repay up to the user's outstanding liabilities
+    let mut liability_reserve = pool.load_reserve(e, &deleverage.liability_asset, true);
+    let cur_d_tokens = from_state.get_liabilities(liability_reserve.config.index);
+    let mut d_tokens_burnt = liability_reserve.to_d_token_down(e, deleverage.amount);
+    let mut tokens_in = deleverage.amount;
+    if d_tokens_burnt > cur_d_tokens {
+        d_tokens_burnt = cur_d_tokens;
+        tokens_in = liability_reserve.to_asset_from_d_token(e, cur_d_tokens);
+    }
+    if d_tokens_burnt == 0 {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    from_state.remove_liabilities(e, &mut liability_reserve, d_tokens_burnt);
+    pool.cache_reserve(liability_reserve);
+
+    // sell the collateral for the repaid liability asset
+    let mut collateral_reserve = pool.load_reserve(e, &deleverage.collateral_asset, true);
+    let cur_b_tokens = from_state.get_collateral(collateral_reserve.config.index);
+    let max_collateral_in = deleverage
+        .max_collateral_in
+        .min(collateral_reserve.to_asset_from_b_token(e, cur_b_tokens));
+    let collateral_spent = if deleverage.collateral_asset == deleverage.liability_asset {
+        tokens_in
+    } else {
+        let adapter = match storage::get_quote_adapter(e, &deleverage.collateral_asset) {
+            Some(adapter) => adapter,
+            None => panic_with_error!(e, PoolError::BadRequest),
+        };
+        if max_collateral_in <= 0 {
+            panic_with_error!(e, PoolError::MaxQuoteExceeded);
+        }
+        swap_quote_for_asset(
+            e,
+            &adapter,
+            &TokenClient::new(e, &deleverage.collateral_asset),
+            &deleverage.liability_asset,
+            tokens_in,
+            max_collateral_in,
+        )
+    };
+    if collateral_spent > max_collateral_in {
+        panic_with_error!(e, PoolError::MaxQuoteExceeded);
+    }
+    let b_tokens_burnt = collateral_reserve.to_b_token_up(e, collateral_spent);
+    from_state.remove_collateral(e, &mut collateral_reserve, b_tokens_burnt);
+    collateral_reserve.require_utilization_below_100(e);
+    pool.cache_reserve(collateral_reserve);
+
+    PoolEvents::withdraw_collateral(
+        e,
+        deleverage.collateral_asset,
+        from.clone(),
+        collateral_spent,
+        b_tokens_burnt,
+    );
+    PoolEvents::repay(
+        e,
+        deleverage.liability_asset,
+        from.clone(),
+        tokens_in,
+        d_tokens_burnt,
+    );
+
+    validate_submit(
+        e,
+        &mut pool,
+        &from_state,
+        prev_positions_count,
+        true,
+        false,
+        &vec![e],
+    )
+    .unwrap_or_else(|error| panic_with_error!(e, error));
+
+    record_checkpoint(e, &mut pool, &from_state);
+
+    pool.store_cached_reserves(e);
+    from_state.store(e);
+
+    from_state.positions
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{pool::execute_set_quote_adapter, storage::PoolConfig, testutils};
+
+    use super::*;
+    use sep_40_oracle::testutils::Asset;
+    use soroban_fixed_point_math::SorobanFixedPoint;
+    use soroban_sdk::{
+        map,
+        testutils::{Address as _, Ledger, LedgerInfo},
+        Symbol,
+    };
+
+    /// Setup a pool where samwise has 30 underlying_0 as collateral and 10 underlying_1 borrowed, with
+    /// underlying_0 whitelisted to swap for underlying_1 at 2 underlying_0 per underlying_1
+    ///
+    /// Returns (pool, samwise, underlying_0, underlying_1)
+    fn setup_deleverage(e: &Env) -> (Address, Address, Address, Address) {
+        e.mock_all_auths_allowing_non_root_auth();
+        e.cost_estimate().budget().reset_unlimited();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(e);
+        let samwise = Address::generate(e);
+        let pool = testutils::create_pool(e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(e);
+        let (adapter, adapter_client) = testutils::create_mock_swap_adapter(e);
+
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(e, &bombadil);
+        let (reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.last_time = 12345;
+        testutils::create_reserve(e, &pool, &underlying_0, &reserve_config_0, &reserve_data_0);
+        underlying_0_client.mint(&pool, &100_0000000);
+
+        let (underlying_1, underlying_1_client) = testutils::create_token_contract(e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_config_1.index = 1;
+        reserve_data_1.last_time = 12345;
+        testutils::create_reserve(e, &pool, &underlying_1, &reserve_config_1, &reserve_data_1);
+        underlying_1_client.mint(&adapter, &100_0000000);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(e, "USD")),
+            &vec![
+                e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![e, 1_0000000, 2_0000000]);
+
+        let positions = Positions {
+            collateral: map![e, (0, 30_0000000)],
+            liabilities: map![e, (1, 10_0000000)],
+            supply: map![e],
+        };
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(e, &pool_config);
+            storage::set_user_positions(e, &samwise, &positions);
+            execute_set_quote_adapter(e, &underlying_0, &Some(adapter.clone()));
+        });
+        adapter_client.set_price(&2_0000000);
+        (pool, samwise, underlying_0, underlying_1)
+    }
+
+    #[test]
+    fn test_execute_deleverage() {
+        let e = Env::default();
+        let (pool_address, samwise, underlying_0, underlying_1) = setup_deleverage(&e);
+
+        e.as_contract(&pool_address, || {
+            let mut pool = Pool::load(&e);
+            let reserve_0 = pool.load_reserve(&e, &underlying_0, false);
+            let reserve_1 = pool.load_reserve(&e, &underlying_1, false);
+            let d_tokens_burnt = reserve_1.to_d_token_down(&e, 4_0000000);
+            let collateral_spent = 4_0000000i128.fixed_mul_ceil(&e, &2_0000000, &1_0000000);
+            let b_tokens_burnt = reserve_0.to_b_token_up(&e, collateral_spent);
+            let underlying_0_client = TokenClient::new(&e, &underlying_0);
+            let underlying_1_client = TokenClient::new(&e, &underlying_1);
+            let pool_balance_0 = underlying_0_client.balance(&pool_address);
+            let pool_balance_1 = underlying_1_client.balance(&pool_address);
+
+            let positions = execute_deleverage(
+                &e,
+                &samwise,
+                Deleverage {
+                    collateral_asset: underlying_0.clone(),
+                    liability_asset: underlying_1.clone(),
+                    amount: 4_0000000,
+                    max_collateral_in: 10_0000000,
+                },
+            );
+            assert_eq!(
+                positions.collateral,
+                map![&e, (0, 30_0000000 - b_tokens_burnt)]
+            );
+            assert_eq!(
+                positions.liabilities,
+                map![&e, (1, 10_0000000 - d_tokens_burnt)]
+            );
+            assert_eq!(
+                underlying_0_client.balance(&pool_address),
+                pool_balance_0 - collateral_spent
+            );
+            assert_eq!(
+                underlying_1_client.balance(&pool_address),
+                pool_balance_1 + 4_0000000
+            );
+            let stored = storage::get_user_positions(&e, &samwise);
+            assert_eq!(stored.collateral, positions.collateral);
+            assert_eq!(stored.liabilities, positions.liabilities);
+        });
+    }
+
+    #[test]
+    fn test_execute_deleverage_same_asset() {
+        let e = Env::default();
+        let (pool_address, samwise, _, underlying_1) = setup_deleverage(&e);
+
+        e.as_contract(&pool_address, || {
+            let mut positions = storage::get_user_positions(&e, &samwise);
+            positions.collateral.set(1, 5_0000000);
+            storage::set_user_positions(&e, &samwise, &positions);
+
+            let mut pool = Pool::load(&e);
+            let reserve_1 = pool.load_reserve(&e, &underlying_1, false);
+            let d_tokens_burnt = reserve_1.to_d_token_down(&e, 2_0000000);
+            let b_tokens_burnt = reserve_1.to_b_token_up(&e, 2_0000000);
+
+            let positions = execute_deleverage(
+                &e,
+                &samwise,
+                Deleverage {
+                    collateral_asset: underlying_1.clone(),
+                    liability_asset: underlying_1.clone(),
+                    amount: 2_0000000,
+                    max_collateral_in: 2_0000000,
+                },
+            );
+            assert_eq!(
+                positions.collateral,
+                map![&e, (0, 30_0000000), (1, 5_0000000 - b_tokens_burnt)]
+            );
+            assert_eq!(
+                positions.liabilities,
+                map![&e, (1, 10_0000000 - d_tokens_burnt)]
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1229)")]
+    fn test_execute_deleverage_max_collateral_exceeded() {
+        let e = Env::default();
+        let (pool_address, samwise, underlying_0, underlying_1) = setup_deleverage(&e);
+
+        e.as_contract(&pool_address, || {
+            execute_deleverage(
+                &e,
+                &samwise,
+                Deleverage {
+                    collateral_asset: underlying_0.clone(),
+                    liability_asset: underlying_1.clone(),
+                    amount: 4_0000000,
+                    max_collateral_in: 0,
+                },
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_execute_deleverage_not_whitelisted() {
+        let e = Env::default();
+        let (pool_address, samwise, underlying_0, underlying_1) = setup_deleverage(&e);
+
+        e.as_contract(&pool_address, || {
+            execute_set_quote_adapter(&e, &underlying_0, &None);
+
+            execute_deleverage(
+                &e,
+                &samwise,
+                Deleverage {
+                    collateral_asset: underlying_0.clone(),
+                    liability_asset: underlying_1.clone(),
+                    amount: 4_0000000,
+                    max_collateral_in: 10_0000000,
+                },
+            );
+        });
+    }
+}
@@ -1,16 +1,20 @@
 use sep_41_token::TokenClient;
 use soroban_sdk::{Address, Env};
 
-use super::{Pool, RequestType, Reserve};
+use crate::storage;
+
+use super::{keeper::get_keeper_pot_of, Pool, RequestType, ReserveState};
 
 /// Gulps the excess tokens in the pool, determined by the difference between the pool token balance
-/// and the reserve total supply, backstop credit, and liabiltiies.
+/// and the reserve total supply, backstop credit, and liabiltiies. The reserve's rounding dust is
+/// swept to the backstop credit first, so it is accounted for instead of being gulped as excess.
+/// Tokens held in the keeper pot are not excess.
 ///
 /// ### Arguments
 /// * `asset` - The address of the asset to gulp
 ///
 /// ### Returns
-/// * The gulped token delta accrued to the backstop credit
+/// * The rounding dust and gulped token delta accrued to the backstop credit
 ///
 /// ### Panics
 /// * If borrowing is not enabled on the pool. This ensures that the backstop can safely process
@@ -21,19 +25,27 @@ pub fn execute_gulp(e: &Env, asset: &Address) -> i128 {
     // ensure the backstop can safely accept new interest
     pool.require_action_allowed(e, RequestType::Borrow as u32);
 
-    let mut reserve = Reserve::load(e, &pool.config, asset);
+    let mut reserve = ReserveState::load(e, &pool.config, asset);
+    let dust = storage::get_res_dust(e, asset) + reserve.dust;
+    if dust > 0 {
+        reserve.data.backstop_credit += dust;
+        reserve.dust = 0;
+        storage::set_res_dust(e, asset, 0);
+    }
+
     let pool_token_balance = TokenClient::new(e, asset).balance(&e.current_contract_address());
-    let reserve_token_balance =
-        reserve.total_supply(e) + reserve.data.backstop_credit - reserve.total_liabilities(e);
-    let token_balance_delta = pool_token_balance - reserve_token_balance;
-    if token_balance_delta <= 0 {
+    let reserve_token_balance = reserve.total_supply(e) + reserve.data.backstop_credit
+        - reserve.total_liabilities(e)
+        + get_keeper_pot_of(e, asset);
+    let token_balance_delta = (pool_token_balance - reserve_token_balance).max(0);
+    if dust + token_balance_delta <= 0 {
         return 0;
     }
 
     reserve.data.backstop_credit += token_balance_delta;
     reserve.store(e);
 
-    return token_balance_delta;
+    dust + token_balance_delta
 }
 
 #[cfg(test)]
@@ -85,6 +97,8 @@ mod tests {
                 bstop_rate: 0_1000000,
                 status: 1,
                 max_positions: 4,
+                min_debt: 0,
+                base: None,
             };
             storage::set_pool_config(&e, &pool_config);
 
@@ -138,6 +152,8 @@ mod tests {
                 bstop_rate: 0_1000000,
                 status: 0,
                 max_positions: 4,
+                min_debt: 0,
+                base: None,
             };
             storage::set_pool_config(&e, &pool_config);
 
@@ -190,6 +206,8 @@ mod tests {
                 bstop_rate: 0_1000000,
                 status: 0,
                 max_positions: 4,
+                min_debt: 0,
+                base: None,
             };
             storage::set_pool_config(&e, &pool_config);
 
@@ -240,6 +258,8 @@ mod tests {
                 bstop_rate: 0_1000000,
                 status: 0,
                 max_positions: 4,
+                min_debt: 0,
+                base: None,
             };
             storage::set_pool_config(&e, &pool_config);
 
@@ -254,6 +274,58 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_execute_gulp_sweeps_dust() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+        e.ledger().set(LedgerInfo {
+            timestamp: 100,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, _) = testutils::create_mock_oracle(&e);
+
+        let (underlying, underlying_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_rate = 1_000_000_000_000;
+        reserve_data.d_rate = 1_000_000_000_000;
+        reserve_data.d_supply = 500 * SCALAR_7;
+        reserve_data.b_supply = 1000 * SCALAR_7;
+        reserve_data.backstop_credit = 0;
+        reserve_data.last_time = 100;
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        // the dust is swept even if the pool holds no excess tokens
+        underlying_client.burn(&pool, &SCALAR_7);
+        e.as_contract(&pool, || {
+            let pool_config = PoolConfig {
+                oracle,
+                min_collateral: 1_0000000,
+                bstop_rate: 0_1000000,
+                status: 0,
+                max_positions: 4,
+                min_debt: 0,
+                base: None,
+            };
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_res_dust(&e, &underlying, 7);
+
+            let token_delta_result = execute_gulp(&e, &underlying);
+            assert_eq!(token_delta_result, 7);
+
+            let new_reserve_data = storage::get_res_data(&e, &underlying);
+            assert_eq!(new_reserve_data.backstop_credit, 7);
+            assert_eq!(storage::get_res_dust(&e, &underlying), 0);
+        });
+    }
+
     #[test]
     #[should_panic(expected = "Error(Contract, #1206)")]
     fn test_execute_gulp_checks_status() {
@@ -293,6 +365,8 @@ mod tests {
                 bstop_rate: 0_1000000,
                 status: 2,
                 max_positions: 4,
+                min_debt: 0,
+                base: None,
             };
             storage::set_pool_config(&e, &pool_config);
 
@@ -187,6 +187,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 5,
+            min_debt: 0,
+            base: None,
         };
 
         let positions = Positions {
@@ -0,0 +1,320 @@
+use sep_41_token::TokenClient;
+use soroban_sdk::{map, panic_with_error, vec, Address, Env};
+
+use crate::{
+    auctions::{self, AuctionType},
+    dependencies::FillHookClient,
+    errors::{PoolError, PoolErrorExt},
+    events::PoolEvents,
+};
+
+use super::{
+    actions::HookFill, checkpoint::record_checkpoint, pool::Pool, submit::validate_submit,
+    Positions, RequestType, User,
+};
+
+/// Fill a user liquidation auction, funding the bid with the lot through a hook contract.
+///
+/// The filler takes on the filled lot and bid, and the lot collateral is withdrawn to the hook.
+/// The hook is then invoked, and must send the bid to the pool before returning. The pool checks
+/// its own balance of each bid asset to settle the bid liabilities the filler took on. All pool
+/// state is updated in memory and only stored after the bid is settled, and the pool can't be
+/// re-entered by the hook.
+///
+/// Returns the new positions for the filler
+///
+/// ### Arguments
+/// * `filler` - The address filling the auction
+/// * `hook_fill` - The auction being filled and the hook funding the bid
+///
+/// ### Panics
+/// * If the filler or hook is the pool
+/// * If the hook does not send the bid to the pool
+/// * If withdrawing the lot leaves a reserve over 100% utilization
+/// * If the filler is unhealthy after the fill
+pub fn execute_fill_with_hook(e: &Env, filler: &Address, hook_fill: HookFill) -> Positions {
+    let pool_address = e.current_contract_address();
+    if filler == &pool_address || hook_fill.hook == pool_address {
+        panic_with_error!(e, &PoolError::BadRequest);
+    }
+
+    let mut pool = Pool::load(e);
+    pool.require_action_allowed(e, RequestType::FillUserLiquidationAuction as u32);
+    let mut filler_state = User::load(e, filler);
+    let prev_positions_count = filler_state.positions.effective_count();
+
+    let filled_auction = auctions::fill(
+        e,
+        &mut pool,
+        AuctionType::UserLiquidation as u32,
+        &hook_fill.user,
+        &mut filler_state,
+        hook_fill.percent,
+    );
+    PoolEvents::fill_auction(
+        e,
+        AuctionType::UserLiquidation as u32,
+        hook_fill.user.clone(),
+        filler.clone(),
+        hook_fill.percent as i128,
+        filled_auction.clone(),
+    );
+
+    // withdraw the lot the filler received to the hook
+    let mut lot = map![e];
+    for (asset, b_tokens) in filled_auction.lot.iter() {
+        let mut reserve = pool.load_reserve(e, &asset, true);
+        let tokens_out = reserve.to_asset_from_b_token(e, b_tokens);
+        filler_state.remove_collateral(e, &mut reserve, b_tokens);
+        reserve.require_utilization_below_100(e);
+        pool.cache_reserve(reserve);
+        PoolEvents::withdraw_collateral(e, asset.clone(), filler.clone(), tokens_out, b_tokens);
+        lot.set(asset, tokens_out);
+    }
+    for (asset, tokens_out) in lot.iter() {
+        TokenClient::new(e, &asset).transfer(&pool_address, &hook_fill.hook, &tokens_out);
+    }
+
+    // record the pool's balance of each bid asset before the hook funds the bid
+    let mut bid = map![e];
+    let mut balances = map![e];
+    for (asset, d_tokens) in filled_auction.bid.iter() {
+        let reserve = pool.load_reserve(e, &asset, false);
+        bid.set(asset.clone(), reserve.to_asset_from_d_token(e, d_tokens));
+        balances.set(
+            asset.clone(),
+            TokenClient::new(e, &asset).balance(&pool_address),
+        );
+    }
+
+    FillHookClient::new(e, &hook_fill.hook).on_fill(&pool_address, filler, &lot, &bid);
+
+    // settle the bid liabilities the filler took on with the tokens sent by the hook
+    for (asset, d_tokens) in filled_auction.bid.iter() {
+        let tokens_in = bid.get_unchecked(asset.clone());
+        let received = TokenClient::new(e, &asset).balance(&pool_address)
+            - balances.get_unchecked(asset.clone());
+        if received < tokens_in {
+            panic_with_error!(e, PoolErrorExt::FillHookFailed);
+        }
+        let mut reserve = pool.load_reserve(e, &asset, true);
+        filler_state.remove_liabilities(e, &mut reserve, d_tokens);
+        pool.cache_reserve(reserve);
+        PoolEvents::repay(e, asset, filler.clone(), tokens_in, d_tokens);
+    }
+
+    validate_submit(
+        e,
+        &mut pool,
+        &filler_state,
+        prev_positions_count,
+        true,
+        false,
+        &vec![e],
+    )
+    .unwrap_or_else(|error| panic_with_error!(e, error));
+
+    record_checkpoint(e, &mut pool, &filler_state);
+
+    pool.store_cached_reserves(e);
+    filler_state.store(e);
+
+    filler_state.positions
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        auctions::AuctionData,
+        storage::{self, PoolConfig},
+        testutils,
+    };
+
+    use super::*;
+    use sep_40_oracle::testutils::Asset;
+    use soroban_sdk::{
+        contract, contractimpl,
+        testutils::{Address as _, Ledger, LedgerInfo},
+        Map, Symbol,
+    };
+
+    /// Sends the bid to the pool out of its own balance, and keeps the lot
+    #[contract]
+    pub struct MockFillHook;
+
+    #[contractimpl]
+    impl MockFillHook {
+        pub fn set_underpay(e: Env, underpay: i128) {
+            e.storage()
+                .instance()
+                .set(&Symbol::new(&e, "Underpay"), &underpay);
+        }
+
+        pub fn on_fill(
+            e: Env,
+            pool: Address,
+            _filler: Address,
+            _lot: Map<Address, i128>,
+            bid: Map<Address, i128>,
+        ) {
+            let underpay: i128 = e
+                .storage()
+                .instance()
+                .get(&Symbol::new(&e, "Underpay"))
+                .unwrap_or(0);
+            for (asset, amount) in bid.iter() {
+                TokenClient::new(&e, &asset).transfer(
+                    &e.current_contract_address(),
+                    &pool,
+                    &(amount - underpay),
+                );
+            }
+        }
+    }
+
+    /// Setup a pool with a user liquidation auction against samwise and a mock fill hook
+    ///
+    /// Returns (pool, samwise, frodo, hook, underlying_0, underlying_1)
+    fn setup_hook_fill(e: &Env) -> (Address, Address, Address, Address, Address, Address) {
+        e.mock_all_auths_allowing_non_root_auth();
+        e.cost_estimate().budget().reset_unlimited();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 300,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(e);
+        let samwise = Address::generate(e);
+        let frodo = Address::generate(e);
+        let pool = testutils::create_pool(e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(e);
+        let hook = e.register(MockFillHook {}, ());
+
+        let (underlying_0, _) = testutils::create_token_contract(e, &bombadil);
+        let (reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.last_time = 12345;
+        testutils::create_reserve(e, &pool, &underlying_0, &reserve_config_0, &reserve_data_0);
+
+        let (underlying_1, underlying_1_client) = testutils::create_token_contract(e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_config_1.index = 1;
+        reserve_data_1.last_time = 12345;
+        testutils::create_reserve(e, &pool, &underlying_1, &reserve_config_1, &reserve_data_1);
+        underlying_1_client.mint(&hook, &100_0000000);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(e, "USD")),
+            &vec![
+                e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![e, 1_0000000, 2_0000000]);
+
+        let positions = Positions {
+            collateral: map![e, (0, 30_0000000)],
+            liabilities: map![e, (1, 10_0000000)],
+            supply: map![e],
+        };
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(e, &pool_config);
+            storage::set_user_positions(e, &samwise, &positions);
+            storage::set_auction(
+                e,
+                &(AuctionType::UserLiquidation as u32),
+                &samwise,
+                &AuctionData {
+                    bid: map![e, (underlying_1.clone(), 10_0000000)],
+                    lot: map![e, (underlying_0.clone(), 25_0000000)],
+                    block: 100,
+                },
+            );
+        });
+        (pool, samwise, frodo, hook, underlying_0, underlying_1)
+    }
+
+    #[test]
+    fn test_execute_fill_with_hook() {
+        let e = Env::default();
+        let (pool_address, samwise, frodo, hook, underlying_0, underlying_1) = setup_hook_fill(&e);
+
+        e.as_contract(&pool_address, || {
+            let mut pool = Pool::load(&e);
+            let reserve_0 = pool.load_reserve(&e, &underlying_0, false);
+            let lot_tokens = reserve_0.to_asset_from_b_token(&e, 12_5000000);
+            let reserve_1 = pool.load_reserve(&e, &underlying_1, false);
+            let bid_tokens = reserve_1.to_asset_from_d_token(&e, 5_0000000);
+            let underlying_0_client = TokenClient::new(&e, &underlying_0);
+            let underlying_1_client = TokenClient::new(&e, &underlying_1);
+            let pool_balance_0 = underlying_0_client.balance(&pool_address);
+            let pool_balance_1 = underlying_1_client.balance(&pool_address);
+
+            let positions = execute_fill_with_hook(
+                &e,
+                &frodo,
+                HookFill {
+                    user: samwise.clone(),
+                    percent: 50,
+                    hook: hook.clone(),
+                },
+            );
+            assert_eq!(positions.collateral.len(), 0);
+            assert_eq!(positions.liabilities.len(), 0);
+
+            assert_eq!(underlying_0_client.balance(&hook), lot_tokens);
+            assert_eq!(
+                underlying_0_client.balance(&pool_address),
+                pool_balance_0 - lot_tokens
+            );
+            assert_eq!(underlying_1_client.balance(&hook), 100_0000000 - bid_tokens);
+            assert_eq!(
+                underlying_1_client.balance(&pool_address),
+                pool_balance_1 + bid_tokens
+            );
+
+            let auction =
+                storage::get_auction(&e, &(AuctionType::UserLiquidation as u32), &samwise);
+            assert_eq!(auction.bid, map![&e, (underlying_1.clone(), 5_0000000)]);
+            assert_eq!(auction.lot, map![&e, (underlying_0.clone(), 12_5000000)]);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1247)")]
+    fn test_execute_fill_with_hook_bid_not_funded() {
+        let e = Env::default();
+        let (pool_address, samwise, frodo, hook, _, _) = setup_hook_fill(&e);
+        MockFillHookClient::new(&e, &hook).set_underpay(&1);
+
+        e.as_contract(&pool_address, || {
+            execute_fill_with_hook(
+                &e,
+                &frodo,
+                HookFill {
+                    user: samwise.clone(),
+                    percent: 50,
+                    hook: hook.clone(),
+                },
+            );
+        });
+    }
+}
@@ -0,0 +1,351 @@
+use cast::i128;
+use soroban_fixed_point_math::SorobanFixedPoint;
+use soroban_sdk::{panic_with_error, Address, Env};
+
+use crate::{
+    constants::{MAX_INSURANCE_HF_BUFFER, MAX_INSURANCE_PREMIUM, SCALAR_7, SECONDS_PER_YEAR},
+    errors::PoolError,
+    events::PoolEvents,
+    storage::{self, InsuranceConfig},
+};
+
+use super::{health_factor::PositionData, Pool, Positions, User};
+
+/// Set or remove the pool's liquidation protection insurance. While insurance is removed, insured
+/// users are neither charged the premium nor protected from liquidation.
+///
+/// ### Arguments
+/// * `config` - The insurance config, or None to disable insurance
+///
+/// ### Panics
+/// If the premium or health factor buffer is not positive or exceeds its max
+pub fn execute_set_insurance_config(e: &Env, config: &Option<InsuranceConfig>) {
+    if let Some(config) = config {
+        if config.premium == 0
+            || config.premium > MAX_INSURANCE_PREMIUM
+            || config.hf_buffer == 0
+            || config.hf_buffer > MAX_INSURANCE_HF_BUFFER
+        {
+            panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+        }
+    }
+    storage::set_insurance_config(e, config);
+}
+
+/// Opt a user in or out of liquidation protection insurance. Any premium owed for the user's
+/// current coverage is charged first.
+///
+/// Returns the user's positions
+///
+/// ### Arguments
+/// * `user` - The user
+/// * `insured` - If the user is insured
+///
+/// ### Panics
+/// If the user opts in while the pool does not offer insurance
+pub fn execute_set_insured(e: &Env, user: &Address, insured: bool) -> Positions {
+    if insured && storage::get_insurance_config(e).is_none() {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    let mut pool = Pool::load(e);
+    let mut user_state = User::load(e, user);
+
+    apply_insurance_premium(e, &mut pool, &mut user_state);
+    let timestamp = if insured {
+        Some(e.ledger().timestamp())
+    } else {
+        None
+    };
+    storage::set_insured(e, user, &timestamp);
+
+    pool.store_cached_reserves(e);
+    user_state.store(e);
+    user_state.positions
+}
+
+/// Charge the insurance premium owed by a user and store the results.
+///
+/// Returns the user's positions
+///
+/// ### Arguments
+/// * `user` - The user to charge
+pub fn execute_apply_insurance_premium(e: &Env, user: &Address) -> Positions {
+    let mut pool = Pool::load(e);
+    let mut user_state = User::load(e, user);
+
+    apply_insurance_premium(e, &mut pool, &mut user_state);
+
+    pool.store_cached_reserves(e);
+    user_state.store(e);
+    user_state.positions
+}
+
+/// Charge an insured user the premium on their liabilities for the time since the premium was last
+/// charged.
+///
+/// The premium is added to the user's liabilities and credited in full to the backstop, so the
+/// insured user effectively borrows at the reserve's rate plus the premium.
+///
+/// Does nothing if the user is not insured.
+///
+/// ### Arguments
+/// * `pool` - The pool
+/// * `user` - The user being charged
+pub fn apply_insurance_premium(e: &Env, pool: &mut Pool, user: &mut User) {
+    let last_time = match storage::get_insured(e, &user.address) {
+        Some(last_time) => last_time,
+        None => return,
+    };
+    let cur_time = e.ledger().timestamp();
+    if cur_time <= last_time {
+        return;
+    }
+
+    // no premium is owed for time the pool did not offer insurance
+    if let Some(config) = storage::get_insurance_config(e) {
+        let elapsed = i128(cur_time - last_time);
+        let reserve_list = storage::get_res_list(e);
+        for (index, d_tokens) in user.positions.liabilities.iter() {
+            let mut reserve = pool.load_reserve(e, &reserve_list.get_unchecked(index), true);
+            let premium = reserve
+                .to_asset_from_d_token(e, d_tokens)
+                .fixed_mul_floor(e, &i128(config.premium), &SCALAR_7)
+                .fixed_mul_floor(e, &elapsed, &SECONDS_PER_YEAR);
+            let d_tokens_minted = reserve.to_d_token_down(e, premium);
+            if d_tokens_minted > 0 {
+                user.add_liabilities(e, &mut reserve, d_tokens_minted);
+                let tokens_charged = reserve.to_asset_from_d_token(e, d_tokens_minted);
+                reserve.data.backstop_credit += tokens_charged;
+                PoolEvents::insurance_premium(
+                    e,
+                    reserve.asset.clone(),
+                    user.address.clone(),
+                    tokens_charged,
+                    d_tokens_minted,
+                );
+            }
+            pool.cache_reserve(reserve);
+        }
+    }
+    storage::set_insured(e, &user.address, &Some(cur_time));
+}
+
+/// Check if an insured user's health factor is still within the insurance buffer, such that they
+/// can't be liquidated even though their liabilities exceed their collateral.
+///
+/// ### Arguments
+/// * `user` - The user being liquidated
+/// * `position_data` - The user's position data
+pub fn is_liq_protected(e: &Env, user: &Address, position_data: &PositionData) -> bool {
+    match storage::get_insurance_config(e) {
+        Some(config) if storage::get_insured(e, user).is_some() => {
+            !position_data.is_hf_under(e, SCALAR_7 - i128(config.hf_buffer))
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{storage::PoolConfig, testutils};
+
+    use super::*;
+    use sep_40_oracle::testutils::Asset;
+    use soroban_sdk::{
+        map,
+        testutils::{Address as _, Ledger, LedgerInfo},
+        vec, Symbol,
+    };
+
+    /// Setup a pool with two reserves, insurance with a 10% premium and a 0.05 health factor
+    /// buffer, and a user with a health factor of ~1.27
+    ///
+    /// Returns (pool, samwise, underlying_1)
+    fn setup_insurance(e: &Env) -> (Address, Address, Address) {
+        e.mock_all_auths();
+        e.cost_estimate().budget().reset_unlimited();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(e);
+        let samwise = Address::generate(e);
+        let pool = testutils::create_pool(e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(e);
+
+        let (underlying_0, _) = testutils::create_token_contract(e, &bombadil);
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_config_0.c_factor = 0_8500000;
+        reserve_data_0.last_time = 12345;
+        testutils::create_reserve(e, &pool, &underlying_0, &reserve_config_0, &reserve_data_0);
+
+        let (underlying_1, _) = testutils::create_token_contract(e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_config_1.index = 1;
+        reserve_data_1.last_time = 12345;
+        testutils::create_reserve(e, &pool, &underlying_1, &reserve_config_1, &reserve_data_1);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(e, "USD")),
+            &vec![
+                e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![e, 1_0000000, 1_0000000]);
+
+        // collateral base: 25 * 0.85 = 21.25, liability base: 12.5 / 0.75 = 16.67
+        let positions = Positions {
+            collateral: map![e, (0, 25_0000000)],
+            liabilities: map![e, (1, 12_5000000)],
+            supply: map![e],
+        };
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(e, &pool_config);
+            storage::set_user_positions(e, &samwise, &positions);
+            execute_set_insurance_config(
+                e,
+                &Some(InsuranceConfig {
+                    premium: 0_1000000,
+                    hf_buffer: 0_0500000,
+                }),
+            );
+        });
+        (pool, samwise, underlying_1)
+    }
+
+    #[test]
+    fn test_apply_insurance_premium() {
+        let e = Env::default();
+        let (pool_address, samwise, underlying_1) = setup_insurance(&e);
+
+        // uninsured users are not charged
+        e.ledger().set_timestamp(12345 + 60 * 60 * 24 * 30);
+        e.as_contract(&pool_address, || {
+            let positions = execute_apply_insurance_premium(&e, &samwise);
+            assert_eq!(positions.liabilities, map![&e, (1, 12_5000000)]);
+            assert!(storage::get_insured(&e, &samwise).is_none());
+
+            execute_set_insured(&e, &samwise, true);
+            assert_eq!(
+                storage::get_insured(&e, &samwise),
+                Some(12345 + 60 * 60 * 24 * 30)
+            );
+        });
+
+        e.ledger().set_timestamp(12345 + 60 * 60 * 24 * 60);
+        e.as_contract(&pool_address, || {
+            let pre_reserve = Pool::load(&e).load_reserve(&e, &underlying_1, false);
+            let positions = execute_apply_insurance_premium(&e, &samwise);
+
+            let reserve = Pool::load(&e).load_reserve(&e, &underlying_1, false);
+            let premium = reserve
+                .to_asset_from_d_token(&e, 12_5000000)
+                .fixed_mul_floor(&e, &0_1000000, &SCALAR_7)
+                .fixed_mul_floor(&e, &(60 * 60 * 24 * 30), &SECONDS_PER_YEAR);
+            let d_tokens_minted = reserve.to_d_token_down(&e, premium);
+            assert!(d_tokens_minted > 0);
+            assert_eq!(
+                positions.liabilities,
+                map![&e, (1, 12_5000000 + d_tokens_minted)]
+            );
+            assert_eq!(
+                reserve.data.d_supply,
+                pre_reserve.data.d_supply + d_tokens_minted
+            );
+            assert_eq!(
+                reserve.data.backstop_credit - pre_reserve.data.backstop_credit,
+                reserve.to_asset_from_d_token(&e, d_tokens_minted)
+            );
+            assert_eq!(
+                storage::get_insured(&e, &samwise),
+                Some(12345 + 60 * 60 * 24 * 60)
+            );
+
+            // opting out removes the insurance
+            execute_set_insured(&e, &samwise, false);
+            assert!(storage::get_insured(&e, &samwise).is_none());
+        });
+    }
+
+    #[test]
+    fn test_is_liq_protected() {
+        let e = Env::default();
+        let (pool_address, samwise, _) = setup_insurance(&e);
+
+        let hf_0_97 = PositionData {
+            collateral_base: 0_9700000,
+            collateral_raw: 1_0000000,
+            liability_base: 1_0000000,
+            liability_raw: 1_0000000,
+            scalar: SCALAR_7,
+        };
+        let hf_0_94 = PositionData {
+            collateral_base: 0_9400000,
+            collateral_raw: 1_0000000,
+            liability_base: 1_0000000,
+            liability_raw: 1_0000000,
+            scalar: SCALAR_7,
+        };
+        e.as_contract(&pool_address, || {
+            assert!(!is_liq_protected(&e, &samwise, &hf_0_97));
+
+            execute_set_insured(&e, &samwise, true);
+            assert!(is_liq_protected(&e, &samwise, &hf_0_97));
+            assert!(!is_liq_protected(&e, &samwise, &hf_0_94));
+
+            // insured users are not protected while insurance is disabled
+            execute_set_insurance_config(&e, &None);
+            assert!(!is_liq_protected(&e, &samwise, &hf_0_97));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_set_insured_without_insurance() {
+        let e = Env::default();
+        let (pool_address, samwise, _) = setup_insurance(&e);
+
+        e.as_contract(&pool_address, || {
+            execute_set_insurance_config(&e, &None);
+            execute_set_insured(&e, &samwise, true);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_set_insurance_config_buffer_too_large() {
+        let e = Env::default();
+        let (pool_address, _, _) = setup_insurance(&e);
+
+        e.as_contract(&pool_address, || {
+            execute_set_insurance_config(
+                &e,
+                &Some(InsuranceConfig {
+                    premium: 0_1000000,
+                    hf_buffer: MAX_INSURANCE_HF_BUFFER + 1,
+                }),
+            );
+        });
+    }
+}
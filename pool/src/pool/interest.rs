@@ -1,40 +1,101 @@
 use cast::i128;
 use soroban_fixed_point_math::SorobanFixedPoint;
-use soroban_sdk::{panic_with_error, Env};
+use soroban_sdk::{panic_with_error, Address, Env};
 
 use crate::{
-    constants::{SCALAR_12, SCALAR_7, SECONDS_PER_YEAR},
-    storage::ReserveConfig,
+    constants::{MAX_STRATEGY_BORROW_RATE, SCALAR_12, SCALAR_7, SECONDS_PER_YEAR},
+    dependencies::RateStrategyClient,
+    storage::{self, ReserveConfig},
     PoolError,
 };
 
-/// Calculates the loan accrual ratio for the Reserve based on the current utilization and
-/// rate modifier for the reserve.
+/// Load the current borrow rate for a reserve, capped at the reserve's max APR if one is set
 ///
 /// ### Arguments
-/// * `config` - The Reserve config to calculate an accrual for
+/// * `asset` - The underlying asset of the reserve
+/// * `config` - The Reserve config to calculate the rate for
 /// * `cur_util` - The current utilization rate of the reserve (7 decimals)
-/// * `ir_mod` - The current interest rate modifier of the reserve (9 decimals)
-/// * `last_block` - The last block an accrual was performed
+/// * `ir_mod` - The current interest rate modifier of the reserve (7 decimals)
 ///
 /// ### Returns
-/// * (i128, i128) - (accrual amount scaled to 9 decimal places, new interest rate modifier scaled to 9 decimal places)
-#[allow(clippy::zero_prefixed_literal)]
-pub fn calc_accrual(
+/// * i128 - The annual borrow rate scaled to 7 decimal places
+pub fn load_borrow_rate(
     e: &Env,
+    asset: &Address,
     config: &ReserveConfig,
     cur_util: i128,
     ir_mod: i128,
-    last_time: u64,
-) -> (i128, i128) {
-    let cur_ir: i128;
+) -> i128 {
+    let rate = load_uncapped_borrow_rate(e, asset, config, cur_util, ir_mod);
+    cap_borrow_rate(e, asset, rate)
+}
+
+/// Cap a borrow rate at the reserve's max APR, if one is set
+///
+/// ### Arguments
+/// * `asset` - The underlying asset of the reserve
+/// * `rate` - The annual borrow rate (7 decimals)
+pub fn cap_borrow_rate(e: &Env, asset: &Address, rate: i128) -> i128 {
+    match storage::get_max_apr(e, asset) {
+        Some(max_apr) => rate.min(i128(max_apr)),
+        None => rate,
+    }
+}
+
+/// Load the current borrow rate for a reserve, ignoring the reserve's max APR. If the reserve
+/// delegates its rate to a rate strategy, the strategy is queried, and the built-in curve is used
+/// instead if the strategy fails or returns a rate outside of 0 and the max strategy borrow rate.
+///
+/// ### Arguments
+/// * `asset` - The underlying asset of the reserve
+/// * `config` - The Reserve config to calculate the rate for
+/// * `cur_util` - The current utilization rate of the reserve (7 decimals)
+/// * `ir_mod` - The current interest rate modifier of the reserve (7 decimals)
+///
+/// ### Returns
+/// * i128 - The annual borrow rate scaled to 7 decimal places
+pub fn load_uncapped_borrow_rate(
+    e: &Env,
+    asset: &Address,
+    config: &ReserveConfig,
+    cur_util: i128,
+    ir_mod: i128,
+) -> i128 {
+    if let Some(strategy) = storage::get_rate_strategy(e, asset) {
+        let result = RateStrategyClient::new(e, &strategy).try_borrow_rate(
+            &e.current_contract_address(),
+            asset,
+            &cur_util,
+            &ir_mod,
+        );
+        if let Ok(Ok(rate)) = result {
+            if (0..=MAX_STRATEGY_BORROW_RATE).contains(&rate) {
+                return rate;
+            }
+        }
+    }
+    calc_borrow_rate(e, config, cur_util, ir_mod)
+}
+
+/// Calculates the current borrow rate for the Reserve based on the current utilization and
+/// rate modifier for the reserve.
+///
+/// ### Arguments
+/// * `config` - The Reserve config to calculate the rate for
+/// * `cur_util` - The current utilization rate of the reserve (7 decimals)
+/// * `ir_mod` - The current interest rate modifier of the reserve (7 decimals)
+///
+/// ### Returns
+/// * i128 - The annual borrow rate scaled to 7 decimal places
+#[allow(clippy::zero_prefixed_literal)]
+pub fn calc_borrow_rate(e: &Env, config: &ReserveConfig, cur_util: i128, ir_mod: i128) -> i128 {
     let target_util: i128 = i128(config.util);
     if cur_util <= target_util {
         let util_scalar = cur_util.fixed_div_ceil(e, &target_util, &SCALAR_7);
         let base_rate =
             util_scalar.fixed_mul_ceil(e, &i128(config.r_one), &SCALAR_7) + i128(config.r_base);
 
-        cur_ir = base_rate.fixed_mul_ceil(e, &ir_mod, &SCALAR_7);
+        base_rate.fixed_mul_ceil(e, &ir_mod, &SCALAR_7)
     } else if cur_util <= 0_9500000 {
         let util_scalar =
             (cur_util - target_util).fixed_div_ceil(e, &(0_9500000 - target_util), &SCALAR_7);
@@ -42,7 +103,7 @@ pub fn calc_accrual(
             + i128(config.r_one)
             + i128(config.r_base);
 
-        cur_ir = base_rate.fixed_mul_ceil(e, &ir_mod, &SCALAR_7);
+        base_rate.fixed_mul_ceil(e, &ir_mod, &SCALAR_7)
     } else {
         let util_scalar = (cur_util - 0_9500000).fixed_div_ceil(e, &0_0500000, &SCALAR_7);
         let extra_rate = util_scalar.fixed_mul_ceil(e, &i128(config.r_three), &SCALAR_7);
@@ -52,11 +113,60 @@ pub fn calc_accrual(
             &i128(config.r_two + config.r_one + config.r_base),
             &SCALAR_7,
         );
-        cur_ir = extra_rate + intersection;
+        extra_rate + intersection
     }
+}
+
+/// Calculates the loan accrual ratio for the Reserve based on the current utilization and
+/// rate modifier for the reserve.
+///
+/// ### Arguments
+/// * `config` - The Reserve config to calculate an accrual for
+/// * `cur_util` - The current utilization rate of the reserve (7 decimals)
+/// * `ir_mod` - The current interest rate modifier of the reserve (9 decimals)
+/// * `last_time` - The last time an accrual was performed
+/// * `cur_time` - The time to accrue to
+///
+/// ### Returns
+/// * (i128, i128) - (accrual amount scaled to 9 decimal places, new interest rate modifier scaled to 9 decimal places)
+pub fn calc_accrual(
+    e: &Env,
+    config: &ReserveConfig,
+    cur_util: i128,
+    ir_mod: i128,
+    last_time: u64,
+    cur_time: u64,
+) -> (i128, i128) {
+    let cur_ir = calc_borrow_rate(e, config, cur_util, ir_mod);
+    calc_accrual_at_rate(e, config, cur_ir, cur_util, ir_mod, last_time, cur_time)
+}
+
+/// Calculates the loan accrual ratio for the Reserve at the given borrow rate, and the new rate
+/// modifier for the reserve based on the current utilization.
+///
+/// ### Arguments
+/// * `config` - The Reserve config to calculate an accrual for
+/// * `cur_ir` - The current annual borrow rate of the reserve (7 decimals)
+/// * `cur_util` - The current utilization rate of the reserve (7 decimals)
+/// * `ir_mod` - The current interest rate modifier of the reserve (9 decimals)
+/// * `last_time` - The last time an accrual was performed
+/// * `cur_time` - The time to accrue to
+///
+/// ### Returns
+/// * (i128, i128) - (accrual amount scaled to 9 decimal places, new interest rate modifier scaled to 9 decimal places)
+pub fn calc_accrual_at_rate(
+    e: &Env,
+    config: &ReserveConfig,
+    cur_ir: i128,
+    cur_util: i128,
+    ir_mod: i128,
+    last_time: u64,
+    cur_time: u64,
+) -> (i128, i128) {
+    let target_util: i128 = i128(config.util);
 
     // update rate_modifier
-    let delta_time = i128(e.ledger().timestamp() - last_time);
+    let delta_time = i128(cur_time - last_time);
     // this should never occur, but require some time to pass
     if delta_time < 1 {
         panic_with_error!(e, PoolError::InternalError);
@@ -101,8 +211,109 @@ pub fn calc_accrual(
 
 #[cfg(test)]
 mod tests {
+    use crate::testutils;
+
     use super::*;
-    use soroban_sdk::testutils::{Ledger, LedgerInfo};
+    use soroban_sdk::{
+        contract, contractimpl,
+        testutils::{Address as _, Ledger, LedgerInfo},
+        Symbol,
+    };
+
+    /// Returns the rate it was set to, or panics if no rate was set
+    #[contract]
+    pub struct MockRateStrategy;
+
+    #[contractimpl]
+    impl MockRateStrategy {
+        pub fn set_rate(e: Env, rate: i128) {
+            e.storage().instance().set(&Symbol::new(&e, "Rate"), &rate);
+        }
+
+        pub fn borrow_rate(
+            e: Env,
+            _pool: Address,
+            _asset: Address,
+            _util: i128,
+            _ir_mod: i128,
+        ) -> i128 {
+            e.storage()
+                .instance()
+                .get(&Symbol::new(&e, "Rate"))
+                .unwrap()
+        }
+    }
+
+    #[test]
+    fn test_load_borrow_rate_strategy() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+        let asset = Address::generate(&e);
+        let strategy = e.register(MockRateStrategy {}, ());
+        let strategy_client = MockRateStrategyClient::new(&e, &strategy);
+        let (reserve_config, _) = testutils::default_reserve_meta();
+
+        e.as_contract(&pool, || {
+            let curve_rate = calc_borrow_rate(&e, &reserve_config, 0_5000000, 1_0000000);
+            assert_eq!(
+                load_borrow_rate(&e, &asset, &reserve_config, 0_5000000, 1_0000000),
+                curve_rate
+            );
+
+            storage::set_rate_strategy(&e, &asset, &Some(strategy.clone()));
+            // the strategy panics, so the built-in curve is used
+            assert_eq!(
+                load_borrow_rate(&e, &asset, &reserve_config, 0_5000000, 1_0000000),
+                curve_rate
+            );
+        });
+
+        strategy_client.set_rate(&0_1234567);
+        e.as_contract(&pool, || {
+            assert_eq!(
+                load_borrow_rate(&e, &asset, &reserve_config, 0_5000000, 1_0000000),
+                0_1234567
+            );
+        });
+
+        // rates over the max are ignored
+        strategy_client.set_rate(&(MAX_STRATEGY_BORROW_RATE + 1));
+        e.as_contract(&pool, || {
+            let curve_rate = calc_borrow_rate(&e, &reserve_config, 0_5000000, 1_0000000);
+            assert_eq!(
+                load_borrow_rate(&e, &asset, &reserve_config, 0_5000000, 1_0000000),
+                curve_rate
+            );
+        });
+    }
+
+    #[test]
+    fn test_load_borrow_rate_max_apr() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+        let asset = Address::generate(&e);
+        let (reserve_config, _) = testutils::default_reserve_meta();
+
+        e.as_contract(&pool, || {
+            let curve_rate = calc_borrow_rate(&e, &reserve_config, 0_9500000, 1_0000000);
+            assert!(curve_rate > 0_5000000);
+
+            storage::set_max_apr(&e, &asset, &Some(0_5000000));
+            assert_eq!(
+                load_borrow_rate(&e, &asset, &reserve_config, 0_9500000, 1_0000000),
+                0_5000000
+            );
+            assert_eq!(
+                load_uncapped_borrow_rate(&e, &asset, &reserve_config, 0_9500000, 1_0000000),
+                curve_rate
+            );
+            // rates under the max are unchanged
+            assert_eq!(
+                load_borrow_rate(&e, &asset, &reserve_config, 0_1000000, 1_0000000),
+                calc_borrow_rate(&e, &reserve_config, 0_1000000, 1_0000000)
+            );
+        });
+    }
 
     #[test]
     fn test_calc_accrual_util_under_target() {
@@ -136,7 +347,14 @@ mod tests {
             max_entry_ttl: 3110400,
         });
 
-        let (accrual, ir_mod) = calc_accrual(&e, &reserve_config, 0_6565656, ir_mod, 0);
+        let (accrual, ir_mod) = calc_accrual(
+            &e,
+            &reserve_config,
+            0_6565656,
+            ir_mod,
+            0,
+            e.ledger().timestamp(),
+        );
 
         assert_eq!(accrual, 1_000_000_852_536);
         assert_eq!(ir_mod, 0_9999066);
@@ -174,7 +392,14 @@ mod tests {
             max_entry_ttl: 3110400,
         });
 
-        let (accrual, ir_mod) = calc_accrual(&e, &reserve_config, 0_7979797, ir_mod, 0);
+        let (accrual, ir_mod) = calc_accrual(
+            &e,
+            &reserve_config,
+            0_7979797,
+            ir_mod,
+            0,
+            e.ledger().timestamp(),
+        );
 
         assert_eq!(accrual, 1_000_002_853_078);
         assert_eq!(ir_mod, 1_0000479);
@@ -212,7 +437,14 @@ mod tests {
             max_entry_ttl: 3110400,
         });
 
-        let (accrual, ir_mod) = calc_accrual(&e, &reserve_config, 0_9696969, ir_mod, 0);
+        let (accrual, ir_mod) = calc_accrual(
+            &e,
+            &reserve_config,
+            0_9696969,
+            ir_mod,
+            0,
+            e.ledger().timestamp(),
+        );
 
         assert_eq!(accrual, 1_000_018_247_510);
         assert_eq!(ir_mod, 1_0002196);
@@ -250,7 +482,14 @@ mod tests {
             max_entry_ttl: 3110400,
         });
 
-        let (_accrual, ir_mod) = calc_accrual(&e, &reserve_config, 0_9696969, ir_mod, 0);
+        let (_accrual, ir_mod) = calc_accrual(
+            &e,
+            &reserve_config,
+            0_9696969,
+            ir_mod,
+            0,
+            e.ledger().timestamp(),
+        );
 
         assert_eq!(ir_mod, 10_0000000);
     }
@@ -287,7 +526,14 @@ mod tests {
             max_entry_ttl: 3110400,
         });
 
-        let (_accrual, ir_mod) = calc_accrual(&e, &reserve_config, 0_2020202, ir_mod, 0);
+        let (_accrual, ir_mod) = calc_accrual(
+            &e,
+            &reserve_config,
+            0_2020202,
+            ir_mod,
+            0,
+            e.ledger().timestamp(),
+        );
 
         assert_eq!(ir_mod, 0_1000000);
     }
@@ -324,7 +570,14 @@ mod tests {
             max_entry_ttl: 3110400,
         });
 
-        let (accrual, ir_mod) = calc_accrual(&e, &reserve_config, 0_6565656, ir_mod, 0);
+        let (accrual, ir_mod) = calc_accrual(
+            &e,
+            &reserve_config,
+            0_6565656,
+            ir_mod,
+            0,
+            e.ledger().timestamp(),
+        );
 
         assert_eq!(accrual, 1_000_000_852_536);
         assert_eq!(ir_mod, 1_0000000);
@@ -362,7 +615,14 @@ mod tests {
             max_entry_ttl: 3110400,
         });
 
-        let (accrual, ir_mod) = calc_accrual(&e, &reserve_config, 0_0000005, ir_mod, 500);
+        let (accrual, ir_mod) = calc_accrual(
+            &e,
+            &reserve_config,
+            0_0000005,
+            ir_mod,
+            500,
+            e.ledger().timestamp(),
+        );
 
         assert_eq!(accrual, 1_000_000_000_001);
         assert_eq!(ir_mod, 0_1000000);
@@ -400,10 +660,32 @@ mod tests {
             max_entry_ttl: 3110400,
         });
 
-        let (accrual_0, ir_mod_0) = calc_accrual(&e, &reserve_config, 0, ir_mod, 0);
-        let (accrual_1, ir_mod_1) = calc_accrual(&e, &reserve_config, 0_6565656, ir_mod, 0);
-        let (accrual_2, ir_mod_2) = calc_accrual(&e, &reserve_config, 0_7565656, ir_mod, 0);
-        let (accrual_3, ir_mod_3) = calc_accrual(&e, &reserve_config, 0_9565656, ir_mod, 0);
+        let (accrual_0, ir_mod_0) =
+            calc_accrual(&e, &reserve_config, 0, ir_mod, 0, e.ledger().timestamp());
+        let (accrual_1, ir_mod_1) = calc_accrual(
+            &e,
+            &reserve_config,
+            0_6565656,
+            ir_mod,
+            0,
+            e.ledger().timestamp(),
+        );
+        let (accrual_2, ir_mod_2) = calc_accrual(
+            &e,
+            &reserve_config,
+            0_7565656,
+            ir_mod,
+            0,
+            e.ledger().timestamp(),
+        );
+        let (accrual_3, ir_mod_3) = calc_accrual(
+            &e,
+            &reserve_config,
+            0_9565656,
+            ir_mod,
+            0,
+            e.ledger().timestamp(),
+        );
 
         assert_eq!(accrual_0, 1_000_003_963_724);
         assert_eq!(ir_mod_0, 0_9992500);
@@ -0,0 +1,233 @@
+use sep_41_token::TokenClient;
+use soroban_sdk::{contracttype, panic_with_error, Address, Env};
+
+use crate::{
+    errors::{PoolError, PoolErrorExt},
+    events::PoolEvents,
+    storage::{self, KeeperConfig},
+};
+
+/// A maintenance task a registered keeper can perform for a rebate
+#[derive(Clone)]
+#[contracttype]
+pub enum KeeperTask {
+    /// Gulp emissions from the backstop
+    GulpEmissions,
+    /// Update the pool status based on the backstop
+    UpdateStatus,
+    /// Delete a stale auction - (auction_type, user)
+    DelAuction(u32, Address),
+}
+
+/// Set the rebate paid to keepers for performing pool maintenance
+///
+/// ### Arguments
+/// * `config` - The keeper rebate config
+///
+/// ### Panics
+/// * If the rebate is not positive
+/// * If the rebate token is changed while the pot still holds tokens
+pub fn execute_set_keeper_config(e: &Env, config: &KeeperConfig) {
+    if config.rebate <= 0 {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+    if let Some(cur_config) = storage::get_keeper_config(e) {
+        if cur_config.token != config.token && storage::get_keeper_pot(e) > 0 {
+            panic_with_error!(e, PoolError::BadRequest);
+        }
+    }
+    storage::set_keeper_config(e, config);
+}
+
+/// Fund the pot keeper rebates are paid from with the rebate token
+///
+/// Returns the new amount of tokens in the pot
+///
+/// ### Arguments
+/// * `from` - The address funding the pot
+/// * `amount` - The amount of tokens to add
+///
+/// ### Panics
+/// If the amount is not positive or keeper rebates are not configured
+pub fn execute_fund_keeper_pot(e: &Env, from: &Address, amount: i128) -> i128 {
+    let config = match storage::get_keeper_config(e) {
+        Some(config) if amount > 0 => config,
+        _ => panic_with_error!(e, PoolError::BadRequest),
+    };
+    TokenClient::new(e, &config.token).transfer(from, &e.current_contract_address(), &amount);
+    let pot = storage::get_keeper_pot(e) + amount;
+    storage::set_keeper_pot(e, pot);
+    pot
+}
+
+/// Withdraw tokens from the pot keeper rebates are paid from
+///
+/// Returns the remaining amount of tokens in the pot
+///
+/// ### Arguments
+/// * `to` - The address receiving the tokens
+/// * `amount` - The amount of tokens to withdraw
+///
+/// ### Panics
+/// If the amount is not positive or is more than the pot holds
+pub fn execute_withdraw_keeper_pot(e: &Env, to: &Address, amount: i128) -> i128 {
+    let pot = storage::get_keeper_pot(e);
+    let config = match storage::get_keeper_config(e) {
+        Some(config) if amount > 0 && amount <= pot => config,
+        _ => panic_with_error!(e, PoolError::BadRequest),
+    };
+    storage::set_keeper_pot(e, pot - amount);
+    TokenClient::new(e, &config.token).transfer(&e.current_contract_address(), to, &amount);
+    pot - amount
+}
+
+/// Require that an address is a registered keeper
+///
+/// ### Panics
+/// If the address is not a registered keeper
+pub fn require_keeper(e: &Env, keeper: &Address) {
+    if !storage::get_keeper(e, keeper) {
+        panic_with_error!(e, PoolErrorExt::KeeperNotRegistered);
+    }
+}
+
+/// Pay a keeper the rebate for a maintenance task they performed. The rebate is capped at the
+/// tokens left in the pot, and nothing is paid once the pot is empty.
+///
+/// Returns the amount of tokens paid
+///
+/// ### Arguments
+/// * `keeper` - The keeper that performed the task
+pub fn pay_keeper_rebate(e: &Env, keeper: &Address) -> i128 {
+    let config = match storage::get_keeper_config(e) {
+        Some(config) => config,
+        None => return 0,
+    };
+    let pot = storage::get_keeper_pot(e);
+    let rebate = config.rebate.min(pot);
+    if rebate <= 0 {
+        return 0;
+    }
+    storage::set_keeper_pot(e, pot - rebate);
+    TokenClient::new(e, &config.token).transfer(&e.current_contract_address(), keeper, &rebate);
+    PoolEvents::keeper_rebate(e, keeper.clone(), config.token, rebate);
+    rebate
+}
+
+/// Fetch the tokens held in the keeper pot if they are of the given asset, so they are excluded
+/// from the pool's reserve accounting
+///
+/// ### Arguments
+/// * `asset` - The asset
+pub fn get_keeper_pot_of(e: &Env, asset: &Address) -> i128 {
+    match storage::get_keeper_config(e) {
+        Some(config) if &config.token == asset => storage::get_keeper_pot(e),
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testutils;
+
+    use super::*;
+    use soroban_sdk::testutils::Address as _;
+
+    /// Setup a pool with a keeper rebate of 1 token, a registered keeper, and a funder holding
+    /// 100 tokens
+    ///
+    /// Returns (pool, keeper, funder, token_client)
+    fn setup_keepers(e: &Env) -> (Address, Address, Address, TokenClient<'_>) {
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(e);
+        let keeper = Address::generate(e);
+        let funder = Address::generate(e);
+        let pool = testutils::create_pool(e);
+        let (token, token_client) = testutils::create_token_contract(e, &bombadil);
+        token_client.mint(&funder, &100_0000000);
+
+        e.as_contract(&pool, || {
+            execute_set_keeper_config(
+                e,
+                &KeeperConfig {
+                    token: token.clone(),
+                    rebate: 1_0000000,
+                },
+            );
+            storage::set_keeper(e, &keeper, true);
+        });
+        (pool, keeper, funder, TokenClient::new(e, &token))
+    }
+
+    #[test]
+    fn test_pay_keeper_rebate() {
+        let e = Env::default();
+        let (pool, keeper, funder, token_client) = setup_keepers(&e);
+
+        e.as_contract(&pool, || {
+            // nothing is paid while the pot is empty
+            assert_eq!(pay_keeper_rebate(&e, &keeper), 0);
+
+            assert_eq!(execute_fund_keeper_pot(&e, &funder, 1_5000000), 1_5000000);
+            assert_eq!(get_keeper_pot_of(&e, &token_client.address), 1_5000000);
+            assert_eq!(get_keeper_pot_of(&e, &funder), 0);
+
+            assert_eq!(pay_keeper_rebate(&e, &keeper), 1_0000000);
+            // the rebate is capped at the pot
+            assert_eq!(pay_keeper_rebate(&e, &keeper), 0_5000000);
+            assert_eq!(pay_keeper_rebate(&e, &keeper), 0);
+            assert_eq!(storage::get_keeper_pot(&e), 0);
+        });
+        assert_eq!(token_client.balance(&keeper), 1_5000000);
+        assert_eq!(token_client.balance(&pool), 0);
+    }
+
+    #[test]
+    fn test_withdraw_keeper_pot() {
+        let e = Env::default();
+        let (pool, _, funder, token_client) = setup_keepers(&e);
+
+        e.as_contract(&pool, || {
+            execute_fund_keeper_pot(&e, &funder, 10_0000000);
+            assert_eq!(
+                execute_withdraw_keeper_pot(&e, &funder, 4_0000000),
+                6_0000000
+            );
+            assert_eq!(storage::get_keeper_pot(&e), 6_0000000);
+        });
+        assert_eq!(token_client.balance(&funder), 94_0000000);
+        assert_eq!(token_client.balance(&pool), 6_0000000);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_set_keeper_config_token_with_funded_pot() {
+        let e = Env::default();
+        let (pool, _, funder, _) = setup_keepers(&e);
+
+        e.as_contract(&pool, || {
+            execute_fund_keeper_pot(&e, &funder, 10_0000000);
+            execute_set_keeper_config(
+                &e,
+                &KeeperConfig {
+                    token: Address::generate(&e),
+                    rebate: 1_0000000,
+                },
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1251)")]
+    fn test_require_keeper_not_registered() {
+        let e = Env::default();
+        let (pool, keeper, _, _) = setup_keepers(&e);
+
+        e.as_contract(&pool, || {
+            require_keeper(&e, &keeper);
+            storage::set_keeper(&e, &keeper, false);
+            require_keeper(&e, &keeper);
+        });
+    }
+}
@@ -0,0 +1,388 @@
+use sep_41_token::TokenClient;
+use soroban_sdk::{Address, Env};
+
+use crate::{dependencies::LiquidationHookClient, events::PoolEvents, storage};
+
+use super::{health_factor::PositionData, pool::Pool, Request, RequestType, ReserveState, User};
+
+/// The maximum number of requests a liquidation hook can return
+const MAX_HOOK_REQUESTS: u32 = 4;
+
+/// Invoke the user's liquidation protection hook, if one is registered, and apply the
+/// returned requests to the user's positions.
+///
+/// The hook is invoked before a user liquidation auction is created against the user, and by its
+/// own entry point. A hook that fails is ignored, so it can't block the liquidation. If the hook
+/// returns too many requests, they are all skipped, and if a request is not a `SupplyCollateral` or
+/// `Repay` that can be safely applied, the request is skipped instead of panicking.
+///
+/// Returns true if the user is no longer eligible to be liquidated
+///
+/// ### Arguments
+/// * `user` - The user about to be liquidated
+pub fn execute_liquidation_hook(e: &Env, user: &Address) -> bool {
+    let hook = match storage::get_liquidation_hook(e, user) {
+        Some(hook) => hook,
+        None => return false,
+    };
+    let requests = match LiquidationHookClient::new(e, &hook)
+        .try_on_liquidation(&e.current_contract_address(), user)
+    {
+        Ok(Ok(requests)) => requests,
+        _ => return false,
+    };
+    if requests.len() > MAX_HOOK_REQUESTS {
+        return false;
+    }
+
+    let mut pool = Pool::load(e);
+    let mut user_state = User::load(e, user);
+    for request in requests.iter() {
+        if request.amount <= 0 || !storage::has_res(e, &request.address) {
+            continue;
+        }
+        let mut reserve = pool.load_reserve(e, &request.address, true);
+        if request.request_type == RequestType::SupplyCollateral as u32 {
+            apply_hook_supply_collateral(e, &pool, &mut user_state, &mut reserve, &hook, &request);
+        } else if request.request_type == RequestType::Repay as u32 {
+            apply_hook_repay(e, &mut user_state, &mut reserve, &hook, &request);
+        }
+        pool.cache_reserve(reserve);
+    }
+    pool.store_cached_reserves(e);
+    user_state.store(e);
+
+    let position_data = PositionData::calculate_from_positions(e, &mut pool, &user_state.positions);
+    position_data.liability_base <= position_data.collateral_base
+}
+
+/// Apply a "supply_collateral" request from a liquidation hook, if it can be safely applied
+fn apply_hook_supply_collateral(
+    e: &Env,
+    pool: &Pool,
+    user: &mut User,
+    reserve: &mut ReserveState,
+    hook: &Address,
+    request: &Request,
+) {
+    let is_new_position = user.get_collateral(reserve.config.index) == 0;
+    if pool.config.status > 3
+        || !reserve.config.enabled
+        || storage::get_blocked(e, &user.address)
+        || (is_new_position && user.positions.effective_count() >= pool.config.max_positions)
+        || reserve.total_supply(e) + request.amount > reserve.config.supply_cap
+    {
+        return;
+    }
+    let b_tokens_minted = reserve.to_b_token_down(e, request.amount);
+    if b_tokens_minted <= 0 || !try_transfer_from_hook(e, &reserve.asset, hook, request.amount) {
+        return;
+    }
+    user.add_collateral(e, reserve, b_tokens_minted);
+    PoolEvents::supply_collateral(
+        e,
+        reserve.asset.clone(),
+        user.address.clone(),
+        request.amount,
+        b_tokens_minted,
+    );
+}
+
+/// Apply a "repay" request from a liquidation hook, if it can be safely applied
+fn apply_hook_repay(
+    e: &Env,
+    user: &mut User,
+    reserve: &mut ReserveState,
+    hook: &Address,
+    request: &Request,
+) {
+    let cur_d_tokens = user.get_liabilities(reserve.config.index);
+    let mut d_tokens_burnt = reserve.to_d_token_down(e, request.amount);
+    let mut tokens_in = request.amount;
+    if d_tokens_burnt > cur_d_tokens {
+        d_tokens_burnt = cur_d_tokens;
+        tokens_in = reserve.to_asset_from_d_token(e, cur_d_tokens);
+    }
+    if d_tokens_burnt <= 0 || !try_transfer_from_hook(e, &reserve.asset, hook, tokens_in) {
+        return;
+    }
+    user.remove_liabilities(e, reserve, d_tokens_burnt);
+    PoolEvents::repay(
+        e,
+        reserve.asset.clone(),
+        user.address.clone(),
+        tokens_in,
+        d_tokens_burnt,
+    );
+}
+
+/// Attempt to transfer tokens from the hook to the pool. Returns true if the transfer succeeded.
+fn try_transfer_from_hook(e: &Env, asset: &Address, hook: &Address, amount: i128) -> bool {
+    matches!(
+        TokenClient::new(e, asset).try_transfer(hook, &e.current_contract_address(), &amount),
+        Ok(Ok(()))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{storage::PoolConfig, testutils, Positions};
+
+    use super::*;
+    use sep_40_oracle::testutils::Asset;
+    use soroban_sdk::{
+        contract, contractimpl, map,
+        testutils::{Address as _, Ledger, LedgerInfo},
+        vec, Symbol, Vec,
+    };
+
+    #[contract]
+    pub struct MockLiquidationHook;
+
+    #[contractimpl]
+    impl MockLiquidationHook {
+        pub fn set_requests(e: Env, requests: Vec<Request>) {
+            e.storage()
+                .instance()
+                .set(&Symbol::new(&e, "Requests"), &requests);
+        }
+
+        pub fn on_liquidation(e: Env, _pool: Address, _user: Address) -> Vec<Request> {
+            e.storage()
+                .instance()
+                .get(&Symbol::new(&e, "Requests"))
+                .unwrap()
+        }
+    }
+
+    /// Setup a pool with an unhealthy user and a registered liquidation hook
+    ///
+    /// Returns (pool, samwise, hook, underlying_0, underlying_1)
+    fn setup_unhealthy_user(e: &Env) -> (Address, Address, Address, Address, Address) {
+        e.mock_all_auths_allowing_non_root_auth();
+        e.cost_estimate().budget().reset_unlimited();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(e);
+        let samwise = Address::generate(e);
+        let pool = testutils::create_pool(e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(e);
+        let hook = e.register(MockLiquidationHook {}, ());
+
+        let (underlying_0, _) = testutils::create_token_contract(e, &bombadil);
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_config_0.c_factor = 0_8500000;
+        reserve_data_0.last_time = 12345;
+        testutils::create_reserve(e, &pool, &underlying_0, &reserve_config_0, &reserve_data_0);
+
+        let (underlying_1, underlying_1_client) = testutils::create_token_contract(e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_config_1.index = 1;
+        reserve_data_1.last_time = 12345;
+        testutils::create_reserve(e, &pool, &underlying_1, &reserve_config_1, &reserve_data_1);
+        underlying_1_client.mint(&hook, &10_0000000);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(e, "USD")),
+            &vec![
+                e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![e, 2_0000000, 4_0000000]);
+
+        // collateral base: 10 * 2 * 0.85 = 17, liability base: 5 * 4 / 0.75 = 26.67
+        let positions = Positions {
+            collateral: map![e, (0, 10_0000000)],
+            liabilities: map![e, (1, 5_0000000)],
+            supply: map![e],
+        };
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(e, &pool_config);
+            storage::set_user_positions(e, &samwise, &positions);
+            storage::set_liquidation_hook(e, &samwise, &Some(hook.clone()));
+        });
+        (pool, samwise, hook, underlying_0, underlying_1)
+    }
+
+    #[test]
+    fn test_execute_liquidation_hook_no_hook() {
+        let e = Env::default();
+        let (pool, samwise, _, _, _) = setup_unhealthy_user(&e);
+
+        e.as_contract(&pool, || {
+            storage::set_liquidation_hook(&e, &samwise, &None);
+
+            assert!(!execute_liquidation_hook(&e, &samwise));
+            assert_eq!(
+                storage::get_user_positions(&e, &samwise).liabilities,
+                map![&e, (1, 5_0000000)]
+            );
+        });
+    }
+
+    #[test]
+    fn test_execute_liquidation_hook_restores_health() {
+        let e = Env::default();
+        let (pool, samwise, hook, underlying_0, underlying_1) = setup_unhealthy_user(&e);
+        let hook_client = MockLiquidationHookClient::new(&e, &hook);
+        hook_client.set_requests(&vec![
+            &e,
+            Request {
+                request_type: RequestType::Repay as u32,
+                address: underlying_1.clone(),
+                amount: 3_0000000,
+            },
+            // ignored - hooks can only supply collateral or repay
+            Request {
+                request_type: RequestType::WithdrawCollateral as u32,
+                address: underlying_0.clone(),
+                amount: 10_0000000,
+            },
+        ]);
+
+        e.as_contract(&pool, || {
+            assert!(execute_liquidation_hook(&e, &samwise));
+
+            let positions = storage::get_user_positions(&e, &samwise);
+            assert_eq!(positions.collateral, map![&e, (0, 10_0000000)]);
+            assert_eq!(positions.liabilities, map![&e, (1, 2_0000000)]);
+            let reserve_data = storage::get_res_data(&e, &underlying_1);
+            assert_eq!(reserve_data.d_supply, 72_0000000);
+        });
+        let underlying_1_client = TokenClient::new(&e, &underlying_1);
+        assert_eq!(underlying_1_client.balance(&hook), 7_0000000);
+    }
+
+    #[test]
+    fn test_execute_liquidation_hook_partial_repay() {
+        let e = Env::default();
+        let (pool, samwise, hook, _, underlying_1) = setup_unhealthy_user(&e);
+        let hook_client = MockLiquidationHookClient::new(&e, &hook);
+        hook_client.set_requests(&vec![
+            &e,
+            Request {
+                request_type: RequestType::Repay as u32,
+                address: underlying_1.clone(),
+                amount: 1_0000000,
+            },
+        ]);
+
+        e.as_contract(&pool, || {
+            assert!(!execute_liquidation_hook(&e, &samwise));
+
+            let positions = storage::get_user_positions(&e, &samwise);
+            assert_eq!(positions.liabilities, map![&e, (1, 4_0000000)]);
+        });
+    }
+
+    #[test]
+    fn test_create_liquidation_runs_hook() {
+        let e = Env::default();
+        let (pool, samwise, hook, underlying_0, underlying_1) = setup_unhealthy_user(&e);
+        let hook_client = MockLiquidationHookClient::new(&e, &hook);
+        hook_client.set_requests(&vec![
+            &e,
+            Request {
+                request_type: RequestType::Repay as u32,
+                address: underlying_1.clone(),
+                amount: 1_0000000,
+            },
+        ]);
+
+        e.as_contract(&pool, || {
+            let auction_data = crate::auctions::create_auction(
+                &e,
+                0,
+                &samwise,
+                &vec![&e, underlying_1.clone()],
+                &vec![&e, underlying_0.clone()],
+                87,
+            );
+
+            // the auction is sized on the 4 d-tokens left after the hook repaid 1
+            assert_eq!(
+                auction_data.bid.get_unchecked(underlying_1.clone()),
+                3_4800000
+            );
+            let positions = storage::get_user_positions(&e, &samwise);
+            assert_eq!(positions.liabilities, map![&e, (1, 4_0000000)]);
+            assert!(storage::has_auction(&e, &0, &samwise));
+        });
+        let underlying_1_client = TokenClient::new(&e, &underlying_1);
+        assert_eq!(underlying_1_client.balance(&hook), 9_0000000);
+    }
+
+    #[test]
+    fn test_execute_liquidation_hook_skips_failed_transfer() {
+        let e = Env::default();
+        let (pool, samwise, hook, _, underlying_1) = setup_unhealthy_user(&e);
+        let underlying_1_client = TokenClient::new(&e, &underlying_1);
+        underlying_1_client.transfer(&hook, &Address::generate(&e), &9_0000000);
+        let hook_client = MockLiquidationHookClient::new(&e, &hook);
+        hook_client.set_requests(&vec![
+            &e,
+            Request {
+                request_type: RequestType::Repay as u32,
+                address: underlying_1.clone(),
+                amount: 3_0000000,
+            },
+        ]);
+
+        e.as_contract(&pool, || {
+            assert!(!execute_liquidation_hook(&e, &samwise));
+
+            let positions = storage::get_user_positions(&e, &samwise);
+            assert_eq!(positions.liabilities, map![&e, (1, 5_0000000)]);
+        });
+        assert_eq!(underlying_1_client.balance(&hook), 1_0000000);
+    }
+
+    #[test]
+    fn test_execute_liquidation_hook_skips_supply_when_frozen() {
+        let e = Env::default();
+        let (pool, samwise, hook, underlying_0, _) = setup_unhealthy_user(&e);
+        let hook_client = MockLiquidationHookClient::new(&e, &hook);
+        hook_client.set_requests(&vec![
+            &e,
+            Request {
+                request_type: RequestType::SupplyCollateral as u32,
+                address: underlying_0.clone(),
+                amount: 1_0000000,
+            },
+        ]);
+
+        e.as_contract(&pool, || {
+            let mut pool_config = storage::get_pool_config(&e);
+            pool_config.status = 4;
+            storage::set_pool_config(&e, &pool_config);
+
+            assert!(!execute_liquidation_hook(&e, &samwise));
+
+            let positions = storage::get_user_positions(&e, &samwise);
+            assert_eq!(positions.collateral, map![&e, (0, 10_0000000)]);
+        });
+    }
+}
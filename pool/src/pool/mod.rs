@@ -1,38 +1,144 @@
+mod accrual_guard;
+pub use accrual_guard::{execute_set_max_accrual_period, execute_set_max_ledger_time};
+
+mod admin_queue;
+pub use admin_queue::{
+    execute_cancel_queued_admin_action, execute_queue_admin_action, execute_queued_admin_action,
+    require_no_timelock,
+};
+
 mod actions;
-pub use actions::{FlashLoan, Request, RequestType};
+pub use actions::{Deleverage, FlashLoan, HookFill, QuoteFill, Request, RequestType};
+
+mod analytics;
+pub use analytics::{execute_set_analytics_window, get_daily_stats};
 
 mod bad_debt;
-pub use bad_debt::{bad_debt, check_and_handle_backstop_bad_debt, check_and_handle_user_bad_debt};
+pub use bad_debt::{
+    bad_debt, check_and_handle_backstop_bad_debt, check_and_handle_user_bad_debt,
+    is_max_positions_exempt,
+};
+
+mod borrower_index;
+pub use borrower_index::get_borrowers;
+
+mod c_factor_ramp;
+pub use c_factor_ramp::execute_set_c_factor_ramp;
+
+mod checkpoint;
+pub use checkpoint::execute_set_checkpoint_retention;
 
 mod config;
 pub use config::{
     execute_cancel_queued_set_reserve, execute_initialize, execute_queue_set_reserve,
-    execute_set_reserve, execute_update_pool,
+    execute_retire_reserve, execute_set_auction_config, execute_set_base,
+    execute_set_bstop_rate_schedule, execute_set_max_apr, execute_set_oracle,
+    execute_set_oracle_asset, execute_set_price_band, execute_set_rate_strategy,
+    execute_set_reserve, execute_set_reserve_paused, execute_update_pool, reserve_seed_amount,
 };
 
+mod config_snapshot;
+pub use config_snapshot::{calc_config_snapshot, ConfigSnapshot};
+
+mod config_export;
+pub use config_export::{execute_queue_import_config, export_config, PoolConfigExport};
+
+mod credit_line;
+pub use credit_line::execute_set_credit_line;
+
+mod deficit;
+pub use deficit::{execute_repay_deficit, get_deficits};
+
+mod deleverage;
+pub use deleverage::execute_deleverage;
+
+mod delegation;
+pub use delegation::{execute_approve_borrow, execute_delegated_borrow};
+
 mod health_factor;
 pub use health_factor::PositionData;
 
+mod hook_fill;
+pub use hook_fill::execute_fill_with_hook;
+
 mod interest;
+#[cfg(any(test, feature = "testutils"))]
+pub use interest::calc_accrual;
+
+mod liquidation_hook;
+pub use liquidation_hook::execute_liquidation_hook;
+
+mod insurance;
+pub use insurance::{
+    execute_apply_insurance_premium, execute_set_insurance_config, execute_set_insured,
+    is_liq_protected,
+};
+
+mod risk_premium;
+pub use risk_premium::{execute_apply_risk_premium, execute_set_risk_premium};
+
+mod reserve_proposal;
+pub use reserve_proposal::{
+    execute_approve_reserve_proposal, execute_propose_reserve, execute_reclaim_reserve_proposal,
+    execute_reject_reserve_proposal, execute_set_listing_bond, get_proposal_approver,
+};
+
+mod quote_fill;
+pub use quote_fill::{execute_fill_with_quote, execute_set_quote_adapter};
+
+mod reveal_fill;
+pub use reveal_fill::execute_reveal_fill;
+
+mod stable_rate;
+pub use stable_rate::{execute_rebalance_stable_rate, execute_set_stable_rate};
 
 mod submit;
 
-pub use submit::{execute_submit, execute_submit_with_flash_loan};
+pub use submit::{
+    execute_set_borrow_buffer, execute_set_user_min_hf, execute_submit,
+    execute_submit_with_flash_loan, is_supply_on_behalf,
+};
 
 #[allow(clippy::module_inception)]
 mod pool;
 pub use pool::Pool;
 
+mod rate_guard;
+
+mod rates;
+pub use rates::{calc_projected_rates, RateProjection};
+
 mod reserve;
-pub use reserve::Reserve;
+pub use reserve::{Reserve, ReserveState};
+
+mod keeper;
+pub use keeper::{
+    execute_fund_keeper_pot, execute_set_keeper_config, execute_withdraw_keeper_pot,
+    pay_keeper_rebate, require_keeper, KeeperTask,
+};
+
+mod reserve_validation;
 
 mod user;
 pub use user::{Positions, User};
 
 mod status;
 pub use status::{
-    calc_pool_backstop_threshold, execute_set_pool_status, execute_update_pool_status,
+    calc_pool_backstop_threshold, execute_set_freeze_expiry, execute_set_frozen_fees,
+    execute_set_pool_status, execute_set_withdraw_fee, execute_update_pool_status,
 };
 
 mod gulp;
 pub use gulp::execute_gulp;
+
+mod rescue;
+pub use rescue::execute_rescue;
+
+mod totals;
+pub use totals::{calc_pool_totals, PoolTotals};
+
+mod summary;
+pub use summary::{calc_bad_debt, calc_reserve_summary, ReserveSummary};
+
+mod util_breaker;
+pub use util_breaker::execute_set_util_breaker;
@@ -1,31 +1,39 @@
+use soroban_fixed_point_math::SorobanFixedPoint;
 use soroban_sdk::{map, panic_with_error, unwrap::UnwrapOptimized, vec, Address, Env, Map, Vec};
 
 use sep_40_oracle::{Asset, PriceFeedClient};
 
 use crate::{
     errors::PoolError,
+    events::PoolEvents,
     storage::{self, PoolConfig},
     Positions,
 };
 
-use super::reserve::Reserve;
+use super::{
+    config::get_active_bstop_rate, reserve::ReserveState, util_breaker::check_util_breaker,
+};
 
 pub struct Pool {
     pub config: PoolConfig,
-    pub reserves: Map<Address, Reserve>,
+    pub reserves: Map<Address, ReserveState>,
     reserves_to_store: Vec<Address>,
+    loaded_reserves: Map<Address, ReserveState>,
     price_decimals: Option<u32>,
     prices: Map<Address, i128>,
 }
 
 impl Pool {
-    /// Load the Pool from the ledger
+    /// Load the Pool from the ledger. The config's `bstop_rate` is the rate active at the current
+    /// ledger timestamp.
     pub fn load(e: &Env) -> Self {
-        let pool_config = storage::get_pool_config(e);
+        let mut pool_config = storage::get_pool_config(e);
+        pool_config.bstop_rate = get_active_bstop_rate(e, &pool_config);
         Pool {
             config: pool_config,
             reserves: map![e],
             reserves_to_store: vec![e],
+            loaded_reserves: map![e],
             price_decimals: None,
             prices: map![e],
         }
@@ -34,18 +42,25 @@ impl Pool {
     /// Load a Reserve from the ledger and update to the current ledger timestamp. Returns
     /// a cached version if it exists.
     ///
+    /// Reserves are only read from the ledger once per Pool. Updates to a reserve must still be
+    /// cached with `cache_reserve` to be stored.
+    ///
     /// ### Arguments
     /// * asset - The address of the underlying asset
     /// * store - If the reserve is expected to be stored to the ledger
-    pub fn load_reserve(&mut self, e: &Env, asset: &Address, store: bool) -> Reserve {
+    pub fn load_reserve(&mut self, e: &Env, asset: &Address, store: bool) -> ReserveState {
         if store && !self.reserves_to_store.contains(asset) {
             self.reserves_to_store.push_back(asset.clone());
         }
 
         if let Some(reserve) = self.reserves.get(asset.clone()) {
             return reserve;
+        } else if let Some(reserve) = self.loaded_reserves.get(asset.clone()) {
+            return reserve;
         } else {
-            Reserve::load(e, &self.config, asset)
+            let reserve = ReserveState::load(e, &self.config, asset);
+            self.loaded_reserves.set(asset.clone(), reserve.clone());
+            reserve
         }
     }
 
@@ -53,11 +68,12 @@ impl Pool {
     ///
     /// ### Arguments
     /// * reserve - The updated reserve
-    pub fn cache_reserve(&mut self, reserve: Reserve) {
+    pub fn cache_reserve(&mut self, reserve: ReserveState) {
         self.reserves.set(reserve.asset.clone(), reserve);
     }
 
-    /// Store the cached reserves to the ledger that need to be written.
+    /// Store the cached reserves to the ledger that need to be written. Each reserve is written
+    /// once, regardless of how many times it was loaded or cached.
     pub fn store_cached_reserves(&self, e: &Env) {
         for address in self.reserves_to_store.iter() {
             let reserve = self
@@ -66,6 +82,7 @@ impl Pool {
                 .unwrap_or_else(|| panic_with_error!(e, PoolError::InternalReserveNotFound));
             reserve.store(e);
         }
+        check_util_breaker(e, self);
     }
 
     /// Require that the action does not violate the pool status, or panic.
@@ -73,14 +90,21 @@ impl Pool {
     /// ### Arguments
     /// * `action_type` - The type of action being performed
     pub fn require_action_allowed(&self, e: &Env, action_type: u32) {
-        // disable borrowing or auction cancellation for any non-active pool and disable supplying for any frozen pool
-        if (self.config.status > 1 && (action_type == 4 || action_type == 9))
-            || (self.config.status > 3 && (action_type == 2 || action_type == 0))
-        {
+        if !self.is_action_allowed(action_type) {
             panic_with_error!(e, PoolError::InvalidPoolStatus);
         }
     }
 
+    /// Check if the action is allowed by the pool status
+    ///
+    /// ### Arguments
+    /// * `action_type` - The type of action being performed
+    pub fn is_action_allowed(&self, action_type: u32) -> bool {
+        // disable borrowing or auction cancellation for any non-active pool and disable supplying for any frozen pool
+        !((self.config.status > 1 && (action_type == 4 || action_type == 9 || action_type == 13))
+            || (self.config.status > 3 && (action_type == 2 || action_type == 0)))
+    }
+
     /// Require that a position does not violate the maximum number of positions, or panic.
     ///
     /// ### Arguments
@@ -111,6 +135,9 @@ impl Pool {
 
     /// Load a price from the Pool's oracle. Returns a cached version if one already exists.
     ///
+    /// The asset is priced as `Asset::Stellar(asset)` unless an oracle asset is set for it. If the asset
+    /// has a price band set, prices outside the band are clamped to it.
+    ///
     /// ### Arguments
     /// * asset - The address of the underlying asset
     ///
@@ -121,13 +148,41 @@ impl Pool {
             return price;
         }
         let oracle_client = PriceFeedClient::new(e, &self.config.oracle);
-        let oracle_asset = Asset::Stellar(asset.clone());
+        let oracle_asset =
+            storage::get_oracle_asset(e, asset).unwrap_or_else(|| Asset::Stellar(asset.clone()));
         let price_data = oracle_client.lastprice(&oracle_asset).unwrap_optimized();
         if price_data.timestamp + 24 * 60 * 60 < e.ledger().timestamp() || price_data.price <= 0 {
             panic_with_error!(e, PoolError::InvalidPrice);
         }
-        self.prices.set(asset.clone(), price_data.price);
-        price_data.price
+        let mut price = price_data.price;
+        if let Some(band) = storage::get_price_band(e, asset) {
+            let clamped_price = price.clamp(band.min_price, band.max_price);
+            if clamped_price != price {
+                PoolEvents::price_clamped(e, asset.clone(), price, clamped_price);
+                price = clamped_price;
+            }
+        }
+        self.prices.set(asset.clone(), price);
+        price
+    }
+
+    /// Convert a value in the oracle's base asset to USD. The value is returned as is if the
+    /// oracle prices assets in USD.
+    ///
+    /// ### Arguments
+    /// * value - The value in the oracle's base asset, with the oracle's decimals
+    ///
+    /// ### Panics
+    /// If the price of the base's USD asset is invalid
+    pub fn load_usd_value(&mut self, e: &Env, value: i128) -> i128 {
+        match self.config.base.clone() {
+            Some(base) => {
+                let oracle_scalar = 10i128.pow(self.load_price_decimals(e));
+                let usd_price = self.load_price(e, &base.usd_asset);
+                value.fixed_div_floor(e, &usd_price, &oracle_scalar)
+            }
+            None => value,
+        }
     }
 }
 
@@ -139,7 +194,11 @@ mod tests {
         Symbol,
     };
 
-    use crate::{pool::User, storage::ReserveData, testutils};
+    use crate::{
+        pool::User,
+        storage::{PoolBase, PriceBand, ReserveData},
+        testutils,
+    };
 
     use super::*;
 
@@ -173,6 +232,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -245,6 +306,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -252,13 +315,13 @@ mod tests {
             let reserve_0 = pool.load_reserve(&e, &underlying_0, false);
             let mut reserve_1 = pool.load_reserve(&e, &underlying_1, true);
             let mut reserve_2 = pool.load_reserve(&e, &underlying_2, true);
-            reserve_2.data.d_rate = 456;
+            reserve_2.data.d_rate = 1_002_000_000_456;
             pool.cache_reserve(reserve_0.clone());
             pool.cache_reserve(reserve_1.clone());
             pool.cache_reserve(reserve_2.clone());
 
             // verify a duplicate cache takes the most recently cached
-            reserve_1.data.d_rate = 123;
+            reserve_1.data.d_rate = 1_001_000_000_123;
             pool.cache_reserve(reserve_1.clone());
 
             // verify reloading without store flag still stores reserve
@@ -287,9 +350,9 @@ mod tests {
             let new_reserve_data = storage::get_res_data(&e, &underlying_0);
             assert_eq!(new_reserve_data.d_rate, 0);
             let new_reserve_data = storage::get_res_data(&e, &reserve_1.asset);
-            assert_eq!(new_reserve_data.d_rate, 123);
+            assert_eq!(new_reserve_data.d_rate, 1_001_000_000_123);
             let new_reserve_data = storage::get_res_data(&e, &reserve_2.asset);
-            assert_eq!(new_reserve_data.d_rate, 456);
+            assert_eq!(new_reserve_data.d_rate, 1_002_000_000_456);
         });
     }
 
@@ -329,6 +392,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -391,6 +456,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -398,8 +465,8 @@ mod tests {
             let reserve_0 = pool.load_reserve(&e, &underlying_0, false);
             let mut reserve_1 = pool.load_reserve(&e, &underlying_1, true);
             let mut reserve_2 = pool.load_reserve(&e, &underlying_2, true);
-            reserve_1.data.b_rate = 123;
-            reserve_2.data.d_rate = 456;
+            reserve_1.data.b_rate = 1_000_000_000_123;
+            reserve_2.data.d_rate = 1_002_000_000_456;
             pool.cache_reserve(reserve_0.clone());
             pool.cache_reserve(reserve_1.clone());
             // pool.cache_reserve(reserve_2.clone());
@@ -408,6 +475,118 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_reserve_cache_reads_reserve_once() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 123456 * 5,
+            protocol_version: 22,
+            sequence_number: 123456,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let oracle = Address::generate(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            let mut pool = Pool::load(&e);
+            let reserve = pool.load_reserve(&e, &underlying, false);
+
+            // overwrite the reserve data on the ledger to ensure it is not read again
+            let mut overwritten_data = reserve_data.clone();
+            overwritten_data.d_rate = 0;
+            storage::set_res_data(&e, &underlying, &overwritten_data);
+
+            let new_reserve = pool.load_reserve(&e, &underlying, false);
+            assert_eq!(new_reserve.data.d_rate, reserve.data.d_rate);
+            assert_eq!(new_reserve.data.last_time, reserve.data.last_time);
+
+            // verify a reserve that was only read is not written to the ledger
+            pool.store_cached_reserves(&e);
+            let new_reserve_data = storage::get_res_data(&e, &underlying);
+            assert_eq!(new_reserve_data.d_rate, 0);
+        });
+    }
+
+    #[test]
+    fn test_reserve_cache_writes_reserve_once() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 123456 * 5,
+            protocol_version: 22,
+            sequence_number: 123456,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let oracle = Address::generate(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.last_time = 123456 * 5 - 24 * 60 * 60;
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            let mut pool = Pool::load(&e);
+
+            // load and update the reserve as multiple requests in a submit would
+            let mut interest_paid = 0;
+            for _ in 0..3 {
+                let mut reserve = pool.load_reserve(&e, &underlying, true);
+                interest_paid = reserve.accrued.interest_paid;
+                reserve.data.b_supply += 1_0000000;
+                pool.cache_reserve(reserve);
+            }
+            let _ = pool.load_reserve(&e, &underlying, false);
+            assert!(interest_paid > 0);
+
+            // verify the reserve is written once, so the accrued interest is only recorded once
+            pool.store_cached_reserves(&e);
+            let new_reserve_data = storage::get_res_data(&e, &underlying);
+            assert_eq!(new_reserve_data.b_supply, reserve_data.b_supply + 3_0000000);
+            let stats = storage::get_res_stats(&e, &underlying);
+            assert_eq!(stats.interest_paid, interest_paid);
+        });
+    }
+
     #[test]
     #[should_panic(expected = "Error(Contract, #1206)")]
     fn test_require_action_allowed_borrow_while_on_ice_panics() {
@@ -421,6 +600,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 2,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -442,6 +623,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 1,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -464,6 +647,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 2,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -485,6 +670,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 1,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -507,6 +694,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 4,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -529,6 +718,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 4,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -550,6 +741,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 4,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -583,6 +776,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -623,6 +818,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -641,6 +838,166 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_load_usd_value() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let xlm = Address::generate(&e);
+        let usdc = Address::generate(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        // prices are denominated in XLM with 9 decimals
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Stellar(xlm.clone()),
+            &vec![&e, Asset::Stellar(usdc.clone())],
+            &9,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 8_000000000]);
+
+        let mut pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            let mut pool = Pool::load(&e);
+
+            // values are already in USD without a base
+            assert_eq!(pool.load_usd_value(&e, 400_000000000), 400_000000000);
+
+            pool_config.base = Some(PoolBase {
+                asset: sep_40_oracle::Asset::Stellar(xlm.clone()),
+                usd_asset: usdc.clone(),
+            });
+            storage::set_pool_config(&e, &pool_config);
+            let mut pool = Pool::load(&e);
+
+            // 400 XLM is worth 50 USDC at 8 XLM per USDC
+            assert_eq!(pool.load_usd_value(&e, 400_000000000), 50_000000000);
+        });
+    }
+
+    #[test]
+    fn test_load_price_clamps_to_band() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let asset_0 = Address::generate(&e);
+        let asset_1 = Address::generate(&e);
+        let asset_2 = Address::generate(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(asset_0.clone()),
+                Asset::Stellar(asset_1.clone()),
+                Asset::Stellar(asset_2.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 0_5000000, 10_0000000, 1_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+            min_debt: 0,
+            base: None,
+        };
+        let band = PriceBand {
+            min_price: 0_9000000,
+            max_price: 1_1000000,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_price_band(&e, &asset_0, &Some(band.clone()));
+            storage::set_price_band(&e, &asset_1, &Some(band.clone()));
+            storage::set_price_band(&e, &asset_2, &Some(band.clone()));
+            let mut pool = Pool::load(&e);
+
+            let price = pool.load_price(&e, &asset_0);
+            assert_eq!(price, 0_9000000);
+
+            let price = pool.load_price(&e, &asset_1);
+            assert_eq!(price, 1_1000000);
+
+            let price = pool.load_price(&e, &asset_2);
+            assert_eq!(price, 1_0000000);
+        });
+    }
+
+    #[test]
+    fn test_load_price_uses_oracle_asset() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let asset_0 = Address::generate(&e);
+        let asset_1 = Address::generate(&e);
+        let wrapped = Address::generate(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(asset_0.clone()),
+                Asset::Other(Symbol::new(&e, "BTC")),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 123, 456]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_oracle_asset(
+                &e,
+                &wrapped,
+                &Some(sep_40_oracle::Asset::Stellar(asset_0.clone())),
+            );
+            storage::set_oracle_asset(
+                &e,
+                &asset_1,
+                &Some(sep_40_oracle::Asset::Other(Symbol::new(&e, "BTC"))),
+            );
+            let mut pool = Pool::load(&e);
+
+            assert_eq!(pool.load_price(&e, &wrapped), 123);
+            assert_eq!(pool.load_price(&e, &asset_1), 456);
+            assert_eq!(pool.load_price(&e, &asset_0), 123);
+        });
+    }
+
     #[test]
     #[should_panic(expected = "Error(Contract, #1210)")]
     fn test_load_price_panics_if_stale() {
@@ -676,6 +1033,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -721,6 +1080,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -743,6 +1104,8 @@ mod tests {
         let mut user = User {
             address: samwise.clone(),
             positions: Positions::env_default(&e),
+            stable_liabilities: None,
+            is_legacy: false,
         };
         let pool_config = PoolConfig {
             oracle,
@@ -750,6 +1113,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -777,6 +1142,8 @@ mod tests {
         let mut user = User {
             address: samwise.clone(),
             positions: Positions::env_default(&e),
+            stable_liabilities: None,
+            is_legacy: false,
         };
         let pool_config = PoolConfig {
             oracle,
@@ -784,6 +1151,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -814,6 +1183,8 @@ mod tests {
         let mut user = User {
             address: samwise.clone(),
             positions: Positions::env_default(&e),
+            stable_liabilities: None,
+            is_legacy: false,
         };
         let pool_config = PoolConfig {
             oracle,
@@ -821,6 +1192,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -852,6 +1225,8 @@ mod tests {
         let mut user = User {
             address: samwise.clone(),
             positions: Positions::env_default(&e),
+            stable_liabilities: None,
+            is_legacy: false,
         };
         let (oracle, _) = testutils::create_mock_oracle(&e);
         let pool_config = PoolConfig {
@@ -860,6 +1235,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
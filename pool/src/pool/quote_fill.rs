@@ -0,0 +1,414 @@
+use sep_41_token::TokenClient;
+use soroban_sdk::{panic_with_error, vec, Address, Env};
+
+use crate::{
+    auctions::{self, AuctionType},
+    dependencies::SwapAdapterClient,
+    errors::PoolError,
+    events::PoolEvents,
+    storage,
+    validator::require_nonnegative,
+};
+
+use super::{
+    actions::QuoteFill, checkpoint::record_checkpoint, insurance::apply_insurance_premium,
+    pool::Pool, risk_premium::apply_risk_premium, stable_rate::apply_stable_rates,
+    submit::validate_submit, Positions, RequestType, User,
+};
+
+/// Set or remove the swap adapter for a quote asset. Only quote assets with an adapter can be used
+/// to fill liquidation auctions with `execute_fill_with_quote`.
+///
+/// ### Arguments
+/// * `quote_asset` - The quote asset
+/// * `adapter` - The swap adapter for the quote asset, or None to remove it from the whitelist
+///
+/// ### Panics
+/// If the adapter is the pool
+pub fn execute_set_quote_adapter(e: &Env, quote_asset: &Address, adapter: &Option<Address>) {
+    if adapter == &Some(e.current_contract_address()) {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    storage::set_quote_adapter(e, quote_asset, adapter);
+}
+
+/// Fill a user liquidation auction, paying for the bid with a whitelisted quote asset instead of
+/// taking on the bid liabilities.
+///
+/// The filler sends up to `max_quote_in` of the quote asset to the pool. The pool swaps the quote asset
+/// through the quote asset's adapter for each bid asset and repays the liabilities the filler took on.
+/// Any unused quote asset is returned to the filler.
+///
+/// Returns the new positions for the filler
+///
+/// ### Arguments
+/// * `filler` - The address filling the auction
+/// * `quote_fill` - The auction being filled and the quote asset used to fill it
+///
+/// ### Panics
+/// * If the quote asset is not whitelisted
+/// * If the fill requires more than `max_quote_in` of the quote asset
+/// * If the adapter does not deliver the requested amount of a bid asset
+/// * If the filler is unhealthy after the fill
+pub fn execute_fill_with_quote(e: &Env, filler: &Address, quote_fill: QuoteFill) -> Positions {
+    if filler == &e.current_contract_address() {
+        panic_with_error!(e, &PoolError::BadRequest);
+    }
+    require_nonnegative(e, &quote_fill.max_quote_in);
+    let adapter = match storage::get_quote_adapter(e, &quote_fill.quote_asset) {
+        Some(adapter) => adapter,
+        None => panic_with_error!(e, PoolError::BadRequest),
+    };
+
+    let mut pool = Pool::load(e);
+    pool.require_action_allowed(e, RequestType::FillUserLiquidationAuction as u32);
+    let mut filler_state = User::load(e, filler);
+    apply_stable_rates(e, &mut pool, &mut filler_state);
+    apply_risk_premium(e, &mut pool, &mut filler_state);
+    apply_insurance_premium(e, &mut pool, &mut filler_state);
+
+    let prev_positions_count = filler_state.positions.effective_count();
+
+    let filled_auction = auctions::fill(
+        e,
+        &mut pool,
+        AuctionType::UserLiquidation as u32,
+        &quote_fill.user,
+        &mut filler_state,
+        quote_fill.percent,
+    );
+    PoolEvents::fill_auction(
+        e,
+        AuctionType::UserLiquidation as u32,
+        quote_fill.user.clone(),
+        filler.clone(),
+        quote_fill.percent as i128,
+        filled_auction.clone(),
+    );
+
+    let pool_address = e.current_contract_address();
+    let quote_client = TokenClient::new(e, &quote_fill.quote_asset);
+    quote_client.transfer(filler, &pool_address, &quote_fill.max_quote_in);
+
+    // repay the bid liabilities the filler took on with the quote asset
+    let mut quote_remaining = quote_fill.max_quote_in;
+    for (asset, d_tokens) in filled_auction.bid.iter() {
+        let mut reserve = pool.load_reserve(e, &asset, true);
+        let tokens_in = reserve.to_asset_from_d_token(e, d_tokens);
+        if asset == quote_fill.quote_asset {
+            quote_remaining -= tokens_in;
+        } else if quote_remaining > 0 {
+            quote_remaining -= swap_quote_for_asset(
+                e,
+                &adapter,
+                &quote_client,
+                &asset,
+                tokens_in,
+                quote_remaining,
+            );
+        } else {
+            quote_remaining = -1;
+        }
+        if quote_remaining < 0 {
+            panic_with_error!(e, PoolError::MaxQuoteExceeded);
+        }
+
+        filler_state.remove_liabilities(e, &mut reserve, d_tokens);
+        pool.cache_reserve(reserve);
+        PoolEvents::repay(e, asset, filler.clone(), tokens_in, d_tokens);
+    }
+    if quote_remaining > 0 {
+        quote_client.transfer(&pool_address, filler, &quote_remaining);
+    }
+
+    // the filler still holds the lot, so the health check guards against fills of
+    // liabilities that could not be fully repaid
+    validate_submit(
+        e,
+        &mut pool,
+        &filler_state,
+        prev_positions_count,
+        true,
+        false,
+        &vec![e],
+    )
+    .unwrap_or_else(|error| panic_with_error!(e, error));
+
+    record_checkpoint(e, &mut pool, &filler_state);
+
+    pool.store_cached_reserves(e);
+    filler_state.store(e);
+
+    filler_state.positions
+}
+
+/// Swap the quote asset held by the pool for exactly `amount_out` of `asset` through the adapter.
+///
+/// Balances are checked directly instead of trusting the adapter's return value.
+///
+/// Returns the amount of the quote asset spent
+pub(super) fn swap_quote_for_asset(
+    e: &Env,
+    adapter: &Address,
+    quote_client: &TokenClient,
+    asset: &Address,
+    amount_out: i128,
+    max_quote_in: i128,
+) -> i128 {
+    let pool_address = e.current_contract_address();
+    let asset_client = TokenClient::new(e, asset);
+    let quote_balance = quote_client.balance(&pool_address);
+    let asset_balance = asset_client.balance(&pool_address);
+
+    quote_client.transfer(&pool_address, adapter, &max_quote_in);
+    SwapAdapterClient::new(e, adapter).swap_exact_out(
+        &quote_client.address,
+        asset,
+        &amount_out,
+        &max_quote_in,
+        &pool_address,
+    );
+
+    if asset_client.balance(&pool_address) - asset_balance < amount_out {
+        panic_with_error!(e, PoolError::QuoteSwapFailed);
+    }
+    quote_balance - quote_client.balance(&pool_address)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        auctions::AuctionData,
+        storage::{PoolConfig, RiskPremium},
+        testutils,
+    };
+
+    use super::*;
+    use sep_40_oracle::testutils::Asset;
+    use soroban_fixed_point_math::SorobanFixedPoint;
+    use soroban_sdk::{
+        map,
+        testutils::{Address as _, Ledger, LedgerInfo},
+        Symbol,
+    };
+
+    /// Setup a pool with a user liquidation auction against samwise and a whitelisted quote asset
+    ///
+    /// Returns (pool, samwise, frodo, adapter, quote_asset, underlying_0, underlying_1)
+    fn setup_quote_fill(
+        e: &Env,
+    ) -> (
+        Address,
+        Address,
+        Address,
+        Address,
+        Address,
+        Address,
+        Address,
+    ) {
+        e.mock_all_auths_allowing_non_root_auth();
+        e.cost_estimate().budget().reset_unlimited();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 300,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(e);
+        let samwise = Address::generate(e);
+        let frodo = Address::generate(e);
+        let pool = testutils::create_pool(e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(e);
+        let (adapter, adapter_client) = testutils::create_mock_swap_adapter(e);
+
+        let (underlying_0, _) = testutils::create_token_contract(e, &bombadil);
+        let (reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.last_time = 12345;
+        testutils::create_reserve(e, &pool, &underlying_0, &reserve_config_0, &reserve_data_0);
+
+        let (underlying_1, underlying_1_client) = testutils::create_token_contract(e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_config_1.index = 1;
+        reserve_data_1.last_time = 12345;
+        testutils::create_reserve(e, &pool, &underlying_1, &reserve_config_1, &reserve_data_1);
+        underlying_1_client.mint(&adapter, &100_0000000);
+
+        let (quote_asset, quote_client) = testutils::create_token_contract(e, &bombadil);
+        quote_client.mint(&frodo, &100_0000000);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(e, "USD")),
+            &vec![
+                e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![e, 1_0000000, 2_0000000]);
+
+        let positions = Positions {
+            collateral: map![e, (0, 30_0000000)],
+            liabilities: map![e, (1, 10_0000000)],
+            supply: map![e],
+        };
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(e, &pool_config);
+            storage::set_user_positions(e, &samwise, &positions);
+            storage::set_auction(
+                e,
+                &(AuctionType::UserLiquidation as u32),
+                &samwise,
+                &AuctionData {
+                    bid: map![e, (underlying_1.clone(), 10_0000000)],
+                    lot: map![e, (underlying_0.clone(), 25_0000000)],
+                    block: 100,
+                },
+            );
+            execute_set_quote_adapter(e, &quote_asset, &Some(adapter.clone()));
+        });
+        adapter_client.set_price(&2_0000000);
+        (
+            pool,
+            samwise,
+            frodo,
+            adapter,
+            quote_asset,
+            underlying_0,
+            underlying_1,
+        )
+    }
+
+    #[test]
+    fn test_execute_fill_with_quote() {
+        let e = Env::default();
+        let (pool_address, samwise, frodo, _, quote_asset, underlying_0, underlying_1) =
+            setup_quote_fill(&e);
+
+        e.as_contract(&pool_address, || {
+            let mut pool = Pool::load(&e);
+            let reserve_1 = pool.load_reserve(&e, &underlying_1, false);
+            let bid_tokens = reserve_1.to_asset_from_d_token(&e, 5_0000000);
+            let underlying_1_client = TokenClient::new(&e, &underlying_1);
+            let pool_balance_1 = underlying_1_client.balance(&pool_address);
+
+            let positions = execute_fill_with_quote(
+                &e,
+                &frodo,
+                QuoteFill {
+                    user: samwise.clone(),
+                    percent: 50,
+                    quote_asset: quote_asset.clone(),
+                    max_quote_in: 50_0000000,
+                },
+            );
+            assert_eq!(positions.collateral, map![&e, (0, 12_5000000)]);
+            assert_eq!(positions.liabilities.len(), 0);
+
+            let quote_client = TokenClient::new(&e, &quote_asset);
+            let quote_spent = bid_tokens.fixed_mul_ceil(&e, &2_0000000, &1_0000000);
+            assert_eq!(quote_client.balance(&frodo), 100_0000000 - quote_spent);
+            assert_eq!(quote_client.balance(&pool_address), 0);
+            assert_eq!(
+                underlying_1_client.balance(&pool_address),
+                pool_balance_1 + bid_tokens
+            );
+
+            let auction =
+                storage::get_auction(&e, &(AuctionType::UserLiquidation as u32), &samwise);
+            assert_eq!(auction.bid, map![&e, (underlying_1.clone(), 5_0000000)]);
+            assert_eq!(auction.lot, map![&e, (underlying_0.clone(), 12_5000000)]);
+            let samwise_positions = storage::get_user_positions(&e, &samwise);
+            assert_eq!(samwise_positions.liabilities, map![&e, (1, 5_0000000)]);
+        });
+    }
+
+    #[test]
+    fn test_execute_fill_with_quote_applies_risk_premium() {
+        let e = Env::default();
+        let (pool_address, samwise, frodo, _, quote_asset, _, _) = setup_quote_fill(&e);
+
+        e.as_contract(&pool_address, || {
+            storage::set_risk_premium(
+                &e,
+                &Some(RiskPremium {
+                    hf_threshold: 1_5000000,
+                    multiplier: 0_5000000,
+                }),
+            );
+            storage::set_risk_mark(&e, &frodo, &Some(map![&e, (1, 1_000_000_000_000)]));
+
+            execute_fill_with_quote(
+                &e,
+                &frodo,
+                QuoteFill {
+                    user: samwise.clone(),
+                    percent: 50,
+                    quote_asset: quote_asset.clone(),
+                    max_quote_in: 50_0000000,
+                },
+            );
+
+            // the filler had no liabilities before the fill, so their risk mark is cleared
+            assert!(storage::get_risk_mark(&e, &frodo).is_none());
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1229)")]
+    fn test_execute_fill_with_quote_max_quote_exceeded() {
+        let e = Env::default();
+        let (pool_address, samwise, frodo, _, quote_asset, _, _) = setup_quote_fill(&e);
+
+        e.as_contract(&pool_address, || {
+            execute_fill_with_quote(
+                &e,
+                &frodo,
+                QuoteFill {
+                    user: samwise.clone(),
+                    percent: 50,
+                    quote_asset: quote_asset.clone(),
+                    max_quote_in: 0,
+                },
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_execute_fill_with_quote_not_whitelisted() {
+        let e = Env::default();
+        let (pool_address, samwise, frodo, _, quote_asset, _, _) = setup_quote_fill(&e);
+
+        e.as_contract(&pool_address, || {
+            execute_set_quote_adapter(&e, &quote_asset, &None);
+
+            execute_fill_with_quote(
+                &e,
+                &frodo,
+                QuoteFill {
+                    user: samwise.clone(),
+                    percent: 50,
+                    quote_asset: quote_asset.clone(),
+                    max_quote_in: 50_0000000,
+                },
+            );
+        });
+    }
+}
@@ -0,0 +1,114 @@
+use soroban_fixed_point_math::SorobanFixedPoint;
+use soroban_sdk::{panic_with_error, Env};
+
+use crate::{
+    constants::{MAX_RESERVE_RATE, SCALAR_12},
+    errors::{PoolError, PoolErrorExt},
+    storage::ReserveData,
+};
+
+/// Require that an update to a reserve's data keeps the b_rate and d_rate consistent
+///
+/// The d_rate can never decrease. The b_rate can only decrease when liabilities are defaulted on,
+/// and by at most the value of the dTokens removed from the reserve. Neither rate can exceed the
+/// max reserve rate.
+///
+/// ### Arguments
+/// * `prev` - The reserve data currently stored on the ledger
+/// * `next` - The reserve data being stored
+///
+/// ### Panics
+/// * `ReserveRateDecreased` - If the d_rate decreased, or the b_rate decreased by more than a default can cause
+/// * `ReserveRateExceeded` - If either rate is greater than the max reserve rate
+pub fn require_valid_rate_update(e: &Env, prev: &ReserveData, next: &ReserveData) {
+    if next.b_rate > MAX_RESERVE_RATE || next.d_rate > MAX_RESERVE_RATE {
+        panic_with_error!(e, PoolErrorExt::ReserveRateExceeded);
+    }
+
+    if next.d_rate < prev.d_rate {
+        panic_with_error!(e, PoolError::ReserveRateDecreased);
+    }
+
+    if next.b_rate < prev.b_rate {
+        // a default removes dTokens and spreads the loss across the bTokens, rounded up
+        let removed_d_tokens = prev.d_supply - next.d_supply;
+        if removed_d_tokens <= 0 || next.b_supply <= 0 {
+            panic_with_error!(e, PoolError::ReserveRateDecreased);
+        }
+        let max_loss = removed_d_tokens
+            .fixed_mul_ceil(e, &next.d_rate, &SCALAR_12)
+            .fixed_div_ceil(e, &next.b_supply, &SCALAR_12);
+        if prev.b_rate - next.b_rate > max_loss {
+            panic_with_error!(e, PoolError::ReserveRateDecreased);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testutils;
+
+    use super::*;
+
+    #[test]
+    fn test_require_valid_rate_update() {
+        let e = Env::default();
+        let (_, prev) = testutils::default_reserve_meta();
+
+        let mut next = prev.clone();
+        next.b_rate += 1;
+        next.d_rate += 1;
+        require_valid_rate_update(&e, &prev, &next);
+
+        // a default of 10 dTokens can lower the b_rate by up to 10 / 100 bTokens
+        let mut next = prev.clone();
+        next.d_supply -= 10_0000000;
+        next.b_rate -= 0_100_000_000_000;
+        require_valid_rate_update(&e, &prev, &next);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1242)")]
+    fn test_require_valid_rate_update_d_rate_decreased() {
+        let e = Env::default();
+        let (_, prev) = testutils::default_reserve_meta();
+
+        let mut next = prev.clone();
+        next.d_rate -= 1;
+        require_valid_rate_update(&e, &prev, &next);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1242)")]
+    fn test_require_valid_rate_update_b_rate_decreased_without_default() {
+        let e = Env::default();
+        let (_, prev) = testutils::default_reserve_meta();
+
+        let mut next = prev.clone();
+        next.b_rate -= 1;
+        require_valid_rate_update(&e, &prev, &next);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1242)")]
+    fn test_require_valid_rate_update_b_rate_loss_exceeds_default() {
+        let e = Env::default();
+        let (_, prev) = testutils::default_reserve_meta();
+
+        let mut next = prev.clone();
+        next.d_supply -= 10_0000000;
+        next.b_rate -= 0_100_000_000_001;
+        require_valid_rate_update(&e, &prev, &next);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1243)")]
+    fn test_require_valid_rate_update_exceeds_max() {
+        let e = Env::default();
+        let (_, prev) = testutils::default_reserve_meta();
+
+        let mut next = prev.clone();
+        next.d_rate = MAX_RESERVE_RATE + 1;
+        require_valid_rate_update(&e, &prev, &next);
+    }
+}
@@ -0,0 +1,126 @@
+use cast::i128;
+use soroban_fixed_point_math::SorobanFixedPoint;
+use soroban_sdk::{contracttype, Address, Env};
+
+use crate::constants::SCALAR_7;
+
+use super::{interest::load_borrow_rate, Pool};
+
+/// The rates of a reserve after a hypothetical change to its supply and liabilities
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[contracttype]
+pub struct RateProjection {
+    pub util: i128,       // the utilization of the reserve, expressed in 7 decimals
+    pub borrow_apr: i128, // the annual borrow rate, expressed in 7 decimals
+    pub supply_apr: i128, // the annual supply rate after the backstop's take, expressed in 7 decimals
+}
+
+/// Project the rates of a reserve at the current ledger if the supplied amounts were added to the
+/// reserve's supply and liabilities. The reserve's rate modifier is not projected, so the rates are
+/// those the reserve would charge immediately after the change.
+///
+/// ### Arguments
+/// * `asset` - The underlying asset of the reserve
+/// * `supply_delta` - The amount of underlying added to (positive) or removed from (negative) the supply
+/// * `borrow_delta` - The amount of underlying added to (positive) or removed from (negative) the liabilities
+pub fn calc_projected_rates(
+    e: &Env,
+    asset: &Address,
+    supply_delta: i128,
+    borrow_delta: i128,
+) -> RateProjection {
+    let mut pool = Pool::load(e);
+    let reserve = pool.load_reserve(e, asset, false);
+    let supply = (reserve.total_supply(e) + supply_delta).max(0);
+    let liabilities = (reserve.total_liabilities(e) + borrow_delta).max(0);
+
+    // match `Reserve::utilization`, which caps the utilization at 100%
+    let util = if liabilities == 0 {
+        0
+    } else if liabilities >= supply {
+        SCALAR_7
+    } else {
+        liabilities.fixed_div_ceil(e, &supply, &SCALAR_7)
+    };
+    let borrow_apr = load_borrow_rate(e, asset, &reserve.config, util, reserve.data.ir_mod);
+    let supply_apr = borrow_apr
+        .fixed_mul_floor(e, &util, &SCALAR_7)
+        .fixed_mul_floor(e, &(SCALAR_7 - i128(pool.config.bstop_rate)), &SCALAR_7);
+    RateProjection {
+        util,
+        borrow_apr,
+        supply_apr,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        storage::{self, PoolConfig},
+        testutils,
+    };
+
+    use super::*;
+    use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
+
+    #[test]
+    fn test_calc_projected_rates() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, _) = testutils::create_mock_oracle(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.last_time = 12345;
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            // no change keeps the reserve at its 75% target utilization
+            let projection = calc_projected_rates(&e, &underlying, 0, 0);
+            assert_eq!(projection.util, 0_7500000);
+            assert_eq!(projection.borrow_apr, 0_0600000);
+            // 6% * 75% * (1 - 20%)
+            assert_eq!(projection.supply_apr, 0_0360000);
+
+            // borrowing 10 pushes utilization to 85%
+            let projection = calc_projected_rates(&e, &underlying, 0, 10_0000000);
+            assert_eq!(projection.util, 0_8500000);
+            // 6% + 50% * (85% - 75%) / (95% - 75%)
+            assert_eq!(projection.borrow_apr, 0_3100000);
+
+            // supplying 50 drops utilization to 50%
+            let projection = calc_projected_rates(&e, &underlying, 50_0000000, 0);
+            assert_eq!(projection.util, 0_5000000);
+            assert!(projection.borrow_apr < 0_0600000);
+
+            // repaying everything leaves no utilization
+            let projection = calc_projected_rates(&e, &underlying, 0, -100_0000000);
+            assert_eq!(projection.util, 0);
+            assert_eq!(projection.supply_apr, 0);
+        });
+    }
+}
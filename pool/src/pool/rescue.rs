@@ -0,0 +1,124 @@
+use sep_41_token::TokenClient;
+use soroban_sdk::{panic_with_error, Address, Env};
+
+use crate::{dependencies::Backstop, errors::PoolError, storage};
+
+/// Transfer the pool's entire balance of a token sent to it by mistake. Tokens the pool holds on
+/// behalf of users can't be rescued, so the token can't be a reserve, the backstop token, or BLND.
+///
+/// Returns the amount of tokens transferred
+///
+/// ### Arguments
+/// * `asset` - The token to rescue
+/// * `to` - The address receiving the tokens
+///
+/// ### Panics
+/// If the token is a reserve, the backstop token, or BLND
+pub fn execute_rescue(e: &Env, asset: &Address, to: &Address) -> i128 {
+    if storage::has_res(e, asset)
+        || *asset == storage::get_blnd_token(e)
+        || *asset == Backstop::load(e).token()
+    {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+
+    let token_client = TokenClient::new(e, asset);
+    let balance = token_client.balance(&e.current_contract_address());
+    if balance > 0 {
+        token_client.transfer(&e.current_contract_address(), to, &balance);
+    }
+    balance
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testutils;
+
+    use super::*;
+    use soroban_sdk::testutils::Address as _;
+
+    /// Create a pool with a backstop, returning the pool and backstop token
+    fn setup_pool(e: &Env) -> (Address, Address) {
+        let bombadil = Address::generate(e);
+        let pool = testutils::create_pool(e);
+        let (blnd, _) = testutils::create_token_contract(e, &bombadil);
+        let (usdc, _) = testutils::create_token_contract(e, &bombadil);
+        let (backstop_token, _) = testutils::create_token_contract(e, &bombadil);
+        testutils::create_backstop(e, &pool, &backstop_token, &usdc, &blnd);
+        e.as_contract(&pool, || {
+            storage::set_blnd_token(e, &blnd);
+        });
+        (pool, backstop_token)
+    }
+
+    #[test]
+    fn test_execute_rescue() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.cost_estimate().budget().reset_unlimited();
+        let (pool, _) = setup_pool(&e);
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let (token, token_client) = testutils::create_token_contract(&e, &bombadil);
+        token_client.mint(&pool, &123_0000000);
+
+        e.as_contract(&pool, || {
+            let amount = execute_rescue(&e, &token, &samwise);
+            assert_eq!(amount, 123_0000000);
+
+            // nothing is left to rescue
+            let amount = execute_rescue(&e, &token, &samwise);
+            assert_eq!(amount, 0);
+        });
+        assert_eq!(token_client.balance(&samwise), 123_0000000);
+        assert_eq!(token_client.balance(&pool), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_execute_rescue_reserve() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.cost_estimate().budget().reset_unlimited();
+        let (pool, _) = setup_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            execute_rescue(&e, &underlying, &bombadil);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_execute_rescue_backstop_token() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.cost_estimate().budget().reset_unlimited();
+        let (pool, backstop_token) = setup_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            execute_rescue(&e, &backstop_token, &bombadil);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_execute_rescue_blnd() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.cost_estimate().budget().reset_unlimited();
+        let (pool, _) = setup_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            let blnd = storage::get_blnd_token(&e);
+            execute_rescue(&e, &blnd, &bombadil);
+        });
+    }
+}
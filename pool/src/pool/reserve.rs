@@ -3,13 +3,22 @@ use soroban_fixed_point_math::SorobanFixedPoint;
 use soroban_sdk::{contracttype, panic_with_error, Address, Env};
 
 use crate::{
-    constants::{SCALAR_12, SCALAR_7},
+    constants::{RATE_EXT_SCALAR, SCALAR_12, SCALAR_18, SCALAR_7},
     errors::PoolError,
+    events::PoolEvents,
     pool::actions::RequestType,
-    storage::{self, PoolConfig, ReserveConfig, ReserveData},
+    storage::{self, PoolConfig, ReserveConfig, ReserveData, ReserveRateExt, ReserveStats},
 };
 
-use super::interest::calc_accrual;
+use super::{
+    accrual_guard::{cap_accrual_time, get_accrual_time, record_accrual_time},
+    c_factor_ramp::apply_c_factor_ramp,
+    interest::{
+        calc_accrual_at_rate, cap_borrow_rate, load_borrow_rate, load_uncapped_borrow_rate,
+    },
+    rate_guard::require_valid_rate_update,
+    status::get_frozen_fees,
+};
 
 #[derive(Clone, Debug)]
 #[contracttype]
@@ -20,8 +29,23 @@ pub struct Reserve {
     pub scalar: i128,
 }
 
-impl Reserve {
-    /// Load a Reserve from the ledger and update to the current ledger timestamp.
+/// A reserve loaded by the pool, including the state that is not part of the `Reserve` returned
+/// by `get_reserve`
+#[derive(Clone, Debug)]
+#[contracttype(export = false)]
+pub struct ReserveState {
+    pub asset: Address,           // the underlying asset address
+    pub config: ReserveConfig,    // the reserve configuration
+    pub data: ReserveData,        // the reserve data
+    pub rate_ext: ReserveRateExt, // the digits of the reserve's rates beyond 12 decimals
+    pub scalar: i128,
+    pub accrued: ReserveStats, // the interest accrued since the reserve was loaded
+    pub dust: i128, // the rounding dust accrued since the reserve was loaded, in underlying tokens
+}
+
+impl ReserveState {
+    /// Load a Reserve from the ledger and update to the current accrual time, which is the ledger
+    /// timestamp unless the accrual guard is limiting it.
     ///
     /// **NOTE**: This function is not cached, and should be called from the Pool.
     ///
@@ -32,55 +56,123 @@ impl Reserve {
     /// ### Panics
     /// Panics if the asset is not supported, if emissions cannot be updated, or if the reserve
     /// cannot be updated to the current ledger timestamp.
-    pub fn load(e: &Env, pool_config: &PoolConfig, asset: &Address) -> Reserve {
+    pub fn load(e: &Env, pool_config: &PoolConfig, asset: &Address) -> ReserveState {
         let reserve_config = storage::get_res_config(e, asset);
         let reserve_data = storage::get_res_data(e, asset);
-        let mut reserve = Reserve {
+        // reserves last stored with 12 decimal rates have no extension, and are migrated to 18
+        // decimal rates the next time they are stored
+        let rate_ext = storage::get_res_rate_ext(e, asset);
+        let mut reserve = ReserveState {
             asset: asset.clone(),
             scalar: 10i128.pow(reserve_config.decimals),
             config: reserve_config,
             data: reserve_data,
+            rate_ext,
+            accrued: ReserveStats::default(),
+            dust: 0,
         };
+        apply_c_factor_ramp(e, &mut reserve);
+
+        // short circuit if the reserve has already been updated this ledger. The accrual time is
+        // never past the ledger timestamp, so this avoids loading the accrual guard.
+        if e.ledger().timestamp() <= reserve.data.last_time {
+            return reserve;
+        }
 
-        // short circuit if the reserve has already been updated this ledger
-        if e.ledger().timestamp() == reserve.data.last_time {
+        // short circuit if the reserve has already been updated to the accrual time
+        let accrual_time = get_accrual_time(e);
+        if accrual_time <= reserve.data.last_time {
             return reserve;
         }
 
         if reserve.data.b_supply == 0 {
-            reserve.data.last_time = e.ledger().timestamp();
+            reserve.data.last_time = accrual_time;
             return reserve;
         }
 
         let cur_util = reserve.utilization(e);
-        if cur_util == 0 {
-            // if there are no assets borrowed, we don't need to update the reserve
-            reserve.data.last_time = e.ledger().timestamp();
+        let frozen_fees = get_frozen_fees(e, pool_config);
+        if cur_util == 0 || frozen_fees == 2 {
+            // if there are no assets borrowed, or borrower interest is halted while the pool is
+            // frozen, we don't need to update the reserve
+            reserve.data.last_time = accrual_time;
             return reserve;
         }
 
-        let (loan_accrual, new_ir_mod) = calc_accrual(
+        // interest only accrues for up to the max accrual period at once, if capped
+        let accrual_time = cap_accrual_time(e, reserve.data.last_time, accrual_time);
+        let uncapped_ir = load_uncapped_borrow_rate(
             e,
+            &reserve.asset,
             &reserve.config,
             cur_util,
             reserve.data.ir_mod,
+        );
+        let cur_ir = cap_borrow_rate(e, &reserve.asset, uncapped_ir);
+        if cur_ir < uncapped_ir {
+            PoolEvents::borrow_rate_capped(e, reserve.asset.clone(), uncapped_ir, cur_ir);
+        }
+        let (loan_accrual, new_ir_mod) = calc_accrual_at_rate(
+            e,
+            &reserve.config,
+            cur_ir,
+            cur_util,
+            reserve.data.ir_mod,
             reserve.data.last_time,
+            accrual_time,
         );
         reserve.data.ir_mod = new_ir_mod;
 
         let pre_update_liabilities = reserve.total_liabilities(e);
-        reserve.data.d_rate = loan_accrual.fixed_mul_ceil(e, &reserve.data.d_rate, &SCALAR_12);
+        let d_rate = loan_accrual.fixed_mul_ceil(e, &reserve.d_rate_18(), &SCALAR_12);
+        reserve.set_d_rate_18(d_rate);
         let accrued_interest = reserve.total_liabilities(e) - pre_update_liabilities;
 
-        reserve.accrue(e, pool_config.bstop_rate, accrued_interest);
+        // the backstop takes no interest while the pool is frozen if the take rate is halted
+        let bstop_rate = if frozen_fees == 1 {
+            0
+        } else {
+            pool_config.bstop_rate
+        };
+        reserve.accrue(e, bstop_rate, accrued_interest);
 
-        reserve.data.last_time = e.ledger().timestamp();
+        reserve.data.last_time = accrual_time;
         reserve
     }
 
-    /// Store the updated reserve to the ledger.
+    /// Store the updated reserve to the ledger, adding any interest accrued to the reserve's lifetime interest,
+    /// and any rounding dust accrued to the reserve's dust.
+    ///
+    /// ### Panics
+    /// If the update to the b_rate or d_rate is invalid
     pub fn store(&self, e: &Env) {
+        let prev_data = storage::get_res_data(e, &self.asset);
+        require_valid_rate_update(e, &prev_data, &self.data);
         storage::set_res_data(e, &self.asset, &self.data);
+        if storage::get_res_rate_ext(e, &self.asset) != self.rate_ext {
+            storage::set_res_rate_ext(e, &self.asset, &self.rate_ext);
+        }
+        if self.accrued.interest_paid > 0 {
+            let mut stats = storage::get_res_stats(e, &self.asset);
+            stats.interest_paid += self.accrued.interest_paid;
+            stats.supplier_interest += self.accrued.supplier_interest;
+            storage::set_res_stats(e, &self.asset, &stats);
+        }
+        if self.dust > 0 {
+            let dust = storage::get_res_dust(e, &self.asset);
+            storage::set_res_dust(e, &self.asset, dust + self.dust);
+        }
+        record_accrual_time(e);
+    }
+
+    /// Fetch the `Reserve` returned by `get_reserve`
+    pub fn to_reserve(&self) -> Reserve {
+        Reserve {
+            asset: self.asset.clone(),
+            config: self.config.clone(),
+            data: self.data.clone(),
+            scalar: self.scalar,
+        }
     }
 
     /// Accrue tokens to the reserve supply. This issues any `backstop_credit` required and updates the reserve's bRate to account for the additional tokens.
@@ -88,7 +180,7 @@ impl Reserve {
     /// ### Arguments
     /// * bstop_rate - The backstop take rate for the pool
     /// * accrued - The amount of additional underlying tokens
-    fn accrue(&mut self, e: &Env, bstop_rate: u32, accrued: i128) {
+    pub(super) fn accrue(&mut self, e: &Env, bstop_rate: u32, accrued: i128) {
         let pre_update_supply = self.total_supply(e);
 
         if accrued > 0 {
@@ -99,11 +191,13 @@ impl Reserve {
                 new_backstop_credit = accrued.fixed_mul_floor(e, &i128(bstop_rate), &SCALAR_7);
                 self.data.backstop_credit += new_backstop_credit;
             }
-            self.data.b_rate = (pre_update_supply + accrued - new_backstop_credit).fixed_div_floor(
-                e,
-                &self.data.b_supply,
-                &SCALAR_12,
-            );
+            let new_supply = pre_update_supply + accrued - new_backstop_credit;
+            let b_rate = new_supply.fixed_div_floor(e, &self.data.b_supply, &SCALAR_18);
+            self.set_b_rate_18(b_rate);
+            // the tokens lost to rounding the bRate down are not owned by any supplier
+            self.dust += new_supply - self.total_supply(e);
+            self.accrued.interest_paid += accrued;
+            self.accrued.supplier_interest += accrued - new_backstop_credit;
         }
     }
 
@@ -122,6 +216,17 @@ impl Reserve {
             .fixed_div_ceil(e, &self.total_supply(e), &SCALAR_7)
     }
 
+    /// Fetch the current annual borrow rate for the reserve normalized to 7 decimals
+    pub fn borrow_rate(&self, e: &Env) -> i128 {
+        load_borrow_rate(
+            e,
+            &self.asset,
+            &self.config,
+            self.utilization(e),
+            self.data.ir_mod,
+        )
+    }
+
     /// Require that the utilization rate is at or below the maximum allowed, or panic.
     pub fn require_utilization_below_max(&self, e: &Env) {
         if self.utilization(e) > i128(self.config.max_util) {
@@ -149,10 +254,34 @@ impl Reserve {
             if action_type == RequestType::Supply as u32
                 || action_type == RequestType::SupplyCollateral as u32
                 || action_type == RequestType::Borrow as u32
+                || action_type == RequestType::BorrowStable as u32
             {
                 panic_with_error!(e, PoolError::ReserveDisabled);
             }
         }
+        if action_type == RequestType::Supply as u32
+            || action_type == RequestType::SupplyCollateral as u32
+        {
+            if storage::get_reserve_pause(e, &self.asset).supply {
+                panic_with_error!(e, PoolError::ReservePaused);
+            }
+        } else if (action_type == RequestType::Borrow as u32
+            || action_type == RequestType::BorrowStable as u32)
+            && storage::get_reserve_pause(e, &self.asset).borrow
+        {
+            panic_with_error!(e, PoolError::ReservePaused);
+        }
+    }
+
+    /// Fetch the amount of underlying tokens that can be withdrawn before the utilization rate
+    /// exceeds the maximum allowed
+    pub fn available_under_max_util(&self, e: &Env) -> i128 {
+        let min_supply =
+            self.total_liabilities(e)
+                .fixed_div_ceil(e, &i128(self.config.max_util), &SCALAR_7);
+        // round the remaining bTokens up to ensure the utilization stays at or below the maximum
+        let min_b_supply = self.to_b_token_up(e, min_supply);
+        self.to_asset_from_b_token(e, (self.data.b_supply - min_b_supply).max(0))
     }
 
     /// Fetch the total liabilities for the reserve in underlying tokens
@@ -165,6 +294,36 @@ impl Reserve {
         self.to_asset_from_b_token(e, self.data.b_supply)
     }
 
+    /********** Rate Functions **********/
+
+    /// Fetch the bRate with 18 decimals
+    pub fn b_rate_18(&self) -> i128 {
+        self.data.b_rate * RATE_EXT_SCALAR + self.rate_ext.b_rate_ext
+    }
+
+    /// Fetch the dRate with 18 decimals
+    pub fn d_rate_18(&self) -> i128 {
+        self.data.d_rate * RATE_EXT_SCALAR - self.rate_ext.d_rate_ext
+    }
+
+    /// Set the bRate from a rate with 18 decimals. The 12 decimal bRate is rounded down.
+    ///
+    /// ### Arguments
+    /// * `b_rate` - The bRate with 18 decimals
+    pub(super) fn set_b_rate_18(&mut self, b_rate: i128) {
+        self.data.b_rate = b_rate / RATE_EXT_SCALAR;
+        self.rate_ext.b_rate_ext = b_rate % RATE_EXT_SCALAR;
+    }
+
+    /// Set the dRate from a rate with 18 decimals. The 12 decimal dRate is rounded up.
+    ///
+    /// ### Arguments
+    /// * `d_rate` - The dRate with 18 decimals
+    pub(super) fn set_d_rate_18(&mut self, d_rate: i128) {
+        self.data.d_rate = (d_rate + RATE_EXT_SCALAR - 1) / RATE_EXT_SCALAR;
+        self.rate_ext.d_rate_ext = self.data.d_rate * RATE_EXT_SCALAR - d_rate;
+    }
+
     /********** Conversion Functions **********/
 
     /// Convert d_tokens to the corresponding asset value
@@ -172,7 +331,7 @@ impl Reserve {
     /// ### Arguments
     /// * `d_tokens` - The amount of tokens to convert
     pub fn to_asset_from_d_token(&self, e: &Env, d_tokens: i128) -> i128 {
-        d_tokens.fixed_mul_ceil(e, &self.data.d_rate, &SCALAR_12)
+        d_tokens.fixed_mul_ceil(e, &self.d_rate_18(), &SCALAR_18)
     }
 
     /// Convert b_tokens to the corresponding asset value
@@ -180,7 +339,7 @@ impl Reserve {
     /// ### Arguments
     /// * `b_tokens` - The amount of tokens to convert
     pub fn to_asset_from_b_token(&self, e: &Env, b_tokens: i128) -> i128 {
-        b_tokens.fixed_mul_floor(e, &self.data.b_rate, &SCALAR_12)
+        b_tokens.fixed_mul_floor(e, &self.b_rate_18(), &SCALAR_18)
     }
 
     /// Convert d_tokens to their corresponding effective asset value. This
@@ -208,7 +367,7 @@ impl Reserve {
     /// ### Arguments
     /// * `amount` - The amount of tokens to convert
     pub fn to_d_token_up(&self, e: &Env, amount: i128) -> i128 {
-        amount.fixed_div_ceil(e, &self.data.d_rate, &SCALAR_12)
+        amount.fixed_div_ceil(e, &self.d_rate_18(), &SCALAR_18)
     }
 
     /// Convert asset tokens to the corresponding d token value - rounding down
@@ -216,7 +375,7 @@ impl Reserve {
     /// ### Arguments
     /// * `amount` - The amount of tokens to convert
     pub fn to_d_token_down(&self, e: &Env, amount: i128) -> i128 {
-        amount.fixed_div_floor(e, &self.data.d_rate, &SCALAR_12)
+        amount.fixed_div_floor(e, &self.d_rate_18(), &SCALAR_18)
     }
 
     /// Convert asset tokens to the corresponding b token value - round up
@@ -224,7 +383,7 @@ impl Reserve {
     /// ### Arguments
     /// * `amount` - The amount of tokens to convert
     pub fn to_b_token_up(&self, e: &Env, amount: i128) -> i128 {
-        amount.fixed_div_ceil(e, &self.data.b_rate, &SCALAR_12)
+        amount.fixed_div_ceil(e, &self.b_rate_18(), &SCALAR_18)
     }
 
     /// Convert asset tokens to the corresponding b token value - round down
@@ -232,14 +391,14 @@ impl Reserve {
     /// ### Arguments
     /// * `amount` - The amount of tokens to convert
     pub fn to_b_token_down(&self, e: &Env, amount: i128) -> i128 {
-        amount.fixed_div_floor(e, &self.data.b_rate, &SCALAR_12)
+        amount.fixed_div_floor(e, &self.b_rate_18(), &SCALAR_18)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::testutils;
+    use crate::{storage::ReservePause, testutils};
     use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
 
     #[test]
@@ -276,10 +435,12 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 5,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
-            let reserve = Reserve::load(&e, &pool_config, &underlying);
+            let reserve = ReserveState::load(&e, &pool_config, &underlying);
 
             // (accrual: 1_002_957_375_248, util: .7864353)
             assert_eq!(reserve.data.d_rate, 1_349_657_798_173);
@@ -289,6 +450,227 @@ mod tests {
             assert_eq!(reserve.data.b_supply, 99_0000000);
             assert_eq!(reserve.data.backstop_credit, 0_0517357);
             assert_eq!(reserve.data.last_time, 617280);
+            assert_eq!(reserve.dust, 1);
+
+            reserve.store(&e);
+            assert_eq!(storage::get_res_dust(&e, &underlying), 1);
+        });
+    }
+
+    #[test]
+    fn test_load_reserve_migrates_rate_precision() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 123456 * 5,
+            protocol_version: 22,
+            sequence_number: 123456,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let oracle = Address::generate(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.d_rate = 1_345_678_123_000;
+        reserve_data.b_rate = 1_123_456_789_000;
+        reserve_data.d_supply = 65_0000000;
+        reserve_data.b_supply = 99_0000000;
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 5,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            // reserves created before the precision upgrade have no extension digits
+            assert_eq!(
+                storage::get_res_rate_ext(&e, &underlying),
+                ReserveRateExt::default()
+            );
+
+            let reserve = ReserveState::load(&e, &pool_config, &underlying);
+
+            // the 12 decimal rates are unchanged by the extra precision
+            assert_eq!(reserve.data.d_rate, 1_349_657_798_173);
+            assert_eq!(reserve.data.b_rate, 1_125_547_124_242);
+            assert_eq!(reserve.data.b_rate, reserve.b_rate_18() / RATE_EXT_SCALAR);
+            assert!(reserve.rate_ext.b_rate_ext < RATE_EXT_SCALAR);
+            assert!(reserve.rate_ext.d_rate_ext < RATE_EXT_SCALAR);
+
+            reserve.store(&e);
+            let rate_ext = storage::get_res_rate_ext(&e, &underlying);
+            assert_eq!(rate_ext, reserve.rate_ext);
+            assert_ne!(rate_ext, ReserveRateExt::default());
+
+            let reloaded = ReserveState::load(&e, &pool_config, &underlying);
+            assert_eq!(reloaded.b_rate_18(), reserve.b_rate_18());
+            assert_eq!(reloaded.d_rate_18(), reserve.d_rate_18());
+        });
+    }
+
+    #[test]
+    fn test_rate_18_conversions() {
+        let e = Env::default();
+
+        let mut reserve = testutils::default_reserve(&e);
+        reserve.rate_ext.b_rate_ext = 500_000;
+        reserve.rate_ext.d_rate_ext = 500_000;
+
+        // a b_rate of 1.0000000000005 and d_rate of 0.9999999999995
+        assert_eq!(
+            reserve.to_asset_from_b_token(&e, 100_000_000_000_000),
+            100_000_000_000_050
+        );
+        assert_eq!(
+            reserve.to_asset_from_d_token(&e, 100_000_000_000_000),
+            99_999_999_999_950
+        );
+
+        reserve.set_d_rate_18(1_000_000_000_000_000_001);
+        assert_eq!(reserve.data.d_rate, 1_000_000_000_001);
+        assert_eq!(reserve.rate_ext.d_rate_ext, 999_999);
+        assert_eq!(reserve.d_rate_18(), 1_000_000_000_000_000_001);
+
+        reserve.set_b_rate_18(1_000_000_000_000_999_999);
+        assert_eq!(reserve.data.b_rate, 1_000_000_000_000);
+        assert_eq!(reserve.rate_ext.b_rate_ext, 999_999);
+        assert_eq!(reserve.b_rate_18(), 1_000_000_000_000_999_999);
+    }
+
+    #[test]
+    fn test_load_reserve_max_accrual_period() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 123456 * 5,
+            protocol_version: 22,
+            sequence_number: 123456,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let oracle = Address::generate(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.d_rate = 1_345_678_123_000;
+        reserve_data.b_rate = 1_123_456_789_000;
+        reserve_data.d_supply = 65_0000000;
+        reserve_data.b_supply = 99_0000000;
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 5,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_max_accrual_period(&e, 86400);
+
+            // only a day of the ~7 days since the last accrual is accrued
+            let reserve = ReserveState::load(&e, &pool_config, &underlying);
+            assert_eq!(reserve.data.last_time, 86400);
+            assert!(reserve.data.d_rate > 1_345_678_123_000);
+            assert!(reserve.data.d_rate < 1_349_657_798_173);
+            reserve.store(&e);
+
+            // the rest is caught up by the following loads
+            let reserve = ReserveState::load(&e, &pool_config, &underlying);
+            assert_eq!(reserve.data.last_time, 2 * 86400);
+            reserve.store(&e);
+        });
+    }
+
+    #[test]
+    fn test_store_reserve_adds_stats() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 123456 * 5,
+            protocol_version: 22,
+            sequence_number: 123456,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let oracle = Address::generate(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.d_rate = 1_345_678_123_000;
+        reserve_data.b_rate = 1_123_456_789_000;
+        reserve_data.d_supply = 65_0000000;
+        reserve_data.b_supply = 99_0000000;
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 5,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_res_stats(
+                &e,
+                &underlying,
+                &ReserveStats {
+                    interest_paid: 10_0000000,
+                    supplier_interest: 8_0000000,
+                },
+            );
+            let reserve = ReserveState::load(&e, &pool_config, &underlying);
+            // the backstop takes 0_0517357 of the interest
+            assert_eq!(reserve.accrued.interest_paid, 0_2586789);
+            assert_eq!(reserve.accrued.supplier_interest, 0_2069432);
+            reserve.store(&e);
+
+            // reloading the reserve at the same time accrues nothing
+            let reserve = ReserveState::load(&e, &pool_config, &underlying);
+            assert_eq!(reserve.accrued, ReserveStats::default());
+            reserve.store(&e);
+
+            assert_eq!(
+                storage::get_res_stats(&e, &underlying),
+                ReserveStats {
+                    interest_paid: 10_2586789,
+                    supplier_interest: 8_2069432,
+                }
+            );
         });
     }
 
@@ -332,10 +714,12 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 5,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
-            let reserve = Reserve::load(&e, &pool_config, &underlying);
+            let reserve = ReserveState::load(&e, &pool_config, &underlying);
 
             // validate that b and d rates are updated
             assert_eq!(reserve.data.last_time, 1000);
@@ -380,10 +764,12 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
-            let reserve = Reserve::load(&e, &pool_config, &underlying);
+            let reserve = ReserveState::load(&e, &pool_config, &underlying);
 
             assert_eq!(reserve.data.d_rate, 0);
             assert_eq!(reserve.data.b_rate, 0);
@@ -427,10 +813,12 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
-            let reserve = Reserve::load(&e, &pool_config, &underlying);
+            let reserve = ReserveState::load(&e, &pool_config, &underlying);
 
             assert_eq!(reserve.data.d_rate, 0);
             assert_eq!(reserve.data.b_rate, reserve_data.b_rate);
@@ -442,6 +830,55 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_load_reserve_same_timestamp() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 123456 * 5,
+            protocol_version: 22,
+            sequence_number: 123456,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let oracle = Address::generate(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.d_rate = 1_345_678_123_000;
+        reserve_data.b_rate = 1_123_456_789_000;
+        reserve_data.last_time = 123456 * 5;
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            let reserve = ReserveState::load(&e, &pool_config, &underlying);
+
+            assert_eq!(reserve.data.d_rate, reserve_data.d_rate);
+            assert_eq!(reserve.data.b_rate, reserve_data.b_rate);
+            assert_eq!(reserve.data.ir_mod, reserve_data.ir_mod);
+            assert_eq!(reserve.data.backstop_credit, 0);
+            assert_eq!(reserve.data.last_time, 617280);
+            assert_eq!(reserve.accrued.interest_paid, 0);
+        });
+    }
+
     #[test]
     fn test_load_reserve_zero_bstop_rate() {
         let e = Env::default();
@@ -476,11 +913,67 @@ mod tests {
             bstop_rate: 0,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            let reserve = ReserveState::load(&e, &pool_config, &underlying);
+
+            // (accrual: 1_002_957_375_248, util: .7864353)
+            assert_eq!(reserve.data.d_rate, 1_349_657_798_173);
+            assert_eq!(reserve.data.b_rate, 1_126_069_707_070);
+            assert_eq!(reserve.data.ir_mod, 1_0449815);
+            assert_eq!(reserve.data.d_supply, 65_0000000);
+            assert_eq!(reserve.data.b_supply, 99_0000000);
+            assert_eq!(reserve.data.backstop_credit, 0);
+            assert_eq!(reserve.data.last_time, 617280);
+        });
+    }
+
+    #[test]
+    fn test_load_reserve_frozen_halts_bstop_rate() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 123456 * 5,
+            protocol_version: 22,
+            sequence_number: 123456,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let oracle = Address::generate(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.d_rate = 1_345_678_123_000;
+        reserve_data.b_rate = 1_123_456_789_000;
+        reserve_data.d_supply = 65_0000000;
+        reserve_data.b_supply = 99_0000000;
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 4,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
-            let reserve = Reserve::load(&e, &pool_config, &underlying);
+            storage::set_frozen_fees(&e, 1);
+            let reserve = ReserveState::load(&e, &pool_config, &underlying);
 
+            // matches a zero backstop rate
             // (accrual: 1_002_957_375_248, util: .7864353)
             assert_eq!(reserve.data.d_rate, 1_349_657_798_173);
             assert_eq!(reserve.data.b_rate, 1_126_069_707_070);
@@ -492,6 +985,56 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_load_reserve_frozen_halts_interest() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 123456 * 5,
+            protocol_version: 22,
+            sequence_number: 123456,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let oracle = Address::generate(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.d_rate = 1_345_678_123_000;
+        reserve_data.b_rate = 1_123_456_789_000;
+        reserve_data.d_supply = 65_0000000;
+        reserve_data.b_supply = 99_0000000;
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 5,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_frozen_fees(&e, 2);
+            let reserve = ReserveState::load(&e, &pool_config, &underlying);
+
+            assert_eq!(reserve.data.d_rate, 1_345_678_123_000);
+            assert_eq!(reserve.data.b_rate, 1_123_456_789_000);
+            assert_eq!(reserve.data.ir_mod, 1_0000000);
+            assert_eq!(reserve.data.backstop_credit, 0);
+            assert_eq!(reserve.data.last_time, 617280);
+        });
+    }
+
     #[test]
     fn test_store() {
         let e = Env::default();
@@ -526,10 +1069,12 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 5,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
-            let reserve = Reserve::load(&e, &pool_config, &underlying);
+            let reserve = ReserveState::load(&e, &pool_config, &underlying);
             reserve.store(&e);
 
             let reserve_data = storage::get_res_data(&e, &underlying);
@@ -560,6 +1105,35 @@ mod tests {
         assert_eq!(result, 0_7864353);
     }
 
+    #[test]
+    fn test_available_under_max_util() {
+        let e = Env::default();
+
+        let mut reserve = testutils::default_reserve(&e);
+        reserve.data.d_rate = 1_345_678_123_000;
+        reserve.data.b_rate = 1_123_456_789_000;
+        reserve.data.b_supply = 99_0000000;
+        reserve.data.d_supply = 65_0000000;
+
+        let result = reserve.available_under_max_util(&e);
+        assert_eq!(result, 19_1495083);
+
+        reserve.data.b_supply -= reserve.to_b_token_up(&e, result);
+        assert!(reserve.utilization(&e) <= i128(reserve.config.max_util));
+    }
+
+    #[test]
+    fn test_available_under_max_util_over_max() {
+        let e = Env::default();
+
+        let mut reserve = testutils::default_reserve(&e);
+        reserve.data.b_supply = 90_0000000;
+        reserve.data.d_supply = 89_0000000;
+
+        let result = reserve.available_under_max_util(&e);
+        assert_eq!(result, 0);
+    }
+
     #[test]
     fn test_utilization_empty() {
         let e = Env::default();
@@ -844,6 +1418,53 @@ mod tests {
         reserve.require_action_allowed(&e, RequestType::Repay as u32);
     }
 
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1238)")]
+    fn test_require_action_allowed_panics_if_supply_paused() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        let reserve = testutils::default_reserve(&e);
+        e.as_contract(&pool, || {
+            storage::set_reserve_pause(
+                &e,
+                &reserve.asset,
+                &ReservePause {
+                    supply: true,
+                    borrow: false,
+                },
+            );
+
+            reserve.require_action_allowed(&e, RequestType::Borrow as u32);
+            reserve.require_action_allowed(&e, RequestType::SupplyCollateral as u32);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1238)")]
+    fn test_require_action_allowed_panics_if_borrow_paused() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        let reserve = testutils::default_reserve(&e);
+        e.as_contract(&pool, || {
+            storage::set_reserve_pause(
+                &e,
+                &reserve.asset,
+                &ReservePause {
+                    supply: false,
+                    borrow: true,
+                },
+            );
+
+            reserve.require_action_allowed(&e, RequestType::Supply as u32);
+            reserve.require_action_allowed(&e, RequestType::Repay as u32);
+            reserve.require_action_allowed(&e, RequestType::Borrow as u32);
+        });
+    }
+
     #[test]
     fn test_accrue() {
         let e = Env::default();
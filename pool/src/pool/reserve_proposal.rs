@@ -0,0 +1,317 @@
+use sep_41_token::TokenClient;
+use soroban_sdk::{panic_with_error, Address, Env};
+
+use crate::{
+    constants::RESERVE_PROPOSAL_PERIOD,
+    errors::PoolError,
+    storage::{self, ReserveConfig, ReserveProposal},
+};
+
+use super::{config::execute_queue_set_reserve, reserve_validation::require_valid_reserve_config};
+
+/// Set the BLND bond required to propose a reserve
+///
+/// ### Arguments
+/// * `bond` - The amount of BLND to bond, or 0 to disable proposals
+///
+/// ### Panics
+/// If the bond is negative
+pub fn execute_set_listing_bond(e: &Env, bond: i128) {
+    if bond < 0 {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+    storage::set_listing_bond(e, bond);
+}
+
+/// Fetch the address that decides reserve proposals. This is the listing council if one is set,
+/// otherwise the admin.
+pub fn get_proposal_approver(e: &Env) -> Address {
+    storage::get_listing_council(e).unwrap_or_else(|| storage::get_admin(e))
+}
+
+/// Propose adding an asset as a reserve, bonding the pool's listing bond in BLND from the proposer.
+/// The proposal must be approved or rejected before it expires, after which the proposer can reclaim
+/// the bond.
+///
+/// Returns the proposal
+///
+/// ### Arguments
+/// * `proposer` - The address proposing the reserve and posting the bond
+/// * `asset` - The underlying asset of the proposed reserve
+/// * `config` - The proposed reserve config
+///
+/// ### Panics
+/// * If proposals are disabled
+/// * If the asset is already a reserve, or already has a pending proposal or queued reserve set
+/// * If the reserve config is invalid
+pub fn execute_propose_reserve(
+    e: &Env,
+    proposer: &Address,
+    asset: &Address,
+    config: &ReserveConfig,
+) -> ReserveProposal {
+    let bond = storage::get_listing_bond(e);
+    if bond == 0
+        || storage::has_res(e, asset)
+        || storage::has_queued_reserve_set(e, asset)
+        || storage::get_reserve_proposal(e, asset).is_some()
+    {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    require_valid_reserve_config(e, config);
+
+    TokenClient::new(e, &storage::get_blnd_token(e)).transfer(
+        proposer,
+        &e.current_contract_address(),
+        &bond,
+    );
+    let proposal = ReserveProposal {
+        proposer: proposer.clone(),
+        config: config.clone(),
+        bond,
+        expiration: e.ledger().timestamp() + RESERVE_PROPOSAL_PERIOD,
+    };
+    storage::set_reserve_proposal(e, asset, &Some(proposal.clone()));
+    proposal
+}
+
+/// Approve a reserve proposal. The proposed reserve is queued behind the usual reserve timelock, and
+/// the bond is returned to the proposer.
+///
+/// Returns the approved proposal
+///
+/// ### Arguments
+/// * `asset` - The underlying asset of the proposed reserve
+///
+/// ### Panics
+/// If the asset has no pending proposal, or the proposal has expired
+pub fn execute_approve_reserve_proposal(e: &Env, asset: &Address) -> ReserveProposal {
+    let proposal = take_pending_proposal(e, asset);
+    execute_queue_set_reserve(e, asset, &proposal.config);
+    TokenClient::new(e, &storage::get_blnd_token(e)).transfer(
+        &e.current_contract_address(),
+        &proposal.proposer,
+        &proposal.bond,
+    );
+    proposal
+}
+
+/// Reject a reserve proposal. The bond is sent to the backstop if it is slashed, and is otherwise
+/// returned to the proposer.
+///
+/// Returns the rejected proposal
+///
+/// ### Arguments
+/// * `asset` - The underlying asset of the proposed reserve
+/// * `slash` - If the bond is slashed
+///
+/// ### Panics
+/// If the asset has no pending proposal, or the proposal has expired
+pub fn execute_reject_reserve_proposal(e: &Env, asset: &Address, slash: bool) -> ReserveProposal {
+    let proposal = take_pending_proposal(e, asset);
+    let to = if slash {
+        storage::get_backstop(e)
+    } else {
+        proposal.proposer.clone()
+    };
+    TokenClient::new(e, &storage::get_blnd_token(e)).transfer(
+        &e.current_contract_address(),
+        &to,
+        &proposal.bond,
+    );
+    proposal
+}
+
+/// Return the bond of a reserve proposal that expired without being approved or rejected to the
+/// proposer.
+///
+/// Returns the expired proposal
+///
+/// ### Arguments
+/// * `asset` - The underlying asset of the proposed reserve
+///
+/// ### Panics
+/// If the asset has no proposal, or the proposal has not expired
+pub fn execute_reclaim_reserve_proposal(e: &Env, asset: &Address) -> ReserveProposal {
+    let proposal = match storage::get_reserve_proposal(e, asset) {
+        Some(proposal) => proposal,
+        None => panic_with_error!(e, PoolError::BadRequest),
+    };
+    if proposal.expiration > e.ledger().timestamp() {
+        panic_with_error!(e, PoolError::ActionNotUnlocked);
+    }
+    storage::set_reserve_proposal(e, asset, &None);
+    TokenClient::new(e, &storage::get_blnd_token(e)).transfer(
+        &e.current_contract_address(),
+        &proposal.proposer,
+        &proposal.bond,
+    );
+    proposal
+}
+
+/// Remove and return the pending proposal for an asset
+///
+/// ### Panics
+/// If the asset has no proposal, or the proposal has expired
+fn take_pending_proposal(e: &Env, asset: &Address) -> ReserveProposal {
+    let proposal = match storage::get_reserve_proposal(e, asset) {
+        Some(proposal) => proposal,
+        None => panic_with_error!(e, PoolError::BadRequest),
+    };
+    if proposal.expiration <= e.ledger().timestamp() {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    storage::set_reserve_proposal(e, asset, &None);
+    proposal
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{storage::PoolConfig, testutils};
+
+    use super::*;
+    use soroban_sdk::testutils::{Address as _, Ledger};
+
+    /// Setup a pool with a listing bond of 100 BLND and a proposer holding 1000 BLND
+    ///
+    /// Returns (pool, backstop, proposer, blnd_client)
+    fn setup_proposals(e: &Env) -> (Address, Address, Address, TokenClient<'_>) {
+        e.mock_all_auths();
+        e.ledger().set_timestamp(1000);
+
+        let bombadil = Address::generate(e);
+        let proposer = Address::generate(e);
+        let backstop = Address::generate(e);
+        let pool = testutils::create_pool(e);
+        let (oracle, _) = testutils::create_mock_oracle(e);
+        let (blnd, blnd_client) = testutils::create_blnd_token(e, &pool, &bombadil);
+        blnd_client.mint(&proposer, &1000_0000000);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(e, &pool_config);
+            storage::set_backstop(e, &backstop);
+            execute_set_listing_bond(e, 100_0000000);
+        });
+        (pool, backstop, proposer, TokenClient::new(e, &blnd))
+    }
+
+    #[test]
+    fn test_propose_and_approve_reserve() {
+        let e = Env::default();
+        let (pool, _, proposer, blnd_client) = setup_proposals(&e);
+        let asset = Address::generate(&e);
+        let (reserve_config, _) = testutils::default_reserve_meta();
+
+        e.as_contract(&pool, || {
+            let proposal = execute_propose_reserve(&e, &proposer, &asset, &reserve_config);
+            assert_eq!(proposal.bond, 100_0000000);
+            assert_eq!(proposal.expiration, 1000 + RESERVE_PROPOSAL_PERIOD);
+            assert_eq!(storage::get_reserve_proposal(&e, &asset), Some(proposal));
+        });
+        assert_eq!(blnd_client.balance(&proposer), 900_0000000);
+
+        e.as_contract(&pool, || {
+            execute_approve_reserve_proposal(&e, &asset);
+            assert!(storage::get_reserve_proposal(&e, &asset).is_none());
+            assert_eq!(
+                storage::get_queued_reserve_set(&e, &asset).new_config,
+                reserve_config
+            );
+        });
+        assert_eq!(blnd_client.balance(&proposer), 1000_0000000);
+    }
+
+    #[test]
+    fn test_reject_reserve_proposal() {
+        let e = Env::default();
+        let (pool, backstop, proposer, blnd_client) = setup_proposals(&e);
+        let asset_0 = Address::generate(&e);
+        let asset_1 = Address::generate(&e);
+        let (reserve_config, _) = testutils::default_reserve_meta();
+
+        e.as_contract(&pool, || {
+            execute_propose_reserve(&e, &proposer, &asset_0, &reserve_config);
+            execute_propose_reserve(&e, &proposer, &asset_1, &reserve_config);
+
+            execute_reject_reserve_proposal(&e, &asset_0, false);
+            execute_reject_reserve_proposal(&e, &asset_1, true);
+            assert!(storage::get_reserve_proposal(&e, &asset_0).is_none());
+            assert!(storage::get_reserve_proposal(&e, &asset_1).is_none());
+            assert!(!storage::has_queued_reserve_set(&e, &asset_0));
+        });
+        assert_eq!(blnd_client.balance(&proposer), 900_0000000);
+        assert_eq!(blnd_client.balance(&backstop), 100_0000000);
+    }
+
+    #[test]
+    fn test_reclaim_expired_reserve_proposal() {
+        let e = Env::default();
+        let (pool, _, proposer, blnd_client) = setup_proposals(&e);
+        let asset = Address::generate(&e);
+        let (reserve_config, _) = testutils::default_reserve_meta();
+
+        e.as_contract(&pool, || {
+            execute_propose_reserve(&e, &proposer, &asset, &reserve_config);
+        });
+        e.ledger().set_timestamp(1000 + RESERVE_PROPOSAL_PERIOD);
+        e.as_contract(&pool, || {
+            execute_reclaim_reserve_proposal(&e, &asset);
+            assert!(storage::get_reserve_proposal(&e, &asset).is_none());
+        });
+        assert_eq!(blnd_client.balance(&proposer), 1000_0000000);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1239)")]
+    fn test_reclaim_pending_reserve_proposal() {
+        let e = Env::default();
+        let (pool, _, proposer, _) = setup_proposals(&e);
+        let asset = Address::generate(&e);
+        let (reserve_config, _) = testutils::default_reserve_meta();
+
+        e.as_contract(&pool, || {
+            execute_propose_reserve(&e, &proposer, &asset, &reserve_config);
+            execute_reclaim_reserve_proposal(&e, &asset);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_approve_expired_reserve_proposal() {
+        let e = Env::default();
+        let (pool, _, proposer, _) = setup_proposals(&e);
+        let asset = Address::generate(&e);
+        let (reserve_config, _) = testutils::default_reserve_meta();
+
+        e.as_contract(&pool, || {
+            execute_propose_reserve(&e, &proposer, &asset, &reserve_config);
+        });
+        e.ledger().set_timestamp(1000 + RESERVE_PROPOSAL_PERIOD);
+        e.as_contract(&pool, || {
+            execute_approve_reserve_proposal(&e, &asset);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_propose_reserve_disabled() {
+        let e = Env::default();
+        let (pool, _, proposer, _) = setup_proposals(&e);
+        let asset = Address::generate(&e);
+        let (reserve_config, _) = testutils::default_reserve_meta();
+
+        e.as_contract(&pool, || {
+            execute_set_listing_bond(&e, 0);
+            execute_propose_reserve(&e, &proposer, &asset, &reserve_config);
+        });
+    }
+}
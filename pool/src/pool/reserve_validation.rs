@@ -0,0 +1,388 @@
+use soroban_sdk::{panic_with_error, Env};
+
+use crate::{constants::SCALAR_7, errors::PoolError, storage::ReserveConfig};
+
+const SCALAR_7_U32: u32 = SCALAR_7 as u32;
+
+/// The minimum margin, in 7 decimals, between the collateral factor and the liability factor's
+/// reciprocal. Ensures a position that supplies and borrows the same asset can always reach a
+/// health factor below 1 before becoming insolvent, leaving room for a liquidation to be profitable.
+const MIN_FACTOR_MARGIN: u32 = 0_0200000;
+
+/// Validate a reserve config
+///
+/// ### Arguments
+/// * `config` - The reserve config to validate
+///
+/// ### Panics
+/// * `InvalidReserveDecimals` - If the decimals are greater than 18
+/// * `InvalidReserveFactors` - If either factor is greater than 1, or the collateral factor does not
+///   leave enough margin below the liability factor's reciprocal
+/// * `InvalidReserveUtil` - If the target utilization is greater than 90% or not less than the max utilization
+///   which is at most 100%
+/// * `InvalidReserveRates` - If the base rate is not between 0.01% and 100%, or the rates do not strictly increase
+///   for a reserve that is not fixed rate
+/// * `InvalidReserveReactivity` - If the reactivity is greater than 0.0001
+#[allow(clippy::zero_prefixed_literal)]
+pub fn require_valid_reserve_config(e: &Env, config: &ReserveConfig) {
+    if config.decimals > 18 {
+        panic_with_error!(e, PoolError::InvalidReserveDecimals);
+    }
+
+    // verify the factors are at most 1, and that a position borrowing against the same asset
+    // becomes liquidatable at least `MIN_FACTOR_MARGIN` before it becomes insolvent
+    if config.c_factor > SCALAR_7_U32
+        || config.l_factor > SCALAR_7_U32
+        || (config.c_factor as u64 * config.l_factor as u64)
+            > (SCALAR_7_U32 as u64 * (SCALAR_7_U32 - MIN_FACTOR_MARGIN) as u64)
+    {
+        panic_with_error!(e, PoolError::InvalidReserveFactors);
+    }
+
+    if config.util > 0_9000000 || config.max_util > SCALAR_7_U32 || config.max_util <= config.util {
+        panic_with_error!(e, PoolError::InvalidReserveUtil);
+    }
+
+    // verify the rate curve is strictly increasing, unless all slopes are 0 for a fixed rate reserve
+    let is_fixed_rate = config.r_one == 0 && config.r_two == 0 && config.r_three == 0;
+    if config.r_base >= 1_0000000
+        || config.r_base < 0_0001000
+        || (!is_fixed_rate
+            && (config.r_base >= config.r_one
+                || config.r_one >= config.r_two
+                || config.r_two >= config.r_three))
+    {
+        panic_with_error!(e, PoolError::InvalidReserveRates);
+    }
+
+    if config.reactivity > 0_0001000 {
+        panic_with_error!(e, PoolError::InvalidReserveReactivity);
+    }
+}
+
+/// Validate the changes between an existing reserve config and a new one
+///
+/// ### Arguments
+/// * `cur_config` - The reserve's current config
+/// * `config` - The new reserve config
+///
+/// ### Panics
+/// * `InvalidReserveDecimals` - If the decimals changed
+/// * `InvalidReserveFactors` - If the liability factor is set to 0 for a borrowable reserve
+pub fn require_valid_reserve_config_changes(
+    e: &Env,
+    cur_config: &ReserveConfig,
+    config: &ReserveConfig,
+) {
+    if cur_config.decimals != config.decimals {
+        panic_with_error!(e, PoolError::InvalidReserveDecimals);
+    }
+    if cur_config.l_factor != 0 && config.l_factor == 0 {
+        panic_with_error!(e, PoolError::InvalidReserveFactors);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_require_valid_reserve_config() {
+        let e = Env::default();
+
+        // valid
+        let metadata = ReserveConfig {
+            index: 0,
+            decimals: 18,
+            c_factor: 0_7500000,
+            l_factor: 0_7500000,
+            util: 0_5000000,
+            max_util: 0_9500000,
+            r_base: 0_0001000,
+            r_one: 0_0500000,
+            r_two: 0_5000000,
+            r_three: 1_5000000,
+            reactivity: 100,
+            supply_cap: 1000000000000000000,
+            enabled: true,
+        };
+        require_valid_reserve_config(&e, &metadata);
+        // no panic
+        assert!(true);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1232)")]
+    fn test_require_valid_reserve_config_validates_decimals() {
+        let e = Env::default();
+
+        let metadata = ReserveConfig {
+            index: 0,
+            decimals: 19,
+            c_factor: 0_7500000,
+            l_factor: 0_7500000,
+            util: 0_5000000,
+            max_util: 0_9500000,
+            r_base: 0_0001000,
+            r_one: 0_0500000,
+            r_two: 0_5000000,
+            r_three: 1_5000000,
+            reactivity: 100,
+            supply_cap: 1000000000000000000,
+            enabled: true,
+        };
+        require_valid_reserve_config(&e, &metadata);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1233)")]
+    fn test_require_valid_reserve_config_validates_c_factor() {
+        let e = Env::default();
+
+        let metadata = ReserveConfig {
+            index: 0,
+            decimals: 18,
+            c_factor: 1_0000001,
+            l_factor: 0_7500000,
+            util: 0_5000000,
+            max_util: 0_9500000,
+            r_base: 0_0001000,
+            r_one: 0_0500000,
+            r_two: 0_5000000,
+            r_three: 1_5000000,
+            reactivity: 100,
+            supply_cap: 1000000000000000000,
+            enabled: true,
+        };
+        require_valid_reserve_config(&e, &metadata);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1233)")]
+    fn test_require_valid_reserve_config_validates_l_factor() {
+        let e = Env::default();
+
+        let metadata = ReserveConfig {
+            index: 0,
+            decimals: 18,
+            c_factor: 0_7500000,
+            l_factor: 1_0000001,
+            util: 0_5000000,
+            max_util: 0_9500000,
+            r_base: 0_0001000,
+            r_one: 0_0500000,
+            r_two: 0_5000000,
+            r_three: 1_5000000,
+            reactivity: 100,
+            supply_cap: 1000000000000000000,
+            enabled: true,
+        };
+        require_valid_reserve_config(&e, &metadata);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1234)")]
+    fn test_require_valid_reserve_config_validates_util() {
+        let e = Env::default();
+
+        let metadata = ReserveConfig {
+            index: 0,
+            decimals: 18,
+            c_factor: 0_7500000,
+            l_factor: 0_7500000,
+            util: 0_9000001,
+            max_util: 0_9500000,
+            r_base: 0_0001000,
+            r_one: 0_0500000,
+            r_two: 0_5000000,
+            r_three: 1_5000000,
+            reactivity: 100,
+            supply_cap: 1000000000000000000,
+            enabled: true,
+        };
+        require_valid_reserve_config(&e, &metadata);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1234)")]
+    fn test_require_valid_reserve_config_validates_max_util() {
+        let e = Env::default();
+
+        let metadata = ReserveConfig {
+            index: 0,
+            decimals: 18,
+            c_factor: 0_7500000,
+            l_factor: 0_7500000,
+            util: 0_5000000,
+            max_util: 1_0000001,
+            r_base: 0_0001000,
+            r_one: 0_0500000,
+            r_two: 0_5000000,
+            r_three: 1_5000000,
+            reactivity: 100,
+            supply_cap: 1000000000000000000,
+            enabled: true,
+        };
+        require_valid_reserve_config(&e, &metadata);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1235)")]
+    fn test_require_valid_reserve_config_validates_r_base_too_high() {
+        let e = Env::default();
+
+        let metadata = ReserveConfig {
+            index: 0,
+            decimals: 18,
+            c_factor: 0_7500000,
+            l_factor: 0_7500000,
+            util: 0_5000000,
+            max_util: 0_9500000,
+            r_base: 1_0000000,
+            r_one: 0_0500000,
+            r_two: 0_5000000,
+            r_three: 1_5000000,
+            reactivity: 100,
+            supply_cap: 1000000000000000000,
+            enabled: true,
+        };
+        require_valid_reserve_config(&e, &metadata);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1235)")]
+    fn test_require_valid_reserve_config_validates_r_base_too_low() {
+        let e = Env::default();
+
+        let metadata = ReserveConfig {
+            index: 0,
+            decimals: 18,
+            c_factor: 0_7500000,
+            l_factor: 0_7500000,
+            util: 0_5000000,
+            max_util: 0_9500000,
+            r_base: 0_0000999,
+            r_one: 0_0500000,
+            r_two: 0_5000000,
+            r_three: 1_5000000,
+            reactivity: 100,
+            supply_cap: 1000000000000000000,
+            enabled: true,
+        };
+        require_valid_reserve_config(&e, &metadata);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1235)")]
+    fn test_require_valid_reserve_config_validates_r_order() {
+        let e = Env::default();
+
+        let metadata = ReserveConfig {
+            index: 0,
+            decimals: 18,
+            c_factor: 0_7500000,
+            l_factor: 0_7500000,
+            util: 0_5000000,
+            max_util: 0_9500000,
+            r_base: 0_0100000,
+            r_one: 0_5000001,
+            r_two: 0_5000000,
+            r_three: 1_5000000,
+            reactivity: 100,
+            supply_cap: 1000000000000000000,
+            enabled: true,
+        };
+        require_valid_reserve_config(&e, &metadata);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1236)")]
+    fn test_require_valid_reserve_config_validates_reactivity() {
+        let e = Env::default();
+
+        let metadata = ReserveConfig {
+            index: 0,
+            decimals: 18,
+            c_factor: 0_7500000,
+            l_factor: 0_7500000,
+            util: 0_5000000,
+            max_util: 0_9500000,
+            r_base: 0_0100000,
+            r_one: 0_0500000,
+            r_two: 0_5000000,
+            r_three: 1_5000000,
+            reactivity: 0_0001001,
+            supply_cap: 1000000000000000000,
+            enabled: true,
+        };
+        require_valid_reserve_config(&e, &metadata);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1233)")]
+    fn test_require_valid_reserve_config_validates_factor_margin() {
+        let e = Env::default();
+
+        let metadata = ReserveConfig {
+            index: 0,
+            decimals: 18,
+            c_factor: 1_0000000,
+            l_factor: 0_9900000,
+            util: 0_5000000,
+            max_util: 0_9500000,
+            r_base: 0_0100000,
+            r_one: 0_0500000,
+            r_two: 0_5000000,
+            r_three: 1_5000000,
+            reactivity: 100,
+            supply_cap: 1000000000000000000,
+            enabled: true,
+        };
+        require_valid_reserve_config(&e, &metadata);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1235)")]
+    fn test_require_valid_reserve_config_validates_r_strictly_increasing() {
+        let e = Env::default();
+
+        let metadata = ReserveConfig {
+            index: 0,
+            decimals: 18,
+            c_factor: 0_7500000,
+            l_factor: 0_7500000,
+            util: 0_5000000,
+            max_util: 0_9500000,
+            r_base: 0_0100000,
+            r_one: 0_5000000,
+            r_two: 0_5000000,
+            r_three: 1_5000000,
+            reactivity: 100,
+            supply_cap: 1000000000000000000,
+            enabled: true,
+        };
+        require_valid_reserve_config(&e, &metadata);
+    }
+
+    #[test]
+    fn test_require_valid_reserve_config_fixed_rate() {
+        let e = Env::default();
+
+        let metadata = ReserveConfig {
+            index: 0,
+            decimals: 7,
+            c_factor: 0_9000000,
+            l_factor: 0_9000000,
+            util: 0_0000050,
+            max_util: 1_0000000,
+            r_base: 0_1000000,
+            r_one: 0,
+            r_two: 0,
+            r_three: 0,
+            reactivity: 0,
+            supply_cap: 1000000000000000000,
+            enabled: true,
+        };
+        require_valid_reserve_config(&e, &metadata);
+    }
+}
@@ -0,0 +1,261 @@
+use soroban_sdk::{panic_with_error, vec, Address, BytesN, Env};
+
+use crate::{
+    auctions::{self, AuctionType},
+    errors::PoolError,
+    events::PoolEvents,
+};
+
+use super::{
+    checkpoint::record_checkpoint, pool::Pool, submit::validate_submit, Positions, RequestType,
+    User,
+};
+
+/// Fill a user liquidation auction by revealing a fill the filler committed to in an earlier block.
+/// This is the only way to fill user liquidation auctions that require a commitment.
+///
+/// Returns the new positions for the filler
+///
+/// ### Arguments
+/// * `filler` - The address filling the auction
+/// * `user` - The user being liquidated
+/// * `percent_filled` - The percentage being filled as a number (i.e. 15 => 15%)
+/// * `salt` - The salt the fill was committed to with
+///
+/// ### Panics
+/// * If the filler is the pool
+/// * If the filler's commitment does not match the fill or can't be revealed in the current block
+/// * If the filler is unhealthy after the fill
+pub fn execute_reveal_fill(
+    e: &Env,
+    filler: &Address,
+    user: &Address,
+    percent_filled: u64,
+    salt: &BytesN<32>,
+) -> Positions {
+    if filler == &e.current_contract_address() {
+        panic_with_error!(e, &PoolError::BadRequest);
+    }
+    auctions::consume_fill_commitment(e, filler, user, percent_filled, salt);
+
+    let mut pool = Pool::load(e);
+    pool.require_action_allowed(e, RequestType::FillUserLiquidationAuction as u32);
+    let mut filler_state = User::load(e, filler);
+    let prev_positions_count = filler_state.positions.effective_count();
+
+    let filled_auction =
+        auctions::fill_revealed(e, &mut pool, user, &mut filler_state, percent_filled);
+    PoolEvents::fill_auction(
+        e,
+        AuctionType::UserLiquidation as u32,
+        user.clone(),
+        filler.clone(),
+        percent_filled as i128,
+        filled_auction,
+    );
+
+    validate_submit(
+        e,
+        &mut pool,
+        &filler_state,
+        prev_positions_count,
+        true,
+        false,
+        &vec![e],
+    )
+    .unwrap_or_else(|error| panic_with_error!(e, error));
+
+    record_checkpoint(e, &mut pool, &filler_state);
+
+    pool.store_cached_reserves(e);
+    filler_state.store(e);
+
+    filler_state.positions
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        auctions::{execute_commit_fill, execute_set_fill_commit_config, hash_fill, AuctionData},
+        storage::{self, FillCommitConfig, PoolConfig},
+        testutils,
+    };
+
+    use super::*;
+    use sep_40_oracle::testutils::Asset;
+    use soroban_sdk::{
+        map,
+        testutils::{Address as _, Ledger, LedgerInfo},
+        Symbol,
+    };
+
+    /// Setup a pool with a user liquidation auction against samwise with a bid worth 20, and
+    /// commit-reveal fills required for auctions worth at least 10
+    ///
+    /// Returns (pool, samwise, frodo, underlying_0, underlying_1)
+    fn setup_reveal_fill(e: &Env) -> (Address, Address, Address, Address, Address) {
+        e.mock_all_auths();
+        e.cost_estimate().budget().reset_unlimited();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 300,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(e);
+        let samwise = Address::generate(e);
+        let frodo = Address::generate(e);
+        let pool = testutils::create_pool(e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(e);
+
+        let (underlying_0, _) = testutils::create_token_contract(e, &bombadil);
+        let (reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.last_time = 12345;
+        testutils::create_reserve(e, &pool, &underlying_0, &reserve_config_0, &reserve_data_0);
+
+        let (underlying_1, _) = testutils::create_token_contract(e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_config_1.index = 1;
+        reserve_data_1.last_time = 12345;
+        testutils::create_reserve(e, &pool, &underlying_1, &reserve_config_1, &reserve_data_1);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(e, "USD")),
+            &vec![
+                e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![e, 1_0000000, 2_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(e, &pool_config);
+            storage::set_user_positions(
+                e,
+                &samwise,
+                &Positions {
+                    collateral: map![e, (0, 30_0000000)],
+                    liabilities: map![e, (1, 10_0000000)],
+                    supply: map![e],
+                },
+            );
+            storage::set_user_positions(
+                e,
+                &frodo,
+                &Positions {
+                    collateral: map![e, (0, 100_0000000)],
+                    liabilities: map![e],
+                    supply: map![e],
+                },
+            );
+            storage::set_auction(
+                e,
+                &(AuctionType::UserLiquidation as u32),
+                &samwise,
+                &AuctionData {
+                    bid: map![e, (underlying_1.clone(), 10_0000000)],
+                    lot: map![e, (underlying_0.clone(), 25_0000000)],
+                    block: 100,
+                },
+            );
+            execute_set_fill_commit_config(
+                e,
+                &Some(FillCommitConfig {
+                    min_value: 10_0000000,
+                    window: 10,
+                }),
+            );
+        });
+        (pool, samwise, frodo, underlying_0, underlying_1)
+    }
+
+    #[test]
+    fn test_execute_reveal_fill() {
+        let e = Env::default();
+        let (pool_address, samwise, frodo, underlying_0, underlying_1) = setup_reveal_fill(&e);
+        let salt = BytesN::from_array(&e, &[1u8; 32]);
+
+        e.as_contract(&pool_address, || {
+            let hash = hash_fill(&e, &samwise, 50, &salt);
+            execute_commit_fill(&e, &frodo, &hash);
+        });
+
+        e.ledger().set_sequence_number(301);
+        e.as_contract(&pool_address, || {
+            let positions = execute_reveal_fill(&e, &frodo, &samwise, 50, &salt);
+            assert_eq!(positions.collateral, map![&e, (0, 112_5000000)]);
+            assert_eq!(positions.liabilities, map![&e, (1, 5_0000000)]);
+            assert!(storage::get_fill_commitment(&e, &frodo).is_none());
+
+            let auction =
+                storage::get_auction(&e, &(AuctionType::UserLiquidation as u32), &samwise);
+            assert_eq!(auction.bid, map![&e, (underlying_1.clone(), 5_0000000)]);
+            assert_eq!(auction.lot, map![&e, (underlying_0.clone(), 12_5000000)]);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1252)")]
+    fn test_fill_requires_commitment() {
+        let e = Env::default();
+        let (pool_address, samwise, frodo, _, _) = setup_reveal_fill(&e);
+
+        e.as_contract(&pool_address, || {
+            let mut pool = Pool::load(&e);
+            let mut frodo_state = User::load(&e, &frodo);
+            auctions::fill(
+                &e,
+                &mut pool,
+                AuctionType::UserLiquidation as u32,
+                &samwise,
+                &mut frodo_state,
+                50,
+            );
+        });
+    }
+
+    #[test]
+    fn test_fill_under_min_value_without_commitment() {
+        let e = Env::default();
+        let (pool_address, samwise, frodo, _, _) = setup_reveal_fill(&e);
+
+        e.as_contract(&pool_address, || {
+            execute_set_fill_commit_config(
+                &e,
+                &Some(FillCommitConfig {
+                    min_value: 20_0000001,
+                    window: 10,
+                }),
+            );
+            let mut pool = Pool::load(&e);
+            let mut frodo_state = User::load(&e, &frodo);
+            auctions::fill(
+                &e,
+                &mut pool,
+                AuctionType::UserLiquidation as u32,
+                &samwise,
+                &mut frodo_state,
+                50,
+            );
+            assert_eq!(frodo_state.positions.liabilities, map![&e, (1, 5_0000000)]);
+        });
+    }
+}
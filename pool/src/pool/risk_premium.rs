@@ -0,0 +1,280 @@
+use cast::i128;
+use soroban_fixed_point_math::SorobanFixedPoint;
+use soroban_sdk::{map, panic_with_error, Address, Env};
+
+use crate::{
+    constants::{SCALAR_12, SCALAR_7},
+    errors::PoolError,
+    events::PoolEvents,
+    storage::{self, RiskPremium},
+};
+
+use super::{health_factor::PositionData, Pool, Positions, User};
+
+/// Set or remove the pool's risk premium
+///
+/// ### Arguments
+/// * `risk_premium` - The risk premium, or None to disable it
+///
+/// ### Panics
+/// If the risk premium is invalid
+pub fn execute_set_risk_premium(e: &Env, risk_premium: &Option<RiskPremium>) {
+    if let Some(risk_premium) = risk_premium {
+        // verify the threshold is between 1 and 2 so only unhealthy-leaning accounts are charged,
+        // and the premium at most doubles the interest accrued
+        if risk_premium.hf_threshold <= SCALAR_7
+            || risk_premium.hf_threshold > 2_0000000
+            || risk_premium.multiplier > 1_0000000
+        {
+            panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+        }
+    }
+    storage::set_risk_premium(e, risk_premium);
+}
+
+/// Checkpoint the risk premium for a user and store the results.
+///
+/// Returns the user's positions
+///
+/// ### Arguments
+/// * `user` - The user to checkpoint
+pub fn execute_apply_risk_premium(e: &Env, user: &Address) -> Positions {
+    let mut pool = Pool::load(e);
+    let mut user_state = User::load(e, user);
+
+    apply_risk_premium(e, &mut pool, &mut user_state);
+
+    pool.store_cached_reserves(e);
+    user_state.store(e);
+    user_state.positions
+}
+
+/// Charge the risk premium on a user's liabilities if they have stayed below the premium's
+/// health factor threshold since the last checkpoint, then mark or unmark the user based on their
+/// current health factor.
+///
+/// The premium is a multiple of the interest accrued on each liability since the user was marked.
+/// It is added to the user's liabilities and accrued to the reserve like regular interest.
+///
+/// Does nothing if the pool has no risk premium.
+///
+/// ### Arguments
+/// * `pool` - The pool
+/// * `user` - The user being checkpointed
+pub fn apply_risk_premium(e: &Env, pool: &mut Pool, user: &mut User) {
+    let risk_premium = match storage::get_risk_premium(e) {
+        Some(risk_premium) => risk_premium,
+        None => return,
+    };
+    let mark = storage::get_risk_mark(e, &user.address);
+    let is_under = user.has_liabilities()
+        && PositionData::calculate_from_positions(e, pool, &user.positions)
+            .is_hf_under(e, risk_premium.hf_threshold);
+    if !is_under {
+        if mark.is_some() {
+            storage::set_risk_mark(e, &user.address, &None);
+        }
+        return;
+    }
+
+    let reserve_list = storage::get_res_list(e);
+    let mut d_rates = map![e];
+    for (index, d_tokens) in user.positions.liabilities.iter() {
+        let mut reserve = pool.load_reserve(e, &reserve_list.get_unchecked(index), true);
+        let marked_d_rate = mark.as_ref().and_then(|mark| mark.get(index));
+        // skip liabilities that have not accrued interest since they were marked
+        if let Some(marked_d_rate) = marked_d_rate.filter(|d_rate| *d_rate < reserve.data.d_rate) {
+            let accrued =
+                d_tokens.fixed_mul_floor(e, &(reserve.data.d_rate - marked_d_rate), &SCALAR_12);
+            let premium = accrued.fixed_mul_floor(e, &i128(risk_premium.multiplier), &SCALAR_7);
+            let d_tokens_minted = reserve.to_d_token_down(e, premium);
+            if d_tokens_minted > 0 {
+                user.add_liabilities(e, &mut reserve, d_tokens_minted);
+                let tokens_charged = reserve.to_asset_from_d_token(e, d_tokens_minted);
+                reserve.accrue(e, pool.config.bstop_rate, tokens_charged);
+                PoolEvents::risk_premium(
+                    e,
+                    reserve.asset.clone(),
+                    user.address.clone(),
+                    tokens_charged,
+                    d_tokens_minted,
+                );
+            }
+        }
+        d_rates.set(index, reserve.data.d_rate);
+        pool.cache_reserve(reserve);
+    }
+    storage::set_risk_mark(e, &user.address, &Some(d_rates));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{storage::PoolConfig, testutils};
+
+    use super::*;
+    use sep_40_oracle::testutils::Asset;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger, LedgerInfo},
+        vec, Symbol,
+    };
+
+    /// Setup a pool with two reserves, a risk premium with a threshold of 1.5, and a user with a
+    /// health factor of ~1.27
+    ///
+    /// Returns (pool, samwise, underlying_1)
+    fn setup_risk_premium(e: &Env) -> (Address, Address, Address) {
+        e.mock_all_auths();
+        e.cost_estimate().budget().reset_unlimited();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(e);
+        let samwise = Address::generate(e);
+        let pool = testutils::create_pool(e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(e);
+
+        let (underlying_0, _) = testutils::create_token_contract(e, &bombadil);
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_config_0.c_factor = 0_8500000;
+        reserve_data_0.last_time = 12345;
+        testutils::create_reserve(e, &pool, &underlying_0, &reserve_config_0, &reserve_data_0);
+
+        let (underlying_1, _) = testutils::create_token_contract(e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_config_1.index = 1;
+        reserve_data_1.last_time = 12345;
+        testutils::create_reserve(e, &pool, &underlying_1, &reserve_config_1, &reserve_data_1);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(e, "USD")),
+            &vec![
+                e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![e, 1_0000000, 1_0000000]);
+
+        // collateral base: 25 * 0.85 = 21.25, liability base: 12.5 / 0.75 = 16.67
+        let positions = Positions {
+            collateral: map![e, (0, 25_0000000)],
+            liabilities: map![e, (1, 12_5000000)],
+            supply: map![e],
+        };
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(e, &pool_config);
+            storage::set_user_positions(e, &samwise, &positions);
+            execute_set_risk_premium(
+                e,
+                &Some(RiskPremium {
+                    hf_threshold: 1_5000000,
+                    multiplier: 0_5000000,
+                }),
+            );
+        });
+        (pool, samwise, underlying_1)
+    }
+
+    #[test]
+    fn test_apply_risk_premium() {
+        let e = Env::default();
+        let (pool_address, samwise, underlying_1) = setup_risk_premium(&e);
+
+        // first checkpoint only marks the user
+        let positions = e.as_contract(&pool_address, || execute_apply_risk_premium(&e, &samwise));
+        assert_eq!(positions.liabilities, map![&e, (1, 12_5000000)]);
+        let marked_d_rate = e.as_contract(&pool_address, || {
+            let mark = storage::get_risk_mark(&e, &samwise).unwrap();
+            mark.get_unchecked(1)
+        });
+
+        // user is still under the threshold at the next checkpoint
+        e.ledger().set_timestamp(12345 + 60 * 60 * 24 * 30);
+        e.as_contract(&pool_address, || {
+            let pre_reserve = Pool::load(&e).load_reserve(&e, &underlying_1, false);
+            let positions = execute_apply_risk_premium(&e, &samwise);
+
+            let reserve = Pool::load(&e).load_reserve(&e, &underlying_1, false);
+            let accrued = 12_5000000i128.fixed_mul_floor(
+                &e,
+                &(reserve.data.d_rate - marked_d_rate),
+                &SCALAR_12,
+            );
+            let premium = accrued.fixed_mul_floor(&e, &0_5000000, &SCALAR_7);
+            let d_tokens_minted = reserve.to_d_token_down(&e, premium);
+            assert!(d_tokens_minted > 0);
+            assert_eq!(
+                positions.liabilities,
+                map![&e, (1, 12_5000000 + d_tokens_minted)]
+            );
+            assert_eq!(
+                reserve.data.d_supply,
+                pre_reserve.data.d_supply + d_tokens_minted
+            );
+            assert!(reserve.data.b_rate > pre_reserve.data.b_rate);
+            assert!(reserve.data.backstop_credit > pre_reserve.data.backstop_credit);
+
+            let mark = storage::get_risk_mark(&e, &samwise).unwrap();
+            assert_eq!(mark.get_unchecked(1), reserve.data.d_rate);
+        });
+    }
+
+    #[test]
+    fn test_apply_risk_premium_healthy_user_unmarked() {
+        let e = Env::default();
+        let (pool_address, samwise, _) = setup_risk_premium(&e);
+
+        e.as_contract(&pool_address, || {
+            execute_apply_risk_premium(&e, &samwise);
+            assert!(storage::get_risk_mark(&e, &samwise).is_some());
+
+            let mut positions = storage::get_user_positions(&e, &samwise);
+            positions.liabilities.set(1, 5_0000000);
+            storage::set_user_positions(&e, &samwise, &positions);
+        });
+
+        e.ledger().set_timestamp(12345 + 60 * 60 * 24 * 30);
+        e.as_contract(&pool_address, || {
+            let positions = execute_apply_risk_premium(&e, &samwise);
+            assert_eq!(positions.liabilities, map![&e, (1, 5_0000000)]);
+            assert!(storage::get_risk_mark(&e, &samwise).is_none());
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_set_risk_premium_threshold_too_low() {
+        let e = Env::default();
+        let (pool_address, _, _) = setup_risk_premium(&e);
+
+        e.as_contract(&pool_address, || {
+            execute_set_risk_premium(
+                &e,
+                &Some(RiskPremium {
+                    hf_threshold: 1_0000000,
+                    multiplier: 0_5000000,
+                }),
+            );
+        });
+    }
+}
@@ -0,0 +1,509 @@
+use cast::i128;
+use soroban_fixed_point_math::SorobanFixedPoint;
+use soroban_sdk::{panic_with_error, Address, Env};
+
+use crate::{
+    constants::{SCALAR_12, SCALAR_18, SCALAR_7, SECONDS_PER_YEAR},
+    errors::{PoolError, PoolErrorExt},
+    events::PoolEvents,
+    storage::{self, StableLoan, StableRateConfig},
+};
+
+use super::{Pool, Positions, ReserveState, User};
+
+/// Set or remove the stable rate borrowing config of a reserve. Removing the config stops new stable
+/// rate borrows, but existing stable rate liabilities keep their rate until swapped to variable.
+///
+/// ### Arguments
+/// * `asset` - The underlying asset of the reserve
+/// * `config` - The stable rate borrowing config, or None to disable stable rate borrowing
+///
+/// ### Panics
+/// If the reserve does not exist or the config is invalid
+pub fn execute_set_stable_rate(e: &Env, asset: &Address, config: &Option<StableRateConfig>) {
+    if !storage::has_res(e, asset) {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    if let Some(config) = config {
+        if config.premium > SCALAR_7 as u32
+            || config.rebalance_util == 0
+            || config.rebalance_util > SCALAR_7 as u32
+        {
+            panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+        }
+    }
+    storage::set_stable_rate_config(e, asset, config);
+}
+
+/// Rebalance a user's stable rate to the reserve's current rates. This can be called by anyone
+/// once the reserve's utilization is at or above the config's rebalance utilization and the user's
+/// stable rate is below the reserve's current borrow rate.
+///
+/// Returns the user's positions
+///
+/// ### Arguments
+/// * `user` - The user whose stable rate is rebalanced
+/// * `asset` - The underlying asset of the reserve
+///
+/// ### Panics
+/// If stable rate borrowing is disabled for the reserve, the user has no stable rate liabilities
+/// for the reserve, or the rebalance conditions are not met
+pub fn execute_rebalance_stable_rate(e: &Env, user: &Address, asset: &Address) -> Positions {
+    let config = match storage::get_stable_rate_config(e, asset) {
+        Some(config) => config,
+        None => panic_with_error!(e, PoolErrorExt::StableRateDisabled),
+    };
+    let mut pool = Pool::load(e);
+    let mut user_state = User::load(e, user);
+    let mut reserve = pool.load_reserve(e, asset, true);
+    let mut loan = match storage::get_stable_loan(e, user, reserve.config.index) {
+        Some(loan) if user_state.get_stable_liabilities(reserve.config.index) > 0 => loan,
+        _ => panic_with_error!(e, PoolError::BadRequest),
+    };
+    settle(
+        e,
+        pool.config.bstop_rate,
+        &mut user_state,
+        &mut reserve,
+        &mut loan,
+    );
+
+    let borrow_rate = reserve.borrow_rate(e);
+    if reserve.utilization(e) < i128(config.rebalance_util) || i128(loan.rate) >= borrow_rate {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    loan.rate = to_rate(borrow_rate + i128(config.premium));
+    storage::set_stable_loan(e, user, reserve.config.index, &Some(loan.clone()));
+    PoolEvents::rebalance_stable_rate(e, asset.clone(), user.clone(), loan.rate);
+
+    pool.cache_reserve(reserve);
+    pool.store_cached_reserves(e);
+    user_state.store(e);
+    user_state.positions
+}
+
+/// Accrue each of a user's stable rate liabilities at their stable rate, instead of the variable
+/// rate they accrued at through the reserve's dRate since they were last settled.
+///
+/// ### Arguments
+/// * `pool` - The pool
+/// * `user` - The user whose stable rate liabilities are settled
+pub fn apply_stable_rates(e: &Env, pool: &mut Pool, user: &mut User) {
+    let stable_liabilities = match &user.stable_liabilities {
+        Some(stable_liabilities) if !stable_liabilities.is_empty() => stable_liabilities.clone(),
+        _ => return,
+    };
+    let reserve_list = storage::get_res_list(e);
+    for (index, _) in stable_liabilities.iter() {
+        let mut reserve = pool.load_reserve(e, &reserve_list.get_unchecked(index), true);
+        match storage::get_stable_loan(e, &user.address, index) {
+            Some(mut loan) => {
+                settle(e, pool.config.bstop_rate, user, &mut reserve, &mut loan);
+                storage::set_stable_loan(e, &user.address, index, &Some(loan));
+            }
+            // should never occur, but treat liabilities without a stable rate as variable
+            None => user.set_stable_liabilities(index, 0),
+        }
+        pool.cache_reserve(reserve);
+    }
+}
+
+/// Add newly borrowed liabilities to the user's stable rate liabilities for the reserve. The
+/// liabilities must already be added to the user's position. The new liabilities are priced at the
+/// reserve's current borrow rate plus the premium, and blended into any existing stable rate.
+///
+/// Returns the user's new stable rate
+///
+/// ### Arguments
+/// * `user` - The user borrowing at a stable rate
+/// * `reserve` - The reserve being borrowed from
+/// * `d_tokens_minted` - The amount of d_tokens borrowed at a stable rate
+///
+/// ### Panics
+/// If stable rate borrowing is disabled for the reserve
+pub fn add_stable_liabilities(
+    e: &Env,
+    user: &mut User,
+    reserve: &ReserveState,
+    d_tokens_minted: i128,
+) -> u32 {
+    let config = match storage::get_stable_rate_config(e, &reserve.asset) {
+        Some(config) => config,
+        None => panic_with_error!(e, PoolErrorExt::StableRateDisabled),
+    };
+    let index = reserve.config.index;
+    let new_rate = reserve.borrow_rate(e) + i128(config.premium);
+    let cur_stable = user.get_stable_liabilities(index);
+    // existing stable rate liabilities were settled to the current ledger at the start of the submit
+    let rate = match storage::get_stable_loan(e, &user.address, index) {
+        Some(loan) if cur_stable > 0 => (cur_stable * i128(loan.rate) + d_tokens_minted * new_rate)
+            .fixed_div_ceil(e, &(cur_stable + d_tokens_minted), &1),
+        _ => new_rate,
+    };
+    let loan = StableLoan {
+        rate: to_rate(rate),
+        d_rate: reserve.data.d_rate,
+        last_time: e.ledger().timestamp(),
+    };
+    user.set_stable_liabilities(index, cur_stable + d_tokens_minted);
+    storage::set_stable_loan(e, &user.address, index, &Some(loan.clone()));
+    loan.rate
+}
+
+/// Swap all of a user's stable rate liabilities for the reserve to the variable rate
+///
+/// Returns the amount of d_tokens swapped
+///
+/// ### Arguments
+/// * `user` - The user swapping to the variable rate
+/// * `reserve` - The reserve
+///
+/// ### Panics
+/// If the user has no stable rate liabilities for the reserve
+pub fn swap_to_variable(e: &Env, user: &mut User, reserve: &ReserveState) -> i128 {
+    let index = reserve.config.index;
+    let stable = user.get_stable_liabilities(index);
+    if stable == 0 {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    user.set_stable_liabilities(index, 0);
+    storage::set_stable_loan(e, &user.address, index, &None);
+    stable
+}
+
+/// Settle a user's stable rate liabilities for a reserve to the current ledger. Any interest owed
+/// above what accrued through the reserve's dRate is charged to the user and accrued to the reserve
+/// like regular interest. Any interest accrued through the dRate above what is owed is forgiven, and
+/// the backstop's share of it is taken back from the reserve's backstop credit.
+fn settle(
+    e: &Env,
+    bstop_rate: u32,
+    user: &mut User,
+    reserve: &mut ReserveState,
+    loan: &mut StableLoan,
+) {
+    let now = e.ledger().timestamp();
+    let index = reserve.config.index;
+    let stable = user.get_stable_liabilities(index);
+    if loan.last_time >= now || stable == 0 {
+        return;
+    }
+
+    let principal = stable.fixed_mul_ceil(e, &loan.d_rate, &SCALAR_12);
+    let interest = principal.fixed_mul_ceil(
+        e,
+        &(i128(loan.rate) * i128(now - loan.last_time)),
+        &(SCALAR_7 * SECONDS_PER_YEAR),
+    );
+    let owed = reserve.to_d_token_up(e, principal + interest);
+    if owed > stable {
+        let d_tokens_minted = owed - stable;
+        user.add_liabilities(e, reserve, d_tokens_minted);
+        user.set_stable_liabilities(index, owed);
+        let tokens_charged = reserve.to_asset_from_d_token(e, d_tokens_minted);
+        reserve.accrue(e, bstop_rate, tokens_charged);
+    } else if owed < stable {
+        // the backstop was credited its share of the forgiven interest when it accrued through the
+        // dRate, so take that share back and only charge the rest to suppliers
+        let d_tokens_forgiven = stable - owed;
+        let tokens_forgiven = reserve.to_asset_from_d_token(e, d_tokens_forgiven);
+        let backstop_share = tokens_forgiven
+            .fixed_mul_floor(e, &i128(bstop_rate), &SCALAR_7)
+            .min(reserve.data.backstop_credit);
+        reserve.data.backstop_credit -= backstop_share;
+        user.remove_liabilities(e, reserve, d_tokens_forgiven);
+        let b_rate_loss = (tokens_forgiven - backstop_share).fixed_div_ceil(
+            e,
+            &reserve.data.b_supply,
+            &SCALAR_18,
+        );
+        reserve.set_b_rate_18((reserve.b_rate_18() - b_rate_loss).max(0));
+        user.set_stable_liabilities(index, owed);
+    }
+    loan.d_rate = reserve.data.d_rate;
+    loan.last_time = now;
+}
+
+/// Convert a rate in 7 decimals to a stored stable rate
+fn to_rate(rate: i128) -> u32 {
+    rate.min(u32::MAX as i128) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{storage::PoolConfig, testutils};
+
+    use super::*;
+    use soroban_sdk::{
+        map,
+        testutils::{Address as _, Ledger, LedgerInfo},
+    };
+
+    /// Setup a pool with a reserve at 75% utilization with stable rate borrowing enabled, and a user
+    /// with liabilities in the reserve
+    ///
+    /// Returns (pool, samwise, underlying)
+    fn setup_stable_rate(e: &Env) -> (Address, Address, Address) {
+        e.mock_all_auths();
+        e.cost_estimate().budget().reset_unlimited();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(e);
+        let samwise = Address::generate(e);
+        let pool = testutils::create_pool(e);
+        let (oracle, _) = testutils::create_mock_oracle(e);
+
+        let (underlying, _) = testutils::create_token_contract(e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.last_time = 12345;
+        testutils::create_reserve(e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        let positions = Positions {
+            collateral: map![e],
+            liabilities: map![e, (0, 10_0000000)],
+            supply: map![e],
+        };
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(e, &pool_config);
+            storage::set_user_positions(e, &samwise, &positions);
+            execute_set_stable_rate(
+                e,
+                &underlying,
+                &Some(StableRateConfig {
+                    premium: 0_0200000,
+                    rebalance_util: 0_5000000,
+                }),
+            );
+        });
+        (pool, samwise, underlying)
+    }
+
+    #[test]
+    fn test_stable_rate_accrues_at_fixed_rate() {
+        let e = Env::default();
+        let (pool_address, samwise, underlying) = setup_stable_rate(&e);
+
+        e.as_contract(&pool_address, || {
+            let mut pool = Pool::load(&e);
+            let mut user = User::load(&e, &samwise);
+            let reserve = pool.load_reserve(&e, &underlying, true);
+            // borrow rate at 75% utilization is 6%, plus the 2% premium
+            let rate = add_stable_liabilities(&e, &mut user, &reserve, 10_0000000);
+            assert_eq!(rate, 0_0800000);
+            assert_eq!(user.get_stable_liabilities(0), 10_0000000);
+            pool.cache_reserve(reserve);
+            pool.store_cached_reserves(&e);
+            user.store(&e);
+        });
+
+        e.ledger().set_timestamp(12345 + 31536000);
+        e.as_contract(&pool_address, || {
+            let mut pool = Pool::load(&e);
+            let mut user = User::load(&e, &samwise);
+            apply_stable_rates(&e, &mut pool, &mut user);
+            let reserve = pool.load_reserve(&e, &underlying, false);
+
+            let stable = user.get_stable_liabilities(0);
+            assert_eq!(user.get_liabilities(0), stable);
+            let owed = reserve.to_asset_from_d_token(&e, stable);
+            assert!((owed - 10_8000000).abs() <= 10);
+
+            let loan = storage::get_stable_loan(&e, &samwise, 0).unwrap();
+            assert_eq!(loan.rate, 0_0800000);
+            assert_eq!(loan.d_rate, reserve.data.d_rate);
+            assert_eq!(loan.last_time, 12345 + 31536000);
+        });
+    }
+
+    #[test]
+    fn test_swap_to_variable() {
+        let e = Env::default();
+        let (pool_address, samwise, underlying) = setup_stable_rate(&e);
+
+        e.as_contract(&pool_address, || {
+            let mut pool = Pool::load(&e);
+            let mut user = User::load(&e, &samwise);
+            let reserve = pool.load_reserve(&e, &underlying, false);
+            add_stable_liabilities(&e, &mut user, &reserve, 4_0000000);
+
+            let swapped = swap_to_variable(&e, &mut user, &reserve);
+            assert_eq!(swapped, 4_0000000);
+            assert_eq!(user.get_stable_liabilities(0), 0);
+            assert_eq!(user.get_liabilities(0), 10_0000000);
+            assert!(storage::get_stable_loan(&e, &samwise, 0).is_none());
+        });
+    }
+
+    #[test]
+    fn test_stable_rate_forgiven_interest_reverses_backstop_share() {
+        let e = Env::default();
+        let (pool_address, samwise, underlying) = setup_stable_rate(&e);
+
+        e.as_contract(&pool_address, || {
+            storage::set_user_stable_liabilities(&e, &samwise, &map![&e, (0, 10_0000000)]);
+            storage::set_stable_loan(
+                &e,
+                &samwise,
+                0,
+                &Some(StableLoan {
+                    rate: 0,
+                    d_rate: SCALAR_12,
+                    last_time: 12345,
+                }),
+            );
+        });
+
+        e.ledger().set_timestamp(12345 + 31536000);
+        e.as_contract(&pool_address, || {
+            let pre_settle = Pool::load(&e).load_reserve(&e, &underlying, false);
+            assert!(pre_settle.data.backstop_credit > 0);
+
+            let mut pool = Pool::load(&e);
+            let mut user = User::load(&e, &samwise);
+            apply_stable_rates(&e, &mut pool, &mut user);
+            let reserve = pool.load_reserve(&e, &underlying, false);
+
+            // all of the interest accrued through the dRate is forgiven
+            let stable = user.get_stable_liabilities(0);
+            assert_eq!(user.get_liabilities(0), stable);
+            assert_eq!(stable, reserve.to_d_token_up(&e, 10_0000000));
+            let tokens_forgiven = reserve.to_asset_from_d_token(&e, 10_0000000 - stable);
+            let backstop_share = tokens_forgiven.fixed_mul_floor(&e, &0_1000000, &SCALAR_7);
+            assert!(backstop_share > 0);
+            assert_eq!(
+                reserve.data.backstop_credit,
+                pre_settle.data.backstop_credit - backstop_share
+            );
+            let b_rate_loss = (tokens_forgiven - backstop_share).fixed_div_ceil(
+                &e,
+                &reserve.data.b_supply,
+                &SCALAR_18,
+            );
+            assert_eq!(reserve.b_rate_18(), pre_settle.b_rate_18() - b_rate_loss);
+        });
+    }
+
+    #[test]
+    fn test_repaid_stable_liabilities_remove_stable_loan() {
+        let e = Env::default();
+        let (pool_address, samwise, underlying) = setup_stable_rate(&e);
+
+        e.as_contract(&pool_address, || {
+            let mut pool = Pool::load(&e);
+            let mut user = User::load(&e, &samwise);
+            let mut reserve = pool.load_reserve(&e, &underlying, true);
+            add_stable_liabilities(&e, &mut user, &reserve, 10_0000000);
+            user.store(&e);
+            assert!(storage::get_stable_loan(&e, &samwise, 0).is_some());
+
+            // repaying caps the stable rate liabilities at the remaining liabilities
+            user.remove_liabilities(&e, &mut reserve, 4_0000000);
+            user.store(&e);
+            assert_eq!(user.get_stable_liabilities(0), 6_0000000);
+            assert!(storage::get_stable_loan(&e, &samwise, 0).is_some());
+
+            user.remove_liabilities(&e, &mut reserve, 6_0000000);
+            user.store(&e);
+            assert_eq!(user.get_stable_liabilities(0), 0);
+            assert!(storage::get_stable_loan(&e, &samwise, 0).is_none());
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1245)")]
+    fn test_add_stable_liabilities_disabled() {
+        let e = Env::default();
+        let (pool_address, samwise, underlying) = setup_stable_rate(&e);
+
+        e.as_contract(&pool_address, || {
+            execute_set_stable_rate(&e, &underlying, &None);
+            let mut pool = Pool::load(&e);
+            let mut user = User::load(&e, &samwise);
+            let reserve = pool.load_reserve(&e, &underlying, false);
+            add_stable_liabilities(&e, &mut user, &reserve, 1_0000000);
+        });
+    }
+
+    #[test]
+    fn test_rebalance_stable_rate() {
+        let e = Env::default();
+        let (pool_address, samwise, underlying) = setup_stable_rate(&e);
+
+        e.as_contract(&pool_address, || {
+            storage::set_user_stable_liabilities(&e, &samwise, &map![&e, (0, 10_0000000)]);
+            storage::set_stable_loan(
+                &e,
+                &samwise,
+                0,
+                &Some(StableLoan {
+                    rate: 0_0100000,
+                    d_rate: SCALAR_12,
+                    last_time: 12345,
+                }),
+            );
+
+            execute_rebalance_stable_rate(&e, &samwise, &underlying);
+            let loan = storage::get_stable_loan(&e, &samwise, 0).unwrap();
+            assert_eq!(loan.rate, 0_0800000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_rebalance_stable_rate_above_borrow_rate() {
+        let e = Env::default();
+        let (pool_address, samwise, underlying) = setup_stable_rate(&e);
+
+        e.as_contract(&pool_address, || {
+            storage::set_user_stable_liabilities(&e, &samwise, &map![&e, (0, 10_0000000)]);
+            storage::set_stable_loan(
+                &e,
+                &samwise,
+                0,
+                &Some(StableLoan {
+                    rate: 0_0800000,
+                    d_rate: SCALAR_12,
+                    last_time: 12345,
+                }),
+            );
+
+            execute_rebalance_stable_rate(&e, &samwise, &underlying);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_set_stable_rate_invalid_rebalance_util() {
+        let e = Env::default();
+        let (pool_address, _, underlying) = setup_stable_rate(&e);
+
+        e.as_contract(&pool_address, || {
+            execute_set_stable_rate(
+                &e,
+                &underlying,
+                &Some(StableRateConfig {
+                    premium: 0_0200000,
+                    rebalance_util: 0,
+                }),
+            );
+        });
+    }
+}
@@ -1,10 +1,13 @@
 use crate::{
-    constants::SCALAR_7,
-    dependencies::{BackstopClient, PoolBackstopData},
-    storage, PoolError,
+    constants::{MAX_WITHDRAW_FEE, MIN_FREEZE_EXPIRY_LEDGERS, SCALAR_7},
+    dependencies::{Backstop, PoolBackstopData},
+    storage::{self, PoolConfig, WithdrawFee},
+    PoolError,
 };
 use soroban_sdk::{panic_with_error, Env};
 
+use super::ReserveState;
+
 /// Update the pool status based on the backstop module
 #[allow(clippy::zero_prefixed_literal)]
 #[allow(clippy::inconsistent_digit_grouping)]
@@ -12,10 +15,7 @@ pub fn execute_update_pool_status(e: &Env) -> u32 {
     let mut pool_config = storage::get_pool_config(e);
 
     // check the pool has met minimum backstop deposits
-    let backstop_id = storage::get_backstop(e);
-    let backstop_client = BackstopClient::new(e, &backstop_id);
-
-    let pool_backstop_data = backstop_client.pool_data(&e.current_contract_address());
+    let pool_backstop_data = Backstop::load(e).pool_data();
     let threshold = calc_pool_backstop_threshold(&pool_backstop_data);
     let mut met_threshold = true;
     if threshold < SCALAR_7 {
@@ -30,8 +30,15 @@ pub fn execute_update_pool_status(e: &Env) -> u32 {
         }
         // Admin frozen
         4 => {
-            // Admin frozen supersedes all other statuses
-            panic_with_error!(e, PoolError::StatusNotAllowed);
+            // Admin frozen supersedes all other statuses until the freeze expires, after which
+            // the status is derived from the backstop
+            match storage::get_freeze_expiry(e) {
+                Some(expiry) if e.ledger().sequence() >= expiry => {
+                    storage::set_freeze_expiry(e, &None);
+                    pool_config.status = calc_backstop_status(&pool_backstop_data, met_threshold);
+                }
+                _ => panic_with_error!(e, PoolError::StatusNotAllowed),
+            }
         }
         // Admin on-ice
         2 => {
@@ -49,22 +56,29 @@ pub fn execute_update_pool_status(e: &Env) -> u32 {
         }
         // Admin status isn't set
         _ => {
-            if pool_backstop_data.q4w_pct >= 0_6000000 {
-                // Q4w over 60% sets pool to Frozen
-                pool_config.status = 5;
-            } else if pool_backstop_data.q4w_pct >= 0_3000000 || !met_threshold {
-                // Q4w over 30% sets pool to On-Ice
-                pool_config.status = 3;
-            } else {
-                // Backstop is healthy and the pool is set to Active
-                pool_config.status = 1;
-            }
+            pool_config.status = calc_backstop_status(&pool_backstop_data, met_threshold);
         }
     }
+    accrue_on_frozen_change(e, pool_config.status);
     storage::set_pool_config(e, &pool_config);
     pool_config.status
 }
 
+/// Calculate the pool status derived from the backstop when no admin status is set
+#[allow(clippy::zero_prefixed_literal)]
+fn calc_backstop_status(pool_backstop_data: &PoolBackstopData, met_threshold: bool) -> u32 {
+    if pool_backstop_data.q4w_pct >= 0_6000000 {
+        // Q4w over 60% sets pool to Frozen
+        5
+    } else if pool_backstop_data.q4w_pct >= 0_3000000 || !met_threshold {
+        // Q4w over 30% sets pool to On-Ice
+        3
+    } else {
+        // Backstop is healthy and the pool is set to Active
+        1
+    }
+}
+
 /// Admin set the pool status
 #[allow(clippy::zero_prefixed_literal)]
 #[allow(clippy::inconsistent_digit_grouping)]
@@ -72,10 +86,7 @@ pub fn execute_set_pool_status(e: &Env, pool_status: u32) {
     let mut pool_config = storage::get_pool_config(e);
 
     // check the pool has met minimum backstop deposits
-    let backstop_id = storage::get_backstop(e);
-    let backstop_client = BackstopClient::new(e, &backstop_id);
-
-    let pool_backstop_data = backstop_client.pool_data(&e.current_contract_address());
+    let pool_backstop_data = Backstop::load(e).pool_data();
 
     match pool_status {
         0 => {
@@ -113,9 +124,109 @@ pub fn execute_set_pool_status(e: &Env, pool_status: u32) {
             panic_with_error!(e, PoolError::BadRequest);
         }
     }
+    let freeze_ledgers = storage::get_freeze_ledgers(e);
+    let freeze_expiry = if pool_status == 4 && freeze_ledgers > 0 {
+        Some(e.ledger().sequence() + freeze_ledgers)
+    } else {
+        None
+    };
+    storage::set_freeze_expiry(e, &freeze_expiry);
+    accrue_on_frozen_change(e, pool_config.status);
     storage::set_pool_config(e, &pool_config);
 }
 
+/// Set the number of ledgers after which an admin freeze expires, allowing `update_status` to
+/// derive the pool status from the backstop. Applies to freezes set after the update.
+///
+/// ### Arguments
+/// * `ledgers` - The number of ledgers, or 0 if freezes do not expire
+///
+/// ### Panics
+/// If the number of ledgers is not 0 and is less than the minimum freeze expiry
+pub fn execute_set_freeze_expiry(e: &Env, ledgers: u32) {
+    if ledgers != 0 && ledgers < MIN_FREEZE_EXPIRY_LEDGERS {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+    storage::set_freeze_ledgers(e, ledgers);
+}
+
+/// Set how interest is charged while the pool is frozen. If the pool is frozen, the reserves are
+/// accrued under the previous mode first.
+///
+/// ### Arguments
+/// * `mode` - 0 to accrue interest as normal, 1 to halt the backstop take rate, or 2 to halt
+///            borrower interest entirely
+///
+/// ### Panics
+/// If the mode is not 0, 1, or 2
+pub fn execute_set_frozen_fees(e: &Env, mode: u32) {
+    if mode > 2 {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+    let pool_config = storage::get_pool_config(e);
+    if is_frozen(pool_config.status) {
+        accrue_reserves(e, &pool_config);
+    }
+    storage::set_frozen_fees(e, mode);
+}
+
+/// Fetch the frozen fee mode that applies to the pool at its current status, or 0 if the pool is
+/// not frozen
+pub(super) fn get_frozen_fees(e: &Env, pool_config: &PoolConfig) -> u32 {
+    if is_frozen(pool_config.status) {
+        storage::get_frozen_fees(e)
+    } else {
+        0
+    }
+}
+
+/// Set the fee charged on collateral withdrawals while the pool is on-ice or frozen. The fee is
+/// credited to the backstop, and discourages a run on the pool while the backstop is stressed.
+///
+/// ### Arguments
+/// * `fee` - The withdrawal fee for each status
+///
+/// ### Panics
+/// If either fee is greater than the max withdrawal fee
+pub fn execute_set_withdraw_fee(e: &Env, fee: &WithdrawFee) {
+    if fee.on_ice > MAX_WITHDRAW_FEE || fee.frozen > MAX_WITHDRAW_FEE {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+    storage::set_withdraw_fee(e, fee);
+}
+
+/// Fetch the withdrawal fee that applies to the pool at its current status, or 0 if the pool is
+/// not on-ice or frozen
+pub(super) fn get_withdraw_fee_rate(e: &Env, pool_config: &PoolConfig) -> u32 {
+    match pool_config.status {
+        2 | 3 => storage::get_withdraw_fee(e).on_ice,
+        4 | 5 => storage::get_withdraw_fee(e).frozen,
+        _ => 0,
+    }
+}
+
+fn is_frozen(status: u32) -> bool {
+    status == 4 || status == 5
+}
+
+/// Accrue every reserve under the stored pool status if the pool is being frozen or unfrozen while a
+/// frozen fee mode is set, so halted fees apply to exactly the time the pool was frozen
+fn accrue_on_frozen_change(e: &Env, new_status: u32) {
+    if storage::get_frozen_fees(e) == 0 {
+        return;
+    }
+    let pool_config = storage::get_pool_config(e);
+    if is_frozen(pool_config.status) != is_frozen(new_status) {
+        accrue_reserves(e, &pool_config);
+    }
+}
+
+fn accrue_reserves(e: &Env, pool_config: &PoolConfig) {
+    for asset in storage::get_res_list(e).iter() {
+        ReserveState::load(e, pool_config, &asset).store(e);
+    }
+}
+
 /// Calculate the threshold for the pool's backstop balance
 ///
 /// Returns the threshold as a percentage^5 in SCALAR_7 points such that SCALAR_7 = 100%
@@ -151,11 +262,16 @@ pub fn calc_pool_backstop_threshold(pool_backstop_data: &PoolBackstopData) -> i1
 mod tests {
     use crate::{
         storage::PoolConfig,
-        testutils::{create_backstop, create_comet_lp_pool, create_pool, create_token_contract},
+        testutils::{
+            self, create_backstop, create_comet_lp_pool, create_pool, create_token_contract,
+        },
     };
 
     use super::*;
-    use soroban_sdk::{testutils::Address as _, vec, Address};
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger, LedgerInfo},
+        vec, Address,
+    };
 
     #[test]
     fn test_set_pool_status_active() {
@@ -191,6 +307,8 @@ mod tests {
             bstop_rate: 0,
             status: 1,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -238,6 +356,8 @@ mod tests {
             bstop_rate: 0,
             status: 1,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -283,6 +403,8 @@ mod tests {
             bstop_rate: 0,
             status: 2,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -325,6 +447,8 @@ mod tests {
             bstop_rate: 0,
             status: 1,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -373,6 +497,8 @@ mod tests {
             bstop_rate: 0,
             status: 5,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -417,6 +543,8 @@ mod tests {
             bstop_rate: 0,
             status: 6,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -459,6 +587,8 @@ mod tests {
             bstop_rate: 0,
             status: 1,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -505,6 +635,8 @@ mod tests {
             bstop_rate: 0,
             status: 2,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -548,6 +680,8 @@ mod tests {
             bstop_rate: 0,
             status: 3,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -595,6 +729,8 @@ mod tests {
             bstop_rate: 0,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -642,6 +778,8 @@ mod tests {
             bstop_rate: 0,
             status: 1,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -690,6 +828,8 @@ mod tests {
             bstop_rate: 0,
             status: 1,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -738,6 +878,8 @@ mod tests {
             bstop_rate: 0,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -786,6 +928,8 @@ mod tests {
             bstop_rate: 0,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -834,6 +978,8 @@ mod tests {
             bstop_rate: 0,
             status: 1,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -881,6 +1027,8 @@ mod tests {
             bstop_rate: 0,
             status: 2,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -929,6 +1077,8 @@ mod tests {
             bstop_rate: 0,
             status: 2,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -977,6 +1127,8 @@ mod tests {
             bstop_rate: 0,
             status: 4,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -986,6 +1138,292 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_update_pool_status_admin_frozen_expired() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 172800,
+            max_entry_ttl: 3110400,
+        });
+        let pool_id = create_pool(&e);
+        let oracle_id = Address::generate(&e);
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let (blnd, blnd_client) = create_token_contract(&e, &bombadil);
+        let (usdc, usdc_client) = create_token_contract(&e, &bombadil);
+        let (lp_token, lp_token_client) = create_comet_lp_pool(&e, &bombadil, &blnd, &usdc);
+        let (_, backstop_client) = create_backstop(&e, &pool_id, &lp_token, &usdc, &blnd);
+
+        // mint lp tokens
+        blnd_client.mint(&samwise, &500_001_0000000);
+        blnd_client.approve(&samwise, &lp_token, &i128::MAX, &99999);
+        usdc_client.mint(&samwise, &12_501_0000000);
+        usdc_client.approve(&samwise, &lp_token, &i128::MAX, &99999);
+        lp_token_client.join_pool(
+            &50_000_0000000,
+            &vec![&e, 500_001_0000000, 12_501_0000000],
+            &samwise,
+        );
+        backstop_client.deposit(&samwise, &pool_id, &50_000_0000000);
+
+        let pool_config = PoolConfig {
+            oracle: oracle_id,
+            min_collateral: 0,
+            bstop_rate: 0,
+            status: 1,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool_id, || {
+            storage::set_admin(&e, &bombadil);
+            storage::set_pool_config(&e, &pool_config);
+
+            execute_set_freeze_expiry(&e, 17280);
+            execute_set_pool_status(&e, 4);
+            assert_eq!(storage::get_freeze_expiry(&e), Some(100 + 17280));
+        });
+
+        e.ledger().set_sequence_number(100 + 17280);
+        e.as_contract(&pool_id, || {
+            let status = execute_update_pool_status(&e);
+            assert_eq!(status, 1);
+            assert_eq!(storage::get_pool_config(&e).status, 1);
+            assert_eq!(storage::get_freeze_expiry(&e), None);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1204)")]
+    fn test_update_pool_status_admin_frozen_not_expired() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 172800,
+            max_entry_ttl: 3110400,
+        });
+        let pool_id = create_pool(&e);
+        let oracle_id = Address::generate(&e);
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let (blnd, blnd_client) = create_token_contract(&e, &bombadil);
+        let (usdc, usdc_client) = create_token_contract(&e, &bombadil);
+        let (lp_token, lp_token_client) = create_comet_lp_pool(&e, &bombadil, &blnd, &usdc);
+        let (_, backstop_client) = create_backstop(&e, &pool_id, &lp_token, &usdc, &blnd);
+
+        // mint lp tokens
+        blnd_client.mint(&samwise, &500_001_0000000);
+        blnd_client.approve(&samwise, &lp_token, &i128::MAX, &99999);
+        usdc_client.mint(&samwise, &12_501_0000000);
+        usdc_client.approve(&samwise, &lp_token, &i128::MAX, &99999);
+        lp_token_client.join_pool(
+            &50_000_0000000,
+            &vec![&e, 500_001_0000000, 12_501_0000000],
+            &samwise,
+        );
+        backstop_client.deposit(&samwise, &pool_id, &50_000_0000000);
+
+        let pool_config = PoolConfig {
+            oracle: oracle_id,
+            min_collateral: 0,
+            bstop_rate: 0,
+            status: 1,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool_id, || {
+            storage::set_admin(&e, &bombadil);
+            storage::set_pool_config(&e, &pool_config);
+
+            execute_set_freeze_expiry(&e, 17280);
+            execute_set_pool_status(&e, 4);
+            assert_eq!(storage::get_freeze_expiry(&e), Some(100 + 17280));
+        });
+
+        e.ledger().set_sequence_number(100 + 17279);
+        e.as_contract(&pool_id, || {
+            let status = execute_update_pool_status(&e);
+            assert_eq!(status, 4);
+        });
+    }
+
+    #[test]
+    fn test_frozen_fees_halt_interest_while_frozen() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 172800,
+            max_entry_ttl: 3110400,
+        });
+        let pool_id = create_pool(&e);
+        let oracle_id = Address::generate(&e);
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let (blnd, blnd_client) = create_token_contract(&e, &bombadil);
+        let (usdc, usdc_client) = create_token_contract(&e, &bombadil);
+        let (lp_token, lp_token_client) = create_comet_lp_pool(&e, &bombadil, &blnd, &usdc);
+        let (_, backstop_client) = create_backstop(&e, &pool_id, &lp_token, &usdc, &blnd);
+
+        // mint lp tokens
+        blnd_client.mint(&samwise, &500_001_0000000);
+        blnd_client.approve(&samwise, &lp_token, &i128::MAX, &99999);
+        usdc_client.mint(&samwise, &12_501_0000000);
+        usdc_client.approve(&samwise, &lp_token, &i128::MAX, &99999);
+        lp_token_client.join_pool(
+            &50_000_0000000,
+            &vec![&e, 500_001_0000000, 12_501_0000000],
+            &samwise,
+        );
+        backstop_client.deposit(&samwise, &pool_id, &50_000_0000000);
+
+        let (underlying, _) = create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.last_time = 12345 - 1000;
+        testutils::create_reserve(&e, &pool_id, &underlying, &reserve_config, &reserve_data);
+
+        let pool_config = PoolConfig {
+            oracle: oracle_id,
+            min_collateral: 0,
+            bstop_rate: 0_2000000,
+            status: 1,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool_id, || {
+            storage::set_admin(&e, &bombadil);
+            storage::set_pool_config(&e, &pool_config);
+
+            execute_set_frozen_fees(&e, 2);
+            execute_set_freeze_expiry(&e, 17280);
+            execute_set_pool_status(&e, 4);
+
+            // interest accrued up to the freeze
+            let frozen_data = storage::get_res_data(&e, &underlying);
+            assert!(frozen_data.d_rate > reserve_data.d_rate);
+            assert!(frozen_data.backstop_credit > 0);
+            assert_eq!(frozen_data.last_time, 12345);
+        });
+
+        e.ledger().set_sequence_number(100 + 17280);
+        e.ledger().set_timestamp(12345 + 17280 * 5);
+        e.as_contract(&pool_id, || {
+            let frozen_data = storage::get_res_data(&e, &underlying);
+            let status = execute_update_pool_status(&e);
+            assert_eq!(status, 1);
+
+            // no interest accrued while frozen
+            let unfrozen_data = storage::get_res_data(&e, &underlying);
+            assert_eq!(unfrozen_data.d_rate, frozen_data.d_rate);
+            assert_eq!(unfrozen_data.b_rate, frozen_data.b_rate);
+            assert_eq!(unfrozen_data.backstop_credit, frozen_data.backstop_credit);
+            assert_eq!(unfrozen_data.last_time, 12345 + 17280 * 5);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_set_frozen_fees_invalid_mode() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool_id = create_pool(&e);
+
+        e.as_contract(&pool_id, || {
+            execute_set_frozen_fees(&e, 3);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_set_freeze_expiry_too_short() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool_id = create_pool(&e);
+
+        e.as_contract(&pool_id, || {
+            execute_set_freeze_expiry(&e, MIN_FREEZE_EXPIRY_LEDGERS - 1);
+        });
+    }
+
+    #[test]
+    fn test_execute_set_withdraw_fee() {
+        let e = Env::default();
+        let pool_id = create_pool(&e);
+        let mut pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 1,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+
+        e.as_contract(&pool_id, || {
+            execute_set_withdraw_fee(
+                &e,
+                &WithdrawFee {
+                    on_ice: 0_0050000,
+                    frozen: MAX_WITHDRAW_FEE,
+                },
+            );
+            assert_eq!(get_withdraw_fee_rate(&e, &pool_config), 0);
+            pool_config.status = 3;
+            assert_eq!(get_withdraw_fee_rate(&e, &pool_config), 0_0050000);
+            pool_config.status = 4;
+            assert_eq!(get_withdraw_fee_rate(&e, &pool_config), MAX_WITHDRAW_FEE);
+
+            execute_set_withdraw_fee(&e, &WithdrawFee::default());
+            assert_eq!(get_withdraw_fee_rate(&e, &pool_config), 0);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_set_withdraw_fee_over_max() {
+        let e = Env::default();
+        let pool_id = create_pool(&e);
+
+        e.as_contract(&pool_id, || {
+            execute_set_withdraw_fee(
+                &e,
+                &WithdrawFee {
+                    on_ice: MAX_WITHDRAW_FEE + 1,
+                    frozen: 0,
+                },
+            );
+        });
+    }
+
     #[test]
     #[should_panic(expected = "Error(Contract, #1204)")]
     fn test_update_pool_status_setup() {
@@ -1021,6 +1459,8 @@ mod tests {
             bstop_rate: 0,
             status: 6,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -1066,6 +1506,8 @@ mod tests {
             bstop_rate: 0,
             status: 5,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -1,18 +1,220 @@
+use cast::i128;
 use moderc3156::FlashLoanClient;
 use sep_41_token::TokenClient;
 use soroban_sdk::{panic_with_error, Address, Env, Map, Vec};
 
-use crate::{events::PoolEvents, storage, AuctionType, PoolError};
+use crate::{
+    constants::{MAX_BORROW_BUFFER, SCALAR_7},
+    emissions,
+    events::PoolEvents,
+    storage, AuctionType, PoolError, PoolErrorExt,
+};
 
 use super::{
     actions::{build_actions_from_request, Actions, Request},
+    analytics::record_activity,
+    bad_debt::is_max_positions_exempt,
+    checkpoint::record_checkpoint,
+    credit_line::{transfer_credit_line_draws, transfer_credit_line_repayments},
     health_factor::PositionData,
+    insurance::apply_insurance_premium,
     pool::Pool,
+    risk_premium::apply_risk_premium,
+    stable_rate::apply_stable_rates,
     FlashLoan, Positions, RequestType, User,
 };
 
+/// Check if a set of requests only supplies to a user who allows others to supply to their positions,
+/// such that the requests can be submitted without the user's authorization
+///
+/// ### Arguments
+/// * from - The address of the user whose positions are being modified
+/// * requests - A vec of requests to be processed
+pub fn is_supply_on_behalf(e: &Env, from: &Address, requests: &Vec<Request>) -> bool {
+    !requests.is_empty()
+        && requests.iter().all(|request| {
+            request.request_type == RequestType::Supply as u32
+                || request.request_type == RequestType::SupplyCollateral as u32
+        })
+        && storage::get_supply_on_behalf(e, from)
+}
+
+/// Require that neither the user whose positions are being modified nor the user sending tokens
+/// is blocklisted if the requests deposit into the pool. Withdrawals and repayments are always allowed.
+///
+/// ### Arguments
+/// * from - The address of the user whose positions are being modified
+/// * spender - The address of the user who is sending tokens to the pool
+/// * requests - A vec of requests to be processed
+///
+/// ### Panics
+/// If a request deposits into the pool and either address is blocked
+pub fn require_deposits_allowed(
+    e: &Env,
+    from: &Address,
+    spender: &Address,
+    requests: &Vec<Request>,
+) {
+    let is_deposit = requests.iter().any(|request| {
+        request.request_type == RequestType::Supply as u32
+            || request.request_type == RequestType::SupplyCollateral as u32
+    });
+    if is_deposit && (storage::get_blocked(e, from) || storage::get_blocked(e, spender)) {
+        panic_with_error!(e, PoolErrorExt::AddressBlocked);
+    }
+}
+
+/// Order a user's requests for execution, if the user opted in to request planning. Within each run of
+/// supply, withdraw, borrow and repay requests, every supply and repay is executed before any
+/// withdrawal or borrow, so the liquidity and positions they add are available to the requests that
+/// remove them. All other requests keep their place in the vector and end the run they follow.
+///
+/// Returns the requests in the order they are executed
+///
+/// ### Arguments
+/// * from - The address of the user whose positions are being modified
+/// * requests - A vec of requests to be processed
+pub fn plan_requests(e: &Env, from: &Address, requests: Vec<Request>) -> Vec<Request> {
+    if !storage::get_plan_requests(e, from) {
+        return requests;
+    }
+
+    let mut planned = Vec::new(e);
+    let mut outflows = Vec::new(e);
+    for request in requests.iter() {
+        let request_type = request.request_type;
+        if request_type == RequestType::Supply as u32
+            || request_type == RequestType::SupplyCollateral as u32
+            || request_type == RequestType::Repay as u32
+        {
+            planned.push_back(request);
+        } else if request_type == RequestType::Withdraw as u32
+            || request_type == RequestType::WithdrawCollateral as u32
+            || request_type == RequestType::Borrow as u32
+            || request_type == RequestType::BorrowStable as u32
+        {
+            outflows.push_back(request);
+        } else {
+            planned.append(&outflows);
+            outflows = Vec::new(e);
+            planned.push_back(request);
+        }
+    }
+    planned.append(&outflows);
+    planned
+}
+
+/// Fetch the request type a request offsets, if it can be netted against an earlier request. A
+/// withdrawal offsets a supply of the same kind, and a repayment offsets a borrow.
+fn offset_request_type(request_type: u32) -> Option<u32> {
+    if request_type == RequestType::Withdraw as u32 {
+        Some(RequestType::Supply as u32)
+    } else if request_type == RequestType::WithdrawCollateral as u32 {
+        Some(RequestType::SupplyCollateral as u32)
+    } else if request_type == RequestType::Repay as u32 {
+        Some(RequestType::Borrow as u32)
+    } else {
+        None
+    }
+}
+
+/// Net requests that offset an earlier request for the same asset into a single request, so each
+/// pair results in a single reserve update and token transfer, if the user opted in to request
+/// planning. A withdrawal is netted against an earlier supply of the same kind, and a repayment
+/// against an earlier borrow, as long as no request other than a supply, withdrawal, borrow or
+/// repayment is between them.
+///
+/// Requests are only netted if the same address sends and receives tokens, otherwise the spender
+/// would not be charged for the supply or repayment and the receiver would not be paid.
+///
+/// Netting only pairs a request with an earlier one, so any withdrawal or repayment cap is applied
+/// to the same balance it would be without netting.
+///
+/// Returns the netted requests
+///
+/// ### Arguments
+/// * from - The address of the user whose positions are being modified
+/// * spender - The address of the user who is sending tokens to the pool
+/// * to - The address of the user who is receiving tokens from the pool
+/// * requests - A vec of requests to be processed
+pub fn net_requests(
+    e: &Env,
+    from: &Address,
+    spender: &Address,
+    to: &Address,
+    requests: Vec<Request>,
+) -> Vec<Request> {
+    if spender != to || !storage::get_plan_requests(e, from) {
+        return requests;
+    }
+
+    let mut netted: Vec<Request> = Vec::new(e);
+    // the index of the first request that can be netted against
+    let mut run_start = 0;
+    for request in requests.iter() {
+        let request_type = request.request_type;
+        if request.amount > 0 {
+            if let Some(offset_type) = offset_request_type(request_type) {
+                let offset_index = (run_start..netted.len()).rev().find(|&index| {
+                    let offset = netted.get_unchecked(index);
+                    offset.request_type == offset_type
+                        && offset.address == request.address
+                        && offset.amount > 0
+                });
+                if let Some(index) = offset_index {
+                    let mut offset = netted.get_unchecked(index);
+                    if offset.amount > request.amount {
+                        offset.amount -= request.amount;
+                        netted.set(index, offset);
+                    } else {
+                        netted.remove(index);
+                        if request.amount > offset.amount {
+                            netted.push_back(Request {
+                                request_type,
+                                address: request.address,
+                                amount: request.amount - offset.amount,
+                            });
+                        }
+                    }
+                    continue;
+                }
+            }
+        }
+
+        let is_nettable = request_type == RequestType::Supply as u32
+            || request_type == RequestType::SupplyCollateral as u32
+            || request_type == RequestType::Borrow as u32
+            || request_type == RequestType::BorrowStable as u32
+            || offset_request_type(request_type).is_some();
+        netted.push_back(request);
+        if !is_nettable {
+            run_start = netted.len();
+        }
+    }
+    netted
+}
+
+/// Set the minimum health factor a user allows their own requests to leave them at. Requests that
+/// are checked for health fail if they would leave the user below it, even if the pool would allow them.
+///
+/// ### Arguments
+/// * `user` - The address of the user
+/// * `min_hf` - The minimum health factor in 7 decimals, or 0 to remove the limit
+///
+/// ### Panics
+/// If the minimum health factor is not 0 and is not greater than 1 and at most 100
+pub fn execute_set_user_min_hf(e: &Env, user: &Address, min_hf: u32) {
+    if min_hf != 0 && (min_hf <= SCALAR_7 as u32 || min_hf > 100_0000000) {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    storage::set_user_min_hf(e, user, min_hf);
+}
+
 /// Execute a set of updates for a user against the pool.
 ///
+/// Returns the new positions for `from`, or the error if the addresses or the user's resulting
+/// positions are invalid
+///
 /// ### Arguments
 /// * from - The address of the user whose positions are being modified
 /// * spender - The address of the user who is sending tokens to the pool
@@ -21,7 +223,7 @@ use super::{
 /// * use_allowance - A bool indicating if transfer_from is to be used
 ///
 /// ### Panics
-/// If the request is unable to be fully executed
+/// If a request is unable to be executed
 pub fn execute_submit(
     e: &Env,
     from: &Address,
@@ -29,18 +231,27 @@ pub fn execute_submit(
     to: &Address,
     requests: Vec<Request>,
     use_allowance: bool,
-) -> Positions {
+) -> Result<Positions, PoolError> {
     if from == &e.current_contract_address()
         || spender == &e.current_contract_address()
         || to == &e.current_contract_address()
     {
-        panic_with_error!(e, &PoolError::BadRequest);
+        return Err(PoolError::BadRequest);
     }
+    require_deposits_allowed(e, from, spender, &requests);
     let mut pool = Pool::load(e);
     let mut from_state = User::load(e, from);
+    let prev_positions = from_state.positions.clone();
+    apply_stable_rates(e, &mut pool, &mut from_state);
+    apply_risk_premium(e, &mut pool, &mut from_state);
+    apply_insurance_premium(e, &mut pool, &mut from_state);
 
     let prev_positions_count = from_state.positions.effective_count();
 
+    record_activity(e, from, &requests);
+    // net before planning, as planning moves repayments ahead of the borrows they offset
+    let requests = net_requests(e, from, spender, to, requests);
+    let requests = plan_requests(e, from, requests);
     let actions = build_actions_from_request(e, &mut pool, &mut from_state, requests);
 
     validate_submit(
@@ -49,8 +260,9 @@ pub fn execute_submit(
         &from_state,
         prev_positions_count,
         actions.check_health,
+        actions.check_min_debt,
         &actions.check_max_util,
-    );
+    )?;
 
     if use_allowance {
         handle_transfer_with_allowance(e, &actions, spender, to);
@@ -58,11 +270,15 @@ pub fn execute_submit(
         handle_transfers(e, &actions, spender, to);
     }
 
+    record_checkpoint(e, &mut pool, &from_state);
+
     // store updated info to ledger
     pool.store_cached_reserves(e);
     from_state.store(e);
 
-    from_state.positions
+    auto_claim_emissions(e, &prev_positions, &from_state);
+
+    Ok(from_state.positions)
 }
 
 /// Same as `execute_submit` but specifically made for performing a flash loan borrow before
@@ -72,12 +288,17 @@ pub fn execute_submit_with_flash_loan(
     from: &Address,
     flash_loan: FlashLoan,
     requests: Vec<Request>,
-) -> Positions {
+) -> Result<Positions, PoolError> {
     if from == &e.current_contract_address() {
-        panic_with_error!(e, &PoolError::BadRequest);
+        return Err(PoolError::BadRequest);
     }
+    require_deposits_allowed(e, from, from, &requests);
     let mut pool = Pool::load(e);
     let mut from_state = User::load(e, from);
+    let prev_positions = from_state.positions.clone();
+    apply_stable_rates(e, &mut pool, &mut from_state);
+    apply_risk_premium(e, &mut pool, &mut from_state);
+    apply_insurance_premium(e, &mut pool, &mut from_state);
 
     let prev_positions_count = from_state.positions.effective_count();
 
@@ -103,6 +324,16 @@ pub fn execute_submit_with_flash_loan(
         );
     }
 
+    let mut activity = requests.clone();
+    activity.push_front(Request {
+        request_type: RequestType::Borrow as u32,
+        address: flash_loan.asset.clone(),
+        amount: flash_loan.amount,
+    });
+    record_activity(e, from, &activity);
+    // net before planning, as planning moves repayments ahead of the borrows they offset
+    let requests = net_requests(e, from, from, from, requests);
+    let requests = plan_requests(e, from, requests);
     let mut actions = build_actions_from_request(e, &mut pool, &mut from_state, requests);
 
     // require flash loaned asset is added to check_max_util
@@ -117,8 +348,9 @@ pub fn execute_submit_with_flash_loan(
         &from_state,
         prev_positions_count,
         true,
+        true,
         &actions.check_max_util,
-    );
+    )?;
 
     // we deal with the flashloan transfer before the others to allow the flash
     // loan to yield the repaid or supplied amount in the transfers.
@@ -142,11 +374,64 @@ pub fn execute_submit_with_flash_loan(
 
     handle_transfer_with_allowance(e, &actions, from, from);
 
+    record_checkpoint(e, &mut pool, &from_state);
+
     // store updated info to ledger
     pool.store_cached_reserves(e);
     from_state.store(e);
 
-    from_state.positions
+    auto_claim_emissions(e, &prev_positions, &from_state);
+
+    Ok(from_state.positions)
+}
+
+/// Claim the emissions of every reserve token the user's balance changed for and send them to
+/// the user, if the user enabled auto-claiming
+///
+/// ### Arguments
+/// * prev_positions - The positions of the user before the submit
+/// * user - The user state after the submit
+fn auto_claim_emissions(e: &Env, prev_positions: &Positions, user: &User) {
+    if !storage::get_auto_claim(e, &user.address) {
+        return;
+    }
+
+    let positions = &user.positions;
+    let mut reserve_token_ids: Vec<u32> = Vec::new(e);
+    let reserve_count = storage::get_res_list(e).len();
+    for index in 0..reserve_count {
+        if prev_positions.liabilities.get(index) != positions.liabilities.get(index) {
+            reserve_token_ids.push_back(index * 2);
+        }
+        if prev_positions.collateral.get(index) != positions.collateral.get(index)
+            || prev_positions.supply.get(index) != positions.supply.get(index)
+        {
+            reserve_token_ids.push_back(index * 2 + 1);
+        }
+    }
+    if reserve_token_ids.is_empty() {
+        return;
+    }
+
+    let amount_claimed =
+        emissions::execute_claim(e, &user.address, &reserve_token_ids, &user.address);
+    if amount_claimed > 0 {
+        PoolEvents::claim(e, user.address.clone(), reserve_token_ids, amount_claimed);
+    }
+}
+
+/// Set the health factor buffer new borrows must leave above the liquidation threshold
+///
+/// ### Arguments
+/// * `buffer` - The buffer in 7 decimals, or 0 to disable
+///
+/// ### Panics
+/// If the buffer is greater than the max borrow buffer
+pub fn execute_set_borrow_buffer(e: &Env, buffer: u32) {
+    if buffer > MAX_BORROW_BUFFER {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+    storage::set_borrow_buffer(e, buffer);
 }
 
 /// Validate submit results in a valid state for the pool and user.
@@ -156,16 +441,21 @@ pub fn execute_submit_with_flash_loan(
 /// * from_state - The user state for "from"
 /// * prev_positions_count - The initial number of positions for "from"
 /// * check_health - A bool indicating if the health factor should be checked
-fn validate_submit(
+/// * check_min_debt - A bool indicating if the liabilities should be checked against the min debt,
+///                    set when the requests borrow. Borrows must also meet the borrow buffer.
+pub(super) fn validate_submit(
     e: &Env,
     pool: &mut Pool,
     from_state: &User,
     prev_positions_count: u32,
     check_health: bool,
+    check_min_debt: bool,
     check_max_util: &Vec<Address>,
-) {
+) -> Result<(), PoolError> {
     // Verify max positions haven't been exceeded
-    pool.require_under_max(e, &from_state.positions, prev_positions_count);
+    if !is_max_positions_exempt(e, &from_state.address) {
+        pool.require_under_max(e, &from_state.positions, prev_positions_count);
+    }
 
     // Verify "from" does not have an active liquidation post requests
     if storage::has_auction(
@@ -173,7 +463,7 @@ fn validate_submit(
         &(AuctionType::UserLiquidation as u32),
         &from_state.address,
     ) {
-        panic_with_error!(e, PoolError::AuctionInProgress);
+        return Err(PoolError::AuctionInProgress);
     }
 
     // Verify all requested reserve's end utilization is below the max utilization
@@ -184,15 +474,25 @@ fn validate_submit(
     }
 
     // panics if the new positions set does not meet the health factor requirement
-    // min is 1.0000100 to prevent rounding errors
+    // min is 1.0000100 to prevent rounding errors, raised by the borrow buffer for borrows
     if check_health && from_state.has_liabilities() {
         let position_data = PositionData::calculate_from_positions(e, pool, &from_state.positions);
-        if position_data.is_hf_under(e, 1_0000100) {
-            panic_with_error!(e, PoolError::InvalidHf);
+        let user_min_hf = storage::get_user_min_hf(e, &from_state.address);
+        let mut min_hf = 1_0000100;
+        if check_min_debt {
+            min_hf = min_hf.max(SCALAR_7 + i128(storage::get_borrow_buffer(e)));
+        }
+        if position_data.is_hf_under(e, min_hf) {
+            return Err(PoolError::InvalidHf);
+        } else if user_min_hf != 0 && position_data.is_hf_under(e, i128(user_min_hf)) {
+            return Err(PoolError::UserMinHfNotMet);
         } else if position_data.collateral_base < pool.config.min_collateral {
-            panic_with_error!(e, PoolError::MinCollateralNotMet);
+            return Err(PoolError::MinCollateralNotMet);
+        } else if check_min_debt && position_data.liability_raw < pool.config.min_debt {
+            return Err(PoolError::MinDebtNotMet);
         }
     }
+    Ok(())
 }
 
 fn handle_transfer_with_allowance(e: &Env, actions: &Actions, spender: &Address, to: &Address) {
@@ -202,6 +502,9 @@ fn handle_transfer_with_allowance(e: &Env, actions: &Actions, spender: &Address,
     // spender owes when amount < 0
     let mut net_balances: Map<Address, i128> = Map::new(e);
 
+    // draw credit lines before the pool transfers tokens out
+    transfer_credit_line_draws(e, actions);
+
     for (token, amount) in actions.spender_transfer.iter() {
         net_balances.set(
             token.clone(),
@@ -230,24 +533,49 @@ fn handle_transfer_with_allowance(e: &Env, actions: &Actions, spender: &Address,
             token.transfer(&e.current_contract_address(), to, &amount);
         }
     }
+
+    // repay credit lines once the tokens repaying them are in the pool
+    transfer_credit_line_repayments(e, actions);
 }
 
 fn handle_transfers(e: &Env, actions: &Actions, spender: &Address, to: &Address) {
+    // if the same address sends and receives tokens, only the net amount of each asset is
+    // transferred. Assets with credit line transfers are not netted, so repayments are held
+    // by the pool before they are settled.
+    let is_netted = |asset: &Address| {
+        spender == to && !actions.credit_line_transfer.contains_key(asset.clone())
+    };
+
     // transfer tokens from sender to pool
-    for (address, amount) in actions.spender_transfer.iter() {
-        TokenClient::new(e, &address).transfer(spender, &e.current_contract_address(), &amount);
+    for (address, mut amount) in actions.spender_transfer.iter() {
+        if is_netted(&address) {
+            amount -= actions.pool_transfer.get(address.clone()).unwrap_or(0);
+        }
+        if amount > 0 {
+            TokenClient::new(e, &address).transfer(spender, &e.current_contract_address(), &amount);
+        }
     }
 
+    // settle credit lines once the tokens repaying them are in the pool, and before the pool
+    // transfers tokens out
+    transfer_credit_line_draws(e, actions);
+    transfer_credit_line_repayments(e, actions);
+
     // transfer tokens from pool to "to"
-    for (address, amount) in actions.pool_transfer.iter() {
-        TokenClient::new(e, &address).transfer(&e.current_contract_address(), to, &amount);
+    for (address, mut amount) in actions.pool_transfer.iter() {
+        if is_netted(&address) {
+            amount -= actions.spender_transfer.get(address.clone()).unwrap_or(0);
+        }
+        if amount > 0 {
+            TokenClient::new(e, &address).transfer(&e.current_contract_address(), to, &amount);
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        storage::{self, PoolConfig},
+        storage::{self, PoolConfig, ReserveEmissionData, UserEmissionData},
         testutils, AuctionData, RequestType,
     };
 
@@ -260,7 +588,520 @@ mod tests {
     };
 
     #[test]
-    fn test_submit() {
+    fn test_is_supply_on_behalf() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let samwise = Address::generate(&e);
+        let asset = Address::generate(&e);
+
+        let supply_requests = vec![
+            &e,
+            Request {
+                request_type: RequestType::Supply as u32,
+                address: asset.clone(),
+                amount: 1_0000000,
+            },
+            Request {
+                request_type: RequestType::SupplyCollateral as u32,
+                address: asset.clone(),
+                amount: 1_0000000,
+            },
+        ];
+        let mut mixed_requests = supply_requests.clone();
+        mixed_requests.push_back(Request {
+            request_type: RequestType::Borrow as u32,
+            address: asset.clone(),
+            amount: 1,
+        });
+
+        e.as_contract(&pool, || {
+            // user has not opted in
+            assert!(!is_supply_on_behalf(&e, &samwise, &supply_requests));
+
+            storage::set_supply_on_behalf(&e, &samwise, true);
+            assert!(is_supply_on_behalf(&e, &samwise, &supply_requests));
+            assert!(!is_supply_on_behalf(&e, &samwise, &mixed_requests));
+            assert!(!is_supply_on_behalf(&e, &samwise, &vec![&e]));
+
+            storage::set_supply_on_behalf(&e, &samwise, false);
+            assert!(!is_supply_on_behalf(&e, &samwise, &supply_requests));
+        });
+    }
+
+    #[test]
+    fn test_plan_requests() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+        let samwise = Address::generate(&e);
+        let asset_0 = Address::generate(&e);
+        let asset_1 = Address::generate(&e);
+
+        let request = |request_type: RequestType, address: &Address, amount: i128| Request {
+            request_type: request_type as u32,
+            address: address.clone(),
+            amount,
+        };
+        let requests = vec![
+            &e,
+            request(RequestType::WithdrawCollateral, &asset_0, 1),
+            request(RequestType::Repay, &asset_1, 2),
+            request(RequestType::Borrow, &asset_1, 3),
+            request(RequestType::SupplyCollateral, &asset_0, 4),
+            request(RequestType::FillUserLiquidationAuction, &samwise, 5),
+            request(RequestType::Withdraw, &asset_0, 6),
+            request(RequestType::Supply, &asset_0, 7),
+        ];
+
+        e.as_contract(&pool, || {
+            // requests are executed in order unless the user opts in
+            let planned = plan_requests(&e, &samwise, requests.clone());
+            assert_eq!(
+                planned
+                    .iter()
+                    .map(|r| r.amount)
+                    .collect::<std::vec::Vec<_>>(),
+                [1, 2, 3, 4, 5, 6, 7]
+            );
+
+            storage::set_plan_requests(&e, &samwise, true);
+            let planned = plan_requests(&e, &samwise, requests.clone());
+            assert_eq!(
+                planned
+                    .iter()
+                    .map(|r| r.amount)
+                    .collect::<std::vec::Vec<_>>(),
+                [2, 4, 1, 3, 5, 7, 6]
+            );
+        });
+    }
+
+    #[test]
+    fn test_net_requests() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let asset_0 = Address::generate(&e);
+        let asset_1 = Address::generate(&e);
+
+        let request = |request_type: RequestType, address: &Address, amount: i128| Request {
+            request_type: request_type as u32,
+            address: address.clone(),
+            amount,
+        };
+        let requests = vec![
+            &e,
+            request(RequestType::Supply, &asset_0, 10),
+            request(RequestType::Borrow, &asset_1, 5),
+            request(RequestType::SupplyCollateral, &asset_0, 8),
+            request(RequestType::Withdraw, &asset_0, 4),
+            request(RequestType::Repay, &asset_1, 7),
+            request(RequestType::WithdrawCollateral, &asset_0, 8),
+            // a withdrawal before the supply it would offset is not netted
+            request(RequestType::Withdraw, &asset_1, 3),
+            request(RequestType::Supply, &asset_1, 3),
+            // other requests end the run that can be netted
+            request(RequestType::FillUserLiquidationAuction, &samwise, 50),
+            request(RequestType::Withdraw, &asset_1, 1),
+        ];
+
+        e.as_contract(&pool, || {
+            // requests are not netted unless the user opts in
+            let netted = net_requests(&e, &samwise, &samwise, &samwise, requests.clone());
+            assert!(netted == requests);
+
+            storage::set_plan_requests(&e, &samwise, true);
+
+            // requests are not netted if the spender does not receive the withdrawn tokens
+            let netted = net_requests(&e, &samwise, &samwise, &frodo, requests.clone());
+            assert!(netted == requests);
+
+            let netted = net_requests(&e, &samwise, &frodo, &frodo, requests.clone());
+            let expected = [
+                (RequestType::Supply, &asset_0, 6),
+                (RequestType::Repay, &asset_1, 2),
+                (RequestType::Withdraw, &asset_1, 3),
+                (RequestType::Supply, &asset_1, 3),
+                (RequestType::FillUserLiquidationAuction, &samwise, 50),
+                (RequestType::Withdraw, &asset_1, 1),
+            ];
+            assert_eq!(netted.len(), expected.len() as u32);
+            for (request, (request_type, address, amount)) in netted.iter().zip(expected) {
+                assert_eq!(request.request_type, request_type as u32);
+                assert_eq!(&request.address, address);
+                assert_eq!(request.amount, amount);
+            }
+        });
+    }
+
+    #[test]
+    fn test_require_deposits_allowed() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let asset = Address::generate(&e);
+
+        let exit_requests = vec![
+            &e,
+            Request {
+                request_type: RequestType::Repay as u32,
+                address: asset.clone(),
+                amount: 1_0000000,
+            },
+            Request {
+                request_type: RequestType::WithdrawCollateral as u32,
+                address: asset.clone(),
+                amount: 1_0000000,
+            },
+        ];
+
+        e.as_contract(&pool, || {
+            storage::set_blocked(&e, &samwise, true);
+
+            // blocked users can always exit
+            require_deposits_allowed(&e, &samwise, &samwise, &exit_requests);
+            require_deposits_allowed(&e, &frodo, &frodo, &exit_requests);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1246)")]
+    fn test_require_deposits_allowed_blocked_spender() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let asset = Address::generate(&e);
+
+        let requests = vec![
+            &e,
+            Request {
+                request_type: RequestType::Repay as u32,
+                address: asset.clone(),
+                amount: 1_0000000,
+            },
+            Request {
+                request_type: RequestType::SupplyCollateral as u32,
+                address: asset.clone(),
+                amount: 1_0000000,
+            },
+        ];
+
+        e.as_contract(&pool, || {
+            storage::set_blocked(&e, &frodo, true);
+            require_deposits_allowed(&e, &samwise, &frodo, &requests);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1246)")]
+    fn test_submit_supply_blocked() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let samwise = Address::generate(&e);
+        let asset = Address::generate(&e);
+
+        let requests = vec![
+            &e,
+            Request {
+                request_type: RequestType::Supply as u32,
+                address: asset.clone(),
+                amount: 1_0000000,
+            },
+        ];
+
+        e.as_contract(&pool, || {
+            storage::set_blocked(&e, &samwise, true);
+            execute_submit(&e, &samwise, &samwise, &samwise, requests, false).unwrap();
+        });
+    }
+
+    #[test]
+    fn test_submit() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let merry = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        underlying_0_client.mint(&frodo, &16_0000000);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            e.mock_all_auths_allowing_non_root_auth();
+            storage::set_pool_config(&e, &pool_config);
+
+            let pre_pool_balance_0 = underlying_0_client.balance(&pool);
+            let pre_pool_balance_1 = underlying_1_client.balance(&pool);
+
+            let pre_res_0_data = storage::get_res_data(&e, &underlying_0);
+            let pre_res_1_data = storage::get_res_data(&e, &underlying_1);
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::SupplyCollateral as u32,
+                    address: underlying_0.clone(),
+                    amount: 15_0000000,
+                },
+                Request {
+                    request_type: RequestType::Borrow as u32,
+                    address: underlying_1.clone(),
+                    amount: 1_5000000,
+                },
+            ];
+            let positions = execute_submit(&e, &samwise, &frodo, &merry, requests, false).unwrap();
+
+            assert_eq!(positions.liabilities.len(), 1);
+            assert_eq!(positions.collateral.len(), 1);
+            assert_eq!(positions.supply.len(), 0);
+            let b_tokens_minted = positions.collateral.get_unchecked(0);
+            assert_eq!(b_tokens_minted, 14_9999884);
+            let d_tokens_minted = positions.liabilities.get_unchecked(1);
+            assert_eq!(d_tokens_minted, 1_4999983);
+
+            let reserve_0 = storage::get_res_data(&e, &underlying_0);
+            assert_eq!(
+                reserve_0.b_supply,
+                pre_res_0_data.b_supply + b_tokens_minted
+            );
+
+            let reserve_1 = storage::get_res_data(&e, &underlying_1);
+            assert_eq!(
+                reserve_1.d_supply,
+                pre_res_1_data.d_supply + d_tokens_minted
+            );
+
+            assert_eq!(
+                underlying_0_client.balance(&pool),
+                pre_pool_balance_0 + 15_0000000
+            );
+            assert_eq!(
+                underlying_1_client.balance(&pool),
+                pre_pool_balance_1 - 1_5000000
+            );
+
+            assert_eq!(underlying_0_client.balance(&frodo), 1_0000000);
+            assert_eq!(underlying_1_client.balance(&merry), 1_5000000);
+        });
+    }
+
+    #[test]
+    fn test_submit_offsetting_requests_spender_not_to() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let merry = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, _) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        underlying_0_client.mint(&frodo, &16_0000000);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            e.mock_all_auths_allowing_non_root_auth();
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_plan_requests(&e, &samwise, true);
+
+            let pre_pool_balance_0 = underlying_0_client.balance(&pool);
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::Supply as u32,
+                    address: underlying_0.clone(),
+                    amount: 10_0000000,
+                },
+                Request {
+                    request_type: RequestType::Withdraw as u32,
+                    address: underlying_0.clone(),
+                    amount: 4_0000000,
+                },
+            ];
+            execute_submit(&e, &samwise, &frodo, &merry, requests, false).unwrap();
+
+            // the spender pays for the full supply and the receiver gets the full withdrawal
+            assert_eq!(underlying_0_client.balance(&frodo), 6_0000000);
+            assert_eq!(underlying_0_client.balance(&merry), 4_0000000);
+            assert_eq!(
+                underlying_0_client.balance(&pool),
+                pre_pool_balance_0 + 6_0000000
+            );
+        });
+    }
+
+    #[test]
+    fn test_submit_nets_borrow_and_repay() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        underlying_0_client.mint(&samwise, &16_0000000);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            e.mock_all_auths_allowing_non_root_auth();
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_plan_requests(&e, &samwise, true);
+
+            let pre_pool_balance_1 = underlying_1_client.balance(&pool);
+            let pre_res_1_data = storage::get_res_data(&e, &underlying_1);
+
+            // planning would move the repayment ahead of the borrow, so the pair must be netted first
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::SupplyCollateral as u32,
+                    address: underlying_0.clone(),
+                    amount: 15_0000000,
+                },
+                Request {
+                    request_type: RequestType::Borrow as u32,
+                    address: underlying_1.clone(),
+                    amount: 1_5000000,
+                },
+                Request {
+                    request_type: RequestType::Repay as u32,
+                    address: underlying_1.clone(),
+                    amount: 1_5000000,
+                },
+            ];
+            let positions =
+                execute_submit(&e, &samwise, &samwise, &samwise, requests, false).unwrap();
+
+            assert_eq!(positions.collateral.len(), 1);
+            assert_eq!(positions.liabilities.len(), 0);
+            assert_eq!(underlying_1_client.balance(&samwise), 0);
+            assert_eq!(underlying_1_client.balance(&pool), pre_pool_balance_1);
+            let reserve_1 = storage::get_res_data(&e, &underlying_1);
+            assert_eq!(reserve_1.d_supply, pre_res_1_data.d_supply);
+        });
+    }
+
+    #[test]
+    fn test_submit_records_checkpoint() {
         let e = Env::default();
         e.cost_estimate().budget().reset_unlimited();
         e.mock_all_auths_allowing_non_root_auth();
@@ -287,7 +1128,7 @@ mod tests {
         let (reserve_config, reserve_data) = testutils::default_reserve_meta();
         testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
 
-        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
         let (reserve_config, reserve_data) = testutils::default_reserve_meta();
         testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
 
@@ -312,16 +1153,14 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             e.mock_all_auths_allowing_non_root_auth();
             storage::set_pool_config(&e, &pool_config);
 
-            let pre_pool_balance_0 = underlying_0_client.balance(&pool);
-            let pre_pool_balance_1 = underlying_1_client.balance(&pool);
-
-            let pre_res_0_data = storage::get_res_data(&e, &underlying_0);
-            let pre_res_1_data = storage::get_res_data(&e, &underlying_1);
+            storage::set_checkpoint_retention(&e, 5);
 
             let requests = vec![
                 &e,
@@ -336,39 +1175,18 @@ mod tests {
                     amount: 1_5000000,
                 },
             ];
-            let positions = execute_submit(&e, &samwise, &frodo, &merry, requests, false);
-
-            assert_eq!(positions.liabilities.len(), 1);
-            assert_eq!(positions.collateral.len(), 1);
-            assert_eq!(positions.supply.len(), 0);
-            let b_tokens_minted = positions.collateral.get_unchecked(0);
-            assert_eq!(b_tokens_minted, 14_9999884);
-            let d_tokens_minted = positions.liabilities.get_unchecked(1);
-            assert_eq!(d_tokens_minted, 1_4999983);
-
+            let positions = execute_submit(&e, &samwise, &frodo, &merry, requests, false).unwrap();
+
+            let history = storage::get_position_history(&e, &samwise);
+            assert_eq!(history.len(), 1);
+            let checkpoint = history.get_unchecked(0);
+            assert_eq!(checkpoint.ledger, 1234);
+            assert_eq!(checkpoint.positions.collateral, positions.collateral);
+            assert_eq!(checkpoint.positions.liabilities, positions.liabilities);
             let reserve_0 = storage::get_res_data(&e, &underlying_0);
-            assert_eq!(
-                reserve_0.b_supply,
-                pre_res_0_data.b_supply + b_tokens_minted
-            );
-
             let reserve_1 = storage::get_res_data(&e, &underlying_1);
-            assert_eq!(
-                reserve_1.d_supply,
-                pre_res_1_data.d_supply + d_tokens_minted
-            );
-
-            assert_eq!(
-                underlying_0_client.balance(&pool),
-                pre_pool_balance_0 + 15_0000000
-            );
-            assert_eq!(
-                underlying_1_client.balance(&pool),
-                pre_pool_balance_1 - 1_5000000
-            );
-
-            assert_eq!(underlying_0_client.balance(&frodo), 1_0000000);
-            assert_eq!(underlying_1_client.balance(&merry), 1_5000000);
+            assert_eq!(checkpoint.b_rates, map![&e, (0, reserve_0.b_rate)]);
+            assert_eq!(checkpoint.d_rates, map![&e, (1, reserve_1.d_rate)]);
         });
     }
 
@@ -425,6 +1243,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             e.mock_all_auths_allowing_non_root_auth();
@@ -452,7 +1272,7 @@ mod tests {
             underlying_0_client.approve(&frodo, &pool, &15_0000000, &e.ledger().sequence());
             assert_eq!(underlying_0_client.allowance(&frodo, &pool), 15_0000000);
 
-            let positions = execute_submit(&e, &samwise, &frodo, &merry, requests, true);
+            let positions = execute_submit(&e, &samwise, &frodo, &merry, requests, true).unwrap();
 
             assert_eq!(positions.liabilities.len(), 1);
             assert_eq!(positions.collateral.len(), 1);
@@ -511,7 +1331,7 @@ mod tests {
             ];
             underlying_0_client.approve(&frodo, &pool, &14_0000000, &e.ledger().sequence());
             assert_eq!(underlying_0_client.allowance(&frodo, &pool), 14_0000000);
-            let positions = execute_submit(&e, &samwise, &frodo, &merry, requests, true);
+            let positions = execute_submit(&e, &samwise, &frodo, &merry, requests, true).unwrap();
 
             // new_allowance = old_allowance - (deposit - borrow)
             assert_eq!(underlying_0_client.allowance(&frodo, &pool), 0);
@@ -585,6 +1405,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             e.mock_all_auths_allowing_non_root_auth();
@@ -606,7 +1428,7 @@ mod tests {
             underlying_0_client.approve(&frodo, &pool, &15_0000000, &e.ledger().sequence());
             assert_eq!(underlying_0_client.allowance(&frodo, &pool), 15_0000000);
 
-            let positions = execute_submit(&e, &samwise, &frodo, &merry, requests, true);
+            let positions = execute_submit(&e, &samwise, &frodo, &merry, requests, true).unwrap();
 
             assert_eq!(positions.liabilities.len(), 1);
             assert_eq!(positions.collateral.len(), 1);
@@ -628,7 +1450,7 @@ mod tests {
             ];
             underlying_1_client.approve(&frodo, &pool, &1_5000001, &e.ledger().sequence());
             assert_eq!(underlying_1_client.allowance(&frodo, &pool), 1_5000001);
-            let positions = execute_submit(&e, &samwise, &frodo, &merry, requests, true);
+            let positions = execute_submit(&e, &samwise, &frodo, &merry, requests, true).unwrap();
 
             // new_allowance = old_allowance - repay
             assert_eq!(underlying_1_client.allowance(&frodo, &pool), 0);
@@ -702,6 +1524,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
 
         e.as_contract(&pool, || {
@@ -721,7 +1545,7 @@ mod tests {
                 },
             ];
 
-            execute_submit(&e, &samwise, &frodo, &merry, requests, true);
+            execute_submit(&e, &samwise, &frodo, &merry, requests, true).unwrap();
         });
     }
     #[test]
@@ -745,33 +1569,324 @@ mod tests {
         let samwise = Address::generate(&e);
         let frodo = Address::generate(&e);
         let pool = testutils::create_pool(&e);
-        let oracle = Address::generate(&e); // will fail if executed against
+        let oracle = Address::generate(&e); // will fail if executed against
+
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        underlying_0_client.mint(&frodo, &16_0000000);
+        underlying_1_client.mint(&frodo, &10_0000000);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            e.mock_all_auths_allowing_non_root_auth();
+            storage::set_pool_config(&e, &pool_config);
+
+            let pre_pool_balance_0 = underlying_0_client.balance(&pool);
+            let pre_pool_balance_1 = underlying_1_client.balance(&pool);
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::SupplyCollateral as u32,
+                    address: underlying_0,
+                    amount: 15_0000000,
+                },
+                // force check_health to true
+                Request {
+                    request_type: RequestType::Borrow as u32,
+                    address: underlying_1.clone(),
+                    amount: 1_5000000,
+                },
+                Request {
+                    request_type: RequestType::Repay as u32,
+                    address: underlying_1,
+                    amount: 1_5000001,
+                },
+            ];
+            let positions = execute_submit(&e, &samwise, &frodo, &frodo, requests, false).unwrap();
+
+            assert_eq!(positions.liabilities.len(), 0);
+            assert_eq!(positions.collateral.len(), 1);
+            assert_eq!(positions.supply.len(), 0);
+            assert_eq!(positions.collateral.get_unchecked(0), 14_9999884);
+
+            assert_eq!(
+                underlying_0_client.balance(&pool),
+                pre_pool_balance_0 + 15_0000000
+            );
+            assert_eq!(
+                underlying_1_client.balance(&pool),
+                pre_pool_balance_1 + 1 // repayment rounded against user
+            );
+
+            assert_eq!(underlying_0_client.balance(&frodo), 1_0000000);
+            assert_eq!(underlying_1_client.balance(&frodo), 10_0000000 - 1);
+        });
+    }
+
+    #[test]
+    fn test_submit_auto_claims_touched_emissions() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        let (blnd, blnd_client) = testutils::create_blnd_token(&e, &pool, &bombadil);
+        let (backstop, _) = testutils::create_backstop(
+            &e,
+            &pool,
+            &Address::generate(&e),
+            &Address::generate(&e),
+            &blnd,
+        );
+        // mock backstop having emissions for pool
+        e.as_contract(&backstop, || {
+            blnd_client.approve(&backstop, &pool, &100_000_0000000_i128, &1000000);
+        });
+        blnd_client.mint(&backstop, &100_000_0000000);
+
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.last_time = 600;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_config.index = 1;
+        reserve_data.last_time = 600;
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        underlying_0_client.mint(&samwise, &10_0000000);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            e.mock_all_auths_allowing_non_root_auth();
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_backstop(&e, &backstop);
+            storage::set_user_positions(
+                &e,
+                &samwise,
+                &Positions {
+                    liabilities: map![&e],
+                    collateral: map![&e, (1, 5_0000000)],
+                    supply: map![&e],
+                },
+            );
+            for res_token_id in [1, 3] {
+                storage::set_res_emis_data(
+                    &e,
+                    &res_token_id,
+                    &ReserveEmissionData {
+                        expiration: 10000,
+                        eps: 0_01000000000000,
+                        index: 1_0000000,
+                        last_time: 600,
+                    },
+                );
+                storage::set_user_emissions(
+                    &e,
+                    &samwise,
+                    &res_token_id,
+                    &UserEmissionData {
+                        index: 1_0000000,
+                        accrued: 1_0000000,
+                    },
+                );
+            }
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::SupplyCollateral as u32,
+                    address: underlying_0.clone(),
+                    amount: 1_0000000,
+                },
+            ];
+
+            // emissions are not claimed unless the user opts in
+            execute_submit(&e, &samwise, &samwise, &samwise, requests.clone(), false).unwrap();
+            assert_eq!(blnd_client.balance(&samwise), 0);
+
+            storage::set_auto_claim(&e, &samwise, true);
+            execute_submit(&e, &samwise, &samwise, &samwise, requests, false).unwrap();
+
+            // only the reserve token touched by the submit is claimed
+            assert_eq!(blnd_client.balance(&samwise), 1_0000000);
+            let user_emis_0 = storage::get_user_emissions(&e, &samwise, &1).unwrap();
+            assert_eq!(user_emis_0.accrued, 0);
+            let user_emis_1 = storage::get_user_emissions(&e, &samwise, &3).unwrap();
+            assert_eq!(user_emis_1.accrued, 1_0000000);
+        });
+    }
+
+    #[test]
+    fn test_submit_requires_healhty() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let merry = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        underlying_0_client.mint(&frodo, &16_0000000);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::SupplyCollateral as u32,
+                    address: underlying_0,
+                    amount: 15_0000000,
+                },
+                Request {
+                    request_type: RequestType::Borrow as u32,
+                    address: underlying_1,
+                    amount: 1_7500000,
+                },
+            ];
+            let result = execute_submit(&e, &samwise, &frodo, &merry, requests, false);
+            assert_eq!(result.err(), Some(PoolError::InvalidHf));
+        });
+    }
+
+    #[test]
+    fn test_submit_requires_user_min_hf() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let merry = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
 
         let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
         let (reserve_config, reserve_data) = testutils::default_reserve_meta();
         testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
 
-        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
         let (reserve_config, reserve_data) = testutils::default_reserve_meta();
         testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
 
         underlying_0_client.mint(&frodo, &16_0000000);
-        underlying_1_client.mint(&frodo, &10_0000000);
 
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
         let pool_config = PoolConfig {
             oracle,
             min_collateral: 1_0000000,
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
-            e.mock_all_auths_allowing_non_root_auth();
             storage::set_pool_config(&e, &pool_config);
 
-            let pre_pool_balance_0 = underlying_0_client.balance(&pool);
-            let pre_pool_balance_1 = underlying_1_client.balance(&pool);
-
             let requests = vec![
                 &e,
                 Request {
@@ -779,44 +1894,49 @@ mod tests {
                     address: underlying_0,
                     amount: 15_0000000,
                 },
-                // force check_health to true
                 Request {
                     request_type: RequestType::Borrow as u32,
                     address: underlying_1.clone(),
-                    amount: 1_5000000,
+                    amount: 1_0000000,
                 },
+            ];
+            // health factor of ~1.69
+            execute_set_user_min_hf(&e, &samwise, 1_5000000);
+            execute_submit(&e, &samwise, &frodo, &merry, requests, false).unwrap();
+        });
+
+        e.as_contract(&pool, || {
+            execute_set_user_min_hf(&e, &samwise, 2_0000000);
+            let requests = vec![
+                &e,
                 Request {
-                    request_type: RequestType::Repay as u32,
+                    request_type: RequestType::Borrow as u32,
                     address: underlying_1,
-                    amount: 1_5000001,
+                    amount: 0_0100000,
                 },
             ];
-            let positions = execute_submit(&e, &samwise, &frodo, &frodo, requests, false);
-
-            assert_eq!(positions.liabilities.len(), 0);
-            assert_eq!(positions.collateral.len(), 1);
-            assert_eq!(positions.supply.len(), 0);
-            assert_eq!(positions.collateral.get_unchecked(0), 14_9999884);
+            let result = execute_submit(&e, &samwise, &frodo, &merry, requests, false);
+            assert_eq!(result.err(), Some(PoolError::UserMinHfNotMet));
+        });
+    }
 
-            assert_eq!(
-                underlying_0_client.balance(&pool),
-                pre_pool_balance_0 + 15_0000000
-            );
-            assert_eq!(
-                underlying_1_client.balance(&pool),
-                pre_pool_balance_1 + 1 // repayment rounded against user
-            );
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_set_user_min_hf_under_one() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let samwise = Address::generate(&e);
 
-            assert_eq!(underlying_0_client.balance(&frodo), 1_0000000);
-            assert_eq!(underlying_1_client.balance(&frodo), 10_0000000 - 1);
+        e.as_contract(&pool, || {
+            execute_set_user_min_hf(&e, &samwise, 1_0000000);
         });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1205)")]
-    fn test_submit_requires_healhty() {
+    fn test_submit_borrow_requires_borrow_buffer() {
         let e = Env::default();
-        e.mock_all_auths();
+        e.mock_all_auths_allowing_non_root_auth();
 
         let bombadil = Address::generate(&e);
         let samwise = Address::generate(&e);
@@ -864,6 +1984,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -872,21 +1994,57 @@ mod tests {
                 &e,
                 Request {
                     request_type: RequestType::SupplyCollateral as u32,
-                    address: underlying_0,
+                    address: underlying_0.clone(),
                     amount: 15_0000000,
                 },
+                Request {
+                    request_type: RequestType::Borrow as u32,
+                    address: underlying_1.clone(),
+                    amount: 1_0000000,
+                },
+            ];
+            // health factor of ~1.69 meets a buffer of 0.5
+            execute_set_borrow_buffer(&e, 0_5000000);
+            execute_submit(&e, &samwise, &frodo, &merry, requests, false).unwrap();
+
+            // withdrawals are only held to the liquidation threshold, health factor of ~1.41
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::WithdrawCollateral as u32,
+                    address: underlying_0.clone(),
+                    amount: 2_5000000,
+                },
+            ];
+            execute_submit(&e, &samwise, &merry, &merry, requests, false).unwrap();
+
+            // borrows are held to the buffer
+            let requests = vec![
+                &e,
                 Request {
                     request_type: RequestType::Borrow as u32,
                     address: underlying_1,
-                    amount: 1_7500000,
+                    amount: 1,
                 },
             ];
-            execute_submit(&e, &samwise, &frodo, &merry, requests, false);
+            let result = execute_submit(&e, &samwise, &frodo, &merry, requests, false);
+            assert_eq!(result.err(), Some(PoolError::InvalidHf));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_set_borrow_buffer_too_large() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_borrow_buffer(&e, MAX_BORROW_BUFFER + 1);
         });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1200)")]
     fn test_submit_from_is_not_self() {
         let e = Env::default();
         e.cost_estimate().budget().reset_unlimited();
@@ -929,6 +2087,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             e.mock_all_auths_allowing_non_root_auth();
@@ -942,12 +2102,12 @@ mod tests {
                     amount: 15_0000000,
                 },
             ];
-            execute_submit(&e, &pool, &samwise, &samwise, requests, false);
+            let result = execute_submit(&e, &pool, &samwise, &samwise, requests, false);
+            assert_eq!(result.err(), Some(PoolError::BadRequest));
         });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1200)")]
     fn test_submit_spender_is_not_self() {
         let e = Env::default();
         e.cost_estimate().budget().reset_unlimited();
@@ -990,6 +2150,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             e.mock_all_auths_allowing_non_root_auth();
@@ -1003,12 +2165,12 @@ mod tests {
                     amount: 15_0000000,
                 },
             ];
-            execute_submit(&e, &samwise, &pool, &samwise, requests, false);
+            let result = execute_submit(&e, &samwise, &pool, &samwise, requests, false);
+            assert_eq!(result.err(), Some(PoolError::BadRequest));
         });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1200)")]
     fn test_submit_to_is_not_self() {
         let e = Env::default();
         e.cost_estimate().budget().reset_unlimited();
@@ -1051,6 +2213,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             e.mock_all_auths_allowing_non_root_auth();
@@ -1064,7 +2228,8 @@ mod tests {
                     amount: 15_0000000,
                 },
             ];
-            execute_submit(&e, &samwise, &samwise, &pool, requests, false);
+            let result = execute_submit(&e, &samwise, &samwise, &pool, requests, false);
+            assert_eq!(result.err(), Some(PoolError::BadRequest));
         });
     }
 
@@ -1121,6 +2286,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 3,
+            min_debt: 0,
+            base: None,
         };
         let user_positions = Positions {
             liabilities: map![&e, (0, 1_0000000)],
@@ -1145,7 +2312,7 @@ mod tests {
                     amount: 1_0000000,
                 },
             ];
-            execute_submit(&e, &samwise, &samwise, &samwise, requests, false);
+            execute_submit(&e, &samwise, &samwise, &samwise, requests, false).unwrap();
         });
     }
 
@@ -1201,6 +2368,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         let user_positions = Positions {
             liabilities: map![&e, (0, 1_0000000), (1, 1_0000000)],
@@ -1228,7 +2397,7 @@ mod tests {
                     amount: 2_0000000,
                 },
             ];
-            let result = execute_submit(&e, &samwise, &samwise, &samwise, requests, false);
+            let result = execute_submit(&e, &samwise, &samwise, &samwise, requests, false).unwrap();
 
             assert_eq!(result.liabilities.len(), 1);
             assert_eq!(result.collateral.len(), 2);
@@ -1251,7 +2420,6 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1212)")]
     fn test_submit_with_ongoing_liquidation_blocked() {
         let e = Env::default();
         e.cost_estimate().budget().reset_unlimited();
@@ -1308,6 +2476,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         let user_positions = Positions {
             liabilities: map![&e, (0, 5_0000000)],
@@ -1333,7 +2503,8 @@ mod tests {
                     amount: 4_0000000,
                 },
             ];
-            execute_submit(&e, &samwise, &samwise, &samwise, requests, false);
+            let result = execute_submit(&e, &samwise, &samwise, &samwise, requests, false);
+            assert_eq!(result.err(), Some(PoolError::AuctionInProgress));
         });
     }
 
@@ -1394,6 +2565,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         let user_positions = Positions {
             liabilities: map![&e, (0, 5_0000000)],
@@ -1426,7 +2599,7 @@ mod tests {
                     amount: 0,
                 },
             ];
-            let result = execute_submit(&e, &samwise, &samwise, &samwise, requests, false);
+            let result = execute_submit(&e, &samwise, &samwise, &samwise, requests, false).unwrap();
 
             assert_eq!(result.liabilities.len(), 1);
             assert_eq!(result.collateral.len(), 1);
@@ -1452,7 +2625,6 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1224)")]
     fn test_submit_under_min_collateral_fails() {
         let e = Env::default();
         e.cost_estimate().budget().reset_unlimited();
@@ -1505,6 +2677,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             e.mock_all_auths_allowing_non_root_auth();
@@ -1523,7 +2697,170 @@ mod tests {
                     amount: 0_01000000,
                 },
             ];
-            execute_submit(&e, &samwise, &frodo, &merry, requests, false);
+            let result = execute_submit(&e, &samwise, &frodo, &merry, requests, false);
+            assert_eq!(result.err(), Some(PoolError::MinCollateralNotMet));
+        });
+    }
+
+    #[test]
+    fn test_submit_under_min_debt_fails() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let merry = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        underlying_0_client.mint(&frodo, &16_0000000);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+            min_debt: 6_0000000,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            e.mock_all_auths_allowing_non_root_auth();
+            storage::set_pool_config(&e, &pool_config);
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::SupplyCollateral as u32,
+                    address: underlying_0,
+                    amount: 15_0000000,
+                },
+                Request {
+                    request_type: RequestType::Borrow as u32,
+                    address: underlying_1,
+                    amount: 1_0000000,
+                },
+            ];
+            let result = execute_submit(&e, &samwise, &frodo, &merry, requests, false);
+            assert_eq!(result.err(), Some(PoolError::MinDebtNotMet));
+        });
+    }
+
+    #[test]
+    fn test_submit_repay_under_min_debt() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let merry = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        underlying_1_client.mint(&frodo, &1_0000000);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+            min_debt: 6_0000000,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            e.mock_all_auths_allowing_non_root_auth();
+            storage::set_pool_config(&e, &pool_config);
+
+            storage::set_user_positions(
+                &e,
+                &samwise,
+                &Positions {
+                    collateral: map![&e, (0, 15_0000000)],
+                    liabilities: map![&e, (1, 1_5000000)],
+                    supply: map![&e],
+                },
+            );
+
+            // repaying below the min debt is allowed
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::Repay as u32,
+                    address: underlying_1,
+                    amount: 1_0000000,
+                },
+            ];
+            let positions = execute_submit(&e, &samwise, &frodo, &merry, requests, false).unwrap();
+            assert!(positions.liabilities.get_unchecked(1) < 1_0000000);
         });
     }
 
@@ -1584,6 +2921,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         let pre_positions = Positions {
             liabilities: map![&e],
@@ -1614,7 +2953,8 @@ mod tests {
                     amount: 2_5000000,
                 },
             ];
-            let positions = execute_submit(&e, &samwise, &samwise, &samwise, requests, false);
+            let positions =
+                execute_submit(&e, &samwise, &samwise, &samwise, requests, false).unwrap();
 
             assert_eq!(positions.liabilities.len(), 0);
             assert_eq!(positions.collateral.len(), 1);
@@ -1709,6 +3049,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         let pre_positions = Positions {
             liabilities: map![&e],
@@ -1737,7 +3079,7 @@ mod tests {
                     amount: 10_0000000,
                 },
             ];
-            execute_submit(&e, &samwise, &samwise, &samwise, requests, false);
+            execute_submit(&e, &samwise, &samwise, &samwise, requests, false).unwrap();
         });
     }
 
@@ -1797,6 +3139,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -1826,7 +3170,8 @@ mod tests {
                     amount: 25_0000000,
                 },
             ];
-            let positions = execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests);
+            let positions =
+                execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests).unwrap();
 
             assert_eq!(positions.liabilities.len(), 1);
             assert_eq!(positions.collateral.len(), 1);
@@ -1923,6 +3268,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -1951,7 +3298,8 @@ mod tests {
                     amount: 25_0000010,
                 },
             ];
-            let positions = execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests);
+            let positions =
+                execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests).unwrap();
 
             assert_eq!(positions.liabilities.len(), 0);
             assert_eq!(positions.collateral.len(), 0);
@@ -1976,7 +3324,6 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1205)")]
     fn test_submit_with_flash_loan_checks_health() {
         let e = Env::default();
         e.cost_estimate().budget().reset_unlimited();
@@ -2030,6 +3377,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -2053,7 +3402,8 @@ mod tests {
                     amount: 8_0000000,
                 },
             ];
-            execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests);
+            let result = execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests);
+            assert_eq!(result.err(), Some(PoolError::InvalidHf));
         });
     }
 
@@ -2112,6 +3462,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -2135,7 +3487,7 @@ mod tests {
                     amount: 50_0000000,
                 },
             ];
-            execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests);
+            execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests).unwrap();
         });
     }
 
@@ -2196,6 +3548,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 3,
+            min_debt: 0,
+            base: None,
         };
         let user_positions = Positions {
             liabilities: map![&e, (1, 1_0000000)],
@@ -2220,7 +3574,7 @@ mod tests {
                     amount: 2_0000000,
                 },
             ];
-            execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests);
+            execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests).unwrap();
         });
     }
 
@@ -2280,6 +3634,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         let user_positions = Positions {
             liabilities: map![&e, (0, 1_0000000), (1, 1_0000000)],
@@ -2307,7 +3663,8 @@ mod tests {
                     amount: 2_0000000,
                 },
             ];
-            let result = execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests);
+            let result =
+                execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests).unwrap();
 
             assert_eq!(result.liabilities.len(), 1);
             assert_eq!(result.collateral.len(), 2);
@@ -2330,7 +3687,6 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1212)")]
     fn test_submit_with_flash_loan_with_ongoing_liquidation_blocked() {
         let e = Env::default();
         e.cost_estimate().budget().reset_unlimited();
@@ -2389,6 +3745,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         let user_positions = Positions {
             liabilities: map![&e, (0, 5_0000000)],
@@ -2419,12 +3777,12 @@ mod tests {
                     amount: 4_5000000,
                 },
             ];
-            execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests);
+            let result = execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests);
+            assert_eq!(result.err(), Some(PoolError::AuctionInProgress));
         });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1224)")]
     fn test_submit_with_flash_loan_under_min_collateral_fails() {
         let e = Env::default();
         e.cost_estimate().budget().reset_unlimited();
@@ -2480,6 +3838,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             e.mock_all_auths_allowing_non_root_auth();
@@ -2503,7 +3863,8 @@ mod tests {
                     amount: 4_9900000,
                 },
             ];
-            execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests);
+            let result = execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests);
+            assert_eq!(result.err(), Some(PoolError::MinCollateralNotMet));
         });
     }
 
@@ -2562,6 +3923,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 2,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -2585,7 +3948,7 @@ mod tests {
                     amount: 25_0000000,
                 },
             ];
-            execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests);
+            execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests).unwrap();
         });
     }
 
@@ -2645,6 +4008,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            min_debt: 0,
+            base: None,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -2668,7 +4033,7 @@ mod tests {
                     amount: 25_0000000,
                 },
             ];
-            execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests);
+            execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests).unwrap();
         });
     }
 }
@@ -0,0 +1,144 @@
+use soroban_sdk::{contracttype, map, Address, Env, Map};
+
+use crate::storage;
+
+use super::Pool;
+
+/// A summary of a reserve's balances for the backstop, in the reserve's underlying tokens
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[contracttype]
+pub struct ReserveSummary {
+    pub index: u32,            // the index of the reserve in the pool
+    pub supply: i128,          // the total supplied tokens, including accrued interest
+    pub liabilities: i128,     // the total borrowed tokens, including accrued interest
+    pub backstop_credit: i128, // the interest owed to the backstop
+    pub bad_debt: i128,        // the bad debt held by the backstop, including accrued interest
+}
+
+/// Calculate the summary of a reserve at the current ledger
+///
+/// ### Arguments
+/// * `asset` - The underlying asset of the reserve
+///
+/// ### Panics
+/// If the reserve does not exist
+pub fn calc_reserve_summary(e: &Env, asset: &Address) -> ReserveSummary {
+    let mut pool = Pool::load(e);
+    let reserve = pool.load_reserve(e, asset, false);
+    let backstop_positions = storage::get_user_positions(e, &storage::get_backstop(e));
+    let bad_debt = backstop_positions
+        .liabilities
+        .get(reserve.config.index)
+        .unwrap_or(0);
+    ReserveSummary {
+        index: reserve.config.index,
+        supply: reserve.total_supply(e),
+        liabilities: reserve.total_liabilities(e),
+        backstop_credit: reserve.data.backstop_credit,
+        bad_debt: reserve.to_asset_from_d_token(e, bad_debt),
+    }
+}
+
+/// Calculate the bad debt held by the backstop at the current ledger, in underlying tokens
+///
+/// Returns a map of the underlying asset of each reserve with bad debt to the amount of bad debt
+pub fn calc_bad_debt(e: &Env) -> Map<Address, i128> {
+    let backstop_positions = storage::get_user_positions(e, &storage::get_backstop(e));
+    let reserve_list = storage::get_res_list(e);
+    let mut pool = Pool::load(e);
+    let mut bad_debt = map![e];
+    for (index, d_tokens) in backstop_positions.liabilities.iter() {
+        let asset = reserve_list.get_unchecked(index);
+        let reserve = pool.load_reserve(e, &asset, false);
+        bad_debt.set(asset, reserve.to_asset_from_d_token(e, d_tokens));
+    }
+    bad_debt
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        pool::Positions,
+        storage::PoolConfig,
+        testutils::{self, create_pool},
+    };
+
+    use super::*;
+    use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
+
+    #[test]
+    fn test_calc_reserve_summary_and_bad_debt() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let backstop = Address::generate(&e);
+        let pool = create_pool(&e);
+        let (oracle, _) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.b_rate = 1_100_000_000_000;
+        reserve_data_0.backstop_credit = 1_0000000;
+        reserve_data_0.last_time = 12345;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config_0, &reserve_data_0);
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_config_1.index = 1;
+        reserve_data_1.d_rate = 1_200_000_000_000;
+        reserve_data_1.last_time = 12345;
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config_1, &reserve_data_1);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_backstop(&e, &backstop);
+
+            // no bad debt
+            assert_eq!(calc_bad_debt(&e).len(), 0);
+            let summary = calc_reserve_summary(&e, &underlying_0);
+            assert_eq!(summary.index, 0);
+            assert_eq!(summary.supply, 110_0000000);
+            assert_eq!(summary.liabilities, 75_0000000);
+            assert_eq!(summary.backstop_credit, 1_0000000);
+            assert_eq!(summary.bad_debt, 0);
+
+            storage::set_user_positions(
+                &e,
+                &backstop,
+                &Positions {
+                    liabilities: map![&e, (1, 5_0000000)],
+                    collateral: map![&e],
+                    supply: map![&e],
+                },
+            );
+            assert_eq!(
+                calc_bad_debt(&e),
+                map![&e, (underlying_1.clone(), 6_0000000)]
+            );
+            let summary = calc_reserve_summary(&e, &underlying_1);
+            assert_eq!(summary.index, 1);
+            assert_eq!(summary.liabilities, 90_0000000);
+            assert_eq!(summary.bad_debt, 6_0000000);
+        });
+    }
+}
@@ -0,0 +1,116 @@
+use soroban_fixed_point_math::SorobanFixedPoint;
+use soroban_sdk::{contracttype, Env};
+
+use crate::storage;
+
+use super::Pool;
+
+/// The aggregate balances of the pool across all reserves, valued in the oracle's base asset
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[contracttype]
+pub struct PoolTotals {
+    pub supplied: i128, // the value of all supplied tokens, including accrued interest
+    pub borrowed: i128, // the value of all borrowed tokens, including accrued interest
+    pub backstop_credit: i128, // the value of the interest owed to the backstop
+    pub tvl: i128, // the value of the tokens held by the pool (supplied + backstop_credit - borrowed)
+}
+
+/// Calculate the pool's aggregate balances across all reserves at the current ledger, valued in
+/// the oracle's base asset
+pub fn calc_pool_totals(e: &Env) -> PoolTotals {
+    let mut pool = Pool::load(e);
+    let mut totals = PoolTotals::default();
+    for asset in storage::get_res_list(e).iter() {
+        let reserve = pool.load_reserve(e, &asset, false);
+        let asset_to_base = pool.load_price(e, &asset);
+        totals.supplied +=
+            asset_to_base.fixed_mul_floor(e, &reserve.total_supply(e), &reserve.scalar);
+        totals.borrowed +=
+            asset_to_base.fixed_mul_ceil(e, &reserve.total_liabilities(e), &reserve.scalar);
+        totals.backstop_credit +=
+            asset_to_base.fixed_mul_floor(e, &reserve.data.backstop_credit, &reserve.scalar);
+    }
+    totals.tvl = totals.supplied + totals.backstop_credit - totals.borrowed;
+    totals
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{storage::PoolConfig, testutils};
+
+    use super::*;
+    use sep_40_oracle::testutils::Asset;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger, LedgerInfo},
+        vec, Address, Symbol,
+    };
+
+    #[test]
+    fn test_calc_pool_totals() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.cost_estimate().budget().reset_unlimited();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.b_rate = 1_100_000_000_000;
+        reserve_data_0.backstop_credit = 1_0000000;
+        reserve_data_0.last_time = 12345;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config_0, &reserve_data_0);
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_config_1.index = 1;
+        reserve_data_1.d_rate = 1_200_000_000_000;
+        reserve_data_1.last_time = 12345;
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config_1, &reserve_data_1);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 2_0000000, 0_5000000]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            let totals = calc_pool_totals(&e);
+            // supplied: 100 * 1.1 * 2 + 100 * 0.5 = 270
+            assert_eq!(totals.supplied, 270_0000000);
+            // borrowed: 75 * 2 + 75 * 1.2 * 0.5 = 195
+            assert_eq!(totals.borrowed, 195_0000000);
+            assert_eq!(totals.backstop_credit, 2_0000000);
+            assert_eq!(totals.tvl, 77_0000000);
+        });
+    }
+}
@@ -1,9 +1,12 @@
 use soroban_fixed_point_math::SorobanFixedPoint;
-use soroban_sdk::{contracttype, panic_with_error, Address, Env, Map};
+use soroban_sdk::{contracttype, panic_with_error, Address, Bytes, Env, Map};
 
-use crate::{constants::SCALAR_12, emissions, storage, validator::require_nonnegative, PoolError};
+use crate::{constants::SCALAR_18, emissions, storage, validator::require_nonnegative, PoolError};
 
-use super::{Pool, Reserve};
+use super::{borrower_index::update_borrower_index, Pool, ReserveState};
+
+/// The number of bytes a packed position takes, a 4 byte key followed by a 16 byte balance
+const PACKED_POSITION_SIZE: u32 = 20;
 
 /// A user / contracts position's with the pool, stored in the Reserve's decimals
 #[derive(Clone)]
@@ -31,6 +34,47 @@ impl Positions {
     pub fn effective_count(&self) -> u32 {
         self.liabilities.len() + self.collateral.len()
     }
+
+    /// Pack the positions into bytes, as a sequence of big endian (reserve index * 3 + position type,
+    /// balance) pairs encoded as a u32 and an i128, where the position type is 0 for liabilities,
+    /// 1 for collateral, and 2 for supply.
+    pub fn pack(&self, e: &Env) -> Bytes {
+        let mut packed = Bytes::new(e);
+        for (position_type, positions) in [&self.liabilities, &self.collateral, &self.supply]
+            .iter()
+            .enumerate()
+        {
+            for (index, balance) in positions.iter() {
+                packed.extend_from_array(&(index * 3 + position_type as u32).to_be_bytes());
+                packed.extend_from_array(&balance.to_be_bytes());
+            }
+        }
+        packed
+    }
+
+    /// Unpack positions from the format created by `pack`
+    pub fn unpack(e: &Env, packed: &Bytes) -> Self {
+        let mut positions = Positions::env_default(e);
+        let mut entry = [0u8; PACKED_POSITION_SIZE as usize];
+        let mut key = [0u8; 4];
+        let mut balance = [0u8; 16];
+        for start in (0..packed.len()).step_by(PACKED_POSITION_SIZE as usize) {
+            packed
+                .slice(start..start + PACKED_POSITION_SIZE)
+                .copy_into_slice(&mut entry);
+            key.copy_from_slice(&entry[..4]);
+            balance.copy_from_slice(&entry[4..]);
+            let key = u32::from_be_bytes(key);
+            let balance = i128::from_be_bytes(balance);
+            let index = key / 3;
+            match key % 3 {
+                0 => positions.liabilities.set(index, balance),
+                1 => positions.collateral.set(index, balance),
+                _ => positions.supply.set(index, balance),
+            }
+        }
+        positions
+    }
 }
 
 /// A user / contracts position's with the pool
@@ -38,20 +82,46 @@ impl Positions {
 pub struct User {
     pub address: Address,
     pub positions: Positions,
+    pub stable_liabilities: Option<Map<u32, i128>>, // Map of Reserve Index to the portion of the liability share balance borrowed at a stable rate, if the user has any stored
+    pub is_legacy: bool, // whether the positions were read from the legacy positions entry, which is removed when they are stored
 }
 
 impl User {
     /// Create an empty User object in the environment
     pub fn load(e: &Env, address: &Address) -> Self {
+        let (positions, is_legacy) = storage::get_user_positions_entry(e, address);
+        // stable rate liabilities are a portion of the user's liabilities
+        let stable_liabilities = if positions.liabilities.is_empty() {
+            None
+        } else {
+            storage::get_user_stable_liabilities(e, address)
+        };
         User {
             address: address.clone(),
-            positions: storage::get_user_positions(e, address),
+            positions,
+            stable_liabilities,
+            is_legacy,
         }
     }
 
     /// Store the user's positions to the ledger
     pub fn store(&self, e: &Env) {
         storage::set_user_positions(e, &self.address, &self.positions);
+        if self.is_legacy {
+            storage::remove_legacy_user_positions(e, &self.address);
+        }
+        if let Some(stable_liabilities) = &self.stable_liabilities {
+            // remove the stable rate loans of any stable rate liabilities that no longer exist
+            if let Some(stored) = storage::get_user_stable_liabilities(e, &self.address) {
+                for (index, _) in stored.iter() {
+                    if !stable_liabilities.contains_key(index) {
+                        storage::set_stable_loan(e, &self.address, index, &None);
+                    }
+                }
+            }
+            storage::set_user_stable_liabilities(e, &self.address, stable_liabilities);
+        }
+        update_borrower_index(e, self);
     }
 
     /// Check if the user has liabilities
@@ -66,7 +136,7 @@ impl User {
 
     /// Add liabilities to the position expressed in debtTokens. Accrues emissions
     /// against the balance if necessary and updates the reserve's d_supply.
-    pub fn add_liabilities(&mut self, e: &Env, reserve: &mut Reserve, amount: i128) {
+    pub fn add_liabilities(&mut self, e: &Env, reserve: &mut ReserveState, amount: i128) {
         if amount <= 0 {
             panic_with_error!(e, PoolError::InvalidDTokenMintAmount)
         }
@@ -80,7 +150,7 @@ impl User {
 
     /// Remove liabilities from the position expressed in debtTokens. Accrues emissions
     /// against the balance if necessary and updates the reserve's d_supply.
-    pub fn remove_liabilities(&mut self, e: &Env, reserve: &mut Reserve, amount: i128) {
+    pub fn remove_liabilities(&mut self, e: &Env, reserve: &mut ReserveState, amount: i128) {
         if amount <= 0 {
             panic_with_error!(e, PoolError::InvalidDTokenBurnAmount)
         }
@@ -95,24 +165,48 @@ impl User {
                 .liabilities
                 .set(reserve.config.index, new_balance);
         }
+        // the variable portion of the liabilities is removed first
+        if self.get_stable_liabilities(reserve.config.index) > new_balance {
+            self.set_stable_liabilities(reserve.config.index, new_balance);
+        }
         reserve.data.d_supply -= amount;
     }
 
+    /// Get the portion of the debtToken position for the reserve at the given index borrowed at a
+    /// stable rate
+    pub fn get_stable_liabilities(&self, reserve_index: u32) -> i128 {
+        match &self.stable_liabilities {
+            Some(stable_liabilities) => stable_liabilities.get(reserve_index).unwrap_or(0),
+            None => 0,
+        }
+    }
+
+    /// Set the portion of the debtToken position for the reserve at the given index borrowed at a
+    /// stable rate. This does not modify the debtToken position itself.
+    pub fn set_stable_liabilities(&mut self, reserve_index: u32, amount: i128) {
+        if amount <= 0 {
+            if let Some(stable_liabilities) = &mut self.stable_liabilities {
+                stable_liabilities.remove(reserve_index);
+            }
+        } else {
+            self.stable_liabilities
+                .get_or_insert_with(|| Map::new(self.address.env()))
+                .set(reserve_index, amount);
+        }
+    }
+
     /// Default on liabilities from the position expressed in debtTokens. Accrues emissions
     /// against the balance if necessary and updates the reserve's b_rate and d_supply.
     ///
     /// This should only be called if the liabilities are being defaulted on. The liability will
     /// be forgiven and suppliers will lose funds.
-    pub fn default_liabilities(&mut self, e: &Env, reserve: &mut Reserve, amount: i128) {
+    pub fn default_liabilities(&mut self, e: &Env, reserve: &mut ReserveState, amount: i128) {
         self.remove_liabilities(e, reserve, amount);
         // determine amount of funds in underlying that have defaulted
         // and deduct them from the b_rate
         let default_amount = reserve.to_asset_from_d_token(e, amount);
-        let b_rate_loss = default_amount.fixed_div_ceil(&e, &reserve.data.b_supply, &SCALAR_12);
-        reserve.data.b_rate -= b_rate_loss;
-        if reserve.data.b_rate < 0 {
-            reserve.data.b_rate = 0;
-        }
+        let b_rate_loss = default_amount.fixed_div_ceil(&e, &reserve.data.b_supply, &SCALAR_18);
+        reserve.set_b_rate_18((reserve.b_rate_18() - b_rate_loss).max(0));
     }
 
     /// Check if the user has collateral
@@ -127,7 +221,7 @@ impl User {
 
     /// Add collateral to the position expressed in blendTokens. Accrues emissions
     /// against the balance if necessary and updates the reserve's b_supply.
-    pub fn add_collateral(&mut self, e: &Env, reserve: &mut Reserve, amount: i128) {
+    pub fn add_collateral(&mut self, e: &Env, reserve: &mut ReserveState, amount: i128) {
         if amount <= 0 {
             panic_with_error!(e, PoolError::InvalidBTokenMintAmount)
         }
@@ -141,7 +235,7 @@ impl User {
 
     /// Remove collateral from the position expressed in blendTokens. Accrues emissions
     /// against the balance if necessary and updates the reserve's d_supply.
-    pub fn remove_collateral(&mut self, e: &Env, reserve: &mut Reserve, amount: i128) {
+    pub fn remove_collateral(&mut self, e: &Env, reserve: &mut ReserveState, amount: i128) {
         if amount <= 0 {
             panic_with_error!(e, PoolError::InvalidBTokenBurnAmount)
         }
@@ -166,7 +260,7 @@ impl User {
 
     /// Add supply to the position expressed in blendTokens. Accrues emissions
     /// against the balance if necessary and updates the reserve's b_supply.
-    pub fn add_supply(&mut self, e: &Env, reserve: &mut Reserve, amount: i128) {
+    pub fn add_supply(&mut self, e: &Env, reserve: &mut ReserveState, amount: i128) {
         if amount <= 0 {
             panic_with_error!(e, PoolError::InvalidBTokenMintAmount)
         }
@@ -180,7 +274,7 @@ impl User {
 
     /// Remove supply from the position expressed in blendTokens. Accrues emissions
     /// against the balance if necessary and updates the reserve's b_supply.
-    pub fn remove_supply(&mut self, e: &Env, reserve: &mut Reserve, amount: i128) {
+    pub fn remove_supply(&mut self, e: &Env, reserve: &mut ReserveState, amount: i128) {
         if amount <= 0 {
             panic_with_error!(e, PoolError::InvalidBTokenBurnAmount)
         }
@@ -249,7 +343,7 @@ impl User {
         }
     }
 
-    fn update_d_emissions(&self, e: &Env, reserve: &Reserve, amount: i128) {
+    fn update_d_emissions(&self, e: &Env, reserve: &ReserveState, amount: i128) {
         emissions::update_emissions(
             e,
             reserve.config.index * 2,
@@ -260,7 +354,7 @@ impl User {
         );
     }
 
-    fn update_b_emissions(&self, e: &Env, reserve: &Reserve, amount: i128) {
+    fn update_b_emissions(&self, e: &Env, reserve: &ReserveState, amount: i128) {
         emissions::update_emissions(
             e,
             reserve.config.index * 2 + 1,
@@ -280,6 +374,8 @@ mod tests {
     use soroban_sdk::{
         map,
         testutils::{Address as _, Ledger, LedgerInfo},
+        xdr::ToXdr,
+        Vec,
     };
 
     #[test]
@@ -296,6 +392,8 @@ mod tests {
                 liabilities: map![&e],
                 supply: map![&e],
             },
+            stable_liabilities: None,
+            is_legacy: false,
         };
         e.as_contract(&pool, || {
             user.store(&e);
@@ -308,6 +406,179 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_load_and_store_stable_liabilities() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        let mut user = User {
+            address: samwise.clone(),
+            positions: Positions {
+                collateral: map![&e],
+                liabilities: map![&e, (0, 1000)],
+                supply: map![&e],
+            },
+            stable_liabilities: None,
+            is_legacy: false,
+        };
+        user.set_stable_liabilities(0, 400);
+        e.as_contract(&pool, || {
+            user.store(&e);
+            let mut loaded_user = User::load(&e, &samwise);
+            assert_eq!(loaded_user.get_stable_liabilities(0), 400);
+            assert_eq!(loaded_user.positions.liabilities.get_unchecked(0), 1000);
+            assert_eq!(
+                storage::get_user_positions(&e, &samwise).liabilities,
+                map![&e, (0, 1000)]
+            );
+
+            loaded_user.set_stable_liabilities(0, 0);
+            loaded_user.store(&e);
+            assert!(storage::get_user_stable_liabilities(&e, &samwise).is_none());
+        });
+    }
+
+    #[test]
+    fn test_pack_and_unpack() {
+        let e = Env::default();
+        let positions = Positions {
+            liabilities: map![&e, (1, 123), (4, 456)],
+            collateral: map![&e, (0, 789), (4, 101112)],
+            supply: map![&e, (2, 131415)],
+        };
+
+        let packed = positions.pack(&e);
+        assert_eq!(packed.len(), 5 * PACKED_POSITION_SIZE);
+        let mut first = [0u8; PACKED_POSITION_SIZE as usize];
+        first[3] = 3;
+        first[4..].copy_from_slice(&123i128.to_be_bytes());
+        assert_eq!(
+            packed.slice(0..PACKED_POSITION_SIZE),
+            Bytes::from_array(&e, &first)
+        );
+
+        let unpacked = Positions::unpack(&e, &packed);
+        assert_eq!(unpacked.liabilities, positions.liabilities);
+        assert_eq!(unpacked.collateral, positions.collateral);
+        assert_eq!(unpacked.supply, positions.supply);
+
+        let empty = Positions::env_default(&e);
+        assert_eq!(empty.pack(&e).len(), 0);
+        assert_eq!(Positions::unpack(&e, &empty.pack(&e)).effective_count(), 0);
+    }
+
+    #[test]
+    fn test_pack_size() {
+        let e = Env::default();
+        let positions = Positions {
+            liabilities: map![&e, (1, 123_0000000), (4, 456_0000000)],
+            collateral: map![&e, (0, 789_0000000), (4, 1011_0000000)],
+            supply: map![&e, (2, 1314_0000000)],
+        };
+
+        // the legacy entry stored the Positions struct, and the previous packed entry stored
+        // each position as an i128 key and an i128 balance
+        let mut pairs: Vec<i128> = Vec::new(&e);
+        for (index, balance) in positions.liabilities.iter() {
+            pairs.push_back(index as i128 * 3);
+            pairs.push_back(balance);
+        }
+        for (index, balance) in positions.collateral.iter() {
+            pairs.push_back(index as i128 * 3 + 1);
+            pairs.push_back(balance);
+        }
+        for (index, balance) in positions.supply.iter() {
+            pairs.push_back(index as i128 * 3 + 2);
+            pairs.push_back(balance);
+        }
+        let legacy_size = positions.clone().to_xdr(&e).len();
+        let pairs_size = pairs.to_xdr(&e).len();
+        let packed_size = positions.pack(&e).to_xdr(&e).len();
+
+        // an 8 byte header followed by 20 bytes per position
+        assert_eq!(packed_size, 8 + 5 * PACKED_POSITION_SIZE);
+        assert!(packed_size < pairs_size);
+        assert!(packed_size * 2 < legacy_size);
+    }
+
+    #[test]
+    fn test_load_migrates_legacy_positions() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        let legacy_positions = Positions {
+            liabilities: map![&e, (1, 500)],
+            collateral: map![&e, (0, 10000)],
+            supply: map![&e],
+        };
+        e.as_contract(&pool, || {
+            let legacy_key = storage::PoolDataKey::Positions(samwise.clone());
+            let packed_key = storage::PoolDataKey::PackedPos(samwise.clone());
+            e.storage().persistent().set(&legacy_key, &legacy_positions);
+
+            // legacy positions are read without being migrated
+            let positions = storage::get_user_positions(&e, &samwise);
+            assert_eq!(positions.collateral.get_unchecked(0), 10000);
+            let mut user = User::load(&e, &samwise);
+            assert!(user.is_legacy);
+            assert_eq!(user.positions.collateral.get_unchecked(0), 10000);
+            assert_eq!(user.positions.liabilities.get_unchecked(1), 500);
+            assert!(e.storage().persistent().has(&legacy_key));
+            assert!(!e.storage().persistent().has(&packed_key));
+
+            // legacy positions are migrated when they are stored
+            user.positions.supply.set(2, 250);
+            user.store(&e);
+            assert!(!e.storage().persistent().has(&legacy_key));
+            assert!(e.storage().persistent().has(&packed_key));
+
+            let loaded_user = User::load(&e, &samwise);
+            assert!(!loaded_user.is_legacy);
+            assert_eq!(loaded_user.positions.collateral.get_unchecked(0), 10000);
+            assert_eq!(loaded_user.positions.liabilities.get_unchecked(1), 500);
+            assert_eq!(loaded_user.positions.supply.get_unchecked(2), 250);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1249)")]
+    fn test_load_archived_positions_panics() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        let user = User {
+            address: samwise.clone(),
+            positions: Positions {
+                collateral: map![&e, (0, 10000)],
+                liabilities: map![&e, (1, 500)],
+                supply: map![&e],
+            },
+            stable_liabilities: None,
+            is_legacy: false,
+        };
+        e.as_contract(&pool, || {
+            user.store(&e);
+
+            // emptied positions clear the marker
+            let mut emptied = user.clone();
+            emptied.positions = Positions::env_default(&e);
+            emptied.store(&e);
+            assert_eq!(User::load(&e, &samwise).positions.effective_count(), 0);
+            user.store(&e);
+
+            // the positions entry is archived while the marker is live
+            let packed_key = storage::PoolDataKey::PackedPos(samwise.clone());
+            e.storage().persistent().remove(&packed_key);
+            User::load(&e, &samwise);
+        });
+    }
+
     #[test]
     fn test_liabilities() {
         let e = Env::default();
@@ -325,6 +596,8 @@ mod tests {
         let mut user = User {
             address: samwise.clone(),
             positions: Positions::env_default(&e),
+            stable_liabilities: None,
+            is_legacy: false,
         };
         e.as_contract(&pool, || {
             assert_eq!(user.get_liabilities(0), 0);
@@ -370,6 +643,8 @@ mod tests {
         let mut user = User {
             address: samwise.clone(),
             positions: Positions::env_default(&e),
+            stable_liabilities: None,
+            is_legacy: false,
         };
         e.as_contract(&pool, || {
             assert_eq!(user.get_liabilities(0), 0);
@@ -417,6 +692,8 @@ mod tests {
                 collateral: map![&e],
                 supply: map![&e],
             },
+            stable_liabilities: None,
+            is_legacy: false,
         };
 
         e.as_contract(&pool, || {
@@ -462,6 +739,8 @@ mod tests {
         let mut user = User {
             address: samwise.clone(),
             positions: Positions::env_default(&e),
+            stable_liabilities: None,
+            is_legacy: false,
         };
         e.as_contract(&pool, || {
             assert_eq!(user.get_liabilities(0), 0);
@@ -511,6 +790,8 @@ mod tests {
                 collateral: map![&e],
                 supply: map![&e],
             },
+            stable_liabilities: None,
+            is_legacy: false,
         };
         e.as_contract(&pool, || {
             let res_0_d_token_index = reserve_0.config.index * 2 + 0;
@@ -554,6 +835,8 @@ mod tests {
         let mut user = User {
             address: samwise.clone(),
             positions: Positions::env_default(&e),
+            stable_liabilities: None,
+            is_legacy: false,
         };
         e.as_contract(&pool, || {
             user.add_liabilities(&e, &mut reserve_0, 123);
@@ -579,6 +862,8 @@ mod tests {
         let mut user = User {
             address: samwise.clone(),
             positions: Positions::env_default(&e),
+            stable_liabilities: None,
+            is_legacy: false,
         };
         e.as_contract(&pool, || {
             assert_eq!(user.get_liabilities(0), 0);
@@ -618,6 +903,8 @@ mod tests {
         let mut user = User {
             address: samwise.clone(),
             positions: Positions::env_default(&e),
+            stable_liabilities: None,
+            is_legacy: false,
         };
         e.as_contract(&pool, || {
             assert_eq!(user.get_liabilities(0), 0);
@@ -652,6 +939,8 @@ mod tests {
         let mut user = User {
             address: samwise.clone(),
             positions: Positions::env_default(&e),
+            stable_liabilities: None,
+            is_legacy: false,
         };
         e.as_contract(&pool, || {
             assert_eq!(user.get_liabilities(0), 0);
@@ -693,6 +982,8 @@ mod tests {
         let mut user = User {
             address: samwise.clone(),
             positions: Positions::env_default(&e),
+            stable_liabilities: None,
+            is_legacy: false,
         };
         e.as_contract(&pool, || {
             assert_eq!(user.get_collateral(0), 0);
@@ -741,6 +1032,8 @@ mod tests {
         let mut user = User {
             address: samwise.clone(),
             positions: Positions::env_default(&e),
+            stable_liabilities: None,
+            is_legacy: false,
         };
         e.as_contract(&pool, || {
             assert_eq!(user.get_collateral(0), 0);
@@ -788,6 +1081,8 @@ mod tests {
                 collateral: map![&e, (reserve_0.config.index, 700)],
                 supply: map![&e, (reserve_0.config.index, 300)],
             },
+            stable_liabilities: None,
+            is_legacy: false,
         };
         e.as_contract(&pool, || {
             let res_0_d_token_index = reserve_0.config.index * 2 + 1;
@@ -832,6 +1127,8 @@ mod tests {
         let mut user = User {
             address: samwise.clone(),
             positions: Positions::env_default(&e),
+            stable_liabilities: None,
+            is_legacy: false,
         };
         e.as_contract(&pool, || {
             assert_eq!(user.get_collateral(0), 0);
@@ -882,6 +1179,8 @@ mod tests {
                 collateral: map![&e, (reserve_0.config.index, 700)],
                 supply: map![&e, (reserve_0.config.index, 300)],
             },
+            stable_liabilities: None,
+            is_legacy: false,
         };
         e.as_contract(&pool, || {
             let res_0_d_token_index = reserve_0.config.index * 2 + 1;
@@ -926,6 +1225,8 @@ mod tests {
         let mut user = User {
             address: samwise.clone(),
             positions: Positions::env_default(&e),
+            stable_liabilities: None,
+            is_legacy: false,
         };
         e.as_contract(&pool, || {
             user.add_collateral(&e, &mut reserve_0, 123);
@@ -952,6 +1253,8 @@ mod tests {
         let mut user = User {
             address: samwise.clone(),
             positions: Positions::env_default(&e),
+            stable_liabilities: None,
+            is_legacy: false,
         };
         e.as_contract(&pool, || {
             assert_eq!(user.get_supply(0), 0);
@@ -989,6 +1292,8 @@ mod tests {
         let mut user = User {
             address: samwise.clone(),
             positions: Positions::env_default(&e),
+            stable_liabilities: None,
+            is_legacy: false,
         };
         e.as_contract(&pool, || {
             assert_eq!(user.get_supply(0), 0);
@@ -1036,6 +1341,8 @@ mod tests {
                 collateral: map![&e, (reserve_0.config.index, 700)],
                 supply: map![&e, (reserve_0.config.index, 300)],
             },
+            stable_liabilities: None,
+            is_legacy: false,
         };
         e.as_contract(&pool, || {
             let res_0_d_token_index = reserve_0.config.index * 2 + 1;
@@ -1080,6 +1387,8 @@ mod tests {
         let mut user = User {
             address: samwise.clone(),
             positions: Positions::env_default(&e),
+            stable_liabilities: None,
+            is_legacy: false,
         };
         e.as_contract(&pool, || {
             assert_eq!(user.get_supply(0), 0);
@@ -1130,6 +1439,8 @@ mod tests {
                 collateral: map![&e, (reserve_0.config.index, 700)],
                 supply: map![&e, (reserve_0.config.index, 300)],
             },
+            stable_liabilities: None,
+            is_legacy: false,
         };
         e.as_contract(&pool, || {
             let res_0_d_token_index = reserve_0.config.index * 2 + 1;
@@ -1174,6 +1485,8 @@ mod tests {
         let mut user = User {
             address: samwise.clone(),
             positions: Positions::env_default(&e),
+            stable_liabilities: None,
+            is_legacy: false,
         };
         e.as_contract(&pool, || {
             user.add_supply(&e, &mut reserve_0, 123);
@@ -1198,6 +1511,8 @@ mod tests {
         let mut user = User {
             address: samwise.clone(),
             positions: Positions::env_default(&e),
+            stable_liabilities: None,
+            is_legacy: false,
         };
         e.as_contract(&pool, || {
             user.add_supply(&e, &mut reserve_0, 123);
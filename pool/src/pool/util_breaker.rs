@@ -0,0 +1,328 @@
+use soroban_fixed_point_math::SorobanFixedPoint;
+use soroban_sdk::{panic_with_error, Env};
+
+use crate::{
+    constants::{MAX_UTIL_BREAKER_ACCRUALS, SCALAR_7},
+    errors::PoolError,
+    events::PoolEvents,
+    storage::{self, UtilBreaker},
+};
+
+use super::{totals::calc_pool_totals, Pool};
+
+/// Set or remove the pool's utilization circuit breaker. Removing the breaker resets its state, but
+/// does not change the pool status if the breaker has put the pool on-ice.
+///
+/// ### Arguments
+/// * `util_breaker` - The utilization circuit breaker, or None to disable it
+///
+/// ### Panics
+/// If the utilization circuit breaker is invalid
+pub fn execute_set_util_breaker(e: &Env, util_breaker: &Option<UtilBreaker>) {
+    match util_breaker {
+        Some(util_breaker) => {
+            // verify at least one threshold is set, the thresholds are at most 100%, and the breaker
+            // requires a bounded number of accruals
+            if (util_breaker.reserve_util == 0 && util_breaker.pool_util == 0)
+                || util_breaker.reserve_util > SCALAR_7 as u32
+                || util_breaker.pool_util > SCALAR_7 as u32
+                || util_breaker.accruals == 0
+                || util_breaker.accruals > MAX_UTIL_BREAKER_ACCRUALS
+            {
+                panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+            }
+        }
+        None => storage::set_util_breaker_state(e, &Default::default()),
+    }
+    storage::set_util_breaker(e, util_breaker);
+}
+
+/// Check the utilization circuit breaker after the pool's reserves have been accrued and stored. This
+/// is evaluated at most once per ledger.
+///
+/// The breaker puts an active pool on-ice once a reserve's utilization, or the pool's aggregate
+/// utilization, is at or above its critical threshold for the configured number of consecutive accruals.
+/// The pool is returned to its previous status once utilization is below the thresholds, if the
+/// status has not been changed since.
+///
+/// Checking the pool's aggregate utilization values every reserve in the oracle's base asset, so it
+/// is only done if the pool threshold is set.
+///
+/// Does nothing if the breaker is disabled
+///
+/// ### Arguments
+/// * `pool` - The pool, with the reserves accrued this ledger cached
+pub fn check_util_breaker(e: &Env, pool: &Pool) {
+    let util_breaker = match storage::get_util_breaker(e) {
+        Some(util_breaker) => util_breaker,
+        None => return,
+    };
+    let mut state = storage::get_util_breaker_state(e);
+    let sequence = e.ledger().sequence();
+    if state.ledger >= sequence {
+        return;
+    }
+    state.ledger = sequence;
+
+    if is_util_critical(e, &util_breaker, pool) {
+        state.count += 1;
+        if !state.tripped && state.count >= util_breaker.accruals {
+            let mut pool_config = storage::get_pool_config(e);
+            // only trip the breaker for an active pool
+            if pool_config.status <= 1 {
+                state.tripped = true;
+                state.prev_status = pool_config.status;
+                pool_config.status = 3;
+                storage::set_pool_config(e, &pool_config);
+                PoolEvents::util_breaker(e, true, pool_config.status);
+            }
+        }
+    } else {
+        state.count = 0;
+        if state.tripped {
+            state.tripped = false;
+            let mut pool_config = storage::get_pool_config(e);
+            if pool_config.status == 3 {
+                pool_config.status = state.prev_status;
+                storage::set_pool_config(e, &pool_config);
+            }
+            PoolEvents::util_breaker(e, false, pool_config.status);
+        }
+    }
+    storage::set_util_breaker_state(e, &state);
+}
+
+/// Check if any cached reserve, or the pool in aggregate, is at or above its critical utilization
+fn is_util_critical(e: &Env, util_breaker: &UtilBreaker, pool: &Pool) -> bool {
+    if util_breaker.reserve_util > 0 {
+        for (_, reserve) in pool.reserves.iter() {
+            if reserve.utilization(e) >= util_breaker.reserve_util as i128 {
+                return true;
+            }
+        }
+    }
+    if util_breaker.pool_util > 0 {
+        let totals = calc_pool_totals(e);
+        if totals.supplied > 0
+            && totals
+                .borrowed
+                .fixed_div_floor(e, &totals.supplied, &SCALAR_7)
+                >= util_breaker.pool_util as i128
+        {
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        storage::{PoolConfig, UtilBreakerState},
+        testutils,
+    };
+
+    use super::*;
+    use sep_40_oracle::testutils::Asset;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger, LedgerInfo},
+        vec, Address, Symbol,
+    };
+
+    fn set_ledger(e: &Env, sequence_number: u32) {
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+    }
+
+    fn pool_config(e: &Env, oracle: Address, status: u32) -> PoolConfig {
+        PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        }
+    }
+
+    #[test]
+    fn test_check_util_breaker_trips_after_accruals() {
+        let e = Env::default();
+        e.mock_all_auths();
+        set_ledger(&e, 100);
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        // default reserve is 75% utilized
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.last_time = 12345;
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config(&e, Address::generate(&e), 1));
+            execute_set_util_breaker(
+                &e,
+                &Some(UtilBreaker {
+                    reserve_util: 0_7000000,
+                    pool_util: 0,
+                    accruals: 2,
+                }),
+            );
+
+            let mut pool = Pool::load(&e);
+            let reserve = pool.load_reserve(&e, &underlying, true);
+            pool.cache_reserve(reserve);
+            pool.store_cached_reserves(&e);
+            // the breaker is only evaluated once per ledger
+            pool.store_cached_reserves(&e);
+            assert_eq!(storage::get_util_breaker_state(&e).count, 1);
+            assert_eq!(storage::get_pool_config(&e).status, 1);
+
+            set_ledger(&e, 101);
+            pool.store_cached_reserves(&e);
+            assert_eq!(
+                storage::get_util_breaker_state(&e),
+                UtilBreakerState {
+                    count: 2,
+                    ledger: 101,
+                    tripped: true,
+                    prev_status: 1,
+                }
+            );
+            assert_eq!(storage::get_pool_config(&e).status, 3);
+        });
+    }
+
+    #[test]
+    fn test_check_util_breaker_recovers() {
+        let e = Env::default();
+        e.mock_all_auths();
+        set_ledger(&e, 100);
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.last_time = 12345;
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config(&e, Address::generate(&e), 3));
+            storage::set_util_breaker(
+                &e,
+                &Some(UtilBreaker {
+                    reserve_util: 0_8000000,
+                    pool_util: 0,
+                    accruals: 2,
+                }),
+            );
+            storage::set_util_breaker_state(
+                &e,
+                &UtilBreakerState {
+                    count: 5,
+                    ledger: 99,
+                    tripped: true,
+                    prev_status: 0,
+                },
+            );
+
+            let mut pool = Pool::load(&e);
+            let reserve = pool.load_reserve(&e, &underlying, true);
+            pool.cache_reserve(reserve);
+            pool.store_cached_reserves(&e);
+            let state = storage::get_util_breaker_state(&e);
+            assert_eq!(state.count, 0);
+            assert!(!state.tripped);
+            assert_eq!(storage::get_pool_config(&e).status, 0);
+        });
+    }
+
+    #[test]
+    fn test_check_util_breaker_pool_util() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.cost_estimate().budget().reset_unlimited();
+        set_ledger(&e, 100);
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        // reserve 0 is 75% utilized and reserve 1 has no liabilities
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.last_time = 12345;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config_0, &reserve_data_0);
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_config_1.index = 1;
+        reserve_data_1.d_supply = 0;
+        reserve_data_1.last_time = 12345;
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config_1, &reserve_data_1);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 1_0000000]);
+
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config(&e, oracle, 0));
+            // the pool is 37.5% utilized in aggregate
+            execute_set_util_breaker(
+                &e,
+                &Some(UtilBreaker {
+                    reserve_util: 0,
+                    pool_util: 0_3500000,
+                    accruals: 1,
+                }),
+            );
+
+            let mut pool = Pool::load(&e);
+            let reserve = pool.load_reserve(&e, &underlying_1, true);
+            pool.cache_reserve(reserve);
+            pool.store_cached_reserves(&e);
+            assert!(storage::get_util_breaker_state(&e).tripped);
+            assert_eq!(storage::get_pool_config(&e).status, 3);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_set_util_breaker_no_thresholds() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_util_breaker(
+                &e,
+                &Some(UtilBreaker {
+                    reserve_util: 0,
+                    pool_util: 0,
+                    accruals: 2,
+                }),
+            );
+        });
+    }
+}
@@ -1,9 +1,13 @@
+use sep_40_oracle::Asset;
 use soroban_sdk::{
-    contracttype, map, panic_with_error, unwrap::UnwrapOptimized, vec, Address, Env, IntoVal, Map,
-    String, Symbol, TryFromVal, Val, Vec,
+    contracttype, map, panic_with_error, unwrap::UnwrapOptimized, vec, Address, Bytes, BytesN, Env,
+    IntoVal, Map, String, Symbol, TryFromVal, Val, Vec,
 };
 
-use crate::{auctions::AuctionData, constants::MAX_RESERVES, pool::Positions, PoolError};
+use crate::{
+    auctions::AuctionData, constants::MAX_RESERVES, emissions::ReserveEmissionMetadata,
+    pool::Positions, PoolError, PoolErrorExt,
+};
 
 /********** Ledger Thresholds **********/
 
@@ -24,11 +28,47 @@ const LEDGER_BUMP_USER: u32 = LEDGER_THRESHOLD_USER + 20 * ONE_DAY_LEDGERS; // ~
 #[derive(Clone)]
 #[contracttype]
 pub struct PoolConfig {
-    pub oracle: Address,      // the contract address of the oracle
-    pub min_collateral: i128, // the minimum amount of collateral required to open a liability position
+    pub oracle: Address,        // the contract address of the oracle
+    pub min_collateral: i128, // the minimum amount of collateral, in the oracle's base asset, required to open a liability position
     pub bstop_rate: u32, // the rate the backstop takes on accrued debt interest, expressed in 7 decimals
     pub status: u32,     // the status of the pool
     pub max_positions: u32, // the maximum number of effective positions a single user can hold, and the max assets an auction can contain
+    pub min_debt: i128, // the minimum value of liabilities a user must hold after borrowing, in the oracle's base asset
+    pub base: Option<PoolBase>, // the oracle's base asset, or None if the oracle prices assets in USD
+}
+
+/// The base asset of a pool whose oracle does not price assets in USD
+#[derive(Clone, PartialEq)]
+#[contracttype]
+pub struct PoolBase {
+    pub asset: Asset,       // the oracle's base asset
+    pub usd_asset: Address, // an asset the oracle prices that is worth 1 USD, used to value the base asset in USD
+}
+
+/// The pool's auction config
+#[derive(Clone, Default)]
+#[contracttype]
+pub struct AuctionConfig {
+    pub start_delay: u32, // the number of blocks after creation before an auction can be filled
+    pub soft_liq_max_value: i128, // the maximum liability value, in the oracle's base asset, that can be soft liquidated
+    pub soft_liq_bonus: u32, // the bonus given to soft liquidators on the seized collateral, expressed in 7 decimals
+    pub interest_interval: u32, // the minimum number of blocks between interest auction creations
+    pub interest_min_value: i128, // the minimum backstop credit value, in the oracle's base asset, required to create an interest auction
+    pub bad_debt_premium_cap: u32, // the maximum premium of the lot value over the bid value paid to bad debt auction fillers, expressed in 7 decimals. 0 disables the cap
+    pub bad_debt_bid_premium: u32, // the premium over the bid that bad debt auctions start at, decaying to 0 as the lot scales in, expressed in 7 decimals
+    pub liq_curve: u32, // the curve user liquidation auctions scale along (0 = linear, 1 = exponential, 2 = stepwise)
+    pub bad_debt_curve: u32, // the curve bad debt auctions scale along
+    pub interest_curve: u32, // the curve interest auctions scale along
+    pub min_fill_pct: u32, // the minimum percent of an auction a partial fill must take, as a number (i.e. 5 => 5%). 0 disables the minimum
+    pub liq_target_hf: u32, // the health factor partial user liquidation fills are capped at, expressed in 7 decimals. 0 disables the cap
+}
+
+/// The ledger and time reserves were last accrued to while the accrual guard is enabled
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct AccrualClock {
+    pub sequence: u32,  // the ledger sequence of the accrual
+    pub timestamp: u64, // the time reserves were accrued to, which may trail the ledger timestamp
 }
 
 /// The pool's emission config
@@ -40,7 +80,7 @@ pub struct PoolEmissionConfig {
 }
 
 /// The configuration information about a reserve asset
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub struct ReserveConfig {
     pub index: u32,       // the index of the reserve in the list
@@ -58,6 +98,93 @@ pub struct ReserveConfig {
     pub enabled: bool,    // the enabled flag of the reserve
 }
 
+/// The actions paused for a reserve outside of its config
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[contracttype]
+pub struct ReservePause {
+    pub supply: bool, // if supplying to the reserve, as collateral or otherwise, is paused
+    pub borrow: bool, // if borrowing from the reserve is paused
+}
+
+/// The fee charged on collateral withdrawals while the backstop is stressed, credited to the backstop
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[contracttype]
+pub struct WithdrawFee {
+    pub on_ice: u32, // the fee charged while the pool is on-ice, in 7 decimals
+    pub frozen: u32, // the fee charged while the pool is frozen, in 7 decimals
+}
+
+/// An in progress decrease of a reserve's collateral factor, which is lowered linearly from
+/// `from_c_factor` to the reserve config's collateral factor between the start and end ledgers
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct CFactorRamp {
+    pub from_c_factor: u32, // the collateral factor at the start of the ramp
+    pub start_ledger: u32,  // the ledger the ramp started at
+    pub end_ledger: u32,    // the ledger the ramp reaches the reserve config's collateral factor at
+}
+
+/// The sanity band for a reserve's oracle price
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct PriceBand {
+    pub min_price: i128, // the minimum price accepted from the oracle, in the oracle's decimals
+    pub max_price: i128, // the maximum price accepted from the oracle, in the oracle's decimals
+}
+
+/// A step in the pool's backstop take rate schedule
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct BstopRateStep {
+    pub start_time: u64, // the timestamp the rate takes effect
+    pub rate: u32, // the rate the backstop takes on accrued debt interest, expressed in 7 decimals
+}
+
+/// The pool's health factor based interest rate surcharge
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct RiskPremium {
+    pub hf_threshold: i128, // the health factor below which a borrower is charged the premium, expressed in 7 decimals
+    pub multiplier: u32, // the additional interest charged as a multiple of the interest accrued on the borrower's liabilities, expressed in 7 decimals
+}
+
+/// The pool's utilization circuit breaker, which puts the pool on-ice once utilization stays above
+/// a critical threshold
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct UtilBreaker {
+    pub reserve_util: u32, // the critical utilization of any single reserve, expressed in 7 decimals, or 0 to not check reserves
+    pub pool_util: u32, // the critical aggregate utilization of the pool, expressed in 7 decimals, or 0 to not check the pool
+    pub accruals: u32, // the number of consecutive accruals utilization must be critical for to trip the breaker
+}
+
+/// The state of the pool's utilization circuit breaker
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[contracttype]
+pub struct UtilBreakerState {
+    pub count: u32, // the number of consecutive accruals utilization has been critical for
+    pub ledger: u32, // the ledger of the last accrual checked
+    pub tripped: bool, // if the breaker has put the pool on-ice
+    pub prev_status: u32, // the status of the pool before the breaker was tripped
+}
+
+/// The cumulative fills of an auction type by a filler
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[contracttype]
+pub struct FillStats {
+    pub count: u32,  // the number of fills
+    pub value: i128, // the cumulative value of the pool's reserve assets filled, in the oracle's base asset
+}
+
+/// The cumulative auction fills of a filler, by auction type
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[contracttype]
+pub struct FillerStats {
+    pub liquidation: FillStats, // valued by the liabilities repaid
+    pub bad_debt: FillStats,    // valued by the bad debt assumed
+    pub interest: FillStats,    // valued by the interest received
+}
+
 #[derive(Clone)]
 #[contracttype]
 pub struct QueuedReserveInit {
@@ -65,6 +192,87 @@ pub struct QueuedReserveInit {
     pub unlock_time: u64,
 }
 
+/// A permissionless proposal to add a reserve, backed by a BLND bond
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ReserveProposal {
+    pub proposer: Address,     // the address that posted the bond
+    pub config: ReserveConfig, // the proposed reserve config
+    pub bond: i128,            // the amount of BLND bonded
+    pub expiration: u64,       // the time the proposal must be approved or rejected by
+}
+
+/// An admin action that must be queued behind a timelock once the pool has left setup
+#[derive(Clone)]
+#[contracttype]
+pub enum AdminAction {
+    /// Update the pool - (backstop_take_rate, max_positions, min_collateral, min_debt)
+    UpdatePool(u32, u32, i128, i128),
+    /// Set the emission configuration - (res_emission_metadata)
+    SetEmissionsConfig(Vec<ReserveEmissionMetadata>),
+    /// Adjust the emission share of a single reserve token - (res_token_id, new_share)
+    AdjustEmissionShare(u32, u64),
+    /// Set the emission decay of a single reserve token - (res_token_id, factor)
+    SetEmissionDecay(u32, u32),
+    /// Set the oracle asset used to price a reserve - (asset, oracle_asset)
+    SetOracleAsset(Address, Asset),
+    /// Remove the oracle asset for a reserve, pricing it by its own address - (asset)
+    RemoveOracleAsset(Address),
+    /// Set the oracle of the pool - (oracle)
+    SetOracle(Address),
+    /// Set the base asset of the pool's oracle - (base)
+    SetBase(PoolBase),
+    /// Remove the base asset of the pool's oracle, valuing its prices in USD
+    RemoveBase,
+    /// Transfer the pool's balance of a token that is not held for users - (asset, to)
+    Rescue(Address, Address),
+    /// Delegate a reserve's borrow rate to a rate strategy - (asset, strategy)
+    SetRateStrategy(Address, Address),
+    /// Set the price band for a reserve - (asset, band)
+    SetPriceBand(Address, PriceBand),
+    /// Remove the price band for a reserve - (asset)
+    RemovePriceBand(Address),
+    /// Set the max annual borrow rate for a reserve - (asset, max_apr)
+    SetMaxApr(Address, u32),
+    /// Remove the max annual borrow rate for a reserve - (asset)
+    RemoveMaxApr(Address),
+    /// Set the stable rate borrowing config of a reserve - (asset, config)
+    SetStableRate(Address, StableRateConfig),
+    /// Set the pool's risk premium - (risk_premium)
+    SetRiskPremium(RiskPremium),
+    /// Set the pool's liquidation protection insurance - (config)
+    SetInsuranceConfig(InsuranceConfig),
+    /// Disable the pool's liquidation protection insurance
+    RemoveInsuranceConfig,
+    /// Set how interest is charged while the pool is frozen - (mode)
+    SetFrozenFees(u32),
+    /// Set the fee charged on collateral withdrawals while the pool is on-ice or frozen - (fee)
+    SetWithdrawFee(WithdrawFee),
+    /// Set the pool's utilization circuit breaker - (util_breaker)
+    SetUtilBreaker(UtilBreaker),
+    /// Remove the pool's utilization circuit breaker
+    RemoveUtilBreaker,
+    /// Set the converter claimed emissions can be routed through - (converter)
+    SetClaimConverter(Address),
+    /// Set the backstop take rate schedule - (schedule)
+    SetBstopRateSchedule(Vec<BstopRateStep>),
+    /// Set the auction config - (config)
+    SetAuctionConfig(AuctionConfig),
+    /// Set the maximum number of seconds reserves can accrue interest for per ledger - (max_ledger_time)
+    SetMaxLedgerTime(u32),
+    /// Set the maximum number of seconds a reserve can accrue interest for in a single accrual - (max_accrual_period)
+    SetMaxAccrualPeriod(u32),
+    /// Whitelist a quote asset with its swap adapter - (quote_asset, adapter)
+    SetQuoteAdapter(Address, Address),
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct QueuedAdminAction {
+    pub action: AdminAction,
+    pub unlock_time: u64,
+}
+
 /// The data for a reserve asset
 #[derive(Clone, Debug)]
 #[contracttype]
@@ -78,6 +286,33 @@ pub struct ReserveData {
     pub last_time: u64, // the last block the data was updated
 }
 
+/// The digits of a reserve's rates beyond 12 decimals, so the rates are tracked with 18 decimals.
+/// The bRate with 18 decimals is `b_rate * 1e6 + b_rate_ext`, and the dRate with 18 decimals is
+/// `d_rate * 1e6 - d_rate_ext`, as the 12 decimal bRate rounds down and the 12 decimal dRate rounds up.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[contracttype]
+pub struct ReserveRateExt {
+    pub b_rate_ext: i128, // the amount the bRate exceeds its 12 decimal value by, with 18 decimals
+    pub d_rate_ext: i128, // the amount the dRate is below its 12 decimal value by, with 18 decimals
+}
+
+/// The lifetime interest of a reserve, in the underlying token's decimals
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[contracttype]
+pub struct ReserveStats {
+    pub interest_paid: i128,     // the interest paid by borrowers
+    pub supplier_interest: i128, // the interest accrued to suppliers, net of the backstop's take
+}
+
+/// The unrecovered losses from bad debt in a reserve, in the underlying token's decimals
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[contracttype]
+pub struct ReserveDeficit {
+    pub backstop: i128, // the bad debt taken on by the backstop that has not been recovered
+    pub suppliers: i128, // the bad debt defaulted onto suppliers that has not been recovered
+    pub recovered: i128, // the bad debt recovered over the reserve's lifetime
+}
+
 /// The emission data for the reserve b or d token
 #[derive(Clone)]
 #[contracttype]
@@ -88,6 +323,14 @@ pub struct ReserveEmissionData {
     pub last_time: u64,
 }
 
+/// The emission decay schedule of a reserve b or d token
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct EmissionDecay {
+    pub factor: u32, // the fraction of the emission share kept each epoch, expressed in 7 decimals
+    pub last_time: u64, // the last time the emission share was decayed
+}
+
 /// The user emission data for the reserve b or d token
 #[derive(Clone)]
 #[contracttype]
@@ -96,6 +339,106 @@ pub struct UserEmissionData {
     pub accrued: i128,
 }
 
+/// A withdrawal queued until the reserve has enough liquidity to process it
+#[derive(Clone)]
+#[contracttype]
+pub struct QueuedWithdrawal {
+    pub b_tokens: i128, // the amount of bTokens queued to be withdrawn
+    pub ledger: u32,    // the ledger sequence the withdrawal was first queued at
+    pub seq: i128, // the amount of bTokens queued to the reserve ahead of this withdrawal, over the queue's lifetime
+}
+
+/// The lifetime totals of a reserve's withdrawal queue. Queued withdrawals are claimed in order,
+/// and a queued withdrawal is first in line once `removed` reaches its `seq`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[contracttype]
+pub struct WithdrawQueue {
+    pub queued: i128,  // the total bTokens added to the queue
+    pub removed: i128, // the total bTokens removed from the queue, by claims or otherwise
+}
+
+/// The stable rate borrowing configuration of a reserve
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct StableRateConfig {
+    pub premium: u32, // the rate added to the reserve's borrow rate when a stable rate is set, expressed in 7 decimals
+    pub rebalance_util: u32, // the utilization at or above which stable rates under the borrow rate can be rebalanced, expressed in 7 decimals
+}
+
+/// The stable rate a user borrows from a reserve at
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct StableLoan {
+    pub rate: u32, // the annual rate the stable liabilities accrue at, expressed in 7 decimals
+    pub d_rate: i128, // the reserve's dRate when the stable liabilities were last accrued
+    pub last_time: u64, // the timestamp the stable liabilities were last accrued at
+}
+
+/// A line of credit a lender extends to a reserve, drawn to honor withdrawals during utilization spikes
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct CreditLine {
+    pub lender: Address, // the address lending to the reserve, which must approve the pool to transfer its tokens
+    pub cap: i128,       // the maximum amount of underlying that can be drawn from the line
+    pub b_tokens: i128, // the bTokens the outstanding draws are held as, which accrue the reserve's supply interest
+}
+
+/// A snapshot of a user's positions and the reserve rates at the end of a submit
+#[derive(Clone)]
+#[contracttype]
+pub struct PositionCheckpoint {
+    pub ledger: u32,             // the ledger sequence the checkpoint was taken at
+    pub timestamp: u64,          // the ledger timestamp the checkpoint was taken at
+    pub positions: Positions,    // the user's b/dToken balances
+    pub b_rates: Map<u32, i128>, // map of reserve index to bToken rate for the user's supply and collateral
+    pub d_rates: Map<u32, i128>, // map of reserve index to dToken rate for the user's liabilities
+}
+
+/// The rebate paid to registered keepers for performing pool maintenance
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct KeeperConfig {
+    pub token: Address, // the token rebates are paid in
+    pub rebate: i128,   // the amount of tokens paid for each maintenance task performed
+}
+
+/// The pool's opt-in liquidation protection, where insured users pay a premium on their liabilities
+/// to the backstop in exchange for only being liquidatable further below a health factor of 1
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct InsuranceConfig {
+    pub premium: u32, // the annual premium charged on an insured user's liabilities, expressed in 7 decimals
+    pub hf_buffer: u32, // the amount an insured user's health factor must fall below 1 before they can be liquidated, expressed in 7 decimals
+}
+
+/// The pool's commit-reveal fill option, where large user liquidation auctions can only be filled
+/// by revealing a fill committed to in an earlier block
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct FillCommitConfig {
+    pub min_value: i128, // the minimum liability value, in the oracle's base asset, of a user liquidation auction that must be filled with a commitment
+    pub window: u32,     // the number of blocks after a commitment's block it can be revealed in
+}
+
+/// A filler's commitment to fill a user liquidation auction
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct FillCommitment {
+    pub hash: BytesN<32>, // the sha256 hash of the XDR encoded (user, percent_filled, salt) of the fill
+    pub block: u32,       // the block the commitment was made in
+}
+
+/// Coarse counters of the pool's activity over a day
+#[derive(Clone)]
+#[contracttype]
+pub struct DailyStats {
+    pub actors: u32,   // the number of unique addresses that submitted requests
+    pub supplies: u32, // the number of supply and supply collateral requests
+    pub borrows: u32,  // the number of borrow requests, including flash loans
+    pub supply_volume: Map<Address, i128>, // map of underlying asset to the tokens supplied
+    pub borrow_volume: Map<Address, i128>, // map of underlying asset to the tokens borrowed
+}
+
 /********** Storage Key Types **********/
 
 const ADMIN_KEY: &str = "Admin";
@@ -104,6 +447,40 @@ const NAME_KEY: &str = "Name";
 const BACKSTOP_KEY: &str = "Backstop";
 const BLND_TOKEN_KEY: &str = "BLNDTkn";
 const POOL_CONFIG_KEY: &str = "Config";
+const AUCTION_CONFIG_KEY: &str = "AuctConfig";
+const LAST_INTEREST_AUCTION_KEY: &str = "LastIntAuct";
+const CHECKPOINT_RETENTION_KEY: &str = "CkptRetain";
+const RISK_PREMIUM_KEY: &str = "RiskPrem";
+const BSTOP_RATE_SCHEDULE_KEY: &str = "BstopSched";
+const MAX_LEDGER_TIME_KEY: &str = "MaxLdgTime";
+const MAX_ACCRUAL_PERIOD_KEY: &str = "MaxAccPer";
+const ACCRUAL_CLOCK_KEY: &str = "AccrClock";
+const AUTO_GULP_KEY: &str = "AutoGulp";
+const INTEREST_DEPOSIT_KEY: &str = "IntDeposit";
+const AUCTIONS_PAUSED_KEY: &str = "AuctPaused";
+const BORROWER_INDEX_KEY: &str = "BorrowIdx";
+const BSTOP_POS_EXEMPT_KEY: &str = "BstopPosEx";
+const ANALYTICS_WINDOW_KEY: &str = "AnlytWin";
+const KEEPER_CONFIG_KEY: &str = "KeeperCfg";
+const KEEPER_POT_KEY: &str = "KeeperPot";
+const BORROWER_COUNT_KEY: &str = "BorrowCnt";
+const ADMIN_ACTION_ID_KEY: &str = "AdminActId";
+const C_FACTOR_RAMP_KEY: &str = "CFactRamp";
+const UTIL_BREAKER_KEY: &str = "UtilBrk";
+const UTIL_BREAKER_STATE_KEY: &str = "UtilBrkSt";
+const FILLER_COUNT_KEY: &str = "FillerCnt";
+const FREEZE_LEDGERS_KEY: &str = "FrzLdgrs";
+const FREEZE_EXPIRY_KEY: &str = "FrzExp";
+const INSURANCE_CONFIG_KEY: &str = "InsureCfg";
+const FILL_COMMIT_CONFIG_KEY: &str = "FillCmtCfg";
+const BORROW_BUFFER_KEY: &str = "BorrowBuf";
+const FROZEN_FEES_KEY: &str = "FrzFees";
+const WITHDRAW_FEE_KEY: &str = "WdrawFee";
+const BSTOP_PRICE_USDC_KEY: &str = "BstopPxUsdc";
+const CLAIM_CONVERTER_KEY: &str = "ClaimConv";
+const LISTING_BOND_KEY: &str = "ListBond";
+const LISTING_COUNCIL_KEY: &str = "ListCncl";
+const LAST_GULP_KEY: &str = "LastGulp";
 const RES_LIST_KEY: &str = "ResList";
 const POOL_EMIS_KEY: &str = "PoolEmis";
 
@@ -114,6 +491,14 @@ pub struct UserReserveKey {
     reserve_id: u32,
 }
 
+#[derive(Clone)]
+#[contracttype]
+pub struct BorrowAllowanceKey {
+    delegator: Address, // the Address whose collateral backs the borrow
+    delegatee: Address, // the Address allowed to borrow
+    asset: Address,     // the reserve asset that can be borrowed
+}
+
 #[derive(Clone)]
 #[contracttype]
 pub struct AuctionKey {
@@ -130,14 +515,98 @@ pub enum PoolDataKey {
     ResInit(Address),
     // A map of underlying asset's contract address to reserve data
     ResData(Address),
+    // A map of underlying asset's contract address to the reserve's lifetime interest
+    ResStats(Address),
+    // A map of underlying asset's contract address to the reserve's unswept rounding dust
+    ResDust(Address),
     // The reserve's emission data
     EmisData(u32),
-    // Map of positions in the pool for a user
+    // Map of positions in the pool for a user, stored before positions were packed
     Positions(Address),
     // The emission information for a reserve asset for a user
     UserEmis(UserReserveKey),
     // The auction's data
     Auction(AuctionKey),
+    // The liquidation protection hook registered by a user
+    LiqHook(Address),
+    // A map of underlying asset's contract address to price band
+    PriceBand(Address),
+    // The amount a delegatee is allowed to borrow against a delegator's positions
+    BorrowAllow(BorrowAllowanceKey),
+    // The withdrawal queued by a user for a reserve
+    WithdrawQueue(UserReserveKey),
+    // The most recent position checkpoints for a user
+    PosHistory(Address),
+    // A map of whitelisted quote asset's contract address to its swap adapter
+    QuoteAdapter(Address),
+    // The liability dRates recorded when a user was last found below the risk premium threshold
+    RiskMark(Address),
+    // A map of underlying asset's contract address to the asset used to price it on the oracle
+    OracleAsset(Address),
+    // If a user allows others to supply to their positions without their authorization
+    SupplyOpt(Address),
+    // The minimum health factor a user allows their own requests to leave them at
+    MinHf(Address),
+    // If a user's emissions are claimed automatically when they submit requests
+    AutoClaim(Address),
+    // If a user's requests are reordered by the pool before they are executed
+    PlanOpt(Address),
+    // A map of underlying asset's contract address to the actions paused for the reserve
+    ResPause(Address),
+    // The borrower stored at a slot of the borrower index
+    Borrower(u32),
+    // The slot of the borrower index a borrower is stored at
+    BorrowerSlot(Address),
+    // The undistributed emissions carried over to the next emission epoch of a reserve token
+    EmisCarry(u32),
+    // The emission decay schedule of a reserve token
+    EmisDecay(u32),
+    // A queued admin action by id
+    AdminAction(u32),
+    // A map of underlying asset's contract address to its in progress collateral factor ramp
+    CFactorRamp(Address),
+    // The filler stored at a slot of the filler index
+    Filler(u32),
+    // The cumulative auction fills of a filler
+    FillerStats(Address),
+    // If the reserve at an index has been retired
+    ResRetired(u32),
+    // The packed positions in the pool for a user
+    PackedPos(Address),
+    // Marks that a user has positions stored, to detect positions that were archived
+    PosMarker(Address),
+    // A map of underlying asset's contract address to the credit line extended to the reserve
+    CreditLine(Address),
+    // A map of underlying asset's contract address to the reserve's stable rate borrowing config
+    StableRate(Address),
+    // The stable rate a user borrows from a reserve at
+    StableLoan(UserReserveKey),
+    // A map of underlying asset's contract address to the reserve's unrecovered bad debt
+    ResDeficit(Address),
+    // If an address is blocked from depositing into the pool
+    Blocked(Address),
+    // A map of underlying asset's contract address to the pending proposal to add it as a reserve
+    ResProposal(Address),
+    // The activity counters for a day, keyed by the number of days since the unix epoch
+    DailyStats(u64),
+    // The last day an address was counted as an active user
+    DailyActor(Address),
+    // If an address is registered as a keeper
+    Keeper(Address),
+    // A map of underlying asset's contract address to the rate strategy computing its borrow rate
+    RateStrategy(Address),
+    // A map of underlying asset's contract address to the digits of the reserve's rates beyond 12 decimals
+    ResRateExt(Address),
+    // A map of underlying asset's contract address to the max annual borrow rate of the reserve
+    MaxApr(Address),
+    // The timestamp the insurance premium was last charged to an insured user
+    Insured(Address),
+    // The fill a filler has committed to
+    FillCommit(Address),
+    // A map of a user's address to the portion of their liabilities borrowed at a stable rate
+    StableLiab(Address),
+    // The lifetime totals of the withdrawal queue of the reserve at an index
+    ResQueue(u32),
 }
 
 /********** Storage **********/
@@ -171,17 +640,61 @@ fn get_persistent_default<K: IntoVal<Env, Val>, V: TryFromVal<Env, Val>, F: FnOn
 
 /// Fetch the user's positions or return an empty Positions struct
 ///
+/// Positions stored before positions were packed are read from their legacy entry.
+///
 /// ### Arguments
 /// * `user` - The address of the user
+///
+/// ### Panics
+/// If the user's positions were archived, and must be restored before they can be read
 pub fn get_user_positions(e: &Env, user: &Address) -> Positions {
-    let key = PoolDataKey::Positions(user.clone());
-    get_persistent_default(
-        e,
-        &key,
-        || Positions::env_default(e),
-        LEDGER_THRESHOLD_USER,
-        LEDGER_BUMP_USER,
-    )
+    get_user_positions_entry(e, user).0
+}
+
+/// Fetch the user's positions or return an empty Positions struct, and whether they were read
+/// from the legacy entry. Legacy positions are not migrated on read, so they must be removed with
+/// `remove_legacy_user_positions` once the positions are written.
+///
+/// ### Arguments
+/// * `user` - The address of the user
+///
+/// ### Panics
+/// If the user's positions were archived, and must be restored before they can be read
+pub fn get_user_positions_entry(e: &Env, user: &Address) -> (Positions, bool) {
+    let key = PoolDataKey::PackedPos(user.clone());
+    if let Some(packed) = e.storage().persistent().get::<PoolDataKey, Bytes>(&key) {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+        return (Positions::unpack(e, &packed), false);
+    }
+    let legacy = e
+        .storage()
+        .persistent()
+        .get::<PoolDataKey, Positions>(&PoolDataKey::Positions(user.clone()));
+    match legacy {
+        Some(positions) => (positions, true),
+        None => {
+            // positions that were archived can't read as empty, or archived debt would be forgiven
+            if e.storage()
+                .persistent()
+                .has(&PoolDataKey::PosMarker(user.clone()))
+            {
+                panic_with_error!(e, PoolErrorExt::PositionsArchived);
+            }
+            (Positions::env_default(e), false)
+        }
+    }
+}
+
+/// Remove the user's legacy positions entry, once their positions have been written packed
+///
+/// ### Arguments
+/// * `user` - The address of the user
+pub fn remove_legacy_user_positions(e: &Env, user: &Address) {
+    e.storage()
+        .persistent()
+        .remove(&PoolDataKey::Positions(user.clone()));
 }
 
 /// Set the user's positions
@@ -190,13 +703,253 @@ pub fn get_user_positions(e: &Env, user: &Address) -> Positions {
 /// * `user` - The address of the user
 /// * `positions` - The new positions for the user
 pub fn set_user_positions(e: &Env, user: &Address, positions: &Positions) {
-    let key = PoolDataKey::Positions(user.clone());
+    let key = PoolDataKey::PackedPos(user.clone());
     e.storage()
         .persistent()
-        .set::<PoolDataKey, Positions>(&key, positions);
+        .set::<PoolDataKey, Bytes>(&key, &positions.pack(e));
     e.storage()
         .persistent()
         .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+
+    // the marker outlives the positions, so positions that are archived are not read as empty
+    let marker_key = PoolDataKey::PosMarker(user.clone());
+    let is_empty = positions.liabilities.is_empty()
+        && positions.collateral.is_empty()
+        && positions.supply.is_empty();
+    if is_empty {
+        e.storage().persistent().remove(&marker_key);
+    } else {
+        e.storage()
+            .persistent()
+            .set::<PoolDataKey, bool>(&marker_key, &true);
+        let max_ttl = e.storage().max_ttl();
+        e.storage()
+            .persistent()
+            .extend_ttl(&marker_key, max_ttl - ONE_DAY_LEDGERS, max_ttl);
+    }
+}
+
+/// Fetch the user's liquidation protection hook, if one is registered
+///
+/// ### Arguments
+/// * `user` - The address of the user
+pub fn get_liquidation_hook(e: &Env, user: &Address) -> Option<Address> {
+    let key = PoolDataKey::LiqHook(user.clone());
+    get_persistent_default(e, &key, || None, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER)
+}
+
+/// Set or remove the user's liquidation protection hook
+///
+/// ### Arguments
+/// * `user` - The address of the user
+/// * `hook` - The address of the hook contract, or None to remove it
+pub fn set_liquidation_hook(e: &Env, user: &Address, hook: &Option<Address>) {
+    let key = PoolDataKey::LiqHook(user.clone());
+    match hook {
+        Some(hook) => {
+            e.storage()
+                .persistent()
+                .set::<PoolDataKey, Address>(&key, hook);
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+        }
+        None => e.storage().persistent().remove(&key),
+    }
+}
+
+/// Fetch if the user allows others to supply to their positions without their authorization
+///
+/// ### Arguments
+/// * `user` - The address of the user
+pub fn get_supply_on_behalf(e: &Env, user: &Address) -> bool {
+    let key = PoolDataKey::SupplyOpt(user.clone());
+    get_persistent_default(e, &key, || false, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER)
+}
+
+/// Set if the user allows others to supply to their positions without their authorization
+///
+/// ### Arguments
+/// * `user` - The address of the user
+/// * `allowed` - If supplying on behalf of the user is allowed
+pub fn set_supply_on_behalf(e: &Env, user: &Address, allowed: bool) {
+    let key = PoolDataKey::SupplyOpt(user.clone());
+    if allowed {
+        e.storage()
+            .persistent()
+            .set::<PoolDataKey, bool>(&key, &allowed);
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+    } else {
+        e.storage().persistent().remove(&key);
+    }
+}
+
+/// Fetch if the address is blocked from depositing into the pool
+///
+/// ### Arguments
+/// * `user` - The address
+pub fn get_blocked(e: &Env, user: &Address) -> bool {
+    let key = PoolDataKey::Blocked(user.clone());
+    get_persistent_default(e, &key, || false, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER)
+}
+
+/// Set if the address is blocked from depositing into the pool
+///
+/// ### Arguments
+/// * `user` - The address
+/// * `blocked` - If the address is blocked
+pub fn set_blocked(e: &Env, user: &Address, blocked: bool) {
+    let key = PoolDataKey::Blocked(user.clone());
+    if blocked {
+        e.storage()
+            .persistent()
+            .set::<PoolDataKey, bool>(&key, &blocked);
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+    } else {
+        e.storage().persistent().remove(&key);
+    }
+}
+
+/// Fetch if the user's emissions are claimed automatically when they submit requests
+///
+/// ### Arguments
+/// * `user` - The address of the user
+pub fn get_auto_claim(e: &Env, user: &Address) -> bool {
+    let key = PoolDataKey::AutoClaim(user.clone());
+    get_persistent_default(e, &key, || false, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER)
+}
+
+/// Set if the user's emissions are claimed automatically when they submit requests
+///
+/// ### Arguments
+/// * `user` - The address of the user
+/// * `auto_claim` - If emissions are claimed automatically
+pub fn set_auto_claim(e: &Env, user: &Address, auto_claim: bool) {
+    let key = PoolDataKey::AutoClaim(user.clone());
+    if auto_claim {
+        e.storage()
+            .persistent()
+            .set::<PoolDataKey, bool>(&key, &auto_claim);
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+    } else {
+        e.storage().persistent().remove(&key);
+    }
+}
+
+/// Fetch if the user's requests are reordered by the pool before they are executed
+///
+/// ### Arguments
+/// * `user` - The address of the user
+pub fn get_plan_requests(e: &Env, user: &Address) -> bool {
+    let key = PoolDataKey::PlanOpt(user.clone());
+    get_persistent_default(e, &key, || false, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER)
+}
+
+/// Set if the user's requests are reordered by the pool before they are executed
+///
+/// ### Arguments
+/// * `user` - The address of the user
+/// * `plan_requests` - If the user's requests are reordered
+pub fn set_plan_requests(e: &Env, user: &Address, plan_requests: bool) {
+    let key = PoolDataKey::PlanOpt(user.clone());
+    if plan_requests {
+        e.storage()
+            .persistent()
+            .set::<PoolDataKey, bool>(&key, &plan_requests);
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+    } else {
+        e.storage().persistent().remove(&key);
+    }
+}
+
+/// Fetch the minimum health factor a user allows their own requests to leave them at
+///
+/// Returns 0 (no limit) if one has not been set
+///
+/// ### Arguments
+/// * `user` - The address of the user
+pub fn get_user_min_hf(e: &Env, user: &Address) -> u32 {
+    let key = PoolDataKey::MinHf(user.clone());
+    get_persistent_default(e, &key, || 0, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER)
+}
+
+/// Set the minimum health factor a user allows their own requests to leave them at
+///
+/// ### Arguments
+/// * `user` - The address of the user
+/// * `min_hf` - The minimum health factor, or 0 to remove the limit
+pub fn set_user_min_hf(e: &Env, user: &Address, min_hf: u32) {
+    let key = PoolDataKey::MinHf(user.clone());
+    if min_hf != 0 {
+        e.storage()
+            .persistent()
+            .set::<PoolDataKey, u32>(&key, &min_hf);
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+    } else {
+        e.storage().persistent().remove(&key);
+    }
+}
+
+/// Fetch the amount of an asset a delegatee is allowed to borrow against the delegator's positions
+///
+/// ### Arguments
+/// * `delegator` - The address whose positions back the borrow
+/// * `delegatee` - The address allowed to borrow
+/// * `asset` - The reserve asset
+pub fn get_borrow_allowance(
+    e: &Env,
+    delegator: &Address,
+    delegatee: &Address,
+    asset: &Address,
+) -> i128 {
+    let key = PoolDataKey::BorrowAllow(BorrowAllowanceKey {
+        delegator: delegator.clone(),
+        delegatee: delegatee.clone(),
+        asset: asset.clone(),
+    });
+    get_persistent_default(e, &key, || 0, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER)
+}
+
+/// Set the amount of an asset a delegatee is allowed to borrow against the delegator's positions.
+/// Removes the allowance if the amount is zero.
+///
+/// ### Arguments
+/// * `delegator` - The address whose positions back the borrow
+/// * `delegatee` - The address allowed to borrow
+/// * `asset` - The reserve asset
+/// * `amount` - The new allowance
+pub fn set_borrow_allowance(
+    e: &Env,
+    delegator: &Address,
+    delegatee: &Address,
+    asset: &Address,
+    amount: i128,
+) {
+    let key = PoolDataKey::BorrowAllow(BorrowAllowanceKey {
+        delegator: delegator.clone(),
+        delegatee: delegatee.clone(),
+        asset: asset.clone(),
+    });
+    if amount == 0 {
+        e.storage().persistent().remove(&key);
+    } else {
+        e.storage()
+            .persistent()
+            .set::<PoolDataKey, i128>(&key, &amount);
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+    }
 }
 
 /********** Admin **********/
@@ -325,184 +1078,1585 @@ pub fn set_pool_config(e: &Env, config: &PoolConfig) {
         .set::<Symbol, PoolConfig>(&Symbol::new(e, POOL_CONFIG_KEY), config);
 }
 
-/********** Reserve Config (ResConfig) **********/
-
-/// Fetch the reserve data for an asset
+/// Fetch the auction configuration
 ///
-/// ### Arguments
-/// * `asset` - The contract address of the asset
+/// Returns the default config if one has not been set
+pub fn get_auction_config(e: &Env) -> AuctionConfig {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, AUCTION_CONFIG_KEY))
+        .unwrap_or_default()
+}
+
+/// Set the auction configuration
+///
+/// ### Arguments
+/// * `config` - The auction configuration
+pub fn set_auction_config(e: &Env, config: &AuctionConfig) {
+    e.storage()
+        .instance()
+        .set::<Symbol, AuctionConfig>(&Symbol::new(e, AUCTION_CONFIG_KEY), config);
+}
+
+/// Fetch the ledger sequence the last interest auction was created at
+pub fn get_last_interest_auction(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, LAST_INTEREST_AUCTION_KEY))
+        .unwrap_or(0)
+}
+
+/// Set the ledger sequence the last interest auction was created at
+///
+/// ### Arguments
+/// * `sequence` - The ledger sequence
+pub fn set_last_interest_auction(e: &Env, sequence: u32) {
+    e.storage()
+        .instance()
+        .set::<Symbol, u32>(&Symbol::new(e, LAST_INTEREST_AUCTION_KEY), &sequence);
+}
+
+/// Fetch the number of position checkpoints retained per user
+///
+/// Returns 0 (disabled) if one has not been set
+pub fn get_checkpoint_retention(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, CHECKPOINT_RETENTION_KEY))
+        .unwrap_or(0)
+}
+
+/// Set the number of position checkpoints retained per user
+///
+/// ### Arguments
+/// * `retention` - The number of checkpoints to retain
+pub fn set_checkpoint_retention(e: &Env, retention: u32) {
+    e.storage()
+        .instance()
+        .set::<Symbol, u32>(&Symbol::new(e, CHECKPOINT_RETENTION_KEY), &retention);
+}
+
+/// Fetch the maximum number of seconds reserves can accrue interest for per ledger
+///
+/// Returns 0 (disabled) if one has not been set
+pub fn get_max_ledger_time(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, MAX_LEDGER_TIME_KEY))
+        .unwrap_or(0)
+}
+
+/// Set the maximum number of seconds reserves can accrue interest for per ledger
+///
+/// ### Arguments
+/// * `max_ledger_time` - The maximum number of seconds per ledger, or 0 to disable the limit
+pub fn set_max_ledger_time(e: &Env, max_ledger_time: u32) {
+    e.storage()
+        .instance()
+        .set::<Symbol, u32>(&Symbol::new(e, MAX_LEDGER_TIME_KEY), &max_ledger_time);
+}
+
+/// Fetch the maximum number of seconds a reserve can accrue interest for in a single accrual
+///
+/// Returns 0 (disabled) if one has not been set
+pub fn get_max_accrual_period(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, MAX_ACCRUAL_PERIOD_KEY))
+        .unwrap_or(0)
+}
+
+/// Set the maximum number of seconds a reserve can accrue interest for in a single accrual
+///
+/// ### Arguments
+/// * `max_accrual_period` - The maximum number of seconds per accrual, or 0 to disable the cap
+pub fn set_max_accrual_period(e: &Env, max_accrual_period: u32) {
+    e.storage()
+        .instance()
+        .set::<Symbol, u32>(&Symbol::new(e, MAX_ACCRUAL_PERIOD_KEY), &max_accrual_period);
+}
+
+/// Fetch the number of ledgers significant collateral factor decreases are ramped over
+///
+/// Returns 0 (disabled) if one has not been set
+pub fn get_c_factor_ramp_ledgers(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, C_FACTOR_RAMP_KEY))
+        .unwrap_or(0)
+}
+
+/// Set the number of ledgers significant collateral factor decreases are ramped over
+///
+/// ### Arguments
+/// * `ledgers` - The number of ledgers to ramp over, or 0 to disable ramping
+pub fn set_c_factor_ramp_ledgers(e: &Env, ledgers: u32) {
+    if ledgers == 0 {
+        e.storage()
+            .instance()
+            .remove(&Symbol::new(e, C_FACTOR_RAMP_KEY));
+    } else {
+        e.storage()
+            .instance()
+            .set::<Symbol, u32>(&Symbol::new(e, C_FACTOR_RAMP_KEY), &ledgers);
+    }
+}
+
+/// Fetch the number of ledgers an admin freeze expires after, or 0 if freezes do not expire
+pub fn get_freeze_ledgers(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, FREEZE_LEDGERS_KEY))
+        .unwrap_or(0)
+}
+
+/// Set the number of ledgers an admin freeze expires after
+///
+/// ### Arguments
+/// * `ledgers` - The number of ledgers, or 0 if freezes do not expire
+pub fn set_freeze_ledgers(e: &Env, ledgers: u32) {
+    if ledgers == 0 {
+        e.storage()
+            .instance()
+            .remove(&Symbol::new(e, FREEZE_LEDGERS_KEY));
+    } else {
+        e.storage()
+            .instance()
+            .set::<Symbol, u32>(&Symbol::new(e, FREEZE_LEDGERS_KEY), &ledgers);
+    }
+}
+
+/// Fetch the ledger the current admin freeze expires at, if it expires
+pub fn get_freeze_expiry(e: &Env) -> Option<u32> {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, FREEZE_EXPIRY_KEY))
+}
+
+/// Set the ledger the current admin freeze expires at
+///
+/// ### Arguments
+/// * `expiry` - The ledger the freeze expires at, or None if the pool is not frozen or the freeze does not expire
+pub fn set_freeze_expiry(e: &Env, expiry: &Option<u32>) {
+    match expiry {
+        Some(expiry) => e
+            .storage()
+            .instance()
+            .set::<Symbol, u32>(&Symbol::new(e, FREEZE_EXPIRY_KEY), expiry),
+        None => e
+            .storage()
+            .instance()
+            .remove(&Symbol::new(e, FREEZE_EXPIRY_KEY)),
+    }
+}
+
+/// Fetch the health factor buffer new borrows must leave above the liquidation threshold, or 0 if disabled
+pub fn get_borrow_buffer(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, BORROW_BUFFER_KEY))
+        .unwrap_or(0)
+}
+
+/// Set the health factor buffer new borrows must leave above the liquidation threshold
+///
+/// ### Arguments
+/// * `buffer` - The buffer in 7 decimals, or 0 to disable
+pub fn set_borrow_buffer(e: &Env, buffer: u32) {
+    if buffer == 0 {
+        e.storage()
+            .instance()
+            .remove(&Symbol::new(e, BORROW_BUFFER_KEY));
+    } else {
+        e.storage()
+            .instance()
+            .set::<Symbol, u32>(&Symbol::new(e, BORROW_BUFFER_KEY), &buffer);
+    }
+}
+
+/// Fetch how interest is charged while the pool is frozen, or 0 if interest accrues as normal
+pub fn get_frozen_fees(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, FROZEN_FEES_KEY))
+        .unwrap_or(0)
+}
+
+/// Set how interest is charged while the pool is frozen
+///
+/// ### Arguments
+/// * `mode` - The frozen fee mode, or 0 if interest accrues as normal
+pub fn set_frozen_fees(e: &Env, mode: u32) {
+    if mode == 0 {
+        e.storage()
+            .instance()
+            .remove(&Symbol::new(e, FROZEN_FEES_KEY));
+    } else {
+        e.storage()
+            .instance()
+            .set::<Symbol, u32>(&Symbol::new(e, FROZEN_FEES_KEY), &mode);
+    }
+}
+
+/// Fetch the fee charged on collateral withdrawals while the backstop is stressed
+///
+/// Returns no fees if one has not been set
+pub fn get_withdraw_fee(e: &Env) -> WithdrawFee {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, WITHDRAW_FEE_KEY))
+        .unwrap_or_default()
+}
+
+/// Set the fee charged on collateral withdrawals while the backstop is stressed
+///
+/// ### Arguments
+/// * `fee` - The withdrawal fee
+pub fn set_withdraw_fee(e: &Env, fee: &WithdrawFee) {
+    if fee == &WithdrawFee::default() {
+        e.storage()
+            .instance()
+            .remove(&Symbol::new(e, WITHDRAW_FEE_KEY));
+    } else {
+        e.storage()
+            .instance()
+            .set::<Symbol, WithdrawFee>(&Symbol::new(e, WITHDRAW_FEE_KEY), fee);
+    }
+}
+
+/// Fetch the ledger and time reserves were last accrued to, if one has been recorded
+pub fn get_accrual_clock(e: &Env) -> Option<AccrualClock> {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, ACCRUAL_CLOCK_KEY))
+}
+
+/// Set the ledger and time reserves were last accrued to
+///
+/// ### Arguments
+/// * `clock` - The accrual clock
+pub fn set_accrual_clock(e: &Env, clock: &AccrualClock) {
+    e.storage()
+        .instance()
+        .set::<Symbol, AccrualClock>(&Symbol::new(e, ACCRUAL_CLOCK_KEY), clock);
+}
+
+/// Fetch if emissions are gulped automatically by the first submit of each emission cycle
+pub fn get_auto_gulp(e: &Env) -> bool {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, AUTO_GULP_KEY))
+        .unwrap_or(false)
+}
+
+/// Set if emissions are gulped automatically by the first submit of each emission cycle
+///
+/// ### Arguments
+/// * `auto_gulp` - If emissions should be gulped automatically
+pub fn set_auto_gulp(e: &Env, auto_gulp: bool) {
+    if auto_gulp {
+        e.storage()
+            .instance()
+            .set::<Symbol, bool>(&Symbol::new(e, AUTO_GULP_KEY), &true);
+    } else {
+        e.storage()
+            .instance()
+            .remove(&Symbol::new(e, AUTO_GULP_KEY));
+    }
+}
+
+/// Fetch if interest auction bids are deposited into the backstop on behalf of the pool
+pub fn get_interest_deposit(e: &Env) -> bool {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, INTEREST_DEPOSIT_KEY))
+        .unwrap_or(true)
+}
+
+/// Set if interest auction bids are deposited into the backstop on behalf of the pool
+///
+/// ### Arguments
+/// * `interest_deposit` - If interest auction bids should be deposited into the backstop
+pub fn set_interest_deposit(e: &Env, interest_deposit: bool) {
+    if interest_deposit {
+        e.storage()
+            .instance()
+            .remove(&Symbol::new(e, INTEREST_DEPOSIT_KEY));
+    } else {
+        e.storage()
+            .instance()
+            .set::<Symbol, bool>(&Symbol::new(e, INTEREST_DEPOSIT_KEY), &false);
+    }
+}
+
+/// Fetch if the creation of new auctions is paused
+pub fn get_auctions_paused(e: &Env) -> bool {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, AUCTIONS_PAUSED_KEY))
+        .unwrap_or(false)
+}
+
+/// Set if the creation of new auctions is paused
+///
+/// ### Arguments
+/// * `paused` - If the creation of new auctions should be paused
+pub fn set_auctions_paused(e: &Env, paused: bool) {
+    if paused {
+        e.storage()
+            .instance()
+            .set::<Symbol, bool>(&Symbol::new(e, AUCTIONS_PAUSED_KEY), &true);
+    } else {
+        e.storage()
+            .instance()
+            .remove(&Symbol::new(e, AUCTIONS_PAUSED_KEY));
+    }
+}
+
+/// Fetch if addresses with liabilities are recorded in the borrower index
+pub fn get_borrower_index(e: &Env) -> bool {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, BORROWER_INDEX_KEY))
+        .unwrap_or(false)
+}
+
+/// Set if addresses with liabilities are recorded in the borrower index
+///
+/// ### Arguments
+/// * `borrower_index` - If addresses with liabilities should be recorded in the borrower index
+pub fn set_borrower_index(e: &Env, borrower_index: bool) {
+    if borrower_index {
+        e.storage()
+            .instance()
+            .set::<Symbol, bool>(&Symbol::new(e, BORROWER_INDEX_KEY), &true);
+    } else {
+        e.storage()
+            .instance()
+            .remove(&Symbol::new(e, BORROWER_INDEX_KEY));
+    }
+}
+
+/// Fetch if the backstop is exempt from the pool's maximum positions
+pub fn get_backstop_positions_exempt(e: &Env) -> bool {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, BSTOP_POS_EXEMPT_KEY))
+        .unwrap_or(false)
+}
+
+/// Set if the backstop is exempt from the pool's maximum positions
+///
+/// ### Arguments
+/// * `exempt` - If the backstop should be exempt from the pool's maximum positions
+pub fn set_backstop_positions_exempt(e: &Env, exempt: bool) {
+    if exempt {
+        e.storage()
+            .instance()
+            .set::<Symbol, bool>(&Symbol::new(e, BSTOP_POS_EXEMPT_KEY), &true);
+    } else {
+        e.storage()
+            .instance()
+            .remove(&Symbol::new(e, BSTOP_POS_EXEMPT_KEY));
+    }
+}
+
+/// Fetch the number of borrowers in the borrower index
+pub fn get_borrower_count(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, BORROWER_COUNT_KEY))
+        .unwrap_or(0)
+}
+
+/// Set the number of borrowers in the borrower index
+///
+/// ### Arguments
+/// * `count` - The number of borrowers in the borrower index
+pub fn set_borrower_count(e: &Env, count: u32) {
+    e.storage()
+        .instance()
+        .set::<Symbol, u32>(&Symbol::new(e, BORROWER_COUNT_KEY), &count);
+}
+
+/// Fetch the number of fillers in the filler index
+pub fn get_filler_count(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, FILLER_COUNT_KEY))
+        .unwrap_or(0)
+}
+
+/// Set the number of fillers in the filler index
+///
+/// ### Arguments
+/// * `count` - The number of fillers in the filler index
+pub fn set_filler_count(e: &Env, count: u32) {
+    e.storage()
+        .instance()
+        .set::<Symbol, u32>(&Symbol::new(e, FILLER_COUNT_KEY), &count);
+}
+
+/// Fetch the timestamp emissions were last gulped at
+///
+/// Returns 0 if emissions have not been gulped
+pub fn get_last_gulp(e: &Env) -> u64 {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, LAST_GULP_KEY))
+        .unwrap_or(0)
+}
+
+/// Set the timestamp emissions were last gulped at
+///
+/// ### Arguments
+/// * `timestamp` - The timestamp of the gulp
+pub fn set_last_gulp(e: &Env, timestamp: u64) {
+    e.storage()
+        .instance()
+        .set::<Symbol, u64>(&Symbol::new(e, LAST_GULP_KEY), &timestamp);
+}
+
+/// Fetch the pool's backstop take rate schedule, ordered by start time
+///
+/// Returns an empty schedule if one has not been set
+pub fn get_bstop_rate_schedule(e: &Env) -> Vec<BstopRateStep> {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, BSTOP_RATE_SCHEDULE_KEY))
+        .unwrap_or(vec![e])
+}
+
+/// Set the pool's backstop take rate schedule
+///
+/// ### Arguments
+/// * `schedule` - The schedule, ordered by start time
+pub fn set_bstop_rate_schedule(e: &Env, schedule: &Vec<BstopRateStep>) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Vec<BstopRateStep>>(&Symbol::new(e, BSTOP_RATE_SCHEDULE_KEY), schedule);
+}
+
+/// Fetch the pool's risk premium, if one is set
+pub fn get_risk_premium(e: &Env) -> Option<RiskPremium> {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, RISK_PREMIUM_KEY))
+}
+
+/// Set or remove the pool's risk premium
+///
+/// ### Arguments
+/// * `risk_premium` - The risk premium, or None to disable it
+pub fn set_risk_premium(e: &Env, risk_premium: &Option<RiskPremium>) {
+    match risk_premium {
+        Some(risk_premium) => e
+            .storage()
+            .instance()
+            .set::<Symbol, RiskPremium>(&Symbol::new(e, RISK_PREMIUM_KEY), risk_premium),
+        None => e
+            .storage()
+            .instance()
+            .remove(&Symbol::new(e, RISK_PREMIUM_KEY)),
+    }
+}
+
+/// Fetch the pool's utilization circuit breaker
+pub fn get_util_breaker(e: &Env) -> Option<UtilBreaker> {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, UTIL_BREAKER_KEY))
+}
+
+/// Set or remove the pool's utilization circuit breaker
+///
+/// ### Arguments
+/// * `util_breaker` - The utilization circuit breaker, or None to disable it
+pub fn set_util_breaker(e: &Env, util_breaker: &Option<UtilBreaker>) {
+    match util_breaker {
+        Some(util_breaker) => e
+            .storage()
+            .instance()
+            .set::<Symbol, UtilBreaker>(&Symbol::new(e, UTIL_BREAKER_KEY), util_breaker),
+        None => e
+            .storage()
+            .instance()
+            .remove(&Symbol::new(e, UTIL_BREAKER_KEY)),
+    }
+}
+
+/// Fetch the state of the pool's utilization circuit breaker
+pub fn get_util_breaker_state(e: &Env) -> UtilBreakerState {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, UTIL_BREAKER_STATE_KEY))
+        .unwrap_or_default()
+}
+
+/// Set the state of the pool's utilization circuit breaker
+///
+/// ### Arguments
+/// * `state` - The state of the utilization circuit breaker
+pub fn set_util_breaker_state(e: &Env, state: &UtilBreakerState) {
+    e.storage()
+        .instance()
+        .set::<Symbol, UtilBreakerState>(&Symbol::new(e, UTIL_BREAKER_STATE_KEY), state);
+}
+
+/// Fetch the USDC token of the backstop token, used to check the backstop token's spot price against
+/// the pool's oracle
+pub fn get_bstop_price_usdc(e: &Env) -> Option<Address> {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, BSTOP_PRICE_USDC_KEY))
+}
+
+/// Set or remove the USDC token of the backstop token
+///
+/// ### Arguments
+/// * `usdc` - The USDC token of the backstop token, or None to disable the backstop token price check
+pub fn set_bstop_price_usdc(e: &Env, usdc: &Option<Address>) {
+    match usdc {
+        Some(usdc) => e
+            .storage()
+            .instance()
+            .set::<Symbol, Address>(&Symbol::new(e, BSTOP_PRICE_USDC_KEY), usdc),
+        None => e
+            .storage()
+            .instance()
+            .remove(&Symbol::new(e, BSTOP_PRICE_USDC_KEY)),
+    }
+}
+
+/// Fetch the converter claimed emissions can be routed through, if one is set
+pub fn get_claim_converter(e: &Env) -> Option<Address> {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, CLAIM_CONVERTER_KEY))
+}
+
+/// Set or remove the converter claimed emissions can be routed through
+///
+/// ### Arguments
+/// * `converter` - The converter, or None to disable converted claims
+pub fn set_claim_converter(e: &Env, converter: &Option<Address>) {
+    match converter {
+        Some(converter) => e
+            .storage()
+            .instance()
+            .set::<Symbol, Address>(&Symbol::new(e, CLAIM_CONVERTER_KEY), converter),
+        None => e
+            .storage()
+            .instance()
+            .remove(&Symbol::new(e, CLAIM_CONVERTER_KEY)),
+    }
+}
+
+/********** Reserve Config (ResConfig) **********/
+
+/// Fetch the reserve data for an asset
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+///
+/// ### Panics
+/// If the reserve does not exist
+pub fn get_res_config(e: &Env, asset: &Address) -> ReserveConfig {
+    let key = PoolDataKey::ResConfig(asset.clone());
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+    e.storage()
+        .persistent()
+        .get::<PoolDataKey, ReserveConfig>(&key)
+        .unwrap_optimized()
+}
+
+/// Set the reserve configuration for an asset
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+/// * `config` - The reserve configuration for the asset
+pub fn set_res_config(e: &Env, asset: &Address, config: &ReserveConfig) {
+    let key = PoolDataKey::ResConfig(asset.clone());
+    e.storage()
+        .persistent()
+        .set::<PoolDataKey, ReserveConfig>(&key, config);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/// Checks if a reserve exists for an asset
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+pub fn has_res(e: &Env, asset: &Address) -> bool {
+    let key = PoolDataKey::ResConfig(asset.clone());
+    e.storage().persistent().has(&key)
+}
+
+/// Fetch a queued reserve set
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+///
+/// ### Panics
+/// If the reserve set has not been queued
+pub fn get_queued_reserve_set(e: &Env, asset: &Address) -> QueuedReserveInit {
+    let key = PoolDataKey::ResInit(asset.clone());
+    e.storage()
+        .temporary()
+        .get::<PoolDataKey, QueuedReserveInit>(&key)
+        .unwrap_optimized()
+}
+
+/// Check if a reserve is actively queued
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+pub fn has_queued_reserve_set(e: &Env, asset: &Address) -> bool {
+    let key = PoolDataKey::ResInit(asset.clone());
+    e.storage().temporary().has(&key)
+}
+
+/// Set a new queued reserve set
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+/// * `config` - The reserve configuration for the asset
+pub fn set_queued_reserve_set(e: &Env, res_init: &QueuedReserveInit, asset: &Address) {
+    let key = PoolDataKey::ResInit(asset.clone());
+    e.storage()
+        .temporary()
+        .set::<PoolDataKey, QueuedReserveInit>(&key, res_init);
+    e.storage()
+        .temporary()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Delete a queued reserve set
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+///
+/// ### Panics
+/// If the reserve set has not been queued
+pub fn del_queued_reserve_set(e: &Env, asset: &Address) {
+    let key = PoolDataKey::ResInit(asset.clone());
+    e.storage().temporary().remove(&key);
+}
+
+/********** Queued Admin Actions **********/
+
+/// Fetch the id the next queued admin action will be stored under
+pub fn get_next_admin_action_id(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, ADMIN_ACTION_ID_KEY))
+        .unwrap_or(0)
+}
+
+/// Set the id the next queued admin action will be stored under
+///
+/// ### Arguments
+/// * `id` - The id of the next queued admin action
+pub fn set_next_admin_action_id(e: &Env, id: u32) {
+    e.storage()
+        .instance()
+        .set::<Symbol, u32>(&Symbol::new(e, ADMIN_ACTION_ID_KEY), &id);
+}
+
+/// Fetch a queued admin action, if it exists
+///
+/// ### Arguments
+/// * `id` - The id of the queued admin action
+pub fn get_queued_admin_action(e: &Env, id: u32) -> Option<QueuedAdminAction> {
+    let key = PoolDataKey::AdminAction(id);
+    e.storage()
+        .temporary()
+        .get::<PoolDataKey, QueuedAdminAction>(&key)
+}
+
+/// Set a queued admin action
+///
+/// ### Arguments
+/// * `id` - The id of the queued admin action
+/// * `queued_action` - The queued admin action
+pub fn set_queued_admin_action(e: &Env, id: u32, queued_action: &QueuedAdminAction) {
+    let key = PoolDataKey::AdminAction(id);
+    e.storage()
+        .temporary()
+        .set::<PoolDataKey, QueuedAdminAction>(&key, queued_action);
+    e.storage()
+        .temporary()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Delete a queued admin action
+///
+/// ### Arguments
+/// * `id` - The id of the queued admin action
+pub fn del_queued_admin_action(e: &Env, id: u32) {
+    let key = PoolDataKey::AdminAction(id);
+    e.storage().temporary().remove(&key);
+}
+
+/********** Reserve Data (ResData) **********/
+
+/// Fetch the reserve data for an asset
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+///
+/// ### Panics
+/// If the reserve does not exist
+pub fn get_res_data(e: &Env, asset: &Address) -> ReserveData {
+    let key = PoolDataKey::ResData(asset.clone());
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+    e.storage()
+        .persistent()
+        .get::<PoolDataKey, ReserveData>(&key)
+        .unwrap_optimized()
+}
+
+/// Set the reserve data for an asset
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+/// * `data` - The reserve data for the asset
+pub fn set_res_data(e: &Env, asset: &Address, data: &ReserveData) {
+    let key = PoolDataKey::ResData(asset.clone());
+    e.storage()
+        .persistent()
+        .set::<PoolDataKey, ReserveData>(&key, data);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/// Fetch the digits of a reserve's rates beyond 12 decimals. Reserves that have not been stored
+/// since rates were tracked with 18 decimals have none.
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+pub fn get_res_rate_ext(e: &Env, asset: &Address) -> ReserveRateExt {
+    let key = PoolDataKey::ResRateExt(asset.clone());
+    get_persistent_default(
+        e,
+        &key,
+        ReserveRateExt::default,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set the digits of a reserve's rates beyond 12 decimals
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+/// * `rate_ext` - The digits of the reserve's rates beyond 12 decimals
+pub fn set_res_rate_ext(e: &Env, asset: &Address, rate_ext: &ReserveRateExt) {
+    let key = PoolDataKey::ResRateExt(asset.clone());
+    if rate_ext == &ReserveRateExt::default() {
+        e.storage().persistent().remove(&key);
+    } else {
+        e.storage()
+            .persistent()
+            .set::<PoolDataKey, ReserveRateExt>(&key, rate_ext);
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+    }
+}
+
+/// Fetch the lifetime interest of a reserve
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+pub fn get_res_stats(e: &Env, asset: &Address) -> ReserveStats {
+    let key = PoolDataKey::ResStats(asset.clone());
+    get_persistent_default(
+        e,
+        &key,
+        ReserveStats::default,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set the lifetime interest of a reserve
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+/// * `stats` - The lifetime interest of the reserve
+pub fn set_res_stats(e: &Env, asset: &Address, stats: &ReserveStats) {
+    let key = PoolDataKey::ResStats(asset.clone());
+    e.storage()
+        .persistent()
+        .set::<PoolDataKey, ReserveStats>(&key, stats);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/// Fetch the rounding dust of a reserve that has not been swept to the backstop credit
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+pub fn get_res_dust(e: &Env, asset: &Address) -> i128 {
+    let key = PoolDataKey::ResDust(asset.clone());
+    get_persistent_default(e, &key, || 0, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED)
+}
+
+/// Set the rounding dust of a reserve, removing it if there is none
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+/// * `dust` - The rounding dust of the reserve, in underlying tokens
+pub fn set_res_dust(e: &Env, asset: &Address, dust: i128) {
+    let key = PoolDataKey::ResDust(asset.clone());
+    if dust == 0 {
+        e.storage().persistent().remove(&key);
+        return;
+    }
+    e.storage()
+        .persistent()
+        .set::<PoolDataKey, i128>(&key, &dust);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/// Fetch the unrecovered bad debt of a reserve
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+pub fn get_res_deficit(e: &Env, asset: &Address) -> ReserveDeficit {
+    let key = PoolDataKey::ResDeficit(asset.clone());
+    get_persistent_default(
+        e,
+        &key,
+        ReserveDeficit::default,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set the unrecovered bad debt of a reserve
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+/// * `deficit` - The unrecovered bad debt of the reserve
+pub fn set_res_deficit(e: &Env, asset: &Address, deficit: &ReserveDeficit) {
+    let key = PoolDataKey::ResDeficit(asset.clone());
+    e.storage()
+        .persistent()
+        .set::<PoolDataKey, ReserveDeficit>(&key, deficit);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/********** Reserve Pause **********/
+
+/// Fetch the actions paused for a reserve
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+pub fn get_reserve_pause(e: &Env, asset: &Address) -> ReservePause {
+    let key = PoolDataKey::ResPause(asset.clone());
+    get_persistent_default(
+        e,
+        &key,
+        ReservePause::default,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set the actions paused for a reserve
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+/// * `pause` - The actions paused for the reserve
+pub fn set_reserve_pause(e: &Env, asset: &Address, pause: &ReservePause) {
+    let key = PoolDataKey::ResPause(asset.clone());
+    if pause.supply || pause.borrow {
+        e.storage()
+            .persistent()
+            .set::<PoolDataKey, ReservePause>(&key, pause);
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+    } else {
+        e.storage().persistent().remove(&key);
+    }
+}
+
+/// Fetch the filler stored at a slot of the filler index
+///
+/// ### Arguments
+/// * `slot` - The slot of the filler index
+pub fn get_filler(e: &Env, slot: u32) -> Option<Address> {
+    let key = PoolDataKey::Filler(slot);
+    get_persistent_default(e, &key, || None, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER)
+}
+
+/// Set the filler stored at a slot of the filler index
+///
+/// ### Arguments
+/// * `slot` - The slot of the filler index
+/// * `filler` - The address of the filler
+pub fn set_filler(e: &Env, slot: u32, filler: &Address) {
+    let key = PoolDataKey::Filler(slot);
+    e.storage()
+        .persistent()
+        .set::<PoolDataKey, Address>(&key, filler);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Fetch the cumulative auction fills of a filler, if they have filled an auction
+///
+/// ### Arguments
+/// * `filler` - The address of the filler
+pub fn get_filler_stats(e: &Env, filler: &Address) -> Option<FillerStats> {
+    let key = PoolDataKey::FillerStats(filler.clone());
+    get_persistent_default(e, &key, || None, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER)
+}
+
+/// Set the cumulative auction fills of a filler
+///
+/// ### Arguments
+/// * `filler` - The address of the filler
+/// * `stats` - The cumulative auction fills of the filler
+pub fn set_filler_stats(e: &Env, filler: &Address, stats: &FillerStats) {
+    let key = PoolDataKey::FillerStats(filler.clone());
+    e.storage()
+        .persistent()
+        .set::<PoolDataKey, FillerStats>(&key, stats);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Fetch the in progress collateral factor ramp for a reserve, if one exists
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+pub fn get_c_factor_ramp(e: &Env, asset: &Address) -> Option<CFactorRamp> {
+    let key = PoolDataKey::CFactorRamp(asset.clone());
+    get_persistent_default(
+        e,
+        &key,
+        || None,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set or remove the collateral factor ramp for a reserve
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+/// * `ramp` - The collateral factor ramp, or None to remove it
+pub fn set_c_factor_ramp(e: &Env, asset: &Address, ramp: &Option<CFactorRamp>) {
+    let key = PoolDataKey::CFactorRamp(asset.clone());
+    match ramp {
+        Some(ramp) => {
+            e.storage()
+                .persistent()
+                .set::<PoolDataKey, CFactorRamp>(&key, ramp);
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+        }
+        None => e.storage().persistent().remove(&key),
+    }
+}
+
+/// Fetch the borrower stored at a slot of the borrower index
+///
+/// ### Arguments
+/// * `slot` - The slot of the borrower index
+pub fn get_borrower(e: &Env, slot: u32) -> Option<Address> {
+    let key = PoolDataKey::Borrower(slot);
+    get_persistent_default(e, &key, || None, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER)
+}
+
+/// Fetch the slot of the borrower index a borrower is stored at, if they are indexed
+///
+/// ### Arguments
+/// * `user` - The address of the borrower
+pub fn get_borrower_slot(e: &Env, user: &Address) -> Option<u32> {
+    let key = PoolDataKey::BorrowerSlot(user.clone());
+    get_persistent_default(e, &key, || None, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER)
+}
+
+/// Store a borrower at a slot of the borrower index
+///
+/// ### Arguments
+/// * `slot` - The slot of the borrower index
+/// * `user` - The address of the borrower
+pub fn set_borrower(e: &Env, slot: u32, user: &Address) {
+    let key = PoolDataKey::Borrower(slot);
+    e.storage()
+        .persistent()
+        .set::<PoolDataKey, Address>(&key, user);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+    let slot_key = PoolDataKey::BorrowerSlot(user.clone());
+    e.storage()
+        .persistent()
+        .set::<PoolDataKey, u32>(&slot_key, &slot);
+    e.storage()
+        .persistent()
+        .extend_ttl(&slot_key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Remove a borrower and the slot they are stored at from the borrower index
+///
+/// ### Arguments
+/// * `slot` - The slot of the borrower index
+/// * `user` - The address of the borrower
+pub fn remove_borrower(e: &Env, slot: u32, user: &Address) {
+    e.storage()
+        .persistent()
+        .remove(&PoolDataKey::Borrower(slot));
+    e.storage()
+        .persistent()
+        .remove(&PoolDataKey::BorrowerSlot(user.clone()));
+}
+
+/********** Price Band **********/
+
+/// Fetch the price band for an asset, if one is set
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+pub fn get_price_band(e: &Env, asset: &Address) -> Option<PriceBand> {
+    let key = PoolDataKey::PriceBand(asset.clone());
+    get_persistent_default(
+        e,
+        &key,
+        || None,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set or remove the price band for an asset
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+/// * `band` - The price band for the asset, or None to remove it
+pub fn set_price_band(e: &Env, asset: &Address, band: &Option<PriceBand>) {
+    let key = PoolDataKey::PriceBand(asset.clone());
+    match band {
+        Some(band) => {
+            e.storage()
+                .persistent()
+                .set::<PoolDataKey, PriceBand>(&key, band);
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+        }
+        None => e.storage().persistent().remove(&key),
+    }
+}
+
+/********** Oracle Asset **********/
+
+/// Fetch the oracle asset used to price a reserve, if one is set
+///
+/// ### Arguments
+/// * `asset` - The contract address of the reserve asset
+pub fn get_oracle_asset(e: &Env, asset: &Address) -> Option<Asset> {
+    let key = PoolDataKey::OracleAsset(asset.clone());
+    get_persistent_default(
+        e,
+        &key,
+        || None,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set or remove the oracle asset used to price a reserve
+///
+/// ### Arguments
+/// * `asset` - The contract address of the reserve asset
+/// * `oracle_asset` - The oracle asset, or None to price the reserve as `Asset::Stellar(asset)`
+pub fn set_oracle_asset(e: &Env, asset: &Address, oracle_asset: &Option<Asset>) {
+    let key = PoolDataKey::OracleAsset(asset.clone());
+    match oracle_asset {
+        Some(oracle_asset) => {
+            e.storage()
+                .persistent()
+                .set::<PoolDataKey, Asset>(&key, oracle_asset);
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+        }
+        None => e.storage().persistent().remove(&key),
+    }
+}
+
+/********** Quote Adapter **********/
+
+/// Fetch the swap adapter for a quote asset, if the quote asset is whitelisted
+///
+/// ### Arguments
+/// * `quote_asset` - The contract address of the quote asset
+pub fn get_quote_adapter(e: &Env, quote_asset: &Address) -> Option<Address> {
+    let key = PoolDataKey::QuoteAdapter(quote_asset.clone());
+    get_persistent_default(
+        e,
+        &key,
+        || None,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set or remove the swap adapter for a quote asset
+///
+/// ### Arguments
+/// * `quote_asset` - The contract address of the quote asset
+/// * `adapter` - The swap adapter for the quote asset, or None to remove it from the whitelist
+pub fn set_quote_adapter(e: &Env, quote_asset: &Address, adapter: &Option<Address>) {
+    let key = PoolDataKey::QuoteAdapter(quote_asset.clone());
+    match adapter {
+        Some(adapter) => {
+            e.storage()
+                .persistent()
+                .set::<PoolDataKey, Address>(&key, adapter);
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+        }
+        None => e.storage().persistent().remove(&key),
+    }
+}
+
+/// Fetch the rate strategy a reserve's borrow rate is delegated to, if one is set
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+pub fn get_rate_strategy(e: &Env, asset: &Address) -> Option<Address> {
+    let key = PoolDataKey::RateStrategy(asset.clone());
+    get_persistent_default(
+        e,
+        &key,
+        || None,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set or remove the rate strategy a reserve's borrow rate is delegated to
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+/// * `strategy` - The rate strategy, or None to use the built-in interest rate curve
+pub fn set_rate_strategy(e: &Env, asset: &Address, strategy: &Option<Address>) {
+    let key = PoolDataKey::RateStrategy(asset.clone());
+    match strategy {
+        Some(strategy) => {
+            e.storage()
+                .persistent()
+                .set::<PoolDataKey, Address>(&key, strategy);
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+        }
+        None => e.storage().persistent().remove(&key),
+    }
+}
+
+/// Fetch the max annual borrow rate for a reserve, if one is set
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+pub fn get_max_apr(e: &Env, asset: &Address) -> Option<u32> {
+    let key = PoolDataKey::MaxApr(asset.clone());
+    get_persistent_default(
+        e,
+        &key,
+        || None,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set or remove the max annual borrow rate for a reserve
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+/// * `max_apr` - The max annual borrow rate (7 decimals), or None to remove it
+pub fn set_max_apr(e: &Env, asset: &Address, max_apr: &Option<u32>) {
+    let key = PoolDataKey::MaxApr(asset.clone());
+    match max_apr {
+        Some(max_apr) => {
+            e.storage()
+                .persistent()
+                .set::<PoolDataKey, u32>(&key, max_apr);
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+        }
+        None => e.storage().persistent().remove(&key),
+    }
+}
+
+/********** Reserve List (ResList) **********/
+
+/// Fetch the list of reserves
+pub fn get_res_list(e: &Env) -> Vec<Address> {
+    get_persistent_default(
+        e,
+        &Symbol::new(e, RES_LIST_KEY),
+        || vec![e],
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Add a reserve to the back of the list and returns the index
+///
+/// ### Arguments
+/// * `asset` - The contract address of the underlying asset
 ///
 /// ### Panics
-/// If the reserve does not exist
-pub fn get_res_config(e: &Env, asset: &Address) -> ReserveConfig {
-    let key = PoolDataKey::ResConfig(asset.clone());
-    e.storage()
-        .persistent()
-        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+/// If the number of reserves in the list exceeds 50, or the asset is already in the list
+///
+// @dev: Once added it can't be removed, only retired
+pub fn push_res_list(e: &Env, asset: &Address) -> u32 {
+    let mut res_list = get_res_list(e);
+    if res_list.len() >= MAX_RESERVES {
+        panic_with_error!(e, PoolError::BadRequest)
+    }
+    // an asset can only ever hold a single index
+    if res_list.contains(asset) {
+        panic_with_error!(e, PoolError::BadRequest)
+    }
+    res_list.push_back(asset.clone());
+    let new_index = res_list.len() - 1;
     e.storage()
         .persistent()
-        .get::<PoolDataKey, ReserveConfig>(&key)
-        .unwrap_optimized()
+        .set::<Symbol, Vec<Address>>(&Symbol::new(e, RES_LIST_KEY), &res_list);
+    e.storage().persistent().extend_ttl(
+        &Symbol::new(e, RES_LIST_KEY),
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    );
+    new_index
 }
 
-/// Set the reserve configuration for an asset
+/// Check if the reserve at an index has been retired
 ///
 /// ### Arguments
-/// * `asset` - The contract address of the asset
-/// * `config` - The reserve configuration for the asset
-pub fn set_res_config(e: &Env, asset: &Address, config: &ReserveConfig) {
-    let key = PoolDataKey::ResConfig(asset.clone());
+/// * `index` - The index of the reserve
+pub fn is_res_retired(e: &Env, index: u32) -> bool {
+    let key = PoolDataKey::ResRetired(index);
+    get_persistent_default(
+        e,
+        &key,
+        || false,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Retire the reserve at an index. The index is kept in the reserve list so it is never reused.
+///
+/// ### Arguments
+/// * `index` - The index of the reserve
+pub fn set_res_retired(e: &Env, index: u32) {
+    let key = PoolDataKey::ResRetired(index);
     e.storage()
         .persistent()
-        .set::<PoolDataKey, ReserveConfig>(&key, config);
+        .set::<PoolDataKey, bool>(&key, &true);
     e.storage()
         .persistent()
         .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
 }
 
-/// Checks if a reserve exists for an asset
+/********** Reserve Emissions **********/
+
+/// Fetch the emission data for the reserve b or d token
 ///
 /// ### Arguments
-/// * `asset` - The contract address of the asset
-pub fn has_res(e: &Env, asset: &Address) -> bool {
-    let key = PoolDataKey::ResConfig(asset.clone());
-    e.storage().persistent().has(&key)
+/// * `res_token_index` - The d/bToken index for the reserve
+pub fn get_res_emis_data(e: &Env, res_token_index: &u32) -> Option<ReserveEmissionData> {
+    let key = PoolDataKey::EmisData(*res_token_index);
+    get_persistent_default(
+        e,
+        &key,
+        || None,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
 }
 
-/// Fetch a queued reserve set
+/// Set the emission data for the reserve b or d token
 ///
 /// ### Arguments
-/// * `asset` - The contract address of the asset
-///
-/// ### Panics
-/// If the reserve set has not been queued
-pub fn get_queued_reserve_set(e: &Env, asset: &Address) -> QueuedReserveInit {
-    let key = PoolDataKey::ResInit(asset.clone());
+/// * `res_token_index` - The d/bToken index for the reserve
+/// * `res_emis_data` - The new emission data for the reserve token
+pub fn set_res_emis_data(e: &Env, res_token_index: &u32, res_emis_data: &ReserveEmissionData) {
+    let key = PoolDataKey::EmisData(*res_token_index);
     e.storage()
-        .temporary()
-        .get::<PoolDataKey, QueuedReserveInit>(&key)
-        .unwrap_optimized()
+        .persistent()
+        .set::<PoolDataKey, ReserveEmissionData>(&key, res_emis_data);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
 }
 
-/// Check if a reserve is actively queued
+/// Fetch the undistributed emissions carried over to the next emission epoch of a reserve b or d token
 ///
 /// ### Arguments
-/// * `asset` - The contract address of the asset
-pub fn has_queued_reserve_set(e: &Env, asset: &Address) -> bool {
-    let key = PoolDataKey::ResInit(asset.clone());
-    e.storage().temporary().has(&key)
+/// * `res_token_index` - The d/bToken index for the reserve
+pub fn get_emission_carryover(e: &Env, res_token_index: u32) -> i128 {
+    let key = PoolDataKey::EmisCarry(res_token_index);
+    get_persistent_default(
+        e,
+        &key,
+        || 0i128,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
 }
 
-/// Set a new queued reserve set
+/// Set the undistributed emissions carried over to the next emission epoch of a reserve b or d token
 ///
 /// ### Arguments
-/// * `asset` - The contract address of the asset
-/// * `config` - The reserve configuration for the asset
-pub fn set_queued_reserve_set(e: &Env, res_init: &QueuedReserveInit, asset: &Address) {
-    let key = PoolDataKey::ResInit(asset.clone());
-    e.storage()
-        .temporary()
-        .set::<PoolDataKey, QueuedReserveInit>(&key, res_init);
-    e.storage()
-        .temporary()
-        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+/// * `res_token_index` - The d/bToken index for the reserve
+/// * `carryover` - The amount of emissions carried over, or 0 to remove the carryover
+pub fn set_emission_carryover(e: &Env, res_token_index: u32, carryover: i128) {
+    let key = PoolDataKey::EmisCarry(res_token_index);
+    if carryover == 0 {
+        e.storage().persistent().remove(&key);
+    } else {
+        e.storage()
+            .persistent()
+            .set::<PoolDataKey, i128>(&key, &carryover);
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+    }
 }
 
-/// Delete a queued reserve set
+/// Fetch the emission decay schedule of a reserve b or d token
 ///
 /// ### Arguments
-/// * `asset` - The contract address of the asset
+/// * `res_token_index` - The d/bToken index for the reserve
+pub fn get_emission_decay(e: &Env, res_token_index: u32) -> Option<EmissionDecay> {
+    let key = PoolDataKey::EmisDecay(res_token_index);
+    get_persistent_default(
+        e,
+        &key,
+        || None,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set or remove the emission decay schedule of a reserve b or d token
 ///
-/// ### Panics
-/// If the reserve set has not been queued
-pub fn del_queued_reserve_set(e: &Env, asset: &Address) {
-    let key = PoolDataKey::ResInit(asset.clone());
-    e.storage().temporary().remove(&key);
+/// ### Arguments
+/// * `res_token_index` - The d/bToken index for the reserve
+/// * `decay` - The emission decay schedule, or None to remove it
+pub fn set_emission_decay(e: &Env, res_token_index: u32, decay: &Option<EmissionDecay>) {
+    let key = PoolDataKey::EmisDecay(res_token_index);
+    match decay {
+        Some(decay) => {
+            e.storage()
+                .persistent()
+                .set::<PoolDataKey, EmissionDecay>(&key, decay);
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+        }
+        None => e.storage().persistent().remove(&key),
+    }
 }
 
-/********** Reserve Data (ResData) **********/
+/********** User Emissions **********/
 
-/// Fetch the reserve data for an asset
+/// Fetch the users emission data for a reserve's b or d token
 ///
 /// ### Arguments
-/// * `asset` - The contract address of the asset
+/// * `user` - The address of the user
+/// * `res_token_index` - The d/bToken index for the reserve
+pub fn get_user_emissions(
+    e: &Env,
+    user: &Address,
+    res_token_index: &u32,
+) -> Option<UserEmissionData> {
+    let key = PoolDataKey::UserEmis(UserReserveKey {
+        user: user.clone(),
+        reserve_id: *res_token_index,
+    });
+    get_persistent_default(e, &key, || None, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER)
+}
+
+/// Set the users emission data for a reserve's d or d token
 ///
-/// ### Panics
-/// If the reserve does not exist
-pub fn get_res_data(e: &Env, asset: &Address) -> ReserveData {
-    let key = PoolDataKey::ResData(asset.clone());
+/// ### Arguments
+/// * `user` - The address of the user
+/// * `res_token_index` - The d/bToken index for the reserve
+/// * `data` - The new user emission d ata for the d/bToken
+pub fn set_user_emissions(e: &Env, user: &Address, res_token_index: &u32, data: &UserEmissionData) {
+    let key = PoolDataKey::UserEmis(UserReserveKey {
+        user: user.clone(),
+        reserve_id: *res_token_index,
+    });
     e.storage()
         .persistent()
-        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+        .set::<PoolDataKey, UserEmissionData>(&key, data);
     e.storage()
         .persistent()
-        .get::<PoolDataKey, ReserveData>(&key)
-        .unwrap_optimized()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
 }
 
-/// Set the reserve data for an asset
+/********** Withdrawal Queue **********/
+
+/// Fetch the withdrawal queued by a user for a reserve
 ///
 /// ### Arguments
-/// * `asset` - The contract address of the asset
-/// * `data` - The reserve data for the asset
-pub fn set_res_data(e: &Env, asset: &Address, data: &ReserveData) {
-    let key = PoolDataKey::ResData(asset.clone());
+/// * `user` - The address of the user
+/// * `reserve_index` - The index of the reserve
+pub fn get_queued_withdrawal(
+    e: &Env,
+    user: &Address,
+    reserve_index: u32,
+) -> Option<QueuedWithdrawal> {
+    let key = PoolDataKey::WithdrawQueue(UserReserveKey {
+        user: user.clone(),
+        reserve_id: reserve_index,
+    });
+    get_persistent_default(e, &key, || None, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER)
+}
+
+/// Set the withdrawal queued by a user for a reserve
+///
+/// ### Arguments
+/// * `user` - The address of the user
+/// * `reserve_index` - The index of the reserve
+/// * `queued` - The queued withdrawal
+pub fn set_queued_withdrawal(
+    e: &Env,
+    user: &Address,
+    reserve_index: u32,
+    queued: &QueuedWithdrawal,
+) {
+    let key = PoolDataKey::WithdrawQueue(UserReserveKey {
+        user: user.clone(),
+        reserve_id: reserve_index,
+    });
     e.storage()
         .persistent()
-        .set::<PoolDataKey, ReserveData>(&key, data);
+        .set::<PoolDataKey, QueuedWithdrawal>(&key, queued);
     e.storage()
         .persistent()
-        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
 }
 
-/********** Reserve List (ResList) **********/
+/// Remove the withdrawal queued by a user for a reserve
+///
+/// ### Arguments
+/// * `user` - The address of the user
+/// * `reserve_index` - The index of the reserve
+pub fn del_queued_withdrawal(e: &Env, user: &Address, reserve_index: u32) {
+    let key = PoolDataKey::WithdrawQueue(UserReserveKey {
+        user: user.clone(),
+        reserve_id: reserve_index,
+    });
+    e.storage().persistent().remove(&key);
+}
 
-/// Fetch the list of reserves
-pub fn get_res_list(e: &Env) -> Vec<Address> {
+/// Fetch the lifetime totals of a reserve's withdrawal queue
+///
+/// ### Arguments
+/// * `reserve_index` - The index of the reserve
+pub fn get_withdraw_queue(e: &Env, reserve_index: u32) -> WithdrawQueue {
+    let key = PoolDataKey::ResQueue(reserve_index);
     get_persistent_default(
         e,
-        &Symbol::new(e, RES_LIST_KEY),
-        || vec![e],
+        &key,
+        WithdrawQueue::default,
         LEDGER_THRESHOLD_SHARED,
         LEDGER_BUMP_SHARED,
     )
 }
 
-/// Add a reserve to the back of the list and returns the index
+/// Set the lifetime totals of a reserve's withdrawal queue
 ///
 /// ### Arguments
-/// * `asset` - The contract address of the underlying asset
-///
-/// ### Panics
-/// If the number of reserves in the list exceeds 50
-///
-// @dev: Once added it can't be removed
-pub fn push_res_list(e: &Env, asset: &Address) -> u32 {
-    let mut res_list = get_res_list(e);
-    if res_list.len() >= MAX_RESERVES {
-        panic_with_error!(e, PoolError::BadRequest)
-    }
-    res_list.push_back(asset.clone());
-    let new_index = res_list.len() - 1;
+/// * `reserve_index` - The index of the reserve
+/// * `queue` - The lifetime totals of the withdrawal queue
+pub fn set_withdraw_queue(e: &Env, reserve_index: u32, queue: &WithdrawQueue) {
+    let key = PoolDataKey::ResQueue(reserve_index);
     e.storage()
         .persistent()
-        .set::<Symbol, Vec<Address>>(&Symbol::new(e, RES_LIST_KEY), &res_list);
-    e.storage().persistent().extend_ttl(
-        &Symbol::new(e, RES_LIST_KEY),
+        .set::<PoolDataKey, WithdrawQueue>(&key, queue);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/********** Credit Line **********/
+
+/// Fetch the credit line extended to a reserve, if one is set
+///
+/// ### Arguments
+/// * `asset` - The contract address of the reserve asset
+pub fn get_credit_line(e: &Env, asset: &Address) -> Option<CreditLine> {
+    let key = PoolDataKey::CreditLine(asset.clone());
+    get_persistent_default(
+        e,
+        &key,
+        || None,
         LEDGER_THRESHOLD_SHARED,
         LEDGER_BUMP_SHARED,
-    );
-    new_index
+    )
 }
 
-/********** Reserve Emissions **********/
+/// Set or remove the credit line extended to a reserve
+///
+/// ### Arguments
+/// * `asset` - The contract address of the reserve asset
+/// * `credit_line` - The credit line, or None to remove it
+pub fn set_credit_line(e: &Env, asset: &Address, credit_line: &Option<CreditLine>) {
+    let key = PoolDataKey::CreditLine(asset.clone());
+    match credit_line {
+        Some(credit_line) => {
+            e.storage()
+                .persistent()
+                .set::<PoolDataKey, CreditLine>(&key, credit_line);
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+        }
+        None => e.storage().persistent().remove(&key),
+    }
+}
 
-/// Fetch the emission data for the reserve b or d token
+/********** Stable Rate **********/
+
+/// Fetch the stable rate borrowing config of a reserve, if stable rate borrowing is enabled
 ///
 /// ### Arguments
-/// * `res_token_index` - The d/bToken index for the reserve
-pub fn get_res_emis_data(e: &Env, res_token_index: &u32) -> Option<ReserveEmissionData> {
-    let key = PoolDataKey::EmisData(*res_token_index);
+/// * `asset` - The contract address of the reserve asset
+pub fn get_stable_rate_config(e: &Env, asset: &Address) -> Option<StableRateConfig> {
+    let key = PoolDataKey::StableRate(asset.clone());
     get_persistent_default(
         e,
         &key,
@@ -512,59 +2666,149 @@ pub fn get_res_emis_data(e: &Env, res_token_index: &u32) -> Option<ReserveEmissi
     )
 }
 
-/// Set the emission data for the reserve b or d token
+/// Set or remove the stable rate borrowing config of a reserve
 ///
 /// ### Arguments
-/// * `res_token_index` - The d/bToken index for the reserve
-/// * `res_emis_data` - The new emission data for the reserve token
-pub fn set_res_emis_data(e: &Env, res_token_index: &u32, res_emis_data: &ReserveEmissionData) {
-    let key = PoolDataKey::EmisData(*res_token_index);
-    e.storage()
-        .persistent()
-        .set::<PoolDataKey, ReserveEmissionData>(&key, res_emis_data);
-    e.storage()
-        .persistent()
-        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+/// * `asset` - The contract address of the reserve asset
+/// * `config` - The stable rate borrowing config, or None to disable stable rate borrowing
+pub fn set_stable_rate_config(e: &Env, asset: &Address, config: &Option<StableRateConfig>) {
+    let key = PoolDataKey::StableRate(asset.clone());
+    match config {
+        Some(config) => {
+            e.storage()
+                .persistent()
+                .set::<PoolDataKey, StableRateConfig>(&key, config);
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+        }
+        None => e.storage().persistent().remove(&key),
+    }
 }
 
-/********** User Emissions **********/
-
-/// Fetch the users emission data for a reserve's b or d token
+/// Fetch the stable rate a user borrows from a reserve at, if they have one
 ///
 /// ### Arguments
 /// * `user` - The address of the user
-/// * `res_token_index` - The d/bToken index for the reserve
-pub fn get_user_emissions(
-    e: &Env,
-    user: &Address,
-    res_token_index: &u32,
-) -> Option<UserEmissionData> {
-    let key = PoolDataKey::UserEmis(UserReserveKey {
+/// * `reserve_index` - The index of the reserve
+pub fn get_stable_loan(e: &Env, user: &Address, reserve_index: u32) -> Option<StableLoan> {
+    let key = PoolDataKey::StableLoan(UserReserveKey {
         user: user.clone(),
-        reserve_id: *res_token_index,
+        reserve_id: reserve_index,
     });
     get_persistent_default(e, &key, || None, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER)
 }
 
-/// Set the users emission data for a reserve's d or d token
+/// Set or remove the stable rate a user borrows from a reserve at
 ///
 /// ### Arguments
 /// * `user` - The address of the user
-/// * `res_token_index` - The d/bToken index for the reserve
-/// * `data` - The new user emission d ata for the d/bToken
-pub fn set_user_emissions(e: &Env, user: &Address, res_token_index: &u32, data: &UserEmissionData) {
-    let key = PoolDataKey::UserEmis(UserReserveKey {
+/// * `reserve_index` - The index of the reserve
+/// * `loan` - The stable rate loan, or None to remove it
+pub fn set_stable_loan(e: &Env, user: &Address, reserve_index: u32, loan: &Option<StableLoan>) {
+    let key = PoolDataKey::StableLoan(UserReserveKey {
         user: user.clone(),
-        reserve_id: *res_token_index,
+        reserve_id: reserve_index,
     });
+    match loan {
+        Some(loan) => {
+            e.storage()
+                .persistent()
+                .set::<PoolDataKey, StableLoan>(&key, loan);
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+        }
+        None => e.storage().persistent().remove(&key),
+    }
+}
+
+/// Fetch the portion of a user's liabilities borrowed at a stable rate, if they have any stored
+///
+/// ### Arguments
+/// * `user` - The address of the user
+pub fn get_user_stable_liabilities(e: &Env, user: &Address) -> Option<Map<u32, i128>> {
+    let key = PoolDataKey::StableLiab(user.clone());
+    get_persistent_default(e, &key, || None, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER)
+}
+
+/// Set the portion of a user's liabilities borrowed at a stable rate, removing it if there is none
+///
+/// ### Arguments
+/// * `user` - The address of the user
+/// * `stable_liabilities` - Map of reserve index to the stable rate portion of the liability share balance
+pub fn set_user_stable_liabilities(e: &Env, user: &Address, stable_liabilities: &Map<u32, i128>) {
+    let key = PoolDataKey::StableLiab(user.clone());
+    if stable_liabilities.is_empty() {
+        e.storage().persistent().remove(&key);
+    } else {
+        e.storage()
+            .persistent()
+            .set::<PoolDataKey, Map<u32, i128>>(&key, stable_liabilities);
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+    }
+}
+
+/********** Position History **********/
+
+/// Fetch the position checkpoints for a user, oldest first
+///
+/// ### Arguments
+/// * `user` - The address of the user
+pub fn get_position_history(e: &Env, user: &Address) -> Vec<PositionCheckpoint> {
+    let key = PoolDataKey::PosHistory(user.clone());
+    get_persistent_default(e, &key, || vec![e], LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER)
+}
+
+/// Set the position checkpoints for a user
+///
+/// ### Arguments
+/// * `user` - The address of the user
+/// * `history` - The position checkpoints, oldest first
+pub fn set_position_history(e: &Env, user: &Address, history: &Vec<PositionCheckpoint>) {
+    let key = PoolDataKey::PosHistory(user.clone());
     e.storage()
         .persistent()
-        .set::<PoolDataKey, UserEmissionData>(&key, data);
+        .set::<PoolDataKey, Vec<PositionCheckpoint>>(&key, history);
     e.storage()
         .persistent()
         .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
 }
 
+/********** Risk Mark **********/
+
+/// Fetch the liability dRates recorded when the user was last found below the risk premium
+/// threshold, if the user is currently marked
+///
+/// ### Arguments
+/// * `user` - The address of the user
+pub fn get_risk_mark(e: &Env, user: &Address) -> Option<Map<u32, i128>> {
+    let key = PoolDataKey::RiskMark(user.clone());
+    get_persistent_default(e, &key, || None, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER)
+}
+
+/// Set or remove the risk mark for a user
+///
+/// ### Arguments
+/// * `user` - The address of the user
+/// * `d_rates` - The dRates of the user's liabilities, or None to remove the mark
+pub fn set_risk_mark(e: &Env, user: &Address, d_rates: &Option<Map<u32, i128>>) {
+    let key = PoolDataKey::RiskMark(user.clone());
+    match d_rates {
+        Some(d_rates) => {
+            e.storage()
+                .persistent()
+                .set::<PoolDataKey, Map<u32, i128>>(&key, d_rates);
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+        }
+        None => e.storage().persistent().remove(&key),
+    }
+}
+
 /********** Pool Emissions **********/
 
 /// Fetch the pool reserve emissions
@@ -596,6 +2840,10 @@ pub fn set_pool_emissions(e: &Env, emissions: &Map<u32, u64>) {
 
 /********** Auctions ***********/
 
+// Auctions are kept in persistent storage so an archived entry is restored rather than lost,
+// and must be explicitly deleted once filled or stale. Auctions created before the move are still
+// in temporary storage, and are moved to persistent storage the next time they are written.
+
 /// Fetch the auction data for an auction
 ///
 /// ### Arguments
@@ -609,6 +2857,16 @@ pub fn get_auction(e: &Env, auction_type: &u32, user: &Address) -> AuctionData {
         user: user.clone(),
         auct_type: *auction_type,
     });
+    if let Some(auction_data) = e
+        .storage()
+        .persistent()
+        .get::<PoolDataKey, AuctionData>(&key)
+    {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+        return auction_data;
+    }
     e.storage()
         .temporary()
         .get::<PoolDataKey, AuctionData>(&key)
@@ -625,10 +2883,11 @@ pub fn has_auction(e: &Env, auction_type: &u32, user: &Address) -> bool {
         user: user.clone(),
         auct_type: *auction_type,
     });
-    e.storage().temporary().has(&key)
+    e.storage().persistent().has(&key) || e.storage().temporary().has(&key)
 }
 
-/// Set the the starting block for an auction
+/// Set the the starting block for an auction. Moves an auction still held in temporary storage
+/// to persistent storage.
 ///
 /// ### Arguments
 /// * `auction_type` - The type of auction
@@ -640,14 +2899,15 @@ pub fn set_auction(e: &Env, auction_type: &u32, user: &Address, auction_data: &A
         auct_type: *auction_type,
     });
     e.storage()
-        .temporary()
+        .persistent()
         .set::<PoolDataKey, AuctionData>(&key, auction_data);
     e.storage()
-        .temporary()
+        .persistent()
         .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+    e.storage().temporary().remove(&key);
 }
 
-/// Remove an auction
+/// Remove an auction, from both persistent and temporary storage
 ///
 /// ### Arguments
 /// * `auction_type` - The type of auction
@@ -657,5 +2917,340 @@ pub fn del_auction(e: &Env, auction_type: &u32, user: &Address) {
         user: user.clone(),
         auct_type: *auction_type,
     });
+    e.storage().persistent().remove(&key);
     e.storage().temporary().remove(&key);
 }
+
+/********** Reserve Proposals **********/
+
+/// Fetch the BLND bond required to propose a reserve
+///
+/// Returns 0 (proposals disabled) if one has not been set
+pub fn get_listing_bond(e: &Env) -> i128 {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, LISTING_BOND_KEY))
+        .unwrap_or(0)
+}
+
+/// Set the BLND bond required to propose a reserve
+///
+/// ### Arguments
+/// * `bond` - The amount of BLND to bond, or 0 to disable proposals
+pub fn set_listing_bond(e: &Env, bond: i128) {
+    e.storage()
+        .instance()
+        .set::<Symbol, i128>(&Symbol::new(e, LISTING_BOND_KEY), &bond);
+}
+
+/// Fetch the council that decides reserve proposals, if one is set
+pub fn get_listing_council(e: &Env) -> Option<Address> {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, LISTING_COUNCIL_KEY))
+}
+
+/// Set or remove the council that decides reserve proposals
+///
+/// ### Arguments
+/// * `council` - The council, or None for the admin to decide proposals
+pub fn set_listing_council(e: &Env, council: &Option<Address>) {
+    match council {
+        Some(council) => e
+            .storage()
+            .instance()
+            .set::<Symbol, Address>(&Symbol::new(e, LISTING_COUNCIL_KEY), council),
+        None => e
+            .storage()
+            .instance()
+            .remove(&Symbol::new(e, LISTING_COUNCIL_KEY)),
+    }
+}
+
+/// Fetch the pending proposal to add an asset as a reserve, if one exists
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+pub fn get_reserve_proposal(e: &Env, asset: &Address) -> Option<ReserveProposal> {
+    let key = PoolDataKey::ResProposal(asset.clone());
+    get_persistent_default(
+        e,
+        &key,
+        || None,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set or remove the pending proposal to add an asset as a reserve
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+/// * `proposal` - The proposal, or None to remove it
+pub fn set_reserve_proposal(e: &Env, asset: &Address, proposal: &Option<ReserveProposal>) {
+    let key = PoolDataKey::ResProposal(asset.clone());
+    match proposal {
+        Some(proposal) => {
+            e.storage()
+                .persistent()
+                .set::<PoolDataKey, ReserveProposal>(&key, proposal);
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+        }
+        None => e.storage().persistent().remove(&key),
+    }
+}
+
+/********** Analytics **********/
+
+/// Fetch the number of days of activity counters retained
+///
+/// Returns 0 (disabled) if one has not been set
+pub fn get_analytics_window(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, ANALYTICS_WINDOW_KEY))
+        .unwrap_or(0)
+}
+
+/// Set the number of days of activity counters retained
+///
+/// ### Arguments
+/// * `window` - The number of days to retain
+pub fn set_analytics_window(e: &Env, window: u32) {
+    e.storage()
+        .instance()
+        .set::<Symbol, u32>(&Symbol::new(e, ANALYTICS_WINDOW_KEY), &window);
+}
+
+/// Fetch the activity counters for a day, if any were recorded
+///
+/// ### Arguments
+/// * `day` - The number of days since the unix epoch
+pub fn get_daily_stats(e: &Env, day: u64) -> Option<DailyStats> {
+    e.storage()
+        .temporary()
+        .get::<PoolDataKey, DailyStats>(&PoolDataKey::DailyStats(day))
+}
+
+/// Set the activity counters for a day. The counters expire once they leave the analytics window.
+///
+/// ### Arguments
+/// * `day` - The number of days since the unix epoch
+/// * `stats` - The activity counters
+/// * `window` - The number of days of activity counters retained
+pub fn set_daily_stats(e: &Env, day: u64, stats: &DailyStats, window: u32) {
+    let key = PoolDataKey::DailyStats(day);
+    e.storage()
+        .temporary()
+        .set::<PoolDataKey, DailyStats>(&key, stats);
+    let ttl = window * ONE_DAY_LEDGERS;
+    e.storage().temporary().extend_ttl(&key, ttl, ttl);
+}
+
+/// Fetch the last day an address was counted as an active user, if it was counted recently
+///
+/// ### Arguments
+/// * `address` - The address
+pub fn get_daily_actor(e: &Env, address: &Address) -> Option<u64> {
+    e.storage()
+        .temporary()
+        .get::<PoolDataKey, u64>(&PoolDataKey::DailyActor(address.clone()))
+}
+
+/// Set the last day an address was counted as an active user
+///
+/// ### Arguments
+/// * `address` - The address
+/// * `day` - The number of days since the unix epoch
+pub fn set_daily_actor(e: &Env, address: &Address, day: u64) {
+    let key = PoolDataKey::DailyActor(address.clone());
+    e.storage().temporary().set::<PoolDataKey, u64>(&key, &day);
+    e.storage()
+        .temporary()
+        .extend_ttl(&key, ONE_DAY_LEDGERS, 2 * ONE_DAY_LEDGERS);
+}
+
+/********** Keepers **********/
+
+/// Fetch the keeper rebate config, if one has been set
+pub fn get_keeper_config(e: &Env) -> Option<KeeperConfig> {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, KEEPER_CONFIG_KEY))
+}
+
+/// Set the keeper rebate config
+///
+/// ### Arguments
+/// * `config` - The keeper rebate config
+pub fn set_keeper_config(e: &Env, config: &KeeperConfig) {
+    e.storage()
+        .instance()
+        .set::<Symbol, KeeperConfig>(&Symbol::new(e, KEEPER_CONFIG_KEY), config);
+}
+
+/// Fetch the amount of tokens available to pay keeper rebates
+pub fn get_keeper_pot(e: &Env) -> i128 {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, KEEPER_POT_KEY))
+        .unwrap_or(0)
+}
+
+/// Set the amount of tokens available to pay keeper rebates
+///
+/// ### Arguments
+/// * `pot` - The amount of tokens
+pub fn set_keeper_pot(e: &Env, pot: i128) {
+    e.storage()
+        .instance()
+        .set::<Symbol, i128>(&Symbol::new(e, KEEPER_POT_KEY), &pot);
+}
+
+/// Check if an address is registered as a keeper
+///
+/// ### Arguments
+/// * `keeper` - The address
+pub fn get_keeper(e: &Env, keeper: &Address) -> bool {
+    let key = PoolDataKey::Keeper(keeper.clone());
+    get_persistent_default(
+        e,
+        &key,
+        || false,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Register or unregister an address as a keeper
+///
+/// ### Arguments
+/// * `keeper` - The address
+/// * `registered` - If the address is registered as a keeper
+pub fn set_keeper(e: &Env, keeper: &Address, registered: bool) {
+    let key = PoolDataKey::Keeper(keeper.clone());
+    if registered {
+        e.storage()
+            .persistent()
+            .set::<PoolDataKey, bool>(&key, &true);
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+    } else {
+        e.storage().persistent().remove(&key);
+    }
+}
+
+/********** Insurance **********/
+
+/// Fetch the pool's liquidation protection insurance config, if one is set
+pub fn get_insurance_config(e: &Env) -> Option<InsuranceConfig> {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, INSURANCE_CONFIG_KEY))
+}
+
+/// Set or remove the pool's liquidation protection insurance config
+///
+/// ### Arguments
+/// * `config` - The insurance config, or None to disable insurance
+pub fn set_insurance_config(e: &Env, config: &Option<InsuranceConfig>) {
+    match config {
+        Some(config) => e
+            .storage()
+            .instance()
+            .set::<Symbol, InsuranceConfig>(&Symbol::new(e, INSURANCE_CONFIG_KEY), config),
+        None => e
+            .storage()
+            .instance()
+            .remove(&Symbol::new(e, INSURANCE_CONFIG_KEY)),
+    }
+}
+
+/// Fetch the timestamp the insurance premium was last charged to a user, if the user is insured
+///
+/// ### Arguments
+/// * `user` - The address of the user
+pub fn get_insured(e: &Env, user: &Address) -> Option<u64> {
+    let key = PoolDataKey::Insured(user.clone());
+    get_persistent_default(e, &key, || None, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER)
+}
+
+/// Set the timestamp the insurance premium was last charged to a user, or remove the user's insurance
+///
+/// ### Arguments
+/// * `user` - The address of the user
+/// * `timestamp` - The timestamp the premium was last charged, or None if the user is not insured
+pub fn set_insured(e: &Env, user: &Address, timestamp: &Option<u64>) {
+    let key = PoolDataKey::Insured(user.clone());
+    match timestamp {
+        Some(timestamp) => {
+            e.storage()
+                .persistent()
+                .set::<PoolDataKey, u64>(&key, timestamp);
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+        }
+        None => e.storage().persistent().remove(&key),
+    }
+}
+
+/********** Fill Commitments **********/
+
+/// Fetch the pool's commit-reveal fill config, if commit-reveal fills are enabled
+pub fn get_fill_commit_config(e: &Env) -> Option<FillCommitConfig> {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, FILL_COMMIT_CONFIG_KEY))
+}
+
+/// Set or remove the pool's commit-reveal fill config
+///
+/// ### Arguments
+/// * `config` - The commit-reveal fill config, or None to disable commit-reveal fills
+pub fn set_fill_commit_config(e: &Env, config: &Option<FillCommitConfig>) {
+    match config {
+        Some(config) => e
+            .storage()
+            .instance()
+            .set::<Symbol, FillCommitConfig>(&Symbol::new(e, FILL_COMMIT_CONFIG_KEY), config),
+        None => e
+            .storage()
+            .instance()
+            .remove(&Symbol::new(e, FILL_COMMIT_CONFIG_KEY)),
+    }
+}
+
+/// Fetch the fill a filler has committed to, if any
+///
+/// ### Arguments
+/// * `filler` - The address of the filler
+pub fn get_fill_commitment(e: &Env, filler: &Address) -> Option<FillCommitment> {
+    e.storage()
+        .temporary()
+        .get(&PoolDataKey::FillCommit(filler.clone()))
+}
+
+/// Set or remove the fill a filler has committed to. Commitments are only stored for long enough to
+/// be revealed.
+///
+/// ### Arguments
+/// * `filler` - The address of the filler
+/// * `commitment` - The commitment, or None to remove it
+pub fn set_fill_commitment(e: &Env, filler: &Address, commitment: &Option<FillCommitment>) {
+    let key = PoolDataKey::FillCommit(filler.clone());
+    match commitment {
+        Some(commitment) => {
+            e.storage()
+                .temporary()
+                .set::<PoolDataKey, FillCommitment>(&key, commitment);
+            e.storage()
+                .temporary()
+                .extend_ttl(&key, ONE_DAY_LEDGERS, ONE_DAY_LEDGERS);
+        }
+        None => e.storage().temporary().remove(&key),
+    }
+}
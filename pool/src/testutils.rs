@@ -2,15 +2,19 @@
 
 use crate::{
     constants::{SCALAR_12, SCALAR_7},
-    pool::Reserve,
-    storage::{self, ReserveConfig, ReserveData},
+    pool::ReserveState,
+    storage::{self, ReserveConfig, ReserveData, ReserveRateExt, ReserveStats},
     PoolContract,
 };
 use blend_contract_sdk::emitter::{Client as EmitterClient, WASM as EmitterWASM};
 use sep_40_oracle::testutils::{MockPriceOracleClient, MockPriceOracleWASM};
 use sep_41_token::testutils::{MockTokenClient, MockTokenWASM};
+use sep_41_token::TokenClient;
 use soroban_fixed_point_math::SorobanFixedPoint;
-use soroban_sdk::{testutils::Address as _, vec, Address, BytesN, Env, IntoVal, String};
+use soroban_sdk::{
+    contract, contractimpl, testutils::Address as _, vec, Address, BytesN, Env, IntoVal, String,
+    Symbol,
+};
 
 use backstop::{BackstopClient, BackstopContract};
 use mock_pool_factory::{MockPoolFactory, MockPoolFactoryClient, PoolInitMeta};
@@ -193,14 +197,62 @@ pub fn create_flashloan_receiver<'a>(
     )
 }
 
+//***** Swap Adapter *****
+
+/// Swaps at a fixed price of `token_in` per `token_out` out of its own balance
+#[contract]
+pub struct MockSwapAdapter;
+
+#[contractimpl]
+impl MockSwapAdapter {
+    pub fn set_price(e: Env, price: i128) {
+        e.storage()
+            .instance()
+            .set(&Symbol::new(&e, "Price"), &price);
+    }
+
+    pub fn swap_exact_out(
+        e: Env,
+        token_in: Address,
+        token_out: Address,
+        amount_out: i128,
+        max_amount_in: i128,
+        to: Address,
+    ) -> i128 {
+        let price: i128 = e
+            .storage()
+            .instance()
+            .get(&Symbol::new(&e, "Price"))
+            .unwrap();
+        let amount_in = amount_out.fixed_mul_ceil(&e, &price, &SCALAR_7);
+        assert!(amount_in <= max_amount_in);
+
+        let adapter = e.current_contract_address();
+        let token_in_client = TokenClient::new(&e, &token_in);
+        let unused = token_in_client.balance(&adapter) - amount_in;
+        TokenClient::new(&e, &token_out).transfer(&adapter, &to, &amount_out);
+        token_in_client.transfer(&adapter, &to, &unused);
+        amount_in
+    }
+}
+
+/// Create a mock swap adapter. The price must be set before it is used.
+pub(crate) fn create_mock_swap_adapter<'a>(e: &Env) -> (Address, MockSwapAdapterClient<'a>) {
+    let contract_id = e.register(MockSwapAdapter {}, ());
+    (
+        contract_id.clone(),
+        MockSwapAdapterClient::new(e, &contract_id),
+    )
+}
+
 //************************************************
 //            Object Creation Helpers
 //************************************************
 
 //***** Reserve *****
 
-pub(crate) fn default_reserve(e: &Env) -> Reserve {
-    Reserve {
+pub(crate) fn default_reserve(e: &Env) -> ReserveState {
+    ReserveState {
         asset: Address::generate(e),
         config: ReserveConfig {
             decimals: 7,
@@ -226,7 +278,10 @@ pub(crate) fn default_reserve(e: &Env) -> Reserve {
             last_time: 0,
             backstop_credit: 0,
         },
+        rate_ext: ReserveRateExt::default(),
         scalar: SCALAR_7,
+        accrued: ReserveStats::default(),
+        dust: 0,
     }
 }
 
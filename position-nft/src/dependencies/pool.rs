@@ -0,0 +1,65 @@
+/**
+ * Partial client for the pool contract
+ */
+use soroban_sdk::{contractclient, contracttype, Address, Env, Map, Vec};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Request {
+    pub request_type: u32,
+    pub address: Address, // asset address or liquidatee
+    pub amount: i128,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Positions {
+    pub liabilities: Map<u32, i128>, // Map of Reserve Index to liability share balance
+    pub collateral: Map<u32, i128>,  // Map of Reserve Index to collateral supply share balance
+    pub supply: Map<u32, i128>,      // Map of Reserve Index to non-collateral supply share balance
+}
+
+#[allow(dead_code)]
+#[contractclient(name = "PoolClient")]
+pub trait Pool {
+    /// Fetch the positions for an address
+    ///
+    /// ### Arguments
+    /// * `address` - The address to fetch positions for
+    fn get_positions(e: Env, address: Address) -> Positions;
+
+    /// Submit a set of requests to the pool where `from` takes on the position, `spender` sends any
+    /// required tokens to the pool and `to` receives any tokens sent from the pool
+    ///
+    /// ### Arguments
+    /// * `from` - The address of the user whose positions are being modified
+    /// * `spender` - The address of the user who is sending tokens to the pool
+    /// * `to` - The address of the user who is receiving tokens from the pool
+    /// * `requests` - A vec of requests to be processed
+    fn submit(
+        e: Env,
+        from: Address,
+        spender: Address,
+        to: Address,
+        requests: Vec<Request>,
+    ) -> Positions;
+
+    /// Approve a delegatee to borrow up to `amount` of an asset against the delegator's positions
+    ///
+    /// ### Arguments
+    /// * `delegator` - The address whose positions back the borrow
+    /// * `delegatee` - The address allowed to borrow
+    /// * `asset` - The reserve asset
+    /// * `amount` - The amount of the asset the delegatee is allowed to borrow
+    fn approve_borrow(e: Env, delegator: Address, delegatee: Address, asset: Address, amount: i128);
+
+    /// Claims outstanding emissions for the caller for the given reserve's
+    ///
+    /// Returns the number of tokens claimed
+    ///
+    /// ### Arguments
+    /// * `from` - The address claiming
+    /// * `reserve_token_ids` - Vector of reserve token ids
+    /// * `to` - The Address to send the claimed tokens to
+    fn claim(e: Env, from: Address, reserve_token_ids: Vec<u32>, to: Address) -> i128;
+}
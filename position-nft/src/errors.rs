@@ -0,0 +1,16 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+/// Error codes for the position NFT contract. Common errors are codes that match up with the built-in
+/// contracts error reporting. Position NFT specific errors start at 1400.
+pub enum PositionNftError {
+    // Common Errors
+    InternalError = 1,
+    AlreadyInitializedError = 3,
+    UnauthorizedError = 4,
+
+    // Position NFT
+    InvalidOwner = 1400,
+}
@@ -0,0 +1,18 @@
+use soroban_sdk::{Address, Env, Symbol};
+
+pub struct PositionNftEvents {}
+
+impl PositionNftEvents {
+    /// Emitted when ownership of the wrapped position is transferred
+    ///
+    /// - topics - `["transfer", from: Address, to: Address]`
+    /// - data - `()`
+    ///
+    /// ### Arguments
+    /// * `from` - The previous owner of the position
+    /// * `to` - The new owner of the position
+    pub fn transfer(e: &Env, from: Address, to: Address) {
+        let topics = (Symbol::new(e, "transfer"), from, to);
+        e.events().publish(topics, ());
+    }
+}
@@ -0,0 +1,15 @@
+#![no_std]
+
+#[cfg(any(test, feature = "testutils"))]
+extern crate std;
+
+mod dependencies;
+mod errors;
+mod events;
+mod position_nft;
+mod storage;
+mod test;
+
+pub use dependencies::{PoolClient, Positions, Request};
+pub use errors::PositionNftError;
+pub use position_nft::*;
@@ -0,0 +1,136 @@
+use crate::{
+    dependencies::{PoolClient, Positions, Request},
+    errors::PositionNftError,
+    events::PositionNftEvents,
+    storage,
+};
+use soroban_sdk::{contract, contractclient, contractimpl, panic_with_error, Address, Env, Vec};
+
+/// ### Position NFT
+///
+/// Wraps a single pool position into a transferable token. The contract address holds the position
+/// in the pool, and the current owner of the token controls it by proxying calls through the
+/// contract, so the position can be sold or used by other protocols without closing it.
+#[contract]
+pub struct PositionNftContract;
+
+#[contractclient(name = "PositionNftClient")]
+pub trait PositionNft {
+    /// Fetch the pool the wrapped position is held in
+    fn pool(e: Env) -> Address;
+
+    /// Fetch the current owner of the wrapped position
+    fn owner(e: Env) -> Address;
+
+    /// Fetch the wrapped position
+    fn get_positions(e: Env) -> Positions;
+
+    /// (Owner only) Transfer ownership of the wrapped position
+    ///
+    /// ### Arguments
+    /// * `from` - The current owner of the position
+    /// * `to` - The new owner of the position
+    ///
+    /// ### Panics
+    /// If `from` is not the owner, or `to` is the contract or the pool
+    fn transfer(e: Env, from: Address, to: Address);
+
+    /// (Owner only) Submit a set of requests against the wrapped position. The owner sends any
+    /// required tokens to the pool and receives any tokens sent from the pool.
+    ///
+    /// Returns the new positions
+    ///
+    /// ### Arguments
+    /// * `requests` - A vec of requests to be processed
+    ///
+    /// ### Panics
+    /// If the request is unable to be fully executed by the pool
+    fn submit(e: Env, requests: Vec<Request>) -> Positions;
+
+    /// (Owner only) Approve a delegatee to borrow an asset against the wrapped position
+    ///
+    /// ### Arguments
+    /// * `delegatee` - The address allowed to borrow
+    /// * `asset` - The reserve asset that can be borrowed
+    /// * `amount` - The amount of the asset the delegatee can borrow
+    fn approve_borrow(e: Env, delegatee: Address, asset: Address, amount: i128);
+
+    /// (Owner only) Claim the emissions earned by the wrapped position and send them to the owner
+    ///
+    /// Returns the number of tokens claimed
+    ///
+    /// ### Arguments
+    /// * `reserve_token_ids` - Vector of reserve token ids
+    fn claim(e: Env, reserve_token_ids: Vec<u32>) -> i128;
+}
+
+#[contractimpl]
+impl PositionNftContract {
+    /// Construct the position NFT contract
+    ///
+    /// ### Arguments
+    /// * `pool` - The pool the wrapped position is held in
+    /// * `owner` - The initial owner of the wrapped position
+    pub fn __constructor(e: Env, pool: Address, owner: Address) {
+        storage::set_pool(&e, &pool);
+        storage::set_owner(&e, &owner);
+    }
+}
+
+#[contractimpl]
+impl PositionNft for PositionNftContract {
+    fn pool(e: Env) -> Address {
+        storage::get_pool(&e)
+    }
+
+    fn owner(e: Env) -> Address {
+        storage::get_owner(&e)
+    }
+
+    fn get_positions(e: Env) -> Positions {
+        let pool_client = PoolClient::new(&e, &storage::get_pool(&e));
+        pool_client.get_positions(&e.current_contract_address())
+    }
+
+    fn transfer(e: Env, from: Address, to: Address) {
+        storage::extend_instance(&e);
+        from.require_auth();
+        if from != storage::get_owner(&e) {
+            panic_with_error!(&e, PositionNftError::UnauthorizedError);
+        }
+        if to == e.current_contract_address() || to == storage::get_pool(&e) {
+            panic_with_error!(&e, PositionNftError::InvalidOwner);
+        }
+
+        storage::set_owner(&e, &to);
+
+        PositionNftEvents::transfer(&e, from, to);
+    }
+
+    fn submit(e: Env, requests: Vec<Request>) -> Positions {
+        storage::extend_instance(&e);
+        let owner = storage::get_owner(&e);
+        owner.require_auth();
+
+        let pool_client = PoolClient::new(&e, &storage::get_pool(&e));
+        pool_client.submit(&e.current_contract_address(), &owner, &owner, &requests)
+    }
+
+    fn approve_borrow(e: Env, delegatee: Address, asset: Address, amount: i128) {
+        storage::extend_instance(&e);
+        let owner = storage::get_owner(&e);
+        owner.require_auth();
+
+        let pool_client = PoolClient::new(&e, &storage::get_pool(&e));
+        pool_client.approve_borrow(&e.current_contract_address(), &delegatee, &asset, &amount);
+    }
+
+    fn claim(e: Env, reserve_token_ids: Vec<u32>) -> i128 {
+        storage::extend_instance(&e);
+        let owner = storage::get_owner(&e);
+        owner.require_auth();
+
+        let pool_client = PoolClient::new(&e, &storage::get_pool(&e));
+        pool_client.claim(&e.current_contract_address(), &reserve_token_ids, &owner)
+    }
+}
@@ -0,0 +1,56 @@
+use soroban_sdk::{unwrap::UnwrapOptimized, Address, Env, Symbol};
+
+/********** Ledger Thresholds **********/
+
+const ONE_DAY_LEDGERS: u32 = 17280; // assumes 5s a ledger
+
+const LEDGER_THRESHOLD_INSTANCE: u32 = ONE_DAY_LEDGERS * 30; // ~ 30 days
+const LEDGER_BUMP_INSTANCE: u32 = LEDGER_THRESHOLD_INSTANCE + ONE_DAY_LEDGERS; // ~ 31 days
+
+/********** Storage Keys **********/
+
+const POOL_KEY: &str = "Pool";
+const OWNER_KEY: &str = "Owner";
+
+/// Bump the instance rent for the contract
+pub fn extend_instance(e: &Env) {
+    e.storage()
+        .instance()
+        .extend_ttl(LEDGER_THRESHOLD_INSTANCE, LEDGER_BUMP_INSTANCE);
+}
+
+/// Fetch the pool the wrapped position is held in
+pub fn get_pool(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get::<Symbol, Address>(&Symbol::new(e, POOL_KEY))
+        .unwrap_optimized()
+}
+
+/// Set the pool the wrapped position is held in
+///
+/// ### Arguments
+/// * `pool` - The address of the pool
+pub fn set_pool(e: &Env, pool: &Address) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Address>(&Symbol::new(e, POOL_KEY), pool);
+}
+
+/// Fetch the current owner of the wrapped position
+pub fn get_owner(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get::<Symbol, Address>(&Symbol::new(e, OWNER_KEY))
+        .unwrap_optimized()
+}
+
+/// Set the owner of the wrapped position
+///
+/// ### Arguments
+/// * `owner` - The address of the new owner
+pub fn set_owner(e: &Env, owner: &Address) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Address>(&Symbol::new(e, OWNER_KEY), owner);
+}
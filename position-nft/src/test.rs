@@ -0,0 +1,210 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    contract, contractimpl, map,
+    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, Events},
+    vec, Address, Env, IntoVal, Map, Symbol, Vec,
+};
+
+use crate::{PositionNftClient, PositionNftContract, Positions, Request};
+
+/// Mock pool that applies supply collateral and borrow requests to reserve index 0
+#[contract]
+struct MockPool;
+
+#[contractimpl]
+impl MockPool {
+    pub fn get_positions(e: Env, address: Address) -> Positions {
+        e.storage().instance().get(&address).unwrap_or(Positions {
+            liabilities: Map::new(&e),
+            collateral: Map::new(&e),
+            supply: Map::new(&e),
+        })
+    }
+
+    pub fn submit(
+        e: Env,
+        from: Address,
+        spender: Address,
+        to: Address,
+        requests: Vec<Request>,
+    ) -> Positions {
+        from.require_auth();
+        spender.require_auth();
+        let mut positions = Self::get_positions(e.clone(), from.clone());
+        for request in requests.iter() {
+            match request.request_type {
+                2 => positions
+                    .collateral
+                    .set(0, positions.collateral.get(0).unwrap_or(0) + request.amount),
+                4 => positions.liabilities.set(
+                    0,
+                    positions.liabilities.get(0).unwrap_or(0) + request.amount,
+                ),
+                _ => panic!("unsupported request"),
+            }
+        }
+        e.storage().instance().set(&from, &positions);
+        e.storage()
+            .instance()
+            .set(&Symbol::new(&e, "to"), &(spender, to));
+        positions
+    }
+
+    pub fn approve_borrow(
+        e: Env,
+        delegator: Address,
+        delegatee: Address,
+        asset: Address,
+        amount: i128,
+    ) {
+        delegator.require_auth();
+        e.storage()
+            .instance()
+            .set(&(delegator, delegatee, asset), &amount);
+    }
+
+    pub fn claim(e: Env, from: Address, _reserve_token_ids: Vec<u32>, to: Address) -> i128 {
+        from.require_auth();
+        e.storage()
+            .instance()
+            .set(&Symbol::new(&e, "to"), &(from, to));
+        1_0000000
+    }
+}
+
+fn create_position_nft<'a>(e: &Env, owner: &Address) -> (Address, PositionNftClient<'a>) {
+    let pool = e.register(MockPool {}, ());
+    let position_nft = e.register(PositionNftContract {}, (pool.clone(), owner.clone()));
+    (pool, PositionNftClient::new(e, &position_nft))
+}
+
+#[test]
+fn test_submit() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let samwise = Address::generate(&e);
+    let asset = Address::generate(&e);
+    let (pool, client) = create_position_nft(&e, &samwise);
+    assert_eq!(client.pool(), pool);
+    assert_eq!(client.owner(), samwise);
+
+    let requests = vec![
+        &e,
+        Request {
+            request_type: 2,
+            address: asset.clone(),
+            amount: 10_0000000,
+        },
+        Request {
+            request_type: 4,
+            address: asset.clone(),
+            amount: 2_0000000,
+        },
+    ];
+    let positions = client.submit(&requests);
+    assert_eq!(positions.collateral, map![&e, (0, 10_0000000)]);
+    assert_eq!(positions.liabilities, map![&e, (0, 2_0000000)]);
+
+    // the owner authorizes the submit and the contract holds the position
+    assert_eq!(
+        e.auths()[0],
+        (
+            samwise.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    client.address.clone(),
+                    Symbol::new(&e, "submit"),
+                    vec![&e, requests.into_val(&e)]
+                )),
+                sub_invocations: std::vec![AuthorizedInvocation {
+                    function: AuthorizedFunction::Contract((
+                        pool.clone(),
+                        Symbol::new(&e, "submit"),
+                        vec![
+                            &e,
+                            client.address.into_val(&e),
+                            samwise.into_val(&e),
+                            samwise.into_val(&e),
+                            requests.into_val(&e),
+                        ]
+                    )),
+                    sub_invocations: std::vec![]
+                }]
+            }
+        )
+    );
+    e.as_contract(&pool, || {
+        let to: (Address, Address) = e.storage().instance().get(&Symbol::new(&e, "to")).unwrap();
+        assert_eq!(to, (samwise.clone(), samwise.clone()));
+    });
+}
+
+#[test]
+fn test_transfer() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let samwise = Address::generate(&e);
+    let frodo = Address::generate(&e);
+    let asset = Address::generate(&e);
+    let (pool, client) = create_position_nft(&e, &samwise);
+
+    client.transfer(&samwise, &frodo);
+    let event = vec![&e, e.events().all().last_unchecked()];
+    assert_eq!(
+        event,
+        vec![
+            &e,
+            (
+                client.address.clone(),
+                (Symbol::new(&e, "transfer"), samwise.clone(), frodo.clone()).into_val(&e),
+                ().into_val(&e)
+            )
+        ]
+    );
+    assert_eq!(client.owner(), frodo);
+
+    // the new owner controls the position and receives its proceeds
+    let claimed = client.claim(&vec![&e, 1]);
+    assert_eq!(claimed, 1_0000000);
+    assert_eq!(e.auths()[0].0, frodo);
+    client.approve_borrow(&samwise, &asset, &5_0000000);
+    assert_eq!(e.auths()[0].0, frodo);
+    e.as_contract(&pool, || {
+        let to: (Address, Address) = e.storage().instance().get(&Symbol::new(&e, "to")).unwrap();
+        assert_eq!(to, (client.address.clone(), frodo.clone()));
+        let allowance: i128 = e
+            .storage()
+            .instance()
+            .get(&(client.address.clone(), samwise.clone(), asset.clone()))
+            .unwrap();
+        assert_eq!(allowance, 5_0000000);
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #4)")]
+fn test_transfer_not_owner() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let samwise = Address::generate(&e);
+    let frodo = Address::generate(&e);
+    let (_, client) = create_position_nft(&e, &samwise);
+
+    client.transfer(&frodo, &frodo);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1400)")]
+fn test_transfer_to_pool() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let samwise = Address::generate(&e);
+    let (pool, client) = create_position_nft(&e, &samwise);
+
+    client.transfer(&samwise, &pool);
+}
@@ -0,0 +1,2 @@
+mod soroswap_router;
+pub use soroswap_router::SoroswapRouterClient;
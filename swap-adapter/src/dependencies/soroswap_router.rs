@@ -0,0 +1,24 @@
+use soroban_sdk::{contractclient, Address, Env, Vec};
+
+/// The subset of the Soroswap router interface used by the adapter
+#[contractclient(name = "SoroswapRouterClient")]
+pub trait SoroswapRouter {
+    /// Fetch the address of the pair contract for two tokens
+    fn router_pair_for(e: Env, token_a: Address, token_b: Address) -> Address;
+
+    /// Fetch the amount of each token in `path` required to receive `amount_out` of the last token
+    fn router_get_amounts_in(e: Env, amount_out: i128, path: Vec<Address>) -> Vec<i128>;
+
+    /// Swap at most `amount_in_max` of the first token in `path` for exactly `amount_out` of the last
+    /// token. The input is transferred from `to`, and the output is sent to `to`.
+    ///
+    /// Returns the amount of each token in `path` swapped
+    fn swap_tokens_for_exact_tokens(
+        e: Env,
+        amount_out: i128,
+        amount_in_max: i128,
+        path: Vec<Address>,
+        to: Address,
+        deadline: u64,
+    ) -> Vec<i128>;
+}
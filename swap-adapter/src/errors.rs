@@ -0,0 +1,18 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+/// Error codes for the swap adapter contracts. Common errors are codes that match up with the built-in
+/// contracts error reporting. Swap adapter specific errors start at 1500.
+pub enum SwapAdapterError {
+    // Common Errors
+    InternalError = 1,
+    AlreadyInitializedError = 3,
+    UnauthorizedError = 4,
+    NegativeAmountError = 8,
+
+    // Swap Adapter
+    InvalidSwapPath = 1500,
+    SlippageExceeded = 1501,
+}
@@ -0,0 +1,22 @@
+#![no_std]
+
+//! Swap adapters convert one asset into another on an external exchange on behalf of a pool.
+//!
+//! The pool only knows the `SwapAdapter` interface, so exchange specific code lives here instead of
+//! in the pool. Collateral swaps, deleverages, and quote asset auction fills all route through the
+//! adapter whitelisted for the asset being sold.
+
+#[cfg(any(test, feature = "testutils"))]
+extern crate std;
+
+mod dependencies;
+mod errors;
+mod soroswap;
+mod storage;
+mod swap_adapter;
+mod test;
+
+pub use dependencies::SoroswapRouterClient;
+pub use errors::SwapAdapterError;
+pub use soroswap::*;
+pub use swap_adapter::{SwapAdapter, SwapAdapterClient};
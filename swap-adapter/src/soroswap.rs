@@ -0,0 +1,98 @@
+use crate::{
+    dependencies::SoroswapRouterClient, errors::SwapAdapterError, storage,
+    swap_adapter::SwapAdapter,
+};
+use sep_41_token::TokenClient;
+use soroban_sdk::{
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    contract, contractimpl, panic_with_error, vec, Address, Env, IntoVal, Symbol,
+};
+
+/// ### Soroswap Adapter
+///
+/// Executes swaps for a pool through the Soroswap router. Each swap is routed through the direct pair
+/// of the two assets.
+#[contract]
+pub struct SoroswapAdapterContract;
+
+#[contractimpl]
+impl SoroswapAdapterContract {
+    /// Construct the Soroswap adapter contract
+    ///
+    /// ### Arguments
+    /// * `router` - The Soroswap router swaps are executed through
+    pub fn __constructor(e: Env, router: Address) {
+        storage::set_router(&e, &router);
+    }
+
+    /// Fetch the Soroswap router swaps are executed through
+    pub fn router(e: Env) -> Address {
+        storage::get_router(&e)
+    }
+}
+
+#[contractimpl]
+impl SwapAdapter for SoroswapAdapterContract {
+    fn swap_exact_out(
+        e: Env,
+        token_in: Address,
+        token_out: Address,
+        amount_out: i128,
+        max_amount_in: i128,
+        to: Address,
+    ) -> i128 {
+        storage::extend_instance(&e);
+        if amount_out <= 0 || max_amount_in <= 0 {
+            panic_with_error!(&e, SwapAdapterError::NegativeAmountError);
+        }
+        if token_in == token_out {
+            panic_with_error!(&e, SwapAdapterError::InvalidSwapPath);
+        }
+
+        let adapter = e.current_contract_address();
+        let router = SoroswapRouterClient::new(&e, &storage::get_router(&e));
+        let path = vec![&e, token_in.clone(), token_out.clone()];
+
+        // quote the swap so the slippage bound fails with an adapter error instead of a router error
+        let amount_in = router
+            .router_get_amounts_in(&amount_out, &path)
+            .first_unchecked();
+        if amount_in > max_amount_in {
+            panic_with_error!(&e, SwapAdapterError::SlippageExceeded);
+        }
+
+        // the router transfers the input from the adapter to the pair
+        let pair = router.router_pair_for(&token_in, &token_out);
+        e.authorize_as_current_contract(vec![
+            &e,
+            InvokerContractAuthEntry::Contract(SubContractInvocation {
+                context: ContractContext {
+                    contract: token_in.clone(),
+                    fn_name: Symbol::new(&e, "transfer"),
+                    args: (adapter.clone(), pair, amount_in).into_val(&e),
+                },
+                sub_invocations: vec![&e],
+            }),
+        ]);
+        let amounts = router.swap_tokens_for_exact_tokens(
+            &amount_out,
+            &max_amount_in,
+            &path,
+            &adapter,
+            &e.ledger().timestamp(),
+        );
+        let amount_in = amounts.first_unchecked();
+        if amount_in > max_amount_in || amounts.last_unchecked() < amount_out {
+            panic_with_error!(&e, SwapAdapterError::SlippageExceeded);
+        }
+
+        // forward the output and any unused input
+        TokenClient::new(&e, &token_out).transfer(&adapter, &to, &amount_out);
+        let token_in_client = TokenClient::new(&e, &token_in);
+        let unused = token_in_client.balance(&adapter);
+        if unused > 0 {
+            token_in_client.transfer(&adapter, &to, &unused);
+        }
+        amount_in
+    }
+}
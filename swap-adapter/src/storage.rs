@@ -0,0 +1,37 @@
+use soroban_sdk::{unwrap::UnwrapOptimized, Address, Env, Symbol};
+
+/********** Ledger Thresholds **********/
+
+const ONE_DAY_LEDGERS: u32 = 17280; // assumes 5s a ledger
+
+const LEDGER_THRESHOLD_INSTANCE: u32 = ONE_DAY_LEDGERS * 30; // ~ 30 days
+const LEDGER_BUMP_INSTANCE: u32 = LEDGER_THRESHOLD_INSTANCE + ONE_DAY_LEDGERS; // ~ 31 days
+
+/********** Storage Keys **********/
+
+const ROUTER_KEY: &str = "Router";
+
+/// Bump the instance rent for the contract
+pub fn extend_instance(e: &Env) {
+    e.storage()
+        .instance()
+        .extend_ttl(LEDGER_THRESHOLD_INSTANCE, LEDGER_BUMP_INSTANCE);
+}
+
+/// Fetch the router swaps are executed through
+pub fn get_router(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get::<Symbol, Address>(&Symbol::new(e, ROUTER_KEY))
+        .unwrap_optimized()
+}
+
+/// Set the router swaps are executed through
+///
+/// ### Arguments
+/// * `router` - The address of the router
+pub fn set_router(e: &Env, router: &Address) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Address>(&Symbol::new(e, ROUTER_KEY), router);
+}
@@ -0,0 +1,34 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+/// ### SwapAdapter
+///
+/// The interface a pool uses to convert an asset it holds into another asset through an external
+/// exchange. The interface is encoded identically to the pool's own `SwapAdapter` dependency, so any
+/// adapter in this crate can be whitelisted on a pool.
+#[contractclient(name = "SwapAdapterClient")]
+pub trait SwapAdapter {
+    /// Swap the adapter's balance of `token_in` for exactly `amount_out` of `token_out`.
+    ///
+    /// The caller transfers the `token_in` available for the swap to the adapter before invoking it. The
+    /// adapter sends `amount_out` of `token_out` and any unused `token_in` to `to`.
+    ///
+    /// Returns the amount of `token_in` spent
+    ///
+    /// ### Arguments
+    /// * `token_in` - The asset being sold
+    /// * `token_out` - The asset being bought
+    /// * `amount_out` - The exact amount of `token_out` to receive
+    /// * `max_amount_in` - The maximum amount of `token_in` that can be spent
+    /// * `to` - The address receiving `token_out` and any unused `token_in`
+    ///
+    /// ### Panics
+    /// If the swap requires more than `max_amount_in` of `token_in`
+    fn swap_exact_out(
+        e: Env,
+        token_in: Address,
+        token_out: Address,
+        amount_out: i128,
+        max_amount_in: i128,
+        to: Address,
+    ) -> i128;
+}
@@ -0,0 +1,139 @@
+#![cfg(test)]
+
+use sep_41_token::TokenClient;
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as _, Ledger},
+    token::StellarAssetClient,
+    vec, Address, Env, Symbol, Vec,
+};
+
+use crate::{SoroswapAdapterContract, SwapAdapterClient};
+
+/// Mock Soroswap router that acts as the pair for every path and swaps at a fixed rate of output
+/// tokens per input token, with 7 decimals
+#[contract]
+struct MockSoroswapRouter;
+
+#[contractimpl]
+impl MockSoroswapRouter {
+    pub fn set_rate(e: Env, rate: i128) {
+        e.storage().instance().set(&Symbol::new(&e, "Rate"), &rate);
+    }
+
+    pub fn router_pair_for(e: Env, _token_a: Address, _token_b: Address) -> Address {
+        e.current_contract_address()
+    }
+
+    pub fn router_get_amounts_in(e: Env, amount_out: i128, _path: Vec<Address>) -> Vec<i128> {
+        let rate: i128 = e
+            .storage()
+            .instance()
+            .get(&Symbol::new(&e, "Rate"))
+            .unwrap();
+        let amount_in = (amount_out * 1_0000000 + rate - 1) / rate;
+        vec![&e, amount_in, amount_out]
+    }
+
+    pub fn swap_tokens_for_exact_tokens(
+        e: Env,
+        amount_out: i128,
+        amount_in_max: i128,
+        path: Vec<Address>,
+        to: Address,
+        deadline: u64,
+    ) -> Vec<i128> {
+        to.require_auth();
+        assert!(e.ledger().timestamp() <= deadline);
+        let amounts = Self::router_get_amounts_in(e.clone(), amount_out, path.clone());
+        let amount_in = amounts.first_unchecked();
+        assert!(amount_in <= amount_in_max);
+
+        let router = e.current_contract_address();
+        TokenClient::new(&e, &path.first_unchecked()).transfer(&to, &router, &amount_in);
+        TokenClient::new(&e, &path.last_unchecked()).transfer(&router, &to, &amount_out);
+        amounts
+    }
+}
+
+/// Create the adapter and a router holding 1000 of `token_out` that buys it at 2 `token_in` each
+///
+/// Returns (adapter_client, router, token_in, token_out)
+fn setup_adapter(e: &Env) -> (SwapAdapterClient<'_>, Address, Address, Address) {
+    e.ledger().set_timestamp(12345);
+    let bombadil = Address::generate(e);
+    let token_in = e
+        .register_stellar_asset_contract_v2(bombadil.clone())
+        .address();
+    let token_out = e.register_stellar_asset_contract_v2(bombadil).address();
+
+    let router = e.register(MockSoroswapRouter {}, ());
+    MockSoroswapRouterClient::new(e, &router).set_rate(&0_5000000);
+    StellarAssetClient::new(e, &token_out)
+        .mock_all_auths()
+        .mint(&router, &1000_0000000);
+
+    let adapter = e.register(SoroswapAdapterContract {}, (router.clone(),));
+    (
+        SwapAdapterClient::new(e, &adapter),
+        router,
+        token_in,
+        token_out,
+    )
+}
+
+#[test]
+fn test_swap_exact_out() {
+    let e = Env::default();
+    let (adapter_client, router, token_in, token_out) = setup_adapter(&e);
+    let pool = Address::generate(&e);
+    StellarAssetClient::new(&e, &token_in)
+        .mock_all_auths()
+        .mint(&adapter_client.address, &120_0000000);
+
+    // no auths are mocked, so the adapter must authorize the router's transfer of its own balance
+    let amount_in =
+        adapter_client.swap_exact_out(&token_in, &token_out, &50_0000000, &120_0000000, &pool);
+    assert_eq!(amount_in, 100_0000000);
+
+    let token_in_client = TokenClient::new(&e, &token_in);
+    let token_out_client = TokenClient::new(&e, &token_out);
+    assert_eq!(token_out_client.balance(&pool), 50_0000000);
+    assert_eq!(token_in_client.balance(&pool), 20_0000000);
+    assert_eq!(token_in_client.balance(&router), 100_0000000);
+    assert_eq!(token_in_client.balance(&adapter_client.address), 0);
+    assert_eq!(token_out_client.balance(&adapter_client.address), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1501)")]
+fn test_swap_exact_out_slippage_exceeded() {
+    let e = Env::default();
+    let (adapter_client, _, token_in, token_out) = setup_adapter(&e);
+    let pool = Address::generate(&e);
+    StellarAssetClient::new(&e, &token_in)
+        .mock_all_auths()
+        .mint(&adapter_client.address, &120_0000000);
+
+    adapter_client.swap_exact_out(&token_in, &token_out, &50_0000000, &99_0000000, &pool);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1500)")]
+fn test_swap_exact_out_same_token() {
+    let e = Env::default();
+    let (adapter_client, _, token_in, _) = setup_adapter(&e);
+    let pool = Address::generate(&e);
+
+    adapter_client.swap_exact_out(&token_in, &token_in, &50_0000000, &120_0000000, &pool);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #8)")]
+fn test_swap_exact_out_negative_amount() {
+    let e = Env::default();
+    let (adapter_client, _, token_in, token_out) = setup_adapter(&e);
+    let pool = Address::generate(&e);
+
+    adapter_client.swap_exact_out(&token_in, &token_out, &-1, &120_0000000, &pool);
+}
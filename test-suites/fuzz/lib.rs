@@ -18,7 +18,7 @@ pub struct NatI128(
     #[arbitrary(with = |u: &mut Unstructured| u.int_in_range(0..=(i64::MAX as i128)))] pub i128,
 );
 
-type ContractResult<T> = Result<T, Result<soroban_sdk::Error, soroban_sdk::InvokeError>>;
+type ContractResult<T, E = soroban_sdk::Error> = Result<T, Result<E, soroban_sdk::InvokeError>>;
 
 /// Panic if a contract call result might have been the result of an unexpected panic.
 ///
@@ -27,13 +27,20 @@ type ContractResult<T> = Result<T, Result<soroban_sdk::Error, soroban_sdk::Invok
 /// thus contracts should never simply call `panic!`, but instead use `panic_with_error!`.
 ///
 /// Other rare types of internal exception can return `InvalidAction`.
+///
+/// Calls to entrypoints that return a typed contract error report any other failure as an
+/// `InvokeError::Abort`, which does not include the error type, so those are not checked.
 #[track_caller]
-pub fn verify_contract_result<T>(env: &soroban_sdk::Env, r: &ContractResult<T>) {
+pub fn verify_contract_result<T, E: Clone + Into<soroban_sdk::Error>>(
+    env: &soroban_sdk::Env,
+    r: &ContractResult<T, E>,
+) {
     use soroban_sdk::testutils::Events;
     use soroban_sdk::xdr::{ScErrorCode, ScErrorType};
     use soroban_sdk::{ConversionError, Error};
     match r {
         Err(Ok(e)) => {
+            let e: Error = e.clone().into();
             if e.is_type(ScErrorType::WasmVm) && e.is_code(ScErrorCode::InvalidAction) {
                 let msg = "contract failed with InvalidAction - unexpected panic?";
                 eprintln!("{msg}");
@@ -0,0 +1,105 @@
+//! An exact rational reference for the pool's fixed-point math
+//!
+//! Every value is kept as an exact fraction in natural units (i.e. a 5% rate is 1/20), so the
+//! reference has no rounding. Differential tests compare the on-chain i128 results against it to
+//! bound the drift rounding introduces.
+
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{One, Zero};
+use pool::ReserveConfig;
+
+pub const SECONDS_PER_YEAR: i128 = 31_536_000;
+
+/// Convert a fixed-point number with `scalar` decimals to an exact fraction
+pub fn from_fixed(value: i128, scalar: i128) -> BigRational {
+    BigRational::new(BigInt::from(value), BigInt::from(scalar))
+}
+
+/// Convert a 7 decimal config value to an exact fraction
+fn from_config(value: u32) -> BigRational {
+    from_fixed(value as i128, 1_0000000)
+}
+
+/// Calculate the relative difference between an on-chain fixed-point value and the reference,
+/// i.e. |actual / reference - 1|
+pub fn relative_drift(actual: i128, scalar: i128, reference: &BigRational) -> BigRational {
+    if reference.is_zero() {
+        return from_fixed(actual, scalar);
+    }
+    let diff = from_fixed(actual, scalar) - reference;
+    let drift = diff / reference;
+    if drift < BigRational::zero() {
+        -drift
+    } else {
+        drift
+    }
+}
+
+/// Calculate the exact loan accrual and new interest rate modifier, mirroring `calc_accrual`
+///
+/// Returns (accrual, new interest rate modifier)
+pub fn calc_accrual(
+    config: &ReserveConfig,
+    util: &BigRational,
+    ir_mod: &BigRational,
+    delta_time: u64,
+) -> (BigRational, BigRational) {
+    let target_util = from_config(config.util);
+    let r_base = from_config(config.r_base);
+    let r_one = from_config(config.r_one);
+    let r_two = from_config(config.r_two);
+    let r_three = from_config(config.r_three);
+    let util_95 = from_fixed(95, 100);
+
+    let cur_ir = if *util <= target_util {
+        (util / &target_util * &r_one + &r_base) * ir_mod
+    } else if *util <= util_95 {
+        let util_scalar = (util - &target_util) / (&util_95 - &target_util);
+        (util_scalar * &r_two + &r_one + &r_base) * ir_mod
+    } else {
+        let util_scalar = (util - &util_95) / from_fixed(5, 100);
+        util_scalar * &r_three + ir_mod * (&r_two + &r_one + &r_base)
+    };
+
+    let delta = BigRational::from_integer(BigInt::from(delta_time));
+    let next_ir_mod = ir_mod + &delta * (util - &target_util) * from_config(config.reactivity);
+    let new_ir_mod = next_ir_mod
+        .max(from_fixed(1, 10))
+        .min(BigRational::from_integer(BigInt::from(10)));
+
+    let time_weight = delta / BigRational::from_integer(BigInt::from(SECONDS_PER_YEAR));
+    (BigRational::one() + time_weight * cur_ir, new_ir_mod)
+}
+
+/// An exact model of a reserve's rates and supplies
+#[derive(Clone, Debug)]
+pub struct ReferenceReserve {
+    pub b_rate: BigRational,
+    pub d_rate: BigRational,
+    pub ir_mod: BigRational,
+    pub b_supply: BigRational,
+    pub d_supply: BigRational,
+    pub backstop_credit: BigRational,
+}
+
+impl ReferenceReserve {
+    /// Accrue interest over `delta_time` seconds, mirroring `ReserveState::load`
+    pub fn accrue(&mut self, config: &ReserveConfig, bstop_rate: u32, delta_time: u64) {
+        if self.b_supply.is_zero() || self.d_supply.is_zero() {
+            return;
+        }
+        let supply = &self.b_supply * &self.b_rate;
+        let liabilities = &self.d_supply * &self.d_rate;
+        let util = (&liabilities / &supply).min(BigRational::one());
+
+        let (accrual, new_ir_mod) = calc_accrual(config, &util, &self.ir_mod, delta_time);
+        self.ir_mod = new_ir_mod;
+        self.d_rate = &self.d_rate * accrual;
+
+        let accrued = &self.d_supply * &self.d_rate - liabilities;
+        let backstop_credit = &accrued * from_config(bstop_rate);
+        self.b_rate = (supply + &accrued - &backstop_credit) / &self.b_supply;
+        self.backstop_credit += backstop_credit;
+    }
+}
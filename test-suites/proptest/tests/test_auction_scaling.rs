@@ -0,0 +1,94 @@
+use auction_math::{get_curve_modifiers, scale_bid_amount, scale_lot_amount, AuctionCurve};
+use proptest::prelude::*;
+use test_suites_proptest::SCALAR_7;
+
+fn auction_curve() -> impl Strategy<Value = AuctionCurve> {
+    prop_oneof![
+        Just(AuctionCurve::Linear),
+        Just(AuctionCurve::Exponential),
+        Just(AuctionCurve::Stepwise),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn test_scale_bid_rounds_up(
+        amount in 0i128..=1_000_000_000_000_000,
+        percent_filled in 1u64..=100,
+        curve in auction_curve(),
+        block_dif in 0u32..=500,
+    ) {
+        let (bid_modifier, _) = get_curve_modifiers(curve, block_dif);
+        let (to_fill, remaining) = scale_bid_amount(amount, percent_filled, bid_modifier).unwrap();
+        let to_fill_base = amount - remaining;
+
+        // the filled base never exceeds the auction and rounds up
+        prop_assert!(remaining >= 0);
+        prop_assert!(to_fill_base * 100 >= amount * percent_filled as i128);
+        // the bid rounds up, so the filler never pays less than the exact amount
+        prop_assert!(to_fill <= to_fill_base);
+        prop_assert!(
+            to_fill * 100 * SCALAR_7 >= amount * percent_filled as i128 * bid_modifier
+        );
+    }
+
+    #[test]
+    fn test_scale_lot_rounds_down(
+        amount in 0i128..=1_000_000_000_000_000,
+        percent_filled in 1u64..=100,
+        curve in auction_curve(),
+        block_dif in 0u32..=500,
+    ) {
+        let (_, lot_modifier) = get_curve_modifiers(curve, block_dif);
+        let (to_fill, remaining) = scale_lot_amount(amount, percent_filled, lot_modifier).unwrap();
+        let to_fill_base = amount - remaining;
+
+        // the filled base never exceeds the auction and rounds down
+        prop_assert!(remaining >= 0);
+        prop_assert!(to_fill_base * 100 <= amount * percent_filled as i128);
+        // the lot rounds down, so the filler never receives more than the exact amount
+        prop_assert!(to_fill >= 0);
+        prop_assert!(to_fill <= to_fill_base);
+        prop_assert!(
+            to_fill * 100 * SCALAR_7 <= amount * percent_filled as i128 * lot_modifier
+        );
+    }
+
+    #[test]
+    fn test_partial_fills_never_exceed_base(
+        bid_amount in 0i128..=1_000_000_000_000_000,
+        lot_amount in 0i128..=1_000_000_000_000_000,
+        fills in prop::collection::vec(1u64..=99, 0..10),
+        curve in auction_curve(),
+        block_dif in 0u32..=500,
+    ) {
+        let (bid_modifier, lot_modifier) = get_curve_modifiers(curve, block_dif);
+        let (full_bid, _) = scale_bid_amount(bid_amount, 100, bid_modifier).unwrap();
+        let (full_lot, _) = scale_lot_amount(lot_amount, 100, lot_modifier).unwrap();
+
+        // fill the auction in pieces, finishing with a 100% fill of whatever remains
+        let mut bid_remaining = bid_amount;
+        let mut lot_remaining = lot_amount;
+        let mut bid_paid = 0;
+        let mut lot_received = 0;
+        for percent_filled in fills.into_iter().chain(core::iter::once(100)) {
+            let (bid, next_bid_remaining) =
+                scale_bid_amount(bid_remaining, percent_filled, bid_modifier).unwrap();
+            let (lot, next_lot_remaining) =
+                scale_lot_amount(lot_remaining, percent_filled, lot_modifier).unwrap();
+            prop_assert!(next_bid_remaining <= bid_remaining);
+            prop_assert!(next_lot_remaining <= lot_remaining);
+            bid_remaining = next_bid_remaining;
+            lot_remaining = next_lot_remaining;
+            bid_paid += bid;
+            lot_received += lot;
+        }
+
+        // the whole auction is consumed, and splitting it never beats filling it at once
+        prop_assert_eq!(bid_remaining, 0);
+        prop_assert_eq!(lot_remaining, 0);
+        prop_assert!(bid_paid >= full_bid);
+        prop_assert!(lot_received <= full_lot);
+        prop_assert!(lot_received <= lot_amount);
+    }
+}
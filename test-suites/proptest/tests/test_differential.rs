@@ -0,0 +1,153 @@
+use auction_math::{get_curve_modifiers, scale_bid_amount, scale_lot_amount, AuctionCurve};
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::Signed;
+use pool::{calc_accrual, PoolConfig, PoolDataKey, ReserveData, ReserveState};
+use proptest::prelude::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+use test_suites_proptest::{
+    create_pool,
+    reference::{self, from_fixed, relative_drift, ReferenceReserve},
+    reserve_config, MAX_IR_MOD, MIN_IR_MOD, SCALAR_12, SCALAR_7,
+};
+
+const ONE_WEEK: u64 = 7 * 24 * 60 * 60;
+const ONE_YEAR: u64 = 31_536_000;
+
+/// The max drift of a single accrual from the reference, 10 ppm
+fn max_accrual_drift() -> BigRational {
+    from_fixed(1, 100_000)
+}
+
+/// The max cumulative drift of a reserve's rates from the reference over a year of accruals, 0.1%
+fn max_rate_drift() -> BigRational {
+    from_fixed(1, 1_000)
+}
+
+proptest! {
+    #[test]
+    fn test_calc_accrual_matches_reference(
+        config in reserve_config(),
+        util in 0i128..=SCALAR_7,
+        ir_mod in MIN_IR_MOD..=MAX_IR_MOD,
+        delta_time in 1u64..=ONE_YEAR,
+    ) {
+        let e = Env::default();
+        let (accrual, new_ir_mod) = calc_accrual(&e, &config, util, ir_mod, 0, delta_time);
+        let (ref_accrual, ref_ir_mod) = reference::calc_accrual(
+            &config,
+            &from_fixed(util, SCALAR_7),
+            &from_fixed(ir_mod, SCALAR_7),
+            delta_time,
+        );
+
+        prop_assert!(relative_drift(accrual, SCALAR_12, &ref_accrual) <= max_accrual_drift());
+        // the rate modifier is rounded once, so it is within a single unit of the reference
+        let ir_mod_diff = (from_fixed(new_ir_mod, SCALAR_7) - ref_ir_mod).abs();
+        prop_assert!(ir_mod_diff <= from_fixed(1, SCALAR_7));
+    }
+
+    #[test]
+    fn test_reserve_rates_match_reference(
+        config in reserve_config(),
+        b_rate in SCALAR_12..=10 * SCALAR_12,
+        d_rate in SCALAR_12..=10 * SCALAR_12,
+        ir_mod in MIN_IR_MOD..=MAX_IR_MOD,
+        b_supply in 1_000_000_000i128..=1_000_000_000_000_000_000,
+        util in 1i128..=SCALAR_7,
+        bstop_rate in 0u32..=0_5000000,
+        steps in prop::collection::vec(1u64..=ONE_WEEK, 1..=52),
+    ) {
+        let e = Env::default();
+        e.ledger().set_timestamp(1_000_000);
+        let pool = create_pool(&e);
+        let asset = Address::generate(&e);
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 0,
+            bstop_rate,
+            status: 0,
+            max_positions: 4,
+            min_debt: 0,
+            base: None,
+        };
+        // size the liabilities to the requested utilization
+        let d_supply = (b_supply * b_rate / d_rate * util / SCALAR_7).max(1);
+        let data = ReserveData {
+            b_rate,
+            d_rate,
+            ir_mod,
+            b_supply,
+            d_supply,
+            backstop_credit: 0,
+            last_time: e.ledger().timestamp(),
+        };
+        e.as_contract(&pool, || {
+            e.storage()
+                .persistent()
+                .set(&PoolDataKey::ResConfig(asset.clone()), &config);
+            e.storage()
+                .persistent()
+                .set(&PoolDataKey::ResData(asset.clone()), &data);
+        });
+
+        let mut reference = ReferenceReserve {
+            b_rate: from_fixed(b_rate, SCALAR_12),
+            d_rate: from_fixed(d_rate, SCALAR_12),
+            ir_mod: from_fixed(ir_mod, SCALAR_7),
+            b_supply: BigRational::from_integer(BigInt::from(b_supply)),
+            d_supply: BigRational::from_integer(BigInt::from(d_supply)),
+            backstop_credit: from_fixed(0, 1),
+        };
+        for delta_time in steps {
+            e.ledger().set_timestamp(e.ledger().timestamp() + delta_time);
+            let reserve = e.as_contract(&pool, || {
+                let reserve = ReserveState::load(&e, &pool_config, &asset);
+                e.storage()
+                    .persistent()
+                    .set(&PoolDataKey::ResData(asset.clone()), &reserve.data);
+                reserve
+            });
+            reference.accrue(&config, bstop_rate, delta_time);
+
+            let d_rate_drift = relative_drift(reserve.data.d_rate, SCALAR_12, &reference.d_rate);
+            prop_assert!(d_rate_drift <= max_rate_drift());
+            let b_rate_drift = relative_drift(reserve.data.b_rate, SCALAR_12, &reference.b_rate);
+            prop_assert!(b_rate_drift <= max_rate_drift());
+            let ir_mod_diff =
+                (from_fixed(reserve.data.ir_mod, SCALAR_7) - &reference.ir_mod).abs();
+            prop_assert!(ir_mod_diff <= max_rate_drift());
+        }
+    }
+
+    #[test]
+    fn test_auction_scaling_matches_reference(
+        amount in 0i128..=1_000_000_000_000_000_000,
+        percent_filled in 1u64..=100,
+        curve in prop_oneof![
+            Just(AuctionCurve::Linear),
+            Just(AuctionCurve::Exponential),
+            Just(AuctionCurve::Stepwise),
+        ],
+        block_dif in 0u32..=500,
+    ) {
+        let (bid_modifier, lot_modifier) = get_curve_modifiers(curve, block_dif);
+        let filled = from_fixed(amount * percent_filled as i128, 100);
+        let two = from_fixed(2, 1);
+
+        // the bid is rounded up twice, so it is at most 2 units over the reference
+        let (bid, _) = scale_bid_amount(amount, percent_filled, bid_modifier).unwrap();
+        let ref_bid = &filled * from_fixed(bid_modifier, SCALAR_7);
+        let bid_diff = from_fixed(bid, 1) - ref_bid;
+        prop_assert!(bid_diff >= from_fixed(0, 1) && bid_diff < two);
+
+        // the lot is rounded down twice, so it is at most 2 units under the reference
+        let (lot, _) = scale_lot_amount(amount, percent_filled, lot_modifier).unwrap();
+        let ref_lot = &filled * from_fixed(lot_modifier, SCALAR_7);
+        let lot_diff = ref_lot - from_fixed(lot, 1);
+        prop_assert!(lot_diff >= from_fixed(0, 1) && lot_diff < two);
+    }
+}
@@ -0,0 +1,57 @@
+use pool::calc_accrual;
+use proptest::prelude::*;
+use soroban_sdk::Env;
+use test_suites_proptest::{reserve_config, MAX_IR_MOD, MIN_IR_MOD, SCALAR_12, SCALAR_7};
+
+const ONE_YEAR: u64 = 31_536_000;
+
+proptest! {
+    #[test]
+    fn test_accrual_never_decreases_rates(
+        config in reserve_config(),
+        util in 0i128..=SCALAR_7,
+        ir_mod in MIN_IR_MOD..=MAX_IR_MOD,
+        delta_time in 1u64..=ONE_YEAR,
+    ) {
+        let e = Env::default();
+        let (accrual, new_ir_mod) = calc_accrual(&e, &config, util, ir_mod, 0, delta_time);
+
+        prop_assert!(accrual >= SCALAR_12);
+        prop_assert!(new_ir_mod >= MIN_IR_MOD);
+        prop_assert!(new_ir_mod <= MAX_IR_MOD);
+    }
+
+    #[test]
+    fn test_accrual_monotonic_in_time(
+        config in reserve_config(),
+        util in 0i128..=SCALAR_7,
+        ir_mod in MIN_IR_MOD..=MAX_IR_MOD,
+        delta_time in 1u64..=ONE_YEAR,
+        extra_time in 0u64..=ONE_YEAR,
+    ) {
+        let e = Env::default();
+        let (accrual, _) = calc_accrual(&e, &config, util, ir_mod, 0, delta_time);
+        let (later_accrual, _) =
+            calc_accrual(&e, &config, util, ir_mod, 0, delta_time + extra_time);
+
+        prop_assert!(later_accrual >= accrual);
+    }
+
+    #[test]
+    fn test_accrual_monotonic_in_util(
+        config in reserve_config(),
+        util in 0i128..=SCALAR_7,
+        extra_util in 0i128..=SCALAR_7,
+        ir_mod in MIN_IR_MOD..=MAX_IR_MOD,
+        delta_time in 1u64..=ONE_YEAR,
+    ) {
+        let e = Env::default();
+        let higher_util = (util + extra_util).min(SCALAR_7);
+        let (accrual, ir_mod_0) = calc_accrual(&e, &config, util, ir_mod, 0, delta_time);
+        let (higher_accrual, ir_mod_1) =
+            calc_accrual(&e, &config, higher_util, ir_mod, 0, delta_time);
+
+        prop_assert!(higher_accrual >= accrual);
+        prop_assert!(ir_mod_1 >= ir_mod_0);
+    }
+}
@@ -0,0 +1,106 @@
+use pool::{Positions, User};
+use proptest::prelude::*;
+use soroban_sdk::{testutils::Address as _, Address, Env};
+use test_suites_proptest::{create_pool, create_reserve, reserve_config, reserve_data};
+
+proptest! {
+    #[test]
+    fn test_b_token_conversions_favor_pool(
+        config in reserve_config(),
+        data in reserve_data(),
+        amount in 0i128..=1_000_000_000_000_000_000,
+    ) {
+        let e = Env::default();
+        let reserve = create_reserve(&e, config, data);
+
+        // supplying mints bTokens rounded down, so they are never worth more than the deposit
+        let minted = reserve.to_b_token_down(&e, amount);
+        prop_assert!(reserve.to_asset_from_b_token(&e, minted) <= amount);
+
+        // withdrawing burns bTokens rounded up, so they are always worth at least the withdrawal
+        let burnt = reserve.to_b_token_up(&e, amount);
+        prop_assert!(reserve.to_asset_from_b_token(&e, burnt) >= amount);
+        prop_assert!(burnt >= minted);
+    }
+
+    #[test]
+    fn test_d_token_conversions_favor_pool(
+        config in reserve_config(),
+        data in reserve_data(),
+        amount in 0i128..=1_000_000_000_000_000_000,
+    ) {
+        let e = Env::default();
+        let reserve = create_reserve(&e, config, data);
+
+        // borrowing mints dTokens rounded up, so the debt is always worth at least the borrow
+        let minted = reserve.to_d_token_up(&e, amount);
+        prop_assert!(reserve.to_asset_from_d_token(&e, minted) >= amount);
+
+        // repaying burns dTokens rounded down, so they are never worth more than the repayment
+        let burnt = reserve.to_d_token_down(&e, amount);
+        prop_assert!(reserve.to_asset_from_d_token(&e, burnt) <= amount);
+        prop_assert!(minted >= burnt);
+    }
+
+    #[test]
+    fn test_positions_add_then_remove_restores_balances(
+        config in reserve_config(),
+        data in reserve_data(),
+        collateral in prop::collection::vec(1i128..=1_000_000_000_000, 1..5),
+        supply in prop::collection::vec(1i128..=1_000_000_000_000, 1..5),
+        liabilities in prop::collection::vec(1i128..=1_000_000_000_000, 1..5),
+    ) {
+        let e = Env::default();
+        let pool = create_pool(&e);
+        let mut reserve = create_reserve(&e, config, data.clone());
+
+        e.as_contract(&pool, || {
+            let mut user = User {
+                address: Address::generate(&e),
+                positions: Positions::env_default(&e),
+                stable_liabilities: None,
+                is_legacy: false,
+            };
+            for amount in collateral.iter() {
+                user.add_collateral(&e, &mut reserve, *amount);
+            }
+            for amount in supply.iter() {
+                user.add_supply(&e, &mut reserve, *amount);
+            }
+            for amount in liabilities.iter() {
+                user.add_liabilities(&e, &mut reserve, *amount);
+            }
+
+            let total_collateral: i128 = collateral.iter().sum();
+            let total_supply: i128 = supply.iter().sum();
+            let total_liabilities: i128 = liabilities.iter().sum();
+            assert_eq!(user.get_collateral(0), total_collateral);
+            assert_eq!(user.get_supply(0), total_supply);
+            assert_eq!(user.get_total_supply(0), total_collateral + total_supply);
+            assert_eq!(user.get_liabilities(0), total_liabilities);
+            assert_eq!(
+                reserve.data.b_supply,
+                data.b_supply + total_collateral + total_supply
+            );
+            assert_eq!(reserve.data.d_supply, data.d_supply + total_liabilities);
+
+            // remove the positions in a different order than they were added
+            for amount in collateral.iter().rev() {
+                user.remove_collateral(&e, &mut reserve, *amount);
+            }
+            for amount in supply.iter().rev() {
+                user.remove_supply(&e, &mut reserve, *amount);
+            }
+            for amount in liabilities.iter().rev() {
+                user.remove_liabilities(&e, &mut reserve, *amount);
+            }
+
+            assert!(!user.has_collateral());
+            assert!(!user.has_liabilities());
+            assert_eq!(user.get_supply(0), 0);
+            assert_eq!(user.positions.effective_count(), 0);
+            assert_eq!(reserve.data.b_supply, data.b_supply);
+            assert_eq!(reserve.data.d_supply, data.d_supply);
+        });
+    }
+}
@@ -0,0 +1,61 @@
+use soroban_sdk::Env;
+
+/// The max CPU instructions a single transaction can consume
+pub const MAX_TX_CPU: u64 = 100_000_000;
+/// The max memory bytes a single transaction can consume
+pub const MAX_TX_MEM: u64 = 41_943_040;
+
+/// The budget consumed by a contract call
+#[derive(Copy, Clone, Debug)]
+pub struct BudgetUsage {
+    pub cpu: u64,
+    pub mem: u64,
+}
+
+/// A regression threshold for the budget a hot path can consume
+#[derive(Copy, Clone, Debug)]
+pub struct BudgetLimit {
+    pub cpu: u64,
+    pub mem: u64,
+}
+
+/// Measure the budget consumed by `call`
+///
+/// The budget is reset to the transaction limits before `call` runs, so `call` panics if it exceeds
+/// them. The budget is left unlimited afterwards so setup for later calls is not metered.
+pub fn measure<T>(env: &Env, call: impl FnOnce() -> T) -> (T, BudgetUsage) {
+    env.cost_estimate()
+        .budget()
+        .reset_limits(MAX_TX_CPU, MAX_TX_MEM);
+    let result = call();
+    let budget = env.cost_estimate().budget();
+    let usage = BudgetUsage {
+        cpu: budget.cpu_instruction_cost(),
+        mem: budget.memory_bytes_cost(),
+    };
+    budget.reset_unlimited();
+    (result, usage)
+}
+
+/// Assert the budget consumed by a hot path is under its regression threshold
+///
+/// ### Arguments
+/// * `name` - The name of the hot path, included in the failure message
+/// * `usage` - The budget consumed by the hot path
+/// * `limit` - The regression threshold for the hot path
+pub fn assert_budget_under(name: &str, usage: BudgetUsage, limit: BudgetLimit) {
+    assert!(
+        usage.cpu <= limit.cpu,
+        "{} consumed {} cpu instructions, over the threshold of {}",
+        name,
+        usage.cpu,
+        limit.cpu
+    );
+    assert!(
+        usage.mem <= limit.mem,
+        "{} consumed {} memory bytes, over the threshold of {}",
+        name,
+        usage.mem,
+        limit.mem
+    );
+}
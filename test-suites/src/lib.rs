@@ -1,12 +1,16 @@
 #![allow(clippy::all)]
 pub mod backstop;
+pub mod budget;
 pub mod emitter;
 pub mod liquidity_pool;
 pub mod oracle;
 pub mod pool;
 pub mod pool_factory;
+pub mod scenario;
 mod setup;
-pub use setup::create_fixture_with_data;
+pub use setup::{
+    create_fixture_with_data, create_fixture_with_pools, create_fixture_with_scenario,
+};
 pub mod assertions;
 pub mod moderc3156;
 pub mod snapshot;
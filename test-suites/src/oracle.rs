@@ -1,5 +1,6 @@
 use soroban_sdk::{testutils::Address as _, Address, Env};
 
+use mock_oracle::{MockOracle, MockOracleClient};
 use sep_40_oracle::testutils::{MockPriceOracleClient, MockPriceOracleWASM};
 
 pub fn create_mock_oracle<'a>(e: &Env) -> (Address, MockPriceOracleClient<'a>) {
@@ -10,3 +11,9 @@ pub fn create_mock_oracle<'a>(e: &Env) -> (Address, MockPriceOracleClient<'a>) {
         MockPriceOracleClient::new(e, &contract_id),
     )
 }
+
+/// Create a mock oracle that supports price schedules and outages
+pub fn create_programmable_oracle<'a>(e: &Env) -> (Address, MockOracleClient<'a>) {
+    let contract_id = e.register(MockOracle {}, ());
+    (contract_id.clone(), MockOracleClient::new(e, &contract_id))
+}
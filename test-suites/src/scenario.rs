@@ -0,0 +1,255 @@
+//! A declarative builder for multi-actor simulations against a `TestFixture`
+//!
+//! A `Scenario` is a list of steps (actor creation, time jumps, price changes and pool actions) that
+//! is executed in order against a fixture's pool. Steps refer to actors by name, so the same scenario
+//! can be reused across tests and run against any fixture.
+
+use std::collections::HashMap;
+
+use pool::{Request, RequestType};
+use sep_40_oracle::testutils::Asset;
+use soroban_sdk::{testutils::Address as _, vec as svec, Address, Vec as SVec};
+
+use crate::test_fixture::{TestFixture, TokenIndex};
+
+/// The actors participating in a scenario, by name
+///
+/// The fixture's whale, `fixture.users[0]`, is always available as "frodo".
+pub struct ScenarioActors {
+    actors: HashMap<&'static str, Address>,
+}
+
+impl ScenarioActors {
+    fn new(fixture: &TestFixture) -> Self {
+        let mut actors = HashMap::new();
+        actors.insert("frodo", fixture.users[0].clone());
+        ScenarioActors { actors }
+    }
+
+    /// Get the address of an actor
+    ///
+    /// ### Panics
+    /// If no actor with the name exists
+    pub fn get(&self, name: &str) -> &Address {
+        self.actors
+            .get(name)
+            .unwrap_or_else(|| panic!("scenario actor {} does not exist", name))
+    }
+}
+
+/// A check run against the fixture in the middle of a scenario
+pub type ScenarioCheck = Box<dyn Fn(&TestFixture, &ScenarioActors)>;
+
+enum Step {
+    Actor(&'static str, Vec<(TokenIndex, i128)>),
+    Jump(u64),
+    JumpWithSequence(u64),
+    Price(TokenIndex, i128),
+    Submit(&'static str, Vec<(RequestType, TokenIndex, i128)>),
+    NewLiquidation(&'static str, Vec<TokenIndex>, Vec<TokenIndex>, u32),
+    FillLiquidation(&'static str, &'static str, i128),
+    Check(ScenarioCheck),
+}
+
+/// A sequence of steps to run against a pool in a `TestFixture`
+pub struct Scenario {
+    pool_index: usize,
+    steps: Vec<Step>,
+}
+
+impl Scenario {
+    /// Create an empty scenario for the first pool in the fixture
+    pub fn new() -> Self {
+        Scenario::for_pool(0)
+    }
+
+    /// Create an empty scenario for the pool at `pool_index` in the fixture
+    pub fn for_pool(pool_index: usize) -> Self {
+        Scenario {
+            pool_index,
+            steps: Vec::new(),
+        }
+    }
+
+    /// Create a new actor and mint them the given token balances
+    pub fn actor(mut self, name: &'static str, balances: &[(TokenIndex, i128)]) -> Self {
+        self.steps.push(Step::Actor(name, balances.to_vec()));
+        self
+    }
+
+    /// Move the ledger timestamp forward by `time` seconds
+    pub fn jump(mut self, time: u64) -> Self {
+        self.steps.push(Step::Jump(time));
+        self
+    }
+
+    /// Move the ledger timestamp forward by `time` seconds, and the sequence by one ledger every 5 seconds
+    pub fn jump_with_sequence(mut self, time: u64) -> Self {
+        self.steps.push(Step::JumpWithSequence(time));
+        self
+    }
+
+    /// Set the oracle price of an asset, leaving the other prices unchanged
+    pub fn price(mut self, asset: TokenIndex, price: i128) -> Self {
+        self.steps.push(Step::Price(asset, price));
+        self
+    }
+
+    /// Walk the oracle price of an asset through `prices`, jumping `interval` seconds after each price
+    pub fn price_path(mut self, asset: TokenIndex, prices: &[i128], interval: u64) -> Self {
+        for price in prices {
+            self = self.price(asset, *price).jump(interval);
+        }
+        self
+    }
+
+    /// Submit requests to the pool as the actor, as (request type, asset, amount)
+    pub fn submit(
+        mut self,
+        actor: &'static str,
+        requests: &[(RequestType, TokenIndex, i128)],
+    ) -> Self {
+        self.steps.push(Step::Submit(actor, requests.to_vec()));
+        self
+    }
+
+    /// Create a liquidation auction for `percent` of the user's position
+    pub fn new_liquidation(
+        mut self,
+        user: &'static str,
+        bid: &[TokenIndex],
+        lot: &[TokenIndex],
+        percent: u32,
+    ) -> Self {
+        self.steps.push(Step::NewLiquidation(
+            user,
+            bid.to_vec(),
+            lot.to_vec(),
+            percent,
+        ));
+        self
+    }
+
+    /// Fill `percent` of the user's liquidation auction as the filler
+    pub fn fill_liquidation(
+        mut self,
+        filler: &'static str,
+        user: &'static str,
+        percent: i128,
+    ) -> Self {
+        self.steps
+            .push(Step::FillLiquidation(filler, user, percent));
+        self
+    }
+
+    /// Run a check against the fixture at this point in the scenario
+    pub fn check(mut self, check: impl Fn(&TestFixture, &ScenarioActors) + 'static) -> Self {
+        self.steps.push(Step::Check(Box::new(check)));
+        self
+    }
+
+    /// Append the steps built by `steps` to the scenario `count` times
+    pub fn repeat(mut self, count: u32, steps: impl Fn(Scenario) -> Scenario) -> Self {
+        for _ in 0..count {
+            self = steps(self);
+        }
+        self
+    }
+
+    /// Run the scenario against the fixture
+    ///
+    /// Returns the actors created by the scenario
+    ///
+    /// ### Panics
+    /// If any step fails, or a step refers to an actor before it is created
+    pub fn run(self, fixture: &TestFixture) -> ScenarioActors {
+        let mut actors = ScenarioActors::new(fixture);
+        let pool = &fixture.pools[self.pool_index].pool;
+        for step in self.steps {
+            match step {
+                Step::Actor(name, balances) => {
+                    let address = Address::generate(&fixture.env);
+                    for (asset, amount) in balances {
+                        fixture.tokens[asset].mint(&address, &amount);
+                    }
+                    actors.actors.insert(name, address);
+                }
+                Step::Jump(time) => fixture.jump(time),
+                Step::JumpWithSequence(time) => fixture.jump_with_sequence(time),
+                Step::Price(asset, price) => set_price(fixture, asset, price),
+                Step::Submit(actor, requests) => {
+                    let mut pool_requests: SVec<Request> = svec![&fixture.env];
+                    for (request_type, asset, amount) in requests {
+                        pool_requests.push_back(Request {
+                            request_type: request_type as u32,
+                            address: fixture.tokens[asset].address.clone(),
+                            amount,
+                        });
+                    }
+                    let address = actors.get(actor);
+                    pool.submit(address, address, address, &pool_requests);
+                }
+                Step::NewLiquidation(user, bid, lot, percent) => {
+                    let mut bid_assets = svec![&fixture.env];
+                    for asset in bid {
+                        bid_assets.push_back(fixture.tokens[asset].address.clone());
+                    }
+                    let mut lot_assets = svec![&fixture.env];
+                    for asset in lot {
+                        lot_assets.push_back(fixture.tokens[asset].address.clone());
+                    }
+                    pool.new_auction(&0, actors.get(user), &bid_assets, &lot_assets, &percent);
+                }
+                Step::FillLiquidation(filler, user, percent) => {
+                    let requests = svec![
+                        &fixture.env,
+                        Request {
+                            request_type: RequestType::FillUserLiquidationAuction as u32,
+                            address: actors.get(user).clone(),
+                            amount: percent,
+                        },
+                    ];
+                    let address = actors.get(filler);
+                    pool.submit(address, address, address, &requests);
+                }
+                Step::Check(check) => check(fixture, &actors),
+            }
+        }
+        actors
+    }
+}
+
+impl Default for Scenario {
+    fn default() -> Self {
+        Scenario::new()
+    }
+}
+
+/// Set the price of `asset` on the fixture's oracle, keeping the current price of every other asset
+fn set_price(fixture: &TestFixture, asset: TokenIndex, price: i128) {
+    // the oracle's asset order, as set in `TestFixture::create`
+    let oracle_assets = [
+        TokenIndex::WETH,
+        TokenIndex::USDC,
+        TokenIndex::XLM,
+        TokenIndex::STABLE,
+    ];
+    if !oracle_assets.contains(&asset) {
+        panic!("scenario asset {:?} has no oracle price", asset);
+    }
+    let mut prices = svec![&fixture.env];
+    for oracle_asset in oracle_assets {
+        if oracle_asset == asset {
+            prices.push_back(price);
+        } else {
+            let address = fixture.tokens[oracle_asset].address.clone();
+            let current = fixture
+                .oracle
+                .lastprice(&Asset::Stellar(address))
+                .unwrap()
+                .price;
+            prices.push_back(current);
+        }
+    }
+    fixture.oracle.set_price_stable(&prices);
+}
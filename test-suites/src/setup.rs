@@ -3,6 +3,7 @@ use soroban_sdk::{vec as svec, String, Vec as SVec};
 
 use crate::{
     pool::default_reserve_metadata,
+    scenario::{Scenario, ScenarioActors},
     test_fixture::{TestFixture, TokenIndex, SCALAR_7},
 };
 
@@ -34,18 +35,90 @@ pub fn create_fixture_with_data<'a>(wasm: bool) -> TestFixture<'a> {
         1_0000000,
     );
 
+    setup_pool_reserves(&mut fixture, 0);
+    let pool_fixture = &fixture.pools[0];
+
+    // deposit into backstop, add to reward zone
+    fixture
+        .backstop
+        .deposit(&frodo, &pool_fixture.pool.address, &(50_000 * SCALAR_7));
+    fixture
+        .backstop
+        .add_reward(&pool_fixture.pool.address, &None);
+    pool_fixture.pool.set_status(&3);
+    pool_fixture.pool.update_status();
+
+    // enable emissions
+    fixture.emitter.distribute();
+    fixture.backstop.distribute();
+    pool_fixture.pool.gulp_emissions();
+
+    fixture.jump(60);
+
+    setup_whale_positions(&fixture, 0);
+
+    fixture.jump(60 * 60); // 1 hr
+
+    fixture.env.cost_estimate().budget().reset_unlimited();
+    fixture
+}
+
+/// Create a test fixture with data, and `num_pools - 1` more pools set up the same way as the first
+/// pool. Every pool shares the fixture's backstop and emitter, has 50k backstop tokens deposited, and
+/// is in the reward zone with emissions accrued.
+pub fn create_fixture_with_pools<'a>(wasm: bool, num_pools: usize) -> TestFixture<'a> {
+    let mut fixture = create_fixture_with_data(wasm);
+    let frodo = fixture.users[0].clone();
+
+    // the reward zone can only be updated shortly after a distribution
+    fixture.distribute_emissions();
+    for pool_index in 1..num_pools {
+        let name = format!("Teapot {}", pool_index);
+        fixture.create_pool(
+            String::from_str(&fixture.env, &name),
+            0_1000000,
+            6,
+            1_0000000,
+        );
+        setup_pool_reserves(&mut fixture, pool_index);
+
+        fixture.backstop_deposit(pool_index, &frodo, 50_000 * SCALAR_7);
+        fixture.add_pool_to_reward_zone(pool_index, None);
+        let pool = &fixture.pools[pool_index].pool;
+        pool.set_status(&3);
+        pool.update_status();
+
+        setup_whale_positions(&fixture, pool_index);
+    }
+
+    // accrue emissions to the new pools
+    fixture.jump(60);
+    fixture.distribute_emissions();
+    for pool_index in 1..num_pools {
+        fixture.pools[pool_index].pool.gulp_emissions();
+    }
+
+    fixture.jump(60 * 60); // 1 hr
+
+    fixture.env.cost_estimate().budget().reset_unlimited();
+    fixture
+}
+
+/// Create the STABLE, XLM, and WETH reserves for a pool and enable emissions for STABLE borrowers and
+/// XLM suppliers
+fn setup_pool_reserves(fixture: &mut TestFixture, pool_index: usize) {
     let mut stable_config = default_reserve_metadata();
     stable_config.decimals = 6;
     stable_config.c_factor = 0_900_0000;
     stable_config.l_factor = 0_950_0000;
     stable_config.util = 0_850_0000;
-    fixture.create_pool_reserve(0, TokenIndex::STABLE, &stable_config);
+    fixture.create_pool_reserve(pool_index, TokenIndex::STABLE, &stable_config);
 
     let mut xlm_config = default_reserve_metadata();
     xlm_config.c_factor = 0_750_0000;
     xlm_config.l_factor = 0_750_0000;
     xlm_config.util = 0_500_0000;
-    fixture.create_pool_reserve(0, TokenIndex::XLM, &xlm_config);
+    fixture.create_pool_reserve(pool_index, TokenIndex::XLM, &xlm_config);
 
     let mut weth_config = default_reserve_metadata();
     weth_config.decimals = 9;
@@ -53,11 +126,9 @@ pub fn create_fixture_with_data<'a>(wasm: bool) -> TestFixture<'a> {
     weth_config.l_factor = 0_800_0000;
     weth_config.util = 0_700_0000;
     weth_config.supply_cap = i128::MAX;
-    fixture.create_pool_reserve(0, TokenIndex::WETH, &weth_config);
+    fixture.create_pool_reserve(pool_index, TokenIndex::WETH, &weth_config);
 
     // enable emissions for pool
-    let pool_fixture = &fixture.pools[0];
-
     let reserve_emissions: soroban_sdk::Vec<ReserveEmissionMetadata> = soroban_sdk::vec![
         &fixture.env,
         ReserveEmissionMetadata {
@@ -71,24 +142,15 @@ pub fn create_fixture_with_data<'a>(wasm: bool) -> TestFixture<'a> {
             share: 0_400_0000
         },
     ];
-    pool_fixture.pool.set_emissions_config(&reserve_emissions);
-
-    // deposit into backstop, add to reward zone
-    fixture
-        .backstop
-        .deposit(&frodo, &pool_fixture.pool.address, &(50_000 * SCALAR_7));
-    fixture
-        .backstop
-        .add_reward(&pool_fixture.pool.address, &None);
-    pool_fixture.pool.set_status(&3);
-    pool_fixture.pool.update_status();
-
-    // enable emissions
-    fixture.emitter.distribute();
-    fixture.backstop.distribute();
-    pool_fixture.pool.gulp_emissions();
+    fixture.pools[pool_index]
+        .pool
+        .set_emissions_config(&reserve_emissions);
+}
 
-    fixture.jump(60);
+/// Supply and borrow each reserve of a pool with the whale
+fn setup_whale_positions(fixture: &TestFixture, pool_index: usize) {
+    let frodo = &fixture.users[0];
+    let pool = &fixture.pools[pool_index].pool;
 
     // supply and borrow STABLE for 80% utilization (close to target)
     let requests: SVec<Request> = svec![
@@ -104,7 +166,7 @@ pub fn create_fixture_with_data<'a>(wasm: bool) -> TestFixture<'a> {
             amount: 8_000 * 10i128.pow(6),
         },
     ];
-    pool_fixture.pool.submit(&frodo, &frodo, &frodo, &requests);
+    pool.submit(frodo, frodo, frodo, &requests);
 
     // supply and borrow WETH for 50% utilization (below target)
     let requests: SVec<Request> = svec![
@@ -120,7 +182,7 @@ pub fn create_fixture_with_data<'a>(wasm: bool) -> TestFixture<'a> {
             amount: 5 * 10i128.pow(9),
         },
     ];
-    pool_fixture.pool.submit(&frodo, &frodo, &frodo, &requests);
+    pool.submit(frodo, frodo, frodo, &requests);
 
     // supply and borrow XLM for 65% utilization (above target)
     let requests: SVec<Request> = svec![
@@ -136,18 +198,26 @@ pub fn create_fixture_with_data<'a>(wasm: bool) -> TestFixture<'a> {
             amount: 65_000 * SCALAR_7,
         },
     ];
-    pool_fixture.pool.submit(&frodo, &frodo, &frodo, &requests);
-
-    fixture.jump(60 * 60); // 1 hr
+    pool.submit(frodo, frodo, frodo, &requests);
+}
 
-    fixture.env.cost_estimate().budget().reset_unlimited();
-    fixture
+/// Create a test fixture with data and run a scenario against its pool
+///
+/// Returns the fixture and the actors created by the scenario
+pub fn create_fixture_with_scenario<'a>(
+    wasm: bool,
+    scenario: Scenario,
+) -> (TestFixture<'a>, ScenarioActors) {
+    let fixture = create_fixture_with_data(wasm);
+    let actors = scenario.run(&fixture);
+    (fixture, actors)
 }
 
 #[cfg(test)]
 mod tests {
 
     use crate::test_fixture::PoolFixture;
+    use pool::{reserve_seed_amount, RESERVE_SEED_AMOUNT};
 
     use super::*;
 
@@ -169,15 +239,15 @@ mod tests {
 
         // validate pool actions
         assert_eq!(
-            2_000 * 10i128.pow(6),
+            2_000 * 10i128.pow(6) + reserve_seed_amount(6),
             fixture.tokens[TokenIndex::STABLE].balance(&pool_fixture.pool.address)
         );
         assert_eq!(
-            35_000 * SCALAR_7,
+            35_000 * SCALAR_7 + RESERVE_SEED_AMOUNT,
             fixture.tokens[TokenIndex::XLM].balance(&pool_fixture.pool.address)
         );
         assert_eq!(
-            5 * 10i128.pow(9),
+            5 * 10i128.pow(9) + reserve_seed_amount(9),
             fixture.tokens[TokenIndex::WETH].balance(&pool_fixture.pool.address)
         );
 
@@ -226,15 +296,15 @@ mod tests {
 
         // validate pool actions
         assert_eq!(
-            2_000 * 10i128.pow(6),
+            2_000 * 10i128.pow(6) + reserve_seed_amount(6),
             fixture.tokens[TokenIndex::STABLE].balance(&pool_fixture.pool.address)
         );
         assert_eq!(
-            35_000 * SCALAR_7,
+            35_000 * SCALAR_7 + RESERVE_SEED_AMOUNT,
             fixture.tokens[TokenIndex::XLM].balance(&pool_fixture.pool.address)
         );
         assert_eq!(
-            5 * 10i128.pow(9),
+            5 * 10i128.pow(9) + reserve_seed_amount(9),
             fixture.tokens[TokenIndex::WETH].balance(&pool_fixture.pool.address)
         );
 
@@ -264,4 +334,36 @@ mod tests {
             emis_data.expiration
         )
     }
+
+    #[test]
+    fn test_create_fixture_with_pools() {
+        let fixture = create_fixture_with_pools(false, 3);
+        assert_eq!(fixture.pools.len(), 3);
+
+        for pool_index in 0..3 {
+            let pool_fixture = &fixture.pools[pool_index];
+            assert!(fixture.is_in_reward_zone(pool_index));
+            assert_eq!(
+                fixture.read_pool_backstop_data(pool_index).tokens,
+                50_000 * SCALAR_7
+            );
+            assert_eq!(
+                2_000 * 10i128.pow(6) + reserve_seed_amount(6),
+                fixture.tokens[TokenIndex::STABLE].balance(&pool_fixture.pool.address)
+            );
+        }
+        assert_eq!(
+            150_000 * SCALAR_7,
+            fixture.lp.balance(&fixture.backstop.address)
+        );
+
+        // pools with equal backstop deposits compete equally for emissions, and the first pool
+        // has been accruing since before the others joined the reward zone
+        fixture.jump(24 * 60 * 60 + 60);
+        fixture.distribute_emissions();
+        let emissions = fixture.gulp_emissions();
+        assert!(emissions[1] > 0);
+        assert_eq!(emissions[1], emissions[2]);
+        assert!(emissions[0] > emissions[1]);
+    }
 }
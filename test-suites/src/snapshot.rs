@@ -23,5 +23,6 @@ pub fn env_from_snapshot() -> Env {
     env.set_config(EnvTestConfig {
         capture_snapshot_at_drop: false,
     });
+    env.cost_estimate().budget().reset_unlimited();
     env
 }
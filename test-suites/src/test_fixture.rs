@@ -4,15 +4,20 @@ use std::ops::Index;
 use crate::backstop::create_backstop;
 use crate::emitter::create_emitter;
 use crate::liquidity_pool::{create_lp_pool, LPClient};
-use crate::oracle::create_mock_oracle;
+use crate::oracle::{create_mock_oracle, create_programmable_oracle};
 use crate::pool::POOL_WASM;
 use crate::pool_factory::create_pool_factory;
 use crate::token::{create_stellar_token, create_token};
-use backstop::BackstopClient;
+use backstop::{BackstopClient, PoolBackstopData};
 use blend_contract_sdk::emitter::Client as EmitterClient;
-use pool::{PoolClient, PoolConfig, PoolDataKey, ReserveConfig, ReserveData, ReserveEmissionData};
+use mock_oracle::MockOracleClient;
+use pool::{
+    reserve_seed_amount, PoolClient, PoolConfig, PoolDataKey, ReserveConfig, ReserveData,
+    ReserveEmissionData,
+};
 use pool_factory::{PoolFactoryClient, PoolInitMeta};
 use sep_40_oracle::testutils::{Asset, MockPriceOracleClient};
+use sep_40_oracle::Asset as OracleAsset;
 use sep_41_token::testutils::MockTokenClient;
 use soroban_sdk::testutils::{Address as _, BytesN as _, EnvTestConfig, Ledger, LedgerInfo};
 use soroban_sdk::{vec as svec, Address, BytesN, Env, Map, String, Symbol};
@@ -206,11 +211,113 @@ impl TestFixture<'_> {
         pool_fixture
             .pool
             .queue_set_reserve(&token.address, reserve_config);
+        // the admin seeds the reserve when it is set
+        token.mint(
+            &self.bombadil,
+            &reserve_seed_amount(reserve_config.decimals),
+        );
         let index = pool_fixture.pool.set_reserve(&token.address);
         pool_fixture.reserves.insert(asset_index, index);
         self.pools.insert(pool_index, pool_fixture);
     }
 
+    /// Replace the oracle of a pool with a programmable mock oracle, which supports price schedules
+    /// and outages. The new oracle prices the same assets at the current prices of the fixture's oracle.
+    pub fn use_programmable_oracle(&self, pool_index: usize) -> MockOracleClient<'_> {
+        let (oracle_id, oracle_client) = create_programmable_oracle(&self.env);
+        let mut assets = svec![&self.env];
+        let mut prices = svec![&self.env];
+        for token_index in [
+            TokenIndex::WETH,
+            TokenIndex::USDC,
+            TokenIndex::XLM,
+            TokenIndex::STABLE,
+        ] {
+            let address = self.tokens[token_index].address.clone();
+            let price = self
+                .oracle
+                .lastprice(&Asset::Stellar(address.clone()))
+                .unwrap()
+                .price;
+            assets.push_back(OracleAsset::Stellar(address));
+            prices.push_back(price);
+        }
+        oracle_client.set_data(
+            &self.bombadil,
+            &OracleAsset::Other(Symbol::new(&self.env, "USD")),
+            &assets,
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&prices);
+
+        let pool_fixture = &self.pools[pool_index];
+        self.env.as_contract(&pool_fixture.pool.address, || {
+            let key = Symbol::new(&self.env, "Config");
+            let mut pool_config: PoolConfig = self.env.storage().instance().get(&key).unwrap();
+            pool_config.oracle = oracle_id;
+            self.env.storage().instance().set(&key, &pool_config);
+        });
+        oracle_client
+    }
+
+    /********** Multi-Pool Helpers **********/
+
+    /// Deposit backstop tokens from `from` into the backstop of a pool
+    ///
+    /// Returns the backstop shares minted
+    pub fn backstop_deposit(&self, pool_index: usize, from: &Address, amount: i128) -> i128 {
+        let pool_fixture = &self.pools[pool_index];
+        self.backstop
+            .deposit(from, &pool_fixture.pool.address, &amount)
+    }
+
+    /// Add a pool to the backstop's reward zone, replacing the pool at `to_remove` if the reward
+    /// zone is full. If the reward zone already has pools, emissions must have been distributed
+    /// within the last hour.
+    pub fn add_pool_to_reward_zone(&self, pool_index: usize, to_remove: Option<usize>) {
+        let to_remove = to_remove.map(|index| self.pools[index].pool.address.clone());
+        self.backstop
+            .add_reward(&self.pools[pool_index].pool.address, &to_remove);
+    }
+
+    /// Check if a pool is in the backstop's reward zone
+    pub fn is_in_reward_zone(&self, pool_index: usize) -> bool {
+        self.backstop
+            .reward_zone()
+            .contains(&self.pools[pool_index].pool.address)
+    }
+
+    /// Distribute emissions from the emitter to the backstop and split them between the pools in
+    /// the reward zone by their backstop deposits
+    ///
+    /// Returns the amount of emissions distributed
+    pub fn distribute_emissions(&self) -> i128 {
+        self.emitter.distribute();
+        self.backstop.distribute()
+    }
+
+    /// Gulp the emissions accrued to each pool in the reward zone. Each pool can only gulp once per day.
+    ///
+    /// Returns the emissions gulped by each pool, indexed by pool, with 0 for pools outside the reward zone
+    pub fn gulp_emissions(&self) -> Vec<i128> {
+        (0..self.pools.len())
+            .map(|pool_index| {
+                if self.is_in_reward_zone(pool_index) {
+                    self.pools[pool_index].pool.gulp_emissions()
+                } else {
+                    0
+                }
+            })
+            .collect()
+    }
+
+    /// Fetch the backstop data of a pool
+    pub fn read_pool_backstop_data(&self, pool_index: usize) -> PoolBackstopData {
+        self.backstop
+            .pool_data(&self.pools[pool_index].pool.address)
+    }
+
     /********** Contract Data Helpers **********/
 
     pub fn read_pool_config(&self, pool_index: usize) -> PoolConfig {
@@ -1,5 +1,5 @@
 #![cfg(test)]
-use pool::{Request, RequestType};
+use pool::{reserve_seed_amount, Request, RequestType};
 use soroban_fixed_point_math::FixedPoint;
 use soroban_sdk::{testutils::Address as AddressTestTrait, vec, Address};
 use test_suites::{
@@ -407,7 +407,7 @@ fn test_backstop_and_pool_failure() {
     assert_approx_eq_rel(gandalf_wd_amount, gandalf_expected_wd_amount, 0_0000100);
     assert_approx_eq_rel(frodo_wd_amount, frodo_expected_wd_amount, 0_0000100);
 
-    // verify STABLE is empty
+    // verify STABLE is empty, except for the bTokens the pool was seeded with
     let final_stable_reserve = pool_fixture.pool.get_reserve(&stable.address);
     // accrual occurs in between default since frodo still holds liabilities
     assert_approx_eq_abs(
@@ -415,5 +415,5 @@ fn test_backstop_and_pool_failure() {
         post_stable_reserve.data.b_rate,
         0_000_000_010_000,
     );
-    assert_eq!(final_stable_reserve.data.b_supply, 0);
+    assert_eq!(final_stable_reserve.data.b_supply, reserve_seed_amount(6));
 }
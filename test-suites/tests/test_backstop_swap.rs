@@ -2,7 +2,7 @@
 
 use backstop::BackstopClient;
 use blend_contract_sdk::{backstop as v1_backstop, emitter};
-use pool::{PoolClient, Request, RequestType, ReserveEmissionMetadata};
+use pool::{PoolClient, Request, RequestType, ReserveEmissionMetadata, RESERVE_SEED_AMOUNT};
 use pool_factory::{PoolFactoryClient, PoolInitMeta};
 use sep_40_oracle::testutils::Asset;
 use sep_41_token::testutils::MockTokenClient;
@@ -637,9 +637,14 @@ fn test_v1_to_v2_backstop_swap_past_max_emissions() {
     let v2_backfill_blnd_0 = pre_backstop_claim_blnd - blnd_client.balance(&v2_backstop);
     // -> backstop gets 70% of emissions, samwise is only backstop user (some rounding loss expected)
     assert_approx_eq_abs(v2_backfill_blnd_0, 7_000_000_0000000, 0_0001000);
-    // -> pool gets 30% of emissions, samwise is only pool user (some rounding loss expected)
+    // -> pool gets 30% of emissions, samwise is only pool user besides the seeded bTokens held by the pool,
+    //    which earn 1.5 BLND of XLM emissions and 3 BLND of USDC emissions (some rounding loss expected)
     let v2_backfill_pool_claim = v2_pool_client.claim(&samwise, &vec![&env, 1, 3], &samwise);
-    assert_approx_eq_abs(v2_backfill_pool_claim, 3_000_000_0000000, 0_0001000);
+    assert_approx_eq_abs(
+        v2_backfill_pool_claim,
+        3_000_000_0000000 - 4_5000000,
+        0_0001000,
+    );
     let v2_backfill_frodo_claim =
         v2_backstop_client.claim(&frodo, &vec![&env, v2_pool_id.clone()], &0);
     assert_eq!(v2_backfill_frodo_claim, 0);
@@ -841,7 +846,10 @@ fn deploy_v2_pool(
     );
     let pool_client = PoolClient::new(&env, &pool_id);
 
-    // setup reserves
+    // setup reserves, which are seeded by the admin
+    let usdc_client = MockTokenClient::new(&env, &usdc);
+    mint_xlm(env, creator, &RESERVE_SEED_AMOUNT);
+    usdc_client.mint(creator, &RESERVE_SEED_AMOUNT);
     let mut xlm_config = default_reserve_metadata();
     xlm_config.c_factor = 0_750_0000;
     xlm_config.l_factor = 0_750_0000;
@@ -880,7 +888,6 @@ fn deploy_v2_pool(
     pool_client.update_status();
 
     // creator adds liquidity to the pool
-    let usdc_client = MockTokenClient::new(&env, &usdc);
     // xlm does not allow minting - take from a whale
     mint_xlm(&env, &creator, &10_000_0000000);
     usdc_client.mint(&creator, &5_000_0000000);
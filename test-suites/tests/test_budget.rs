@@ -0,0 +1,505 @@
+#![cfg(test)]
+use pool::{Request, RequestType};
+use soroban_sdk::{testutils::Address as _, vec, Address, Vec};
+use test_suites::{
+    budget::{assert_budget_under, measure, BudgetLimit},
+    create_fixture_with_data,
+    test_fixture::{TestFixture, TokenIndex, SCALAR_7},
+};
+
+// Regression thresholds for hot paths. They sit below the transaction limits to leave headroom for
+// the rest of a transaction, and should be tightened as paths are optimized.
+const MB: u64 = 1024 * 1024;
+const SUBMIT_1_LIMIT: BudgetLimit = BudgetLimit {
+    cpu: 50_000_000,
+    mem: 25 * MB,
+};
+const SUBMIT_3_LIMIT: BudgetLimit = BudgetLimit {
+    cpu: 65_000_000,
+    mem: 30 * MB,
+};
+const SUBMIT_5_LIMIT: BudgetLimit = BudgetLimit {
+    cpu: 80_000_000,
+    mem: 35 * MB,
+};
+const FILL_LIMIT: BudgetLimit = BudgetLimit {
+    cpu: 80_000_000,
+    mem: 35 * MB,
+};
+const FILL_SINGLE_ASSET_LIMIT: BudgetLimit = BudgetLimit {
+    cpu: 70_000_000,
+    mem: 32 * MB,
+};
+const GULP_EMISSIONS_LIMIT: BudgetLimit = BudgetLimit {
+    cpu: 40_000_000,
+    mem: 20 * MB,
+};
+
+/// Create a user holding STABLE, XLM and WETH
+fn create_user(fixture: &TestFixture) -> Address {
+    let user = Address::generate(&fixture.env);
+    fixture.tokens[TokenIndex::STABLE].mint(&user, &(10_000 * 10i128.pow(6)));
+    fixture.tokens[TokenIndex::XLM].mint(&user, &(100_000 * SCALAR_7));
+    fixture.tokens[TokenIndex::WETH].mint(&user, &(10 * 10i128.pow(9)));
+    user
+}
+
+#[test]
+fn test_budget_submit() {
+    let fixture = create_fixture_with_data(true);
+    let pool_fixture = &fixture.pools[0];
+    let stable = &fixture.tokens[TokenIndex::STABLE].address;
+    let xlm = &fixture.tokens[TokenIndex::XLM].address;
+    let weth = &fixture.tokens[TokenIndex::WETH].address;
+
+    // 1 request
+    let sam = create_user(&fixture);
+    let requests: Vec<Request> = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::SupplyCollateral as u32,
+            address: xlm.clone(),
+            amount: 10_000 * SCALAR_7,
+        },
+    ];
+    let (_, usage) = measure(&fixture.env, || {
+        pool_fixture.pool.submit(&sam, &sam, &sam, &requests)
+    });
+    assert_budget_under("submit 1 request", usage, SUBMIT_1_LIMIT);
+
+    // 3 requests
+    let sam = create_user(&fixture);
+    let requests: Vec<Request> = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::SupplyCollateral as u32,
+            address: weth.clone(),
+            amount: 10i128.pow(9),
+        },
+        Request {
+            request_type: RequestType::SupplyCollateral as u32,
+            address: xlm.clone(),
+            amount: 10_000 * SCALAR_7,
+        },
+        Request {
+            request_type: RequestType::Borrow as u32,
+            address: stable.clone(),
+            amount: 100 * 10i128.pow(6),
+        },
+    ];
+    let (_, usage) = measure(&fixture.env, || {
+        pool_fixture.pool.submit(&sam, &sam, &sam, &requests)
+    });
+    assert_budget_under("submit 3 requests", usage, SUBMIT_3_LIMIT);
+
+    // 5 requests
+    let sam = create_user(&fixture);
+    let requests: Vec<Request> = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::SupplyCollateral as u32,
+            address: weth.clone(),
+            amount: 10i128.pow(9),
+        },
+        Request {
+            request_type: RequestType::SupplyCollateral as u32,
+            address: xlm.clone(),
+            amount: 10_000 * SCALAR_7,
+        },
+        Request {
+            request_type: RequestType::Supply as u32,
+            address: stable.clone(),
+            amount: 1_000 * 10i128.pow(6),
+        },
+        Request {
+            request_type: RequestType::Borrow as u32,
+            address: stable.clone(),
+            amount: 100 * 10i128.pow(6),
+        },
+        Request {
+            request_type: RequestType::Borrow as u32,
+            address: xlm.clone(),
+            amount: 1_000 * SCALAR_7,
+        },
+    ];
+    let (_, usage) = measure(&fixture.env, || {
+        pool_fixture.pool.submit(&sam, &sam, &sam, &requests)
+    });
+    assert_budget_under("submit 5 requests", usage, SUBMIT_5_LIMIT);
+}
+
+#[test]
+fn test_budget_fill_liquidation_and_bad_debt_auctions() {
+    let fixture = create_fixture_with_data(true);
+    let frodo = fixture.users[0].clone();
+    let pool_fixture = &fixture.pools[0];
+    let stable = &fixture.tokens[TokenIndex::STABLE].address;
+    let weth = &fixture.tokens[TokenIndex::WETH].address;
+
+    let sam = create_user(&fixture);
+    let requests: Vec<Request> = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::SupplyCollateral as u32,
+            address: weth.clone(),
+            amount: 10i128.pow(9),
+        },
+        Request {
+            request_type: RequestType::Borrow as u32,
+            address: stable.clone(),
+            amount: 100 * 10i128.pow(6),
+        },
+    ];
+    pool_fixture.pool.submit(&sam, &sam, &sam, &requests);
+
+    // nuke eth price so sam can be liquidated
+    fixture.oracle.set_price_stable(&vec![
+        &fixture.env,
+        10_0000000, // eth
+        1_0000000,  // usdc
+        0_1000000,  // xlm
+        1_0000000,  // stable
+    ]);
+    pool_fixture.pool.new_auction(
+        &0,
+        &sam,
+        &vec![&fixture.env, stable.clone()],
+        &vec![&fixture.env, weth.clone()],
+        &100,
+    );
+
+    // fill once the bid has scaled to zero, leaving sam with bad debt
+    fixture.jump_with_sequence(401 * 5);
+    let requests: Vec<Request> = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::FillUserLiquidationAuction as u32,
+            address: sam.clone(),
+            amount: 100,
+        },
+    ];
+    let (_, usage) = measure(&fixture.env, || {
+        pool_fixture.pool.submit(&frodo, &frodo, &frodo, &requests)
+    });
+    assert_budget_under("fill user liquidation auction", usage, FILL_LIMIT);
+
+    pool_fixture.pool.new_auction(
+        &1,
+        &fixture.backstop.address,
+        &vec![&fixture.env, stable.clone()],
+        &vec![&fixture.env, fixture.lp.address.clone()],
+        &100,
+    );
+    fixture.jump_with_sequence(101 * 5);
+    let requests: Vec<Request> = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::FillBadDebtAuction as u32,
+            address: fixture.backstop.address.clone(),
+            amount: 100,
+        },
+    ];
+    let (_, usage) = measure(&fixture.env, || {
+        pool_fixture.pool.submit(&frodo, &frodo, &frodo, &requests)
+    });
+    assert_budget_under("fill bad debt auction", usage, FILL_LIMIT);
+}
+
+#[test]
+fn test_budget_fill_interest_auction() {
+    let fixture = create_fixture_with_data(true);
+    let frodo = fixture.users[0].clone();
+    let pool_fixture = &fixture.pools[0];
+    pool_fixture.pool.set_interest_deposit(&false);
+
+    // accrue backstop credit
+    for _ in 0..12 {
+        fixture.jump(60 * 60 * 24 * 7);
+        fixture.emitter.distribute();
+        fixture.backstop.distribute();
+        pool_fixture.pool.gulp_emissions();
+    }
+    let auction_data = pool_fixture.pool.new_auction(
+        &2,
+        &fixture.backstop.address,
+        &vec![&fixture.env, fixture.lp.address.clone()],
+        &vec![
+            &fixture.env,
+            fixture.tokens[TokenIndex::STABLE].address.clone(),
+            fixture.tokens[TokenIndex::WETH].address.clone(),
+            fixture.tokens[TokenIndex::XLM].address.clone(),
+        ],
+        &100,
+    );
+    fixture.jump_with_sequence(101 * 5);
+    fixture.lp.approve(
+        &frodo,
+        &fixture.backstop.address,
+        &auction_data.bid.get_unchecked(fixture.lp.address.clone()),
+        &fixture.env.ledger().sequence(),
+    );
+
+    let requests: Vec<Request> = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::FillInterestAuction as u32,
+            address: fixture.backstop.address.clone(),
+            amount: 100,
+        },
+    ];
+    let (_, usage) = measure(&fixture.env, || {
+        pool_fixture.pool.submit(&frodo, &frodo, &frodo, &requests)
+    });
+    assert_budget_under("fill interest auction", usage, FILL_LIMIT);
+}
+
+#[test]
+fn test_budget_gulp_emissions() {
+    let fixture = create_fixture_with_data(true);
+    let pool_fixture = &fixture.pools[0];
+
+    fixture.jump(60 * 60 * 24 * 7);
+    fixture.emitter.distribute();
+    fixture.backstop.distribute();
+    let (_, usage) = measure(&fixture.env, || pool_fixture.pool.gulp_emissions());
+    assert_budget_under("gulp_emissions", usage, GULP_EMISSIONS_LIMIT);
+}
+
+#[test]
+fn test_submit_touches_reserve_entries_once() {
+    let fixture = create_fixture_with_data(true);
+    let pool_fixture = &fixture.pools[0];
+    let xlm = &fixture.tokens[TokenIndex::XLM].address;
+
+    // 1 request
+    fixture.jump_with_sequence(60);
+    let sam = create_user(&fixture);
+    let requests: Vec<Request> = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::SupplyCollateral as u32,
+            address: xlm.clone(),
+            amount: 10_000 * SCALAR_7,
+        },
+    ];
+    pool_fixture.pool.submit(&sam, &sam, &sam, &requests);
+    let single = fixture.env.cost_estimate().resources();
+
+    // 3 requests against the same reserve
+    fixture.jump_with_sequence(60);
+    let sam = create_user(&fixture);
+    let requests: Vec<Request> = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::SupplyCollateral as u32,
+            address: xlm.clone(),
+            amount: 5_000 * SCALAR_7,
+        },
+        Request {
+            request_type: RequestType::SupplyCollateral as u32,
+            address: xlm.clone(),
+            amount: 3_000 * SCALAR_7,
+        },
+        Request {
+            request_type: RequestType::SupplyCollateral as u32,
+            address: xlm.clone(),
+            amount: 2_000 * SCALAR_7,
+        },
+    ];
+    pool_fixture.pool.submit(&sam, &sam, &sam, &requests);
+    let multiple = fixture.env.cost_estimate().resources();
+
+    // the reserve is read and written once per submit, regardless of the number of requests
+    assert_eq!(multiple.read_entries, single.read_entries);
+    assert_eq!(multiple.write_entries, single.write_entries);
+    assert_eq!(multiple.write_bytes, single.write_bytes);
+}
+
+#[test]
+fn test_budget_repeat_requests_skip_accrual() {
+    let fixture = create_fixture_with_data(true);
+    let pool_fixture = &fixture.pools[0];
+    let xlm = &fixture.tokens[TokenIndex::XLM].address;
+
+    // 1 request, accruing the reserve
+    fixture.jump_with_sequence(60 * 60);
+    let sam = create_user(&fixture);
+    let requests: Vec<Request> = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::SupplyCollateral as u32,
+            address: xlm.clone(),
+            amount: 3_000 * SCALAR_7,
+        },
+    ];
+    let (_, single) = measure(&fixture.env, || {
+        pool_fixture.pool.submit(&sam, &sam, &sam, &requests)
+    });
+
+    // 3 requests against the same reserve, only the first of which accrues it
+    fixture.jump_with_sequence(60 * 60);
+    let sam = create_user(&fixture);
+    let requests: Vec<Request> = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::SupplyCollateral as u32,
+            address: xlm.clone(),
+            amount: 1_000 * SCALAR_7,
+        },
+        Request {
+            request_type: RequestType::SupplyCollateral as u32,
+            address: xlm.clone(),
+            amount: 1_000 * SCALAR_7,
+        },
+        Request {
+            request_type: RequestType::SupplyCollateral as u32,
+            address: xlm.clone(),
+            amount: 1_000 * SCALAR_7,
+        },
+    ];
+    let (_, multiple) = measure(&fixture.env, || {
+        pool_fixture.pool.submit(&sam, &sam, &sam, &requests)
+    });
+
+    // the repeat requests cost less than the first request's accrual and submission overhead
+    assert!(
+        multiple.cpu < 2 * single.cpu,
+        "repeat requests consumed {} cpu instructions, over twice the {} of a single request",
+        multiple.cpu,
+        single.cpu
+    );
+    assert!(
+        multiple.mem < 2 * single.mem,
+        "repeat requests consumed {} memory bytes, over twice the {} of a single request",
+        multiple.mem,
+        single.mem
+    );
+
+    // a submit in the same ledger does not accrue the reserve again
+    let sam = create_user(&fixture);
+    let requests: Vec<Request> = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::SupplyCollateral as u32,
+            address: xlm.clone(),
+            amount: 3_000 * SCALAR_7,
+        },
+    ];
+    let (_, same_ledger) = measure(&fixture.env, || {
+        pool_fixture.pool.submit(&sam, &sam, &sam, &requests)
+    });
+    assert!(same_ledger.cpu <= single.cpu);
+}
+
+#[test]
+fn test_budget_fill_single_asset_liquidation() {
+    let fixture = create_fixture_with_data(true);
+    let frodo = fixture.users[0].clone();
+    let pool_fixture = &fixture.pools[0];
+    let stable = &fixture.tokens[TokenIndex::STABLE].address;
+    let weth = &fixture.tokens[TokenIndex::WETH].address;
+    let xlm = &fixture.tokens[TokenIndex::XLM].address;
+
+    // sam backs their loan with WETH, merry with WETH and XLM
+    let sam = create_user(&fixture);
+    let requests: Vec<Request> = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::SupplyCollateral as u32,
+            address: weth.clone(),
+            amount: 10i128.pow(9),
+        },
+        Request {
+            request_type: RequestType::Borrow as u32,
+            address: stable.clone(),
+            amount: 100 * 10i128.pow(6),
+        },
+    ];
+    pool_fixture.pool.submit(&sam, &sam, &sam, &requests);
+    let merry = create_user(&fixture);
+    let requests: Vec<Request> = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::SupplyCollateral as u32,
+            address: weth.clone(),
+            amount: 10i128.pow(9),
+        },
+        Request {
+            request_type: RequestType::SupplyCollateral as u32,
+            address: xlm.clone(),
+            amount: 100 * SCALAR_7,
+        },
+        Request {
+            request_type: RequestType::Borrow as u32,
+            address: stable.clone(),
+            amount: 100 * 10i128.pow(6),
+        },
+    ];
+    pool_fixture.pool.submit(&merry, &merry, &merry, &requests);
+
+    // nuke eth price so both users can be liquidated
+    fixture.oracle.set_price_stable(&vec![
+        &fixture.env,
+        10_0000000, // eth
+        1_0000000,  // usdc
+        0_1000000,  // xlm
+        1_0000000,  // stable
+    ]);
+    pool_fixture.pool.new_auction(
+        &0,
+        &sam,
+        &vec![&fixture.env, stable.clone()],
+        &vec![&fixture.env, weth.clone()],
+        &100,
+    );
+    pool_fixture.pool.new_auction(
+        &0,
+        &merry,
+        &vec![&fixture.env, stable.clone()],
+        &vec![&fixture.env, weth.clone(), xlm.clone()],
+        &100,
+    );
+
+    fixture.jump_with_sequence(200 * 5);
+    let requests: Vec<Request> = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::FillUserLiquidationAuction as u32,
+            address: sam.clone(),
+            amount: 100,
+        },
+    ];
+    let (_, single) = measure(&fixture.env, || {
+        pool_fixture.pool.submit(&frodo, &frodo, &frodo, &requests)
+    });
+    assert_budget_under(
+        "fill single asset user liquidation auction",
+        single,
+        FILL_SINGLE_ASSET_LIMIT,
+    );
+
+    // the multi asset fill runs in the same ledger, so it skips accruing the shared reserves
+    let requests: Vec<Request> = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::FillUserLiquidationAuction as u32,
+            address: merry.clone(),
+            amount: 100,
+        },
+    ];
+    let (_, multiple) = measure(&fixture.env, || {
+        pool_fixture.pool.submit(&frodo, &frodo, &frodo, &requests)
+    });
+    assert!(
+        single.cpu < multiple.cpu,
+        "single asset fill consumed {} cpu instructions, not under the {} of a multi asset fill",
+        single.cpu,
+        multiple.cpu
+    );
+    assert!(
+        single.mem < multiple.mem,
+        "single asset fill consumed {} memory bytes, not under the {} of a multi asset fill",
+        single.mem,
+        multiple.mem
+    );
+}
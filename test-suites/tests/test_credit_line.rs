@@ -0,0 +1,99 @@
+#![cfg(test)]
+use pool::{Request, RequestType};
+use soroban_sdk::{testutils::Address as _, vec, Address, Vec};
+use test_suites::{create_fixture_with_data, test_fixture::TokenIndex};
+
+#[test]
+fn test_credit_line_draw_and_repay() {
+    let fixture = create_fixture_with_data(false);
+    let frodo = fixture.users[0].clone();
+    let pool_fixture = &fixture.pools[0];
+    let stable = &fixture.tokens[TokenIndex::STABLE];
+    let cap = 1_000 * 10i128.pow(6);
+
+    let lender = Address::generate(&fixture.env);
+    stable.mint(&lender, &cap);
+    stable.approve(
+        &lender,
+        &pool_fixture.pool.address,
+        &cap,
+        &(fixture.env.ledger().sequence() + 99999),
+    );
+    pool_fixture
+        .pool
+        .set_credit_line(&stable.address, &lender, &cap);
+
+    // frodo withdraws enough STABLE to push utilization above its 95% maximum, so the line is drawn
+    let requests: Vec<Request> = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::WithdrawCollateral as u32,
+            address: stable.address.clone(),
+            amount: 1_800 * 10i128.pow(6),
+        },
+    ];
+    pool_fixture.pool.submit(&frodo, &frodo, &frodo, &requests);
+    let drawn = cap - stable.balance(&lender);
+    assert!(drawn > 0 && drawn < cap);
+    let credit_line = pool_fixture.pool.get_credit_line(&stable.address).unwrap();
+    assert!(credit_line.b_tokens > 0);
+    let reserve = pool_fixture.pool.get_reserve(&stable.address);
+    assert!(reserve.utilization(&fixture.env) <= 0_9500000);
+
+    // a later supply repays the line with the interest it earned
+    fixture.jump(24 * 60 * 60);
+    let sam = Address::generate(&fixture.env);
+    stable.mint(&sam, &(2_000 * 10i128.pow(6)));
+    let requests: Vec<Request> = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::Supply as u32,
+            address: stable.address.clone(),
+            amount: 2_000 * 10i128.pow(6),
+        },
+    ];
+    pool_fixture.pool.submit(&sam, &sam, &sam, &requests);
+    let credit_line = pool_fixture.pool.get_credit_line(&stable.address).unwrap();
+    assert_eq!(credit_line.b_tokens, 0);
+    assert!(stable.balance(&lender) > cap);
+
+    // the repaid line can be removed
+    pool_fixture
+        .pool
+        .set_credit_line(&stable.address, &lender, &0);
+    assert!(pool_fixture.pool.get_credit_line(&stable.address).is_none());
+}
+
+#[test]
+fn test_credit_line_not_drawn_below_max_util() {
+    let fixture = create_fixture_with_data(false);
+    let frodo = fixture.users[0].clone();
+    let pool_fixture = &fixture.pools[0];
+    let stable = &fixture.tokens[TokenIndex::STABLE];
+    let cap = 1_000 * 10i128.pow(6);
+
+    let lender = Address::generate(&fixture.env);
+    stable.mint(&lender, &cap);
+    stable.approve(
+        &lender,
+        &pool_fixture.pool.address,
+        &cap,
+        &(fixture.env.ledger().sequence() + 99999),
+    );
+    pool_fixture
+        .pool
+        .set_credit_line(&stable.address, &lender, &cap);
+
+    let requests: Vec<Request> = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::WithdrawCollateral as u32,
+            address: stable.address.clone(),
+            amount: 1_000 * 10i128.pow(6),
+        },
+    ];
+    pool_fixture.pool.submit(&frodo, &frodo, &frodo, &requests);
+    assert_eq!(stable.balance(&lender), cap);
+    let credit_line = pool_fixture.pool.get_credit_line(&stable.address).unwrap();
+    assert_eq!(credit_line.b_tokens, 0);
+}
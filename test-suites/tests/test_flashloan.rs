@@ -320,15 +320,10 @@ fn test_flashloan_reentrancy_disabled() {
         },
     ];
 
-    // validate re-entrancy attack is protected against by the env
+    // validate re-entrancy attack is protected against by the env, which aborts the invocation
+    // instead of failing with a pool error
     let result = pool_fixture
         .pool
         .try_flash_loan(&samwise, &flash_loan, &requests);
-    assert_eq!(
-        result.err(),
-        Some(Ok(soroban_sdk::Error::from_type_and_code(
-            soroban_sdk::xdr::ScErrorType::Context,
-            soroban_sdk::xdr::ScErrorCode::InvalidAction
-        )))
-    );
+    assert_eq!(result.err(), Some(Err(soroban_sdk::InvokeError::Abort)));
 }
@@ -1,10 +1,10 @@
 #![cfg(test)]
 use backstop::{BackstopDataKey, PoolBalance};
 use cast::i128;
-use pool::{AuctionData, FlashLoan, PoolDataKey, Request, RequestType, ReserveConfig};
+use pool::{AuctionData, FlashLoan, PoolDataKey, PoolError, Request, RequestType, ReserveConfig};
 use soroban_fixed_point_math::FixedPoint;
 use soroban_sdk::{
-    map,
+    contract, contractimpl, map,
     testutils::{Address as AddressTestTrait, Events},
     vec, Address, Env, Error, FromVal, IntoVal, Symbol, TryFromVal, Val, Vec,
 };
@@ -48,12 +48,29 @@ fn assert_fill_auction_event_no_data(
     assert!(AuctionData::try_from_val(env, &event_data.get_unchecked(2)).is_ok());
 }
 
+/// A liquidation hook that never returns, and instead burns through the entire budget
+#[contract]
+pub struct BudgetBurningHook;
+
+#[contractimpl]
+impl BudgetBurningHook {
+    pub fn on_liquidation(e: Env, _pool: Address, _user: Address) -> Vec<Request> {
+        let key = Symbol::new(&e, "Burn");
+        loop {
+            e.storage().instance().has(&key);
+        }
+    }
+}
+
 #[test]
 fn test_liquidations() {
     let fixture = create_fixture_with_data(false);
     let frodo = fixture.users.get(0).unwrap();
     let pool_fixture = &fixture.pools[0];
 
+    // donate interest auction bids so the backstop can be drained to default bad debt
+    pool_fixture.pool.set_interest_deposit(&false);
+
     // accrue interest
     let requests: Vec<Request> = vec![
         &fixture.env,
@@ -293,7 +310,7 @@ fn test_liquidations() {
     let weth_lot_amount = auction_data
         .lot
         .get_unchecked(fixture.tokens[TokenIndex::WETH].address.clone());
-    assert_approx_eq_abs(weth_lot_amount, 4_260750195, 1000);
+    assert_approx_eq_abs(weth_lot_amount, 4_260752712, 1000);
     let events = fixture.env.events().all();
     let event = vec![&fixture.env, events.get_unchecked(events.len() - 1)];
     assert_eq!(
@@ -889,7 +906,7 @@ fn test_liquidations() {
     let events = fixture.env.events().all();
     // bad debt event occurs before the auction fill event
     let event = vec![&fixture.env, events.get_unchecked(events.len() - 2)];
-    let bad_debt: i128 = 9_2903008;
+    let bad_debt: i128 = 9_2903028;
     assert_eq!(
         event,
         vec![
@@ -1060,10 +1077,7 @@ fn test_user_restore_position_and_delete_liquidation() {
         pool_fixture
             .pool
             .try_submit(&samwise, &samwise, &samwise, &delete_only_request);
-    assert_eq!(
-        delete_only.err(),
-        Some(Ok(Error::from_contract_error(1205)))
-    );
+    assert_eq!(delete_only.err(), Some(Ok(PoolError::InvalidHf)));
 
     // validate health factor must be fully restored before deleting position
     let short_supply_delete_request: Vec<Request> = vec![
@@ -1083,10 +1097,7 @@ fn test_user_restore_position_and_delete_liquidation() {
         pool_fixture
             .pool
             .try_submit(&samwise, &samwise, &samwise, &short_supply_delete_request);
-    assert_eq!(
-        short_supply_delete.err(),
-        Some(Ok(Error::from_contract_error(1205)))
-    );
+    assert_eq!(short_supply_delete.err(), Some(Ok(PoolError::InvalidHf)));
 
     let short_repay_delete_request: Vec<Request> = vec![
         &fixture.env,
@@ -1105,10 +1116,7 @@ fn test_user_restore_position_and_delete_liquidation() {
         pool_fixture
             .pool
             .try_submit(&samwise, &samwise, &samwise, &short_repay_delete_request);
-    assert_eq!(
-        short_repay_delete.err(),
-        Some(Ok(Error::from_contract_error(1205)))
-    );
+    assert_eq!(short_repay_delete.err(), Some(Ok(PoolError::InvalidHf)));
 
     // validate positions can't be modified without deleting liquidation
     let healthy_no_delete_request: Vec<Request> = vec![
@@ -1125,7 +1133,7 @@ fn test_user_restore_position_and_delete_liquidation() {
             .try_submit(&samwise, &samwise, &samwise, &healthy_no_delete_request);
     assert_eq!(
         healthy_no_delete.err(),
-        Some(Ok(Error::from_contract_error(1212)))
+        Some(Ok(PoolError::AuctionInProgress))
     );
 
     // validate flash loan endpoint also requires liquidation to be deleted
@@ -1140,7 +1148,7 @@ fn test_user_restore_position_and_delete_liquidation() {
             .try_flash_loan(&samwise, &flash_loan, &healthy_no_delete_request);
     assert_eq!(
         flash_loan_no_delete.err(),
-        Some(Ok(Error::from_contract_error(1212)))
+        Some(Ok(PoolError::AuctionInProgress))
     );
 
     // validate liquidation can be deleted after restoring position
@@ -1322,9 +1330,13 @@ fn test_bad_debt() {
 
     // use magic to delete Sam's collateral
     fixture.env.as_contract(&pool_fixture.pool.address, || {
-        let key = PoolDataKey::Positions(sam.clone());
+        let key = PoolDataKey::PackedPos(sam.clone());
         sam_positions.collateral = map![&fixture.env];
-        fixture.env.storage().persistent().set(&key, &sam_positions);
+        fixture
+            .env
+            .storage()
+            .persistent()
+            .set(&key, &sam_positions.pack(&fixture.env));
     });
 
     // Validate invalid liquidaiton can't be created with no bid
@@ -1407,3 +1419,66 @@ fn test_bad_debt() {
         0_0000100,
     );
 }
+
+#[test]
+fn test_liquidation_hook_cant_block_new_auction() {
+    let fixture = create_fixture_with_data(false);
+    let pool_fixture = &fixture.pools[0];
+    let hook = fixture.env.register(BudgetBurningHook {}, ());
+
+    // Create a user that is supply STABLE (cf = 90%, $1) and borrowing XLM (lf = 75%, $0.10)
+    let samwise = Address::generate(&fixture.env);
+    fixture.tokens[TokenIndex::STABLE].mint(&samwise, &(1100 * 10i128.pow(6)));
+
+    // deposit $1k stable and borrow to 90% borrow limit ($810)
+    let setup_request: Vec<Request> = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::SupplyCollateral as u32,
+            address: fixture.tokens[TokenIndex::STABLE].address.clone(),
+            amount: 1000 * 10i128.pow(6),
+        },
+        Request {
+            request_type: RequestType::Borrow as u32,
+            address: fixture.tokens[TokenIndex::XLM].address.clone(),
+            amount: 6075 * SCALAR_7,
+        },
+    ];
+    pool_fixture
+        .pool
+        .submit(&samwise, &samwise, &samwise, &setup_request);
+    pool_fixture
+        .pool
+        .set_liquidation_hook(&samwise, &Some(hook.clone()));
+
+    // simulate 20% XLM price increase ($972 liabilities, $900 limit)
+    fixture.oracle.set_price_stable(&vec![
+        &fixture.env,
+        2000_0000000, // eth
+        1_0000000,    // usdc
+        0_1200000,    // xlm
+        1_0000000,    // stable
+    ]);
+
+    // the hook would exhaust any budget, but creating the auction never invokes it
+    fixture.env.cost_estimate().budget().reset_default();
+    let auction_data = pool_fixture.pool.new_auction(
+        &0,
+        &samwise,
+        &vec![
+            &fixture.env,
+            fixture.tokens[TokenIndex::XLM].address.clone(),
+        ],
+        &vec![
+            &fixture.env,
+            fixture.tokens[TokenIndex::STABLE].address.clone(),
+        ],
+        &50,
+    );
+    assert_eq!(auction_data.bid.len(), 1);
+    assert_eq!(auction_data.lot.len(), 1);
+    let stored_auction = pool_fixture.pool.get_auction(&0, &samwise);
+    assert_eq!(stored_auction.bid, auction_data.bid);
+    assert_eq!(stored_auction.lot, auction_data.lot);
+    fixture.env.cost_estimate().budget().reset_unlimited();
+}
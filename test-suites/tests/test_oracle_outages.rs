@@ -0,0 +1,150 @@
+#![cfg(test)]
+use mock_oracle::{Outage, ScheduledPrice};
+use pool::{PoolError, Request, RequestType};
+use sep_40_oracle::Asset;
+use soroban_sdk::{testutils::Address as _, vec, Address, Error, Vec};
+use test_suites::{
+    create_fixture_with_data,
+    test_fixture::{TestFixture, TokenIndex},
+};
+
+/// Create a user with 1 WETH of collateral borrowing 1000 STABLE
+fn create_borrower(fixture: &TestFixture) -> Address {
+    let sam = Address::generate(&fixture.env);
+    fixture.tokens[TokenIndex::WETH].mint(&sam, &(10 * 10i128.pow(9)));
+    let requests: Vec<Request> = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::SupplyCollateral as u32,
+            address: fixture.tokens[TokenIndex::WETH].address.clone(),
+            amount: 10i128.pow(9),
+        },
+        Request {
+            request_type: RequestType::Borrow as u32,
+            address: fixture.tokens[TokenIndex::STABLE].address.clone(),
+            amount: 1_000 * 10i128.pow(6),
+        },
+    ];
+    fixture.pools[0].pool.submit(&sam, &sam, &sam, &requests);
+    sam
+}
+
+fn borrow_request(fixture: &TestFixture) -> Vec<Request> {
+    vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::Borrow as u32,
+            address: fixture.tokens[TokenIndex::STABLE].address.clone(),
+            amount: 10 * 10i128.pow(6),
+        },
+    ]
+}
+
+#[test]
+fn test_stale_price_blocks_borrows() {
+    let fixture = create_fixture_with_data(false);
+    let oracle = fixture.use_programmable_oracle(0);
+    let pool = &fixture.pools[0].pool;
+    let sam = create_borrower(&fixture);
+    let weth = Asset::Stellar(fixture.tokens[TokenIndex::WETH].address.clone());
+
+    // a price updated within a day is accepted
+    oracle.set_outage(&weth, &Some(Outage::Stale(23 * 60 * 60)));
+    pool.submit(&sam, &sam, &sam, &borrow_request(&fixture));
+
+    // a price over a day old is rejected
+    oracle.set_outage(&weth, &Some(Outage::Stale(25 * 60 * 60)));
+    let result = pool.try_submit(&sam, &sam, &sam, &borrow_request(&fixture));
+    assert_eq!(result.err(), Some(Ok(PoolError::InvalidPrice)));
+
+    // borrowing resumes once the oracle recovers
+    oracle.set_outage(&weth, &None);
+    pool.submit(&sam, &sam, &sam, &borrow_request(&fixture));
+}
+
+#[test]
+fn test_missing_price_blocks_borrows() {
+    let fixture = create_fixture_with_data(false);
+    let oracle = fixture.use_programmable_oracle(0);
+    let pool = &fixture.pools[0].pool;
+    let sam = create_borrower(&fixture);
+    let weth = Asset::Stellar(fixture.tokens[TokenIndex::WETH].address.clone());
+
+    oracle.set_outage(&weth, &Some(Outage::Missing));
+    let result = pool.try_submit(&sam, &sam, &sam, &borrow_request(&fixture));
+    assert!(result.is_err());
+
+    // a gap in a price schedule is also reported as a missing price
+    oracle.set_outage(&weth, &None);
+    let sequence = fixture.env.ledger().sequence();
+    oracle.set_schedule(
+        &weth,
+        &vec![
+            &fixture.env,
+            ScheduledPrice {
+                ledger: sequence + 10,
+                price: None,
+            },
+            ScheduledPrice {
+                ledger: sequence + 20,
+                price: Some(2000_0000000),
+            },
+        ],
+    );
+    pool.submit(&sam, &sam, &sam, &borrow_request(&fixture));
+    fixture.jump_with_sequence(10 * 5);
+    let result = pool.try_submit(&sam, &sam, &sam, &borrow_request(&fixture));
+    assert!(result.is_err());
+    fixture.jump_with_sequence(10 * 5);
+    pool.submit(&sam, &sam, &sam, &borrow_request(&fixture));
+}
+
+#[test]
+fn test_liquidation_during_price_schedule() {
+    let fixture = create_fixture_with_data(false);
+    let oracle = fixture.use_programmable_oracle(0);
+    let pool = &fixture.pools[0].pool;
+    let sam = create_borrower(&fixture);
+    let weth = Asset::Stellar(fixture.tokens[TokenIndex::WETH].address.clone());
+    let bid = vec![
+        &fixture.env,
+        fixture.tokens[TokenIndex::STABLE].address.clone(),
+    ];
+    let lot = vec![
+        &fixture.env,
+        fixture.tokens[TokenIndex::WETH].address.clone(),
+    ];
+
+    // WETH falls from 2000 to 800 over 30 ledgers
+    let sequence = fixture.env.ledger().sequence();
+    oracle.set_schedule(
+        &weth,
+        &vec![
+            &fixture.env,
+            ScheduledPrice {
+                ledger: sequence + 10,
+                price: Some(1600_0000000),
+            },
+            ScheduledPrice {
+                ledger: sequence + 20,
+                price: Some(1400_0000000),
+            },
+            ScheduledPrice {
+                ledger: sequence + 30,
+                price: Some(800_0000000),
+            },
+        ],
+    );
+
+    // sam stays healthy until the price drops below their liabilities
+    for _ in 0..2 {
+        fixture.jump_with_sequence(10 * 5);
+        let result = pool.try_new_auction(&0, &sam, &bid, &lot, &100);
+        assert_eq!(result.err(), Some(Ok(Error::from_contract_error(1211))));
+    }
+    fixture.jump_with_sequence(10 * 5);
+    let auction = pool.new_auction(&0, &sam, &bid, &lot, &100);
+    assert_eq!(auction.block, fixture.env.ledger().sequence() + 1);
+    assert!(auction.bid.get_unchecked(bid.get_unchecked(0)) > 0);
+    assert!(auction.lot.get_unchecked(lot.get_unchecked(0)) > 0);
+}
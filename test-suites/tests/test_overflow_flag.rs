@@ -83,10 +83,10 @@ fn test_pool_overflow() {
     assert!(weth.balance(&samwise) >= max_weth_deposit - 1);
 }
 
-// This test ensures that an accessible underflow in the auction flow cannot be hit due to the overflow-checks flag being set
-// Without this flag set, filling an auction on the same block it's started would cause an underflow
+// This test ensures that an accessible underflow in the auction flow cannot be hit
+// Filling an auction on the same block it's started would cause an underflow, so the fill must be rejected
 #[test]
-#[should_panic(expected = "Error(WasmVm, InvalidAction)")]
+#[should_panic(expected = "Error(Contract, #1225)")]
 fn test_auction_underflow_panics() {
     let fixture = create_fixture_with_data(true);
     let frodo = fixture.users.get(0).unwrap();
@@ -1,9 +1,9 @@
 #![cfg(test)]
 
-use pool::{Request, RequestType, ReserveEmissionMetadata};
+use pool::{AdminAction, AdminOp, Request, RequestType, ReserveEmissionMetadata};
 use soroban_fixed_point_math::FixedPoint;
 use soroban_sdk::{
-    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, Events},
+    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, Events, Ledger},
     vec, Address, IntoVal, Symbol, Val,
 };
 use test_suites::{
@@ -525,7 +525,7 @@ fn test_pool_user() {
             )
         ]
     );
-    assert_eq!(result, 2940_3117269); // ~ 4.99k / (100k + 4.99k) * 0.12 (xlm eps) * 5d23hr59m in seconds
+    assert_eq!(result, 2940_3114470); // ~ 4.99k / (100k + 4.99k) * 0.12 (xlm eps) * 5d23hr59m in seconds
     assert_eq!(blnd.balance(&sam), sam_blnd_balance + result);
 
     // Sam sends XLM to the pool
@@ -565,17 +565,17 @@ fn test_pool_config() {
 
     let pool_fixture = &fixture.pools[0];
 
-    // Update pool config (admin only)
+    // Update pool config (admin only), which must be queued once the pool has left setup
     let backstop_take_rate: u32 = 0_0500000;
-    pool_fixture
+    let result = pool_fixture
         .pool
-        .update_pool(&backstop_take_rate, &6, &0_5000000);
-    let event_data: soroban_sdk::Vec<Val> = vec![
-        &fixture.env,
-        backstop_take_rate.into_val(&fixture.env),
-        6u32.into_val(&fixture.env),
-        0_5000000i128.into_val(&fixture.env),
-    ];
+        .try_update_pool(&backstop_take_rate, &6, &0_5000000, &0);
+    assert_eq!(
+        result.err(),
+        Some(Ok(soroban_sdk::Error::from_contract_error(1240)))
+    );
+    let action = AdminAction::UpdatePool(backstop_take_rate, 6, 0_5000000, 0);
+    let id = pool_fixture.pool.queue_admin_action(&action);
     assert_eq!(
         fixture.env.auths()[0],
         (
@@ -583,14 +583,35 @@ fn test_pool_config() {
             AuthorizedInvocation {
                 function: AuthorizedFunction::Contract((
                     pool_fixture.pool.address.clone(),
-                    Symbol::new(&fixture.env, "update_pool"),
-                    event_data.into_val(&fixture.env)
+                    Symbol::new(&fixture.env, "queue_admin_action"),
+                    vec![&fixture.env, action.into_val(&fixture.env)]
                 )),
                 sub_invocations: std::vec![]
             }
         )
     );
-    let event = vec![&fixture.env, fixture.env.events().all().last_unchecked()];
+    let unlock_time = fixture.env.ledger().timestamp() + 3 * 24 * 60 * 60;
+    let queued_action = pool_fixture.pool.get_queued_action(&id).unwrap();
+    assert_eq!(queued_action.unlock_time, unlock_time);
+
+    // the action can't be executed until the timelock has passed
+    fixture.jump(3 * 24 * 60 * 60 - 1);
+    let result = pool_fixture.pool.try_execute_queued_action(&id);
+    assert_eq!(
+        result.err(),
+        Some(Ok(soroban_sdk::Error::from_contract_error(1239)))
+    );
+    fixture.jump(1);
+    pool_fixture.pool.execute_queued_action(&id);
+    let events = fixture.env.events().all();
+    let event_data: soroban_sdk::Vec<Val> = vec![
+        &fixture.env,
+        backstop_take_rate.into_val(&fixture.env),
+        6u32.into_val(&fixture.env),
+        0_5000000i128.into_val(&fixture.env),
+        0i128.into_val(&fixture.env),
+    ];
+    let event = vec![&fixture.env, events.get_unchecked(events.len() - 3)];
     assert_eq!(
         event,
         vec![
@@ -639,7 +660,8 @@ fn test_pool_config() {
     fixture.jump(604800); // 1 week
 
     pool_fixture.pool.set_reserve(&blnd.address);
-    let event = vec![&fixture.env, fixture.env.events().all().last_unchecked()];
+    let events = fixture.env.events().all();
+    let event = vec![&fixture.env, events.get_unchecked(events.len() - 2)];
     let event_data: soroban_sdk::Vec<Val> = vec![
         &fixture.env,
         blnd.address.into_val(&fixture.env),
@@ -697,7 +719,8 @@ fn test_pool_config() {
             )
         ]
     );
-    let event = vec![&fixture.env, fixture.env.events().all().last_unchecked()];
+    let events = fixture.env.events().all();
+    let event = vec![&fixture.env, events.get_unchecked(events.len() - 2)];
     assert_eq!(
         event,
         vec![
@@ -709,6 +732,20 @@ fn test_pool_config() {
             )
         ]
     );
+    // a snapshot of the new config is emitted after the reserve is set
+    let snapshot = pool_fixture.pool.get_config_snapshot();
+    let event = vec![&fixture.env, events.last_unchecked()];
+    assert_eq!(
+        event,
+        vec![
+            &fixture.env,
+            (
+                pool_fixture.pool.address.clone(),
+                (Symbol::new(&fixture.env, "config_snapshot"),).into_val(&fixture.env),
+                snapshot.into_val(&fixture.env)
+            )
+        ]
+    );
     let new_reserve_config = fixture.read_reserve_config(0, TokenIndex::BLND);
     assert_eq!(new_reserve_config.l_factor, 0_500_0000);
     assert_eq!(new_reserve_config.c_factor, 0);
@@ -902,7 +939,8 @@ fn test_pool_config() {
             share: 0_200_0000
         },
     ];
-    pool_fixture.pool.set_emissions_config(&reserve_emissions);
+    let action = AdminAction::SetEmissionsConfig(reserve_emissions);
+    let id = pool_fixture.pool.queue_admin_action(&action);
     assert_eq!(
         fixture.env.auths()[0],
         (
@@ -910,16 +948,187 @@ fn test_pool_config() {
             AuthorizedInvocation {
                 function: AuthorizedFunction::Contract((
                     pool_fixture.pool.address.clone(),
-                    Symbol::new(&fixture.env, "set_emissions_config"),
-                    vec![&fixture.env, reserve_emissions.to_val()]
+                    Symbol::new(&fixture.env, "queue_admin_action"),
+                    vec![&fixture.env, action.into_val(&fixture.env)]
                 )),
                 sub_invocations: std::vec![]
             }
         )
     );
+    fixture.jump(24 * 60 * 60);
+    pool_fixture.pool.execute_queued_action(&id);
     let new_emissions_config = fixture.read_pool_emissions(0);
     assert_eq!(new_emissions_config.len(), 3);
     assert_eq!(new_emissions_config.get_unchecked(0), 0_400_0000);
     assert_eq!(new_emissions_config.get_unchecked(1 * 2 + 1), 0_400_0000);
     assert_eq!(new_emissions_config.get_unchecked(3 * 2 + 1), 0_200_0000);
 }
+
+/// Test that admin operations batched with `admin_multicall` are applied atomically
+#[test]
+fn test_pool_admin_multicall() {
+    let fixture = create_fixture_with_data(false);
+    let pool_fixture = &fixture.pools[0];
+    let blnd = &fixture.tokens[TokenIndex::BLND];
+
+    let mut reserve_config = default_reserve_metadata();
+    reserve_config.c_factor = 0_200_0000;
+    let reserve_emissions: soroban_sdk::Vec<ReserveEmissionMetadata> = soroban_sdk::vec![
+        &fixture.env,
+        ReserveEmissionMetadata {
+            res_index: 0, // USDC
+            res_type: 0,  // d_token
+            share: 1_000_0000
+        },
+    ];
+    let ops = vec![
+        &fixture.env,
+        AdminOp::QueueAdminAction(AdminAction::UpdatePool(0_0500000, 6, 0_5000000, 0)),
+        AdminOp::QueueSetReserve(blnd.address.clone(), reserve_config.clone()),
+        AdminOp::SetStatus(4),
+        AdminOp::QueueAdminAction(AdminAction::SetEmissionsConfig(reserve_emissions)),
+    ];
+    pool_fixture.pool.admin_multicall(&ops);
+    assert_eq!(
+        fixture.env.auths()[0],
+        (
+            fixture.bombadil.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    pool_fixture.pool.address.clone(),
+                    Symbol::new(&fixture.env, "admin_multicall"),
+                    vec![&fixture.env, ops.to_val()]
+                )),
+                sub_invocations: std::vec![]
+            }
+        )
+    );
+    let new_pool_config = fixture.read_pool_config(0);
+    assert_eq!(new_pool_config.bstop_rate, 0_1000000);
+    assert_eq!(new_pool_config.status, 4);
+    assert!(pool_fixture.pool.get_queued_action(&0).is_some());
+    assert!(pool_fixture.pool.get_queued_action(&1).is_some());
+
+    // a failing operation reverts the entire batch
+    let ops = vec![
+        &fixture.env,
+        AdminOp::QueueAdminAction(AdminAction::UpdatePool(0_1000000, 6, 0_5000000, 0)),
+        AdminOp::CancelSetReserve(blnd.address.clone()),
+        AdminOp::SetStatus(7),
+    ];
+    let result = pool_fixture.pool.try_admin_multicall(&ops);
+    assert!(result.is_err());
+    assert!(pool_fixture.pool.get_queued_action(&2).is_none());
+    let new_pool_config = fixture.read_pool_config(0);
+    assert_eq!(new_pool_config.status, 4);
+
+    // queued actions are executable after their timelocks
+    fixture.jump(604800); // 1 week
+    let ops = vec![
+        &fixture.env,
+        AdminOp::ExecuteQueuedAction(0),
+        AdminOp::ExecuteQueuedAction(1),
+    ];
+    pool_fixture.pool.admin_multicall(&ops);
+    let new_pool_config = fixture.read_pool_config(0);
+    assert_eq!(new_pool_config.bstop_rate, 0_0500000);
+    assert_eq!(new_pool_config.max_positions, 6);
+    assert_eq!(new_pool_config.min_collateral, 0_5000000);
+    let new_emissions_config = fixture.read_pool_emissions(0);
+    assert_eq!(new_emissions_config.len(), 1);
+    assert_eq!(new_emissions_config.get_unchecked(0), 1_000_0000);
+
+    pool_fixture.pool.set_reserve(&blnd.address);
+    let new_reserve_config = fixture.read_reserve_config(0, TokenIndex::BLND);
+    assert_eq!(new_reserve_config.c_factor, 0_200_0000);
+}
+
+/// Test the first submit of an emission cycle gulps emissions when auto gulp is enabled
+#[test]
+fn test_pool_auto_gulp() {
+    let fixture = create_fixture_with_data(false);
+    let pool_fixture = &fixture.pools[0];
+    let xlm = &fixture.tokens[TokenIndex::XLM];
+
+    let sam = Address::generate(&fixture.env);
+    xlm.mint(&sam, &(1_000 * SCALAR_7));
+
+    pool_fixture.pool.set_auto_gulp(&true);
+    assert!(pool_fixture.pool.get_auto_gulp());
+
+    fixture.jump(24 * 60 * 60);
+    fixture.emitter.distribute();
+    fixture.backstop.distribute();
+
+    let requests = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::Supply as u32,
+            address: xlm.address.clone(),
+            amount: 100 * SCALAR_7,
+        },
+    ];
+    pool_fixture.pool.submit(&sam, &sam, &sam, &requests);
+    // gulping emissions resets the emission expiration to a week from now
+    let expiration = fixture.env.ledger().timestamp() + 7 * 24 * 60 * 60;
+    let stable_emissions = pool_fixture.pool.get_reserve_emissions(&0).unwrap();
+    assert_eq!(stable_emissions.expiration, expiration);
+
+    // emissions are only gulped once per cycle
+    fixture.jump(60);
+    fixture.emitter.distribute();
+    fixture.backstop.distribute();
+    pool_fixture.pool.submit(&sam, &sam, &sam, &requests);
+    let stable_emissions = pool_fixture.pool.get_reserve_emissions(&0).unwrap();
+    assert_eq!(stable_emissions.expiration, expiration);
+}
+
+/// Test the borrower index tracks addresses with liabilities while enabled
+#[test]
+fn test_pool_borrower_index() {
+    let fixture = create_fixture_with_data(false);
+    let pool_fixture = &fixture.pools[0];
+    let xlm = &fixture.tokens[TokenIndex::XLM];
+
+    let sam = Address::generate(&fixture.env);
+    xlm.mint(&sam, &(1_000 * SCALAR_7));
+
+    // existing borrowers are not indexed before the index is enabled
+    assert!(!pool_fixture.pool.get_borrower_index());
+    pool_fixture.pool.set_borrower_index(&true);
+    assert!(pool_fixture.pool.get_borrower_index());
+    assert_eq!(pool_fixture.pool.get_borrower_count(), 0);
+
+    let requests = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::SupplyCollateral as u32,
+            address: xlm.address.clone(),
+            amount: 500 * SCALAR_7,
+        },
+        Request {
+            request_type: RequestType::Borrow as u32,
+            address: xlm.address.clone(),
+            amount: 100 * SCALAR_7,
+        },
+    ];
+    pool_fixture.pool.submit(&sam, &sam, &sam, &requests);
+    assert_eq!(pool_fixture.pool.get_borrower_count(), 1);
+    assert_eq!(
+        pool_fixture.pool.get_borrowers(&0, &10),
+        vec![&fixture.env, sam.clone()]
+    );
+
+    // a full repay removes the borrower from the index
+    let requests = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::Repay as u32,
+            address: xlm.address.clone(),
+            amount: 101 * SCALAR_7,
+        },
+    ];
+    pool_fixture.pool.submit(&sam, &sam, &sam, &requests);
+    assert_eq!(pool_fixture.pool.get_borrower_count(), 0);
+    assert_eq!(pool_fixture.pool.get_borrowers(&0, &10).len(), 0);
+}
@@ -0,0 +1,185 @@
+#![cfg(test)]
+
+use pool::{reserve_seed_amount, Request, RequestType};
+use sep_40_oracle::testutils::Asset;
+use soroban_sdk::{testutils::Address as _, vec, Address, String, Symbol};
+use test_suites::{
+    assertions::assert_approx_eq_abs,
+    pool::default_reserve_metadata,
+    test_fixture::{TestFixture, TokenIndex, SCALAR_12, SCALAR_7},
+    token::create_token,
+};
+
+/// Test reserves accrue and settle interest uniformly for assets between 0 and 18 decimals
+#[test]
+fn test_pool_decimals_matrix() {
+    let mut fixture = TestFixture::create(false);
+    let whale = Address::generate(&fixture.env);
+
+    fixture.create_pool(String::from_str(&fixture.env, "Teapot"), 1000000, 6, 0);
+
+    let xlm_config = default_reserve_metadata();
+    fixture.create_pool_reserve(0, TokenIndex::XLM, &xlm_config);
+
+    // create a reserve priced at $1 for each decimal value in the matrix
+    let decimals_matrix = [0u32, 6, 7, 9, 18];
+    let mut tokens = std::vec::Vec::new();
+    let mut oracle_assets = vec![
+        &fixture.env,
+        Asset::Stellar(fixture.tokens[TokenIndex::WETH].address.clone()),
+        Asset::Stellar(fixture.tokens[TokenIndex::USDC].address.clone()),
+        Asset::Stellar(fixture.tokens[TokenIndex::XLM].address.clone()),
+        Asset::Stellar(fixture.tokens[TokenIndex::STABLE].address.clone()),
+    ];
+    let mut oracle_prices = vec![&fixture.env, 2000_0000000, 1_0000000, 1000000, 1_0000000];
+    for decimals in decimals_matrix {
+        let (token_id, token_client) =
+            create_token(&fixture.env, &fixture.bombadil, decimals, "TKN");
+        let mut config = default_reserve_metadata();
+        config.decimals = decimals;
+        config.supply_cap = i128::MAX;
+        let pool_client = &fixture.pools[0].pool;
+        pool_client.queue_set_reserve(&token_id, &config);
+        token_client.mint(&fixture.bombadil, &reserve_seed_amount(decimals));
+        pool_client.set_reserve(&token_id);
+
+        oracle_assets.push_back(Asset::Stellar(token_id));
+        oracle_prices.push_back(1_0000000);
+        tokens.push(token_client);
+    }
+    fixture.oracle.set_data(
+        &fixture.bombadil,
+        &Asset::Other(Symbol::new(&fixture.env, "USD")),
+        &oracle_assets,
+        &7,
+        &300,
+    );
+    fixture.oracle.set_price_stable(&oracle_prices);
+
+    // setup backstop and update pool status
+    fixture.tokens[TokenIndex::BLND].mint(&whale, &(500_100 * SCALAR_7));
+    fixture.tokens[TokenIndex::USDC].mint(&whale, &(12_600 * SCALAR_7));
+    fixture.lp.join_pool(
+        &(50_000 * SCALAR_7),
+        &vec![&fixture.env, 500_100 * SCALAR_7, 12_600 * SCALAR_7],
+        &whale,
+    );
+    fixture
+        .backstop
+        .deposit(&whale, &fixture.pools[0].pool.address, &(50_000 * SCALAR_7));
+    fixture.pools[0].pool.set_status(&0);
+    fixture.jump_with_sequence(60);
+
+    // supply 10k tokens to each reserve and borrow 5k against XLM collateral
+    let pool_client = &fixture.pools[0].pool;
+    let mut borrowers = std::vec::Vec::new();
+    for (decimals, token) in decimals_matrix.iter().zip(tokens.iter()) {
+        let scalar = 10i128.pow(*decimals);
+        let seed = reserve_seed_amount(*decimals);
+        token.mint(&whale, &(10_000 * scalar));
+        pool_client.submit(
+            &whale,
+            &whale,
+            &whale,
+            &vec![
+                &fixture.env,
+                Request {
+                    request_type: RequestType::Supply as u32,
+                    address: token.address.clone(),
+                    amount: 10_000 * scalar,
+                },
+            ],
+        );
+
+        let borrower = Address::generate(&fixture.env);
+        fixture.tokens[TokenIndex::XLM].mint(&borrower, &(200_000 * SCALAR_7));
+        pool_client.submit(
+            &borrower,
+            &borrower,
+            &borrower,
+            &vec![
+                &fixture.env,
+                Request {
+                    request_type: RequestType::SupplyCollateral as u32,
+                    address: fixture.tokens[TokenIndex::XLM].address.clone(),
+                    amount: 200_000 * SCALAR_7,
+                },
+                Request {
+                    request_type: RequestType::Borrow as u32,
+                    address: token.address.clone(),
+                    amount: 5_000 * scalar,
+                },
+            ],
+        );
+        assert_eq!(token.balance(&borrower), 5_000 * scalar);
+        assert_eq!(token.balance(&pool_client.address), 5_000 * scalar + seed);
+
+        let reserve = pool_client.get_reserve(&token.address);
+        assert_eq!(reserve.scalar, scalar);
+        assert_eq!(reserve.data.b_rate, SCALAR_12);
+        assert_eq!(reserve.data.b_supply, 10_000 * scalar + seed);
+        assert_eq!(reserve.data.d_supply, 5_000 * scalar);
+        borrowers.push(borrower);
+    }
+
+    fixture.jump_with_sequence(30 * 24 * 60 * 60);
+
+    // repay and withdraw everything, and record the interest paid for each reserve
+    let pool_client = &fixture.pools[0].pool;
+    let mut interest_paid = std::vec::Vec::new();
+    for ((decimals, token), borrower) in decimals_matrix
+        .iter()
+        .zip(tokens.iter())
+        .zip(borrowers.iter())
+    {
+        let scalar = 10i128.pow(*decimals);
+        token.mint(borrower, &(100 * scalar));
+        pool_client.submit(
+            borrower,
+            borrower,
+            borrower,
+            &vec![
+                &fixture.env,
+                Request {
+                    request_type: RequestType::Repay as u32,
+                    address: token.address.clone(),
+                    amount: 5_100 * scalar,
+                },
+            ],
+        );
+        let positions = pool_client.get_positions(borrower);
+        assert_eq!(positions.liabilities.len(), 0);
+        let paid = 5_100 * scalar - token.balance(borrower);
+        assert!(paid > 5_000 * scalar);
+
+        pool_client.submit(
+            &whale,
+            &whale,
+            &whale,
+            &vec![
+                &fixture.env,
+                Request {
+                    request_type: RequestType::Withdraw as u32,
+                    address: token.address.clone(),
+                    amount: 20_000 * scalar,
+                },
+            ],
+        );
+        let withdrawn = token.balance(&whale);
+        assert!(withdrawn > 10_000 * scalar);
+        // suppliers earn the interest less the 10% backstop take rate
+        assert!(withdrawn - 10_000 * scalar < paid - 5_000 * scalar);
+
+        let reserve = pool_client.get_reserve(&token.address);
+        assert_eq!(reserve.data.d_supply, 0);
+        assert_eq!(reserve.data.b_supply, reserve_seed_amount(*decimals));
+        interest_paid.push(paid - 5_000 * scalar);
+    }
+
+    // the interest is the same for each reserve, up to 1 base unit of the lowest decimal asset
+    let interest_7 = interest_paid[2];
+    for (decimals, interest) in decimals_matrix.iter().zip(interest_paid.iter()) {
+        let scalar = 10i128.pow(*decimals);
+        assert_approx_eq_abs(interest * SCALAR_7, interest_7 * scalar, SCALAR_7 * scalar);
+    }
+}
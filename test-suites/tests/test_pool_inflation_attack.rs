@@ -1,6 +1,6 @@
 #![cfg(test)]
 
-use pool::{Request, RequestType};
+use pool::{Request, RequestType, RESERVE_SEED_AMOUNT};
 use soroban_sdk::{testutils::Address as _, vec, Address, String};
 use test_suites::{
     pool::default_reserve_metadata,
@@ -117,6 +117,6 @@ fn test_pool_inflation_attack() {
     );
     assert_eq!(
         fixture.tokens[TokenIndex::XLM].balance(&fixture.pools[0].pool.address),
-        inflation_amount
+        inflation_amount + RESERVE_SEED_AMOUNT
     );
 }
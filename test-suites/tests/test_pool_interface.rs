@@ -0,0 +1,45 @@
+#![cfg(test)]
+use pool_interface::{PoolClient, PoolError, RequestBuilder};
+use soroban_sdk::{testutils::Address as _, Address};
+use test_suites::{create_fixture_with_data, test_fixture::TokenIndex};
+
+#[test]
+fn test_pool_interface_client() {
+    let fixture = create_fixture_with_data(false);
+    let pool_fixture = &fixture.pools[0];
+    let pool_client = PoolClient::new(&fixture.env, &pool_fixture.pool.address);
+    let stable = &fixture.tokens[TokenIndex::STABLE];
+    let xlm = &fixture.tokens[TokenIndex::XLM];
+
+    let samwise = Address::generate(&fixture.env);
+    stable.mint(&samwise, &(1_000 * 10i128.pow(6)));
+
+    let requests = RequestBuilder::new(&fixture.env)
+        .supply_collateral(&stable.address, 100 * 10i128.pow(6))
+        .borrow(&xlm.address, 10_0000000)
+        .build();
+    let positions = pool_client.submit(&samwise, &samwise, &samwise, &requests);
+
+    // the interface types decode the pool's positions
+    let stable_index = pool_fixture.reserves[&TokenIndex::STABLE];
+    let xlm_index = pool_fixture.reserves[&TokenIndex::XLM];
+    let pool_positions = pool_fixture.pool.get_positions(&samwise);
+    assert_eq!(positions, pool_client.get_positions(&samwise));
+    assert_eq!(
+        positions.collateral.get(stable_index),
+        pool_positions.collateral.get(stable_index)
+    );
+    assert_eq!(
+        positions.liabilities.get(xlm_index),
+        pool_positions.liabilities.get(xlm_index)
+    );
+    assert!(positions.liabilities.get_unchecked(xlm_index) > 0);
+    assert_eq!(positions.supply.len(), 0);
+
+    // the interface returns the pool's errors as typed errors
+    let requests = RequestBuilder::new(&fixture.env)
+        .borrow(&xlm.address, 10000_0000000)
+        .build();
+    let result = pool_client.try_submit(&samwise, &samwise, &samwise, &requests);
+    assert_eq!(result.err(), Some(Ok(PoolError::InvalidHf)));
+}
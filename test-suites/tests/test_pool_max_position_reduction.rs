@@ -1,7 +1,7 @@
 #![cfg(test)]
 
-use pool::{Request, RequestType};
-use soroban_sdk::{testutils::Address as _, vec, Address, Error};
+use pool::{AdminAction, PoolError, Request, RequestType};
+use soroban_sdk::{testutils::Address as _, vec, Address};
 use test_suites::{
     create_fixture_with_data,
     test_fixture::{TokenIndex, SCALAR_7},
@@ -72,7 +72,11 @@ fn test_pool_max_positions_reduction() {
     fixture.jump_with_sequence(100);
 
     // admin lowers max positions to 4
-    pool_fixture.pool.update_pool(&0_1000000, &4, &1_0000000);
+    let id = pool_fixture
+        .pool
+        .queue_admin_action(&AdminAction::UpdatePool(0_1000000, 4, 1_0000000, 0));
+    fixture.jump_with_sequence(3 * 24 * 60 * 60);
+    pool_fixture.pool.execute_queued_action(&id);
 
     fixture.jump_with_sequence(100);
 
@@ -82,7 +86,7 @@ fn test_pool_max_positions_reduction() {
         Request {
             request_type: RequestType::Repay as u32,
             address: weth.address.clone(),
-            amount: weth_amount / 2 + 1000,
+            amount: weth_amount / 2 + weth_amount / 100,
         },
     ];
     let result = pool_fixture.pool.submit(&sam, &sam, &sam, &request);
@@ -96,7 +100,7 @@ fn test_pool_max_positions_reduction() {
         Request {
             request_type: RequestType::WithdrawCollateral as u32,
             address: xlm.address.clone(),
-            amount: xlm_amount + SCALAR_7,
+            amount: xlm_amount + xlm_amount / 100,
         },
     ];
     let result = pool_fixture.pool.submit(&sam, &sam, &sam, &request);
@@ -115,7 +119,7 @@ fn test_pool_max_positions_reduction() {
     let max_position_error = pool_fixture.pool.try_submit(&sam, &sam, &sam, &request);
     assert_eq!(
         max_position_error.err(),
-        Some(Ok(Error::from_contract_error(1208)))
+        Some(Ok(PoolError::MaxPositionsExceeded))
     );
 
     // verify sam can use existing positions
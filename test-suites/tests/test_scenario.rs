@@ -0,0 +1,164 @@
+#![cfg(test)]
+use std::{cell::Cell, rc::Rc};
+
+use pool::RequestType;
+use test_suites::{
+    create_fixture_with_scenario,
+    scenario::Scenario,
+    test_fixture::{TokenIndex, SCALAR_12, SCALAR_7},
+};
+
+const ONE_MONTH: u64 = 30 * 24 * 60 * 60;
+
+#[test]
+fn test_scenario_long_horizon_accrual() {
+    let last_d_rate = Rc::new(Cell::new(SCALAR_12));
+    let last_d_rate_check = last_d_rate.clone();
+    let scenario = Scenario::new()
+        .actor(
+            "sam",
+            &[
+                (TokenIndex::WETH, 10 * 10i128.pow(9)),
+                (TokenIndex::STABLE, 10_000 * 10i128.pow(6)),
+            ],
+        )
+        .submit(
+            "sam",
+            &[
+                (
+                    RequestType::SupplyCollateral,
+                    TokenIndex::WETH,
+                    10i128.pow(9),
+                ),
+                (RequestType::Borrow, TokenIndex::STABLE, 500 * 10i128.pow(6)),
+            ],
+        )
+        // accrue interest for a year, with sam topping up collateral and STABLE supply every month
+        .repeat(12, |scenario| {
+            let last_d_rate = last_d_rate_check.clone();
+            scenario
+                .jump(ONE_MONTH)
+                .submit(
+                    "sam",
+                    &[
+                        (
+                            RequestType::SupplyCollateral,
+                            TokenIndex::WETH,
+                            10i128.pow(8),
+                        ),
+                        (RequestType::Supply, TokenIndex::STABLE, 10 * 10i128.pow(6)),
+                    ],
+                )
+                .check(move |fixture, _| {
+                    let d_rate = fixture.read_reserve_data(0, TokenIndex::STABLE).d_rate;
+                    assert!(d_rate > last_d_rate.get());
+                    last_d_rate.set(d_rate);
+                })
+        });
+    let (fixture, actors) = create_fixture_with_scenario(true, scenario);
+    let pool_fixture = &fixture.pools[0];
+    let sam = actors.get("sam");
+
+    // sam's debt is unchanged in dTokens but has grown in value over the year
+    let positions = pool_fixture.pool.get_positions(sam);
+    assert_eq!(
+        positions
+            .collateral
+            .get_unchecked(pool_fixture.reserves[&TokenIndex::WETH]),
+        10i128.pow(9) + 12 * 10i128.pow(8)
+    );
+    let d_tokens = positions
+        .liabilities
+        .get_unchecked(pool_fixture.reserves[&TokenIndex::STABLE]);
+    let stable_reserve = pool_fixture
+        .pool
+        .get_reserve(&fixture.tokens[TokenIndex::STABLE].address);
+    assert_eq!(stable_reserve.data.d_rate, last_d_rate.get());
+    assert!(d_tokens * stable_reserve.data.d_rate / SCALAR_12 > 500 * 10i128.pow(6));
+}
+
+#[test]
+fn test_scenario_repeated_liquidations() {
+    let scenario = Scenario::new()
+        .actor(
+            "sam",
+            &[
+                (TokenIndex::WETH, 10 * 10i128.pow(9)),
+                (TokenIndex::STABLE, 10_000 * 10i128.pow(6)),
+            ],
+        )
+        .actor(
+            "merry",
+            &[
+                (TokenIndex::XLM, 500_000 * SCALAR_7),
+                (TokenIndex::STABLE, 20_000 * 10i128.pow(6)),
+            ],
+        )
+        // merry provides STABLE liquidity and XLM collateral to back the liquidations
+        .submit(
+            "merry",
+            &[
+                (
+                    RequestType::Supply,
+                    TokenIndex::STABLE,
+                    10_000 * 10i128.pow(6),
+                ),
+                (
+                    RequestType::SupplyCollateral,
+                    TokenIndex::XLM,
+                    200_000 * SCALAR_7,
+                ),
+            ],
+        )
+        // sam borrows, the WETH price crashes, and merry fully liquidates sam
+        .repeat(3, |scenario| {
+            scenario
+                .submit(
+                    "sam",
+                    &[
+                        (
+                            RequestType::SupplyCollateral,
+                            TokenIndex::WETH,
+                            10i128.pow(9),
+                        ),
+                        (
+                            RequestType::Borrow,
+                            TokenIndex::STABLE,
+                            1_000 * 10i128.pow(6),
+                        ),
+                    ],
+                )
+                .jump(ONE_MONTH)
+                .price_path(
+                    TokenIndex::WETH,
+                    &[1600_0000000, 1200_0000000, 800_0000000],
+                    60 * 60,
+                )
+                .new_liquidation("sam", &[TokenIndex::STABLE], &[TokenIndex::WETH], 100)
+                .jump_with_sequence(201 * 5)
+                .fill_liquidation("merry", "sam", 100)
+                .check(|fixture, actors| {
+                    let positions = fixture.pools[0].pool.get_positions(actors.get("sam"));
+                    assert_eq!(positions.collateral.len(), 0);
+                    assert_eq!(positions.liabilities.len(), 0);
+                })
+                .price(TokenIndex::WETH, 2000_0000000)
+        });
+    let (fixture, actors) = create_fixture_with_scenario(true, scenario);
+    let pool_fixture = &fixture.pools[0];
+
+    // merry took on all of sam's WETH collateral and STABLE debt
+    let merry_positions = pool_fixture.pool.get_positions(actors.get("merry"));
+    let weth_collateral = merry_positions
+        .collateral
+        .get_unchecked(pool_fixture.reserves[&TokenIndex::WETH]);
+    assert!(weth_collateral > 0 && weth_collateral <= 3 * 10i128.pow(9));
+    let stable_d_tokens = merry_positions
+        .liabilities
+        .get_unchecked(pool_fixture.reserves[&TokenIndex::STABLE]);
+    assert!(stable_d_tokens > 0 && stable_d_tokens <= 3_000 * 10i128.pow(6));
+    assert_eq!(
+        fixture.tokens[TokenIndex::WETH].balance(actors.get("sam")),
+        7 * 10i128.pow(9)
+    );
+}
@@ -0,0 +1,103 @@
+#![cfg(test)]
+use pool::{AdminAction, Request, RequestType, StableRateConfig};
+use soroban_sdk::{vec, Error, Vec};
+use test_suites::{create_fixture_with_data, test_fixture::TokenIndex};
+
+#[test]
+fn test_stable_rate_borrow_and_swap_to_variable() {
+    let fixture = create_fixture_with_data(false);
+    let frodo = fixture.users[0].clone();
+    let pool_fixture = &fixture.pools[0];
+    let stable = &fixture.tokens[TokenIndex::STABLE];
+    let reserve_index = pool_fixture.pool.get_reserve(&stable.address).config.index;
+
+    // the pool is active, so stable rate borrowing is enabled through the admin queue
+    let id = pool_fixture
+        .pool
+        .queue_admin_action(&AdminAction::SetStableRate(
+            stable.address.clone(),
+            StableRateConfig {
+                premium: 0_0500000,
+                rebalance_util: 0_9000000,
+            },
+        ));
+    fixture.jump_with_sequence(3 * 24 * 60 * 60);
+    pool_fixture.pool.execute_queued_action(&id);
+
+    // frodo borrows STABLE at a stable rate
+    let requests: Vec<Request> = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::BorrowStable as u32,
+            address: stable.address.clone(),
+            amount: 100 * 10i128.pow(6),
+        },
+    ];
+    let positions = pool_fixture.pool.submit(&frodo, &frodo, &frodo, &requests);
+    let stable_d_tokens = pool_fixture
+        .pool
+        .get_stable_liabilities(&frodo)
+        .get_unchecked(reserve_index);
+    assert!(stable_d_tokens > 0);
+    let loan = pool_fixture
+        .pool
+        .get_stable_loan(&frodo, &stable.address)
+        .unwrap();
+    let reserve = pool_fixture.pool.get_reserve(&stable.address);
+    assert!(loan.rate > 0_0500000);
+    assert_eq!(loan.d_rate, reserve.data.d_rate);
+
+    // the stable rate is above the variable rate, so frodo's stable liabilities grow when settled
+    fixture.jump(7 * 24 * 60 * 60);
+    let requests: Vec<Request> = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::SwapToVariable as u32,
+            address: stable.address.clone(),
+            amount: 0,
+        },
+    ];
+    let pre_liabilities = positions.liabilities.get_unchecked(reserve_index);
+    let positions = pool_fixture.pool.submit(&frodo, &frodo, &frodo, &requests);
+    assert!(positions.liabilities.get_unchecked(reserve_index) > pre_liabilities);
+    assert!(pool_fixture.pool.get_stable_liabilities(&frodo).is_empty());
+    assert!(pool_fixture
+        .pool
+        .get_stable_loan(&frodo, &stable.address)
+        .is_none());
+}
+
+#[test]
+fn test_stable_rate_not_rebalanced_below_rebalance_util() {
+    let fixture = create_fixture_with_data(false);
+    let frodo = fixture.users[0].clone();
+    let pool_fixture = &fixture.pools[0];
+    let stable = &fixture.tokens[TokenIndex::STABLE];
+
+    // the pool is active, so stable rate borrowing is enabled through the admin queue
+    let id = pool_fixture
+        .pool
+        .queue_admin_action(&AdminAction::SetStableRate(
+            stable.address.clone(),
+            StableRateConfig {
+                premium: 0_0100000,
+                rebalance_util: 0_9000000,
+            },
+        ));
+    fixture.jump_with_sequence(3 * 24 * 60 * 60);
+    pool_fixture.pool.execute_queued_action(&id);
+    let requests: Vec<Request> = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::BorrowStable as u32,
+            address: stable.address.clone(),
+            amount: 100 * 10i128.pow(6),
+        },
+    ];
+    pool_fixture.pool.submit(&frodo, &frodo, &frodo, &requests);
+
+    let result = pool_fixture
+        .pool
+        .try_rebalance_stable_rate(&frodo, &stable.address);
+    assert_eq!(result.err(), Some(Ok(Error::from_contract_error(1200))));
+}